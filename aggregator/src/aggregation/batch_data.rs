@@ -3,13 +3,13 @@ use halo2_ecc::bigint::CRTInteger;
 use halo2_proofs::{
     circuit::{AssignedCell, Layouter, Region, Value},
     halo2curves::bn256::Fr,
-    plonk::{Advice, Column, ConstraintSystem, Error, Expression, SecondPhase, Selector},
+    plonk::{Advice, Column, ConstraintSystem, Error, Expression, Selector},
     poly::Rotation,
 };
 use itertools::Itertools;
 use zkevm_circuits::{
     table::{KeccakTable, LookupTable, RangeTable, U8Table},
-    util::{Challenges, Expr},
+    util::{advice_column_in_phase2, Challenges, Expr},
 };
 
 use crate::{
@@ -95,9 +95,9 @@ impl<const N_SNARKS: usize> BatchDataConfig<N_SNARKS> {
             boundary_count: meta.advice_column(),
             chunk_idx: meta.advice_column(),
             is_padding: meta.advice_column(),
-            bytes_rlc: meta.advice_column_in(SecondPhase),
-            preimage_rlc: meta.advice_column_in(SecondPhase),
-            digest_rlc: meta.advice_column_in(SecondPhase),
+            bytes_rlc: advice_column_in_phase2(meta, challenge.phases()),
+            preimage_rlc: advice_column_in_phase2(meta, challenge.phases()),
+            digest_rlc: advice_column_in_phase2(meta, challenge.phases()),
             data_selector: meta.complex_selector(),
             hash_selector: meta.complex_selector(),
         };
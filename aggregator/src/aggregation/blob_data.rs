@@ -1,19 +1,20 @@
-use std::io::Write;
-
 use gadgets::util::Expr;
 use halo2_ecc::bigint::CRTInteger;
 use halo2_proofs::{
     circuit::{AssignedCell, Layouter, Region, Value},
     halo2curves::bn256::Fr,
-    plonk::{Advice, Column, ConstraintSystem, Error, Expression, SecondPhase, Selector},
+    plonk::{Advice, Column, ConstraintSystem, Error, Expression, Selector},
     poly::Rotation,
 };
 use itertools::Itertools;
-use zkevm_circuits::{table::U8Table, util::Challenges};
+use zkevm_circuits::{
+    table::U8Table,
+    util::{advice_column_in_phase2, Challenges},
+};
 
 use crate::{
-    aggregation::{decoder::witgen::init_zstd_encoder, rlc::POWS_OF_256},
-    blob::{BatchData, BLOB_WIDTH, N_BLOB_BYTES, N_DATA_BYTES_PER_COEFFICIENT},
+    aggregation::rlc::POWS_OF_256,
+    blob::{BatchData, BlobEnvelope, BLOB_WIDTH, N_BLOB_BYTES, N_DATA_BYTES_PER_COEFFICIENT},
     RlcConfig,
 };
 
@@ -25,14 +26,19 @@ use crate::{
 /// Since the check for the most-significant byte being zero is already done in the
 /// BarycentricConfig, in the BlobDataConfig we only represent the 31 meaningful bytes. Hence the
 /// BlobDataConfig has 4096 * 31 rows. Each row is a byte value and the purpose of the
-/// BlobDataConfig is to compute a random-linear combination of these bytes. These bytes are in
-/// fact the zstd encoded form of the raw batch data represented in BatchDataConfig.
+/// BlobDataConfig is to compute a random-linear combination of these bytes. From
+/// [`BlobEnvelope::V1`] the first byte is the codec header, and the following bytes are the raw
+/// batch data represented in BatchDataConfig, encoded with that codec. Before, the bytes are the
+/// zstd encoded batch data. The header row only exists from [`BlobEnvelope::V1`], hence the
+/// circuit's fixed columns depend on the envelope version.
 #[derive(Clone, Debug)]
 pub struct BlobDataConfig<const N_SNARKS: usize> {
     /// Selector to mark the first row in the layout, enabled at offset=0.
     q_first: Selector,
-    /// Whether the row is enabled or not. We need exactly N_BLOB_BYTES rows, enabled from offset=1
-    /// to offset=N_BLOB_BYTES.
+    /// Selector to mark the codec header byte, enabled at offset=1 from [`BlobEnvelope::V1`].
+    q_header: Selector,
+    /// Whether the row is enabled or not. We need exactly N_BLOB_BYTES rows: the payload is
+    /// enabled from offset=1 to offset=N_BLOB_BYTES, or from offset=2 after the header.
     q_enabled: Selector,
     /// The byte value at this row.
     byte: Column<Advice>,
@@ -40,15 +46,19 @@ pub struct BlobDataConfig<const N_SNARKS: usize> {
     /// (4096 * 31) could be filled. Padded bytes must be 0 and bytes_rlc must continue while in
     /// the padded region.
     is_padding: Column<Advice>,
-    /// running RLC of bytes seen so far. It remains unchanged once padded territory starts.
+    /// running RLC of payload bytes seen so far. It remains unchanged once padded territory
+    /// starts.
     bytes_rlc: Column<Advice>,
-    /// running accumulator of the number of bytes in the blob.
+    /// running accumulator of the number of payload bytes in the blob.
     bytes_len: Column<Advice>,
 }
 
 pub struct AssignedBlobDataExport {
     pub bytes_rlc: AssignedCell<Fr, Fr>,
     pub bytes_len: AssignedCell<Fr, Fr>,
+    /// Whether the payload is zstd encoded, i.e. the envelope has no codec header byte or it is
+    /// not 0.
+    pub is_encoded: AssignedCell<Fr, Fr>,
 }
 
 impl<const N_SNARKS: usize> BlobDataConfig<N_SNARKS> {
@@ -60,9 +70,10 @@ impl<const N_SNARKS: usize> BlobDataConfig<N_SNARKS> {
         let config = Self {
             q_enabled: meta.selector(),
             q_first: meta.complex_selector(),
+            q_header: meta.selector(),
             byte: meta.advice_column(),
             is_padding: meta.advice_column(),
-            bytes_rlc: meta.advice_column_in(SecondPhase),
+            bytes_rlc: advice_column_in_phase2(meta, challenges.phases()),
             bytes_len: meta.advice_column(),
         };
 
@@ -91,6 +102,21 @@ impl<const N_SNARKS: usize> BlobDataConfig<N_SNARKS> {
             ]
         });
 
+        meta.create_gate("BlobDataConfig: header row", |meta| {
+            let is_header = meta.query_selector(config.q_header);
+
+            let is_padding = meta.query_advice(config.is_padding, Rotation::cur());
+            let bytes_rlc = meta.query_advice(config.bytes_rlc, Rotation::cur());
+            let bytes_len = meta.query_advice(config.bytes_len, Rotation::cur());
+
+            // the codec header byte is not part of the payload
+            vec![
+                is_header.expr() * is_padding,
+                is_header.expr() * bytes_rlc,
+                is_header.expr() * bytes_len,
+            ]
+        });
+
         meta.create_gate("BlobDataConfig: main gate", |meta| {
             let is_enabled = meta.query_selector(config.q_enabled);
 
@@ -151,12 +177,13 @@ impl<const N_SNARKS: usize> BlobDataConfig<N_SNARKS> {
             |mut region| self.assign_rows(&mut region, batch_data, &challenge_value),
         )?;
 
-        let cooked_bytes_len = layouter.assign_region(
+        let (cooked_bytes_len, is_encoded) = layouter.assign_region(
             || "BlobData internal checks",
             |mut region| {
                 self.assign_internal_checks(
                     &mut region,
                     rlc_config,
+                    batch_data.envelope,
                     barycentric_assignments,
                     &assigned_bytes,
                     &bytes_len,
@@ -167,6 +194,7 @@ impl<const N_SNARKS: usize> BlobDataConfig<N_SNARKS> {
         Ok(AssignedBlobDataExport {
             bytes_rlc,
             bytes_len: cooked_bytes_len,
+            is_encoded,
         })
     }
 
@@ -184,21 +212,17 @@ impl<const N_SNARKS: usize> BlobDataConfig<N_SNARKS> {
         ),
         Error,
     > {
-        let batch_bytes = batch_data.get_batch_data_bytes();
-        let blob_bytes = {
-            let mut encoder = init_zstd_encoder(None);
-            encoder
-                .set_pledged_src_size(Some(batch_bytes.len() as u64))
-                .map_err(|_| Error::Synthesis)?;
-            encoder
-                .write_all(&batch_bytes)
-                .map_err(|_| Error::Synthesis)?;
-            encoder.finish().map_err(|_| Error::Synthesis)?
-        };
+        let blob_bytes = batch_data.get_encoded_batch_data_bytes();
         assert!(blob_bytes.len() <= N_BLOB_BYTES, "too many blob bytes");
 
+        let has_header = batch_data.envelope.header_byte().is_some();
+        let n_header_bytes = usize::from(has_header);
+
         self.q_first.enable(region, 0)?;
-        for i in 1..=N_BLOB_BYTES {
+        if has_header {
+            self.q_header.enable(region, 1)?;
+        }
+        for i in (1 + n_header_bytes)..=N_BLOB_BYTES {
             self.q_enabled.enable(region, i)?;
         }
 
@@ -217,7 +241,10 @@ impl<const N_SNARKS: usize> BlobDataConfig<N_SNARKS> {
         let mut last_bytes_len = None;
         for (i, &byte) in blob_bytes.iter().enumerate() {
             let byte_value = Value::known(Fr::from(byte as u64));
-            bytes_rlc = bytes_rlc * challenges.keccak_input() + byte_value;
+            // the codec header byte is not part of the payload
+            if i >= n_header_bytes {
+                bytes_rlc = bytes_rlc * challenges.keccak_input() + byte_value;
+            }
 
             assigned_bytes.push(region.assign_advice(
                 || "byte",
@@ -237,7 +264,7 @@ impl<const N_SNARKS: usize> BlobDataConfig<N_SNARKS> {
                 || "bytes_len",
                 self.bytes_len,
                 i + 1,
-                || Value::known(Fr::from(i as u64 + 1)),
+                || Value::known(Fr::from((i + 1 - n_header_bytes) as u64)),
             )?);
         }
 
@@ -277,10 +304,11 @@ impl<const N_SNARKS: usize> BlobDataConfig<N_SNARKS> {
         &self,
         region: &mut Region<Fr>,
         rlc_config: &RlcConfig,
+        envelope: BlobEnvelope,
         barycentric_assignments: &[CRTInteger<Fr>],
         assigned_bytes: &[AssignedCell<Fr, Fr>],
         bytes_len: &AssignedCell<Fr, Fr>,
-    ) -> Result<AssignedCell<Fr, Fr>, Error> {
+    ) -> Result<(AssignedCell<Fr, Fr>, AssignedCell<Fr, Fr>), Error> {
         rlc_config.init(region)?;
         let mut rlc_config_offset = 0;
 
@@ -347,11 +375,40 @@ impl<const N_SNARKS: usize> BlobDataConfig<N_SNARKS> {
             region.constrain_equal(limb3.cell(), blob_crt.truncation.limbs[2].cell())?;
         }
 
+        ////////////////////////////////////////////////////////////////////////////////
+        ///////////////////////////////////// CODEC ////////////////////////////////////
+        ////////////////////////////////////////////////////////////////////////////////
+
+        // Before the v1 envelope, the blob is always zstd encoded.
+        let is_encoded = match envelope {
+            BlobEnvelope::V0 => one.clone(),
+            BlobEnvelope::V1(_) => {
+                // The codec header byte is one of 0 (uncompressed), 1 (zstd) or 2 (tuned zstd):
+                // codec * (codec - 1) * (codec - 2) == 0
+                let codec = &assigned_bytes[0];
+                let codec_minus_one =
+                    rlc_config.sub(region, codec, &one, &mut rlc_config_offset)?;
+                let codec_minus_two =
+                    rlc_config.sub(region, &codec_minus_one, &one, &mut rlc_config_offset)?;
+                let codec_check =
+                    rlc_config.mul(region, codec, &codec_minus_one, &mut rlc_config_offset)?;
+                let codec_check = rlc_config.mul(
+                    region,
+                    &codec_check,
+                    &codec_minus_two,
+                    &mut rlc_config_offset,
+                )?;
+                rlc_config.enforce_zero(region, &codec_check)?;
+                let is_uncompressed = rlc_config.is_zero(region, codec, &mut rlc_config_offset)?;
+                rlc_config.not(region, &is_uncompressed, &mut rlc_config_offset)?
+            }
+        };
+
         // The zstd decoder (DecoderConfig) exports an encoded length that is 1 more than the
         // actual number of bytes in encoded data. Accordingly we "cook" the actual len(bytes) here
         // by adding +1 to it before exporting.
         let cooked_bytes_len = rlc_config.add(region, bytes_len, &one, &mut rlc_config_offset)?;
 
-        Ok(cooked_bytes_len)
+        Ok((cooked_bytes_len, is_encoded))
     }
 }
@@ -11,7 +11,6 @@ use itertools::Itertools;
 use rand::Rng;
 #[cfg(not(feature = "disable_proof_aggregation"))]
 use std::rc::Rc;
-use std::{env, fs::File};
 
 #[cfg(not(feature = "disable_proof_aggregation"))]
 use snark_verifier::loader::halo2::halo2_ecc::halo2_base;
@@ -127,15 +126,7 @@ impl<const N_SNARKS: usize> Circuit<Fr> for AggregationCircuit<N_SNARKS> {
     }
 
     fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
-        let params = env::var("AGGREGATION_CONFIG").map_or_else(
-            |_| ConfigParams::aggregation_param(),
-            |path| {
-                serde_json::from_reader(
-                    File::open(path.as_str()).unwrap_or_else(|_| panic!("{path:?} does not exist")),
-                )
-                .unwrap()
-            },
-        );
+        let params = ConfigParams::aggregation();
 
         let challenges = Challenges::construct_p1(meta);
         let config = AggregationConfig::configure(meta, &params, challenges);
@@ -450,8 +441,9 @@ impl<const N_SNARKS: usize> Circuit<Fr> for AggregationCircuit<N_SNARKS> {
                 barycentric_assignments,
             )?;
 
-            let batch_bytes = batch_data.get_batch_data_bytes();
-            let encoded_batch_bytes = batch_data.get_encoded_batch_data_bytes();
+            // the decoder proves the zstd encoding of the batch data when the blob is zstd
+            // encoded, and a one byte frame not linked to anything otherwise
+            let (decoded_bytes, encoded_bytes) = batch_data.get_decoder_bytes();
 
             let MultiBlockProcessResult {
                 witness_rows,
@@ -461,7 +453,10 @@ impl<const N_SNARKS: usize> Circuit<Fr> for AggregationCircuit<N_SNARKS> {
                 sequence_info_arr,
                 address_table_rows: address_table_arr,
                 sequence_exec_results,
-            } = process(&encoded_batch_bytes, challenges.keccak_input());
+            } = process(&encoded_bytes, challenges.keccak_input()).map_err(|e| {
+                log::error!("decoder witness generation err {:#?}", e);
+                Error::Synthesis
+            })?;
 
             // sanity check:
             let (recovered_bytes, sequence_exec_info_arr) = sequence_exec_results.into_iter().fold(
@@ -473,14 +468,14 @@ impl<const N_SNARKS: usize> Circuit<Fr> for AggregationCircuit<N_SNARKS> {
                 },
             );
             assert_eq!(
-                batch_bytes, recovered_bytes,
+                decoded_bytes, recovered_bytes,
                 "original and recovered bytes mismatch"
             );
 
             let decoder_exports = config.decoder_config.assign(
                 &mut layouter,
-                &batch_bytes,
-                &encoded_batch_bytes,
+                &decoded_bytes,
+                &encoded_bytes,
                 witness_rows,
                 decoded_literals,
                 fse_aux_tables,
@@ -495,6 +490,9 @@ impl<const N_SNARKS: usize> Circuit<Fr> for AggregationCircuit<N_SNARKS> {
             layouter.assign_region(
                 || "consistency checks",
                 |mut region| -> Result<(), Error> {
+                    config.rlc_config.init(&mut region)?;
+                    let mut rlc_config_offset = 0;
+
                     region.constrain_equal(
                         assigned_batch_hash.num_valid_snarks.cell(),
                         batch_data_exports.num_valid_chunks.cell(),
@@ -539,25 +537,75 @@ impl<const N_SNARKS: usize> Circuit<Fr> for AggregationCircuit<N_SNARKS> {
                         region.constrain_equal(c.cell(), ec.cell())?;
                     }
 
-                    // equate rlc (from blob data) with decoder's encoded_rlc
+                    // The blob payload is either zstd encoded, and then equals the decoder's
+                    // encoded bytes, or uncompressed, and then equals the batch data.
+                    //
+                    // equate rlc (from blob data) with decoder's encoded_rlc, or with the rlc
+                    // from batch data
+                    let expected_bytes_rlc = config.rlc_config.select(
+                        &mut region,
+                        &decoder_exports.encoded_rlc,
+                        &batch_data_exports.bytes_rlc,
+                        &blob_data_exports.is_encoded,
+                        &mut rlc_config_offset,
+                    )?;
                     region.constrain_equal(
                         blob_data_exports.bytes_rlc.cell(),
-                        decoder_exports.encoded_rlc.cell(),
+                        expected_bytes_rlc.cell(),
+                    )?;
+                    // equate len(blob_bytes) with decoder's encoded_len, or with len(batch_data),
+                    // both being 1 more than the actual number of bytes.
+                    let one = config.rlc_config.load_private(
+                        &mut region,
+                        &Fr::one(),
+                        &mut rlc_config_offset,
+                    )?;
+                    region.constrain_equal(
+                        one.cell(),
+                        config.rlc_config.one_cell(one.cell().region_index),
+                    )?;
+                    let cooked_batch_data_len = config.rlc_config.add(
+                        &mut region,
+                        &batch_data_exports.batch_data_len,
+                        &one,
+                        &mut rlc_config_offset,
+                    )?;
+                    let expected_bytes_len = config.rlc_config.select(
+                        &mut region,
+                        &decoder_exports.encoded_len,
+                        &cooked_batch_data_len,
+                        &blob_data_exports.is_encoded,
+                        &mut rlc_config_offset,
                     )?;
-                    // equate len(blob_bytes) with decoder's encoded_len
                     region.constrain_equal(
                         blob_data_exports.bytes_len.cell(),
-                        decoder_exports.encoded_len.cell(),
+                        expected_bytes_len.cell(),
+                    )?;
+                    // equate rlc (from batch data) with decoder's decoded_rlc, if the decoder is
+                    // not bypassed
+                    let expected_batch_data_rlc = config.rlc_config.select(
+                        &mut region,
+                        &decoder_exports.decoded_rlc,
+                        &batch_data_exports.bytes_rlc,
+                        &blob_data_exports.is_encoded,
+                        &mut rlc_config_offset,
                     )?;
-                    // equate rlc (from batch data) with decoder's decoded_rlc
                     region.constrain_equal(
                         batch_data_exports.bytes_rlc.cell(),
-                        decoder_exports.decoded_rlc.cell(),
+                        expected_batch_data_rlc.cell(),
+                    )?;
+                    // equate len(batch_data) with decoder's decoded_len, if the decoder is not
+                    // bypassed
+                    let expected_batch_data_len = config.rlc_config.select(
+                        &mut region,
+                        &decoder_exports.decoded_len,
+                        &batch_data_exports.batch_data_len,
+                        &blob_data_exports.is_encoded,
+                        &mut rlc_config_offset,
                     )?;
-                    // equate len(batch_data) with decoder's decoded_len
                     region.constrain_equal(
                         batch_data_exports.batch_data_len.cell(),
-                        decoder_exports.decoded_len.cell(),
+                        expected_batch_data_len.cell(),
                     )?;
 
                     Ok(())
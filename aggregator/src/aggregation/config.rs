@@ -63,7 +63,7 @@ impl<const N_SNARKS: usize> AggregationConfig<N_SNARKS> {
 
         // hash configuration for aggregation circuit
         let (keccak_table, keccak_circuit_config) = {
-            let keccak_table = KeccakTable::construct(meta);
+            let keccak_table = KeccakTable::construct(meta, challenges.phases());
 
             let challenges_exprs = challenges.exprs(meta);
             let keccak_circuit_config_args = KeccakCircuitConfigArgs {
@@ -13,16 +13,14 @@ use halo2_proofs::{
     arithmetic::Field,
     circuit::{AssignedCell, Layouter, Value},
     halo2curves::bn256::Fr,
-    plonk::{
-        Advice, Column, ConstraintSystem, Error, Expression, Fixed, SecondPhase, VirtualCells,
-    },
+    plonk::{Advice, Column, ConstraintSystem, Error, Expression, Fixed, VirtualCells},
     poly::Rotation,
 };
 use itertools::Itertools;
 use zkevm_circuits::{
     evm_circuit::{BaseConstraintBuilder, ConstrainBuilderCommon},
     table::{BitwiseOpTable, LookupTable, Pow2Table, PowOfRandTable, RangeTable, U8Table},
-    util::Challenges,
+    util::{advice_column_in_phase2, ChallengePhases, Challenges},
 };
 
 use self::{
@@ -31,9 +29,9 @@ use self::{
         SeqInstTable as SequenceInstructionTable,
     },
     witgen::{
-        util::value_bits_le, AddressTableRow, BlockInfo, FseAuxiliaryTableData, FseTableKind,
-        SequenceExec, SequenceInfo, ZstdTag, ZstdWitnessRow, N_BITS_PER_BYTE, N_BITS_REPEAT_FLAG,
-        N_BITS_ZSTD_TAG, N_BLOCK_HEADER_BYTES, N_BLOCK_SIZE_TARGET,
+        util::value_bits_le, AddressTableRow, BlockInfo, BlockType, FseAuxiliaryTableData,
+        FseTableKind, SequenceExec, SequenceInfo, ZstdTag, ZstdWitnessRow, N_BITS_PER_BYTE,
+        N_BITS_REPEAT_FLAG, N_BITS_ZSTD_TAG, N_BLOCK_HEADER_BYTES, N_BLOCK_SIZE_TARGET,
     },
 };
 use super::util::BooleanAdvice;
@@ -163,14 +161,25 @@ struct TagConfig {
     is_sequence_header: Column<Advice>,
     /// Degree reduction: SequenceFseCode
     is_fse_code: Column<Advice>,
+    /// Degree reduction: SequenceRleCode
+    is_rle_code: Column<Advice>,
     /// Degree reduction: SequencesData
     is_sequence_data: Column<Advice>,
     /// Degree reduction: Null
     is_null: Column<Advice>,
+    /// Degree reduction: LiteralsRawBytes or LiteralsRleBytes, i.e. the row holds a literal.
+    is_literal: Column<Advice>,
+    /// The 1-indexed literal of the block held by this row. A tag=LiteralsRleBytes spans over
+    /// many rows with the same byte_idx (one per literal), hence we cannot use tag_idx instead.
+    literal_idx: Column<Advice>,
 }
 
 impl TagConfig {
-    fn configure(meta: &mut ConstraintSystem<Fr>, q_enable: Column<Fixed>) -> Self {
+    fn configure(
+        meta: &mut ConstraintSystem<Fr>,
+        q_enable: Column<Fixed>,
+        phases: ChallengePhases,
+    ) -> Self {
         let tag = meta.advice_column();
         let tag_idx = meta.advice_column();
         let tag_len = meta.advice_column();
@@ -188,9 +197,9 @@ impl TagConfig {
                 |meta| meta.query_advice(tag_len, Rotation::cur()),
             ),
             max_len: meta.advice_column(),
-            tag_rlc_acc: meta.advice_column_in(SecondPhase),
-            tag_rlc: meta.advice_column_in(SecondPhase),
-            rpow_tag_len: meta.advice_column_in(SecondPhase),
+            tag_rlc_acc: advice_column_in_phase2(meta, phases),
+            tag_rlc: advice_column_in_phase2(meta, phases),
+            rpow_tag_len: advice_column_in_phase2(meta, phases),
             is_reverse: meta.advice_column(),
             is_change: BooleanAdvice::construct(meta, |meta| {
                 meta.query_fixed(q_enable, Rotation::cur())
@@ -201,8 +210,11 @@ impl TagConfig {
             is_literals_header: meta.advice_column(),
             is_sequence_header: meta.advice_column(),
             is_fse_code: meta.advice_column(),
+            is_rle_code: meta.advice_column(),
             is_sequence_data: meta.advice_column(),
             is_null: meta.advice_column(),
+            is_literal: meta.advice_column(),
+            literal_idx: meta.advice_column(),
         }
     }
 }
@@ -215,7 +227,12 @@ struct BlockConfig {
     block_idx: Column<Advice>,
     /// Whether this block is the last block in the zstd encoded data.
     is_last_block: Column<Advice>,
-    /// The regenerated size of the block, i.e. the length of raw literals.
+    /// Whether this block is a Raw_Block, i.e. its content is stored as is.
+    is_raw_block: Column<Advice>,
+    /// Whether this block is an RLE_Block, i.e. its content is a single byte repeated block_len
+    /// times.
+    is_rle_block: Column<Advice>,
+    /// The regenerated size of the block's literals, i.e. the number of literals.
     regen_size: Column<Advice>,
     /// Helper boolean column to tell us whether we are in the block's contents. This field is not
     /// set for FrameHeaderDescriptor and FrameContentSize. For the tags that occur while decoding
@@ -226,15 +243,19 @@ struct BlockConfig {
     /// Helper gadget to know if the number of sequences is 0.
     is_empty_sequences: IsEqualConfig<Fr>,
     /// For sequence decoding, the tag=ZstdBlockSequenceHeader bytes tell us the Compression_Mode
-    /// utilised for Literals Lengths, Match Offsets and Match Lengths. We expect only 2
-    /// possibilities:
+    /// utilised for Literals Lengths, Match Offsets and Match Lengths. There are 4 possibilities:
     /// 1. Predefined_Mode (value=0)
-    /// 2. Fse_Compressed_Mode (value=2)
+    /// 2. RLE_Mode (value=1)
+    /// 3. Fse_Compressed_Mode (value=2)
+    /// 4. Repeat_Mode (value=3)
     ///
-    /// Which means a single boolean flag is sufficient to take note of which compression mode is
-    /// utilised for each of the above purposes. The boolean flag will be set if we utilise the
-    /// Fse_Compressed_Mode.
+    /// We use a boolean flag for each of the non-predefined modes, for each of the above purposes.
+    /// This boolean flag is set if we utilise the Fse_Compressed_Mode.
     compression_modes: [Column<Advice>; 3],
+    /// The boolean flag set if we utilise the RLE_Mode.
+    rle_modes: [Column<Advice>; 3],
+    /// The boolean flag set if we utilise the Repeat_Mode.
+    repeat_modes: [Column<Advice>; 3],
 }
 
 impl BlockConfig {
@@ -244,6 +265,8 @@ impl BlockConfig {
             block_len: meta.advice_column(),
             block_idx: meta.advice_column(),
             is_last_block: meta.advice_column(),
+            is_raw_block: meta.advice_column(),
+            is_rle_block: meta.advice_column(),
             regen_size: meta.advice_column(),
             is_block: meta.advice_column(),
             num_sequences,
@@ -258,32 +281,106 @@ impl BlockConfig {
                 meta.advice_column(),
                 meta.advice_column(),
             ],
+            rle_modes: [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ],
+            repeat_modes: [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ],
         }
     }
 }
 
 impl BlockConfig {
+    /// Whether the block is a Compressed_Block, i.e. neither a Raw_Block nor an RLE_Block.
+    fn is_compressed_block(
+        &self,
+        meta: &mut VirtualCells<Fr>,
+        rotation: Rotation,
+    ) -> Expression<Fr> {
+        1.expr()
+            - meta.query_advice(self.is_raw_block, rotation)
+            - meta.query_advice(self.is_rle_block, rotation)
+    }
+
+    /// The Compression_Mode (0, 1, 2 or 3) of the table at the given index (LLT, MOT, MLT).
+    fn mode(&self, meta: &mut VirtualCells<Fr>, idx: usize, rotation: Rotation) -> Expression<Fr> {
+        meta.query_advice(self.rle_modes[idx], rotation)
+            + meta.query_advice(self.compression_modes[idx], rotation) * 2.expr()
+            + meta.query_advice(self.repeat_modes[idx], rotation) * 3.expr()
+    }
+
+    /// Whether the table at the given index (LLT, MOT, MLT) is described in its own section, i.e.
+    /// RLE_Mode or Fse_Compressed_Mode.
+    fn has_section(
+        &self,
+        meta: &mut VirtualCells<Fr>,
+        idx: usize,
+        rotation: Rotation,
+    ) -> Expression<Fr> {
+        meta.query_advice(self.rle_modes[idx], rotation)
+            + meta.query_advice(self.compression_modes[idx], rotation)
+    }
+
+    /// The tag of the section describing the table at the given index (LLT, MOT, MLT), if any.
+    fn section_tag(
+        &self,
+        meta: &mut VirtualCells<Fr>,
+        idx: usize,
+        rotation: Rotation,
+    ) -> Expression<Fr> {
+        meta.query_advice(self.rle_modes[idx], rotation) * ZstdTag::ZstdBlockSequenceRleCode.expr()
+            + meta.query_advice(self.compression_modes[idx], rotation)
+                * ZstdTag::ZstdBlockSequenceFseCode.expr()
+    }
+
+    fn is_predefined_at(
+        &self,
+        meta: &mut VirtualCells<Fr>,
+        idx: usize,
+        rotation: Rotation,
+    ) -> Expression<Fr> {
+        1.expr()
+            - meta.query_advice(self.compression_modes[idx], rotation)
+            - meta.query_advice(self.rle_modes[idx], rotation)
+            - meta.query_advice(self.repeat_modes[idx], rotation)
+    }
+
     fn is_predefined_llt(&self, meta: &mut VirtualCells<Fr>, rotation: Rotation) -> Expression<Fr> {
-        not::expr(meta.query_advice(self.compression_modes[0], rotation))
+        self.is_predefined_at(meta, 0, rotation)
     }
 
     fn is_predefined_mot(&self, meta: &mut VirtualCells<Fr>, rotation: Rotation) -> Expression<Fr> {
-        not::expr(meta.query_advice(self.compression_modes[1], rotation))
+        self.is_predefined_at(meta, 1, rotation)
     }
 
     fn is_predefined_mlt(&self, meta: &mut VirtualCells<Fr>, rotation: Rotation) -> Expression<Fr> {
-        not::expr(meta.query_advice(self.compression_modes[2], rotation))
+        self.is_predefined_at(meta, 2, rotation)
     }
 
-    fn are_predefined_all(
+    fn is_repeat(
         &self,
         meta: &mut VirtualCells<Fr>,
+        fse_decoder: &FseDecoder,
         rotation: Rotation,
     ) -> Expression<Fr> {
-        and::expr([
-            self.is_predefined_llt(meta, rotation),
-            self.is_predefined_mot(meta, rotation),
-            self.is_predefined_mlt(meta, rotation),
+        sum::expr([
+            and::expr([
+                fse_decoder.is_llt(meta, rotation),
+                meta.query_advice(self.repeat_modes[0], rotation),
+            ]),
+            and::expr([
+                fse_decoder.is_mlt(meta, rotation),
+                meta.query_advice(self.repeat_modes[2], rotation),
+            ]),
+            and::expr([
+                fse_decoder.is_mot(meta, rotation),
+                meta.query_advice(self.repeat_modes[1], rotation),
+            ]),
         ])
     }
 
@@ -805,10 +902,17 @@ impl FseDecoder {
 
             let mut cb = BaseConstraintBuilder::default();
 
-            cb.require_equal(
-                "is_predefined value",
-                meta.query_advice(fse_decoder.is_predefined, Rotation::cur()),
-                block_config.is_predefined(meta, &fse_decoder, Rotation::cur()),
+            // A table in Repeat_Mode carries the is_predefined flag of the table it repeats, which
+            // is checked by the lookups to the FSE table.
+            cb.condition(
+                not::expr(block_config.is_repeat(meta, &fse_decoder, Rotation::cur())),
+                |cb| {
+                    cb.require_equal(
+                        "is_predefined value",
+                        meta.query_advice(fse_decoder.is_predefined, Rotation::cur()),
+                        block_config.is_predefined(meta, &fse_decoder, Rotation::cur()),
+                    );
+                },
             );
 
             cb.gate(condition)
@@ -1124,7 +1228,7 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
         debug_assert!(meta.degree() <= 9);
 
         // Peripheral configs
-        let tag_config = TagConfig::configure(meta, q_enable);
+        let tag_config = TagConfig::configure(meta, q_enable, challenges.phases());
         let block_config = BlockConfig::configure(meta, q_enable);
         let sequences_header_decoder =
             SequencesHeaderDecoder::configure(meta, byte, q_enable, u8_table);
@@ -1141,13 +1245,13 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
         let sequence_execution_config = SequenceExecutionConfig::configure(
             meta,
             challenges.keccak_input(),
+            challenges.phases(),
             &LiteralTable::construct([
                 q_enable.into(),
-                tag_config.tag.into(),
+                tag_config.is_literal.into(),
                 block_config.block_idx.into(),
-                tag_config.tag_idx.into(),
+                tag_config.literal_idx.into(),
                 byte.into(),
-                tag_config.is_change.column.into(),
                 is_padding.column.into(),
             ]),
             &sequence_instruction_table,
@@ -1156,6 +1260,7 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
                 block_config.is_block.into(),
                 block_config.block_idx.into(),
                 block_config.num_sequences.into(),
+                block_config.regen_size.into(),
             ]),
         );
 
@@ -1188,7 +1293,7 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
                 .collect::<Vec<_>>()
                 .try_into()
                 .expect("N_BITS_PER_BYTE advice columns into array"),
-            encoded_rlc: meta.advice_column_in(SecondPhase),
+            encoded_rlc: advice_column_in_phase2(meta, challenges.phases()),
             decoded_len: meta.advice_column(),
             is_padding,
             tag_config,
@@ -1248,8 +1353,10 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
         is_tag!(is_block_header, BlockHeader);
         is_tag!(is_zb_literals_header, ZstdBlockLiteralsHeader);
         is_tag!(is_zb_raw_block, ZstdBlockLiteralsRawBytes);
+        is_tag!(is_zb_rle_literals, ZstdBlockLiteralsRleBytes);
         is_tag!(is_zb_sequence_header, ZstdBlockSequenceHeader);
         is_tag!(is_zb_sequence_fse, ZstdBlockSequenceFseCode);
+        is_tag!(is_zb_sequence_rle, ZstdBlockSequenceRleCode);
         is_tag!(is_zb_sequence_data, ZstdBlockSequenceData);
 
         is_prev_tag!(is_prev_frame_content_size, FrameContentSize);
@@ -1382,11 +1489,16 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
                 is_zb_sequence_header(meta)
             );
             degree_reduction_check!(config.tag_config.is_fse_code, is_zb_sequence_fse(meta));
+            degree_reduction_check!(config.tag_config.is_rle_code, is_zb_sequence_rle(meta));
             degree_reduction_check!(
                 config.tag_config.is_sequence_data,
                 is_zb_sequence_data(meta)
             );
             degree_reduction_check!(config.tag_config.is_null, is_null(meta));
+            degree_reduction_check!(
+                config.tag_config.is_literal,
+                is_zb_raw_block(meta) + is_zb_rle_literals(meta)
+            );
 
             // Lookups enabled check.
             macro_rules! lookups_enabled_check {
@@ -1532,6 +1644,7 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
                         meta.query_advice(config.tag_config.is_literals_header, Rotation::cur()),
                         is_zb_raw_block(meta),
                         meta.query_advice(config.tag_config.is_sequence_header, Rotation::cur()),
+                        meta.query_advice(config.tag_config.is_rle_code, Rotation::cur()),
                     ]),
                     |cb| {
                         cb.require_equal(
@@ -1972,18 +2085,47 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
             let block_type_bit1 = config.bits[1].expr_at(meta, Rotation::cur());
             let block_type_bit2 = config.bits[2].expr_at(meta, Rotation::cur());
 
-            // We expect a Block_Type of Compressed_Block, i.e. Block_Type == 2.
+            // We expect a Block_Type of Raw_Block (0), RLE_Block (1) or Compressed_Block (2),
+            // i.e. Block_Type != Reserved (3).
+            cb.require_zero(
+                "Block_Type is not Reserved",
+                block_type_bit1.expr() * block_type_bit2.expr(),
+            );
             cb.require_equal(
-                "Block_Type is Compressed_Block (bit 1)",
-                block_type_bit1,
-                0.expr(),
+                "is_raw_block assigned correctly",
+                meta.query_advice(config.block_config.is_raw_block, Rotation::cur()),
+                not::expr(block_type_bit1.expr()) * not::expr(block_type_bit2.expr()),
             );
             cb.require_equal(
-                "Block_Type is Compressed_Block (bit 2)",
-                block_type_bit2,
-                1.expr(),
+                "is_rle_block assigned correctly",
+                meta.query_advice(config.block_config.is_rle_block, Rotation::cur()),
+                block_type_bit1.expr() * not::expr(block_type_bit2.expr()),
             );
 
+            // A Compressed_Block begins with the literals header, whereas the content of a
+            // Raw_Block or RLE_Block is directly its literals, i.e. block_len bytes stored as is or
+            // a single byte repeated block_len times.
+            cb.require_equal(
+                "tag_next depends on Block_Type",
+                meta.query_advice(config.tag_config.tag_next, Rotation::cur()),
+                select::expr(
+                    block_type_bit2.expr(),
+                    ZstdTag::ZstdBlockLiteralsHeader.expr(),
+                    select::expr(
+                        block_type_bit1.expr(),
+                        ZstdTag::ZstdBlockLiteralsRleBytes.expr(),
+                        ZstdTag::ZstdBlockLiteralsRawBytes.expr(),
+                    ),
+                ),
+            );
+            cb.condition(not::expr(block_type_bit2), |cb| {
+                cb.require_equal(
+                    "Raw_Block or RLE_Block: regen_size == block_len",
+                    meta.query_advice(config.block_config.regen_size, Rotation::cur()),
+                    meta.query_advice(config.block_config.block_len, Rotation::cur()),
+                );
+            });
+
             // is_last_block is assigned correctly.
             cb.require_equal(
                 "is_last_block assigned correctly",
@@ -1998,15 +2140,18 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
                 meta.query_advice(config.block_config.block_idx, Rotation::prev()) + 1.expr(),
             );
 
-            // block_len, block_idx and is_last_block fields do not change in the BlockHeader. We
-            // explicitly do this check since tag=BlockHeader has is_block=false, to facilitate the
-            // change of these parameters between blocks (at the tag=BlockHeader boundary). For the
-            // subsequent tags, these fields remain the same and is checked via the gate for
-            // is_block=true.
+            // block_len, block_idx, is_last_block, the block type and regen_size fields do not
+            // change in the BlockHeader. We explicitly do this check since tag=BlockHeader has
+            // is_block=false, to facilitate the change of these parameters between blocks (at the
+            // tag=BlockHeader boundary). For the subsequent tags, these fields remain the same and
+            // is checked via the gate for is_block=true.
             for column in [
                 config.block_config.block_len,
                 config.block_config.block_idx,
                 config.block_config.is_last_block,
+                config.block_config.is_raw_block,
+                config.block_config.is_rle_block,
+                config.block_config.regen_size,
             ] {
                 cb.require_equal(
                     "BlockHeader: block_idx/block_len/is_last_block",
@@ -2021,15 +2166,19 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
             }
 
             // We now validate the end of the previous block.
-            // - tag=BlockHeader is preceded by tag in [FrameContentSize, SeqHeader, SeqData].
+            // - tag=BlockHeader is preceded by tag in [FrameContentSize, SeqHeader, SeqData,
+            //   RawBytes, RleBytes].
             // - if prev_tag=SequenceHeader: prev block had no sequences.
             // - if prev_tag=SequenceData: all sequences from prev block were decoded.
+            // - if prev_tag in [RawBytes, RleBytes]: prev block was a Raw_Block or RLE_Block, which
+            //   is checked while processing the literals.
             cb.require_equal(
-                "tag::prev in [FCS, SH, SD]",
+                "tag::prev in [FCS, SH, SD, RawBytes, RleBytes]",
                 sum::expr([
                     is_prev_frame_content_size(meta),
                     is_prev_sequence_header(meta),
                     is_prev_sequence_data(meta),
+                    meta.query_advice(config.tag_config.is_literal, Rotation::prev()),
                 ]),
                 1.expr(),
             );
@@ -2100,6 +2249,18 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
                 meta.query_advice(config.block_config.block_idx, Rotation::prev()),
             );
 
+            // the block type remains unchanged.
+            for column in [
+                config.block_config.is_raw_block,
+                config.block_config.is_rle_block,
+            ] {
+                cb.require_equal(
+                    "block type unchanged during block",
+                    meta.query_advice(column, Rotation::cur()),
+                    meta.query_advice(column, Rotation::prev()),
+                );
+            }
+
             // the number of sequences in the block remains the same.
             cb.require_equal(
                 "num_sequences::cur == num_sequences::prev",
@@ -2115,7 +2276,13 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
             );
 
             // the compression modes are remembered throughout the block's context.
-            for column in config.block_config.compression_modes {
+            for column in config
+                .block_config
+                .compression_modes
+                .into_iter()
+                .chain(config.block_config.rle_modes)
+                .chain(config.block_config.repeat_modes)
+            {
                 cb.require_equal(
                     "compression_modes::cur == compression_modes::prev (during block)",
                     meta.query_advice(column, Rotation::cur()),
@@ -2143,9 +2310,21 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
             let literals_block_type_bit0 = config.bits[0].expr_at(meta, Rotation::cur());
             let literals_block_type_bit1 = config.bits[1].expr_at(meta, Rotation::cur());
 
-            // We expect a Raw_Literals_Block, i.e. bit0 and bit1 are both 0.
-            cb.require_zero("Raw_Literals_Block: bit0", literals_block_type_bit0);
-            cb.require_zero("Raw_Literals_Block: bit1", literals_block_type_bit1);
+            // We expect a Raw_Literals_Block (0) or RLE_Literals_Block (1), i.e. bit1 is 0. Both
+            // have the same Size_Format, the literals follow the header.
+            cb.require_zero(
+                "Raw_Literals_Block or RLE_Literals_Block: bit1",
+                literals_block_type_bit1,
+            );
+            cb.require_equal(
+                "tag_next depends on Literals_Block_Type",
+                meta.query_advice(config.tag_config.tag_next, Rotation::cur()),
+                select::expr(
+                    literals_block_type_bit0,
+                    ZstdTag::ZstdBlockLiteralsRleBytes.expr(),
+                    ZstdTag::ZstdBlockLiteralsRawBytes.expr(),
+                ),
+            );
 
             let size_format_bit0 = config.bits[2].expr_at(meta, Rotation::cur());
             let size_format_bit1 = config.bits[3].expr_at(meta, Rotation::cur());
@@ -2239,6 +2418,108 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
 
         debug_assert!(meta.degree() <= 9);
 
+        ///////////////////////////////////////////////////////////////////////////////////////////
+        /////////////////////////// ZstdTag::ZstdBlockLiteralsRleBytes ////////////////////////////
+        ///////////////////////////////////////////////////////////////////////////////////////////
+        meta.create_gate("DecoderConfig: tag ZstdBlockLiteralsRleBytes", |meta| {
+            let condition = and::expr([
+                meta.query_fixed(config.q_enable, Rotation::cur()),
+                is_zb_rle_literals(meta),
+                config.tag_config.is_change.expr_at(meta, Rotation::cur()),
+            ]);
+
+            let mut cb = BaseConstraintBuilder::default();
+
+            // The single byte is repeated over regen_size rows, one per literal, all of them with
+            // the same byte_idx. The number of rows is checked via literal_idx.
+            cb.require_equal(
+                "tag_len(RleBytes) == 1",
+                meta.query_advice(config.tag_config.tag_len, Rotation::cur()),
+                1.expr(),
+            );
+
+            cb.gate(condition)
+        });
+
+        debug_assert!(meta.degree() <= 9);
+
+        ///////////////////////////////////////////////////////////////////////////////////////////
+        ///////////////////////////////////// Block literals //////////////////////////////////////
+        ///////////////////////////////////////////////////////////////////////////////////////////
+        meta.create_gate("DecoderConfig: literals", |meta| {
+            let condition = and::expr([
+                meta.query_fixed(config.q_enable, Rotation::cur()),
+                meta.query_advice(config.tag_config.is_literal, Rotation::cur()),
+            ]);
+
+            let mut cb = BaseConstraintBuilder::default();
+
+            // The literals of a block are held by consecutive rows.
+            cb.require_equal(
+                "literal_idx::cur == literal_idx::prev + 1 (starting at 1)",
+                meta.query_advice(config.tag_config.literal_idx, Rotation::cur()),
+                meta.query_advice(config.tag_config.is_literal, Rotation::prev())
+                    * meta.query_advice(config.tag_config.literal_idx, Rotation::prev())
+                    + 1.expr(),
+            );
+
+            // The literals are followed by the sequences section of a Compressed_Block, else the
+            // Raw_Block or RLE_Block ends here, and it has no sequences.
+            let is_compressed_block = config
+                .block_config
+                .is_compressed_block(meta, Rotation::cur());
+            cb.condition(
+                config.tag_config.is_change.expr_at(meta, Rotation::cur()),
+                |cb| {
+                    cb.require_equal(
+                        "tag_next after literals",
+                        meta.query_advice(config.tag_config.tag_next, Rotation::cur()),
+                        select::expr(
+                            is_compressed_block.expr(),
+                            ZstdTag::ZstdBlockSequenceHeader.expr(),
+                            select::expr(
+                                meta.query_advice(
+                                    config.block_config.is_last_block,
+                                    Rotation::cur(),
+                                ),
+                                ZstdTag::Null.expr(),
+                                ZstdTag::BlockHeader.expr(),
+                            ),
+                        ),
+                    );
+                },
+            );
+            cb.condition(not::expr(is_compressed_block), |cb| {
+                cb.require_zero(
+                    "Raw_Block or RLE_Block: num_sequences == 0",
+                    meta.query_advice(config.block_config.num_sequences, Rotation::cur()),
+                );
+            });
+
+            cb.gate(condition)
+        });
+
+        meta.create_gate("DecoderConfig: end of literals", |meta| {
+            let condition = and::expr([
+                meta.query_fixed(config.q_enable, Rotation::cur()),
+                not::expr(meta.query_advice(config.tag_config.is_literal, Rotation::cur())),
+                meta.query_advice(config.tag_config.is_literal, Rotation::prev()),
+            ]);
+
+            let mut cb = BaseConstraintBuilder::default();
+
+            // All of the block's literals were processed.
+            cb.require_equal(
+                "literal_idx::prev == regen_size::prev",
+                meta.query_advice(config.tag_config.literal_idx, Rotation::prev()),
+                meta.query_advice(config.block_config.regen_size, Rotation::prev()),
+            );
+
+            cb.gate(condition)
+        });
+
+        debug_assert!(meta.degree() <= 9);
+
         ///////////////////////////////////////////////////////////////////////////////////////////
         //////////////////////////// ZstdTag::ZstdBlockSequenceHeader /////////////////////////////
         ///////////////////////////////////////////////////////////////////////////////////////////
@@ -2271,46 +2552,76 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
                 decoded_sequences_header.num_sequences,
             );
 
-            // The compression modes for literals length, match length and offsets are expected to
-            // be either Predefined_Mode or Fse_Compressed_Mode, i.e. compression mode==0 or
-            // compression_mode==2. i.e. bit0==0.
-            cb.require_zero("ll: bit0 == 0", decoded_sequences_header.comp_mode_bit0_ll);
-            cb.require_zero("om: bit0 == 0", decoded_sequences_header.comp_mode_bit0_om);
-            cb.require_zero("ml: bit0 == 0", decoded_sequences_header.comp_mode_bit0_ml);
-
-            // Depending on bit1==0 or bit1==1 we know whether the compression mode is
-            // Predefined_Mode or Fse_Compressed_Mode. The compression_modes flag is set when
-            // Fse_Compressed_Mode is utilised.
-            cb.require_equal(
-                "block_config: compression_modes llt",
-                meta.query_advice(config.block_config.compression_modes[0], Rotation::cur()),
-                decoded_sequences_header.comp_mode_bit1_ll,
-            );
-            cb.require_equal(
-                "block_config: compression_modes mot",
-                meta.query_advice(config.block_config.compression_modes[1], Rotation::cur()),
-                decoded_sequences_header.comp_mode_bit1_om,
-            );
-            cb.require_equal(
-                "block_config: compression_modes mlt",
-                meta.query_advice(config.block_config.compression_modes[2], Rotation::cur()),
-                decoded_sequences_header.comp_mode_bit1_ml,
-            );
+            // The compression modes for literals length, match length and offsets are given by
+            // (bit1, bit0):
+            // - (0, 0): Predefined_Mode
+            // - (0, 1): RLE_Mode, the rle_modes flag is set.
+            // - (1, 0): Fse_Compressed_Mode, the compression_modes flag is set.
+            // - (1, 1): Repeat_Mode, the repeat_modes flag is set.
+            for (idx, bit0, bit1, names) in [
+                (
+                    0,
+                    decoded_sequences_header.comp_mode_bit0_ll,
+                    decoded_sequences_header.comp_mode_bit1_ll,
+                    [
+                        "block_config: rle_modes llt",
+                        "block_config: compression_modes llt",
+                        "block_config: repeat_modes llt",
+                    ],
+                ),
+                (
+                    1,
+                    decoded_sequences_header.comp_mode_bit0_om,
+                    decoded_sequences_header.comp_mode_bit1_om,
+                    [
+                        "block_config: rle_modes mot",
+                        "block_config: compression_modes mot",
+                        "block_config: repeat_modes mot",
+                    ],
+                ),
+                (
+                    2,
+                    decoded_sequences_header.comp_mode_bit0_ml,
+                    decoded_sequences_header.comp_mode_bit1_ml,
+                    [
+                        "block_config: rle_modes mlt",
+                        "block_config: compression_modes mlt",
+                        "block_config: repeat_modes mlt",
+                    ],
+                ),
+            ] {
+                cb.require_equal(
+                    names[0],
+                    meta.query_advice(config.block_config.rle_modes[idx], Rotation::cur()),
+                    bit0.expr() * not::expr(bit1.expr()),
+                );
+                cb.require_equal(
+                    names[1],
+                    meta.query_advice(config.block_config.compression_modes[idx], Rotation::cur()),
+                    not::expr(bit0.expr()) * bit1.expr(),
+                );
+                cb.require_equal(
+                    names[2],
+                    meta.query_advice(config.block_config.repeat_modes[idx], Rotation::cur()),
+                    bit0 * bit1,
+                );
+            }
 
-            // If all the three LLT, MOT and MLT use the Predefined_Mode, we have no FSE tables to
-            // decode in the sequences section. And the tag=ZstdBlockSequenceHeader will
-            // immediately be followed by tag=ZstdBlockSequenceData.
-            let no_fse_tables = config
-                .block_config
-                .are_predefined_all(meta, Rotation::cur());
+            // The tables in Predefined_Mode or Repeat_Mode are not described in the sequences
+            // section. The tag=ZstdBlockSequenceHeader is followed by the section of the first
+            // table in RLE_Mode (tag=ZstdBlockSequenceRleCode) or Fse_Compressed_Mode
+            // (tag=ZstdBlockSequenceFseCode), in the order LLT, MOT, MLT. If there is no such
+            // table, it is immediately followed by tag=ZstdBlockSequenceData.
+            let mut tag_next = ZstdTag::ZstdBlockSequenceData.expr();
+            for idx in (0..3).rev() {
+                let section_tag = config.block_config.section_tag(meta, idx, Rotation::cur());
+                let has_section = config.block_config.has_section(meta, idx, Rotation::cur());
+                tag_next = section_tag + not::expr(has_section) * tag_next;
+            }
             cb.require_equal(
-                "SequenceHeader: tag_next=FseCode or tag_next=SequencesData",
+                "SequenceHeader: tag_next=RleCode or tag_next=FseCode or tag_next=SequencesData",
                 meta.query_advice(config.tag_config.tag_next, Rotation::cur()),
-                select::expr(
-                    no_fse_tables,
-                    ZstdTag::ZstdBlockSequenceData.expr(),
-                    ZstdTag::ZstdBlockSequenceFseCode.expr(),
-                ),
+                tag_next,
             );
 
             cb.gate(condition)
@@ -2403,28 +2714,40 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
         );
 
         meta.lookup_any(
-            "DecoderConfig: tag ZstdBlockSequenceFseCode (table kind)",
+            "DecoderConfig: tag ZstdBlockSequenceFseCode/ZstdBlockSequenceRleCode (table kind)",
             |meta| {
                 let condition = and::expr([
-                    meta.query_advice(config.tag_config.is_fse_code, Rotation::cur()),
-                    config.tag_config.is_change.expr_at(meta, Rotation::cur()),
+                    meta.query_fixed(q_enable, Rotation::cur()),
+                    sum::expr([
+                        and::expr([
+                            meta.query_advice(config.tag_config.is_fse_code, Rotation::cur()),
+                            config.tag_config.is_change.expr_at(meta, Rotation::cur()),
+                        ]),
+                        meta.query_advice(config.tag_config.is_rle_code, Rotation::cur()),
+                    ]),
                 ]);
 
-                let (cmode_llt, cmode_mot, cmode_mlt) = (
-                    meta.query_advice(config.block_config.compression_modes[0], Rotation::cur()),
-                    meta.query_advice(config.block_config.compression_modes[1], Rotation::cur()),
-                    meta.query_advice(config.block_config.compression_modes[2], Rotation::cur()),
+                let (mode_llt, mode_mot, mode_mlt) = (
+                    config.block_config.mode(meta, 0, Rotation::cur()),
+                    config.block_config.mode(meta, 1, Rotation::cur()),
+                    config.block_config.mode(meta, 2, Rotation::cur()),
                 );
-
-                let cmodes_lc = (4.expr() * cmode_llt) + (2.expr() * cmode_mot) + cmode_mlt;
+                let modes_lc = (16.expr() * mode_llt) + (4.expr() * mode_mot) + mode_mlt;
+
+                // The table described by the previous section, if the previous tag was not the
+                // sequences section header.
+                let table_kind_prev = not::expr(
+                    meta.query_advice(config.tag_config.is_sequence_header, Rotation::prev()),
+                ) * meta
+                    .query_advice(config.fse_decoder.table_kind, Rotation::prev());
                 [
                     FixedLookupTag::SeqTagOrder.expr(),
-                    cmodes_lc,
+                    modes_lc,
                     meta.query_advice(config.tag_config.tag, Rotation::prev()), // tag_prev
                     meta.query_advice(config.tag_config.tag, Rotation::cur()),  // tag_cur
                     meta.query_advice(config.tag_config.tag_next, Rotation::cur()), // tag_next
                     meta.query_advice(config.fse_decoder.table_kind, Rotation::cur()), // table_kind
-                    0.expr(),                                                   // unused
+                    table_kind_prev,                                            // table_kind_prev
                 ]
                 .into_iter()
                 .zip_eq(config.fixed_table.table_exprs(meta))
@@ -2874,6 +3197,7 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
                     fse_table_kind,
                     fse_table_size,
                     is_predefined_mode,
+                    0.expr(), // is_repeat
                     fse_symbol,
                     norm_prob.expr(),
                     norm_prob.expr(),
@@ -2887,6 +3211,69 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
             },
         );
 
+        ///////////////////////////////////////////////////////////////////////////////////////////
+        //////////////////////////// ZstdTag::ZstdBlockSequenceRleCode ////////////////////////////
+        ///////////////////////////////////////////////////////////////////////////////////////////
+        meta.create_gate("DecoderConfig: tag ZstdBlockSequenceRleCode", |meta| {
+            let condition = and::expr([
+                meta.query_fixed(config.q_enable, Rotation::cur()),
+                meta.query_advice(config.tag_config.is_rle_code, Rotation::cur()),
+            ]);
+
+            let mut cb = BaseConstraintBuilder::default();
+
+            // The FSE table in RLE_Mode is described by a single byte, i.e. the only symbol of
+            // the table.
+            cb.require_equal(
+                "RleCode: tag_len == 1",
+                meta.query_advice(config.tag_config.tag_len, Rotation::cur()),
+                1.expr(),
+            );
+
+            // A table with a single symbol has accuracy log 0.
+            cb.require_equal(
+                "RleCode: table_size == 1",
+                meta.query_advice(config.fse_decoder.table_size, Rotation::cur()),
+                1.expr(),
+            );
+
+            cb.gate(condition)
+        });
+
+        meta.lookup_any(
+            "DecoderConfig: tag ZstdBlockSequenceRleCode (symbol of the table)",
+            |meta| {
+                let condition = and::expr([
+                    meta.query_fixed(config.q_enable, Rotation::cur()),
+                    meta.query_advice(config.tag_config.is_rle_code, Rotation::cur()),
+                ]);
+
+                let (block_idx, fse_table_kind, fse_symbol) = (
+                    meta.query_advice(config.block_config.block_idx, Rotation::cur()),
+                    meta.query_advice(config.fse_decoder.table_kind, Rotation::cur()),
+                    meta.query_advice(config.byte, Rotation::cur()),
+                );
+
+                [
+                    0.expr(), // q_first=0
+                    block_idx,
+                    fse_table_kind,
+                    1.expr(), // table_size
+                    0.expr(), // is_predefined
+                    0.expr(), // is_repeat
+                    fse_symbol,
+                    1.expr(), // symbol_count
+                    1.expr(), // symbol_count_acc
+                    0.expr(), // is_prob_less_than1
+                    0.expr(), // is_padding
+                ]
+                .into_iter()
+                .zip_eq(config.fse_table.table_exprs_by_symbol(meta))
+                .map(|(arg, table)| (condition.expr() * arg, table))
+                .collect()
+            },
+        );
+
         debug_assert!(meta.degree() <= 9);
 
         ///////////////////////////////////////////////////////////////////////////////////////////
@@ -3470,7 +3857,7 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
                 let (nb, table_size) = (
                     config
                         .bitstream_decoder
-                        .bitstring_len_unchecked(meta, Rotation::cur()),
+                        .bitstring_len(meta, Rotation::cur()),
                     meta.query_advice(config.fse_decoder.table_size, Rotation::cur()),
                 );
 
@@ -3502,6 +3889,10 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
                 );
                 let is_predefined_mode =
                     meta.query_advice(config.fse_decoder.is_predefined, Rotation::cur());
+                let is_repeat_mode =
+                    config
+                        .block_config
+                        .is_repeat(meta, &config.fse_decoder, Rotation::cur());
 
                 [
                     0.expr(), // q_first=0
@@ -3510,6 +3901,7 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
                     table_kind,
                     table_size,
                     is_predefined_mode, // is_predefined
+                    is_repeat_mode,     // is_repeat
                     0.expr(),           // is_padding
                 ]
                 .into_iter()
@@ -3591,6 +3983,10 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
                 );
                 let is_predefined_mode =
                     meta.query_advice(config.fse_decoder.is_predefined, Rotation::cur());
+                let is_repeat_mode =
+                    config
+                        .block_config
+                        .is_repeat(meta, &config.fse_decoder, Rotation::cur());
 
                 [
                     0.expr(), // q_first=0
@@ -3598,6 +3994,7 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
                     table_kind,
                     table_size,
                     is_predefined_mode, // is_predefined
+                    is_repeat_mode,     // is_repeat
                     state,
                     symbol,
                     baseline,
@@ -3649,22 +4046,44 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
             // the following scenarios:
             // - end of block (is_last=true) with tag=SequenceData
             // - end of block (is_last=true) with tag=SequenceHeader and num_sequences=0
+            // - end of Raw_Block or RLE_Block (is_last=true) with tag in [RawBytes, RleBytes]
             // - the last tag ended OK
             cb.require_equal(
                 "is_null: block::is_last=true on the previous row",
                 meta.query_advice(config.block_config.is_last_block, Rotation::prev()),
                 1.expr(),
             );
-            cb.require_equal(
-                "is_null: tag::prev check",
-                meta.query_advice(config.tag_config.tag, Rotation::prev()),
-                select::expr(
+            cb.condition(
+                config
+                    .block_config
+                    .is_compressed_block(meta, Rotation::prev()),
+                |cb| {
+                    cb.require_equal(
+                        "is_null: tag::prev check",
+                        meta.query_advice(config.tag_config.tag, Rotation::prev()),
+                        select::expr(
+                            config
+                                .block_config
+                                .is_empty_sequences(meta, Rotation::prev()),
+                            ZstdTag::ZstdBlockSequenceHeader.expr(),
+                            ZstdTag::ZstdBlockSequenceData.expr(),
+                        ),
+                    );
+                },
+            );
+            cb.condition(
+                not::expr(
                     config
                         .block_config
-                        .is_empty_sequences(meta, Rotation::prev()),
-                    ZstdTag::ZstdBlockSequenceHeader.expr(),
-                    ZstdTag::ZstdBlockSequenceData.expr(),
+                        .is_compressed_block(meta, Rotation::prev()),
                 ),
+                |cb| {
+                    cb.require_equal(
+                        "is_null: tag::prev check (Raw_Block or RLE_Block)",
+                        meta.query_advice(config.tag_config.is_literal, Rotation::prev()),
+                        1.expr(),
+                    );
+                },
             );
             cb.require_equal(
                 "is_null: tag_idx::prev == tag_len::prev",
@@ -4608,7 +5027,8 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
         /////////////////////////////////////////////////////////
         //////// Assign FSE and Bitstream Accumulation  /////////
         /////////////////////////////////////////////////////////
-        self.fse_table.assign(layouter, fse_aux_tables, n_enabled)?;
+        self.fse_table
+            .assign(layouter, &fse_aux_tables, n_enabled)?;
         self.bitstring_table_1
             .assign(layouter, &block_info_arr, &witness_rows, n_enabled)?;
         self.bitstring_table_2
@@ -4632,6 +5052,14 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
             .state
             .block_idx;
         for curr_block_idx in 1..=max_block_idx {
+            // Raw_Block and RLE_Block have no literals header.
+            if !literal_header_rows
+                .iter()
+                .any(|r| r.state.block_idx == curr_block_idx)
+            {
+                literal_headers.push((curr_block_idx, 0, (0, 0, 0)));
+                continue;
+            }
             let byte_idx = literal_header_rows
                 .iter()
                 .find(|r| r.state.block_idx == curr_block_idx)
@@ -4716,6 +5144,7 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
                 }
                 let mut last_byte_idx = 0u64;
                 let mut last_bit_start_idx = 0u64;
+                let mut literal_idx = 0u64;
 
                 /////////////////////////////////////////
                 ///////// Assign Witness Rows  //////////
@@ -4934,6 +5363,14 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
                         || Value::known(Fr::from(is_fse_code as u64)),
                     )?;
 
+                    let is_rle_code = row.state.tag == ZstdTag::ZstdBlockSequenceRleCode;
+                    region.assign_advice(
+                        || "tag_config.is_rle_code",
+                        self.tag_config.is_rle_code,
+                        i,
+                        || Value::known(Fr::from(is_rle_code as u64)),
+                    )?;
+
                     let is_null = row.state.tag == ZstdTag::Null;
                     region.assign_advice(
                         || "tag_config.is_null",
@@ -4942,6 +5379,24 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
                         || Value::known(Fr::from(is_null as u64)),
                     )?;
 
+                    let is_literal = matches!(
+                        row.state.tag,
+                        ZstdTag::ZstdBlockLiteralsRawBytes | ZstdTag::ZstdBlockLiteralsRleBytes
+                    );
+                    region.assign_advice(
+                        || "tag_config.is_literal",
+                        self.tag_config.is_literal,
+                        i,
+                        || Value::known(Fr::from(is_literal as u64)),
+                    )?;
+                    literal_idx = if is_literal { literal_idx + 1 } else { 0 };
+                    region.assign_advice(
+                        || "tag_config.literal_idx",
+                        self.tag_config.literal_idx,
+                        i,
+                        || Value::known(Fr::from(literal_idx)),
+                    )?;
+
                     region.assign_advice(
                         || "tag_config.is_change",
                         self.tag_config.is_change.column,
@@ -5030,6 +5485,28 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
                             i,
                             || Value::known(Fr::from(curr_block_info.is_last_block as u64)),
                         )?;
+                        region.assign_advice(
+                            || "block_config.is_raw_block",
+                            self.block_config.is_raw_block,
+                            i,
+                            || {
+                                Value::known(Fr::from(matches!(
+                                    curr_block_info.block_type,
+                                    BlockType::RawBlock
+                                ) as u64))
+                            },
+                        )?;
+                        region.assign_advice(
+                            || "block_config.is_rle_block",
+                            self.block_config.is_rle_block,
+                            i,
+                            || {
+                                Value::known(Fr::from(matches!(
+                                    curr_block_info.block_type,
+                                    BlockType::RleBlock
+                                ) as u64))
+                            },
+                        )?;
                         region.assign_advice(
                             || "block_config.is_block",
                             self.block_config.is_block,
@@ -5048,15 +5525,17 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
                             i,
                             || Value::known(Fr::from(curr_block_info.regen_size)),
                         )?;
+                        // A table in Repeat_Mode carries the is_predefined flag of the table it
+                        // repeats, which is why we read it from the block's FSE table.
                         let is_predefined = match row.fse_data.table_kind {
                             // default: ignored case
                             0 => false,
-                            // LLT
-                            1 => !curr_sequence_info.compression_mode[0],
-                            // MOT
-                            2 => !curr_sequence_info.compression_mode[1],
-                            // MLT
-                            3 => !curr_sequence_info.compression_mode[2],
+                            // LLT, MOT, MLT
+                            1..=3 => fse_aux_tables.iter().any(|table| {
+                                table.block_idx == curr_block_info.block_idx as u64
+                                    && table.table_kind as u64 == row.fse_data.table_kind
+                                    && table.is_predefined
+                            }),
                             _ => unreachable!("table_kind in [1, 2, 3]"),
                         };
                         region.assign_advice(
@@ -5072,12 +5551,18 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
                             .zip_eq(curr_sequence_info.compression_mode.iter())
                             .enumerate()
                         {
-                            region.assign_advice(
-                                || table_name,
-                                self.block_config.compression_modes[idx],
-                                i,
-                                || Value::known(Fr::from(compression_mode as u64)),
-                            )?;
+                            for (column, mode) in [
+                                (self.block_config.rle_modes[idx], 1),
+                                (self.block_config.compression_modes[idx], 2),
+                                (self.block_config.repeat_modes[idx], 3),
+                            ] {
+                                region.assign_advice(
+                                    || table_name,
+                                    column,
+                                    i,
+                                    || Value::known(Fr::from((compression_mode == mode) as u64)),
+                                )?;
+                            }
                         }
                         let is_empty_sequences =
                             IsEqualChip::construct(self.block_config.is_empty_sequences.clone());
@@ -5438,7 +5923,7 @@ impl<const L: usize, const R: usize> DecoderConfig<L, R> {
 #[cfg(test)]
 mod tests {
     use crate::{
-        witgen::{init_zstd_encoder, process, MultiBlockProcessResult},
+        witgen::{decode_frame, init_zstd_encoder, process, MultiBlockProcessResult, ZstdFeature},
         DecoderConfig, DecoderConfigArgs,
     };
     use halo2_proofs::{
@@ -5515,7 +6000,10 @@ mod tests {
                 sequence_info_arr,
                 address_table_rows: address_table_arr,
                 sequence_exec_results,
-            } = process(&self.compressed, challenges.keccak_input());
+            } = process(&self.compressed, challenges.keccak_input()).map_err(|e| {
+                log::error!("decoder witness generation err {:#?}", e);
+                Error::Synthesis
+            })?;
 
             let (recovered_bytes, sequence_exec_info_arr) = sequence_exec_results.into_iter().fold(
                 (Vec::new(), Vec::new()),
@@ -5741,6 +6229,114 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_decoder_config_rle_mode() {
+        // Every sequence has the same literals length, match length and (repeated) offset, so
+        // that the FSE tables are encoded in RLE_Mode, and then re-used in Repeat_Mode.
+        let raw = (0u32..300)
+            .flat_map(|i| {
+                let mut chunk = i.wrapping_mul(2654435761).to_le_bytes().to_vec();
+                chunk.extend_from_slice(b"0123456789abcdefghij");
+                chunk
+            })
+            .collect::<Vec<u8>>();
+        let compressed = {
+            let mut encoder = init_zstd_encoder(Some(1024));
+            encoder
+                .set_pledged_src_size(Some(raw.len() as u64))
+                .expect("Encoder src_size: raw.len()");
+            encoder.write_all(&raw).expect("Encoder wirte_all");
+            encoder.finish().expect("Encoder success")
+        };
+
+        let features = decode_frame(&compressed)
+            .expect("reference decoder")
+            .features;
+        assert!(features.contains(&ZstdFeature::RleMode));
+
+        let k = 18;
+        let decoder_config_tester: DecoderConfigTester<256, 256> =
+            DecoderConfigTester { raw, compressed, k };
+        let mock_prover = MockProver::<Fr>::run(k, &decoder_config_tester, vec![]).unwrap();
+        mock_prover.assert_satisfied_par();
+    }
+
+    #[test]
+    fn test_decoder_config_raw_block() {
+        // Pseudo-random bytes do not compress, so that they are stored in a Raw_Block.
+        let raw = (0..1000)
+            .scan(0x2545f4914f6cdd1du64, |state, _| {
+                *state ^= *state << 13;
+                *state ^= *state >> 7;
+                *state ^= *state << 17;
+                Some((*state >> 56) as u8)
+            })
+            .collect::<Vec<u8>>();
+        let compressed = {
+            let mut encoder = init_zstd_encoder(None);
+            encoder
+                .set_pledged_src_size(Some(raw.len() as u64))
+                .expect("Encoder src_size: raw.len()");
+            encoder.write_all(&raw).expect("Encoder wirte_all");
+            encoder.finish().expect("Encoder success")
+        };
+
+        let features = decode_frame(&compressed)
+            .expect("reference decoder")
+            .features;
+        assert!(features.contains(&ZstdFeature::RawBlock));
+
+        let k = 18;
+        let decoder_config_tester: DecoderConfigTester<256, 256> =
+            DecoderConfigTester { raw, compressed, k };
+        let mock_prover = MockProver::<Fr>::run(k, &decoder_config_tester, vec![]).unwrap();
+        mock_prover.assert_satisfied_par();
+    }
+
+    #[test]
+    fn test_decoder_config_raw_and_rle_blocks() {
+        // The encoder never emits RLE literals, so the frame is written by hand: a Raw_Block, an
+        // RLE_Block, a compressed block of RLE literals with one sequence and a last Raw_Block.
+        let compressed = vec![
+            // single segment frame, with a 1-byte frame content size
+            0x20, 118, //
+            // Raw_Block of 5 bytes
+            0x28, 0x00, 0x00, b'h', b'e', b'l', b'l', b'o', //
+            // RLE_Block of 7 bytes
+            0x3a, 0x00, 0x00, b'a', //
+            // compressed block of 9 bytes, with 100 RLE literals
+            0x4c, 0x00, 0x00, 0x45, 0x06, b'z', //
+            // one sequence, LL=16, OF=1 and ML=3, with the 3 tables in RLE_Mode
+            0x01, 0x54, 0x10, 0x02, 0x00, 0x08, //
+            // last Raw_Block of 3 bytes
+            0x19, 0x00, 0x00, b'b', b'y', b'e',
+        ];
+        let raw = [
+            b"hello".to_vec(),
+            vec![b'a'; 7],
+            vec![b'z'; 103],
+            b"bye".to_vec(),
+        ]
+        .concat();
+
+        let decoded = decode_frame(&compressed).expect("reference decoder");
+        assert_eq!(decoded.content, raw);
+        for feature in [
+            ZstdFeature::RawBlock,
+            ZstdFeature::RleBlock,
+            ZstdFeature::RleLiterals,
+            ZstdFeature::RleMode,
+        ] {
+            assert!(decoded.features.contains(&feature));
+        }
+
+        let k = 18;
+        let decoder_config_tester: DecoderConfigTester<256, 256> =
+            DecoderConfigTester { raw, compressed, k };
+        let mock_prover = MockProver::<Fr>::run(k, &decoder_config_tester, vec![]).unwrap();
+        mock_prover.assert_satisfied_par();
+    }
+
     #[test]
     #[ignore = "multi_blob: heavy"]
     fn test_decoder_config_large_multi_block() -> Result<(), std::io::Error> {
@@ -1,17 +1,15 @@
 use gadgets::util::{and, not, select, Expr};
 use halo2_proofs::{
     circuit::{AssignedCell, Layouter, Region, Value},
-    plonk::{
-        Advice, Any, Column, ConstraintSystem, Error, Expression, Fixed, SecondPhase, VirtualCells,
-    },
+    plonk::{Advice, Any, Column, ConstraintSystem, Error, Expression, Fixed, VirtualCells},
     poly::Rotation,
 };
 use itertools::Itertools;
 use tables::SeqInstTable;
-use witgen::{SequenceExec, SequenceExecInfo, SequenceInfo, ZstdTag};
+use witgen::{SequenceExec, SequenceExecInfo, SequenceInfo};
 use zkevm_circuits::{
     evm_circuit::{BaseConstraintBuilder, ConstrainBuilderCommon},
-    util::Field,
+    util::{advice_column_in_phase2, ChallengePhases, Field},
 };
 
 use super::tables;
@@ -31,6 +29,8 @@ pub struct SequenceConfig {
     block_index: Column<Advice>,
     // Number of sequences decoded from the sequences section header in the block.
     num_sequences: Column<Advice>,
+    // Number of literals in the block, i.e. the regenerated size of its literals section.
+    num_literals: Column<Advice>,
 }
 
 impl SequenceConfig {
@@ -39,13 +39,19 @@ impl SequenceConfig {
         &self,
         layouter: &mut impl Layouter<F>,
         seq_cfg: &SequenceInfo,
+        num_literals: usize,
     ) -> Result<(), Error> {
         layouter.assign_region(
             || "seq cfg mock",
             |mut region| {
                 let mut offset = 0usize;
 
-                for col in [self.flag, self.block_index, self.num_sequences] {
+                for col in [
+                    self.flag,
+                    self.block_index,
+                    self.num_sequences,
+                    self.num_literals,
+                ] {
                     region.assign_advice(
                         || "flush for non lookup",
                         col,
@@ -59,6 +65,7 @@ impl SequenceConfig {
                     (self.flag, F::one()),
                     (self.block_index, F::from(seq_cfg.block_idx as u64)),
                     (self.num_sequences, F::from(seq_cfg.num_sequences as u64)),
+                    (self.num_literals, F::from(num_literals as u64)),
                 ] {
                     region.assign_advice(
                         || "flush mock table",
@@ -78,23 +85,25 @@ impl SequenceConfig {
         )
     }
 
-    /// construct table for rows: [enabled, blk_index, num_seq]
-    pub fn construct(cols: [Column<Any>; 4]) -> Self {
+    /// construct table for rows: [enabled, blk_index, num_seq, num_lit]
+    pub fn construct(cols: [Column<Any>; 5]) -> Self {
         Self {
             q_enabled: cols[0].try_into().unwrap(),
             flag: cols[1].try_into().unwrap(),
             block_index: cols[2].try_into().unwrap(),
             num_sequences: cols[3].try_into().unwrap(),
+            num_literals: cols[4].try_into().unwrap(),
         }
     }
 
-    /// export the exps for literal copying lookup: [tag, blk_ind, byte_ind, char, padding]
-    pub fn lookup_tbl<F: Field>(&self, meta: &mut VirtualCells<'_, F>) -> [Expression<F>; 4] {
+    /// export the exps for instruction counts lookup: [enabled, flag, blk_ind, num_seq, num_lit]
+    pub fn lookup_tbl<F: Field>(&self, meta: &mut VirtualCells<'_, F>) -> [Expression<F>; 5] {
         [
             meta.query_fixed(self.q_enabled, Rotation::cur()),
             meta.query_advice(self.flag, Rotation::cur()),
             meta.query_advice(self.block_index, Rotation::cur()),
             meta.query_advice(self.num_sequences, Rotation::cur()),
+            meta.query_advice(self.num_literals, Rotation::cur()),
         ]
     }
 }
@@ -104,17 +113,14 @@ impl SequenceConfig {
 pub struct LiteralTable {
     // the enabled flag
     q_enabled: Column<Fixed>,
-    // the tag for current row in literal section
-    tag: Column<Advice>,
+    // the flag set on the rows holding a literal
+    flag: Column<Advice>,
     // the index of block which the literal section is in
     block_index: Column<Advice>,
-    // the 1-indexed byte of byte of literal section's raw bytes
+    // the 1-indexed literal of the block
     byte_index: Column<Advice>,
     // the corresponding char of current index
     char: Column<Advice>,
-    // the flag IN NEXT ROW is set to 1 indicate it is
-    // the last byte in current section
-    last_flag: Column<Advice>,
     // the flag should be 0 for a valid lookup row
     padding_flag: Column<Advice>,
 }
@@ -132,11 +138,10 @@ impl LiteralTable {
                 let mut offset = 0usize;
 
                 for col in [
-                    self.tag,
+                    self.flag,
                     self.block_index,
                     self.byte_index,
                     self.char,
-                    self.last_flag,
                     self.padding_flag,
                 ] {
                     region.assign_advice(
@@ -147,7 +152,6 @@ impl LiteralTable {
                     )?;
                 }
                 offset += 1;
-                // TODO: ensure the index in literal table is 0 or 1 indexed
                 for (i, char) in literals.iter().copied().enumerate() {
                     region.assign_fixed(
                         || "enable mock table",
@@ -156,11 +160,10 @@ impl LiteralTable {
                         || Value::known(F::one()),
                     )?;
                     for (col, val) in [
-                        (self.tag, F::from(ZstdTag::ZstdBlockLiteralsRawBytes as u64)),
+                        (self.flag, F::one()),
                         (self.block_index, F::one()),
                         (self.byte_index, F::from(i as u64 + 1)),
                         (self.char, F::from(char)),
-                        (self.last_flag, F::zero()),
                         (self.padding_flag, F::zero()),
                     ] {
                         region.assign_advice(
@@ -173,80 +176,37 @@ impl LiteralTable {
                     offset += 1;
                 }
 
-                for col in [self.byte_index, self.char, self.padding_flag] {
-                    region.assign_advice(
-                        || "flush dummy row for border",
-                        col,
-                        offset,
-                        || Value::known(F::zero()),
-                    )?;
-                }
-                region.assign_advice(
-                    || "set dummy border",
-                    self.tag,
-                    offset,
-                    || Value::known(F::from(ZstdTag::ZstdBlockLiteralsRawBytes as u64)),
-                )?;
-                region.assign_advice(
-                    || "set dummy border",
-                    self.block_index,
-                    offset,
-                    || Value::known(F::from(2)),
-                )?;
-                region.assign_advice(
-                    || "set dummy border",
-                    self.last_flag,
-                    offset,
-                    || Value::known(F::one()),
-                )?;
-
                 Ok(())
             },
         )
     }
 
-    /// construct table for rows: [q_enable, tag, blk_index, byte_index, char, last, padding]
-    pub fn construct(cols: [Column<Any>; 7]) -> Self {
+    /// construct table for rows: [q_enable, flag, blk_index, byte_index, char, padding]
+    pub fn construct(cols: [Column<Any>; 6]) -> Self {
         Self {
             q_enabled: cols[0].try_into().unwrap(),
-            tag: cols[1].try_into().unwrap(),
+            flag: cols[1].try_into().unwrap(),
             block_index: cols[2].try_into().unwrap(),
             byte_index: cols[3].try_into().unwrap(),
             char: cols[4].try_into().unwrap(),
-            last_flag: cols[5].try_into().unwrap(),
-            padding_flag: cols[6].try_into().unwrap(),
+            padding_flag: cols[5].try_into().unwrap(),
         }
     }
 
-    /// export the exps for literal copying lookup: [tag, blk_ind, byte_ind, char, padding]
+    /// export the exps for literal copying lookup: [flag, blk_ind, byte_ind, char, padding]
     pub fn lookup_tbl_for_lit_cp<F: Field>(
         &self,
         meta: &mut VirtualCells<'_, F>,
     ) -> [Expression<F>; 6] {
         [
             meta.query_fixed(self.q_enabled, Rotation::cur()),
-            meta.query_advice(self.tag, Rotation::cur()),
+            meta.query_advice(self.flag, Rotation::cur()),
             meta.query_advice(self.block_index, Rotation::cur()),
             meta.query_advice(self.byte_index, Rotation::cur()),
             meta.query_advice(self.char, Rotation::cur()),
             meta.query_advice(self.padding_flag, Rotation::cur()),
         ]
     }
-
-    /// export the exps for literal size lookup: [tag, blk_ind, byte_ind, flag, padding]
-    pub fn lookup_tbl_for_lit_size<F: Field>(
-        &self,
-        meta: &mut VirtualCells<'_, F>,
-    ) -> [Expression<F>; 6] {
-        [
-            meta.query_fixed(self.q_enabled, Rotation::cur()),
-            meta.query_advice(self.tag, Rotation::cur()),
-            meta.query_advice(self.block_index, Rotation::cur()),
-            meta.query_advice(self.byte_index, Rotation::cur()),
-            meta.query_advice(self.last_flag, Rotation::next()),
-            meta.query_advice(self.padding_flag, Rotation::cur()),
-        ]
-    }
 }
 
 /// SeqExecConfig handling the sequences in each block and output the
@@ -297,6 +257,7 @@ impl<F: Field> SeqExecConfig<F> {
     pub fn configure(
         meta: &mut ConstraintSystem<F>,
         challenges: Expression<F>,
+        phases: ChallengePhases,
         literal_table: &LiteralTable,
         inst_table: &SeqInstTable<F>,
         seq_config: &SequenceConfig,
@@ -306,7 +267,7 @@ impl<F: Field> SeqExecConfig<F> {
         let seq_index = meta.advice_column();
         let decoded_len = meta.advice_column();
         let decoded_byte = meta.advice_column();
-        let decoded_rlc = meta.advice_column_in(SecondPhase);
+        let decoded_rlc = advice_column_in_phase2(meta, phases);
         let s_last_lit_cp_phase =
             BooleanAdvice::construct(meta, |meta| meta.query_fixed(q_enabled, Rotation::cur()));
         let s_lit_cp_phase =
@@ -597,7 +558,7 @@ impl<F: Field> SeqExecConfig<F> {
                 .into_iter()
                 .zip_eq([
                     1.expr(),
-                    ZstdTag::ZstdBlockLiteralsRawBytes.expr(),
+                    1.expr(),
                     block_index,
                     literal_pos,
                     cp_byte,
@@ -629,32 +590,6 @@ impl<F: Field> SeqExecConfig<F> {
                 .collect()
         });
 
-        debug_assert!(meta.degree() <= 9);
-        meta.lookup_any("actual literal byte", |meta| {
-            let q_enabled = meta.query_fixed(q_enabled, Rotation::prev());
-            let block_index = meta.query_advice(block_index, Rotation::prev());
-            let literal_pos_at_block_end = meta.query_advice(literal_pos, Rotation::prev());
-
-            let tbl_exprs = literal_table.lookup_tbl_for_lit_size(meta);
-            tbl_exprs
-                .into_iter()
-                .zip_eq([
-                    1.expr(),
-                    ZstdTag::ZstdBlockLiteralsRawBytes.expr(),
-                    block_index,
-                    literal_pos_at_block_end,
-                    1.expr(),
-                    0.expr(),
-                ])
-                .map(|(lookup_expr, src_expr)| {
-                    (
-                        src_expr * is_block_begin.expr() * q_enabled.expr(),
-                        lookup_expr,
-                    )
-                })
-                .collect()
-        });
-
         debug_assert!(meta.degree() <= 9);
         meta.lookup_any("instruction counts", |meta| {
             let q_enabled = meta.query_fixed(q_enabled, Rotation::prev());
@@ -663,11 +598,19 @@ impl<F: Field> SeqExecConfig<F> {
                 // if we have a additional literal copying phase, we 
                 // in fact has one extra instruction
                 - s_last_lit_cp_phase.expr_at(meta, Rotation::prev());
+            // all of the literals in the block have been copied
+            let literal_pos_at_block_end = meta.query_advice(literal_pos, Rotation::prev());
 
             seq_config
                 .lookup_tbl(meta)
                 .into_iter()
-                .zip_eq([1.expr(), 1.expr(), block_index, seq_index_at_block_end])
+                .zip_eq([
+                    1.expr(),
+                    1.expr(),
+                    block_index,
+                    seq_index_at_block_end,
+                    literal_pos_at_block_end,
+                ])
                 .map(|(lookup_expr, src_expr)| {
                     (
                         src_expr * is_block_begin.expr() * q_enabled.expr(),
@@ -1142,7 +1085,6 @@ mod tests {
                 meta.advice_column().into(),
                 meta.advice_column().into(),
                 meta.advice_column().into(),
-                meta.advice_column().into(),
             ]);
 
             let seq_cfg = SequenceConfig::construct([
@@ -1150,6 +1092,7 @@ mod tests {
                 meta.advice_column().into(),
                 meta.advice_column().into(),
                 meta.advice_column().into(),
+                meta.advice_column().into(),
             ]);
 
             let inst_tbl = SeqInstTable::configure(meta);
@@ -1160,6 +1103,7 @@ mod tests {
             let config = SeqExecConfig::configure(
                 meta,
                 chng.keccak_input(),
+                chng.phases(),
                 &literal_tbl,
                 &inst_tbl,
                 &seq_cfg,
@@ -1189,7 +1133,9 @@ mod tests {
                     .as_slice(),
             )?;
 
-            config.seq_cfg.mock_assign(&mut layouter, &self.seq_conf)?;
+            config
+                .seq_cfg
+                .mock_assign(&mut layouter, &self.seq_conf, self.literals.len())?;
 
             config
                 .inst_tbl
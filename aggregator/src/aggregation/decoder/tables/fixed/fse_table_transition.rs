@@ -1,4 +1,5 @@
 use halo2_proofs::{circuit::Value, halo2curves::bn256::Fr};
+use itertools::Itertools;
 
 use super::{FixedLookupTag, FixedLookupValues};
 
@@ -11,6 +12,9 @@ pub struct RomFseTableTransition {
     pub table_kind_prev: u64,
     /// The FSE table currently decoded.
     pub table_kind_curr: u64,
+    /// Whether the FSE table currently decoded is re-used from the previous block, i.e.
+    /// Repeat_Mode. This is not possible in the first block.
+    pub is_repeat: bool,
 }
 
 impl FixedLookupValues for RomFseTableTransition {
@@ -23,21 +27,31 @@ impl FixedLookupValues for RomFseTableTransition {
         (1..N_MAX_BLOCKS)
             .flat_map(|block_idx_curr| {
                 let table_kind_prev = if block_idx_curr == 1 { None } else { Some(MLT) };
+                let is_repeat_options = if block_idx_curr == 1 {
+                    vec![false]
+                } else {
+                    vec![false, true]
+                };
                 [
                     (block_idx_curr - 1, block_idx_curr, table_kind_prev, LLT),
                     (block_idx_curr, block_idx_curr, Some(LLT), MOT),
                     (block_idx_curr, block_idx_curr, Some(MOT), MLT),
                 ]
+                .into_iter()
+                .cartesian_product(is_repeat_options)
             })
             .map(
-                |(block_idx_prev, block_idx_curr, table_kind_prev, table_kind_curr)| {
+                |(
+                    (block_idx_prev, block_idx_curr, table_kind_prev, table_kind_curr),
+                    is_repeat,
+                )| {
                     [
                         Value::known(Fr::from(FixedLookupTag::FseTableTransition as u64)),
                         Value::known(Fr::from(block_idx_prev)),
                         Value::known(Fr::from(block_idx_curr)),
                         Value::known(table_kind_prev.map_or(Fr::zero(), |v| Fr::from(v as u64))),
                         Value::known(Fr::from(table_kind_curr as u64)),
-                        Value::known(Fr::zero()),
+                        Value::known(Fr::from(is_repeat)),
                         Value::known(Fr::zero()),
                     ]
                 },
@@ -1,4 +1,5 @@
 use halo2_proofs::{circuit::Value, halo2curves::bn256::Fr};
+use itertools::Itertools;
 
 use crate::aggregation::decoder::{
     tables::fixed::FixedLookupTag,
@@ -7,34 +8,27 @@ use crate::aggregation::decoder::{
 
 use super::FixedLookupValues;
 
-/// The possible orders are:
+/// The Compression_Mode of each of LLT, MOT and MLT is one of:
 ///
-/// - (1, 1, 1):
-///     - SequenceHeader > FseCode > FseCode (LLT)
-///     - FseCode > FseCode > FseCode (MOT)
-///     - FseCode > FseCode > SequenceData (MLT)
-/// - (1, 1, 0):
-///     - SequenceHeader > FseCode > FseCode (LLT)
-///     - FseCode > FseCode > SequenceData (MOT)
-/// - (1, 0, 1):
-///     - SequenceHeader > FseCode > FseCode (LLT)
-///     - FseCode > FseCode > SequenceData (MLT)
-/// - (0, 1, 1):
-///     - SequenceHeader > FseCode > FseCode (MOT)
-///     - FseCode > FseCode > SequenceData (MLT)
-/// - (1, 0, 0):
-///     - SequenceHeader > FseCode > SequenceData (LLT)
-/// - (0, 1, 0):
-///     - SequenceHeader > FseCode > SequenceData (MOT)
-/// - (0, 0, 1):
-///     - SequenceHeader > FseCode > SequenceData (MLT)
+/// - 0: Predefined_Mode
+/// - 1: RLE_Mode
+/// - 2: Fse_Compressed_Mode
+/// - 3: Repeat_Mode
+///
+/// Only the tables in RLE_Mode (RleCode) and Fse_Compressed_Mode (FseCode) are described in the
+/// sequences section, in the order LLT > MOT > MLT. For instance with the modes (2, 0, 1):
+///
+/// - SequenceHeader > FseCode > RleCode (LLT, previous table: none)
+/// - FseCode > RleCode > SequenceData (MLT, previous table: LLT)
+///
+/// The FSE table of the previous section makes sure that no section is skipped.
 pub struct RomSeqTagOrder {
-    /// Boolean flag to mark if LLT is Fse_Compressed_Mode or Predefined_Mode.
-    pub cmode_llt: bool,
-    /// Boolean flag to mark if MOT is Fse_Compressed_Mode or Predefined_Mode.
-    pub cmode_mot: bool,
-    /// Boolean flag to mark if MLT is Fse_Compressed_Mode or Predefined_Mode.
-    pub cmode_mlt: bool,
+    /// Compression_Mode of the LLT.
+    pub mode_llt: u64,
+    /// Compression_Mode of the MOT.
+    pub mode_mot: u64,
+    /// Compression_Mode of the MLT.
+    pub mode_mlt: u64,
     /// Tag that was handled before the current tag.
     pub tag_prev: ZstdTag,
     /// Tag currently being handled.
@@ -43,6 +37,8 @@ pub struct RomSeqTagOrder {
     pub tag_next: ZstdTag,
     /// The FSE table that we expect with the current tag.
     pub fse_table: FseTableKind,
+    /// The FSE table described by the previous section, or 0 for the first section.
+    pub fse_table_prev: u64,
 }
 
 impl FixedLookupValues for RomSeqTagOrder {
@@ -50,44 +46,44 @@ impl FixedLookupValues for RomSeqTagOrder {
         use FseTableKind::{LLT, MLT, MOT};
         use ZstdTag::{
             ZstdBlockSequenceData as SeqData, ZstdBlockSequenceFseCode as FseCode,
-            ZstdBlockSequenceHeader as SeqHeader,
+            ZstdBlockSequenceHeader as SeqHeader, ZstdBlockSequenceRleCode as RleCode,
+        };
+
+        let section_tag = |mode: u64| match mode {
+            1 => Some(RleCode),
+            2 => Some(FseCode),
+            _ => None,
         };
 
-        [
-            // (1, 1, 1)
-            (1, 1, 1, SeqHeader, FseCode, FseCode, LLT),
-            (1, 1, 1, FseCode, FseCode, FseCode, MOT),
-            (1, 1, 1, FseCode, FseCode, SeqData, MLT),
-            // (1, 1, 0)
-            (1, 1, 0, SeqHeader, FseCode, FseCode, LLT),
-            (1, 1, 0, FseCode, FseCode, SeqData, MOT),
-            // (1, 0, 1)
-            (1, 0, 1, SeqHeader, FseCode, FseCode, LLT),
-            (1, 0, 1, FseCode, FseCode, SeqData, MLT),
-            // (0, 1, 1)
-            (0, 1, 1, SeqHeader, FseCode, FseCode, MOT),
-            (0, 1, 1, FseCode, FseCode, SeqData, MLT),
-            // (1, 0, 0)
-            (1, 0, 0, SeqHeader, FseCode, SeqData, LLT),
-            // (0, 1, 0)
-            (0, 1, 0, SeqHeader, FseCode, SeqData, MOT),
-            // (0, 0, 1)
-            (0, 0, 1, SeqHeader, FseCode, SeqData, MLT),
-        ]
-        .map(
-            |(cmode_llt, cmode_mot, cmode_mlt, tag_prev, tag_curr, tag_next, table_kind)| {
-                let cmode_lc = 4 * cmode_llt + 2 * cmode_mot + cmode_mlt;
-                [
-                    Value::known(Fr::from(FixedLookupTag::SeqTagOrder as u64)),
-                    Value::known(Fr::from(cmode_lc)),
-                    Value::known(Fr::from(tag_prev as u64)),
-                    Value::known(Fr::from(tag_curr as u64)),
-                    Value::known(Fr::from(tag_next as u64)),
-                    Value::known(Fr::from(table_kind as u64)),
-                    Value::known(Fr::zero()),
-                ]
-            },
-        )
-        .to_vec()
+        (0..4u64)
+            .cartesian_product(0..4u64)
+            .cartesian_product(0..4u64)
+            .flat_map(|((mode_llt, mode_mot), mode_mlt)| {
+                let sections = [(mode_llt, LLT), (mode_mot, MOT), (mode_mlt, MLT)]
+                    .into_iter()
+                    .filter_map(|(mode, table_kind)| section_tag(mode).map(|tag| (tag, table_kind)))
+                    .collect::<Vec<_>>();
+                let modes_lc = 16 * mode_llt + 4 * mode_mot + mode_mlt;
+                (0..sections.len())
+                    .map(|i| {
+                        let (tag_curr, table_kind) = sections[i];
+                        let (tag_prev, table_kind_prev) = match i {
+                            0 => (SeqHeader, 0),
+                            _ => (sections[i - 1].0, sections[i - 1].1 as u64),
+                        };
+                        let tag_next = sections.get(i + 1).map_or(SeqData, |&(tag, _)| tag);
+                        [
+                            Value::known(Fr::from(FixedLookupTag::SeqTagOrder as u64)),
+                            Value::known(Fr::from(modes_lc)),
+                            Value::known(Fr::from(tag_prev as u64)),
+                            Value::known(Fr::from(tag_curr as u64)),
+                            Value::known(Fr::from(tag_next as u64)),
+                            Value::known(Fr::from(table_kind as u64)),
+                            Value::known(Fr::from(table_kind_prev)),
+                        ]
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
     }
 }
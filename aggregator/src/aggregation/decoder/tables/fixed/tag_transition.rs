@@ -21,20 +21,33 @@ impl FixedLookupValues for RomTagTransition {
     fn values() -> Vec<[Value<Fr>; 7]> {
         use ZstdTag::{
             BlockHeader, FrameContentSize, FrameHeaderDescriptor, Null, ZstdBlockLiteralsHeader,
-            ZstdBlockLiteralsRawBytes, ZstdBlockSequenceData, ZstdBlockSequenceFseCode,
-            ZstdBlockSequenceHeader,
+            ZstdBlockLiteralsRawBytes, ZstdBlockLiteralsRleBytes, ZstdBlockSequenceData,
+            ZstdBlockSequenceFseCode, ZstdBlockSequenceHeader, ZstdBlockSequenceRleCode,
         };
 
         [
             (FrameHeaderDescriptor, FrameContentSize),
             (FrameContentSize, BlockHeader),
             (BlockHeader, ZstdBlockLiteralsHeader),
+            (BlockHeader, ZstdBlockLiteralsRawBytes), // Raw_Block
+            (BlockHeader, ZstdBlockLiteralsRleBytes), // RLE_Block
             (ZstdBlockLiteralsHeader, ZstdBlockLiteralsRawBytes),
+            (ZstdBlockLiteralsHeader, ZstdBlockLiteralsRleBytes),
             (ZstdBlockLiteralsRawBytes, ZstdBlockSequenceHeader),
+            (ZstdBlockLiteralsRawBytes, BlockHeader), // Raw_Block
+            (ZstdBlockLiteralsRawBytes, Null),        // Raw_Block
+            (ZstdBlockLiteralsRleBytes, ZstdBlockSequenceHeader),
+            (ZstdBlockLiteralsRleBytes, BlockHeader), // RLE_Block
+            (ZstdBlockLiteralsRleBytes, Null),        // RLE_Block
             (ZstdBlockSequenceHeader, ZstdBlockSequenceFseCode),
+            (ZstdBlockSequenceHeader, ZstdBlockSequenceRleCode),
             (ZstdBlockSequenceHeader, ZstdBlockSequenceData),
             (ZstdBlockSequenceFseCode, ZstdBlockSequenceFseCode),
+            (ZstdBlockSequenceFseCode, ZstdBlockSequenceRleCode),
             (ZstdBlockSequenceFseCode, ZstdBlockSequenceData),
+            (ZstdBlockSequenceRleCode, ZstdBlockSequenceFseCode),
+            (ZstdBlockSequenceRleCode, ZstdBlockSequenceRleCode),
+            (ZstdBlockSequenceRleCode, ZstdBlockSequenceData),
             (ZstdBlockSequenceData, BlockHeader), // multi-block
             (ZstdBlockSequenceData, Null),
             (Null, Null),
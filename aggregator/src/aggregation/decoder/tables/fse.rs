@@ -25,6 +25,9 @@ use crate::aggregation::{
 
 const N_ROWS_PER_FSE: usize = 1 << 10;
 
+/// The rotation to the same row of the FSE table of the same kind in the previous block.
+const ROTATION_PREV_BLOCK: Rotation = Rotation(-3 * N_ROWS_PER_FSE as i32);
+
 /// The FSE table verifies that given the symbols and the states allocated to those symbols, the
 /// baseline and number of bits (nb) are assigned correctly to them.
 ///
@@ -43,6 +46,9 @@ const N_ROWS_PER_FSE: usize = 1 << 10;
 /// fixed column ``q_start``. Upon finishing the FSE table, remaining rows are marked with the
 /// ``is_padding`` column.
 ///
+/// A table re-used from the previous block (Repeat_Mode) is marked with ``is_repeat``, and is a
+/// row-by-row copy of the table of the same kind 3 tables above, i.e. in the previous block.
+///
 /// Each table begins with symbols that have a "less than 1" probability, whereby the state
 /// allocated to them is at the end of the table (highest state) and retreating. For example, if
 /// the symbol=3 has a normalised probability of prob==-1, then it is allocated the state 0x3f (63)
@@ -204,10 +210,10 @@ impl<const L: usize, const R: usize> FseTable<L, R> {
 
         // Check that on the starting row of every FSE table, i.e. q_start=true:
         //
-        // - tuple (block_idx::prev, block_idx::cur, table_kind::prev, table_kind::cur)
+        // - tuple (block_idx::prev, block_idx::cur, table_kind::prev, table_kind::cur, is_repeat)
         //
         // is in fact a valid transition. All valid transitions are provided in the fixed-table
-        // RomFseTableTransition.
+        // RomFseTableTransition. A table of the first block cannot be in Repeat_Mode.
         meta.lookup_any(
             "FseTable: start row (ROM block_idx and table_kind transition)",
             |meta| {
@@ -230,7 +236,7 @@ impl<const L: usize, const R: usize> FseTable<L, R> {
                     block_idx_curr,
                     table_kind_prev,
                     table_kind_curr,
-                    0.expr(), // unused
+                    config.sorted_table.is_repeat.expr_at(meta, Rotation::cur()),
                     0.expr(), // unused
                 ]
                 .into_iter()
@@ -240,6 +246,41 @@ impl<const L: usize, const R: usize> FseTable<L, R> {
             },
         );
 
+        // A table in Repeat_Mode is a copy of the table of the same kind in the previous block.
+        meta.create_gate("FseTable: repeated table", |meta| {
+            let condition = and::expr([
+                meta.query_fixed(q_enable, Rotation::cur()),
+                config.sorted_table.is_repeat.expr_at(meta, Rotation::cur()),
+            ]);
+
+            let mut cb = BaseConstraintBuilder::default();
+
+            cb.require_equal(
+                "repeated table: block_idx == block_idx::prev_block + 1",
+                meta.query_advice(config.sorted_table.block_idx, Rotation::cur()),
+                meta.query_advice(config.sorted_table.block_idx, ROTATION_PREV_BLOCK) + 1.expr(),
+            );
+            for column in [
+                config.sorted_table.table_kind,
+                config.sorted_table.table_size,
+                config.sorted_table.is_predefined.column,
+                config.state,
+                config.symbol,
+                config.baseline,
+                config.nb,
+                config.is_skipped_state.column,
+                config.is_padding.column,
+            ] {
+                cb.require_equal(
+                    "repeated table: columns copied from the previous block",
+                    meta.query_advice(column, Rotation::cur()),
+                    meta.query_advice(column, ROTATION_PREV_BLOCK),
+                );
+            }
+
+            cb.gate(condition)
+        });
+
         // The starting row of every FSE table, i.e. q_start=true.
         meta.create_gate("FseTable: start row", |meta| {
             let condition = and::expr([
@@ -766,7 +807,7 @@ impl<const L: usize, const R: usize> FseTable<L, R> {
     pub fn assign(
         &self,
         layouter: &mut impl Layouter<Fr>,
-        data: Vec<FseAuxiliaryTableData>,
+        data: &[FseAuxiliaryTableData],
         n_enabled: usize,
     ) -> Result<(), Error> {
         layouter.assign_region(
@@ -1070,6 +1111,12 @@ impl<const L: usize, const R: usize> FseTable<L, R> {
                                     sorted_offset,
                                     || Value::known(Fr::from(table.is_predefined as u64)),
                                 )?;
+                                region.assign_advice(
+                                    || "sorted_table.is_repeat",
+                                    self.sorted_table.is_repeat.column,
+                                    sorted_offset,
+                                    || Value::known(Fr::from(table.is_repeat as u64)),
+                                )?;
                                 region.assign_advice(
                                     || "sorted_table.table_size",
                                     self.sorted_table.table_size,
@@ -1235,6 +1282,12 @@ impl<const L: usize, const R: usize> FseTable<L, R> {
                             offset,
                             || Value::known(Fr::from(table.is_predefined as u64)),
                         )?;
+                        region.assign_advice(
+                            || "sorted_table.is_repeat",
+                            self.sorted_table.is_repeat.column,
+                            offset,
+                            || Value::known(Fr::from(table.is_repeat as u64)),
+                        )?;
                     }
                     fse_offset = target_end_offset;
                     sorted_offset = target_end_offset;
@@ -1283,6 +1336,7 @@ impl<const L: usize, const R: usize> FseTable<L, R> {
             self.sorted_table
                 .is_predefined
                 .expr_at(meta, Rotation::cur()),
+            self.sorted_table.is_repeat.expr_at(meta, Rotation::cur()),
             meta.query_advice(self.state, Rotation::cur()),
             meta.query_advice(self.symbol, Rotation::cur()),
             meta.query_advice(self.baseline, Rotation::cur()),
@@ -1305,6 +1359,7 @@ impl<const L: usize, const R: usize> FseTable<L, R> {
             self.sorted_table
                 .is_predefined
                 .expr_at(meta, Rotation::cur()),
+            self.sorted_table.is_repeat.expr_at(meta, Rotation::cur()),
             meta.query_advice(self.symbol, Rotation::cur()),
             meta.query_advice(self.symbol_count, Rotation::cur()),
             meta.query_advice(self.symbol_count_acc, Rotation::cur()),
@@ -1325,6 +1380,7 @@ impl<const L: usize, const R: usize> FseTable<L, R> {
             self.sorted_table
                 .is_predefined
                 .expr_at(meta, Rotation::cur()),
+            self.sorted_table.is_repeat.expr_at(meta, Rotation::cur()),
             self.is_padding.expr_at(meta, Rotation::cur()),
         ]
     }
@@ -1387,6 +1443,9 @@ struct FseSortedStatesTable {
     /// [doclink1]: https://github.com/facebook/zstd/blob/dev/doc/zstd_compression_format.md#default-distributions
     /// [doclink2]: https://github.com/facebook/zstd/blob/dev/doc/zstd_compression_format.md#appendix-a---decoding-tables-for-predefined-codes
     is_predefined: BooleanAdvice,
+    /// A boolean to indicate whether the FSE table is re-used from the previous block, i.e.
+    /// Repeat_Mode.
+    is_repeat: BooleanAdvice,
     /// The FSE symbol, starting at the first symbol with prob>=1.
     symbol: Column<Advice>,
     /// Boolean column to mark if we are moving to the next symbol.
@@ -1443,6 +1502,9 @@ impl FseSortedStatesTable {
             is_predefined: BooleanAdvice::construct(meta, |meta| {
                 meta.query_fixed(q_enable, Rotation::cur())
             }),
+            is_repeat: BooleanAdvice::construct(meta, |meta| {
+                meta.query_fixed(q_enable, Rotation::cur())
+            }),
             symbol: meta.advice_column(),
             is_new_symbol: BooleanAdvice::construct(meta, |meta| {
                 meta.query_fixed(q_enable, Rotation::cur())
@@ -1624,6 +1686,7 @@ impl FseSortedStatesTable {
                     config.table_kind,
                     config.table_size,
                     config.is_predefined.column,
+                    config.is_repeat.column,
                 ] {
                     cb.require_equal(
                         "FseSortedStatesTable: columns that remain unchanged",
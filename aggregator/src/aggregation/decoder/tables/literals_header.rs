@@ -25,8 +25,9 @@ pub struct LiteralsHeaderTable {
     /// Fixed column to mark the first row of the table.
     q_first: Column<Fixed>,
     /// The block index in which we find this literals header. Since every block will have a
-    /// literals header, and block_idx in 1..=n, we know that on the first row block_idx=1 and on
-    /// subsequent rows, block_idx increments by 1.
+    /// row, and block_idx in 1..=n, we know that on the first row block_idx=1 and on subsequent
+    /// rows, block_idx increments by 1. Raw and RLE blocks do not have a literals header, their
+    /// row is filled with zeroes and never looked up.
     pub block_idx: Column<Advice>,
     /// The first byte of the literals header.
     pub byte0: Column<Advice>,
@@ -215,14 +216,14 @@ impl LiteralsHeaderTable {
 
                     let [n_bits_fmt, n_bits_regen, n_bytes_header]: [usize; 3] =
                         match literals_block_type {
-                            BlockType::RawBlock => match size_format {
+                            BlockType::RawBlock | BlockType::RleBlock => match size_format {
                                 0b00 | 0b10 => [1, 5, 1],
                                 0b01 => [2, 12, 2],
                                 0b11 => [2, 20, 3],
                                 _ => unreachable!("size_format out of bound"),
                             },
                             _ => unreachable!(
-                                "BlockType::* unexpected. Must be raw bytes for literals."
+                                "BlockType::* unexpected. Must be raw or rle bytes for literals."
                             ),
                         };
 
@@ -9,6 +9,9 @@ mod types;
 pub use types::*;
 
 pub mod util;
+
+mod reference;
+pub use reference::{decode_frame, DecodedFrame, ZstdFeature};
 use util::{be_bits_to_value, increment_idx, le_bits_to_value, value_bits_le};
 
 const CMOT_N: u64 = 31;
@@ -147,6 +150,7 @@ fn process_block<F: Field>(
     last_row: &ZstdWitnessRow<F>,
     randomness: Value<F>,
     repeated_offset: [usize; 3],
+    prev_fse_aux_tables: &[FseAuxiliaryTableData],
 ) -> AggregateBlockResult<F> {
     let mut witness_rows = vec![];
 
@@ -177,8 +181,22 @@ fn process_block<F: Field>(
             block_info.block_len,
             block_info.is_last_block,
             repeated_offset,
+            prev_fse_aux_tables,
+        ),
+        BlockType::RawBlock | BlockType::RleBlock => process_block_raw_or_rle(
+            src,
+            decoded_bytes,
+            block_idx,
+            byte_offset,
+            last_row,
+            randomness,
+            block_info.block_len,
+            block_info.is_last_block,
+            matches!(block_info.block_type, BlockType::RleBlock),
+            repeated_offset,
+            prev_fse_aux_tables,
         ),
-        _ => unreachable!("BlockType::ZstdCompressedBlock expected"),
+        BlockType::Reserved => unreachable!("BlockType::Reserved is invalid"),
     };
     block_info.regen_size = regen_size;
     witness_rows.extend_from_slice(&rows);
@@ -219,8 +237,10 @@ fn process_block_header<F: Field>(
         (bh_bytes[2] as usize * 256 * 256 + bh_bytes[1] as usize * 256 + bh_bytes[0] as usize) >> 3;
 
     let tag_next = match block_info.block_type {
+        BlockType::RawBlock => ZstdTag::ZstdBlockLiteralsRawBytes,
+        BlockType::RleBlock => ZstdTag::ZstdBlockLiteralsRleBytes,
         BlockType::ZstdCompressedBlock => ZstdTag::ZstdBlockLiteralsHeader,
-        _ => unreachable!("BlockType::ZstdCompressedBlock expected"),
+        BlockType::Reserved => unreachable!("BlockType::Reserved is invalid"),
     };
 
     let tag_rlc_iter = bh_bytes
@@ -309,6 +329,7 @@ fn process_block_zstd<F: Field>(
     block_size: usize,
     last_block: bool,
     repeated_offset: [usize; 3],
+    prev_fse_aux_tables: &[FseAuxiliaryTableData],
 ) -> BlockProcessingResult<F> {
     let expected_end_offset = byte_offset + block_size;
     let mut witness_rows = vec![];
@@ -319,6 +340,7 @@ fn process_block_zstd<F: Field>(
         witness_rows: rows,
         regen_size,
         compressed_size: _,
+        literals_block_type,
     } = process_block_zstd_literals_header::<F>(src, block_idx, byte_offset, last_row, randomness);
 
     witness_rows.extend_from_slice(&rows);
@@ -328,57 +350,16 @@ fn process_block_zstd<F: Field>(
         witness_rows: rows,
         literals,
         regen_size: _,
-    } = {
-        let last_row = rows.last().cloned().unwrap();
-        let multiplier =
-            (0..last_row.state.tag_len).fold(Value::known(F::one()), |acc, _| acc * randomness);
-        let value_rlc = last_row.encoded_data.value_rlc * multiplier + last_row.state.tag_rlc;
-        let tag = ZstdTag::ZstdBlockLiteralsRawBytes;
-        let tag_next = ZstdTag::ZstdBlockSequenceHeader;
-        let literals = src[byte_offset..(byte_offset + regen_size)].to_vec();
-        let tag_rlc_iter = literals.iter().scan(Value::known(F::zero()), |acc, &byte| {
-            *acc = *acc * randomness + Value::known(F::from(byte as u64));
-            Some(*acc)
-        });
-        let tag_rlc = tag_rlc_iter.clone().last().expect("Literals must exist.");
-
-        LiteralsBlockResult {
-            offset: byte_offset + regen_size,
-            witness_rows: literals
-                .iter()
-                .zip(tag_rlc_iter)
-                .enumerate()
-                .map(|(i, (&value_byte, tag_rlc_acc))| ZstdWitnessRow {
-                    state: ZstdState {
-                        tag,
-                        tag_next,
-                        block_idx,
-                        max_tag_len: tag.max_len(),
-                        tag_len: regen_size as u64,
-                        tag_idx: (i + 1) as u64,
-                        is_tag_change: i == 0,
-                        tag_rlc,
-                        tag_rlc_acc,
-                    },
-                    encoded_data: EncodedData {
-                        byte_idx: (byte_offset + i + 1) as u64,
-                        encoded_len: last_row.encoded_data.encoded_len,
-                        value_byte,
-                        value_rlc,
-                        reverse: false,
-                        ..Default::default()
-                    },
-                    decoded_data: DecodedData {
-                        decoded_len: last_row.decoded_data.decoded_len,
-                    },
-                    bitstream_read_data: BitstreamReadRow::default(),
-                    fse_data: FseDecodingRow::default(),
-                })
-                .collect::<Vec<_>>(),
-            literals: literals.iter().map(|b| *b as u64).collect::<Vec<u64>>(),
-            regen_size,
-        }
-    };
+    } = process_block_literals::<F>(
+        src,
+        block_idx,
+        byte_offset,
+        rows.last().expect("last row expected to exist"),
+        randomness,
+        matches!(literals_block_type, BlockType::RleBlock),
+        regen_size,
+        ZstdTag::ZstdBlockSequenceHeader,
+    );
 
     witness_rows.extend_from_slice(&rows);
 
@@ -404,6 +385,7 @@ fn process_block_zstd<F: Field>(
         last_block,
         randomness,
         repeated_offset,
+        prev_fse_aux_tables,
     );
 
     // sanity check:
@@ -429,6 +411,168 @@ fn process_block_zstd<F: Field>(
     }
 }
 
+/// Raw literals are the regen_size bytes as is, whereas RLE literals are a single byte repeated
+/// regen_size times. The latter has one row per literal, all of them at the same byte_idx.
+#[allow(clippy::too_many_arguments)]
+fn process_block_literals<F: Field>(
+    src: &[u8],
+    block_idx: u64,
+    byte_offset: usize,
+    last_row: &ZstdWitnessRow<F>,
+    randomness: Value<F>,
+    is_rle: bool,
+    regen_size: usize,
+    tag_next: ZstdTag,
+) -> LiteralsBlockResult<F> {
+    let multiplier =
+        (0..last_row.state.tag_len).fold(Value::known(F::one()), |acc, _| acc * randomness);
+    let value_rlc = last_row.encoded_data.value_rlc * multiplier + last_row.state.tag_rlc;
+    let (tag, tag_len) = if is_rle {
+        (ZstdTag::ZstdBlockLiteralsRleBytes, 1)
+    } else {
+        (ZstdTag::ZstdBlockLiteralsRawBytes, regen_size)
+    };
+    let literals = if is_rle {
+        vec![src[byte_offset]; regen_size]
+    } else {
+        src[byte_offset..(byte_offset + regen_size)].to_vec()
+    };
+    let tag_rlc_iter = src[byte_offset..(byte_offset + tag_len)]
+        .iter()
+        .scan(Value::known(F::zero()), |acc, &byte| {
+            *acc = *acc * randomness + Value::known(F::from(byte as u64));
+            Some(*acc)
+        })
+        .collect::<Vec<Value<F>>>();
+    let tag_rlc = *tag_rlc_iter.last().expect("Literals must exist.");
+
+    LiteralsBlockResult {
+        offset: byte_offset + tag_len,
+        witness_rows: literals
+            .iter()
+            .enumerate()
+            .map(|(i, &value_byte)| {
+                let j = if is_rle { 0 } else { i };
+                ZstdWitnessRow {
+                    state: ZstdState {
+                        tag,
+                        tag_next,
+                        block_idx,
+                        max_tag_len: tag.max_len(),
+                        tag_len: tag_len as u64,
+                        tag_idx: (j + 1) as u64,
+                        is_tag_change: i == 0,
+                        tag_rlc,
+                        tag_rlc_acc: tag_rlc_iter[j],
+                    },
+                    encoded_data: EncodedData {
+                        byte_idx: (byte_offset + j + 1) as u64,
+                        encoded_len: last_row.encoded_data.encoded_len,
+                        value_byte,
+                        value_rlc,
+                        reverse: false,
+                        ..Default::default()
+                    },
+                    decoded_data: DecodedData {
+                        decoded_len: last_row.decoded_data.decoded_len,
+                    },
+                    bitstream_read_data: BitstreamReadRow::default(),
+                    fse_data: FseDecodingRow::default(),
+                }
+            })
+            .collect::<Vec<_>>(),
+        literals: literals.iter().map(|b| *b as u64).collect::<Vec<u64>>(),
+        regen_size,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn process_block_raw_or_rle<F: Field>(
+    src: &[u8],
+    decoded_bytes: &mut Vec<u8>,
+    block_idx: u64,
+    byte_offset: usize,
+    last_row: &ZstdWitnessRow<F>,
+    randomness: Value<F>,
+    block_size: usize,
+    last_block: bool,
+    is_rle: bool,
+    repeated_offset: [usize; 3],
+    prev_fse_aux_tables: &[FseAuxiliaryTableData],
+) -> BlockProcessingResult<F> {
+    let tag_next = if last_block {
+        ZstdTag::Null
+    } else {
+        ZstdTag::BlockHeader
+    };
+    let LiteralsBlockResult {
+        offset,
+        witness_rows,
+        literals,
+        regen_size,
+    } = process_block_literals::<F>(
+        src,
+        block_idx,
+        byte_offset,
+        last_row,
+        randomness,
+        is_rle,
+        block_size,
+        tag_next,
+    );
+
+    // the block has no sequences section, all its literals are copied as is.
+    let recovered_bytes = literals.iter().map(|&b| b as u8).collect::<Vec<u8>>();
+    decoded_bytes.extend_from_slice(&recovered_bytes);
+
+    // every block has its 3 FSE tables. Those of a block without sequences repeat the tables of
+    // the previous block, so that a later block in Repeat_Mode still finds them, or are the
+    // predefined ones if there is none yet.
+    let fse_aux_tables =
+        [FseTableKind::LLT, FseTableKind::MOT, FseTableKind::MLT].map(|table_kind| {
+            match prev_fse_aux_tables
+                .iter()
+                .rev()
+                .find(|table| table.table_kind == table_kind)
+            {
+                Some(table) => table.repeat(block_idx),
+                None => {
+                    FseAuxiliaryTableData::reconstruct(
+                        src,
+                        block_idx,
+                        table_kind,
+                        byte_offset,
+                        true,
+                    )
+                    .expect("predefined FSE table expected")
+                    .2
+                }
+            }
+        });
+
+    BlockProcessingResult {
+        offset,
+        witness_rows,
+        sequence_info: SequenceInfo {
+            block_idx: block_idx as usize,
+            num_sequences: 0,
+            compression_mode: [0; 3],
+        },
+        fse_aux_tables,
+        address_table_rows: vec![],
+        sequence_exec_result: SequenceExecResult {
+            exec_trace: vec![SequenceExec(
+                0,
+                SequenceExecInfo::LiteralCopy(0..literals.len()),
+            )],
+            recovered_bytes,
+        },
+        literals,
+        repeated_offset,
+        regen_size: regen_size as u64,
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SequencesProcessingResult<F> {
     pub offset: usize,
@@ -453,6 +597,7 @@ fn process_sequences<F: Field>(
     last_block: bool,
     randomness: Value<F>,
     mut repeated_offset: [usize; 3],
+    prev_fse_aux_tables: &[FseAuxiliaryTableData],
 ) -> SequencesProcessingResult<F> {
     // Initialize witness values
     let mut witness_rows: Vec<ZstdWitnessRow<F>> = vec![];
@@ -500,31 +645,37 @@ fn process_sequences<F: Field>(
 
     assert!(reserved == 0, "Reserved bits must be 0");
 
-    // Note: Only 2 modes of FSE encoding are accepted (instead of 4):
+    // All 4 modes of FSE encoding are accepted:
     // 0 - Predefined.
+    // 1 - RLE, a single symbol byte.
     // 2 - Variable bit packing.
-    assert!(
-        literal_lengths_mode == 2 || literal_lengths_mode == 0,
-        "Only FSE_Compressed_Mode or Predefined are allowed"
-    );
-    assert!(
-        offsets_mode == 2 || offsets_mode == 0,
-        "Only FSE_Compressed_Mode or Predefined are allowed"
-    );
-    assert!(
-        match_lengths_mode == 2 || match_lengths_mode == 0,
-        "Only FSE_Compressed_Mode or Predefined are allowed"
-    );
-    sequence_info.compression_mode = [
-        literal_lengths_mode > 0,
-        offsets_mode > 0,
-        match_lengths_mode > 0,
-    ];
+    // 3 - Repeat, the table of the previous block.
+    sequence_info.compression_mode = [literal_lengths_mode, offsets_mode, match_lengths_mode];
 
     let multiplier =
         (0..last_row.state.tag_len).fold(Value::known(F::one()), |acc, _| acc * randomness);
     let value_rlc = last_row.encoded_data.value_rlc * multiplier + last_row.state.tag_rlc;
-    let is_all_predefined_fse = literal_lengths_mode + offsets_mode + match_lengths_mode < 1;
+
+    // The tables described in RLE_Mode or FSE_Compressed_Mode have their own sections (in the
+    // order LLT, MOT, MLT) after the sequences header. The tag following a section (or the
+    // header) is the next such section if any, or else the sequences data.
+    let section_tag = |mode: u8| match mode {
+        1 => Some(ZstdTag::ZstdBlockSequenceRleCode),
+        2 => Some(ZstdTag::ZstdBlockSequenceFseCode),
+        _ => None,
+    };
+    let next_section_tag = |modes: &[u8]| {
+        modes
+            .iter()
+            .find_map(|&mode| section_tag(mode))
+            .unwrap_or(ZstdTag::ZstdBlockSequenceData)
+    };
+    let prev_fse_aux_table = |table_kind: FseTableKind| {
+        prev_fse_aux_tables
+            .iter()
+            .rev()
+            .find(|table| table.table_kind == table_kind)
+    };
 
     // Add witness rows for the sequence header
     let sequence_header_start_offset = byte_offset;
@@ -545,11 +696,7 @@ fn process_sequences<F: Field>(
         .map(|(i, (&value_byte, tag_rlc_acc))| ZstdWitnessRow {
             state: ZstdState {
                 tag: ZstdTag::ZstdBlockSequenceHeader,
-                tag_next: if is_all_predefined_fse {
-                    ZstdTag::ZstdBlockSequenceData
-                } else {
-                    ZstdTag::ZstdBlockSequenceFseCode
-                },
+                tag_next: next_section_tag(&sequence_info.compression_mode),
                 block_idx,
                 max_tag_len: ZstdTag::ZstdBlockSequenceHeader.max_len(),
                 tag_len: num_sequence_header_bytes as u64,
@@ -583,80 +730,63 @@ fn process_sequences<F: Field>(
     let fse_starting_byte_offset = byte_offset;
 
     // Literal Length Table (LLT)
-    let (n_fse_bytes_llt, bit_boundaries_llt, table_llt) = FseAuxiliaryTableData::reconstruct(
-        src,
-        block_idx,
-        FseTableKind::LLT,
-        byte_offset,
-        literal_lengths_mode < 2,
-    )
-    .expect("Reconstructing FSE-packed Literl Length (LL) table should not fail.");
+    let (n_fse_bytes_llt, bit_boundaries_llt, table_llt) =
+        FseAuxiliaryTableData::from_compression_mode(
+            src,
+            block_idx,
+            FseTableKind::LLT,
+            byte_offset,
+            literal_lengths_mode,
+            prev_fse_aux_table(FseTableKind::LLT),
+        )
+        .expect("Reconstructing FSE-packed Literl Length (LL) table should not fail.");
     let llt = table_llt.parse_state_table();
     // Determine the accuracy log of LLT
-    let al_llt = if literal_lengths_mode > 0 {
-        bit_boundaries_llt
-            .first()
-            .expect("Accuracy Log should exist")
-            .1
-            + 5
-    } else {
-        6
-    };
+    let al_llt = table_llt.table_size.trailing_zeros();
 
     // Cooked Match Offset Table (CMOT)
     let byte_offset = byte_offset + n_fse_bytes_llt;
-    let (n_fse_bytes_cmot, bit_boundaries_cmot, table_cmot) = FseAuxiliaryTableData::reconstruct(
-        src,
-        block_idx,
-        FseTableKind::MOT,
-        byte_offset,
-        offsets_mode < 2,
-    )
-    .expect("Reconstructing FSE-packed Cooked Match Offset (CMO) table should not fail.");
+    let (n_fse_bytes_cmot, bit_boundaries_cmot, table_cmot) =
+        FseAuxiliaryTableData::from_compression_mode(
+            src,
+            block_idx,
+            FseTableKind::MOT,
+            byte_offset,
+            offsets_mode,
+            prev_fse_aux_table(FseTableKind::MOT),
+        )
+        .expect("Reconstructing FSE-packed Cooked Match Offset (CMO) table should not fail.");
     let cmot = table_cmot.parse_state_table();
     // Determine the accuracy log of CMOT
-    let al_cmot = if offsets_mode > 0 {
-        bit_boundaries_cmot
-            .first()
-            .expect("Accuracy Log should exist")
-            .1
-            + 5
-    } else {
-        5
-    };
+    let al_cmot = table_cmot.table_size.trailing_zeros();
 
     // Match Length Table (MLT)
     let byte_offset = byte_offset + n_fse_bytes_cmot;
-    let (n_fse_bytes_mlt, bit_boundaries_mlt, table_mlt) = FseAuxiliaryTableData::reconstruct(
-        src,
-        block_idx,
-        FseTableKind::MLT,
-        byte_offset,
-        match_lengths_mode < 2,
-    )
-    .expect("Reconstructing FSE-packed Match Length (ML) table should not fail.");
+    let (n_fse_bytes_mlt, bit_boundaries_mlt, table_mlt) =
+        FseAuxiliaryTableData::from_compression_mode(
+            src,
+            block_idx,
+            FseTableKind::MLT,
+            byte_offset,
+            match_lengths_mode,
+            prev_fse_aux_table(FseTableKind::MLT),
+        )
+        .expect("Reconstructing FSE-packed Match Length (ML) table should not fail.");
     let mlt = table_mlt.parse_state_table();
     // Determine the accuracy log of MLT
-    let al_mlt = if match_lengths_mode > 0 {
-        bit_boundaries_mlt
-            .first()
-            .expect("Accuracy Log should exist")
-            .1
-            + 5
-    } else {
-        6
-    };
+    let al_mlt = table_mlt.table_size.trailing_zeros();
 
     // Add witness rows for the above three FSE tables
     let mut last_row = header_rows.last().cloned().unwrap();
-    for (start_offset, end_offset, bit_boundaries, tag_len, table, is_fse_section_end) in [
+    for (start_offset, end_offset, bit_boundaries, tag_len, table, mode, tag_next) in [
         (
             fse_starting_byte_offset,
             fse_starting_byte_offset + n_fse_bytes_llt,
             bit_boundaries_llt,
             n_fse_bytes_llt as u64,
             &table_llt,
-            offsets_mode + match_lengths_mode < 1,
+            literal_lengths_mode,
+            next_section_tag(&[offsets_mode, match_lengths_mode]),
         ),
         (
             fse_starting_byte_offset + n_fse_bytes_llt,
@@ -664,7 +794,8 @@ fn process_sequences<F: Field>(
             bit_boundaries_cmot,
             n_fse_bytes_cmot as u64,
             &table_cmot,
-            match_lengths_mode < 1,
+            offsets_mode,
+            next_section_tag(&[match_lengths_mode]),
         ),
         (
             fse_starting_byte_offset + n_fse_bytes_llt + n_fse_bytes_cmot,
@@ -672,10 +803,52 @@ fn process_sequences<F: Field>(
             bit_boundaries_mlt,
             n_fse_bytes_mlt as u64,
             &table_mlt,
-            true,
+            match_lengths_mode,
+            ZstdTag::ZstdBlockSequenceData,
         ),
     ] {
-        if end_offset > start_offset {
+        if mode == 1 {
+            // RLE_Mode: the section is a single byte, i.e. the symbol of a table with a single
+            // state (AL=0).
+            let value_byte = src[start_offset];
+            let multiplier =
+                (0..last_row.state.tag_len).fold(Value::known(F::one()), |acc, _| acc * randomness);
+            let value_rlc = last_row.encoded_data.value_rlc * multiplier + last_row.state.tag_rlc;
+            let tag_rlc = Value::known(F::from(value_byte as u64));
+
+            witness_rows.push(ZstdWitnessRow {
+                state: ZstdState {
+                    tag: ZstdTag::ZstdBlockSequenceRleCode,
+                    tag_next,
+                    block_idx,
+                    max_tag_len: ZstdTag::ZstdBlockSequenceRleCode.max_len(),
+                    tag_len,
+                    tag_idx: 1,
+                    is_tag_change: true,
+                    tag_rlc,
+                    tag_rlc_acc: tag_rlc,
+                },
+                encoded_data: EncodedData {
+                    byte_idx: (start_offset + 1) as u64,
+                    encoded_len,
+                    value_byte,
+                    value_rlc,
+                    reverse: false,
+                    ..Default::default()
+                },
+                decoded_data: DecodedData {
+                    decoded_len: last_row.decoded_data.decoded_len,
+                },
+                bitstream_read_data: BitstreamReadRow::default(),
+                fse_data: FseDecodingRow {
+                    table_kind: table.table_kind as u64,
+                    table_size: table.table_size,
+                    symbol: value_byte as u64,
+                    ..Default::default()
+                },
+            });
+            last_row = witness_rows.last().cloned().unwrap();
+        } else if end_offset > start_offset {
             let mut tag_rlc_iter =
                 src[start_offset..end_offset]
                     .iter()
@@ -899,11 +1072,7 @@ fn process_sequences<F: Field>(
                 witness_rows.push(ZstdWitnessRow {
                     state: ZstdState {
                         tag: ZstdTag::ZstdBlockSequenceFseCode,
-                        tag_next: if is_fse_section_end {
-                            ZstdTag::ZstdBlockSequenceData
-                        } else {
-                            ZstdTag::ZstdBlockSequenceFseCode
-                        },
+                        tag_next,
                         block_idx,
                         max_tag_len: ZstdTag::ZstdBlockSequenceFseCode.max_len(),
                         tag_len,
@@ -954,11 +1123,7 @@ fn process_sequences<F: Field>(
                     witness_rows.push(ZstdWitnessRow {
                         state: ZstdState {
                             tag: ZstdTag::ZstdBlockSequenceFseCode,
-                            tag_next: if is_fse_section_end {
-                                ZstdTag::ZstdBlockSequenceData
-                            } else {
-                                ZstdTag::ZstdBlockSequenceFseCode
-                            },
+                            tag_next,
                             block_idx,
                             max_tag_len: ZstdTag::ZstdBlockSequenceFseCode.max_len(),
                             tag_len,
@@ -1106,8 +1271,12 @@ fn process_sequences<F: Field>(
     while sequence_bitstream[current_bit_idx] == 0 {
         (current_byte_idx, current_bit_idx) = increment_idx(current_byte_idx, current_bit_idx);
     }
-    // Exclude the sentinel 1-bit
-    (current_byte_idx, current_bit_idx) = increment_idx(current_byte_idx, current_bit_idx);
+    // Exclude the sentinel 1-bit, unless the LLT state is initialised by reading 0 bits (RLE_Mode)
+    // in which case the sentinel bit is held until the next non-zero read.
+    let mut tail_holding_bit = al_llt == 0;
+    if !tail_holding_bit {
+        (current_byte_idx, current_bit_idx) = increment_idx(current_byte_idx, current_bit_idx);
+    }
 
     // Update accumulators
     if current_byte_idx > last_byte_idx {
@@ -1153,7 +1322,6 @@ fn process_sequences<F: Field>(
     let mut last_states: [u64; 3] = [0, 0, 0];
     let mut last_symbols: [u64; 3] = [0, 0, 0];
     let mut current_decoding_state;
-    let mut tail_holding_bit = false;
 
     while current_bit_idx + nb <= bitstream_end_bit_idx {
         let bitstring_value =
@@ -1432,6 +1600,12 @@ fn process_sequences<F: Field>(
             );
 
             raw_sequence_instructions.push(new_instruction);
+
+            // With tables that read 0 bits (RLE_Mode), the bitstream may not run out after the
+            // last sequence.
+            if raw_sequence_instructions.len() == sequence_info.num_sequences {
+                break;
+            }
         }
 
         let next_nb = if is_init {
@@ -1607,6 +1781,7 @@ pub struct LiteralsHeaderProcessingResult<F> {
     pub witness_rows: Vec<ZstdWitnessRow<F>>,
     pub regen_size: usize,
     pub compressed_size: usize,
+    pub literals_block_type: BlockType,
 }
 
 fn process_block_zstd_literals_header<F: Field>(
@@ -1628,13 +1803,13 @@ fn process_block_zstd_literals_header<F: Field>(
 
     let [n_bits_fmt, n_bits_regen, n_bits_compressed, _n_streams, n_bytes_header, _branch]: [usize;
         6] = match literals_block_type {
-        BlockType::RawBlock => match size_format {
+        BlockType::RawBlock | BlockType::RleBlock => match size_format {
             0b00 | 0b10 => [1, 5, 0, 1, 1, 0],
             0b01 => [2, 12, 0, 1, 2, 1],
             0b11 => [2, 20, 0, 1, 3, 2],
             _ => unreachable!("size_format out of bound"),
         },
-        _ => unreachable!("BlockType::* unexpected. Must be raw bytes for literals."),
+        _ => unreachable!("BlockType::* unexpected. Must be raw or rle bytes for literals."),
     };
 
     // Bits for representing regenerated_size and compressed_size
@@ -1648,7 +1823,8 @@ fn process_block_zstd_literals_header<F: Field>(
         le_bits_to_value(&sizing_bits[n_bits_regen..(n_bits_regen + n_bits_compressed)]) as usize;
     let tag_next = match literals_block_type {
         BlockType::RawBlock => ZstdTag::ZstdBlockLiteralsRawBytes,
-        _ => unreachable!("BlockType::* unexpected. Must be raw bytes for literals."),
+        BlockType::RleBlock => ZstdTag::ZstdBlockLiteralsRleBytes,
+        _ => unreachable!("BlockType::* unexpected. Must be raw or rle bytes for literals."),
     };
 
     let tag_rlc_iter =
@@ -1699,6 +1875,7 @@ fn process_block_zstd_literals_header<F: Field>(
             .collect::<Vec<_>>(),
         regen_size,
         compressed_size,
+        literals_block_type,
     }
 }
 
@@ -1714,8 +1891,14 @@ pub struct MultiBlockProcessResult<F> {
     pub sequence_exec_results: Vec<SequenceExecResult>,
 }
 
-/// Process a slice of bytes into decompression circuit witness rows
-pub fn process<F: Field>(src: &[u8], randomness: Value<F>) -> MultiBlockProcessResult<F> {
+/// Process a slice of bytes into decompression circuit witness rows.
+///
+/// Returns an error, instead of witness rows the circuit would not satisfy, if the frame is
+/// malformed or uses zstd features the decoder circuit does not constrain.
+pub fn process<F: Field>(
+    src: &[u8],
+    randomness: Value<F>,
+) -> std::io::Result<MultiBlockProcessResult<F>> {
     let mut witness_rows = vec![];
     let mut decoded_bytes: Vec<u8> = vec![];
     let mut literals: Vec<Vec<u64>> = vec![];
@@ -1725,6 +1908,17 @@ pub fn process<F: Field>(src: &[u8], randomness: Value<F>) -> MultiBlockProcessR
     let mut address_table_arr: Vec<Vec<AddressTableRow>> = vec![];
     let mut sequence_exec_info_arr: Vec<SequenceExecResult> = vec![];
 
+    // fail early, with the offending features, on frames the circuit cannot constrain
+    let unsupported = decode_frame(src)?.unsupported_features();
+    if !unsupported.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            format!(
+                "zstd frame uses features not supported by the decoder circuit: {unsupported:?}"
+            ),
+        ));
+    }
+
     // FrameHeaderDescriptor and FrameContentSize
     let (mut byte_offset, rows) = process_frame_header::<F>(
         src,
@@ -1755,6 +1949,7 @@ pub fn process<F: Field>(src: &[u8], randomness: Value<F>) -> MultiBlockProcessR
             witness_rows.last().expect("last row expected to exist"),
             randomness,
             repeated_offset,
+            &fse_aux_tables,
         );
         log::debug!("processed block={:?}: offset={:?}", block_idx, offset);
 
@@ -1779,7 +1974,7 @@ pub fn process<F: Field>(src: &[u8], randomness: Value<F>) -> MultiBlockProcessR
         }
     }
 
-    MultiBlockProcessResult {
+    Ok(MultiBlockProcessResult {
         witness_rows,
         literal_bytes: literals,
         fse_aux_tables,
@@ -1787,7 +1982,7 @@ pub fn process<F: Field>(src: &[u8], randomness: Value<F>) -> MultiBlockProcessR
         sequence_info_arr,
         address_table_rows: address_table_arr,
         sequence_exec_results: sequence_exec_info_arr,
-    }
+    })
 }
 
 #[cfg(test)]
@@ -1893,7 +2088,7 @@ mod tests {
                 sequence_info_arr: _s,
                 address_table_rows: _a,
                 sequence_exec_results,
-            } = process::<Fr>(&compressed, Value::known(Fr::from(123456789)));
+            } = process::<Fr>(&compressed, Value::known(Fr::from(123456789)))?;
 
             let decoded_bytes = sequence_exec_results
                 .into_iter()
@@ -1901,8 +2096,22 @@ mod tests {
                 .collect::<Vec<u8>>();
 
             assert!(raw_input_bytes == decoded_bytes);
+            assert!(decode_frame(&compressed)?.content == decoded_bytes);
         }
 
         Ok(())
     }
+
+    #[test]
+    fn test_zstd_witness_processing_unsupported_frame() {
+        use super::*;
+        use halo2_proofs::halo2curves::bn256::Fr;
+
+        // single rle block of "zzzzz", followed by a content checksum
+        let encoded = [0x24, 0x05, 0x2b, 0x00, 0x00, b'z', 0xde, 0xad, 0xbe, 0xef];
+        let err = process::<Fr>(&encoded, Value::known(Fr::from(123456789)))
+            .err()
+            .expect("frame with a checksum is not supported");
+        assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+    }
 }
@@ -24,3 +24,23 @@ pub fn init_zstd_encoder(
 ) -> zstd::stream::Encoder<'static, Vec<u8>> {
     init_zstd_encoder_n(target_block_size.unwrap_or(N_BLOCK_SIZE_TARGET))
 }
+
+/// Zstd encoder configuration with a tuned compression level and, optionally, window size.
+pub fn init_zstd_encoder_tuned(
+    target_block_size: Option<u32>,
+    level: i32,
+    window_log: Option<u32>,
+) -> zstd::stream::Encoder<'static, Vec<u8>> {
+    use zstd::stream::raw::CParameter;
+
+    let mut encoder = init_zstd_encoder(target_block_size);
+    encoder
+        .set_parameter(CParameter::CompressionLevel(level))
+        .expect("infallible");
+    if let Some(window_log) = window_log {
+        encoder
+            .set_parameter(CParameter::WindowLog(window_log))
+            .expect("infallible");
+    }
+    encoder
+}
@@ -0,0 +1,1013 @@
+//! A plain zstd decoder following RFC 8878, used as a reference for the witness generation and
+//! to report the features of a frame that the decoder circuit does not yet constrain.
+use std::{
+    collections::BTreeSet,
+    io::{Error, ErrorKind, Result},
+};
+
+/// Magic number that optionally prefixes a frame.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Maximum accuracy log of the FSE table describing huffman weights.
+const MAX_HUFFMAN_WEIGHTS_ACCURACY_LOG: u8 = 6;
+
+/// Maximum number of bits of a huffman code.
+const MAX_HUFFMAN_CODE_BITS: u8 = 11;
+
+fn invalid<T>(msg: &str) -> Result<T> {
+    Err(Error::new(ErrorKind::InvalidData, msg.to_string()))
+}
+
+/// The parts of the zstd format used by a frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ZstdFeature {
+    /// The frame header carries a window descriptor instead of the single segment flag.
+    MultiSegment,
+    /// The frame header carries a dictionary id.
+    DictionaryId,
+    /// The frame ends with a content checksum.
+    Checksum,
+    /// A block stored as is.
+    RawBlock,
+    /// A block made of a single repeated byte.
+    RleBlock,
+    /// A block with a literals and a sequences section.
+    CompressedBlock,
+    /// Literals stored as is.
+    RawLiterals,
+    /// Literals made of a single repeated byte.
+    RleLiterals,
+    /// Huffman coded literals in a single stream.
+    HuffmanLiterals1Stream,
+    /// Huffman coded literals in four streams.
+    HuffmanLiterals4Streams,
+    /// Huffman coded literals reusing the table of a previous block.
+    TreelessLiterals,
+    /// Huffman weights described by an FSE table.
+    FseHuffmanWeights,
+    /// Huffman weights stored as 4-bit values.
+    DirectHuffmanWeights,
+    /// A sequence table using the predefined distribution.
+    PredefinedMode,
+    /// A sequence table with a single symbol.
+    RleMode,
+    /// A sequence table described in the block.
+    FseCompressedMode,
+    /// A sequence table reused from a previous block.
+    RepeatMode,
+}
+
+impl ZstdFeature {
+    /// Whether the decoder circuit constrains frames using this feature.
+    pub fn supported_by_circuit(&self) -> bool {
+        matches!(
+            self,
+            Self::RawBlock
+                | Self::RleBlock
+                | Self::CompressedBlock
+                | Self::RawLiterals
+                | Self::RleLiterals
+                | Self::PredefinedMode
+                | Self::RleMode
+                | Self::FseCompressedMode
+                | Self::RepeatMode
+        )
+    }
+}
+
+/// Output of [`decode_frame`].
+#[derive(Clone, Debug, Default)]
+pub struct DecodedFrame {
+    /// The decompressed content.
+    pub content: Vec<u8>,
+    /// Number of bytes of the frame, including the optional magic number and checksum.
+    pub frame_len: usize,
+    /// The frame content size declared in the header, if any.
+    pub frame_content_size: Option<u64>,
+    /// The features used by the frame.
+    pub features: BTreeSet<ZstdFeature>,
+}
+
+impl DecodedFrame {
+    /// The features used by the frame that the decoder circuit does not constrain.
+    pub fn unsupported_features(&self) -> Vec<ZstdFeature> {
+        self.features
+            .iter()
+            .copied()
+            .filter(|feature| !feature.supported_by_circuit())
+            .collect()
+    }
+}
+
+/// Reads a bitstream from its last byte backwards, as used for huffman and FSE streams. Bits
+/// read past the beginning of the stream are zero.
+struct BackwardBitReader<'a> {
+    data: &'a [u8],
+    /// Number of bits left to read, negative once the reader overflowed.
+    pos: isize,
+}
+
+impl<'a> BackwardBitReader<'a> {
+    fn new(data: &'a [u8]) -> Result<Self> {
+        let Some(&last) = data.last() else {
+            return invalid("empty backward bitstream");
+        };
+        if last == 0 {
+            return invalid("backward bitstream without a start marker");
+        }
+        let marker = 7 - last.leading_zeros() as isize;
+        Ok(Self {
+            data,
+            pos: (data.len() as isize - 1) * 8 + marker,
+        })
+    }
+
+    fn bit(&self, idx: isize) -> u64 {
+        if idx < 0 {
+            0
+        } else {
+            ((self.data[(idx / 8) as usize] >> (idx % 8)) & 1) as u64
+        }
+    }
+
+    fn peek(&self, n: u8) -> u64 {
+        (0..n as isize).fold(0, |acc, k| (acc << 1) | self.bit(self.pos - 1 - k))
+    }
+
+    fn read(&mut self, n: u8) -> u64 {
+        let value = self.peek(n);
+        self.pos -= n as isize;
+        value
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos == 0
+    }
+
+    fn overflowed(&self) -> bool {
+        self.pos < 0
+    }
+}
+
+/// Reads a bitstream from its first byte forwards, as used for FSE table descriptions.
+struct ForwardBitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ForwardBitReader<'a> {
+    /// Bits past the end of the data are zero, the caller checks [`Self::bytes_read`].
+    fn peek(&self, n: u8) -> u64 {
+        (0..n as usize).fold(0, |acc, k| {
+            let idx = self.pos + k;
+            let bit = self
+                .data
+                .get(idx / 8)
+                .map_or(0, |byte| (byte >> (idx % 8)) & 1);
+            acc | ((bit as u64) << k)
+        })
+    }
+
+    fn skip(&mut self, n: u8) {
+        self.pos += n as usize;
+    }
+
+    fn read(&mut self, n: u8) -> u64 {
+        let value = self.peek(n);
+        self.skip(n);
+        value
+    }
+
+    fn bytes_read(&self) -> usize {
+        (self.pos + 7) / 8
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct FseEntry {
+    symbol: u8,
+    num_bits: u8,
+    baseline: u16,
+}
+
+/// An FSE decoding table. A table with accuracy log 0 always decodes the same symbol.
+#[derive(Clone, Debug)]
+struct FseTable {
+    accuracy_log: u8,
+    entries: Vec<FseEntry>,
+}
+
+impl FseTable {
+    fn rle(symbol: u8) -> Self {
+        Self {
+            accuracy_log: 0,
+            entries: vec![FseEntry {
+                symbol,
+                ..Default::default()
+            }],
+        }
+    }
+
+    /// Build the decoding table from normalised probabilities, -1 standing for "less than 1".
+    fn from_distribution(accuracy_log: u8, probs: &[i16]) -> Result<Self> {
+        let table_size = 1usize << accuracy_log;
+        let total: usize = probs.iter().map(|&p| p.unsigned_abs() as usize).sum();
+        if total != table_size {
+            return invalid("FSE probabilities do not sum up to the table size");
+        }
+
+        let mut entries = vec![FseEntry::default(); table_size];
+        let mut high_threshold = table_size - 1;
+        for (symbol, &prob) in probs.iter().enumerate() {
+            if prob == -1 {
+                entries[high_threshold].symbol = symbol as u8;
+                high_threshold = high_threshold.wrapping_sub(1);
+            }
+        }
+
+        let step = (table_size >> 1) + (table_size >> 3) + 3;
+        let mask = table_size - 1;
+        let mut pos = 0;
+        for (symbol, &prob) in probs.iter().enumerate() {
+            for _ in 0..prob.max(0) {
+                entries[pos].symbol = symbol as u8;
+                pos = (pos + step) & mask;
+                while pos > high_threshold {
+                    pos = (pos + step) & mask;
+                }
+            }
+        }
+        if pos != 0 {
+            return invalid("FSE symbol spreading did not end at state 0");
+        }
+
+        let mut next_state: Vec<usize> = probs
+            .iter()
+            .map(|&p| if p == -1 { 1 } else { p.max(0) as usize })
+            .collect();
+        for entry in entries.iter_mut() {
+            let state = next_state[entry.symbol as usize];
+            next_state[entry.symbol as usize] += 1;
+            let num_bits = accuracy_log - (usize::BITS - 1 - state.leading_zeros()) as u8;
+            entry.num_bits = num_bits;
+            entry.baseline = ((state << num_bits) - table_size) as u16;
+        }
+
+        Ok(Self {
+            accuracy_log,
+            entries,
+        })
+    }
+
+    /// Parse an FSE table description. Returns the table and the number of bytes read.
+    fn decode_description(
+        src: &[u8],
+        max_accuracy_log: u8,
+        max_symbol: u8,
+    ) -> Result<(Self, usize)> {
+        let mut reader = ForwardBitReader { data: src, pos: 0 };
+        let accuracy_log = reader.read(4) as u8 + 5;
+        if accuracy_log > max_accuracy_log {
+            return invalid("FSE accuracy log too large");
+        }
+
+        let mut probs: Vec<i16> = vec![];
+        let mut remaining = (1i32 << accuracy_log) + 1;
+        let mut threshold = 1i32 << accuracy_log;
+        let mut num_bits = accuracy_log + 1;
+        while remaining > 1 {
+            if probs.len() > max_symbol as usize {
+                return invalid("FSE table description has too many symbols");
+            }
+            let max = 2 * threshold - 1 - remaining;
+            let bits = reader.peek(num_bits) as i32;
+            let value = if (bits & (threshold - 1)) < max {
+                reader.skip(num_bits - 1);
+                bits & (threshold - 1)
+            } else {
+                reader.skip(num_bits);
+                let value = bits & (2 * threshold - 1);
+                if value >= threshold {
+                    value - max
+                } else {
+                    value
+                }
+            };
+            let prob = value - 1;
+            remaining -= prob.abs();
+            probs.push(prob as i16);
+
+            if prob == 0 {
+                loop {
+                    let repeat = reader.read(2);
+                    probs.extend(std::iter::repeat(0).take(repeat as usize));
+                    if repeat != 3 {
+                        break;
+                    }
+                }
+            }
+            while remaining < threshold && num_bits > 1 {
+                num_bits -= 1;
+                threshold >>= 1;
+            }
+        }
+        if remaining != 1
+            || probs.len() > max_symbol as usize + 1
+            || reader.bytes_read() > src.len()
+        {
+            return invalid("invalid FSE table description");
+        }
+
+        Ok((
+            Self::from_distribution(accuracy_log, &probs)?,
+            reader.bytes_read(),
+        ))
+    }
+
+    fn init_state(&self, reader: &mut BackwardBitReader) -> usize {
+        reader.read(self.accuracy_log) as usize
+    }
+
+    fn symbol(&self, state: usize) -> u8 {
+        self.entries[state].symbol
+    }
+
+    fn update_state(&self, state: usize, reader: &mut BackwardBitReader) -> usize {
+        let entry = self.entries[state];
+        entry.baseline as usize + reader.read(entry.num_bits) as usize
+    }
+}
+
+/// A huffman decoding table, indexed by the next `max_bits` bits of the stream.
+#[derive(Clone, Debug)]
+struct HuffmanTable {
+    max_bits: u8,
+    /// (symbol, number of bits) per index.
+    entries: Vec<(u8, u8)>,
+}
+
+impl HuffmanTable {
+    /// Parse a huffman tree description. Returns the table and the number of bytes read.
+    fn decode_description(
+        src: &[u8],
+        features: &mut BTreeSet<ZstdFeature>,
+    ) -> Result<(Self, usize)> {
+        let Some(&header) = src.first() else {
+            return invalid("missing huffman tree description");
+        };
+        let (mut weights, len) = if header < 128 {
+            features.insert(ZstdFeature::FseHuffmanWeights);
+            let len = 1 + header as usize;
+            let Some(data) = src.get(1..len) else {
+                return invalid("truncated huffman weights");
+            };
+            let (table, n) =
+                FseTable::decode_description(data, MAX_HUFFMAN_WEIGHTS_ACCURACY_LOG, 255)?;
+            let Some(stream) = data.get(n..) else {
+                return invalid("truncated huffman weights");
+            };
+            let mut reader = BackwardBitReader::new(stream)?;
+            let mut state1 = table.init_state(&mut reader);
+            let mut state2 = table.init_state(&mut reader);
+            let mut weights = vec![];
+            loop {
+                weights.push(table.symbol(state1));
+                state1 = table.update_state(state1, &mut reader);
+                if reader.overflowed() {
+                    weights.push(table.symbol(state2));
+                    break;
+                }
+                weights.push(table.symbol(state2));
+                state2 = table.update_state(state2, &mut reader);
+                if reader.overflowed() {
+                    weights.push(table.symbol(state1));
+                    break;
+                }
+                if weights.len() > 255 {
+                    return invalid("too many huffman weights");
+                }
+            }
+            (weights, len)
+        } else {
+            features.insert(ZstdFeature::DirectHuffmanWeights);
+            let num_weights = header as usize - 127;
+            let len = 1 + (num_weights + 1) / 2;
+            let Some(data) = src.get(1..len) else {
+                return invalid("truncated huffman weights");
+            };
+            let weights = (0..num_weights)
+                .map(|i| {
+                    let byte = data[i / 2];
+                    if i % 2 == 0 {
+                        byte >> 4
+                    } else {
+                        byte & 0xf
+                    }
+                })
+                .collect();
+            (weights, len)
+        };
+
+        if weights.iter().any(|&w| w > MAX_HUFFMAN_CODE_BITS) {
+            return invalid("huffman weight too large");
+        }
+        // the weight of the last symbol is implied by the others
+        let sum: u64 = weights
+            .iter()
+            .filter(|&&w| w > 0)
+            .map(|&w| 1u64 << (w - 1))
+            .sum();
+        if sum == 0 {
+            return invalid("huffman weights are all zero");
+        }
+        let max_bits = (64 - sum.leading_zeros()) as u8;
+        let left = (1u64 << max_bits) - sum;
+        if !left.is_power_of_two() || max_bits > MAX_HUFFMAN_CODE_BITS {
+            return invalid("invalid huffman weights");
+        }
+        weights.push(left.trailing_zeros() as u8 + 1);
+
+        let mut rank_start = vec![0usize; max_bits as usize + 2];
+        let mut next = 0;
+        for weight in 1..=max_bits {
+            rank_start[weight as usize] = next;
+            next += weights.iter().filter(|&&w| w == weight).count() << (weight - 1);
+        }
+        let mut entries = vec![(0, 0); 1 << max_bits];
+        for (symbol, &weight) in weights.iter().enumerate() {
+            if weight == 0 {
+                continue;
+            }
+            let start = rank_start[weight as usize];
+            let n = 1 << (weight - 1);
+            for entry in entries[start..start + n].iter_mut() {
+                *entry = (symbol as u8, max_bits + 1 - weight);
+            }
+            rank_start[weight as usize] += n;
+        }
+
+        Ok((Self { max_bits, entries }, len))
+    }
+
+    fn decode_stream(&self, stream: &[u8], n: usize, out: &mut Vec<u8>) -> Result<()> {
+        let mut reader = BackwardBitReader::new(stream)?;
+        for _ in 0..n {
+            let (symbol, num_bits) = self.entries[reader.peek(self.max_bits) as usize];
+            reader.read(num_bits);
+            out.push(symbol);
+        }
+        if !reader.is_empty() {
+            return invalid("huffman stream not fully consumed");
+        }
+        Ok(())
+    }
+}
+
+/// Baselines and number of extra bits of the literal length codes.
+#[rustfmt::skip]
+const LL_CODES: [(u32, u8); 36] = [
+    (0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (5, 0), (6, 0), (7, 0),
+    (8, 0), (9, 0), (10, 0), (11, 0), (12, 0), (13, 0), (14, 0), (15, 0),
+    (16, 1), (18, 1), (20, 1), (22, 1), (24, 2), (28, 2), (32, 3), (40, 3),
+    (48, 4), (64, 6), (128, 7), (256, 8), (512, 9), (1024, 10), (2048, 11), (4096, 12),
+    (8192, 13), (16384, 14), (32768, 15), (65536, 16),
+];
+
+/// Baselines and number of extra bits of the match length codes.
+#[rustfmt::skip]
+const ML_CODES: [(u32, u8); 53] = [
+    (3, 0), (4, 0), (5, 0), (6, 0), (7, 0), (8, 0), (9, 0), (10, 0),
+    (11, 0), (12, 0), (13, 0), (14, 0), (15, 0), (16, 0), (17, 0), (18, 0),
+    (19, 0), (20, 0), (21, 0), (22, 0), (23, 0), (24, 0), (25, 0), (26, 0),
+    (27, 0), (28, 0), (29, 0), (30, 0), (31, 0), (32, 0), (33, 0), (34, 0),
+    (35, 1), (37, 1), (39, 1), (41, 1), (43, 2), (47, 2), (51, 3), (59, 3),
+    (67, 4), (83, 4), (99, 5), (131, 7), (259, 8), (515, 9), (1027, 10), (2051, 11),
+    (4099, 12), (8195, 13), (16387, 14), (32771, 15), (65539, 16),
+];
+
+const LL_PREDEFINED: [i16; 36] = [
+    4, 3, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2, 2, 3, 2, 1, 1, 1, 1, 1,
+    -1, -1, -1, -1,
+];
+
+const ML_PREDEFINED: [i16; 53] = [
+    1, 4, 3, 2, 2, 2, 2, 2, 2, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, -1, -1, -1, -1, -1, -1, -1,
+];
+
+const OF_PREDEFINED: [i16; 29] = [
+    1, 1, 1, 1, 1, 1, 2, 2, 2, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, -1, -1, -1, -1, -1,
+];
+
+/// The sequence table kinds, in the order of the compression modes byte.
+#[derive(Clone, Copy, Debug)]
+enum SequenceTable {
+    LiteralLength,
+    Offset,
+    MatchLength,
+}
+
+impl SequenceTable {
+    fn mode_shift(&self) -> u8 {
+        match self {
+            Self::LiteralLength => 6,
+            Self::Offset => 4,
+            Self::MatchLength => 2,
+        }
+    }
+
+    fn max_accuracy_log(&self) -> u8 {
+        match self {
+            Self::LiteralLength | Self::MatchLength => 9,
+            Self::Offset => 8,
+        }
+    }
+
+    fn max_symbol(&self) -> u8 {
+        match self {
+            Self::LiteralLength => 35,
+            Self::Offset => 31,
+            Self::MatchLength => 52,
+        }
+    }
+
+    fn predefined(&self) -> Result<FseTable> {
+        match self {
+            Self::LiteralLength => FseTable::from_distribution(6, &LL_PREDEFINED),
+            Self::Offset => FseTable::from_distribution(5, &OF_PREDEFINED),
+            Self::MatchLength => FseTable::from_distribution(6, &ML_PREDEFINED),
+        }
+    }
+}
+
+/// State carried from one block of a frame to the next.
+#[derive(Default)]
+struct FrameState {
+    huffman: Option<HuffmanTable>,
+    /// Sequence tables in the order literal length, offset, match length.
+    tables: [Option<FseTable>; 3],
+    repeat_offsets: [usize; 3],
+}
+
+/// Decode a single zstd frame, with or without the magic number.
+pub fn decode_frame(src: &[u8]) -> Result<DecodedFrame> {
+    let mut features = BTreeSet::new();
+    let mut offset = if src.starts_with(&ZSTD_MAGIC) { 4 } else { 0 };
+
+    let Some(&fhd) = src.get(offset) else {
+        return invalid("missing frame header descriptor");
+    };
+    offset += 1;
+    if fhd & 0b1000 != 0 {
+        return invalid("reserved bit set in frame header descriptor");
+    }
+    let fcs_flag = fhd >> 6;
+    let single_segment = (fhd >> 5) & 1 == 1;
+    let has_checksum = (fhd >> 2) & 1 == 1;
+    let dict_id_len = [0, 1, 2, 4][(fhd & 3) as usize];
+
+    if !single_segment {
+        features.insert(ZstdFeature::MultiSegment);
+        offset += 1;
+    }
+    if dict_id_len > 0 {
+        features.insert(ZstdFeature::DictionaryId);
+        offset += dict_id_len;
+    }
+    let fcs_len = match fcs_flag {
+        0 => single_segment as usize,
+        1 => 2,
+        2 => 4,
+        _ => 8,
+    };
+    let frame_content_size = match src.get(offset..offset + fcs_len) {
+        None => return invalid("truncated frame header"),
+        Some([]) => None,
+        Some(bytes) => {
+            let value = bytes
+                .iter()
+                .rev()
+                .fold(0u64, |acc, &byte| (acc << 8) | byte as u64);
+            Some(if fcs_len == 2 { value + 256 } else { value })
+        }
+    };
+    offset += fcs_len;
+
+    let mut state = FrameState {
+        repeat_offsets: [1, 4, 8],
+        ..Default::default()
+    };
+    let mut content = vec![];
+    loop {
+        let Some(header) = src.get(offset..offset + 3) else {
+            return invalid("truncated block header");
+        };
+        offset += 3;
+        let header = header[0] as usize | (header[1] as usize) << 8 | (header[2] as usize) << 16;
+        let last_block = header & 1 == 1;
+        let block_size = header >> 3;
+        match (header >> 1) & 3 {
+            0 => {
+                features.insert(ZstdFeature::RawBlock);
+                let Some(block) = src.get(offset..offset + block_size) else {
+                    return invalid("truncated raw block");
+                };
+                content.extend_from_slice(block);
+                offset += block_size;
+            }
+            1 => {
+                features.insert(ZstdFeature::RleBlock);
+                let Some(&byte) = src.get(offset) else {
+                    return invalid("truncated rle block");
+                };
+                content.extend(std::iter::repeat(byte).take(block_size));
+                offset += 1;
+            }
+            2 => {
+                features.insert(ZstdFeature::CompressedBlock);
+                let Some(block) = src.get(offset..offset + block_size) else {
+                    return invalid("truncated compressed block");
+                };
+                decode_compressed_block(block, &mut state, &mut content, &mut features)?;
+                offset += block_size;
+            }
+            _ => return invalid("reserved block type"),
+        }
+        if last_block {
+            break;
+        }
+    }
+
+    if has_checksum {
+        features.insert(ZstdFeature::Checksum);
+        if src.len() < offset + 4 {
+            return invalid("truncated content checksum");
+        }
+        offset += 4;
+    }
+    if let Some(fcs) = frame_content_size {
+        if fcs != content.len() as u64 {
+            return invalid("frame content size mismatch");
+        }
+    }
+
+    Ok(DecodedFrame {
+        content,
+        frame_len: offset,
+        frame_content_size,
+        features,
+    })
+}
+
+fn decode_compressed_block(
+    block: &[u8],
+    state: &mut FrameState,
+    content: &mut Vec<u8>,
+    features: &mut BTreeSet<ZstdFeature>,
+) -> Result<()> {
+    let (literals, offset) = decode_literals_section(block, state, features)?;
+    decode_sequences_section(&block[offset..], &literals, state, content, features)
+}
+
+/// Decode the literals section of a block. Returns the literals and the section length.
+fn decode_literals_section(
+    block: &[u8],
+    state: &mut FrameState,
+    features: &mut BTreeSet<ZstdFeature>,
+) -> Result<(Vec<u8>, usize)> {
+    let byte = |i: usize| -> Result<usize> {
+        match block.get(i) {
+            Some(&b) => Ok(b as usize),
+            None => invalid("truncated literals section header"),
+        }
+    };
+    let b0 = byte(0)?;
+    let block_type = b0 & 3;
+    let size_format = (b0 >> 2) & 3;
+
+    if block_type < 2 {
+        let (header_len, regen_size) = match size_format {
+            0 | 2 => (1, b0 >> 3),
+            1 => (2, (b0 >> 4) + (byte(1)? << 4)),
+            _ => (3, (b0 >> 4) + (byte(1)? << 4) + (byte(2)? << 12)),
+        };
+        return if block_type == 0 {
+            features.insert(ZstdFeature::RawLiterals);
+            match block.get(header_len..header_len + regen_size) {
+                Some(literals) => Ok((literals.to_vec(), header_len + regen_size)),
+                None => invalid("truncated raw literals"),
+            }
+        } else {
+            features.insert(ZstdFeature::RleLiterals);
+            Ok((vec![byte(header_len)? as u8; regen_size], header_len + 1))
+        };
+    }
+
+    let (header_len, num_streams, regen_size, compressed_size) = match size_format {
+        0 | 1 => {
+            let h = b0 | byte(1)? << 8 | byte(2)? << 16;
+            let num_streams = if size_format == 0 { 1 } else { 4 };
+            (3, num_streams, (h >> 4) & 0x3ff, (h >> 14) & 0x3ff)
+        }
+        2 => {
+            let h = b0 | byte(1)? << 8 | byte(2)? << 16 | byte(3)? << 24;
+            (4, 4, (h >> 4) & 0x3fff, h >> 18)
+        }
+        _ => {
+            let h = b0 | byte(1)? << 8 | byte(2)? << 16 | byte(3)? << 24 | byte(4)? << 32;
+            (5, 4, (h >> 4) & 0x3ffff, (h >> 22) & 0x3ffff)
+        }
+    };
+    let Some(mut data) = block.get(header_len..header_len + compressed_size) else {
+        return invalid("truncated compressed literals");
+    };
+
+    if block_type == 2 {
+        let (table, n) = HuffmanTable::decode_description(data, features)?;
+        state.huffman = Some(table);
+        data = &data[n..];
+    } else {
+        features.insert(ZstdFeature::TreelessLiterals);
+    }
+    let Some(table) = state.huffman.as_ref() else {
+        return invalid("treeless literals without a previous huffman table");
+    };
+
+    let mut literals = Vec::with_capacity(regen_size);
+    if num_streams == 1 {
+        features.insert(ZstdFeature::HuffmanLiterals1Stream);
+        table.decode_stream(data, regen_size, &mut literals)?;
+    } else {
+        features.insert(ZstdFeature::HuffmanLiterals4Streams);
+        if data.len() < 6 {
+            return invalid("truncated huffman jump table");
+        }
+        let mut sizes = [0usize; 4];
+        for (i, size) in sizes.iter_mut().take(3).enumerate() {
+            *size = data[2 * i] as usize | (data[2 * i + 1] as usize) << 8;
+        }
+        let streams_len = data.len() - 6;
+        if sizes[..3].iter().sum::<usize>() > streams_len {
+            return invalid("huffman jump table exceeds the literals");
+        }
+        sizes[3] = streams_len - sizes[..3].iter().sum::<usize>();
+        let segment = (regen_size + 3) / 4;
+        if segment * 3 > regen_size {
+            return invalid("too few literals for four huffman streams");
+        }
+        let mut start = 6;
+        for (i, size) in sizes.into_iter().enumerate() {
+            let n = if i < 3 {
+                segment
+            } else {
+                regen_size - 3 * segment
+            };
+            table.decode_stream(&data[start..start + size], n, &mut literals)?;
+            start += size;
+        }
+    }
+
+    Ok((literals, header_len + compressed_size))
+}
+
+fn decode_sequences_section(
+    section: &[u8],
+    literals: &[u8],
+    state: &mut FrameState,
+    content: &mut Vec<u8>,
+    features: &mut BTreeSet<ZstdFeature>,
+) -> Result<()> {
+    let byte = |i: usize| -> Result<usize> {
+        match section.get(i) {
+            Some(&b) => Ok(b as usize),
+            None => invalid("truncated sequences section header"),
+        }
+    };
+    let b0 = byte(0)?;
+    let (num_sequences, mut offset) = match b0 {
+        0 => (0, 1),
+        1..=127 => (b0, 1),
+        128..=254 => (((b0 - 128) << 8) + byte(1)?, 2),
+        _ => (byte(1)? + (byte(2)? << 8) + 0x7f00, 3),
+    };
+    if num_sequences == 0 {
+        if offset != section.len() {
+            return invalid("trailing bytes after an empty sequences section");
+        }
+        content.extend_from_slice(literals);
+        return Ok(());
+    }
+
+    let modes = byte(offset)?;
+    offset += 1;
+    if modes & 3 != 0 {
+        return invalid("reserved bits set in sequence compression modes");
+    }
+    for (i, kind) in [
+        SequenceTable::LiteralLength,
+        SequenceTable::Offset,
+        SequenceTable::MatchLength,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        match (modes >> kind.mode_shift()) & 3 {
+            0 => {
+                features.insert(ZstdFeature::PredefinedMode);
+                state.tables[i] = Some(kind.predefined()?);
+            }
+            1 => {
+                features.insert(ZstdFeature::RleMode);
+                let symbol = byte(offset)? as u8;
+                if symbol > kind.max_symbol() {
+                    return invalid("rle sequence symbol out of range");
+                }
+                state.tables[i] = Some(FseTable::rle(symbol));
+                offset += 1;
+            }
+            2 => {
+                features.insert(ZstdFeature::FseCompressedMode);
+                let (table, n) = FseTable::decode_description(
+                    &section[offset..],
+                    kind.max_accuracy_log(),
+                    kind.max_symbol(),
+                )?;
+                state.tables[i] = Some(table);
+                offset += n;
+            }
+            _ => {
+                features.insert(ZstdFeature::RepeatMode);
+                if state.tables[i].is_none() {
+                    return invalid("repeat mode without a previous table");
+                }
+            }
+        }
+    }
+    let [Some(ll_table), Some(of_table), Some(ml_table)] = &state.tables else {
+        unreachable!("all sequence tables are set above");
+    };
+
+    let mut reader = BackwardBitReader::new(&section[offset..])?;
+    let mut ll_state = ll_table.init_state(&mut reader);
+    let mut of_state = of_table.init_state(&mut reader);
+    let mut ml_state = ml_table.init_state(&mut reader);
+    let mut literals = literals.iter();
+    let repeat_offsets = &mut state.repeat_offsets;
+
+    for i in 0..num_sequences {
+        let of_code = of_table.symbol(of_state);
+        let ml_code = ml_table.symbol(ml_state) as usize;
+        let ll_code = ll_table.symbol(ll_state) as usize;
+        if of_code > 31 || ml_code >= ML_CODES.len() || ll_code >= LL_CODES.len() {
+            return invalid("sequence code out of range");
+        }
+
+        let offset_value = (1usize << of_code) + reader.read(of_code) as usize;
+        let (ml_base, ml_bits) = ML_CODES[ml_code];
+        let match_length = ml_base as usize + reader.read(ml_bits) as usize;
+        let (ll_base, ll_bits) = LL_CODES[ll_code];
+        let literal_length = ll_base as usize + reader.read(ll_bits) as usize;
+
+        let match_offset = if offset_value > 3 {
+            let match_offset = offset_value - 3;
+            *repeat_offsets = [match_offset, repeat_offsets[0], repeat_offsets[1]];
+            match_offset
+        } else {
+            let idx = offset_value - 1 + (literal_length == 0) as usize;
+            let match_offset = if idx == 3 {
+                repeat_offsets[0] - 1
+            } else {
+                repeat_offsets[idx]
+            };
+            match idx {
+                0 => {}
+                1 => repeat_offsets.swap(0, 1),
+                _ => *repeat_offsets = [match_offset, repeat_offsets[0], repeat_offsets[1]],
+            }
+            match_offset
+        };
+
+        if literals.len() < literal_length {
+            return invalid("sequence consumes more literals than available");
+        }
+        content.extend(literals.by_ref().take(literal_length));
+        if match_offset == 0 || match_offset > content.len() {
+            return invalid("match offset out of range");
+        }
+        let start = content.len() - match_offset;
+        for j in 0..match_length {
+            content.push(content[start + j]);
+        }
+
+        if i + 1 < num_sequences {
+            ll_state = ll_table.update_state(ll_state, &mut reader);
+            ml_state = ml_table.update_state(ml_state, &mut reader);
+            of_state = of_table.update_state(of_state, &mut reader);
+        }
+    }
+    if !reader.is_empty() {
+        return invalid("sequences bitstream not fully consumed");
+    }
+    content.extend(literals);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::witgen::init_zstd_encoder_tuned;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use std::io::Write;
+    use zstd_encoder::zstd;
+
+    /// Inputs mixing random bytes, runs and repeated chunks, so that encoders emit raw, rle and
+    /// compressed blocks with every kind of literals and sequence table.
+    fn random_input(rng: &mut StdRng) -> Vec<u8> {
+        let len = rng.gen_range(1..300_000);
+        let alphabet = rng.gen_range(2..=256u32);
+        let mut data = Vec::with_capacity(len);
+        while data.len() < len {
+            match rng.gen_range(0..4) {
+                0 => {
+                    let n = rng.gen_range(1..2000);
+                    data.extend((0..n).map(|_| rng.gen_range(0..alphabet) as u8));
+                }
+                1 => {
+                    let n = rng.gen_range(1..5000);
+                    data.extend(std::iter::repeat(rng.gen::<u8>()).take(n));
+                }
+                _ if !data.is_empty() => {
+                    let start = rng.gen_range(0..data.len());
+                    let n = rng.gen_range(1..=(data.len() - start).min(3000));
+                    data.extend_from_within(start..start + n);
+                }
+                _ => data.push(rng.gen()),
+            }
+        }
+        data.truncate(len);
+        data
+    }
+
+    #[test]
+    fn reference_decoder_matches_zstd() -> Result<()> {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let mut all_features = BTreeSet::new();
+        let mut inputs: Vec<Vec<u8>> = (0..40).map(|_| random_input(&mut rng)).collect();
+        // a single run and incompressible bytes, spanning several blocks
+        inputs.push(vec![0; 300_000]);
+        inputs.push((0..300_000).map(|_| rng.gen()).collect());
+        for data in inputs {
+            let level = rng.gen_range(1..=19);
+
+            // the circuit's own encoder configuration
+            let mut encoder = init_zstd_encoder_tuned(None, level, None);
+            encoder.set_pledged_src_size(Some(data.len() as u64))?;
+            encoder.write_all(&data)?;
+            let encoded = encoder.finish()?;
+            let decoded = decode_frame(&encoded)?;
+            assert_eq!(decoded.content, data);
+            assert_eq!(decoded.frame_len, encoded.len());
+            all_features.extend(decoded.features);
+
+            // a stock encoder, with compressed literals and the magic number
+            let encoded = zstd::stream::encode_all(data.as_slice(), level)?;
+            let decoded = decode_frame(&encoded)?;
+            assert_eq!(decoded.content, data);
+            assert_eq!(decoded.frame_len, encoded.len());
+            all_features.extend(decoded.features);
+        }
+
+        for feature in [
+            ZstdFeature::RawBlock,
+            ZstdFeature::RleBlock,
+            ZstdFeature::CompressedBlock,
+            ZstdFeature::RawLiterals,
+            ZstdFeature::HuffmanLiterals4Streams,
+            ZstdFeature::PredefinedMode,
+            ZstdFeature::FseCompressedMode,
+        ] {
+            assert!(all_features.contains(&feature), "{feature:?} not covered");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_features() -> Result<()> {
+        // single segment frame of 5 bytes, with one compressed block of rle literals "zzzzz"
+        let encoded = [0x20, 0x05, 0x1d, 0x00, 0x00, 0x29, b'z', 0x00];
+        let decoded = decode_frame(&encoded)?;
+        assert_eq!(decoded.content, b"zzzzz");
+        assert!(decoded.features.contains(&ZstdFeature::RleLiterals));
+        assert_eq!(decoded.unsupported_features(), vec![]);
+
+        // the same content as a single rle block, with a content checksum
+        let encoded = [0x24, 0x05, 0x2b, 0x00, 0x00, b'z', 0xde, 0xad, 0xbe, 0xef];
+        let decoded = decode_frame(&encoded)?;
+        assert_eq!(decoded.content, b"zzzzz");
+        assert_eq!(decoded.unsupported_features(), vec![ZstdFeature::Checksum]);
+
+        Ok(())
+    }
+}
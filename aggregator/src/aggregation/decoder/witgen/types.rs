@@ -47,7 +47,7 @@ pub struct BlockInfo {
 pub struct SequenceInfo {
     pub block_idx: usize,
     pub num_sequences: usize,
-    pub compression_mode: [bool; 3],
+    pub compression_mode: [u8; 3],
 }
 
 /// The type for indicate each range in output bytes by sequence execution
@@ -114,6 +114,10 @@ pub enum ZstdTag {
     ZstdBlockSequenceFseCode,
     /// sequence bitstream for recovering instructions
     ZstdBlockSequenceData,
+    /// The single byte describing the symbol of an FSE table in RLE_Mode.
+    ZstdBlockSequenceRleCode,
+    /// The single byte repeated as the literals of an RLE_Literals_Block or RLE_Block.
+    ZstdBlockLiteralsRleBytes,
 }
 
 impl ZstdTag {
@@ -129,6 +133,8 @@ impl ZstdTag {
             Self::ZstdBlockSequenceHeader => true,
             Self::ZstdBlockSequenceFseCode => true,
             Self::ZstdBlockSequenceData => true,
+            Self::ZstdBlockSequenceRleCode => true,
+            Self::ZstdBlockLiteralsRleBytes => true,
         }
     }
 
@@ -144,6 +150,8 @@ impl ZstdTag {
             Self::ZstdBlockSequenceHeader => false,
             Self::ZstdBlockSequenceFseCode => false,
             Self::ZstdBlockSequenceData => true,
+            Self::ZstdBlockSequenceRleCode => false,
+            Self::ZstdBlockLiteralsRleBytes => false,
         }
     }
 
@@ -161,6 +169,8 @@ impl ZstdTag {
             Self::ZstdBlockSequenceHeader => 4,
             Self::ZstdBlockSequenceFseCode => 128,
             Self::ZstdBlockSequenceData => (1 << 17) - 1,
+            Self::ZstdBlockSequenceRleCode => 1,
+            Self::ZstdBlockLiteralsRleBytes => 1,
         }
     }
 }
@@ -199,6 +209,8 @@ impl ToString for ZstdTag {
             Self::ZstdBlockSequenceHeader => "ZstdBlockSequenceHeader",
             Self::ZstdBlockSequenceFseCode => "ZstdBlockSequenceFseCode",
             Self::ZstdBlockSequenceData => "ZstdBlockSequenceData",
+            Self::ZstdBlockSequenceRleCode => "ZstdBlockSequenceRleCode",
+            Self::ZstdBlockLiteralsRleBytes => "ZstdBlockLiteralsRleBytes",
         })
     }
 }
@@ -541,6 +553,8 @@ pub struct FseAuxiliaryTableData {
     pub block_idx: u64,
     /// Indicates whether the table is pre-defined.
     pub is_predefined: bool,
+    /// Indicates whether the table is the one from the previous block, re-used in Repeat_Mode.
+    pub is_repeat: bool,
     /// The FSE table kind, variants are: LLT=1, MOT=2, MLT=3.
     pub table_kind: FseTableKind,
     /// The FSE table's size, i.e. 1 << AL (accuracy log).
@@ -734,6 +748,7 @@ impl FseAuxiliaryTableData {
             Self {
                 block_idx,
                 is_predefined,
+                is_repeat: false,
                 table_kind,
                 table_size,
                 normalised_probs,
@@ -743,6 +758,67 @@ impl FseAuxiliaryTableData {
         ))
     }
 
+    /// Gets the FSE table of the given kind as per the Symbol Compression Mode described in the
+    /// sequences section header:
+    ///
+    /// - Predefined_Mode (0) and FSE_Compressed_Mode (2) are reconstructed from the bitstream.
+    /// - RLE_Mode (1) reads the single symbol byte.
+    /// - Repeat_Mode (3) re-uses the table of the same kind from the previous block.
+    pub fn from_compression_mode(
+        src: &[u8],
+        block_idx: u64,
+        table_kind: FseTableKind,
+        byte_offset: usize,
+        mode: u8,
+        prev_table: Option<&Self>,
+    ) -> std::io::Result<ReconstructedFse> {
+        match mode {
+            1 => {
+                let symbol = *src
+                    .get(byte_offset)
+                    .ok_or(std::io::Error::from(std::io::ErrorKind::UnexpectedEof))?;
+                Ok((1, vec![], Self::rle(block_idx, table_kind, symbol)))
+            }
+            3 => {
+                let prev_table = prev_table.ok_or(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "Repeat_Mode without an FSE table from a previous block",
+                ))?;
+                Ok((0, vec![], prev_table.repeat(block_idx)))
+            }
+            _ => Self::reconstruct(src, block_idx, table_kind, byte_offset, mode < 2),
+        }
+    }
+
+    /// The FSE table described in RLE_Mode, i.e. a table with a single state (AL=0) that always
+    /// emits the given symbol while reading 0 bits from the bitstream.
+    pub fn rle(block_idx: u64, table_kind: FseTableKind, symbol: u8) -> Self {
+        let normalised_probs = BTreeMap::from([(symbol as u64, 1)]);
+        let (sym_to_states, sym_to_sorted_states) =
+            Self::transform_normalised_probs(&normalised_probs, 0);
+
+        Self {
+            block_idx,
+            is_predefined: false,
+            is_repeat: false,
+            table_kind,
+            table_size: 1,
+            normalised_probs,
+            sym_to_states,
+            sym_to_sorted_states,
+        }
+    }
+
+    /// The FSE table re-used in Repeat_Mode, i.e. the table of the same kind from the previous
+    /// block, now assigned to the current block.
+    pub fn repeat(&self, block_idx: u64) -> Self {
+        Self {
+            block_idx,
+            is_repeat: true,
+            ..self.clone()
+        }
+    }
+
     #[allow(non_snake_case)]
     fn transform_normalised_probs(
         normalised_probs: &BTreeMap<u64, i32>,
@@ -1,6 +1,6 @@
 use halo2_proofs::{
     halo2curves::bn256::Fr,
-    plonk::{Advice, Column, ConstraintSystem, Fixed, SecondPhase, Selector},
+    plonk::{Advice, Column, ConstraintSystem, Fixed, Selector},
     poly::Rotation,
 };
 
@@ -9,7 +9,7 @@ use halo2_proofs::plonk::FirstPhase;
 use itertools::Itertools;
 use zkevm_circuits::{
     table::{KeccakTable, LookupTable},
-    util::{Challenges, Expr},
+    util::{advice_column_in_phase2, Challenges, Expr},
 };
 
 /// This config is used to compute RLCs for bytes.
@@ -48,7 +48,7 @@ impl RlcConfig {
             column
         };
 
-        let phase_2_column = meta.advice_column_in(SecondPhase);
+        let phase_2_column = advice_column_in_phase2(meta, challenge.phases());
         meta.enable_equality(phase_2_column);
 
         let fixed = meta.fixed_column();
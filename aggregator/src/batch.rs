@@ -6,8 +6,9 @@ use ethers_core::utils::keccak256;
 use gadgets::Field;
 
 use crate::{
-    blob::{BatchData, PointEvaluationAssignments},
+    blob::{BatchData, BlobEnvelope, PointEvaluationAssignments},
     chunk::ChunkInfo,
+    pi::BatchPi,
 };
 
 #[derive(Default, Debug, Clone)]
@@ -38,11 +39,22 @@ pub struct BatchHash<const N_SNARKS: usize> {
     pub(crate) point_evaluation_assignments: PointEvaluationAssignments,
     /// The 4844 versioned hash for the blob.
     pub(crate) versioned_hash: H256,
+    /// The layout of the blob, and the codec used to encode the batch data into it.
+    pub(crate) envelope: BlobEnvelope,
 }
 
 impl<const N_SNARKS: usize> BatchHash<N_SNARKS> {
     /// Build Batch hash from an ordered list of #N_SNARKS of chunks.
     pub fn construct(chunks_with_padding: &[ChunkInfo]) -> Self {
+        Self::construct_with_envelope(chunks_with_padding, BlobEnvelope::default())
+    }
+
+    /// Build Batch hash from an ordered list of #N_SNARKS of chunks, whose data is encoded into
+    /// a blob laid out as `envelope`.
+    pub fn construct_with_envelope(
+        chunks_with_padding: &[ChunkInfo],
+        envelope: BlobEnvelope,
+    ) -> Self {
         assert_eq!(
             chunks_with_padding.len(),
             N_SNARKS,
@@ -121,7 +133,8 @@ impl<const N_SNARKS: usize> BatchHash<N_SNARKS> {
             .collect::<Vec<_>>();
         let batch_data_hash = keccak256(preimage);
 
-        let batch_data = BatchData::<N_SNARKS>::new(number_of_valid_chunks, chunks_with_padding);
+        let batch_data = BatchData::<N_SNARKS>::new(number_of_valid_chunks, chunks_with_padding)
+            .with_envelope(envelope);
         let point_evaluation_assignments = PointEvaluationAssignments::from(&batch_data);
         let versioned_hash = batch_data.get_versioned_hash();
 
@@ -137,25 +150,18 @@ impl<const N_SNARKS: usize> BatchHash<N_SNARKS> {
         //     y ||
         //     versioned_hash
         // )
-        let preimage = [
-            chunks_with_padding[0].chain_id.to_be_bytes().as_ref(),
-            chunks_with_padding[0].prev_state_root.as_bytes(),
-            chunks_with_padding[N_SNARKS - 1].post_state_root.as_bytes(),
-            chunks_with_padding[N_SNARKS - 1].withdraw_root.as_bytes(),
-            chunks_with_padding[N_SNARKS - 1].sequencer_root.as_bytes(),
-            batch_data_hash.as_slice(),
-            point_evaluation_assignments
-                .challenge
-                .to_be_bytes()
-                .as_ref(),
-            point_evaluation_assignments
-                .evaluation
-                .to_be_bytes()
-                .as_ref(),
-            versioned_hash.as_bytes(),
-        ]
-        .concat();
-        let public_input_hash: H256 = keccak256(preimage).into();
+        let public_input_hash = BatchPi {
+            chain_id: chunks_with_padding[0].chain_id,
+            prev_state_root: chunks_with_padding[0].prev_state_root,
+            post_state_root: chunks_with_padding[N_SNARKS - 1].post_state_root,
+            withdraw_root: chunks_with_padding[N_SNARKS - 1].withdraw_root,
+            sequencer_root: chunks_with_padding[N_SNARKS - 1].sequencer_root,
+            data_hash: batch_data_hash.into(),
+            z: point_evaluation_assignments.challenge,
+            y: point_evaluation_assignments.evaluation,
+            versioned_hash,
+        }
+        .hash();
 
         log::info!(
             "batch pi hash {:?}, datahash {}, z {}, y {}, versioned hash {:x}",
@@ -174,6 +180,7 @@ impl<const N_SNARKS: usize> BatchHash<N_SNARKS> {
             number_of_valid_chunks,
             point_evaluation_assignments,
             versioned_hash,
+            envelope,
         }
     }
 
@@ -207,24 +214,7 @@ impl<const N_SNARKS: usize> BatchHash<N_SNARKS> {
         //      y ||
         //      blob_versioned_hash
         //  )
-        let batch_public_input_hash_preimage = [
-            self.chain_id.to_be_bytes().as_ref(),
-            self.chunks_with_padding[0].prev_state_root.as_bytes(),
-            self.chunks_with_padding[N_SNARKS - 1]
-                .post_state_root
-                .as_bytes(),
-            self.chunks_with_padding[N_SNARKS - 1]
-                .withdraw_root
-                .as_bytes(),
-            self.chunks_with_padding[N_SNARKS - 1]
-                .sequencer_root
-                .as_bytes(),
-            self.data_hash.as_bytes(),
-            &self.point_evaluation_assignments.challenge.to_be_bytes(),
-            &self.point_evaluation_assignments.evaluation.to_be_bytes(),
-            self.versioned_hash.as_bytes(),
-        ]
-        .concat();
+        let batch_public_input_hash_preimage = BatchPi::from(self).to_bytes();
         res.push(batch_public_input_hash_preimage);
 
         // compute piHash for each chunk for i in [0..N_SNARKS)
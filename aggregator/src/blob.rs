@@ -1,5 +1,9 @@
 use crate::{
-    aggregation::{interpolate, witgen::init_zstd_encoder, BLS_MODULUS},
+    aggregation::{
+        interpolate,
+        witgen::{init_zstd_encoder, init_zstd_encoder_tuned, N_BLOCK_SIZE_TARGET},
+        BLS_MODULUS,
+    },
     BatchHash, ChunkInfo,
 };
 
@@ -55,6 +59,75 @@ pub static KZG_TRUSTED_SETUP: Lazy<Arc<c_kzg::KzgSettings>> = Lazy::new(|| {
     )
 });
 
+/// Tuned parameters of the zstd encoder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ZstdParams {
+    /// The compression level.
+    pub level: i32,
+    /// Log2 of the window size. The decoder circuit only supports single segment frames, so the
+    /// window must cover the whole batch data.
+    pub window_log: Option<u32>,
+    /// Target size of a compressed block, at most [`N_BLOCK_SIZE_TARGET`].
+    pub target_block_size: u32,
+}
+
+/// The codec used to encode the batch data into a [`BlobEnvelope::V1`] blob.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlobCodec {
+    /// The batch data is stored as is. This bypasses the zstd decoder, and is useful when the
+    /// batch data does not compress, e.g. for high-entropy tx data.
+    Uncompressed,
+    /// The batch data is zstd encoded with the default encoder parameters.
+    Zstd,
+    /// The batch data is zstd encoded with tuned encoder parameters.
+    ZstdTuned(ZstdParams),
+}
+
+impl BlobCodec {
+    /// The header byte identifying the codec in the blob.
+    pub fn header_byte(&self) -> u8 {
+        match self {
+            Self::Uncompressed => 0,
+            Self::Zstd => 1,
+            Self::ZstdTuned(_) => 2,
+        }
+    }
+
+    /// Whether the payload is zstd encoded.
+    pub fn is_encoded(&self) -> bool {
+        !matches!(self, Self::Uncompressed)
+    }
+}
+
+/// The layout of the blob.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlobEnvelope {
+    /// The blob holds the batch data zstd encoded with the default encoder parameters.
+    #[default]
+    V0,
+    /// The blob starts with a header byte identifying the codec, followed by the batch data
+    /// encoded with that codec.
+    V1(BlobCodec),
+}
+
+impl BlobEnvelope {
+    /// The codec used to encode the batch data.
+    pub fn codec(&self) -> BlobCodec {
+        match self {
+            Self::V0 => BlobCodec::Zstd,
+            Self::V1(codec) => *codec,
+        }
+    }
+
+    /// The header byte identifying the codec, absent before [`BlobEnvelope::V1`].
+    pub fn header_byte(&self) -> Option<u8> {
+        match self {
+            Self::V0 => None,
+            Self::V1(codec) => Some(codec.header_byte()),
+        }
+    }
+}
+
 /// Helper struct to generate witness for the Batch Data Config.
 #[derive(Clone, Debug)]
 pub struct BatchData<const N_SNARKS: usize> {
@@ -72,6 +145,8 @@ pub struct BatchData<const N_SNARKS: usize> {
     /// copied over for the padded chunks. The `chunk_data_digest` for padded chunks is the
     /// `chunk_data_digest` of the last valid chunk (from Aggregation Circuit's perspective).
     pub chunk_data: [Vec<u8>; N_SNARKS],
+    /// The layout of the blob, and the codec used to encode the batch data into it.
+    pub envelope: BlobEnvelope,
 }
 
 impl<const N_SNARKS: usize> From<&BatchHash<N_SNARKS>> for BatchData<N_SNARKS> {
@@ -80,6 +155,7 @@ impl<const N_SNARKS: usize> From<&BatchHash<N_SNARKS>> for BatchData<N_SNARKS> {
             batch_hash.number_of_valid_chunks,
             &batch_hash.chunks_with_padding,
         )
+        .with_envelope(batch_hash.envelope)
     }
 }
 
@@ -115,6 +191,7 @@ impl<const N_SNARKS: usize> From<&Vec<Vec<u8>>> for BatchData<N_SNARKS> {
             num_valid_chunks: num_valid_chunks.try_into().unwrap(),
             chunk_sizes,
             chunk_data,
+            envelope: BlobEnvelope::default(),
         }
     }
 }
@@ -215,7 +292,20 @@ impl<const N_SNARKS: usize> BatchData<N_SNARKS> {
             num_valid_chunks: num_valid_chunks as u16,
             chunk_sizes,
             chunk_data,
+            envelope: BlobEnvelope::default(),
+        }
+    }
+
+    /// Set the layout of the blob, and the codec used to encode the batch data into it.
+    pub fn with_envelope(mut self, envelope: BlobEnvelope) -> Self {
+        if let BlobCodec::ZstdTuned(params) = envelope.codec() {
+            assert!(
+                params.target_block_size <= N_BLOCK_SIZE_TARGET,
+                "target block size exceeds the decoder circuit's block size"
+            );
         }
+        self.envelope = envelope;
+        self
     }
 
     /// Get the versioned hash as per EIP-4844.
@@ -276,23 +366,64 @@ impl<const N_SNARKS: usize> BatchData<N_SNARKS> {
             .collect()
     }
 
-    /// Get the zstd encoded batch data bytes.
-    pub fn get_encoded_batch_data_bytes(&self) -> Vec<u8> {
+    /// Get the zstd encoded batch data bytes, with the encoder parameters of the codec. The
+    /// default parameters are used when the blob is not zstd encoded.
+    pub(crate) fn get_zstd_encoded_batch_data_bytes(&self) -> Vec<u8> {
         let batch_data_bytes = self.get_batch_data_bytes();
-        let mut encoder = init_zstd_encoder(None);
+        let mut encoder = match self.envelope.codec() {
+            BlobCodec::ZstdTuned(params) => init_zstd_encoder_tuned(
+                Some(params.target_block_size),
+                params.level,
+                params.window_log,
+            ),
+            BlobCodec::Uncompressed | BlobCodec::Zstd => init_zstd_encoder(None),
+        };
         encoder
             .set_pledged_src_size(Some(batch_data_bytes.len() as u64))
             .expect("infallible");
         encoder.write_all(&batch_data_bytes).expect("infallible");
-        let encoded_bytes = encoder.finish().expect("infallible");
+        encoder.finish().expect("infallible")
+    }
+
+    /// Get the blob bytes: the codec header byte, if any, followed by the batch data bytes
+    /// encoded with that codec.
+    pub fn get_encoded_batch_data_bytes(&self) -> Vec<u8> {
+        let batch_data_bytes = self.get_batch_data_bytes();
+        let header = self.envelope.header_byte();
+        let payload = if self.envelope.codec().is_encoded() {
+            self.get_zstd_encoded_batch_data_bytes()
+        } else {
+            batch_data_bytes.clone()
+        };
         log::info!(
-            "compress batch data from {} to {}, compression ratio {:.2}, blob usage {:.3}",
+            "encode batch data with {:?} from {} to {}, compression ratio {:.2}, blob usage {:.3}",
+            self.envelope,
             batch_data_bytes.len(),
-            encoded_bytes.len(),
-            batch_data_bytes.len() as f32 / encoded_bytes.len() as f32,
-            encoded_bytes.len() as f32 / N_BLOB_BYTES as f32
+            payload.len(),
+            batch_data_bytes.len() as f32 / payload.len() as f32,
+            (header.iter().len() + payload.len()) as f32 / N_BLOB_BYTES as f32
         );
-        encoded_bytes
+        header.into_iter().chain(payload).collect()
+    }
+
+    /// Get the (decoded, encoded) bytes proven by the zstd decoder circuit. When the blob is not
+    /// zstd encoded, the decoder is bypassed and proves a one byte frame which is not linked to
+    /// the batch data.
+    pub(crate) fn get_decoder_bytes(&self) -> (Vec<u8>, Vec<u8>) {
+        if self.envelope.codec().is_encoded() {
+            (
+                self.get_batch_data_bytes(),
+                self.get_zstd_encoded_batch_data_bytes(),
+            )
+        } else {
+            let decoded_bytes = vec![0];
+            let mut encoder = init_zstd_encoder(None);
+            encoder
+                .set_pledged_src_size(Some(decoded_bytes.len() as u64))
+                .expect("infallible");
+            encoder.write_all(&decoded_bytes).expect("infallible");
+            (decoded_bytes, encoder.finish().expect("infallible"))
+        }
     }
 
     /// Get the BLOB_WIDTH number of scalar field elements, as 32-bytes unsigned integers.
@@ -755,6 +886,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn blob_envelope() {
+        let batch_data = BatchData::<MAX_AGG_SNARKS>::from(&vec![vec![1, 2, 3]]);
+
+        // no header before the v1 envelope
+        assert_eq!(
+            batch_data.get_encoded_batch_data_bytes(),
+            batch_data.get_zstd_encoded_batch_data_bytes()
+        );
+
+        let blob_bytes = batch_data
+            .clone()
+            .with_envelope(BlobEnvelope::V1(BlobCodec::Uncompressed))
+            .get_encoded_batch_data_bytes();
+        assert_eq!(blob_bytes[0], 0);
+        assert_eq!(
+            &blob_bytes[1..],
+            batch_data.get_batch_data_bytes().as_slice()
+        );
+
+        let blob_bytes = batch_data
+            .clone()
+            .with_envelope(BlobEnvelope::V1(BlobCodec::Zstd))
+            .get_encoded_batch_data_bytes();
+        assert_eq!(blob_bytes[0], 1);
+        assert_eq!(
+            &blob_bytes[1..],
+            batch_data.get_zstd_encoded_batch_data_bytes().as_slice()
+        );
+    }
+
     #[test]
     fn default_batch_data() {
         let mut default_metadata = [0u8; BatchData::<MAX_AGG_SNARKS>::n_rows_metadata()];
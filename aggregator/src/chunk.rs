@@ -6,6 +6,8 @@ use serde::{Deserialize, Serialize};
 use std::iter;
 use zkevm_circuits::witness::Block;
 
+use crate::pi::ChunkPi;
+
 #[derive(Default, Debug, Clone, Deserialize, Serialize)]
 /// A chunk is a set of continuous blocks.
 /// ChunkInfo is metadata of chunk, with following fields:
@@ -245,15 +247,6 @@ impl ChunkInfo {
     ///     chunk txdata hash
     /// ]
     pub fn extract_hash_preimage(&self) -> Vec<u8> {
-        [
-            self.chain_id.to_be_bytes().as_ref(),
-            self.prev_state_root.as_bytes(),
-            self.post_state_root.as_bytes(),
-            self.withdraw_root.as_bytes(),
-            self.sequencer_root.as_bytes(),
-            self.data_hash.as_bytes(),
-            self.tx_bytes_hash().as_bytes(),
-        ]
-        .concat()
+        ChunkPi::from(self).to_bytes()
     }
 }
@@ -1,7 +1,5 @@
 //! Circuit implementation for compression circuit.
 
-use std::fs::File;
-
 use ark_std::{end_timer, start_timer};
 use halo2_proofs::{
     circuit::{Cell, Layouter, SimpleFloorPlanner, Value},
@@ -72,14 +70,9 @@ impl Circuit<Fr> for CompressionCircuit {
     }
 
     fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
-        // Too bad that configure function doesn't take additional input
-        // it would be nicer to load parameters from API rather than ENV
-        let path = std::env::var("COMPRESSION_CONFIG")
-            .unwrap_or_else(|_| "configs/compression_wide.config".to_owned());
-        let params: ConfigParams = serde_json::from_reader(
-            File::open(path.as_str()).unwrap_or_else(|_| panic!("{path:?} does not exist")),
-        )
-        .unwrap_or_else(|_| ConfigParams::default_compress_wide_param());
+        // configure function doesn't take additional input, so the params are installed by the
+        // caller with `ConfigParams::with_compression`, or loaded from ENV
+        let params = ConfigParams::compression();
 
         log::info!(
             "compression circuit configured with k = {} and {:?} advice columns",
@@ -1,12 +1,12 @@
 // A chain_id is u64 and uses 8 bytes
-pub(crate) const CHAIN_ID_LEN: usize = 8;
+pub const CHAIN_ID_LEN: usize = 8;
 
 // ================================
 // hash parameters
 // ================================
 
 /// Digest length
-pub(crate) const DIGEST_LEN: usize = 32;
+pub const DIGEST_LEN: usize = 32;
 
 // TODO: make this dynamic
 pub(crate) const LOG_DEGREE: u32 = 21;
@@ -24,12 +24,14 @@ pub(crate) const LOG_DEGREE: u32 = 21;
 // - chunk_data_hash    32 bytes
 // - chunk_tx_data_hash 32 bytes
 
-pub(crate) const PREV_STATE_ROOT_INDEX: usize = 8;
-pub(crate) const POST_STATE_ROOT_INDEX: usize = 40;
-pub(crate) const WITHDRAW_ROOT_INDEX: usize = 72;
-pub(crate) const SEQUENCER_ROOT_INDEX: usize = 104;
-pub(crate) const CHUNK_DATA_HASH_INDEX: usize = 136;
-pub(crate) const CHUNK_TX_DATA_HASH_INDEX: usize = 168;
+pub const PREV_STATE_ROOT_INDEX: usize = 8;
+pub const POST_STATE_ROOT_INDEX: usize = 40;
+pub const WITHDRAW_ROOT_INDEX: usize = 72;
+pub const SEQUENCER_ROOT_INDEX: usize = 104;
+pub const CHUNK_DATA_HASH_INDEX: usize = 136;
+pub const CHUNK_TX_DATA_HASH_INDEX: usize = 168;
+/// Length of the chunk pi hash preimage
+pub const CHUNK_PI_PREIMAGE_LEN: usize = 200;
 
 // ================================
 // indices for batch pi hash table
@@ -46,16 +48,18 @@ pub(crate) const CHUNK_TX_DATA_HASH_INDEX: usize = 168;
 // - y                  32 bytes
 // - versioned_hash     32 bytes
 
-pub(crate) const BATCH_Z_OFFSET: usize = 168;
-pub(crate) const BATCH_Y_OFFSET: usize = 200;
-pub(crate) const BATCH_VH_OFFSET: usize = 232;
+pub const BATCH_Z_OFFSET: usize = 168;
+pub const BATCH_Y_OFFSET: usize = 200;
+pub const BATCH_VH_OFFSET: usize = 232;
+/// Length of the batch pi hash preimage
+pub const BATCH_PI_PREIMAGE_LEN: usize = 264;
 
 // ================================
 // aggregator parameters
 // ================================
 
 /// An decomposed accumulator consists of 12 field elements
-pub(crate) const ACC_LEN: usize = 12;
+pub const ACC_LEN: usize = 12;
 
 /// number of limbs when decomposing a field element in the ECC chip
 pub(crate) const LIMBS: usize = 3;
@@ -344,8 +344,8 @@ pub(crate) struct AssignedBatchHash {
 // 6. chunk[i]'s chunk_pi_hash_rlc_cells == chunk[i-1].chunk_pi_hash_rlc_cells when chunk[i] is
 // padded
 // 7. the hash input length are correct
-// - hashes[0] has 200 bytes
-// - hashes[1..N_SNARKS+1] has 168 bytes input
+// - hashes[0] has 264 bytes input, see BATCH_PI_PREIMAGE_LEN
+// - hashes[1..N_SNARKS+1] has 200 bytes input, see CHUNK_PI_PREIMAGE_LEN
 // - batch's data_hash length is 32 * number_of_valid_snarks
 // 8. batch data hash is correct w.r.t. its RLCs
 // 9. is_final_cells are set correctly
@@ -623,8 +623,8 @@ fn copy_constraints<const N_SNARKS: usize>(
 // 6. chunk[i]'s chunk_pi_hash_rlc_cells == chunk[i-1].chunk_pi_hash_rlc_cells when chunk[i] is
 // padded
 // 7. the hash input length are correct
-// - hashes[0] has 232 bytes
-// - hashes[1..N_SNARKS+1] has 200 bytes input
+// - hashes[0] has 264 bytes input, see BATCH_PI_PREIMAGE_LEN
+// - hashes[1..N_SNARKS+1] has 200 bytes input, see CHUNK_PI_PREIMAGE_LEN
 // - batch's data_hash length is 32 * number_of_valid_snarks
 // 8. batch data hash is correct w.r.t. its RLCs
 // 9. is_final_cells are set correctly
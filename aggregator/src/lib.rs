@@ -18,6 +18,8 @@ mod constants;
 mod core;
 /// Parameters for compression circuit
 mod param;
+/// Public input preimages of chunk and batch proofs
+pub mod pi;
 /// utilities
 mod util;
 
@@ -27,7 +29,7 @@ mod tests;
 pub use self::core::extract_proof_and_instances_with_pairing_check;
 pub use aggregation::*;
 pub use batch::BatchHash;
-pub use blob::BatchData;
+pub use blob::{BatchData, BlobCodec, BlobEnvelope, ZstdParams};
 pub use chunk::ChunkInfo;
 pub use compression::*;
 pub use constants::MAX_AGG_SNARKS;
@@ -1,7 +1,13 @@
+use std::{fs::File, path::Path};
+
+use eth_types::scoped::{Scope, ScopedValue};
 use snark_verifier::loader::halo2::halo2_ecc::fields::fp::FpStrategy;
 
 use crate::{BITS, LIMBS};
 
+static COMPRESSION_PARAMS: ScopedValue<ConfigParams> = ScopedValue::new();
+static AGGREGATION_PARAMS: ScopedValue<ConfigParams> = ScopedValue::new();
+
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
 /// Parameters for aggregation circuit and compression circuit configs.
 pub struct ConfigParams {
@@ -16,6 +22,66 @@ pub struct ConfigParams {
 }
 
 impl ConfigParams {
+    /// Read the params from a json config file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, std::io::Error> {
+        let f = File::open(path)?;
+        Ok(serde_json::from_reader(f)?)
+    }
+
+    /// Compression params from the file at the `COMPRESSION_CONFIG` env var, or the default
+    /// wide params.
+    pub fn compression_from_env() -> Self {
+        let path = std::env::var("COMPRESSION_CONFIG")
+            .unwrap_or_else(|_| "configs/compression_wide.config".to_owned());
+        if !Path::new(&path).exists() {
+            panic!("{path:?} does not exist");
+        }
+        Self::from_file(&path).unwrap_or_else(|_| Self::default_compress_wide_param())
+    }
+
+    /// Aggregation params from the file at the `AGGREGATION_CONFIG` env var, or the default
+    /// aggregation params.
+    pub fn aggregation_from_env() -> Self {
+        std::env::var("AGGREGATION_CONFIG").map_or_else(
+            |_| Self::aggregation_param(),
+            |path| {
+                Self::from_file(&path).unwrap_or_else(|err| panic!("{path:?} is invalid: {err}"))
+            },
+        )
+    }
+
+    /// Params the compression circuit is configured with: the ones installed by
+    /// [`ConfigParams::with_compression`], or the env vars otherwise.
+    pub fn compression() -> Self {
+        COMPRESSION_PARAMS.get_or_else(Self::compression_from_env)
+    }
+
+    /// Params the aggregation circuit is configured with: the ones installed by
+    /// [`ConfigParams::with_aggregation`], or the env vars otherwise.
+    pub fn aggregation() -> Self {
+        AGGREGATION_PARAMS.get_or_else(Self::aggregation_from_env)
+    }
+
+    /// Run `f` with the compression circuit configured by `self`, see [`Scope::run`].
+    pub fn with_compression<R: Send>(&self, f: impl FnOnce() -> R + Send) -> R {
+        self.install_compression(Scope::new()).run(f)
+    }
+
+    /// Run `f` with the aggregation circuit configured by `self`, see [`Scope::run`].
+    pub fn with_aggregation<R: Send>(&self, f: impl FnOnce() -> R + Send) -> R {
+        self.install_aggregation(Scope::new()).run(f)
+    }
+
+    /// Add `self` as the compression params to the values installed by `scope`.
+    pub fn install_compression(&self, scope: Scope) -> Scope {
+        scope.set(&COMPRESSION_PARAMS, self.clone())
+    }
+
+    /// Add `self` as the aggregation params to the values installed by `scope`.
+    pub fn install_aggregation(&self, scope: Scope) -> Scope {
+        scope.set(&AGGREGATION_PARAMS, self.clone())
+    }
+
     /// Same with scroll-prover/integration/configs/layer3.config
     pub(crate) fn aggregation_param() -> Self {
         Self {
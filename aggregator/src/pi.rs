@@ -0,0 +1,378 @@
+//! Typed encoding of the public inputs of chunk and batch proofs.
+//!
+//! A chunk or batch proof exposes, after the accumulator, the keccak hash of a fixed size
+//! preimage, one byte per instance cell. This module builds and parses those preimages and
+//! checks them against the instance column of a proof.
+
+use std::fmt;
+
+use eth_types::{ToBigEndian, H256, U256};
+use ethers_core::utils::keccak256;
+use halo2_proofs::halo2curves::{bn256::Fr, ff::PrimeField};
+
+use crate::{batch::BatchHash, chunk::ChunkInfo};
+
+pub use crate::constants::{
+    ACC_LEN, BATCH_PI_PREIMAGE_LEN, BATCH_VH_OFFSET, BATCH_Y_OFFSET, BATCH_Z_OFFSET, CHAIN_ID_LEN,
+    CHUNK_DATA_HASH_INDEX, CHUNK_PI_PREIMAGE_LEN, CHUNK_TX_DATA_HASH_INDEX, DIGEST_LEN,
+    POST_STATE_ROOT_INDEX, PREV_STATE_ROOT_INDEX, SEQUENCER_ROOT_INDEX, WITHDRAW_ROOT_INDEX,
+};
+
+/// Errors when decoding or checking public inputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PiError {
+    /// The preimage does not have the expected length.
+    InvalidLength {
+        /// expected number of bytes
+        expected: usize,
+        /// actual number of bytes
+        found: usize,
+    },
+    /// The instance columns do not hold a public input hash.
+    InvalidInstances(String),
+    /// The public input hash of the proof is not the one of the preimage.
+    HashMismatch {
+        /// hash of the preimage
+        expected: H256,
+        /// hash found in the instances
+        found: H256,
+    },
+}
+
+impl fmt::Display for PiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength { expected, found } => {
+                write!(
+                    f,
+                    "invalid preimage length: expected {expected}, found {found}"
+                )
+            }
+            Self::InvalidInstances(reason) => write!(f, "invalid instances: {reason}"),
+            Self::HashMismatch { expected, found } => {
+                write!(
+                    f,
+                    "pi hash mismatch: expected {expected:?}, found {found:?}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for PiError {}
+
+/// A field that differs between two public input preimages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldMismatch {
+    /// name of the field
+    pub field: &'static str,
+    /// value on the expected side
+    pub expected: String,
+    /// value on the other side
+    pub found: String,
+}
+
+impl fmt::Display for FieldMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: expected {}, found {}",
+            self.field, self.expected, self.found
+        )
+    }
+}
+
+fn compare<T: PartialEq + fmt::Debug>(
+    field: &'static str,
+    expected: &T,
+    found: &T,
+) -> Option<FieldMismatch> {
+    (expected != found).then(|| FieldMismatch {
+        field,
+        expected: format!("{expected:?}"),
+        found: format!("{found:?}"),
+    })
+}
+
+fn check_length(bytes: &[u8], expected: usize) -> Result<(), PiError> {
+    if bytes.len() != expected {
+        return Err(PiError::InvalidLength {
+            expected,
+            found: bytes.len(),
+        });
+    }
+    Ok(())
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    u64::from_be_bytes(bytes[..CHAIN_ID_LEN].try_into().unwrap())
+}
+
+fn read_h256(bytes: &[u8], offset: usize) -> H256 {
+    H256::from_slice(&bytes[offset..offset + DIGEST_LEN])
+}
+
+/// Extract the public input hash from the instance column of a proof, as returned by
+/// `Proof::instances()`. The column holds the 32 bytes of the hash, optionally preceded by
+/// the accumulator.
+pub fn pi_hash_from_instances(instances: &[Vec<Fr>]) -> Result<H256, PiError> {
+    let [column] = instances else {
+        return Err(PiError::InvalidInstances(format!(
+            "expected 1 instance column, found {}",
+            instances.len()
+        )));
+    };
+    let digest = match column.len() {
+        DIGEST_LEN => column.as_slice(),
+        len if len == ACC_LEN + DIGEST_LEN => &column[ACC_LEN..],
+        len => {
+            return Err(PiError::InvalidInstances(format!(
+                "expected {} or {} instances, found {len}",
+                DIGEST_LEN,
+                ACC_LEN + DIGEST_LEN
+            )))
+        }
+    };
+    let mut hash = H256::zero();
+    for (i, cell) in digest.iter().enumerate() {
+        let repr = cell.to_repr();
+        if repr[1..].iter().any(|&b| b != 0) {
+            return Err(PiError::InvalidInstances(format!(
+                "pi hash byte {i} is not a byte: {cell:?}"
+            )));
+        }
+        hash.0[i] = repr[0];
+    }
+    Ok(hash)
+}
+
+/// The preimage of a chunk's public input hash.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ChunkPi {
+    /// Chain identifier
+    pub chain_id: u64,
+    /// state root before the chunk
+    pub prev_state_root: H256,
+    /// state root after the chunk
+    pub post_state_root: H256,
+    /// the withdraw root after the chunk
+    pub withdraw_root: H256,
+    /// the sequencer root after the chunk
+    pub sequencer_root: H256,
+    /// the data hash of the chunk
+    pub data_hash: H256,
+    /// the keccak hash of the flattened L2 tx bytes of the chunk
+    pub tx_data_hash: H256,
+}
+
+impl From<&ChunkInfo> for ChunkPi {
+    fn from(chunk: &ChunkInfo) -> Self {
+        Self {
+            chain_id: chunk.chain_id,
+            prev_state_root: chunk.prev_state_root,
+            post_state_root: chunk.post_state_root,
+            withdraw_root: chunk.withdraw_root,
+            sequencer_root: chunk.sequencer_root,
+            data_hash: chunk.data_hash,
+            tx_data_hash: chunk.tx_bytes_hash(),
+        }
+    }
+}
+
+impl ChunkPi {
+    /// Encode the preimage, laid out as
+    /// chain id || prev state root || post state root || withdraw root || sequencer root ||
+    /// chunk data hash || chunk txdata hash
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [
+            self.chain_id.to_be_bytes().as_ref(),
+            self.prev_state_root.as_bytes(),
+            self.post_state_root.as_bytes(),
+            self.withdraw_root.as_bytes(),
+            self.sequencer_root.as_bytes(),
+            self.data_hash.as_bytes(),
+            self.tx_data_hash.as_bytes(),
+        ]
+        .concat()
+    }
+
+    /// Decode a preimage encoded by [`ChunkPi::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PiError> {
+        check_length(bytes, CHUNK_PI_PREIMAGE_LEN)?;
+        Ok(Self {
+            chain_id: read_u64(bytes),
+            prev_state_root: read_h256(bytes, PREV_STATE_ROOT_INDEX),
+            post_state_root: read_h256(bytes, POST_STATE_ROOT_INDEX),
+            withdraw_root: read_h256(bytes, WITHDRAW_ROOT_INDEX),
+            sequencer_root: read_h256(bytes, SEQUENCER_ROOT_INDEX),
+            data_hash: read_h256(bytes, CHUNK_DATA_HASH_INDEX),
+            tx_data_hash: read_h256(bytes, CHUNK_TX_DATA_HASH_INDEX),
+        })
+    }
+
+    /// The public input hash committed by a chunk proof.
+    pub fn hash(&self) -> H256 {
+        keccak256(self.to_bytes()).into()
+    }
+
+    /// The fields of `other` that differ from `self`.
+    pub fn diff(&self, other: &Self) -> Vec<FieldMismatch> {
+        [
+            compare("chain_id", &self.chain_id, &other.chain_id),
+            compare(
+                "prev_state_root",
+                &self.prev_state_root,
+                &other.prev_state_root,
+            ),
+            compare(
+                "post_state_root",
+                &self.post_state_root,
+                &other.post_state_root,
+            ),
+            compare("withdraw_root", &self.withdraw_root, &other.withdraw_root),
+            compare(
+                "sequencer_root",
+                &self.sequencer_root,
+                &other.sequencer_root,
+            ),
+            compare("data_hash", &self.data_hash, &other.data_hash),
+            compare("tx_data_hash", &self.tx_data_hash, &other.tx_data_hash),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Check that the instances of a chunk proof commit to this preimage.
+    pub fn check_instances(&self, instances: &[Vec<Fr>]) -> Result<(), PiError> {
+        check_hash(self.hash(), instances)
+    }
+}
+
+/// The preimage of a batch's public input hash.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BatchPi {
+    /// Chain identifier
+    pub chain_id: u64,
+    /// state root before the first chunk
+    pub prev_state_root: H256,
+    /// state root after the last chunk
+    pub post_state_root: H256,
+    /// the withdraw root after the last chunk
+    pub withdraw_root: H256,
+    /// the sequencer root after the last chunk
+    pub sequencer_root: H256,
+    /// the batch data hash
+    pub data_hash: H256,
+    /// the challenge point z of the blob polynomial
+    pub z: U256,
+    /// the evaluation y of the blob polynomial at z
+    pub y: U256,
+    /// the 4844 versioned hash of the blob
+    pub versioned_hash: H256,
+}
+
+impl<const N_SNARKS: usize> From<&BatchHash<N_SNARKS>> for BatchPi {
+    fn from(batch: &BatchHash<N_SNARKS>) -> Self {
+        let last_chunk = &batch.chunks_with_padding[N_SNARKS - 1];
+        Self {
+            chain_id: batch.chain_id,
+            prev_state_root: batch.chunks_with_padding[0].prev_state_root,
+            post_state_root: last_chunk.post_state_root,
+            withdraw_root: last_chunk.withdraw_root,
+            sequencer_root: last_chunk.sequencer_root,
+            data_hash: batch.data_hash,
+            z: batch.point_evaluation_assignments.challenge,
+            y: batch.point_evaluation_assignments.evaluation,
+            versioned_hash: batch.versioned_hash,
+        }
+    }
+}
+
+impl BatchPi {
+    /// Encode the preimage, laid out as
+    /// chain id || prev state root || post state root || withdraw root || sequencer root ||
+    /// batch data hash || z || y || versioned hash
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [
+            self.chain_id.to_be_bytes().as_ref(),
+            self.prev_state_root.as_bytes(),
+            self.post_state_root.as_bytes(),
+            self.withdraw_root.as_bytes(),
+            self.sequencer_root.as_bytes(),
+            self.data_hash.as_bytes(),
+            &self.z.to_be_bytes(),
+            &self.y.to_be_bytes(),
+            self.versioned_hash.as_bytes(),
+        ]
+        .concat()
+    }
+
+    /// Decode a preimage encoded by [`BatchPi::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PiError> {
+        check_length(bytes, BATCH_PI_PREIMAGE_LEN)?;
+        Ok(Self {
+            chain_id: read_u64(bytes),
+            prev_state_root: read_h256(bytes, PREV_STATE_ROOT_INDEX),
+            post_state_root: read_h256(bytes, POST_STATE_ROOT_INDEX),
+            withdraw_root: read_h256(bytes, WITHDRAW_ROOT_INDEX),
+            sequencer_root: read_h256(bytes, SEQUENCER_ROOT_INDEX),
+            data_hash: read_h256(bytes, CHUNK_DATA_HASH_INDEX),
+            z: U256::from_big_endian(&bytes[BATCH_Z_OFFSET..BATCH_Z_OFFSET + DIGEST_LEN]),
+            y: U256::from_big_endian(&bytes[BATCH_Y_OFFSET..BATCH_Y_OFFSET + DIGEST_LEN]),
+            versioned_hash: read_h256(bytes, BATCH_VH_OFFSET),
+        })
+    }
+
+    /// The public input hash committed by a batch proof.
+    pub fn hash(&self) -> H256 {
+        keccak256(self.to_bytes()).into()
+    }
+
+    /// The fields of `other` that differ from `self`.
+    pub fn diff(&self, other: &Self) -> Vec<FieldMismatch> {
+        [
+            compare("chain_id", &self.chain_id, &other.chain_id),
+            compare(
+                "prev_state_root",
+                &self.prev_state_root,
+                &other.prev_state_root,
+            ),
+            compare(
+                "post_state_root",
+                &self.post_state_root,
+                &other.post_state_root,
+            ),
+            compare("withdraw_root", &self.withdraw_root, &other.withdraw_root),
+            compare(
+                "sequencer_root",
+                &self.sequencer_root,
+                &other.sequencer_root,
+            ),
+            compare("data_hash", &self.data_hash, &other.data_hash),
+            compare("z", &self.z, &other.z),
+            compare("y", &self.y, &other.y),
+            compare(
+                "versioned_hash",
+                &self.versioned_hash,
+                &other.versioned_hash,
+            ),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Check that the instances of a batch proof commit to this preimage.
+    pub fn check_instances(&self, instances: &[Vec<Fr>]) -> Result<(), PiError> {
+        check_hash(self.hash(), instances)
+    }
+}
+
+fn check_hash(expected: H256, instances: &[Vec<Fr>]) -> Result<(), PiError> {
+    let found = pi_hash_from_instances(instances)?;
+    if found != expected {
+        return Err(PiError::HashMismatch { expected, found });
+    }
+    Ok(())
+}
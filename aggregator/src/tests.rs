@@ -2,6 +2,7 @@ mod aggregation;
 mod blob;
 mod compression;
 mod mock_chunk;
+mod pi;
 mod rlc;
 
 #[macro_export]
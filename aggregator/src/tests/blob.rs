@@ -2,7 +2,9 @@ use crate::{
     aggregation::{
         AssignedBarycentricEvaluationConfig, BarycentricEvaluationConfig, BlobDataConfig, RlcConfig,
     },
-    blob::{BatchData, PointEvaluationAssignments, N_BYTES_U256},
+    blob::{
+        BatchData, BlobCodec, BlobEnvelope, PointEvaluationAssignments, ZstdParams, N_BYTES_U256,
+    },
     param::ConfigParams,
     BatchDataConfig, MAX_AGG_SNARKS,
 };
@@ -59,7 +61,7 @@ impl Circuit<Fr> for BlobCircuit {
         let u8_table = U8Table::construct(meta);
         let range_table = RangeTable::construct(meta);
         let challenges = Challenges::construct_p1(meta);
-        let keccak_table = KeccakTable::construct(meta);
+        let keccak_table = KeccakTable::construct(meta, challenges.phases());
 
         let rlc = RlcConfig::configure(meta, &keccak_table, challenges);
 
@@ -310,6 +312,29 @@ fn generic_batch_data_is_valid() {
     assert_eq!(check_data(generic_batch_data()), Ok(()));
 }
 
+#[test]
+fn zstd_batch_data_with_header_is_valid() {
+    let batch_data = generic_batch_data().with_envelope(BlobEnvelope::V1(BlobCodec::Zstd));
+    assert_eq!(check_data(batch_data), Ok(()));
+}
+
+#[test]
+fn uncompressed_batch_data_is_valid() {
+    let batch_data = generic_batch_data().with_envelope(BlobEnvelope::V1(BlobCodec::Uncompressed));
+    assert_eq!(check_data(batch_data), Ok(()));
+}
+
+#[test]
+fn tuned_zstd_batch_data_is_valid() {
+    let batch_data =
+        generic_batch_data().with_envelope(BlobEnvelope::V1(BlobCodec::ZstdTuned(ZstdParams {
+            level: 3,
+            window_log: None,
+            target_block_size: 64 * 1024,
+        })));
+    assert_eq!(check_data(batch_data), Ok(()));
+}
+
 #[test]
 fn inconsistent_chunk_size() {
     let mut blob_data = generic_batch_data();
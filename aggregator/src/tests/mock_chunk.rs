@@ -78,7 +78,7 @@ impl Circuit<Fr> for MockChunkCircuit {
         meta.set_minimum_degree(4);
 
         let challenges = Challenges::construct_p1(meta);
-        let keccak_table = KeccakTable::construct(meta);
+        let keccak_table = KeccakTable::construct(meta, challenges.phases());
         let rlc_config = RlcConfig::configure(meta, &keccak_table, challenges);
         let instance = meta.instance_column();
         meta.enable_equality(instance);
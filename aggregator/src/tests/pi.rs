@@ -0,0 +1,88 @@
+use ark_std::test_rng;
+use eth_types::H256;
+use halo2_proofs::halo2curves::bn256::Fr;
+use itertools::Itertools;
+
+use crate::{
+    batch::BatchHash,
+    pi::{BatchPi, ChunkPi, PiError, ACC_LEN},
+    ChunkInfo,
+};
+
+fn hash_instances(hash: H256, with_accumulator: bool) -> Vec<Vec<Fr>> {
+    let accumulator = if with_accumulator { ACC_LEN } else { 0 };
+    vec![std::iter::repeat(Fr::from(1))
+        .take(accumulator)
+        .chain(hash.as_bytes().iter().map(|&b| Fr::from(b as u64)))
+        .collect()]
+}
+
+#[test]
+fn chunk_pi_roundtrip() {
+    let mut rng = test_rng();
+    let chunk = ChunkInfo::mock_random_chunk_info_for_testing(&mut rng);
+    let pi = ChunkPi::from(&chunk);
+
+    let bytes = pi.to_bytes();
+    assert_eq!(bytes, chunk.extract_hash_preimage());
+    assert_eq!(ChunkPi::from_bytes(&bytes), Ok(pi.clone()));
+    assert_eq!(pi.hash(), chunk.public_input_hash());
+    assert_eq!(
+        ChunkPi::from_bytes(&bytes[1..]),
+        Err(PiError::InvalidLength {
+            expected: 200,
+            found: 199
+        })
+    );
+
+    assert_eq!(pi.check_instances(&hash_instances(pi.hash(), true)), Ok(()));
+    assert_eq!(
+        pi.check_instances(&hash_instances(pi.hash(), false)),
+        Ok(())
+    );
+
+    let mut other = pi.clone();
+    other.post_state_root = H256::repeat_byte(0xab);
+    let mismatches = pi.diff(&other);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].field, "post_state_root");
+    assert_eq!(
+        pi.check_instances(&hash_instances(other.hash(), true)),
+        Err(PiError::HashMismatch {
+            expected: pi.hash(),
+            found: other.hash()
+        })
+    );
+}
+
+#[test]
+fn batch_pi_roundtrip() {
+    const N_SNARKS: usize = 4;
+
+    let mut rng = test_rng();
+    let mut chunks = (0..2)
+        .map(|_| ChunkInfo::mock_random_chunk_info_for_testing(&mut rng))
+        .collect_vec();
+    chunks[1].prev_state_root = chunks[0].post_state_root;
+    let padded_chunk = ChunkInfo::mock_padded_chunk_info_for_testing(&chunks[1]);
+    chunks.resize(N_SNARKS, padded_chunk);
+
+    let batch_hash = BatchHash::<N_SNARKS>::construct(&chunks);
+    let pi = BatchPi::from(&batch_hash);
+
+    let bytes = pi.to_bytes();
+    assert_eq!(bytes, batch_hash.extract_hash_preimages()[0]);
+    assert_eq!(BatchPi::from_bytes(&bytes), Ok(pi.clone()));
+    assert_eq!(pi.hash(), batch_hash.public_input_hash);
+    assert_eq!(
+        pi.check_instances(&batch_hash.instances_exclude_acc()),
+        Ok(())
+    );
+
+    let mut instances = hash_instances(pi.hash(), true);
+    instances[0][ACC_LEN] = Fr::from(256);
+    assert!(matches!(
+        pi.check_instances(&instances),
+        Err(PiError::InvalidInstances(_))
+    ));
+}
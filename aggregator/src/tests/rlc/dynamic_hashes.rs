@@ -45,7 +45,7 @@ impl Circuit<Fr> for DynamicHashCircuit {
         // hash config
         // hash configuration for aggregation circuit
         let keccak_circuit_config = {
-            let keccak_table = KeccakTable::construct(meta);
+            let keccak_table = KeccakTable::construct(meta, challenges.phases());
             let challenges_exprs = challenges.exprs(meta);
 
             let keccak_circuit_config_args = KeccakCircuitConfigArgs {
@@ -31,7 +31,7 @@ impl Circuit<Fr> for ArithTestCircuit {
 
     fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
         let challenges = Challenges::construct_p1(meta);
-        let keccak_table = KeccakTable::construct(meta);
+        let keccak_table = KeccakTable::construct(meta, challenges.phases());
         RlcConfig::configure(meta, &keccak_table, challenges)
     }
 
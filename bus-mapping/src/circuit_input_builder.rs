@@ -2,15 +2,22 @@
 //! types from geth / web3 and outputs the circuit inputs.
 
 mod access;
+/// Beacon roots contract (EIP-4788)
+pub mod beacon_roots;
 mod block;
 mod builder_client;
 mod call;
 /// Curie hardfork
 pub mod curie;
 mod execution;
+/// History storage contract (EIP-2935)
+pub mod history_storage;
 mod input_state_ref;
+mod l1_fee;
 #[cfg(feature = "scroll")]
 mod l2;
+/// System calls before the txs of a block
+pub mod system_call;
 #[cfg(all(feature = "tracer-tests", feature = "enable-memory", test))]
 mod tracer_tests;
 mod transaction;
@@ -41,15 +48,15 @@ pub use execution::{
 };
 pub use input_state_ref::CircuitInputStateRef;
 use itertools::Itertools;
+pub use l1_fee::{L1FeeModel, TxL1Fee, TX_L1_COMMIT_EXTRA_COST, TX_L1_FEE_PRECISION};
 #[cfg(feature = "scroll")]
 use mpt_zktrie::state::ZktrieState;
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
-pub use transaction::{
-    Transaction, TransactionContext, TxL1Fee, TX_L1_COMMIT_EXTRA_COST, TX_L1_FEE_PRECISION,
-};
+pub use transaction::{Transaction, TransactionContext};
 
 /// Setup parameters for ECC-related precompile calls.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct PrecompileEcParams {
     /// Maximum number of EcAdd ops supported in one block.
     pub ec_add: usize,
@@ -57,6 +64,8 @@ pub struct PrecompileEcParams {
     pub ec_mul: usize,
     /// Maximum number of EcPairing ops supported in one block.
     pub ec_pairing: usize,
+    /// Maximum number of (G1, G2) pairs supported in one EcPairing op.
+    pub ec_pairing_pairs: usize,
 }
 
 impl Default for PrecompileEcParams {
@@ -65,12 +74,13 @@ impl Default for PrecompileEcParams {
             ec_add: 50,
             ec_mul: 50,
             ec_pairing: 2,
+            ec_pairing_pairs: N_PAIRING_PER_OP,
         }
     }
 }
 
 /// Circuit Setup Parameters
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct CircuitsParams {
     /// Maximum number of rw operations in the state circuit (RwTable length /
     /// number of rows). This must be at least the number of rw operations
@@ -301,6 +311,13 @@ impl<'a> CircuitInputBuilder {
             eth_block.number,
             eth_block.transactions.len()
         );
+        let block_num = eth_block
+            .number
+            .ok_or(Error::EthTypeError(eth_types::Error::IncompleteBlock))?
+            .as_u64();
+        if system_call::is_system_call_enabled(self.block.chain_id, block_num) {
+            self.handle_system_call(block_num)?;
+        }
         for (tx_index, tx) in eth_block.transactions.iter().enumerate() {
             let chunk_tx_idx = self.block.txs.len();
             if self.block.txs.len() >= self.block.circuits_params.max_txs {
@@ -342,6 +359,23 @@ impl<'a> CircuitInputBuilder {
         Ok(())
     }
 
+    /// Apply the system call of a block before its transactions.
+    fn handle_system_call(&mut self, block_num: u64) -> Result<(), Error> {
+        let mut dummy_tx = Transaction::dummy();
+        let mut dummy_tx_ctx = TransactionContext::default();
+        let mut state = self.state_ref(&mut dummy_tx, &mut dummy_tx_ctx);
+        let step = system_call::gen_system_call_step(&mut state, block_num)?;
+        log::debug!(
+            "system call of block {block_num}: {} rws",
+            step.bus_mapping_instance.len()
+        );
+        self.block
+            .block_steps
+            .system_call_steps
+            .insert(block_num, step);
+        Ok(())
+    }
+
     fn check_post_state(&self, post_states: &[eth_types::l2_types::AccountProofWrapper]) {
         for account_post_state in post_states {
             let account_post_state = account_post_state.clone();
@@ -492,21 +526,6 @@ impl<'a> CircuitInputBuilder {
             )?;
         }
 
-        // 0-block chunk is only valid for vk gen.
-        if let Some(last_block_num) = state.block.last_block_num() {
-            // Curie sys contract upgrade
-            let is_curie_fork_block =
-                curie::is_curie_fork_block(state.block.chain_id, last_block_num);
-            if is_curie_fork_block {
-                log::info!(
-                    "apply curie, chain id {}, block num {}",
-                    state.block.chain_id,
-                    last_block_num
-                );
-                curie::apply_curie(&mut state, &mut end_block_step)?;
-            }
-        }
-
         state.push_op(
             &mut end_block_step,
             RW::READ,
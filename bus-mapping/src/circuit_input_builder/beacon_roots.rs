@@ -0,0 +1,56 @@
+// Adapted from https://eips.ethereum.org/EIPS/eip-4788
+
+use eth_types::{
+    forks::{ChainSpec, HardforkId},
+    ToWord,
+};
+
+use crate::{
+    l2_predeployed::beacon_roots,
+    operation::{StorageOp, RW},
+    Error,
+};
+
+use super::{CircuitInputStateRef, ExecStep};
+
+/// Whether this blk writes its parent beacon block root into the beacon roots contract
+pub fn is_beacon_roots_enabled(chain_id: u64, blk: u64) -> bool {
+    // the evm circuit checks the fork against the block number, see `HardforkGadget`
+    ChainSpec::for_chain_id(chain_id).is_active_at_block(HardforkId::BeaconRoots, blk)
+}
+
+/// Write the timestamp and the parent beacon block root of `block_num` into their slots of the
+/// beacon roots contract, done by the system call of the block.
+/// Num of rws: 2
+pub fn apply_beacon_root(
+    state: &mut CircuitInputStateRef,
+    step: &mut ExecStep,
+    block_num: u64,
+    tx_id: usize,
+) -> Result<(), Error> {
+    let block = &state.block.blocks[&block_num];
+    let timestamp = block.timestamp.as_u64();
+    let root = block
+        .header
+        .parent_beacon_block_root
+        .ok_or(Error::EthTypeError(eth_types::Error::IncompleteBlock))?;
+    let address = *beacon_roots::ADDRESS;
+    log::trace!("beacon roots: block {block_num} timestamp {timestamp} root {root:?}");
+
+    for (slot, value) in [
+        (beacon_roots::timestamp_slot(timestamp), timestamp.into()),
+        (beacon_roots::root_slot(timestamp), root.to_word()),
+    ] {
+        let value_prev = *state.sdb.get_storage(&address, &slot).1;
+        let committed_value = *state.sdb.get_committed_storage(&address, &slot).1;
+        // the writes are done by the system before the block, so they are never reverted
+        state.push_op(
+            step,
+            RW::WRITE,
+            StorageOp::new(address, slot, value, value_prev, tx_id, committed_value),
+        )?;
+        state.sdb.set_storage(&address, &slot, &value);
+    }
+
+    Ok(())
+}
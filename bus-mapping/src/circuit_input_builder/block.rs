@@ -9,7 +9,7 @@ use crate::{
     operation::{OperationContainer, RWCounter},
     Error,
 };
-use eth_types::{Address, ToWord, Word, H256};
+use eth_types::{block_header::BlockHeader, Address, ToWord, Word, H256};
 use std::collections::{BTreeMap, HashMap};
 
 /// Context of a [`Block`] which can mutate in a [`Transaction`].
@@ -51,6 +51,9 @@ pub struct BlockSteps {
     pub padding_step: ExecStep,
     /// EndBlock step that appears in the last EVM row.
     pub end_block_step: ExecStep,
+    /// SystemCall steps by block number, each one placed before the first
+    /// transaction of its block.
+    pub system_call_steps: BTreeMap<u64, ExecStep>,
 }
 
 impl Default for BlockSteps {
@@ -64,6 +67,7 @@ impl Default for BlockSteps {
                 exec_state: ExecState::EndBlock,
                 ..ExecStep::default()
             },
+            system_call_steps: BTreeMap::new(),
         }
     }
 }
@@ -94,6 +98,10 @@ pub struct Block {
     pub parent_hash: H256,
     /// State root of this block
     pub state_root: H256,
+    /// Hash of this block, as reported by the node
+    pub hash: H256,
+    /// Header of this block
+    pub header: BlockHeader,
 }
 impl Block {
     /// Create a new block.
@@ -145,6 +153,8 @@ impl Block {
             base_fee: eth_block.base_fee_per_gas.unwrap_or_default(),
             parent_hash: eth_block.parent_hash,
             state_root: eth_block.state_root,
+            hash: eth_block.hash.unwrap_or_default(),
+            header: BlockHeader::from(eth_block),
         })
     }
 }
@@ -1,4 +1,5 @@
 use eth_types::{
+    block_header::parent_beacon_block_root,
     constants::SCROLL_COINBASE,
     geth_types::{self, Account, BlockConstants},
     state_db::{self, CodeDB, StateDB},
@@ -476,6 +477,7 @@ impl<P: JsonRpcClient> BuilderClient<P> {
                 difficulty,
                 gas_limit: eth_block.gas_limit,
                 base_fee: eth_block.base_fee_per_gas.unwrap(),
+                parent_beacon_block_root: parent_beacon_block_root(eth_block),
             },
             accounts: proofs
                 .into_iter()
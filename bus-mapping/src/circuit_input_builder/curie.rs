@@ -1,6 +1,9 @@
 // Adapted from https://github.com/scroll-tech/go-ethereum/blob/8dc419a70b94f5ca185dcf818a48a3bd2eefc392/consensus/misc/curie.go
 
-use eth_types::Word;
+use eth_types::{
+    forks::{ChainSpec, HardforkId},
+    Word,
+};
 
 use crate::{
     l2_predeployed::l1_gas_price_oracle,
@@ -12,12 +15,8 @@ use super::{CircuitInputStateRef, ExecStep};
 
 /// Get curie fork block height
 pub fn get_curie_fork_block(chain_id: u64) -> u64 {
-    for (fork, fork_chain_id, fork_blk) in eth_types::forks::hardfork_heights() {
-        if fork == eth_types::forks::HardforkId::Curie && chain_id == fork_chain_id {
-            return fork_blk;
-        }
-    }
-    0
+    // the evm circuit checks curie against the block number, see `HardforkGadget`
+    ChainSpec::for_chain_id(chain_id).fork_block(HardforkId::Curie)
 }
 
 /// Whether this blk has enabled curie fork
@@ -34,50 +33,15 @@ pub fn is_curie_fork_block(chain_id: u64, blk: u64) -> bool {
     result
 }
 
-/// Insert needed rws for the contract upgrade
-/// Num of rws: 3
-pub fn apply_curie(state: &mut CircuitInputStateRef, step: &mut ExecStep) -> Result<(), Error> {
-    // The chunk should not includes other txs.
-    // let v1_codesize = l1_gas_price_oracle::V1_BYTECODE.len();
-    // let v1_codehash = hash_code(&l1_gas_price_oracle::V1_BYTECODE);
-    // let v1_keccak_codehash = hash_code_keccak(&l1_gas_price_oracle::V1_BYTECODE);
-    // log::debug!("l1_oracle poseidon codehash {:?}", v1_codehash);
-    // log::debug!("l1_oracle keccak codehash {:?}", v1_keccak_codehash);
-    // let v2_codesize = l1_gas_price_oracle::V2_BYTECODE.len();
-    // let v2_codehash = hash_code(&l1_gas_price_oracle::V2_BYTECODE);
-    // let v2_keccak_codehash = hash_code_keccak(&l1_gas_price_oracle::V2_BYTECODE);
-
-    // state.push_op(
-    //     step,
-    //     RW::WRITE,
-    //     AccountOp {
-    //         address: *l1_gas_price_oracle::ADDRESS,
-    //         field: AccountField::CodeHash,
-    //         value_prev: v1_codehash.to_word(),
-    //         value: v2_codehash.to_word(),
-    //     },
-    // )?;
-    // state.push_op(
-    //     step,
-    //     RW::WRITE,
-    //     AccountOp {
-    //         address: *l1_gas_price_oracle::ADDRESS,
-    //         field: AccountField::KeccakCodeHash,
-    //         value_prev: v1_keccak_codehash.to_word(),
-    //         value: v2_keccak_codehash.to_word(),
-    //     },
-    // )?;
-    // state.push_op(
-    //     step,
-    //     RW::WRITE,
-    //     AccountOp {
-    //         address: *l1_gas_price_oracle::ADDRESS,
-    //         field: AccountField::CodeSize,
-    //         value_prev: v1_codesize.to_word(),
-    //         value: v2_codesize.to_word(),
-    //     },
-    // )?;
-
+/// Write the storage of the L1 gas price oracle upgraded by curie, done by the system call of
+/// the fork block. The code of the oracle is left as is.
+/// Num of rws: 4
+pub fn apply_curie(
+    state: &mut CircuitInputStateRef,
+    step: &mut ExecStep,
+    tx_id: usize,
+) -> Result<(), Error> {
+    let address = *l1_gas_price_oracle::ADDRESS;
     for (slot, value) in [
         (*l1_gas_price_oracle::IS_CURIE_SLOT, Word::from(1)),
         (*l1_gas_price_oracle::L1_BLOB_BASEFEE_SLOT, Word::from(1)),
@@ -90,18 +54,15 @@ pub fn apply_curie(state: &mut CircuitInputStateRef, step: &mut ExecStep) -> Res
             *l1_gas_price_oracle::INITIAL_BLOB_SCALAR,
         ),
     ] {
+        let value_prev = *state.sdb.get_storage(&address, &slot).1;
+        let committed_value = *state.sdb.get_committed_storage(&address, &slot).1;
+
         state.push_op(
             step,
             RW::WRITE,
-            StorageOp::new(
-                *l1_gas_price_oracle::ADDRESS,
-                slot,
-                value,
-                Word::from(0),
-                0,
-                Word::zero(),
-            ),
+            StorageOp::new(address, slot, value, value_prev, tx_id, committed_value),
         )?;
+        state.sdb.set_storage(&address, &slot, &value);
     }
 
     Ok(())
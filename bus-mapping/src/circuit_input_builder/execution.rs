@@ -10,11 +10,11 @@ use crate::{
     error::{ExecError, OogError},
     exec_trace::OperationRef,
     operation::RWCounter,
-    precompile::{PrecompileAuxData, PrecompileCalls},
+    precompile::{PrecompileAuxData, PrecompileCalls, MODEXP_INPUT_LIMIT},
 };
 use eth_types::{
     evm_types::{memory::MemoryWordRange, Gas, GasCost, MemoryAddress, OpcodeId, ProgramCounter},
-    sign_types::SignData,
+    sign_types::{P256SignData, SignData},
     Address, GethExecStep, ToLittleEndian, Word, H256, U256,
 };
 use ethers_core::k256::elliptic_curve::subtle::CtOption;
@@ -27,6 +27,7 @@ use halo2_proofs::{
     },
     plonk::Expression,
 };
+use serde::{Deserialize, Serialize};
 
 /// An execution step of the EVM.
 #[derive(Clone, Debug)]
@@ -153,6 +154,8 @@ pub enum ExecState {
     EndTx,
     /// Virtual step End Block
     EndBlock,
+    /// Virtual step System Call, applying the pre-block protocol updates
+    SystemCall,
     /// Virtual step Padding
     Padding,
 }
@@ -206,7 +209,7 @@ impl ExecState {
 }
 
 /// Defines the various source/destination types for a copy event.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CopyDataType {
     /// When we need to pad the Copy rows of the circuit up to a certain maximum
     /// with rows that are not "useful".
@@ -360,7 +363,7 @@ pub struct CopyStep {
 }
 
 /// Defines an enum type that can hold either a number or a hash value.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum NumberOrHash {
     /// Variant to indicate a number value.
     Number(usize),
@@ -379,7 +382,7 @@ pub enum NumberOrHash {
 ///
 /// Additionally, when the destination is memory, `bytes_write_prev` holds the memory content
 /// *before* the write.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct CopyBytes {
     /// Represents the list of (bytes, is_code, mask) copied during this copy event
     pub bytes: Vec<(u8, bool, bool)>,
@@ -410,7 +413,7 @@ impl CopyBytes {
 /// Save address, storage_key, storage_key_index and is_warm_prev
 /// to column value_word_rlc, value_word_rlc_prev, value and
 /// value_prev in copy circuit.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CopyAccessList {
     /// Access list address
     pub address: Address,
@@ -446,7 +449,7 @@ impl CopyAccessList {
 /// Defines a copy event associated with EVM opcodes such as CALLDATACOPY,
 /// CODECOPY, CREATE, etc. More information:
 /// <https://github.com/privacy-scaling-explorations/zkevm-specs/blob/master/specs/copy-proof.md>.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CopyEvent {
     /// Represents the start address at the source of the copy event.
     pub src_addr: u64,
@@ -848,7 +851,7 @@ where
 }
 
 /// Intermediary multiplication step, representing `a * b == d (mod 2^256)`
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ExpStep {
     /// First multiplicand.
     pub a: Word,
@@ -869,7 +872,7 @@ impl From<(Word, Word, Word)> for ExpStep {
 }
 
 /// Event representating an exponentiation `a ^ b == d (mod 2^256)`.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ExpEvent {
     /// Base `a` for the exponentiation.
     pub base: Word,
@@ -897,7 +900,7 @@ impl Default for ExpEvent {
 }
 
 /// I/Os from all precompiled contract calls in a block.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct PrecompileEvents {
     /// All events.
     pub events: Vec<PrecompileEvent>,
@@ -918,6 +921,20 @@ impl PrecompileEvents {
             .cloned()
             .collect()
     }
+    /// Get all P256Verify events.
+    pub fn get_p256_verify_events(&self) -> Vec<P256SignData> {
+        self.events
+            .iter()
+            .filter_map(|e| {
+                if let PrecompileEvent::P256Verify(sign_data) = e {
+                    Some(sign_data)
+                } else {
+                    None
+                }
+            })
+            .cloned()
+            .collect()
+    }
     /// Get all EcAdd events.
     pub fn get_ec_add_events(&self) -> Vec<EcAddOp> {
         self.events
@@ -991,7 +1008,7 @@ impl PrecompileEvents {
 }
 
 /// I/O from a precompiled contract call.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum PrecompileEvent {
     /// Represents the I/O from Ecrecover call.
     Ecrecover(SignData),
@@ -1005,6 +1022,8 @@ pub enum PrecompileEvent {
     ModExp(BigModExp),
     /// Represents the I/O from SHA256 call.
     SHA256(SHA256),
+    /// Represents the I/O from P256Verify call.
+    P256Verify(P256SignData),
 }
 
 impl Default for PrecompileEvent {
@@ -1014,7 +1033,7 @@ impl Default for PrecompileEvent {
 }
 
 /// EcAdd operation: P + Q = R
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EcAddOp {
     /// EVM input for first operand to EcAdd.
     pub p: (U256, U256),
@@ -1116,7 +1135,7 @@ impl EcAddOp {
 }
 
 /// EcMul operation: s.P = R
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EcMulOp {
     /// The EVM inputs to the G1 point.
     pub p: (U256, U256),
@@ -1224,15 +1243,15 @@ impl EcMulOp {
     }
 }
 
-/// The number of pairing inputs per pairing operation. If the inputs provided to the precompile
-/// call are < 4, we append (G1::infinity, G2::generator) until we have the required no. of inputs.
+/// The default maximum number of pairing inputs per pairing operation, see
+/// [`PrecompileEcParams::ec_pairing_pairs`](crate::circuit_input_builder::PrecompileEcParams).
 pub const N_PAIRING_PER_OP: usize = 4;
 
 /// The number of bytes taken to represent a pair (G1, G2).
 pub const N_BYTES_PER_PAIR: usize = 192;
 
 /// Pair of (G1, G2).
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EcPairingPair {
     /// EVM inputs for the G1 point.
     pub g1_point: (U256, U256),
@@ -1318,9 +1337,9 @@ impl EcPairingPair {
             .collect()
     }
 
-    /// Padding pair for EcPairing operation. The pairing check is done with a constant number
-    /// `N_PAIRING_PER_OP` of (G1, G2) pairs. In case EVM inputs are less in number, we pad them
-    /// with `(G1::Infinity, G2::Infinity)` for simplicity.
+    /// Padding pair for EcPairing operation. The ECC circuit does the pairing check with a constant
+    /// number of (G1, G2) pairs. In case EVM inputs are less in number, we pad them with
+    /// `(G1::Infinity, G2::Infinity)` for simplicity.
     pub fn padding_pair() -> Self {
         Self {
             g1_point: (U256::zero(), U256::zero()),
@@ -1334,10 +1353,10 @@ impl EcPairingPair {
 }
 
 /// EcPairing operation
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EcPairingOp {
-    /// tuples of G1 and G2 points supplied to the ECC circuit.
-    pub pairs: [EcPairingPair; N_PAIRING_PER_OP],
+    /// tuples of G1 and G2 points supplied to the ecPairing call.
+    pub pairs: Vec<EcPairingPair>,
     /// Result from the pairing check.
     pub output: Word,
     /// Input bytes to the ecPairing call.
@@ -1359,24 +1378,10 @@ impl Default for EcPairingOp {
         let g2_y0 = U256::from_little_endian(&g2_point.y.c1.to_bytes());
         let g2_y1 = U256::from_little_endian(&g2_point.y.c0.to_bytes());
         Self {
-            pairs: [
-                EcPairingPair {
-                    g1_point: (g1_x, g1_y),
-                    g2_point: (g2_x0, g2_x1, g2_y0, g2_y1),
-                },
-                EcPairingPair {
-                    g1_point: (g1_x, g1_y),
-                    g2_point: (g2_x0, g2_x1, g2_y0, g2_y1),
-                },
-                EcPairingPair {
-                    g1_point: (g1_x, g1_y),
-                    g2_point: (g2_x0, g2_x1, g2_y0, g2_y1),
-                },
-                EcPairingPair {
-                    g1_point: (g1_x, g1_y),
-                    g2_point: (g2_x0, g2_x1, g2_y0, g2_y1),
-                },
-            ],
+            pairs: vec![EcPairingPair {
+                g1_point: (g1_x, g1_y),
+                g2_point: (g2_x0, g2_x1, g2_y0, g2_y1),
+            }],
             output: Word::zero(),
             // It does not matter what the input bytes and return bytes are in this case, as this
             // operation is a filler op. It is not an op constructed from an EVM call to the
@@ -1398,6 +1403,20 @@ impl EcPairingOp {
             .collect::<Vec<u8>>()
     }
 
+    /// The (G1, G2) pairs of the op, left-padded with [`EcPairingPair::padding_pair`] to `n_pairs`
+    /// pairs. Padding on the left keeps the RLC of the big-endian input bytes unchanged.
+    pub fn padded_pairs(&self, n_pairs: usize) -> Vec<EcPairingPair> {
+        assert!(
+            self.pairs.len() <= n_pairs,
+            "ecPairing op with {} pairs > {n_pairs}",
+            self.pairs.len()
+        );
+        std::iter::repeat(EcPairingPair::padding_pair())
+            .take(n_pairs - self.pairs.len())
+            .chain(self.pairs.iter().copied())
+            .collect()
+    }
+
     /// A check on the op to tell the ECC Circuit whether or not to skip the op.
     pub fn skip_by_ecc_circuit(&self) -> bool {
         false
@@ -1416,11 +1435,9 @@ impl EcPairingOp {
         let other_g1 = G1Affine::from(G1Affine::generator() * Fr::from(6));
         let other_g2 = G2Affine::generator();
         Self {
-            pairs: [
+            pairs: vec![
                 EcPairingPair::new(g1_neg, g2),
                 EcPairingPair::new(other_g1, other_g2),
-                EcPairingPair::new(G1Affine::identity(), G2Affine::generator()),
-                EcPairingPair::new(G1Affine::identity(), G2Affine::generator()),
             ],
             output: 1.into(),
             ..Default::default()
@@ -1429,31 +1446,38 @@ impl EcPairingOp {
 }
 
 /// Event representating an exponentiation `a ^ b == d (mod m)` in precompile modexp.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct BigModExp {
-    /// Base `a` for the exponentiation.
-    pub base: Word,
-    /// Exponent `b` for the exponentiation.
-    pub exponent: Word,
-    /// Modulus `m`
-    pub modulus: Word,
-    /// Mod exponentiation result.
-    pub result: Word,
+    /// Input bytes of the call, up to `MODEXP_INPUT_LIMIT` bytes.
+    pub input: Vec<u8>,
+    /// Base `a` for the exponentiation, as big-endian bytes of the specified length. All the
+    /// operands are empty if the input lengths exceed `MODEXP_SIZE_LIMIT` or
+    /// `MODEXP_BLOCK_ROWS_LIMIT`.
+    pub base: Vec<u8>,
+    /// Exponent `b` for the exponentiation, as big-endian bytes of the specified length.
+    pub exponent: Vec<u8>,
+    /// Modulus `m`, as big-endian bytes of the specified length.
+    pub modulus: Vec<u8>,
+    /// Mod exponentiation result, as big-endian bytes of the modulus length.
+    pub result: Vec<u8>,
 }
 
-impl Default for BigModExp {
-    fn default() -> Self {
-        Self {
-            modulus: 1.into(),
-            base: Default::default(),
-            exponent: Default::default(),
-            result: Default::default(),
-        }
+impl BigModExp {
+    /// Input bytes of the call, right-padded with zeros to `MODEXP_INPUT_LIMIT` bytes.
+    pub fn padded_input(&self) -> Vec<u8> {
+        let mut input = self.input.clone();
+        input.resize(MODEXP_INPUT_LIMIT, 0);
+        input
+    }
+
+    /// The first (up to) 32 bytes of the exponent, which the gas cost depends on.
+    pub fn exponent_head(&self) -> &[u8] {
+        &self.exponent[..self.exponent.len().min(32)]
     }
 }
 
 /// Event representating an SHA256 hash in precompile sha256.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct SHA256 {
     /// input bytes
     pub input: Vec<u8>,
@@ -0,0 +1,97 @@
+// Adapted from https://eips.ethereum.org/EIPS/eip-2935
+
+use eth_types::{
+    evm_types::block_utils::calculate_block_hash,
+    forks::{ChainSpec, HardforkId},
+    ToWord, Word,
+};
+
+use crate::{
+    l2_predeployed::history_storage,
+    operation::{StorageOp, RW},
+    Error,
+};
+
+use super::{CircuitInputStateRef, ExecStep};
+
+/// Whether this blk serves BLOCKHASH from the history storage contract
+pub fn is_history_storage_enabled(chain_id: u64, blk: u64) -> bool {
+    // the evm circuit checks the fork against the block number, see `HardforkGadget`
+    ChainSpec::for_chain_id(chain_id).is_active_at_block(HardforkId::HistoryStorage, blk)
+}
+
+/// Write the parent hash of `block_num` into its slot of the history storage contract, done by
+/// the system call of the block. With the `scroll` feature the hash of a block is
+/// `keccak256(chain_id || number)`, as returned by BLOCKHASH before the fork. The genesis block
+/// has no parent, so it writes nothing.
+/// Num of rws: 1, or 0 at genesis
+pub fn apply_history_storage(
+    state: &mut CircuitInputStateRef,
+    step: &mut ExecStep,
+    block_num: u64,
+    tx_id: usize,
+) -> Result<(), Error> {
+    let Some(parent_num) = block_num.checked_sub(1) else {
+        return Ok(());
+    };
+    let parent_hash = if cfg!(feature = "scroll") {
+        let (sha3_input, sha3_output) =
+            calculate_block_hash(state.block.chain_id, parent_num.into());
+        state.block.sha3_inputs.push(sha3_input);
+        sha3_output
+    } else {
+        state.block.blocks[&block_num].parent_hash.to_word()
+    };
+    let address = *history_storage::ADDRESS;
+    let slot = history_storage::slot(parent_num);
+
+    let value_prev = *state.sdb.get_storage(&address, &slot).1;
+    let committed_value = *state.sdb.get_committed_storage(&address, &slot).1;
+    log::trace!("history storage: block {block_num} parent hash {parent_hash:?} in slot {slot}");
+
+    // the write is done by the system before the block, so it is never reverted
+    state.push_op(
+        step,
+        RW::WRITE,
+        StorageOp::new(
+            address,
+            slot,
+            parent_hash,
+            value_prev,
+            tx_id,
+            committed_value,
+        ),
+    )?;
+    state.sdb.set_storage(&address, &slot, &parent_hash);
+
+    Ok(())
+}
+
+/// The hash of `block_number` kept by the history storage contract, read by BLOCKHASH.
+/// Num of rws: 1
+pub fn read_history_storage(
+    state: &mut CircuitInputStateRef,
+    step: &mut ExecStep,
+    block_number: u64,
+) -> Result<Word, Error> {
+    let address = *history_storage::ADDRESS;
+    let slot = history_storage::slot(block_number);
+
+    let value = *state.sdb.get_storage(&address, &slot).1;
+    let committed_value = *state.sdb.get_committed_storage(&address, &slot).1;
+
+    state.push_op(
+        step,
+        RW::READ,
+        StorageOp::new(
+            address,
+            slot,
+            value,
+            value,
+            state.tx_ctx.id(),
+            committed_value,
+        ),
+    )?;
+
+    Ok(value)
+}
@@ -29,8 +29,8 @@ use eth_types::{
         memory::{MemoryRange, MemoryWordRange},
         Gas, GasCost, Memory, MemoryAddress, MemoryRef, OpcodeId, StackAddress, MAX_CODE_SIZE,
     },
+    forks::ChainSpec,
     state_db::{CodeDB, StateDB},
-    utils::is_precompiled,
     Address, Bytecode, GethExecStep, ToAddress, ToBigEndian, ToWord, Word, H256, U256,
 };
 use ethers_core::utils::{get_contract_address, get_create2_address};
@@ -1049,9 +1049,10 @@ impl<'a> CircuitInputStateRef<'a> {
         Ok(())
     }
 
-    /// Check if address is a precompiled or not.
+    /// Check if address is a precompiled or not, at the block of the current tx.
     pub fn is_precompiled(&self, address: &Address) -> bool {
-        address.0[0..19] == [0u8; 19] && (1..=9).contains(&address.0[19])
+        ChainSpec::for_chain_id(self.block.chain_id)
+            .is_precompiled_at_block(address, self.tx.block_num)
     }
 
     /// Parse [`Call`] from a *CALL*/CREATE* step without information about success and persistent.
@@ -1091,7 +1092,7 @@ impl<'a> CircuitInputStateRef<'a> {
                     CallKind::CallCode | CallKind::DelegateCall => stack.nth_last(1)?.to_address(),
                     _ => address,
                 };
-                if is_precompiled(&code_address) {
+                if self.is_precompiled(&code_address) {
                     (CodeSource::Address(code_address), CodeDB::empty_code_hash())
                 } else {
                     let (found, account) = self.sdb.get_account(&code_address);
@@ -1649,7 +1650,10 @@ impl<'a> CircuitInputStateRef<'a> {
         step: &GethExecStep,
         next_step: Option<&GethExecStep>,
     ) -> Result<Option<ExecError>, Error> {
-        if matches!(step.op, OpcodeId::INVALID(_)) {
+        if matches!(step.op, OpcodeId::INVALID(_))
+            || !ChainSpec::for_chain_id(self.block.chain_id)
+                .is_opcode_available_at_block(step.op, self.tx.block_num)
+        {
             return Ok(Some(ExecError::InvalidOpcode));
         }
 
@@ -1867,8 +1871,8 @@ impl<'a> CircuitInputStateRef<'a> {
                 //   because the callGasTemp might probably be smaller than the gas
                 //   on top of the stack (step.stack.last())
                 // Therefore we postpone the oog handling to the implementor of callop.
-                if is_precompiled(&code_address) {
-                    let precompile_call: PrecompileCalls = code_address[19].into();
+                if self.is_precompiled(&code_address) {
+                    let precompile_call: PrecompileCalls = code_address.into();
                     match precompile_call {
                         PrecompileCalls::Ripemd160 | PrecompileCalls::Blake2F => {
                             // Log the precompile address and gas left. Since this failure is mainly
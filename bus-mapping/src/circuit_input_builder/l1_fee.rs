@@ -0,0 +1,195 @@
+//! L1 data fee of transactions, charged from the values of the L1GasPriceOracle contract
+
+use eth_types::{state_db::StateDB, Word};
+use serde::{Deserialize, Serialize};
+
+use super::curie::is_curie_enabled;
+use crate::l2_predeployed::l1_gas_price_oracle;
+
+/// Precision of transaction L1 fee
+pub const TX_L1_FEE_PRECISION: u64 = 1_000_000_000;
+/// Extra cost as the bytes of rlped tx commited to L1 (assume to non-zero, overestimated a bit)
+pub const TX_L1_COMMIT_EXTRA_COST: u64 = 64;
+
+/// Formula of the L1 data fee. Each model reads its own slots of the L1GasPriceOracle contract.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum L1FeeModel {
+    /// `fee_scalar * base_fee * (tx_data_gas_cost + fee_overhead + 64) / 1e9`
+    #[default]
+    PreCurie,
+    /// `(commit_scalar * base_fee + blob_scalar * tx_rlp_signed_len * l1_blob_basefee) / 1e9`
+    Curie,
+}
+
+impl L1FeeModel {
+    /// All models.
+    pub const ALL: [Self; 2] = [Self::PreCurie, Self::Curie];
+    /// Maximum number of oracle slots read by a model.
+    pub const MAX_ORACLE_SLOTS: usize = 6;
+
+    /// The model in use at block `block_number` of chain `chain_id`.
+    pub fn for_block(chain_id: u64, block_number: u64) -> Self {
+        if is_curie_enabled(chain_id, block_number) {
+            Self::Curie
+        } else {
+            Self::PreCurie
+        }
+    }
+
+    /// The L1GasPriceOracle slots read by the model, in read order.
+    pub fn oracle_slots(&self) -> Vec<Word> {
+        match self {
+            Self::PreCurie => vec![
+                *l1_gas_price_oracle::BASE_FEE_SLOT,
+                *l1_gas_price_oracle::OVERHEAD_SLOT,
+                *l1_gas_price_oracle::SCALAR_SLOT,
+            ],
+            // TODO: we could skip the "overhead" and "scalar" for curie
+            Self::Curie => vec![
+                *l1_gas_price_oracle::BASE_FEE_SLOT,
+                *l1_gas_price_oracle::OVERHEAD_SLOT,
+                *l1_gas_price_oracle::SCALAR_SLOT,
+                *l1_gas_price_oracle::L1_BLOB_BASEFEE_SLOT,
+                *l1_gas_price_oracle::COMMIT_SCALAR_SLOT,
+                *l1_gas_price_oracle::BLOB_SCALAR_SLOT,
+            ],
+        }
+    }
+
+    /// Divisor of the model's formula, the L1 fee is the quotient.
+    pub fn precision(&self) -> u64 {
+        match self {
+            Self::PreCurie | Self::Curie => TX_L1_FEE_PRECISION,
+        }
+    }
+}
+
+/// Transaction L1 fee for L1GasPriceOracle contract
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TxL1Fee {
+    /// chain id
+    pub chain_id: u64,
+    /// block number
+    pub block_number: u64,
+    /// L1 base fee
+    pub base_fee: u64,
+    /// L1 fee overhead
+    pub fee_overhead: u64,
+    /// L1 fee scalar
+    pub fee_scalar: u64,
+    /// L1 blob fee
+    pub l1_blob_basefee: u64,
+    /// L1 commit scalar
+    pub commit_scalar: u64,
+    /// l1 blob scalar
+    pub blob_scalar: u64,
+}
+
+impl TxL1Fee {
+    /// The fee model of the block.
+    pub fn model(&self) -> L1FeeModel {
+        L1FeeModel::for_block(self.chain_id, self.block_number)
+    }
+
+    /// The values of the oracle slots read by the fee model, in the order of
+    /// [`L1FeeModel::oracle_slots`].
+    pub fn oracle_values(&self) -> Vec<u64> {
+        match self.model() {
+            L1FeeModel::PreCurie => vec![self.base_fee, self.fee_overhead, self.fee_scalar],
+            L1FeeModel::Curie => vec![
+                self.base_fee,
+                self.fee_overhead,
+                self.fee_scalar,
+                self.l1_blob_basefee,
+                self.commit_scalar,
+                self.blob_scalar,
+            ],
+        }
+    }
+
+    /// Calculate L1 fee and remainder of transaction.
+    pub fn tx_l1_fee(&self, tx_data_gas_cost: u64, tx_rlp_signed_len: u64) -> (u64, u64) {
+        let tx_l1_fee = match self.model() {
+            L1FeeModel::PreCurie => self.tx_l1_fee_before_curie(tx_data_gas_cost),
+            L1FeeModel::Curie => self.tx_l1_fee_after_curie(tx_rlp_signed_len),
+        };
+        let precision = self.model().precision() as u128;
+        (
+            (tx_l1_fee / precision) as u64,
+            (tx_l1_fee % precision) as u64,
+        )
+    }
+
+    fn tx_l1_fee_before_curie(&self, tx_data_gas_cost: u64) -> u128 {
+        // <https://github.com/scroll-tech/go-ethereum/blob/49192260a177f1b63fc5ea3b872fb904f396260c/rollup/fees/rollup_fee.go#L118>
+        let tx_l1_gas = tx_data_gas_cost + self.fee_overhead + TX_L1_COMMIT_EXTRA_COST;
+        self.fee_scalar as u128 * self.base_fee as u128 * tx_l1_gas as u128
+    }
+
+    fn tx_l1_fee_after_curie(&self, tx_rlp_signed_len: u64) -> u128 {
+        // for curie upgrade:
+        // new formula: https://github.com/scroll-tech/go-ethereum/blob/develop/rollup/fees/rollup_fee.go#L165
+        // "commitScalar * l1BaseFee + blobScalar * _data.length * l1BlobBaseFee",
+        let tx_l1_fee = self.commit_scalar as u128 * self.base_fee as u128
+            + self.blob_scalar as u128 * tx_rlp_signed_len as u128 * self.l1_blob_basefee as u128;
+        log::debug!(
+            "tx_l1_fee {} commit_scalar {} base_fee {} blob_scalar {}
+            tx_rlp_signed_len {} l1_blob_basefee {}  tx_quient {},reminder {}",
+            tx_l1_fee,
+            self.commit_scalar,
+            self.base_fee,
+            self.blob_scalar,
+            tx_rlp_signed_len,
+            self.l1_blob_basefee,
+            tx_l1_fee / TX_L1_FEE_PRECISION as u128,
+            tx_l1_fee % TX_L1_FEE_PRECISION as u128
+        );
+        tx_l1_fee
+    }
+
+    pub(crate) fn get_current_values_from_state_db(
+        sdb: &StateDB,
+        chain_id: u64,
+        block_number: u64,
+    ) -> Self {
+        Self::from_oracle_slots(chain_id, block_number, |slot| {
+            sdb.get_storage(&l1_gas_price_oracle::ADDRESS, slot)
+                .1
+                .as_u64()
+        })
+    }
+
+    pub(crate) fn get_committed_values_from_state_db(
+        sdb: &StateDB,
+        chain_id: u64,
+        block_number: u64,
+    ) -> Self {
+        Self::from_oracle_slots(chain_id, block_number, |slot| {
+            sdb.get_committed_storage(&l1_gas_price_oracle::ADDRESS, slot)
+                .1
+                .as_u64()
+        })
+    }
+
+    fn from_oracle_slots(chain_id: u64, block_number: u64, read: impl Fn(&Word) -> u64) -> Self {
+        let [base_fee, fee_overhead, fee_scalar, l1_blob_basefee, commit_scalar, blob_scalar] = [
+            &l1_gas_price_oracle::BASE_FEE_SLOT,
+            &l1_gas_price_oracle::OVERHEAD_SLOT,
+            &l1_gas_price_oracle::SCALAR_SLOT,
+            &l1_gas_price_oracle::L1_BLOB_BASEFEE_SLOT,
+            &l1_gas_price_oracle::COMMIT_SCALAR_SLOT,
+            &l1_gas_price_oracle::BLOB_SCALAR_SLOT,
+        ]
+        .map(|slot| read(slot));
+        Self {
+            chain_id,
+            block_number,
+            base_fee,
+            fee_overhead,
+            fee_scalar,
+            l1_blob_basefee,
+            commit_scalar,
+            blob_scalar,
+        }
+    }
+}
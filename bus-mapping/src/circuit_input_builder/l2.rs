@@ -74,7 +74,8 @@ impl CircuitInputBuilder {
         Ok(())
     }
 
-    fn collect_account_proofs(
+    /// Account proofs of `storage_trace`, in the form taken by [`ZktrieState`].
+    pub fn collect_account_proofs(
         storage_trace: &StorageTrace,
     ) -> impl Iterator<Item = (&Address, impl IntoIterator<Item = &[u8]>)> + Clone {
         storage_trace.proofs.iter().flat_map(|kv_map| {
@@ -84,7 +85,8 @@ impl CircuitInputBuilder {
         })
     }
 
-    fn collect_storage_proofs(
+    /// Storage proofs of `storage_trace`, in the form taken by [`ZktrieState`].
+    pub fn collect_storage_proofs(
         storage_trace: &StorageTrace,
     ) -> impl Iterator<Item = (&Address, &H256, impl IntoIterator<Item = &[u8]>)> + Clone {
         storage_trace.storage_proofs.iter().flat_map(|(k, kv_map)| {
@@ -0,0 +1,102 @@
+// System calls are the state updates defined by the protocol that happen before the
+// transactions of a block. They are done on behalf of the system, so they cost no gas, carry no
+// signature and can not be reverted.
+//
+// System calls are not part of the block traces: instead of executing the code of the system
+// contracts, their storage effects are applied directly. Each call into a system contract reads
+// the code hash of the contract, so that the effects are the ones of the code of the EIP.
+
+use super::{CircuitInputStateRef, ExecState, ExecStep};
+use crate::{
+    circuit_input_builder::{
+        beacon_roots::{apply_beacon_root, is_beacon_roots_enabled},
+        curie::{apply_curie, is_curie_fork_block},
+        history_storage::{apply_history_storage, is_history_storage_enabled},
+    },
+    l2_predeployed::{beacon_roots, history_storage},
+    operation::AccountField,
+    Error,
+};
+use eth_types::{utils::hash_code_keccak, Address, ToWord};
+
+/// Whether `block_num` starts with a SystemCall step: one of the forks of the system contracts
+/// is active, or the block is the curie fork block. Other blocks before these forks keep the
+/// steps they had without system calls.
+pub fn is_system_call_enabled(chain_id: u64, block_num: u64) -> bool {
+    is_beacon_roots_enabled(chain_id, block_num)
+        || is_history_storage_enabled(chain_id, block_num)
+        || is_curie_fork_block(chain_id, block_num)
+}
+
+/// Generate the SystemCall step of `block_num`. Its storage ops are tagged with the id of the
+/// next tx, i.e. the first tx of the block if there is one.
+pub fn gen_system_call_step(
+    state: &mut CircuitInputStateRef,
+    block_num: u64,
+) -> Result<ExecStep, Error> {
+    let mut exec_step = ExecStep {
+        exec_state: ExecState::SystemCall,
+        rwc: state.block_ctx.rwc,
+        ..Default::default()
+    };
+    let tx_id = state.block.txs.len() + 1;
+
+    // curie: upgrade of the L1 gas price oracle at the fork block
+    if is_curie_fork_block(state.block.chain_id, block_num) {
+        apply_curie(state, &mut exec_step, tx_id)?;
+    }
+    // EIP-4788: parent beacon block root into the beacon roots contract
+    if is_beacon_roots_enabled(state.block.chain_id, block_num) {
+        read_system_contract_code_hash(
+            state,
+            &mut exec_step,
+            *beacon_roots::ADDRESS,
+            &beacon_roots::BYTECODE,
+        )?;
+        apply_beacon_root(state, &mut exec_step, block_num, tx_id)?;
+    }
+    // EIP-2935: parent hash into the history storage contract
+    if is_history_storage_enabled(state.block.chain_id, block_num) {
+        read_system_contract_code_hash(
+            state,
+            &mut exec_step,
+            *history_storage::ADDRESS,
+            &history_storage::BYTECODE,
+        )?;
+        apply_history_storage(state, &mut exec_step, block_num, tx_id)?;
+    }
+
+    Ok(exec_step)
+}
+
+/// Read the keccak code hash of the system contract at `address`, which must be the hash of
+/// `code`, the code of the EIP.
+/// Num of rws: 1
+fn read_system_contract_code_hash(
+    state: &mut CircuitInputStateRef,
+    step: &mut ExecStep,
+    address: Address,
+    code: &[u8],
+) -> Result<(), Error> {
+    let account = state.sdb.get_account(&address).1;
+    let code_hash = if cfg!(feature = "scroll") {
+        account.keccak_code_hash
+    } else {
+        account.code_hash
+    };
+    if code_hash != hash_code_keccak(code) {
+        log::error!("system contract {address:?} has code hash {code_hash:?}, not the EIP code");
+        return Err(Error::InternalError("system contract code mismatch"));
+    }
+
+    state.account_read(
+        step,
+        address,
+        if cfg!(feature = "scroll") {
+            AccountField::KeccakCodeHash
+        } else {
+            AccountField::CodeHash
+        },
+        code_hash.to_word(),
+    )
+}
@@ -1,10 +1,7 @@
 //! Transaction & TransactionContext utility module.
 
-use super::{
-    call::ReversionGroup, curie::is_curie_enabled, Call, CallContext, CallKind, CodeSource,
-    ExecStep,
-};
-use crate::{l2_predeployed::l1_gas_price_oracle, Error};
+use super::{call::ReversionGroup, Call, CallContext, CallKind, CodeSource, ExecStep, TxL1Fee};
+use crate::Error;
 use eth_types::evm_types::gas_utils::tx_data_gas_cost;
 use eth_types::{
     evm_types::OpcodeId,
@@ -15,11 +12,6 @@ use eth_types::{
 };
 use ethers_core::utils::get_contract_address;
 
-/// Precision of transaction L1 fee
-pub const TX_L1_FEE_PRECISION: u64 = 1_000_000_000;
-/// Extra cost as the bytes of rlped tx commited to L1 (assume to non-zero, overestimated a bit)
-pub const TX_L1_COMMIT_EXTRA_COST: u64 = 64;
-
 #[derive(Debug, Default)]
 /// Context of a [`Transaction`] which can mutate in an [`ExecStep`].
 pub struct TransactionContext {
@@ -474,139 +466,3 @@ impl Transaction {
         different_opcodes || different_precompiles
     }
 }
-
-/// Transaction L1 fee for L1GasPriceOracle contract
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
-pub struct TxL1Fee {
-    /// chain id
-    pub chain_id: u64,
-    /// block number
-    pub block_number: u64,
-    /// L1 base fee
-    pub base_fee: u64,
-    /// L1 fee overhead
-    pub fee_overhead: u64,
-    /// L1 fee scalar
-    pub fee_scalar: u64,
-    /// L1 blob fee
-    pub l1_blob_basefee: u64,
-    /// L1 commit scalar
-    pub commit_scalar: u64,
-    /// l1 blob scalar
-    pub blob_scalar: u64,
-}
-
-impl TxL1Fee {
-    /// Calculate L1 fee and remainder of transaction.
-    pub fn tx_l1_fee(&self, tx_data_gas_cost: u64, tx_rlp_signed_len: u64) -> (u64, u64) {
-        let is_curie = is_curie_enabled(self.chain_id, self.block_number);
-        if is_curie {
-            self.tx_l1_fee_after_curie(tx_rlp_signed_len)
-        } else {
-            self.tx_l1_fee_before_curie(tx_data_gas_cost)
-        }
-    }
-
-    fn tx_l1_fee_before_curie(&self, tx_data_gas_cost: u64) -> (u64, u64) {
-        // <https://github.com/scroll-tech/go-ethereum/blob/49192260a177f1b63fc5ea3b872fb904f396260c/rollup/fees/rollup_fee.go#L118>
-        let tx_l1_gas = tx_data_gas_cost + self.fee_overhead + TX_L1_COMMIT_EXTRA_COST;
-        let tx_l1_fee = self.fee_scalar as u128 * self.base_fee as u128 * tx_l1_gas as u128;
-        (
-            (tx_l1_fee / TX_L1_FEE_PRECISION as u128) as u64,
-            (tx_l1_fee % TX_L1_FEE_PRECISION as u128) as u64,
-        )
-    }
-
-    fn tx_l1_fee_after_curie(&self, tx_rlp_signed_len: u64) -> (u64, u64) {
-        // for curie upgrade:
-        // new formula: https://github.com/scroll-tech/go-ethereum/blob/develop/rollup/fees/rollup_fee.go#L165
-        // "commitScalar * l1BaseFee + blobScalar * _data.length * l1BlobBaseFee",
-        let tx_l1_fee = self.commit_scalar as u128 * self.base_fee as u128
-            + self.blob_scalar as u128 * tx_rlp_signed_len as u128 * self.l1_blob_basefee as u128;
-        log::debug!(
-            "tx_l1_fee {} commit_scalar {} base_fee {} blob_scalar {}
-            tx_rlp_signed_len {} l1_blob_basefee {}  tx_quient {},reminder {}",
-            tx_l1_fee,
-            self.commit_scalar,
-            self.base_fee,
-            self.blob_scalar,
-            tx_rlp_signed_len,
-            self.l1_blob_basefee,
-            tx_l1_fee / TX_L1_FEE_PRECISION as u128,
-            tx_l1_fee % TX_L1_FEE_PRECISION as u128
-        );
-        (
-            (tx_l1_fee / TX_L1_FEE_PRECISION as u128) as u64,
-            (tx_l1_fee % TX_L1_FEE_PRECISION as u128) as u64,
-        )
-    }
-
-    fn get_current_values_from_state_db(sdb: &StateDB, chain_id: u64, block_number: u64) -> Self {
-        let [base_fee, fee_overhead, fee_scalar] = [
-            &l1_gas_price_oracle::BASE_FEE_SLOT,
-            &l1_gas_price_oracle::OVERHEAD_SLOT,
-            &l1_gas_price_oracle::SCALAR_SLOT,
-        ]
-        .map(|slot| {
-            sdb.get_storage(&l1_gas_price_oracle::ADDRESS, slot)
-                .1
-                .as_u64()
-        });
-        let [l1_blob_basefee, commit_scalar, blob_scalar] = [
-            &l1_gas_price_oracle::L1_BLOB_BASEFEE_SLOT,
-            &l1_gas_price_oracle::COMMIT_SCALAR_SLOT,
-            &l1_gas_price_oracle::BLOB_SCALAR_SLOT,
-        ]
-        .map(|slot| {
-            sdb.get_storage(&l1_gas_price_oracle::ADDRESS, slot)
-                .1
-                .as_u64()
-        });
-
-        Self {
-            chain_id,
-            block_number,
-            base_fee,
-            fee_overhead,
-            fee_scalar,
-            l1_blob_basefee,
-            commit_scalar,
-            blob_scalar,
-        }
-    }
-
-    fn get_committed_values_from_state_db(sdb: &StateDB, chain_id: u64, block_number: u64) -> Self {
-        let [base_fee, fee_overhead, fee_scalar] = [
-            &l1_gas_price_oracle::BASE_FEE_SLOT,
-            &l1_gas_price_oracle::OVERHEAD_SLOT,
-            &l1_gas_price_oracle::SCALAR_SLOT,
-        ]
-        .map(|slot| {
-            sdb.get_committed_storage(&l1_gas_price_oracle::ADDRESS, slot)
-                .1
-                .as_u64()
-        });
-
-        let [l1_blob_basefee, commit_scalar, blob_scalar] = [
-            &l1_gas_price_oracle::L1_BLOB_BASEFEE_SLOT,
-            &l1_gas_price_oracle::COMMIT_SCALAR_SLOT,
-            &l1_gas_price_oracle::BLOB_SCALAR_SLOT,
-        ]
-        .map(|slot| {
-            sdb.get_committed_storage(&l1_gas_price_oracle::ADDRESS, slot)
-                .1
-                .as_u64()
-        });
-
-        Self {
-            chain_id,
-            block_number,
-            base_fee,
-            fee_overhead,
-            fee_scalar,
-            l1_blob_basefee,
-            commit_scalar,
-            blob_scalar,
-        }
-    }
-}
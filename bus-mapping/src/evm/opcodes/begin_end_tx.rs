@@ -19,11 +19,12 @@ use eth_types::{
         gas_utils::{tx_access_list_gas_cost, tx_data_gas_cost},
         GasCost, MAX_REFUND_QUOTIENT_OF_GAS_USED,
     },
+    forks::ChainSpec,
     state_db::CodeDB,
-    utils::is_precompiled,
     Bytecode, ToWord, Word,
 };
 use ethers_core::utils::get_contract_address;
+use strum::IntoEnumIterator;
 
 // #[derive(Clone, Copy, Debug)]
 // pub(crate) struct BeginEndTx;
@@ -148,8 +149,11 @@ pub fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<Vec<ExecSt
     )?;
 
     // Add precompile contract address to access list
-    for address in 1..=9 {
-        let address = eth_types::Address::from_low_u64_be(address);
+    let precompiles = PrecompileCalls::iter()
+        .map(eth_types::Address::from)
+        .filter(|address| state.is_precompiled(address))
+        .collect::<Vec<_>>();
+    for address in precompiles {
         let is_warm_prev = !state.sdb.add_account_to_access_list(address);
         state.tx_access_list_account_write(
             &mut exec_step,
@@ -204,7 +208,7 @@ pub fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<Vec<ExecSt
 
     // Get code_hash of callee account
     let callee_account = &state.sdb.get_account(&call.address).1.clone();
-    let is_precompile = is_precompiled(&call.address);
+    let is_precompile = state.is_precompiled(&call.address);
     let callee_exists = !callee_account.is_empty();
     //if !callee_exists && call.value.is_zero() {
     if callee_account.code_hash == CodeDB::empty_code_hash() {
@@ -417,8 +421,10 @@ pub fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<Vec<ExecSt
                 state.call_context_write(&mut exec_step, call.call_id, field, value)?;
             }
 
-            let precompile_call: PrecompileCalls = call.address.0[19].into();
+            let precompile_call: PrecompileCalls = call.address.into();
             let (result, precompile_call_gas_cost, has_oog_err) = execute_precompiled(
+                &ChainSpec::for_chain_id(state.block.chain_id),
+                state.tx.block_num,
                 &precompile_call.into(),
                 &state.tx.input,
                 exec_step.gas_left.0 - exec_step.gas_cost.as_u64(),
@@ -726,103 +732,29 @@ fn write_tx_receipt(
     Ok(())
 }
 
-// Add 3(or 6 after curie) RW read operations for transaction L1 fee.
+// Add the RW read operations of the oracle slots of the transaction L1 fee model: 3 before curie,
+// 6 after curie.
 fn gen_tx_l1_fee_ops(
     state: &mut CircuitInputStateRef,
     exec_step: &mut ExecStep,
 ) -> Result<(), Error> {
     let tx_id = state.tx_ctx.id();
 
-    let base_fee = Word::from(state.tx.l1_fee.base_fee);
-    let fee_overhead = Word::from(state.tx.l1_fee.fee_overhead);
-    let fee_scalar = Word::from(state.tx.l1_fee.fee_scalar);
-    let l1_blob_basefee = Word::from(state.tx.l1_fee.l1_blob_basefee);
-    let commit_scalar = Word::from(state.tx.l1_fee.commit_scalar);
-    let blob_scalar = Word::from(state.tx.l1_fee.blob_scalar);
-
-    let base_fee_committed = Word::from(state.tx.l1_fee_committed.base_fee);
-    let fee_overhead_committed = Word::from(state.tx.l1_fee_committed.fee_overhead);
-    let fee_scalar_committed = Word::from(state.tx.l1_fee_committed.fee_scalar);
-
-    let l1_blob_basefee_committed = Word::from(state.tx.l1_fee_committed.l1_blob_basefee);
-    let commit_scalar_committed = Word::from(state.tx.l1_fee_committed.commit_scalar);
-    let blob_scalar_committed = Word::from(state.tx.l1_fee_committed.blob_scalar);
-
-    state.push_op(
-        exec_step,
-        RW::READ,
-        StorageOp::new(
-            *l1_gas_price_oracle::ADDRESS,
-            *l1_gas_price_oracle::BASE_FEE_SLOT,
-            base_fee,
-            base_fee,
-            tx_id,
-            base_fee_committed,
-        ),
-    )?;
-    state.push_op(
-        exec_step,
-        RW::READ,
-        StorageOp::new(
-            *l1_gas_price_oracle::ADDRESS,
-            *l1_gas_price_oracle::OVERHEAD_SLOT,
-            fee_overhead,
-            fee_overhead,
-            tx_id,
-            fee_overhead_committed,
-        ),
-    )?;
-    state.push_op(
-        exec_step,
-        RW::READ,
-        StorageOp::new(
-            *l1_gas_price_oracle::ADDRESS,
-            *l1_gas_price_oracle::SCALAR_SLOT,
-            fee_scalar,
-            fee_scalar,
-            tx_id,
-            fee_scalar_committed,
-        ),
-    )?;
-
-    // curie operations
-    let chain_id = state.block.chain_id;
-    let block_number = state.tx.block_num;
-    if is_curie_enabled(chain_id, block_number) {
-        state.push_op(
-            exec_step,
-            RW::READ,
-            StorageOp::new(
-                *l1_gas_price_oracle::ADDRESS,
-                *l1_gas_price_oracle::L1_BLOB_BASEFEE_SLOT,
-                l1_blob_basefee,
-                l1_blob_basefee,
-                tx_id,
-                l1_blob_basefee_committed,
-            ),
-        )?;
-        state.push_op(
-            exec_step,
-            RW::READ,
-            StorageOp::new(
-                *l1_gas_price_oracle::ADDRESS,
-                *l1_gas_price_oracle::COMMIT_SCALAR_SLOT,
-                commit_scalar,
-                commit_scalar,
-                tx_id,
-                commit_scalar_committed,
-            ),
-        )?;
+    let slots = state.tx.l1_fee.model().oracle_slots();
+    let values = state.tx.l1_fee.oracle_values();
+    let committed_values = state.tx.l1_fee_committed.oracle_values();
+    for ((slot, value), committed_value) in slots.into_iter().zip(values).zip(committed_values) {
+        let value = Word::from(value);
         state.push_op(
             exec_step,
             RW::READ,
             StorageOp::new(
                 *l1_gas_price_oracle::ADDRESS,
-                *l1_gas_price_oracle::BLOB_SCALAR_SLOT,
-                blob_scalar,
-                blob_scalar,
+                slot,
+                value,
+                value,
                 tx_id,
-                blob_scalar_committed,
+                Word::from(committed_value),
             ),
         )?;
     }
@@ -1,9 +1,16 @@
 use crate::{
-    circuit_input_builder::{CircuitInputStateRef, ExecStep},
+    circuit_input_builder::{
+        history_storage::{is_history_storage_enabled, read_history_storage},
+        CircuitInputStateRef, ExecStep,
+    },
+    operation::CallContextField,
     Error,
 };
 use eth_types::{
-    evm_types::block_utils::{calculate_block_hash, is_valid_block_number},
+    evm_types::block_utils::{
+        calculate_block_hash, is_valid_block_number, is_valid_block_number_in_window,
+        HISTORY_SERVE_WINDOW,
+    },
     GethExecStep,
 };
 
@@ -25,7 +32,23 @@ impl Opcode for Blockhash {
         assert_eq!(block_number, geth_step.stack.last()?);
 
         let current_block_number = state.tx.block_num;
-        let block_hash = if is_valid_block_number(block_number, current_block_number.into()) {
+        let block_hash = if is_history_storage_enabled(state.block.chain_id, current_block_number) {
+            if is_valid_block_number_in_window(
+                block_number,
+                current_block_number.into(),
+                HISTORY_SERVE_WINDOW,
+            ) {
+                state.call_context_read(
+                    &mut exec_step,
+                    state.call()?.call_id,
+                    CallContextField::TxId,
+                    state.tx_ctx.id().into(),
+                )?;
+                read_history_storage(state, &mut exec_step, block_number.as_u64())?
+            } else {
+                0.into()
+            }
+        } else if is_valid_block_number(block_number, current_block_number.into()) {
             if cfg!(feature = "scroll") {
                 let (sha3_input, sha3_output) =
                     calculate_block_hash(state.block.chain_id, block_number);
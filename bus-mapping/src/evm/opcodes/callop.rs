@@ -17,8 +17,8 @@ use eth_types::{
         gas_utils::{eip150_gas, memory_expansion_gas_cost},
         Gas, GasCost, OpcodeId, GAS_STIPEND_CALL_WITH_VALUE,
     },
+    forks::ChainSpec,
     state_db::CodeDB,
-    utils::is_precompiled,
     GethExecStep, ToWord, Word,
 };
 use std::cmp::min;
@@ -195,7 +195,7 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
 
         let code_address = callee_call.code_address();
         let is_precompile = code_address
-            .map(|ref addr| is_precompiled(addr))
+            .map(|ref addr| state.is_precompiled(addr))
             .unwrap_or(false);
         // CALLCODE does not need to do real transfer.
         // Transfer value only for CALL opcode, is_precheck_ok = true.
@@ -287,11 +287,13 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
             // 1. Call to precompiled.
             (false, true, _) => {
                 let code_address = code_address.unwrap();
-                let precompile_call: PrecompileCalls = code_address.0[19].into();
+                let precompile_call: PrecompileCalls = code_address.into();
 
                 // get the result of the precompile call.
                 // For failed call, it will cost all gas provided.
                 let (result, precompile_call_gas_cost, has_oog_err) = execute_precompiled(
+                    &ChainSpec::for_chain_id(state.block.chain_id),
+                    state.tx.block_num,
                     &code_address,
                     if args_length != 0 {
                         let caller_memory = &state.caller_ctx()?.memory;
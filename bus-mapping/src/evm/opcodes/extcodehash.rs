@@ -76,6 +76,21 @@ impl Opcode for Extcodehash {
             },
             code_hash.to_word(),
         )?;
+        if cfg!(feature = "scroll") {
+            // the circuit looks the code hashes up in the bytecode table, which needs the code.
+            let poseidon_code_hash = if exists {
+                state.code(account.code_hash)?;
+                account.code_hash
+            } else {
+                H256::zero()
+            };
+            state.account_read(
+                &mut exec_step,
+                external_address,
+                AccountField::CodeHash,
+                poseidon_code_hash.to_word(),
+            )?;
+        }
         #[cfg(feature = "enable-stack")]
         assert_eq!(steps[1].stack.last()?, code_hash.to_word());
         // Stack write of the result of EXTCODEHASH.
@@ -97,6 +112,7 @@ mod extcodehash_tests {
         address, bytecode,
         evm_types::{OpcodeId, StackAddress},
         geth_types::GethData,
+        state_db::CodeDB,
         Bytecode, Bytes, Word, U256,
     };
     use ethers_core::utils::keccak256;
@@ -178,7 +194,12 @@ mod extcodehash_tests {
         .unwrap()
         .into();
 
-        let code_hash = Word::from(keccak256(code_ext));
+        let code_hash = Word::from(keccak256(&code_ext));
+        let poseidon_code_hash = if exists {
+            CodeDB::hash(&code_ext).to_word()
+        } else {
+            Word::zero()
+        };
 
         let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
         builder
@@ -291,9 +312,29 @@ mod extcodehash_tests {
                 }
             )
         );
+        let stack_push_index = if cfg!(feature = "scroll") {
+            assert_eq!(
+                {
+                    let operation = &container.account[indices[6].as_usize()];
+                    (operation.rw(), operation.op())
+                },
+                (
+                    RW::READ,
+                    &AccountOp {
+                        address: external_address,
+                        field: AccountField::CodeHash,
+                        value: poseidon_code_hash,
+                        value_prev: poseidon_code_hash,
+                    }
+                )
+            );
+            7
+        } else {
+            6
+        };
         assert_eq!(
             {
-                let operation = &container.stack[indices[6].as_usize()];
+                let operation = &container.stack[indices[stack_push_index].as_usize()];
                 (operation.rw(), operation.op())
             },
             (
@@ -2,9 +2,7 @@ use eth_types::U256;
 use itertools::Itertools;
 
 use crate::{
-    circuit_input_builder::{
-        EcPairingOp, EcPairingPair, PrecompileEvent, N_BYTES_PER_PAIR, N_PAIRING_PER_OP,
-    },
+    circuit_input_builder::{EcPairingOp, EcPairingPair, PrecompileEvent, N_BYTES_PER_PAIR},
     precompile::{EcPairingAuxData, EcPairingError, PrecompileAuxData},
 };
 
@@ -12,6 +10,7 @@ pub(crate) fn opt_data(
     input_bytes: &[u8],
     output_bytes: &[u8],
     return_bytes: &[u8],
+    max_pairs: Option<usize>,
 ) -> (Option<PrecompileEvent>, Option<PrecompileAuxData>) {
     // assertions.
     let pairing_check = if output_bytes.is_empty() {
@@ -38,8 +37,8 @@ pub(crate) fn opt_data(
     }
 
     let op = if !input_bytes.is_empty() {
-        if (input_bytes.len() > N_PAIRING_PER_OP * N_BYTES_PER_PAIR)
-            || (input_bytes.len() % N_BYTES_PER_PAIR != 0)
+        if input_bytes.len() % N_BYTES_PER_PAIR != 0
+            || max_pairs.is_some_and(|max_pairs| input_bytes.len() > max_pairs * N_BYTES_PER_PAIR)
         {
             return (
                 None,
@@ -48,12 +47,8 @@ pub(crate) fn opt_data(
                 )))),
             );
         }
-        debug_assert!(
-            input_bytes.len() % N_BYTES_PER_PAIR == 0
-                && input_bytes.len() <= N_PAIRING_PER_OP * N_BYTES_PER_PAIR
-        );
         // process input bytes.
-        let mut pairs = input_bytes
+        let pairs = input_bytes
             .chunks_exact(N_BYTES_PER_PAIR)
             .map(|chunk| {
                 // process <= 192 bytes chunk at a time.
@@ -71,19 +66,16 @@ pub(crate) fn opt_data(
                 }
             })
             .collect_vec();
-        // pad the pairs to make them of fixed size: N_PAIRING_PER_OP.
-        pairs.resize(N_PAIRING_PER_OP, EcPairingPair::padding_pair());
         EcPairingOp {
-            pairs: <[_; N_PAIRING_PER_OP]>::try_from(pairs).unwrap(),
+            pairs,
             output: pairing_check,
             input_bytes: input_bytes.to_vec(),
             output_bytes: output_bytes.to_vec(),
             return_bytes: return_bytes.to_vec(),
         }
     } else {
-        let pairs = [EcPairingPair::padding_pair(); N_PAIRING_PER_OP];
         EcPairingOp {
-            pairs,
+            pairs: vec![],
             output: pairing_check,
             input_bytes: vec![],
             output_bytes: output_bytes.to_vec(),
@@ -5,7 +5,7 @@ use crate::{
         Call, CircuitInputStateRef, ExecState, ExecStep, PrecompileEvent, SHA256,
     },
     operation::CallContextField,
-    precompile::{PrecompileAuxData, PrecompileCalls},
+    precompile::{ec_pairing_max_pairs, modexp_size_limit, PrecompileAuxData, PrecompileCalls},
     Error,
 };
 
@@ -14,12 +14,14 @@ mod ec_mul;
 mod ec_pairing;
 mod ecrecover;
 mod modexp;
+mod p256_verify;
 
 use ec_add::opt_data as opt_data_ec_add;
 use ec_mul::opt_data as opt_data_ec_mul;
 use ec_pairing::opt_data as opt_data_ec_pairing;
 use ecrecover::opt_data as opt_data_ecrecover;
 use modexp::opt_data as opt_data_modexp;
+use p256_verify::opt_data as opt_data_p256_verify;
 
 pub fn gen_associated_ops(
     state: &mut CircuitInputStateRef,
@@ -62,10 +64,21 @@ pub fn gen_ops(
         PrecompileCalls::Ecrecover => opt_data_ecrecover(input_bytes, output_bytes, return_bytes),
         PrecompileCalls::Bn128Add => opt_data_ec_add(input_bytes, output_bytes, return_bytes),
         PrecompileCalls::Bn128Mul => opt_data_ec_mul(input_bytes, output_bytes, return_bytes),
-        PrecompileCalls::Bn128Pairing => {
-            opt_data_ec_pairing(input_bytes, output_bytes, return_bytes)
+        PrecompileCalls::Bn128Pairing => opt_data_ec_pairing(
+            input_bytes,
+            output_bytes,
+            return_bytes,
+            ec_pairing_max_pairs(state.block.chain_id, state.tx.block_num),
+        ),
+        PrecompileCalls::Modexp => opt_data_modexp(
+            input_bytes,
+            output_bytes,
+            return_bytes,
+            modexp_size_limit(state.block.chain_id, state.tx.block_num),
+        ),
+        PrecompileCalls::P256Verify => {
+            opt_data_p256_verify(input_bytes, output_bytes, return_bytes)
         }
-        PrecompileCalls::Modexp => opt_data_modexp(input_bytes, output_bytes, return_bytes),
         PrecompileCalls::Identity => (
             None,
             Some(PrecompileAuxData::Identity {
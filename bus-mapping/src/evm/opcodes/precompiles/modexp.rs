@@ -3,26 +3,25 @@ use crate::{
     precompile::{ModExpAuxData, PrecompileAuxData},
 };
 
-use eth_types::Word;
-
 pub(crate) fn opt_data(
     input_bytes: &[u8],
     output_bytes: &[u8],
     return_bytes: &[u8],
+    size_limit: usize,
 ) -> (Option<PrecompileEvent>, Option<PrecompileAuxData>) {
-    let aux_data = ModExpAuxData::new(input_bytes, output_bytes, return_bytes);
-    if aux_data.valid {
-        let event = BigModExp {
-            base: Word::from_big_endian(&aux_data.inputs[0]),
-            exponent: Word::from_big_endian(&aux_data.inputs[1]),
-            modulus: Word::from_big_endian(&aux_data.inputs[2]),
-            result: Word::from_big_endian(&aux_data.output),
-        };
-        (
-            Some(PrecompileEvent::ModExp(event)),
-            Some(PrecompileAuxData::Modexp(aux_data)),
-        )
-    } else {
-        (None, Some(PrecompileAuxData::Modexp(aux_data)))
-    }
+    let aux_data = ModExpAuxData::new(input_bytes, output_bytes, return_bytes, size_limit);
+    // the modexp circuit also proves the input lens of the invalid calls, so we always emit an
+    // event.
+    let [base, exponent, modulus] = aux_data.inputs.clone();
+    let event = BigModExp {
+        input: input_bytes.to_vec(),
+        base,
+        exponent,
+        modulus,
+        result: aux_data.output.clone(),
+    };
+    (
+        Some(PrecompileEvent::ModExp(event)),
+        Some(PrecompileAuxData::Modexp(aux_data)),
+    )
 }
@@ -0,0 +1,57 @@
+use eth_types::{
+    sign_types::{biguint_to_32bytes_le, P256SignData, SECP256R1_Q},
+    ToBigEndian, ToLittleEndian,
+};
+use halo2_proofs::halo2curves::{
+    group::ff::PrimeField,
+    secp256r1::{Fp, Fq, Secp256r1Affine},
+};
+use num::{BigUint, Integer};
+
+use crate::{
+    circuit_input_builder::PrecompileEvent,
+    precompile::{P256VerifyAuxData, PrecompileAuxData, P256VERIFY_INPUT_LEN},
+};
+
+pub(crate) fn opt_data(
+    input_bytes: &[u8],
+    output_bytes: &[u8],
+    return_bytes: &[u8],
+) -> (Option<PrecompileEvent>, Option<PrecompileAuxData>) {
+    let aux_data = P256VerifyAuxData::new(input_bytes, output_bytes, return_bytes);
+
+    // The precompile rejects any input that is not exactly 160 bytes long.
+    if input_bytes.len() != P256VERIFY_INPUT_LEN {
+        return (None, Some(PrecompileAuxData::P256Verify(aux_data)));
+    }
+
+    // We skip the validation through sig circuit if r, s or the public key coordinates were not
+    // in canonical form.
+    let opt_sig_r: Option<Fq> = Fq::from_bytes(&aux_data.sig_r.to_le_bytes()).into();
+    let opt_sig_s: Option<Fq> = Fq::from_bytes(&aux_data.sig_s.to_le_bytes()).into();
+    let opt_pk_x: Option<Fp> = Fp::from_bytes(&aux_data.pk_x.to_le_bytes()).into();
+    let opt_pk_y: Option<Fp> = Fp::from_bytes(&aux_data.pk_y.to_le_bytes()).into();
+    let (Some(sig_r), Some(sig_s), Some(pk_x), Some(pk_y)) =
+        (opt_sig_r, opt_sig_s, opt_pk_x, opt_pk_y)
+    else {
+        return (None, Some(PrecompileAuxData::P256Verify(aux_data)));
+    };
+
+    let sign_data = P256SignData {
+        signature: (sig_r, sig_s),
+        // the public key is not checked to be on the curve here, the sig circuit does that.
+        pk: Secp256r1Affine { x: pk_x, y: pk_y },
+        msg_hash: {
+            let msg_hash = BigUint::from_bytes_be(&aux_data.msg_hash.to_be_bytes());
+            let msg_hash = msg_hash.mod_floor(&*SECP256R1_Q);
+            let msg_hash_le = biguint_to_32bytes_le(msg_hash);
+            Fq::from_repr(msg_hash_le).unwrap()
+        },
+    };
+    debug_assert_eq!(sign_data.verify(), aux_data.is_valid);
+
+    (
+        Some(PrecompileEvent::P256Verify(sign_data)),
+        Some(PrecompileAuxData::P256Verify(aux_data)),
+    )
+}
@@ -1,20 +1,50 @@
 //! precompile helpers
 
-use eth_types::{evm_types::GasCost, Address, ToBigEndian, Word};
-use revm_precompile::{Precompile, PrecompileError, Precompiles};
+use eth_types::{
+    evm_types::GasCost,
+    forks::{ChainSpec, HardforkId},
+    Address, ToBigEndian, Word,
+};
+use num::{BigUint, Zero};
+#[cfg(feature = "scroll")]
+use revm_precompile::Precompiles;
+use revm_precompile::{Precompile, PrecompileError};
+use serde::{Deserialize, Serialize};
 use strum_macros::EnumIter;
 
-use crate::circuit_input_builder::{EcMulOp, EcPairingOp};
+use crate::circuit_input_builder::{EcMulOp, EcPairingOp, N_PAIRING_PER_OP};
 
 pub(crate) fn execute_precompiled(
+    chain_spec: &ChainSpec,
+    block_number: u64,
     address: &Address,
     input: &[u8],
     gas: u64,
 ) -> (Vec<u8>, u64, bool) {
     #[cfg(feature = "scroll")]
-    let precompiles = Precompiles::bernoulli();
+    let precompiles = if *address == PrecompileCalls::Modexp.into()
+        && chain_spec.is_active_at_block(HardforkId::LargeModexp, block_number)
+    {
+        // the bernoulli modexp rejects operands larger than 32 bytes, while the fork supports
+        // them up to `MODEXP_SIZE_LIMIT` bytes.
+        let mut header = input[..input.len().min(96)].to_vec();
+        header.resize(96, 0);
+        if !ModExpAuxData::check_input(&header, MODEXP_SIZE_LIMIT).0 {
+            return (vec![], gas, false);
+        }
+        Precompiles::berlin()
+    } else if *address == PrecompileCalls::Bn128Pairing.into()
+        && chain_spec.is_active_at_block(HardforkId::LargeEcPairing, block_number)
+    {
+        // the bernoulli ecPairing rejects more than 4 pairs, while the fork supports as many
+        // pairs as `PrecompileEcParams::ec_pairing_pairs`. A call with more pairs overflows the
+        // ECC circuit in the capacity checker, so that the tx is never included.
+        Precompiles::berlin()
+    } else {
+        chain_spec.precompiles_at_block(block_number)
+    };
     #[cfg(not(feature = "scroll"))]
-    let precompiles = Precompiles::berlin();
+    let precompiles = chain_spec.precompiles_at_block(block_number);
 
     let Some(Precompile::Standard(precompile_fn)) =
         precompiles.get(address.as_fixed_bytes().into())
@@ -42,7 +72,7 @@ pub(crate) fn execute_precompiled(
 }
 
 /// Addresses of the precompiled contracts.
-#[derive(Copy, Clone, Debug, Eq, PartialEq, EnumIter)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, EnumIter, Serialize, Deserialize)]
 pub enum PrecompileCalls {
     /// Elliptic Curve Recovery
     Ecrecover = 0x01,
@@ -62,6 +92,8 @@ pub enum PrecompileCalls {
     Bn128Pairing = 0x08,
     /// Compression function
     Blake2F = 0x09,
+    /// secp256r1 signature verification (RIP-7212)
+    P256Verify = 0x100,
 }
 
 impl Default for PrecompileCalls {
@@ -72,9 +104,7 @@ impl Default for PrecompileCalls {
 
 impl From<PrecompileCalls> for Address {
     fn from(value: PrecompileCalls) -> Self {
-        let mut addr = [0u8; 20];
-        addr[19] = value as u8;
-        Self(addr)
+        Self::from_low_u64_be(value.into())
     }
 }
 
@@ -107,6 +137,20 @@ impl From<u8> for PrecompileCalls {
     }
 }
 
+impl From<Address> for PrecompileCalls {
+    fn from(value: Address) -> Self {
+        if value == Self::P256Verify.into() {
+            Self::P256Verify
+        } else {
+            debug_assert!(
+                value[..19].iter().all(|&b| b == 0),
+                "not a precompile {value:?}"
+            );
+            value[19].into()
+        }
+    }
+}
+
 impl PrecompileCalls {
     /// Get the base gas cost for the precompile call.
     pub fn base_gas_cost(&self) -> GasCost {
@@ -120,6 +164,7 @@ impl PrecompileCalls {
             Self::Bn128Mul => GasCost::PRECOMPILE_BN256MUL,
             Self::Bn128Pairing => GasCost::PRECOMPILE_BN256PAIRING,
             Self::Blake2F => GasCost::PRECOMPILE_BLAKE2F,
+            Self::P256Verify => GasCost::PRECOMPILE_P256VERIFY,
         }
     }
 
@@ -134,13 +179,14 @@ impl PrecompileCalls {
             Self::Ecrecover | Self::Bn128Add => Some(128),
             Self::Bn128Mul => Some(96),
             Self::Modexp => Some(MODEXP_INPUT_LIMIT),
+            Self::P256Verify => Some(P256VERIFY_INPUT_LEN),
             _ => None,
         }
     }
 }
 
 /// Auxiliary data for Ecrecover
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EcrecoverAuxData {
     /// Keccak hash of the message being signed.
     pub msg_hash: Word,
@@ -196,24 +242,117 @@ impl EcrecoverAuxData {
     }
 }
 
-/// size limit of modexp
-pub const MODEXP_SIZE_LIMIT: usize = 32;
+/// Length of a well-formed P256VERIFY input: [msg_hash | r | s | pk_x | pk_y]
+pub const P256VERIFY_INPUT_LEN: usize = 160;
+
+/// Auxiliary data for P256Verify
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct P256VerifyAuxData {
+    /// Hash of the message being signed.
+    pub msg_hash: Word,
+    /// r-component of signature.
+    pub sig_r: Word,
+    /// s-component of signature.
+    pub sig_s: Word,
+    /// x co-ordinate of the public key.
+    pub pk_x: Word,
+    /// y co-ordinate of the public key.
+    pub pk_y: Word,
+    /// Whether the signature was verified, i.e. the output is 1 rather than empty.
+    pub is_valid: bool,
+    /// Input bytes to the P256Verify call.
+    pub input_bytes: Vec<u8>,
+    /// Output bytes from the P256Verify call.
+    pub output_bytes: Vec<u8>,
+    /// Bytes returned to the caller from the P256Verify call.
+    pub return_bytes: Vec<u8>,
+}
+
+impl P256VerifyAuxData {
+    /// Create a new instance of P256Verify auxiliary data.
+    pub fn new(input: &[u8], output: &[u8], return_bytes: &[u8]) -> Self {
+        let mut resized_input = input.to_vec();
+        resized_input.resize(P256VERIFY_INPUT_LEN, 0u8);
+
+        // a successful verification returns 32 bytes of big-endian 1, a failed one returns
+        // nothing.
+        let is_valid = !output.is_empty();
+        assert!(!is_valid || Word::from_big_endian(output) == Word::one());
+
+        Self {
+            msg_hash: Word::from_big_endian(&resized_input[0x00..0x20]),
+            sig_r: Word::from_big_endian(&resized_input[0x20..0x40]),
+            sig_s: Word::from_big_endian(&resized_input[0x40..0x60]),
+            pk_x: Word::from_big_endian(&resized_input[0x60..0x80]),
+            pk_y: Word::from_big_endian(&resized_input[0x80..0xa0]),
+            is_valid,
+            input_bytes: input.to_vec(),
+            output_bytes: output.to_vec(),
+            return_bytes: return_bytes.to_vec(),
+        }
+    }
+}
+
+/// Size limit of the modexp operands once `HardforkId::LargeModexp` is active, which covers the
+/// 2048-bit moduli of RSA.
+pub const MODEXP_SIZE_LIMIT: usize = 256;
+/// Limit of the block rows of a modexp call once `HardforkId::LargeModexp` is active, see
+/// `modexp_block_rows`.
+///
+/// A call takes `MODEXP_INPUT_LIMIT + max(base_len, mod_len) + modexp_block_rows` rows of the
+/// ModExp circuit, i.e. at most 264,288 rows. The capacity checker allows the circuit 950,000 rows
+/// (`MAX_KECCAK_ROWS` at 95% confidence), so a chunk fits three worst case calls, e.g. a 256 bytes
+/// modulus with a 64 bytes exponent. An RSA verification with a 256 bytes modulus and the
+/// exponent 65537 takes 13,920 rows. Bounding each of the operands instead would allow a 256 bytes
+/// modulus with a 256 bytes exponent, i.e. 1,050,208 rows that could never be proven.
+pub const MODEXP_BLOCK_ROWS_LIMIT: usize = MODEXP_SIZE_LIMIT * (2 + 16 * 64);
+/// Size limit of the modexp operands before `HardforkId::LargeModexp`, as in bernoulli.
+pub const MODEXP_BERNOULLI_SIZE_LIMIT: usize = 32;
 /// size of input limit
-pub const MODEXP_INPUT_LIMIT: usize = 192;
+pub const MODEXP_INPUT_LIMIT: usize = 96 + 3 * MODEXP_SIZE_LIMIT;
+
+/// Maximum number of pairs of an ecPairing call of the chain at this block, `None` if the number
+/// is only limited by the ECC circuit, see `PrecompileEcParams::ec_pairing_pairs`.
+pub fn ec_pairing_max_pairs(chain_id: u64, block_number: u64) -> Option<usize> {
+    if ChainSpec::for_chain_id(chain_id)
+        .is_active_at_block(HardforkId::LargeEcPairing, block_number)
+    {
+        None
+    } else {
+        // as in bernoulli
+        Some(N_PAIRING_PER_OP)
+    }
+}
+
+/// Number of rows taken by the multiplication blocks of a modexp call, which prove the reduction
+/// of the accumulator, its square and its product with the base for each exponent bit.
+pub fn modexp_block_rows(exp_len: usize, mod_len: usize) -> usize {
+    mod_len * (2 + 16 * exp_len)
+}
+
+/// Size limit of the modexp operands of the chain at this block.
+pub fn modexp_size_limit(chain_id: u64, block_number: u64) -> usize {
+    if ChainSpec::for_chain_id(chain_id).is_active_at_block(HardforkId::LargeModexp, block_number) {
+        MODEXP_SIZE_LIMIT
+    } else {
+        MODEXP_BERNOULLI_SIZE_LIMIT
+    }
+}
 
 /// Auxiliary data for Modexp
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ModExpAuxData {
     /// The specified len of inputs: [base, exp, modulus]
     pub input_lens: [Word; 3],
-    /// Input value [base, exp, modulus], limited to SIZE_LIMIT
-    pub inputs: [[u8; MODEXP_SIZE_LIMIT]; 3],
+    /// Input value [base, exp, modulus], as big-endian bytes of the specified lens, empty if
+    /// the input is invalid
+    pub inputs: [Vec<u8>; 3],
     /// Input valid.
     pub valid: bool,
     /// len of output, limited to lens of moduls, but can be 0
     pub output_len: usize,
-    /// output of modexp.
-    pub output: [u8; MODEXP_SIZE_LIMIT],
+    /// Result of modexp, as big-endian bytes of the modulus len, empty if the input is invalid
+    pub output: Vec<u8>,
     /// Input to the modexp call.
     pub input_bytes: Vec<u8>,
     /// Output bytes from the modexp call.
@@ -223,29 +362,21 @@ pub struct ModExpAuxData {
 }
 
 impl ModExpAuxData {
-    /// If mem is smaller than U256, left pad zero
-    /// Or else, keep the least 32bytes.
-    fn parse_memory_to_value(mem: &[u8]) -> [u8; MODEXP_SIZE_LIMIT] {
-        let mut value_bytes = [0u8; MODEXP_SIZE_LIMIT];
-        if !mem.is_empty() {
-            let copy_len = mem.len().min(MODEXP_SIZE_LIMIT);
-            let src_offset = mem.len() - copy_len;
-            let dst_offset = MODEXP_SIZE_LIMIT - copy_len;
-            value_bytes.as_mut_slice()[dst_offset..].copy_from_slice(&mem[src_offset..]);
-        }
-        value_bytes
-    }
-
-    /// check input
-    pub fn check_input(input: &[u8]) -> (bool, [Word; 3]) {
+    /// Check the input lens against the size limit of the operands and the rows they take in the
+    /// ModExp circuit, see `MODEXP_BLOCK_ROWS_LIMIT`.
+    pub fn check_input(input: &[u8], size_limit: usize) -> (bool, [Word; 3]) {
         let mut i = input.chunks(32);
         let base_len = Word::from_big_endian(i.next().unwrap_or(&[]));
         let exp_len = Word::from_big_endian(i.next().unwrap_or(&[]));
         let modulus_len = Word::from_big_endian(i.next().unwrap_or(&[]));
 
-        let limit = Word::from(MODEXP_SIZE_LIMIT);
+        let limit = Word::from(size_limit);
 
-        let input_valid = base_len <= limit && exp_len <= limit && modulus_len <= limit;
+        let input_valid = base_len <= limit
+            && exp_len <= limit
+            && modulus_len <= limit
+            && modexp_block_rows(exp_len.as_usize(), modulus_len.as_usize())
+                <= MODEXP_BLOCK_ROWS_LIMIT;
         log::debug!("modexp base_len {base_len} exp_len {exp_len} modulus_len {modulus_len}");
         if !input_valid {
             log::warn!("modexp input input_valid {input_valid}");
@@ -253,48 +384,50 @@ impl ModExpAuxData {
         (input_valid, [base_len, exp_len, modulus_len])
     }
 
-    /// Create a new instance of modexp auxiliary data.
-    pub fn new(input: &[u8], output: &[u8], return_bytes: &[u8]) -> Self {
+    /// Compute `base ** exp % modulus` as big-endian bytes of the modulus len, the result is 0
+    /// if the modulus is 0.
+    fn mod_exp([base, exp, modulus]: &[Vec<u8>; 3]) -> Vec<u8> {
+        let modulus_value = BigUint::from_bytes_be(modulus);
+        let mut output = vec![0u8; modulus.len()];
+        if !modulus_value.is_zero() {
+            let result = BigUint::from_bytes_be(base)
+                .modpow(&BigUint::from_bytes_be(exp), &modulus_value)
+                .to_bytes_be();
+            output[modulus.len() - result.len()..].copy_from_slice(&result);
+        }
+        output
+    }
+
+    /// Create a new instance of modexp auxiliary data, for operands of up to `size_limit` bytes.
+    pub fn new(input: &[u8], output: &[u8], return_bytes: &[u8], size_limit: usize) -> Self {
         let mut resized_input = input.to_vec();
         if resized_input.len() < 96 {
             resized_input.resize(96, 0);
         }
 
-        let (input_valid, [base_len, exp_len, modulus_len]) = Self::check_input(&resized_input);
+        let (input_valid, input_lens) = Self::check_input(&resized_input, size_limit);
 
-        let base_mem_len = if input_valid { base_len.as_usize() } else { 0 };
-        let exp_mem_len = if input_valid { exp_len.as_usize() } else { 0 };
-        let modulus_mem_len = if input_valid {
-            modulus_len.as_usize()
-        } else {
-            0
-        };
-
-        let (base, exp, modulus) = if base_mem_len == 0 && modulus_mem_len == 0 {
-            // special case
-            ([0u8; 32], [0u8; 32], [0u8; 32])
-        } else {
-            // In non scroll mode, this can be dangerous.
-            // If base and mod are all 0, exp can be very huge.
-            resized_input.resize(96 + base_mem_len + exp_mem_len + modulus_mem_len, 0);
+        let (inputs, result) = if input_valid {
+            let lens = input_lens.map(|len| len.as_usize());
+            resized_input.resize(96 + lens.iter().sum::<usize>(), 0);
             let mut cur_input_begin = &resized_input[96..];
-
-            let base = Self::parse_memory_to_value(&cur_input_begin[..base_mem_len]);
-            cur_input_begin = &cur_input_begin[base_mem_len..];
-            let exp = Self::parse_memory_to_value(&cur_input_begin[..exp_mem_len]);
-            cur_input_begin = &cur_input_begin[exp_mem_len..];
-            let modulus = Self::parse_memory_to_value(&cur_input_begin[..modulus_mem_len]);
-            (base, exp, modulus)
+            let inputs = lens.map(|len| {
+                let (value, rest) = cur_input_begin.split_at(len);
+                cur_input_begin = rest;
+                value.to_vec()
+            });
+            let result = Self::mod_exp(&inputs);
+            (inputs, result)
+        } else {
+            (Default::default(), Vec::new())
         };
-        let output_len = output.len();
-        let output = Self::parse_memory_to_value(output);
 
         Self {
             valid: input_valid,
-            input_lens: [base_len, exp_len, modulus_len],
-            inputs: [base, exp, modulus],
-            output,
-            output_len,
+            input_lens,
+            inputs,
+            output: result,
+            output_len: output.len(),
             input_bytes: input.to_vec(),
             output_bytes: output.to_vec(),
             return_bytes: return_bytes.to_vec(),
@@ -303,7 +436,7 @@ impl ModExpAuxData {
 }
 
 /// Auxiliary data for EcAdd, i.e. P + Q = R
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EcAddAuxData {
     /// x co-ordinate of the first point.
     pub p_x: Word,
@@ -348,7 +481,7 @@ impl EcAddAuxData {
 }
 
 /// Auxiliary data for EcMul, i.e. s * P = R
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EcMulAuxData {
     /// x co-ordinate of the point.
     pub p_x: Word,
@@ -395,20 +528,20 @@ impl EcMulAuxData {
 }
 
 /// Auxiliary data for EcPairing.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EcPairingAuxData(pub EcPairingOp);
 
 /// Erroneous bytes passed to the EcPairing precompile call.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EcPairingError {
     /// the calldatalength passed to EcPairing precompile call is expected to be:
-    /// 1. len(input) <= 768
-    /// 2. len(input) % 192 == 0
+    /// 1. len(input) % 192 == 0
+    /// 2. len(input) <= 768, before `HardforkId::LargeEcPairing`
     InvalidInputLen(Vec<u8>),
 }
 
 /// Auxiliary data attached to an internal state for precompile verification.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PrecompileAuxData {
     /// Base precompile (used for SHA256, RIPEMD-160 and BLAKE2F).
     Base {
@@ -447,6 +580,8 @@ pub enum PrecompileAuxData {
     EcMul(EcMulAuxData),
     /// EcPairing.
     EcPairing(Box<Result<EcPairingAuxData, EcPairingError>>),
+    /// P256Verify.
+    P256Verify(P256VerifyAuxData),
 }
 
 impl Default for PrecompileAuxData {
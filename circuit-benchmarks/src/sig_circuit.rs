@@ -0,0 +1,139 @@
+//! Sig circuit benchmarks
+
+#[cfg(test)]
+mod tests {
+    use ark_std::{end_timer, start_timer};
+    use env_logger::Env;
+    use eth_types::sign_types::{sign, SignData};
+    use ethers::utils::keccak256;
+    use halo2_proofs::{
+        arithmetic::Field,
+        halo2curves::{
+            bn256::{Bn256, Fr, G1Affine},
+            group::{Curve, Group},
+            secp256k1::{Fq, Secp256k1},
+        },
+        plonk::{create_proof, keygen_pk, keygen_vk, verify_proof},
+        poly::{
+            commitment::ParamsProver,
+            kzg::{
+                commitment::{KZGCommitmentScheme, ParamsKZG, ParamsVerifierKZG},
+                multiopen::{ProverSHPLONK, VerifierSHPLONK},
+                strategy::SingleStrategy,
+            },
+        },
+        transcript::{
+            Blake2bRead, Blake2bWrite, Challenge255, TranscriptReadBuffer, TranscriptWriterBuffer,
+        },
+    };
+    use rand::{Rng, RngCore, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+    use std::env::var;
+    use zkevm_circuits::sig_circuit::SigCircuit;
+
+    #[cfg_attr(not(feature = "benches"), ignore)]
+    #[cfg_attr(not(feature = "print-trace"), allow(unused_variables))] // FIXME: remove this after ark-std upgrade
+    #[test]
+    fn bench_sig_circuit_prover() {
+        env_logger::Builder::from_env(Env::default().default_filter_or("debug")).init();
+        let setup_prfx = crate::constants::SETUP_PREFIX;
+        let proof_gen_prfx = crate::constants::PROOFGEN_PREFIX;
+        let proof_ver_prfx = crate::constants::PROOFVER_PREFIX;
+        // Unique string used by bench results module for parsing the result
+        const BENCHMARK_ID: &str = "Sig Circuit";
+
+        // The ecdsa chip is laid out for 2^20 rows
+        let degree: u32 = var("DEGREE")
+            .unwrap_or_else(|_| "20".to_string())
+            .parse()
+            .expect("Cannot parse DEGREE env var as u32");
+        let num_sigs: usize = var("NUM_SIGS")
+            .unwrap_or_else(|_| "64".to_string())
+            .parse()
+            .expect("Cannot parse NUM_SIGS env var as usize");
+
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        // Initialize the circuit
+        let mut circuit = SigCircuit::<Fr>::new(num_sigs);
+        circuit.signatures = (0..num_sigs).map(|_| gen_sign_data(&mut rng)).collect();
+
+        // Bench setup generation
+        let setup_message = format!("{BENCHMARK_ID} {setup_prfx} with degree = {degree}");
+        let start1 = start_timer!(|| setup_message);
+        let general_params = ParamsKZG::<Bn256>::setup(degree, &mut rng);
+        let verifier_params: ParamsVerifierKZG<Bn256> = general_params.verifier_params().clone();
+        end_timer!(start1);
+
+        // Initialize the proving key
+        let vk = keygen_vk(&general_params, &circuit).expect("keygen_vk should not fail");
+        let pk = keygen_pk(&general_params, vk, &circuit).expect("keygen_pk should not fail");
+        // Create a proof
+        let mut transcript = Blake2bWrite::<_, G1Affine, Challenge255<_>>::init(vec![]);
+
+        // Bench proof generation time
+        let proof_message = format!(
+            "{BENCHMARK_ID} {proof_gen_prfx} with degree = {degree}, {num_sigs} signatures"
+        );
+        let start2 = start_timer!(|| proof_message);
+        create_proof::<
+            KZGCommitmentScheme<Bn256>,
+            ProverSHPLONK<'_, Bn256>,
+            Challenge255<G1Affine>,
+            XorShiftRng,
+            Blake2bWrite<Vec<u8>, G1Affine, Challenge255<G1Affine>>,
+            SigCircuit<Fr>,
+        >(
+            &general_params,
+            &pk,
+            &[circuit],
+            &[&[]],
+            rng,
+            &mut transcript,
+        )
+        .expect("proof generation should not fail");
+        let proof = transcript.finalize();
+        end_timer!(start2);
+
+        // Bench verification time
+        let start3 = start_timer!(|| format!("{BENCHMARK_ID} {proof_ver_prfx}"));
+        let mut verifier_transcript = Blake2bRead::<_, G1Affine, Challenge255<_>>::init(&proof[..]);
+        let strategy = SingleStrategy::new(&general_params);
+
+        verify_proof::<
+            KZGCommitmentScheme<Bn256>,
+            VerifierSHPLONK<'_, Bn256>,
+            Challenge255<G1Affine>,
+            Blake2bRead<&[u8], G1Affine, Challenge255<G1Affine>>,
+            SingleStrategy<'_, Bn256>,
+        >(
+            &verifier_params,
+            pk.get_vk(),
+            strategy,
+            &[&[]],
+            &mut verifier_transcript,
+        )
+        .expect("failed to verify bench circuit");
+        end_timer!(start3);
+    }
+
+    fn gen_sign_data(mut rng: impl RngCore) -> SignData {
+        let sk = Fq::random(&mut rng);
+        let pk = (Secp256k1::generator() * sk).to_affine();
+
+        let mut msg = vec![0; rng.gen_range(0..128)];
+        rng.fill_bytes(&mut msg);
+        let msg_hash = Fq::from_bytes(&keccak256(&msg)).unwrap();
+        let randomness = Fq::random(&mut rng);
+
+        SignData {
+            signature: sign(randomness, sk, msg_hash),
+            pk,
+            msg: msg.into(),
+            msg_hash,
+        }
+    }
+}
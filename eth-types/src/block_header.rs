@@ -0,0 +1,118 @@
+//! The header of a block.
+
+use crate::{Address, Block, Bytes, H256, H64, U256, U64};
+use ethers_core::types::Bloom;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Header of a block, holding the header fields of the block, including the ones unknown to
+/// `Block`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockHeader {
+    /// Hash of the parent block
+    pub parent_hash: H256,
+    /// Hash of the list of ommers
+    pub ommers_hash: H256,
+    /// Address receiving the fees of the block
+    pub beneficiary: Address,
+    /// State root after the block
+    pub state_root: H256,
+    /// Root of the transactions trie
+    pub transactions_root: H256,
+    /// Root of the receipts trie
+    pub receipts_root: H256,
+    /// Bloom filter of the logs of the block
+    pub logs_bloom: Bloom,
+    /// Difficulty
+    pub difficulty: U256,
+    /// Block number
+    pub number: u64,
+    /// Gas limit
+    pub gas_limit: u64,
+    /// Gas used by the transactions of the block
+    pub gas_used: u64,
+    /// Timestamp
+    pub timestamp: u64,
+    /// Extra data
+    pub extra_data: Bytes,
+    /// Mix hash
+    pub mix_hash: H256,
+    /// Nonce
+    pub nonce: H64,
+    /// Base fee per gas, since EIP-1559
+    pub base_fee_per_gas: Option<U256>,
+    /// Blob gas used by the transactions of the block, since EIP-4844
+    pub blob_gas_used: Option<u64>,
+    /// Excess blob gas, since EIP-4844
+    pub excess_blob_gas: Option<u64>,
+    /// Root of the parent beacon block, since EIP-4788
+    pub parent_beacon_block_root: Option<H256>,
+}
+
+impl<TX> From<&Block<TX>> for BlockHeader {
+    fn from(block: &Block<TX>) -> Self {
+        Self {
+            parent_hash: block.parent_hash,
+            ommers_hash: block.uncles_hash,
+            beneficiary: block.author.unwrap_or_default(),
+            state_root: block.state_root,
+            transactions_root: block.transactions_root,
+            receipts_root: block.receipts_root,
+            logs_bloom: block.logs_bloom.unwrap_or_default(),
+            difficulty: block.difficulty,
+            number: block.number.unwrap_or_default().low_u64(),
+            gas_limit: block.gas_limit.low_u64(),
+            gas_used: block.gas_used.low_u64(),
+            timestamp: block.timestamp.low_u64(),
+            extra_data: block.extra_data.clone(),
+            mix_hash: block.mix_hash.unwrap_or_default(),
+            nonce: block.nonce.unwrap_or_default(),
+            base_fee_per_gas: block.base_fee_per_gas,
+            blob_gas_used: other_field::<_, U64>(block, "blobGasUsed").map(|gas| gas.as_u64()),
+            excess_blob_gas: other_field::<_, U64>(block, "excessBlobGas").map(|gas| gas.as_u64()),
+            parent_beacon_block_root: parent_beacon_block_root(block),
+        }
+    }
+}
+
+/// Root of the parent beacon block of `block`, since EIP-4788.
+pub fn parent_beacon_block_root<TX>(block: &Block<TX>) -> Option<H256> {
+    other_field(block, PARENT_BEACON_BLOCK_ROOT)
+}
+
+/// Set the root of the parent beacon block of `block`.
+pub fn set_parent_beacon_block_root<TX>(block: &mut Block<TX>, root: H256) {
+    block.other.insert(
+        PARENT_BEACON_BLOCK_ROOT.to_string(),
+        serde_json::to_value(root).expect("hash to json"),
+    );
+}
+
+const PARENT_BEACON_BLOCK_ROOT: &str = "parentBeaconBlockRoot";
+
+/// A header field unknown to `Block`, kept in its other fields.
+fn other_field<TX, T: DeserializeOwned>(block: &Block<TX>, key: &str) -> Option<T> {
+    block.other.get_deserialized(key).and_then(Result::ok)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancun_fields_from_block() {
+        let root = H256::repeat_byte(0xbe);
+        let mut block = Block::<()>::default();
+        set_parent_beacon_block_root(&mut block, root);
+        block
+            .other
+            .insert("blobGasUsed".to_string(), "0x20000".into());
+        block
+            .other
+            .insert("excessBlobGas".to_string(), "0x0".into());
+
+        let header = BlockHeader::from(&block);
+        assert_eq!(header.parent_beacon_block_root, Some(root));
+        assert_eq!(header.blob_gas_used, Some(0x20000));
+        assert_eq!(header.excess_blob_gas, Some(0));
+    }
+}
@@ -3,8 +3,12 @@
 use std::str::FromStr;
 
 use ethers_core::types::{Address, U256};
+use serde::{Deserialize, Serialize};
 
-use crate::l2_types::BlockTrace;
+use crate::{
+    l2_types::BlockTrace,
+    scoped::{Scope, ScopedValue},
+};
 
 /// Read env var with default value
 pub fn read_env_var<T: Clone + FromStr>(var_name: &'static str, default: T) -> T {
@@ -16,39 +20,102 @@ pub fn read_env_var<T: Clone + FromStr>(var_name: &'static str, default: T) -> T
 /// Scroll coinbase
 pub const SCROLL_COINBASE: &str = "0x5300000000000000000000000000000000000005";
 
+/// Chain id used when neither a [`BlockConstants`] nor the `CHAIN_ID` env var is set.
+pub const DEFAULT_CHAIN_ID: u64 = 534352;
+
+/// Per-chain block constants the circuits are built for.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockConstants {
+    /// Chain id
+    pub chain_id: u64,
+    /// Coinbase of every block
+    pub coinbase: Address,
+    /// Difficulty of every block
+    pub difficulty: U256,
+}
+
+static BLOCK_CONSTANTS: ScopedValue<BlockConstants> = ScopedValue::new();
+
+impl BlockConstants {
+    /// Load the constants from the `CHAIN_ID`, `COINBASE` and `DIFFICULTY` env vars.
+    pub fn from_env() -> Self {
+        let default_coinbase = if cfg!(feature = "scroll") {
+            Address::from_str(SCROLL_COINBASE).unwrap()
+        } else {
+            Address::zero()
+        };
+        Self {
+            chain_id: read_env_var("CHAIN_ID", DEFAULT_CHAIN_ID),
+            coinbase: read_env_var("COINBASE", default_coinbase),
+            difficulty: read_env_var("DIFFICULTY", U256::zero()),
+        }
+    }
+
+    /// Constants of the chain `trace` belongs to.
+    pub fn from_trace(trace: &BlockTrace) -> Self {
+        Self {
+            chain_id: trace.chain_id,
+            coinbase: trace.coinbase.address.unwrap(),
+            difficulty: U256::zero(),
+        }
+    }
+
+    /// The constants installed by [`BlockConstants::scoped`], or the env vars otherwise.
+    pub fn current() -> Self {
+        BLOCK_CONSTANTS.get_or_else(Self::from_env)
+    }
+
+    /// Run `f` with these constants installed, see [`Scope::run`].
+    pub fn scoped<R: Send>(&self, f: impl FnOnce() -> R + Send) -> R {
+        self.install(Scope::new()).run(f)
+    }
+
+    /// Add these constants to the values installed by `scope`.
+    pub fn install(&self, scope: Scope) -> Scope {
+        scope.set(&BLOCK_CONSTANTS, self.clone())
+    }
+}
+
 /// Get COINBASE constant used for circuit
 pub fn get_coinbase_constant() -> Address {
-    let default_coinbase = if cfg!(feature = "scroll") {
-        Address::from_str(SCROLL_COINBASE).unwrap()
-    } else {
-        Address::zero()
-    };
-    read_env_var("COINBASE", default_coinbase)
+    BlockConstants::current().coinbase
+}
+
+/// Get DIFFICULTY constant used for circuit
+pub fn get_difficulty_constant() -> U256 {
+    BlockConstants::current().difficulty
+}
+
+/// Get CHAIN_ID constant used for circuit
+pub fn get_chain_id_constant() -> u64 {
+    BlockConstants::current().chain_id
 }
 
 /// Set COINBASE env var
+#[deprecated(note = "install the constants with `BlockConstants::scoped` instead")]
 pub fn set_env_coinbase(coinbase: &Address) -> String {
     let coinbase = format!("0x{}", hex::encode(coinbase));
     std::env::set_var("COINBASE", &coinbase);
     coinbase
 }
 
-/// Get DIFFICULTY constant used for circuit
-pub fn get_difficulty_constant() -> U256 {
-    read_env_var("DIFFICULTY", U256::zero())
-}
-
 ///  Set scroll block constants using trace
+#[deprecated(note = "install `BlockConstants::from_trace` with `BlockConstants::scoped` instead")]
 pub fn set_scroll_block_constants_with_trace(trace: &BlockTrace) {
+    let constants = BlockConstants::from_trace(trace);
+    #[allow(deprecated)]
     set_scroll_block_constants(
-        &trace.coinbase.address.unwrap(),
-        trace.chain_id,
-        U256::zero(),
+        &constants.coinbase,
+        constants.chain_id,
+        constants.difficulty,
     )
 }
 
-/// Set scroll block constants
+/// Set scroll block constants in the env vars, which [`BlockConstants::current`] reads outside of
+/// a scope.
+#[deprecated(note = "install the constants with `BlockConstants::scoped` instead")]
 pub fn set_scroll_block_constants(coinbase: &Address, chain_id: u64, difficulty: U256) {
+    #[allow(deprecated)]
     set_env_coinbase(coinbase);
     std::env::set_var("CHAIN_ID", format!("{}", chain_id));
     std::env::set_var("DIFFICULTY", difficulty.to_string());
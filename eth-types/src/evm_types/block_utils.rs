@@ -6,6 +6,9 @@ use ethers_core::utils::keccak256;
 /// Maximum range of previous blocks allowed inside BLOCKHASH opcode
 pub const NUM_PREV_BLOCK_ALLOWED: u64 = 256;
 
+/// Range of previous blocks served by the history storage contract (EIP-2935)
+pub const HISTORY_SERVE_WINDOW: u64 = 8191;
+
 /// Calculate block hash by chain ID and block number (only for scroll).
 /// Return a pair of input and output.
 pub fn calculate_block_hash(chain_id: u64, block_number: U256) -> (Vec<u8>, U256) {
@@ -21,9 +24,18 @@ pub fn calculate_block_hash(chain_id: u64, block_number: U256) -> (Vec<u8>, U256
 
 /// Check if a block number is valid corresponding to the current block number.
 pub fn is_valid_block_number(block_number: U256, current_block_number: U256) -> bool {
+    is_valid_block_number_in_window(block_number, current_block_number, NUM_PREV_BLOCK_ALLOWED)
+}
+
+/// Check if a block number is one of the `window` blocks before the current block number.
+pub fn is_valid_block_number_in_window(
+    block_number: U256,
+    current_block_number: U256,
+    window: u64,
+) -> bool {
     block_number < current_block_number
         && block_number
             >= current_block_number
-                .checked_sub(NUM_PREV_BLOCK_ALLOWED.into())
+                .checked_sub(window.into())
                 .unwrap_or_default()
 }
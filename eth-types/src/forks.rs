@@ -1,11 +1,91 @@
 //! Hardfork related codes for Scroll networks
-use crate::constants::read_env_var;
+use crate::{evm_types::OpcodeId, scoped::ScopedValue, Address};
+use revm_precompile::Precompiles;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{Error, ErrorKind},
+    path::Path,
+    sync::LazyLock,
+};
 
 /// Hardfork ID for scroll networks
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum HardforkId {
     /// Curie hardfork
     Curie = 3,
+    /// Serves BLOCKHASH from the history storage contract (EIP-2935), which is written the
+    /// parent hash at the start of each block. Unlike curie it is opt-in: it never activates
+    /// unless the chain spec schedules it.
+    HistoryStorage = 5,
+    /// Exposes the parent beacon block root through the beacon roots contract (EIP-4788), which
+    /// is written the root at the start of each block. Opt-in as well, and never active with the
+    /// `scroll` feature, see [`HardforkId::is_schedulable`].
+    BeaconRoots = 6,
+    /// Raises the modexp operand limit from the 32 bytes of bernoulli to `MODEXP_SIZE_LIMIT`
+    /// bytes, within `MODEXP_BLOCK_ROWS_LIMIT` rows of the ModExp circuit. Opt-in as well.
+    LargeModexp = 7,
+    /// Lifts the 4 pairs limit of the bernoulli ecPairing, up to the number of pairs the ECC
+    /// circuit is configured with. Opt-in as well.
+    LargeEcPairing = 8,
+    /// Enables the RIP-7212 P256VERIFY precompile at address 0x100. Opt-in as well.
+    P256Verify = 9,
+}
+
+impl HardforkId {
+    /// All hardforks, in activation order.
+    pub const ALL: [Self; 6] = [
+        Self::Curie,
+        Self::HistoryStorage,
+        Self::BeaconRoots,
+        Self::LargeModexp,
+        Self::LargeEcPairing,
+        Self::P256Verify,
+    ];
+
+    /// Activation of the fork on chains that do not schedule it.
+    pub fn default_condition(&self) -> ForkCondition {
+        match self {
+            Self::Curie => ForkCondition::Block(0),
+            Self::HistoryStorage
+            | Self::BeaconRoots
+            | Self::LargeModexp
+            | Self::LargeEcPairing
+            | Self::P256Verify => ForkCondition::Never,
+        }
+    }
+
+    /// Whether a chain can schedule the fork. The block table of the scroll circuits is laid out
+    /// by the public input and holds no beacon root, so the root written by `BeaconRoots` would
+    /// be unconstrained.
+    pub fn is_schedulable(&self) -> bool {
+        !(cfg!(feature = "scroll") && *self == Self::BeaconRoots)
+    }
+
+    /// Opcodes the fork introduces, which are invalid before it activates.
+    pub fn opcodes(&self) -> &'static [OpcodeId] {
+        match self {
+            Self::Curie => &[OpcodeId::TLOAD, OpcodeId::TSTORE, OpcodeId::MCOPY],
+            Self::HistoryStorage
+            | Self::BeaconRoots
+            | Self::LargeModexp
+            | Self::LargeEcPairing
+            | Self::P256Verify => &[],
+        }
+    }
+
+    /// Name of the fork in the `MORPH_*_<NAME>_BLOCK` env vars.
+    fn env_name(&self) -> &'static str {
+        match self {
+            Self::Curie => "CURIE",
+            Self::HistoryStorage => "HISTORY_STORAGE",
+            Self::BeaconRoots => "BEACON_ROOTS",
+            Self::LargeModexp => "LARGE_MODEXP",
+            Self::LargeEcPairing => "LARGE_EC_PAIRING",
+            Self::P256Verify => "P256_VERIFY",
+        }
+    }
 }
 
 /// Morph devnet and qanet chain id
@@ -15,24 +95,367 @@ pub const MORPH_TESTNET_CHAIN_ID: u64 = 2810;
 /// Morph mainnet chain id
 pub const MORPH_MAINNET_CHAIN_ID: u64 = 2818;
 
-/// Get hardforks of Morph networks.
-/// Returns a list of triplets of (hardfork id, chain id, block number)
+/// Whether `chain_id` is one of the Morph networks. The circuits look the fork heights of these
+/// chains up in the ChainFork fixed table, any other chain takes the default activations.
+pub fn is_morph_chain(chain_id: u64) -> bool {
+    matches!(
+        chain_id,
+        MORPH_DEVNET_CHAIN_ID | MORPH_TESTNET_CHAIN_ID | MORPH_MAINNET_CHAIN_ID
+    )
+}
+
+/// When a hardfork activates. The circuits compare the fork heights to the block number, so
+/// forks activate by block number only.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ForkCondition {
+    /// Active from this block number on
+    Block(u64),
+    /// Never active
+    Never,
+}
+
+impl ForkCondition {
+    /// Whether the fork is active for a block with this number.
+    pub fn is_active(&self, block_number: u64) -> bool {
+        match *self {
+            Self::Block(block) => block_number >= block,
+            Self::Never => false,
+        }
+    }
+}
+
+/// The set of precompiled contracts of a chain.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PrecompileSet {
+    /// Berlin precompiles
+    #[cfg_attr(not(feature = "scroll"), default)]
+    Berlin,
+    /// Scroll bernoulli precompiles, plus P256VERIFY once the `P256Verify` hardfork is active.
+    /// Requires the `scroll` feature, falls back to berlin otherwise.
+    #[cfg_attr(feature = "scroll", default)]
+    Bernoulli,
+}
+
+impl PrecompileSet {
+    /// The precompiled contracts of this set.
+    pub fn precompiles(&self) -> &'static Precompiles {
+        match self {
+            Self::Berlin => Precompiles::berlin(),
+            Self::Bernoulli => crate::utils::precompiles(),
+        }
+    }
+}
+
+/// Fork schedule and precompiles of a chain.
+///
+/// Forks missing from `forks` take their [`HardforkId::default_condition`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainSpec {
+    /// Chain id
+    pub chain_id: u64,
+    /// Activation of each hardfork
+    #[serde(default)]
+    pub forks: BTreeMap<HardforkId, ForkCondition>,
+    /// Precompiled contracts
+    #[serde(default)]
+    pub precompiles: PrecompileSet,
+}
+
+static CHAIN_SPECS: ScopedValue<Vec<ChainSpec>> = ScopedValue::new();
+
+/// The chains read from the env by [`ChainSpec::from_env`], loaded once. The error is kept as
+/// its message, `io::Error` is not `Clone`.
+static ENV_CHAIN_SPECS: LazyLock<Result<Vec<ChainSpec>, String>> =
+    LazyLock::new(|| ChainSpec::from_env().map_err(|err| err.to_string()));
+
+impl ChainSpec {
+    /// A chain with every hardfork at its default activation.
+    pub fn new(chain_id: u64) -> Self {
+        Self {
+            chain_id,
+            forks: BTreeMap::new(),
+            precompiles: PrecompileSet::default(),
+        }
+    }
+
+    /// Set the activation of `fork`.
+    pub fn with_fork(mut self, fork: HardforkId, condition: ForkCondition) -> Self {
+        self.forks.insert(fork, condition);
+        self
+    }
+
+    /// Activation of `fork`.
+    pub fn fork_condition(&self, fork: HardforkId) -> ForkCondition {
+        self.forks
+            .get(&fork)
+            .copied()
+            .unwrap_or_else(|| fork.default_condition())
+    }
+
+    /// Whether `fork` is active at block `block_number`, as the circuits check it, see
+    /// `HardforkGadget`.
+    pub fn is_active_at_block(&self, fork: HardforkId, block_number: u64) -> bool {
+        self.fork_condition(fork).is_active(block_number)
+    }
+
+    /// Block number `fork` activates at, `u64::MAX` if it never does.
+    pub fn fork_block(&self, fork: HardforkId) -> u64 {
+        match self.fork_condition(fork) {
+            ForkCondition::Block(block) => block,
+            ForkCondition::Never => u64::MAX,
+        }
+    }
+
+    /// The precompiled contracts of the chain.
+    pub fn precompiles(&self) -> &'static Precompiles {
+        self.precompiles.precompiles()
+    }
+
+    /// The precompiled contracts of the chain at block `block_number`.
+    pub fn precompiles_at_block(&self, block_number: u64) -> &'static Precompiles {
+        #[cfg(feature = "scroll")]
+        if self.precompiles == PrecompileSet::Bernoulli
+            && self.is_active_at_block(HardforkId::P256Verify, block_number)
+        {
+            return crate::utils::precompiles_with_p256_verify();
+        }
+        #[cfg(not(feature = "scroll"))]
+        let _ = block_number;
+        self.precompiles()
+    }
+
+    /// Check if address is a precompiled of the chain at block `block_number`.
+    pub fn is_precompiled_at_block(&self, address: &Address, block_number: u64) -> bool {
+        self.precompiles_at_block(block_number)
+            .get(address.as_fixed_bytes().into())
+            .is_some()
+    }
+
+    /// Whether `opcode` is defined at block `block_number`, i.e. the fork introducing it, if any,
+    /// is active, see [`HardforkId::opcodes`].
+    pub fn is_opcode_available_at_block(&self, opcode: OpcodeId, block_number: u64) -> bool {
+        HardforkId::ALL
+            .into_iter()
+            .find(|fork| fork.opcodes().contains(&opcode))
+            .map_or(true, |fork| self.is_active_at_block(fork, block_number))
+    }
+
+    /// Read a list of chain specs from a json file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Vec<Self>, Error> {
+        Self::check(serde_json::from_reader(File::open(path)?)?)
+    }
+
+    /// Reject the specs the circuits cannot check:
+    /// - only the fork heights of the Morph chains are in the ChainFork fixed table, see
+    ///   [`is_morph_chain`];
+    /// - only the schedulable forks may activate, see [`HardforkId::is_schedulable`];
+    /// - the scroll circuits constrain the bernoulli precompiles, so no chain may run berlin.
+    pub fn check(specs: Vec<Self>) -> Result<Vec<Self>, Error> {
+        let invalid = |spec: &Self, reason: String| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("chain {} {reason}", spec.chain_id),
+            )
+        };
+        for spec in specs.iter() {
+            if !is_morph_chain(spec.chain_id) {
+                return Err(invalid(spec, "is not a Morph chain".to_string()));
+            }
+            for fork in HardforkId::ALL {
+                if !fork.is_schedulable() && spec.fork_condition(fork) != ForkCondition::Never {
+                    return Err(invalid(spec, format!("schedules {fork:?}")));
+                }
+            }
+            if cfg!(feature = "scroll") && spec.precompiles == PrecompileSet::Berlin {
+                return Err(invalid(spec, "runs the berlin precompiles".to_string()));
+            }
+        }
+        Ok(specs)
+    }
+
+    /// The known chains from the env: the file at the `CHAIN_SPECS` env var, or
+    /// [`ChainSpec::morph_from_env`], checked by [`ChainSpec::check`].
+    pub fn from_env() -> Result<Vec<Self>, Error> {
+        match std::env::var("CHAIN_SPECS") {
+            Ok(path) => Self::load(&path)
+                .map_err(|err| Error::new(err.kind(), format!("failed to load {path}: {err}"))),
+            Err(_) => Self::check(Self::morph_from_env()?),
+        }
+    }
+
+    /// The Morph networks, with the fork heights read from the `MORPH_<NETWORK>_<FORK>_BLOCK`
+    /// env vars, e.g. `MORPH_MAINNET_CURIE_BLOCK` or `MORPH_TESTNET_HISTORY_STORAGE_BLOCK`.
+    /// Errors on the values that are not block numbers.
+    pub fn morph_from_env() -> Result<Vec<Self>, Error> {
+        let fork_at = |var: String| match std::env::var(&var) {
+            Err(_) => Ok(ForkCondition::Never),
+            Ok(value) => match value.parse() {
+                Ok(u64::MAX) => Ok(ForkCondition::Never),
+                Ok(block) => Ok(ForkCondition::Block(block)),
+                Err(err) => Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("{var}={value} is not a block number: {err}"),
+                )),
+            },
+        };
+        [
+            // devnet and qanet
+            (MORPH_DEVNET_CHAIN_ID, "DEVNET"),
+            (MORPH_TESTNET_CHAIN_ID, "TESTNET"),
+            (MORPH_MAINNET_CHAIN_ID, "MAINNET"),
+        ]
+        .into_iter()
+        .map(|(chain_id, network)| {
+            HardforkId::ALL
+                .into_iter()
+                .try_fold(Self::new(chain_id), |spec, fork| {
+                    let var = format!("MORPH_{network}_{}_BLOCK", fork.env_name());
+                    Ok(spec.with_fork(fork, fork_at(var)?))
+                })
+        })
+        .collect()
+    }
+
+    /// The known chains: the ones installed by [`ChainSpec::scoped`], or the ones of
+    /// [`ChainSpec::from_env`]. The env is only read on the first call, and errors if its specs
+    /// are invalid.
+    pub fn try_registered() -> Result<Vec<Self>, Error> {
+        match CHAIN_SPECS.get() {
+            Some(specs) => Ok(specs),
+            None => ENV_CHAIN_SPECS
+                .clone()
+                .map_err(|err| Error::new(ErrorKind::InvalidInput, err)),
+        }
+    }
+
+    /// The known chains, see [`ChainSpec::try_registered`]. Panics if the specs of the env are
+    /// invalid.
+    pub fn registered() -> Vec<Self> {
+        Self::try_registered().unwrap_or_else(|err| panic!("Invalid chain specs: {err}"))
+    }
+
+    /// The spec of `chain_id`, or a chain with every fork at its default activation if unknown.
+    pub fn for_chain_id(chain_id: u64) -> Self {
+        Self::registered()
+            .into_iter()
+            .find(|spec| spec.chain_id == chain_id)
+            .unwrap_or_else(|| Self::new(chain_id))
+    }
+
+    /// Run `f` with `specs` as the known chains, see [`crate::scoped::Scope::run`]. Panics if
+    /// [`ChainSpec::check`] rejects them.
+    pub fn scoped<R: Send>(specs: Vec<Self>, f: impl FnOnce() -> R + Send) -> R {
+        let specs = Self::check(specs).unwrap_or_else(|err| panic!("Invalid chain specs: {err}"));
+        CHAIN_SPECS.with(specs, f)
+    }
+}
+
+/// Get hardforks of the known chains.
+/// Returns a list of triplets of (hardfork id, chain id, block number), with `u64::MAX` for forks
+/// that never activate.
 pub fn hardfork_heights() -> Vec<(HardforkId, u64, u64)> {
-    vec![
-        (
-            HardforkId::Curie,
-            MORPH_DEVNET_CHAIN_ID,
-            read_env_var("MORPH_DEVNET_CURIE_BLOCK", u64::MAX),
-        ), // devnet and qanet
-        (
-            HardforkId::Curie,
-            MORPH_TESTNET_CHAIN_ID,
-            read_env_var("MORPH_TESTNET_CURIE_BLOCK", u64::MAX),
-        ), // testnet
-        (
-            HardforkId::Curie,
-            MORPH_MAINNET_CHAIN_ID,
-            read_env_var("MORPH_MAINNET_CURIE_BLOCK", u64::MAX),
-        ), // mainnet
-    ]
+    ChainSpec::registered()
+        .iter()
+        .flat_map(|spec| {
+            HardforkId::ALL
+                .into_iter()
+                .map(|fork| (fork, spec.chain_id, spec.fork_block(fork)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chain_spec_forks() {
+        let specs: Vec<ChainSpec> = serde_json::from_str(
+            r#"[
+                {"chain_id": 53077, "forks": {"Curie": {"Block": 10}}},
+                {"chain_id": 2810, "forks": {"Curie": {"Block": 1000}}, "precompiles": "Bernoulli"},
+                {"chain_id": 2818, "forks": {"Curie": "Never", "HistoryStorage": {"Block": 20}}}
+            ]"#,
+        )
+        .unwrap();
+
+        ChainSpec::scoped(specs, || {
+            let spec = ChainSpec::for_chain_id(MORPH_DEVNET_CHAIN_ID);
+            assert!(!spec.is_active_at_block(HardforkId::Curie, 9));
+            assert!(spec.is_active_at_block(HardforkId::Curie, 10));
+
+            let spec = ChainSpec::for_chain_id(MORPH_TESTNET_CHAIN_ID);
+            assert!(!spec.is_opcode_available_at_block(OpcodeId::TSTORE, 999));
+            assert!(spec.is_opcode_available_at_block(OpcodeId::TSTORE, 1000));
+            assert!(spec.is_opcode_available_at_block(OpcodeId::SSTORE, 0));
+            assert_eq!(spec.precompiles, PrecompileSet::Bernoulli);
+
+            // unknown chains have curie since genesis and never the opt-in history storage
+            let spec = ChainSpec::for_chain_id(4);
+            assert_eq!(spec.fork_block(HardforkId::Curie), 0);
+            assert!(!spec.is_active_at_block(HardforkId::HistoryStorage, u64::MAX));
+
+            assert_eq!(
+                hardfork_heights(),
+                vec![
+                    (HardforkId::Curie, MORPH_DEVNET_CHAIN_ID, 10),
+                    (HardforkId::HistoryStorage, MORPH_DEVNET_CHAIN_ID, u64::MAX),
+                    (HardforkId::BeaconRoots, MORPH_DEVNET_CHAIN_ID, u64::MAX),
+                    (HardforkId::LargeModexp, MORPH_DEVNET_CHAIN_ID, u64::MAX),
+                    (HardforkId::LargeEcPairing, MORPH_DEVNET_CHAIN_ID, u64::MAX),
+                    (HardforkId::P256Verify, MORPH_DEVNET_CHAIN_ID, u64::MAX),
+                    (HardforkId::Curie, MORPH_TESTNET_CHAIN_ID, 1000),
+                    (HardforkId::HistoryStorage, MORPH_TESTNET_CHAIN_ID, u64::MAX),
+                    (HardforkId::BeaconRoots, MORPH_TESTNET_CHAIN_ID, u64::MAX),
+                    (HardforkId::LargeModexp, MORPH_TESTNET_CHAIN_ID, u64::MAX),
+                    (HardforkId::LargeEcPairing, MORPH_TESTNET_CHAIN_ID, u64::MAX),
+                    (HardforkId::P256Verify, MORPH_TESTNET_CHAIN_ID, u64::MAX),
+                    (HardforkId::Curie, MORPH_MAINNET_CHAIN_ID, u64::MAX),
+                    (HardforkId::HistoryStorage, MORPH_MAINNET_CHAIN_ID, 20),
+                    (HardforkId::BeaconRoots, MORPH_MAINNET_CHAIN_ID, u64::MAX),
+                    (HardforkId::LargeModexp, MORPH_MAINNET_CHAIN_ID, u64::MAX),
+                    (HardforkId::LargeEcPairing, MORPH_MAINNET_CHAIN_ID, u64::MAX),
+                    (HardforkId::P256Verify, MORPH_MAINNET_CHAIN_ID, u64::MAX),
+                ]
+            );
+        });
+
+        // the circuits only look the fork heights of the Morph chains up
+        assert!(ChainSpec::check(vec![ChainSpec::new(MORPH_MAINNET_CHAIN_ID)]).is_ok());
+        assert!(ChainSpec::check(vec![ChainSpec::new(1)]).is_err());
+
+        // the circuits compare the fork heights to the block number
+        let parse = |json: &str| serde_json::from_str::<Vec<ChainSpec>>(json);
+        assert!(parse(r#"[{"chain_id": 2818, "forks": {"Curie": {"Block": 1000}}}]"#).is_ok());
+        assert!(parse(r#"[{"chain_id": 2818, "forks": {"Curie": {"Timestamp": 1000}}}]"#).is_err());
+
+        // the scroll circuits constrain the bernoulli precompiles
+        let spec = ChainSpec {
+            precompiles: PrecompileSet::Berlin,
+            ..ChainSpec::new(MORPH_MAINNET_CHAIN_ID)
+        };
+        assert_eq!(
+            ChainSpec::check(vec![spec]).is_err(),
+            cfg!(feature = "scroll")
+        );
+
+        // the scroll circuits can not check the beacon root
+        let spec = ChainSpec::new(MORPH_MAINNET_CHAIN_ID)
+            .with_fork(HardforkId::BeaconRoots, ForkCondition::Block(0));
+        assert_eq!(
+            ChainSpec::check(vec![spec]).is_err(),
+            cfg!(feature = "scroll")
+        );
+    }
+
+    #[cfg(feature = "scroll")]
+    #[test]
+    fn p256_verify_precompile() {
+        let p256_verify = Address::from_low_u64_be(0x100);
+        let spec = ChainSpec::new(1).with_fork(HardforkId::P256Verify, ForkCondition::Block(10));
+        assert!(!spec.is_precompiled_at_block(&p256_verify, 9));
+        assert!(spec.is_precompiled_at_block(&p256_verify, 10));
+        assert!(spec.is_precompiled_at_block(&Address::from_low_u64_be(1), 9));
+        assert!(!ChainSpec::new(2).is_precompiled_at_block(&p256_verify, u64::MAX - 1));
+    }
 }
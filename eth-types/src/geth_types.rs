@@ -3,6 +3,7 @@
 #[cfg(feature = "scroll")]
 use crate::l2_types::BlockTrace;
 use crate::{
+    block_header::parent_beacon_block_root,
     sign_types::{biguint_to_32bytes_le, ct_option_ok_or, recover_pk2, SignData, SECP256K1_Q},
     AccessList, Address, Block, Bytes, Error, GethExecTrace, Hash, ToBigEndian, ToLittleEndian,
     Word, U64,
@@ -14,14 +15,14 @@ use ethers_core::types::{
 use halo2curves::{group::ff::PrimeField, secp256k1::Fq};
 use num::Integer;
 use num_bigint::BigUint;
-use serde::{Serialize, Serializer};
+use serde::{Deserialize, Serialize, Serializer};
 use serde_with::serde_as;
 use sha3::{Digest, Keccak256};
 use std::collections::HashMap;
 use strum_macros::EnumIter;
 
 /// Tx type
-#[derive(Default, Debug, Copy, Clone, EnumIter, Serialize, PartialEq, Eq)]
+#[derive(Default, Debug, Copy, Clone, EnumIter, Serialize, Deserialize, PartialEq, Eq)]
 pub enum TxType {
     /// EIP 155 tx
     #[default]
@@ -212,6 +213,8 @@ pub struct BlockConstants {
     pub gas_limit: Word,
     /// base fee
     pub base_fee: Word,
+    /// parent beacon block root, written into the beacon roots contract before the txs
+    pub parent_beacon_block_root: Option<H256>,
 }
 
 impl<TX> TryFrom<&Block<TX>> for BlockConstants {
@@ -225,6 +228,7 @@ impl<TX> TryFrom<&Block<TX>> for BlockConstants {
             difficulty: block.difficulty,
             gas_limit: block.gas_limit,
             base_fee: block.base_fee_per_gas.ok_or(Error::IncompleteBlock)?,
+            parent_beacon_block_root: parent_beacon_block_root(block),
         })
     }
 }
@@ -238,6 +242,7 @@ impl BlockConstants {
         difficulty: Word,
         gas_limit: Word,
         base_fee: Word,
+        parent_beacon_block_root: Option<H256>,
     ) -> BlockConstants {
         BlockConstants {
             coinbase,
@@ -246,6 +251,7 @@ impl BlockConstants {
             difficulty,
             gas_limit,
             base_fee,
+            parent_beacon_block_root,
         }
     }
 }
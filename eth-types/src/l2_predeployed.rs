@@ -92,3 +92,62 @@ pub mod l2_sequencer_set {
     /// the slot of sequencer root in L2SequencerSet
     pub static SEQUENCER_SET_ROOT_SLOT: LazyLock<U256> = LazyLock::new(|| U256::from(101));
 }
+
+/// Helper for the history storage contract (EIP-2935)
+pub mod history_storage {
+    use super::*;
+    use crate::{evm_types::block_utils::HISTORY_SERVE_WINDOW, U256};
+    use std::{str::FromStr, sync::LazyLock};
+
+    /// address of the history storage system contract
+    pub static ADDRESS: LazyLock<Address> =
+        LazyLock::new(|| Address::from_str("0x0000F90827F1C53a10cb7A02335B175320002935").unwrap());
+
+    /// Runtime bytecode of the contract, from the EIP
+    pub static BYTECODE: LazyLock<Vec<u8>> = LazyLock::new(|| {
+        hex::decode(
+            "3373fffffffffffffffffffffffffffffffffffffffe14604657602036036042575f35600143038111604257\
+             611fff81430311604257611fff9006545f5260205ff35b5f5ffd5b5f35611fff60014303065500",
+        )
+        .expect("decode history storage bytecode")
+    });
+
+    /// the slot holding the hash of a block, as a ring buffer of `HISTORY_SERVE_WINDOW` slots
+    pub fn slot(block_number: u64) -> U256 {
+        U256::from(block_number % HISTORY_SERVE_WINDOW)
+    }
+}
+
+/// Helper for the beacon roots contract (EIP-4788)
+pub mod beacon_roots {
+    use super::*;
+    use crate::U256;
+    use std::{str::FromStr, sync::LazyLock};
+
+    /// address of the beacon roots system contract
+    pub static ADDRESS: LazyLock<Address> =
+        LazyLock::new(|| Address::from_str("0x000F3df6D732807Ef1319fB7B8bB8522d0Beac02").unwrap());
+
+    /// number of roots kept by the contract, as a ring buffer keyed by timestamp
+    pub const HISTORY_BUFFER_LENGTH: u64 = 8191;
+
+    /// Runtime bytecode of the contract, from the EIP
+    pub static BYTECODE: LazyLock<Vec<u8>> = LazyLock::new(|| {
+        hex::decode(
+            "3373fffffffffffffffffffffffffffffffffffffffe14604d57602036146024575f5ffd5b5f3580156049\
+             5762001fff810690815414603c575f5ffd5b62001fff01545f5260205ff35b5f5ffd5b62001fff4206428155\
+             5f359062001fff015500",
+        )
+        .expect("decode beacon roots bytecode")
+    });
+
+    /// the slot holding the timestamp of a block
+    pub fn timestamp_slot(timestamp: u64) -> U256 {
+        U256::from(timestamp % HISTORY_BUFFER_LENGTH)
+    }
+
+    /// the slot holding the parent beacon block root of a block
+    pub fn root_slot(timestamp: u64) -> U256 {
+        U256::from(timestamp % HISTORY_BUFFER_LENGTH + HISTORY_BUFFER_LENGTH)
+    }
+}
@@ -1,6 +1,7 @@
 //! L2 types used to deserialize traces for l2geth.
 
 use crate::{
+    block_header::parent_beacon_block_root,
     evm_types::{Gas, GasCost, OpcodeId, ProgramCounter},
     EthBlock, GethCallTrace, GethExecError, GethExecStep, GethExecTrace, GethPrestateTrace, Hash,
     ToBigEndian, Transaction, H256,
@@ -108,6 +109,13 @@ pub struct BlockTrace {
     pub start_l1_queue_index: u64,
 }
 
+impl BlockTrace {
+    /// Root of the parent beacon block, kept in the other fields of the header
+    pub fn parent_beacon_block_root(&self) -> Option<H256> {
+        parent_beacon_block_root(&self.header)
+    }
+}
+
 impl From<BlockTrace> for EthBlock {
     fn from(b: BlockTrace) -> Self {
         let mut txs = Vec::new();
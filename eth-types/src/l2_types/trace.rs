@@ -2,7 +2,7 @@ use crate::{
     evm_types::OpcodeId,
     l2_types::BlockTrace,
     state_db::{CodeDB, StateDB},
-    utils::is_precompiled,
+    utils::{is_precompiled, may_be_precompiled},
     Address, Error, H256,
 };
 use ethers_core::types::Bytes;
@@ -51,7 +51,14 @@ pub fn collect_codes(
             .flatten_trace(&execution_result.prestate)
             .into_iter()
             .filter(|call| {
-                let is_call_to_precompile = call.to.as_ref().map(is_precompiled).unwrap_or(false);
+                // precompiles enabled by a hardfork have no code, whether the fork is active or not
+                let is_call_to_precompile = call
+                    .to
+                    .as_ref()
+                    .map(|to| {
+                        is_precompiled(to) || (may_be_precompiled(to) && call.is_callee_code_empty)
+                    })
+                    .unwrap_or(false);
                 let is_call_to_empty = call.gas_used.is_zero()
                     && !call.call_type.is_create()
                     && call.is_callee_code_empty;
@@ -24,6 +24,7 @@ pub mod macros;
 pub mod error;
 #[macro_use]
 pub mod bytecode;
+pub mod block_header;
 pub mod constants;
 pub mod evm_types;
 pub mod forks;
@@ -31,6 +32,7 @@ pub mod geth_types;
 /// L2 system contracts
 pub mod l2_predeployed;
 pub mod l2_types;
+pub mod scoped;
 pub mod sign_types;
 pub mod state_db;
 pub mod utils;
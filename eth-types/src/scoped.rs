@@ -0,0 +1,236 @@
+//! Configuration values that can be overridden for the duration of a closure.
+//!
+//! Circuit `configure` functions take no input, so values such as the keccak rows or the layer
+//! configs cannot be threaded through them. A [`ScopedValue`] lets a caller install such a value
+//! explicitly around keygen/proving, instead of mutating the process environment.
+//!
+//! The installed values are per scope, so that provers configured differently can run
+//! concurrently. A [`Scope`] runs its closure on a rayon pool whose workers all see the values of
+//! the scope: parallel iterators and joins inside the closure see them as well, threads spawned
+//! otherwise and rayon tasks outliving the closure do not. The pools are kept once their scope
+//! returns and reused by later scopes, one scope at a time.
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::HashMap,
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, RwLock,
+    },
+};
+
+/// Installed values, by id of their [`ScopedValue`].
+type Values = HashMap<usize, Arc<dyn Any + Send + Sync>>;
+
+/// Values of the scope a pool runs, shared by its workers.
+type Slot = Arc<RwLock<Arc<Values>>>;
+
+thread_local! {
+    /// The slot of the pool the current thread is a worker of, none outside of the scope pools.
+    static SLOT: RefCell<Option<Slot>> = const { RefCell::new(None) };
+}
+
+/// Pools of the scopes that returned, ready for the next ones.
+static IDLE_POOLS: Mutex<Vec<(rayon::ThreadPool, Slot)>> = Mutex::new(Vec::new());
+
+/// The values installed for the current thread.
+fn installed() -> Arc<Values> {
+    SLOT.with(|slot| {
+        slot.borrow()
+            .as_ref()
+            .map(|slot| slot.read().unwrap().clone())
+            .unwrap_or_default()
+    })
+}
+
+/// A pool of as many threads as the current one, whose workers see the values of `slot`.
+fn new_pool() -> (rayon::ThreadPool, Slot) {
+    let slot = Slot::default();
+    let worker_slot = slot.clone();
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(rayon::current_num_threads())
+        .start_handler(move |_| SLOT.with(|slot| *slot.borrow_mut() = Some(worker_slot.clone())))
+        .build()
+        .expect("failed to build the thread pool of a scope");
+    (pool, slot)
+}
+
+/// A pool taken for a scope, cleared and given back when the scope returns or panics.
+struct ScopePool(Option<(rayon::ThreadPool, Slot)>);
+
+impl ScopePool {
+    /// Take an idle pool, or build one if none is left, and install `values` for its workers.
+    fn take(values: Values) -> Self {
+        let (pool, slot) = IDLE_POOLS.lock().unwrap().pop().unwrap_or_else(new_pool);
+        *slot.write().unwrap() = Arc::new(values);
+        Self(Some((pool, slot)))
+    }
+
+    fn install<R: Send>(&self, f: impl FnOnce() -> R + Send) -> R {
+        let (pool, _) = self.0.as_ref().expect("pool of a running scope");
+        pool.install(f)
+    }
+}
+
+impl Drop for ScopePool {
+    fn drop(&mut self) {
+        if let Some((pool, slot)) = self.0.take() {
+            *slot.write().unwrap() = Arc::default();
+            IDLE_POOLS.lock().unwrap().push((pool, slot));
+        }
+    }
+}
+
+/// Next id to give to a [`ScopedValue`], 0 is reserved for the ones without an id yet.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+
+/// A value that is unset unless a caller is running inside a [`Scope`] installing it.
+#[derive(Debug)]
+pub struct ScopedValue<T> {
+    /// Assigned on first use.
+    id: AtomicUsize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for ScopedValue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> ScopedValue<T> {
+    /// Create an unset value.
+    pub const fn new() -> Self {
+        Self {
+            id: AtomicUsize::new(0),
+            _marker: PhantomData,
+        }
+    }
+
+    fn id(&self) -> usize {
+        match self.id.load(Ordering::Acquire) {
+            0 => {
+                let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+                self.id
+                    .compare_exchange(0, id, Ordering::AcqRel, Ordering::Acquire)
+                    .map_or_else(|set| set, |_| id)
+            }
+            id => id,
+        }
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> ScopedValue<T> {
+    /// The value installed by the innermost scope the current thread runs in, if any.
+    pub fn get(&self) -> Option<T> {
+        installed()
+            .get(&self.id())
+            .and_then(|value| value.downcast_ref::<T>())
+            .cloned()
+    }
+
+    /// The installed value, or `fallback` (typically an env var loader) if none.
+    pub fn get_or_else(&self, fallback: impl FnOnce() -> T) -> T {
+        self.get().unwrap_or_else(fallback)
+    }
+
+    /// Run `f` with `value` installed, see [`Scope::run`].
+    pub fn with<R: Send>(&self, value: T, f: impl FnOnce() -> R + Send) -> R {
+        Scope::new().set(self, value).run(f)
+    }
+}
+
+/// Values to install together for the duration of a closure.
+#[derive(Clone)]
+pub struct Scope {
+    values: Values,
+}
+
+impl Default for Scope {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scope {
+    /// A scope with the values installed for the current thread.
+    pub fn new() -> Self {
+        Self {
+            values: (*installed()).clone(),
+        }
+    }
+
+    /// Install `value` for `slot`.
+    pub fn set<T: Clone + Send + Sync + 'static>(
+        mut self,
+        slot: &ScopedValue<T>,
+        value: T,
+    ) -> Self {
+        self.values.insert(slot.id(), Arc::new(value));
+        self
+    }
+
+    /// Run `f` with the values of the scope installed.
+    ///
+    /// `f` runs on a rayon pool, of as many threads as the current one when it was built, whose
+    /// workers have the values installed. The calling thread blocks until `f` returns, and panics
+    /// of `f` are propagated.
+    pub fn run<R: Send>(self, f: impl FnOnce() -> R + Send) -> R {
+        ScopePool::take(self.values).install(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Scope, ScopedValue};
+    use rayon::prelude::*;
+
+    #[test]
+    fn scoped_value_restores() {
+        static VALUE: ScopedValue<u32> = ScopedValue::new();
+        static OTHER: ScopedValue<&str> = ScopedValue::new();
+        assert_eq!(VALUE.get(), None);
+        VALUE.with(1, || {
+            assert_eq!(VALUE.get(), Some(1));
+            VALUE.with(2, || assert_eq!(VALUE.get_or_else(|| 0), 2));
+            assert_eq!(VALUE.get(), Some(1));
+            Scope::new().set(&OTHER, "other").run(|| {
+                assert_eq!((VALUE.get(), OTHER.get()), (Some(1), Some("other")));
+            });
+        });
+        assert_eq!(VALUE.get_or_else(|| 0), 0);
+
+        let _ = std::panic::catch_unwind(|| VALUE.with(3, || panic!("propagated")));
+        assert_eq!(VALUE.get(), None);
+    }
+
+    #[test]
+    fn scoped_values_run_concurrently() {
+        static VALUE: ScopedValue<u32> = ScopedValue::new();
+        let handles = (0..8)
+            .map(|i| {
+                std::thread::spawn(move || {
+                    VALUE.with(i, || {
+                        // the rayon workers of the scope see its value
+                        (0..100).into_par_iter().for_each(|_| {
+                            assert_eq!(VALUE.get(), Some(i));
+                        });
+                        VALUE.with(i + 100, || {
+                            rayon::join(
+                                || assert_eq!(VALUE.get(), Some(i + 100)),
+                                || assert_eq!(VALUE.get(), Some(i + 100)),
+                            )
+                        });
+                        assert_eq!(VALUE.get(), Some(i));
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(VALUE.get(), None);
+    }
+}
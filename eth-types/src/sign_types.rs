@@ -1,4 +1,4 @@
-//! secp256k1 signature types and helper functions.
+//! secp256k1 and secp256r1 signature types and helper functions.
 
 use crate::{
     address,
@@ -22,9 +22,11 @@ use halo2curves::{
         Curve,
     },
     secp256k1::{Fp, Fq, Secp256k1Affine},
+    secp256r1::{Fp as P256Fp, Fq as P256Fq, Secp256r1Affine},
     Coordinates, CurveAffine,
 };
 use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
 use sha3::digest::generic_array::GenericArray;
 use std::sync::LazyLock;
 use subtle::CtOption;
@@ -49,9 +51,25 @@ pub fn sign(randomness: Fq, sk: Fq, msg_hash: Fq) -> (Fq, Fq, u8) {
     (sig_r, sig_s, u8::from(sig_v))
 }
 
+/// Do a secp256r1 (P-256) signature with a given randomness value.
+pub fn sign_p256(randomness: P256Fq, sk: P256Fq, msg_hash: P256Fq) -> (P256Fq, P256Fq) {
+    let randomness_inv =
+        Option::<P256Fq>::from(randomness.invert()).expect("cannot invert randomness");
+    let generator = Secp256r1Affine::generator();
+    let sig_point = generator * randomness;
+
+    let x = *Option::<Coordinates<_>>::from(sig_point.to_affine().coordinates())
+        .expect("point is the identity")
+        .x();
+
+    let sig_r = p256_x_mod_n(&x);
+    let sig_s = randomness_inv * (msg_hash + sig_r * sk);
+    (sig_r, sig_s)
+}
+
 /// Signature data required by the SignVerify Chip as input to verify a
 /// signature.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SignData {
     /// Secp256k1 signature point (r, s, v)
     /// v must be 0 or 1
@@ -143,6 +161,59 @@ impl Default for SignData {
     }
 }
 
+/// Signature data required by the SignVerify Chip as input to verify a secp256r1 (P-256)
+/// signature, as done by the RIP-7212 precompile.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct P256SignData {
+    /// Secp256r1 signature (r, s)
+    pub signature: (P256Fq, P256Fq),
+    /// Secp256r1 public key, which is not necessarily on the curve
+    pub pk: Secp256r1Affine,
+    /// Hash of the message being signed, reduced modulo the curve order
+    pub msg_hash: P256Fq,
+}
+
+impl P256SignData {
+    /// Verify the signature the same way as RIP-7212: `r` and `s` are non-zero, the public key
+    /// is a point on the curve other than the identity, and `x(u1 * G + u2 * pk) == r (mod n)`.
+    pub fn verify(&self) -> bool {
+        let (r, s) = self.signature;
+        if bool::from(r.is_zero())
+            || bool::from(s.is_zero())
+            || bool::from(self.pk.is_identity())
+            || !bool::from(self.pk.is_on_curve())
+        {
+            return false;
+        }
+        let s_inv = s.invert().unwrap();
+        let point = Secp256r1Affine::generator() * (self.msg_hash * s_inv) + self.pk * (r * s_inv);
+        Option::<Coordinates<_>>::from(point.to_affine().coordinates())
+            .is_some_and(|coordinates| p256_x_mod_n(coordinates.x()) == r)
+    }
+}
+
+static P256_SIGN_DATA_DEFAULT: LazyLock<P256SignData> = LazyLock::new(|| {
+    let sk = P256Fq::one();
+    let msg_hash = P256Fq::one();
+    let (sig_r, sig_s) = sign_p256(P256Fq::from(2), sk, msg_hash);
+    let sign_data = P256SignData {
+        signature: (sig_r, sig_s),
+        pk: (Secp256r1Affine::generator() * sk).to_affine(),
+        msg_hash,
+    };
+    assert!(sign_data.verify());
+
+    sign_data
+});
+
+impl Default for P256SignData {
+    // Hardcoded valid signature of a hardcoded private key, nonce and message hash, to be used
+    // for padding the P-256 signature verifications.
+    fn default() -> Self {
+        P256_SIGN_DATA_DEFAULT.clone()
+    }
+}
+
 /// Convert a `BigUint` into 32 bytes in little endian.
 pub fn biguint_to_32bytes_le(v: BigUint) -> [u8; 32] {
     let mut res = [0u8; 32];
@@ -198,6 +269,17 @@ pub fn recover_pk2(
 pub static SECP256K1_Q: LazyLock<BigUint> =
     LazyLock::new(|| BigUint::from_bytes_le(&(Fq::zero() - Fq::one()).to_repr()) + 1u64);
 
+/// Secp256r1 Curve Scalar.  Reference: Section 2.4.2 (parameter `n`) in "SEC 2: Recommended
+/// Elliptic Curve Domain Parameters" document at http://www.secg.org/sec2-v2.pdf
+pub static SECP256R1_Q: LazyLock<BigUint> =
+    LazyLock::new(|| BigUint::from_bytes_le(&(P256Fq::zero() - P256Fq::one()).to_repr()) + 1u64);
+
+/// Reduce a secp256r1 x coordinate modulo the curve order.
+pub fn p256_x_mod_n(x: &P256Fp) -> P256Fq {
+    let x = BigUint::from_bytes_le(&x.to_repr()) % &*SECP256R1_Q;
+    P256Fq::from_repr(biguint_to_32bytes_le(x)).unwrap()
+}
+
 /// Helper function to convert a `CtOption` into an `Result`.  Similar to
 /// `Option::ok_or`.
 pub fn ct_option_ok_or<T, E>(v: CtOption<T>, err: E) -> Result<T, E> {
@@ -2,17 +2,50 @@
 
 use crate::Address;
 use revm_precompile::Precompiles;
+#[cfg(feature = "scroll")]
+use std::sync::LazyLock;
 
 mod io;
 pub use io::*;
 mod codehash;
 pub use codehash::*;
 
-/// Check if address is a precompiled or not.
-pub fn is_precompiled(address: &Address) -> bool {
+/// The precompiled contracts of the chain, without the ones enabled by opt-in hardforks.
+pub fn precompiles() -> &'static Precompiles {
+    #[cfg(feature = "scroll")]
+    {
+        Precompiles::bernoulli()
+    }
+    #[cfg(not(feature = "scroll"))]
+    Precompiles::berlin()
+}
+
+/// The bernoulli precompiles with RIP-7212 (P256VERIFY), enabled by the `P256Verify` hardfork.
+#[cfg(feature = "scroll")]
+pub fn precompiles_with_p256_verify() -> &'static Precompiles {
+    // RIP-7212 is not part of any revm spec, so it is enabled on top of bernoulli.
+    static PRECOMPILES: LazyLock<Precompiles> = LazyLock::new(|| {
+        let mut precompiles = Precompiles::bernoulli().clone();
+        precompiles.extend([revm_precompile::secp256r1::P256VERIFY]);
+        precompiles
+    });
+    &PRECOMPILES
+}
+
+/// Check if address is a precompiled at some block, including the precompiles enabled by
+/// hardforks.
+pub fn may_be_precompiled(address: &Address) -> bool {
     #[cfg(feature = "scroll")]
-    let precompiles = Precompiles::bernoulli();
+    {
+        precompiles_with_p256_verify()
+            .get(address.as_fixed_bytes().into())
+            .is_some()
+    }
     #[cfg(not(feature = "scroll"))]
-    let precompiles = Precompiles::berlin();
-    precompiles.get(address.as_fixed_bytes().into()).is_some()
+    is_precompiled(address)
+}
+
+/// Check if address is a precompiled or not.
+pub fn is_precompiled(address: &Address) -> bool {
+    precompiles().get(address.as_fixed_bytes().into()).is_some()
 }
@@ -1,15 +1,12 @@
 //! Some handy helpers
 
 use crate::{Address, Hash};
-use revm_precompile::Precompiles;
 
 /// Check if address is a precompiled or not.
 pub fn is_precompiled(address: &Address) -> bool {
-    #[cfg(feature = "scroll")]
-    let precompiles = Precompiles::bernoulli();
-    #[cfg(not(feature = "scroll"))]
-    let precompiles = Precompiles::berlin();
-    precompiles.get(address.as_fixed_bytes().into()).is_some()
+    super::precompiles()
+        .get(address.as_fixed_bytes().into())
+        .is_some()
 }
 
 /// Default number of bytes to pack into a field element.
@@ -3,6 +3,7 @@
 #[cfg(feature = "scroll")]
 use eth_types::l2_types::BlockTrace;
 use eth_types::{
+    forks::{ChainSpec, HardforkId},
     geth_types::{Account, BlockConstants, Transaction},
     Address, Error, GethExecTrace, Word,
 };
@@ -32,6 +33,22 @@ pub struct TraceConfig {
     pub l1_queue_index: u64,
 }
 
+impl TraceConfig {
+    /// The config sent to the tracer. The tracer writes any parent beacon block root into the
+    /// beacon roots contract before the txs, so the root is dropped before the fork, as the
+    /// circuit input builder does.
+    fn for_tracer(&self) -> Self {
+        let mut config = self.clone();
+        if !ChainSpec::for_chain_id(config.chain_id).is_active_at_block(
+            HardforkId::BeaconRoots,
+            config.block_constants.number.as_u64(),
+        ) {
+            config.block_constants.parent_beacon_block_root = None;
+        }
+        config
+    }
+}
+
 /// Configuration structure for `logger.Config`
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "PascalCase")]
@@ -81,7 +98,7 @@ pub struct ChainConfig {}
 
 #[cfg(not(feature = "scroll"))]
 pub fn trace(config: &TraceConfig) -> Result<Vec<GethExecTrace>, Error> {
-    let trace_config = &serde_json::to_string_pretty(&config).unwrap();
+    let trace_config = &serde_json::to_string_pretty(&config.for_tracer()).unwrap();
     log::trace!("trace config: {}", trace_config);
     // Get the trace
     let trace_string = geth_utils::trace(trace_config).map_err(|error| match error {
@@ -99,7 +116,7 @@ pub fn trace(config: &TraceConfig) -> Result<Vec<GethExecTrace>, Error> {
 /// Creates a l2-trace for the specified config
 #[cfg(feature = "scroll")]
 pub fn l2trace(config: &TraceConfig) -> Result<BlockTrace, Error> {
-    let l2_config = config.clone();
+    let l2_config = config.for_tracer();
     let trace_config = &serde_json::to_string_pretty(&l2_config).unwrap();
     log::trace!("trace config: {}", trace_config);
     // Get the trace
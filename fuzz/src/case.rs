@@ -0,0 +1,72 @@
+//! Serializable fuzz input.
+
+use eth_types::{Address, Bytes, Error, Word};
+use ethers_signers::Signer;
+use mock::{eth, test_ctx::TestContext, MOCK_WALLETS};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Number of contracts deployed by a case. The tx always calls the first one.
+pub const NUM_CONTRACTS: usize = 4;
+/// Number of accounts of a case: the contracts plus the sender.
+pub const NUM_ACCOUNTS: usize = NUM_CONTRACTS + 1;
+
+/// Address of the `idx`-th contract of a case.
+pub fn contract_address(idx: usize) -> Address {
+    Address::from_low_u64_be(0xc0de_0000 + idx as u64)
+}
+
+/// A single fuzz input: the code of each contract and the root tx.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FuzzCase {
+    /// Code of the contracts, deployed at [`contract_address`].
+    pub contracts: Vec<Bytes>,
+    /// Calldata of the tx.
+    pub calldata: Bytes,
+    /// Value transferred by the tx.
+    pub value: Word,
+    /// Gas limit of the tx.
+    pub gas: u64,
+}
+
+impl FuzzCase {
+    /// Load a case from a json corpus file.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let file = std::fs::File::open(path).map_err(|e| format!("{path:?}: {e}"))?;
+        serde_json::from_reader(file).map_err(|e| format!("{path:?}: {e}"))
+    }
+
+    /// Store the case as a json corpus file.
+    pub fn to_file(&self, path: &Path) -> Result<(), String> {
+        let file = std::fs::File::create(path).map_err(|e| format!("{path:?}: {e}"))?;
+        serde_json::to_writer_pretty(file, self).map_err(|e| format!("{path:?}: {e}"))
+    }
+
+    /// Build and trace the case.
+    pub fn test_ctx(&self) -> Result<TestContext<NUM_ACCOUNTS, 1>, Error> {
+        assert!(self.contracts.len() <= NUM_CONTRACTS);
+        TestContext::new(
+            None,
+            |accs| {
+                for (idx, acc) in accs.into_iter().enumerate() {
+                    if idx == NUM_CONTRACTS {
+                        acc.address(MOCK_WALLETS[0].address()).balance(eth(10));
+                    } else {
+                        acc.address(contract_address(idx))
+                            .balance(eth(1))
+                            .code(self.contracts.get(idx).cloned().unwrap_or_default());
+                    }
+                }
+            },
+            |mut txs, accs| {
+                txs[0]
+                    .from(MOCK_WALLETS[0].clone())
+                    .to(accs[0].address)
+                    .input(self.calldata.clone())
+                    .value(self.value)
+                    .gas(self.gas.into());
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+    }
+}
@@ -0,0 +1,77 @@
+//! Differential fuzzing of the witness generator.
+//!
+//! Random [`eth_types::Bytecode`] programs are deployed with
+//! [`mock::TestContext`], traced and fed through the
+//! [`bus_mapping::circuit_input_builder::CircuitInputBuilder`]. The final
+//! state of the root call (stack, memory, storage, gas and return data) is
+//! rebuilt from the bus-mapping operations and compared against the same
+//! transaction executed by revm.
+
+#![deny(missing_docs)]
+
+pub mod case;
+pub mod outcome;
+pub mod program;
+pub mod reference;
+pub mod witness;
+
+pub use case::FuzzCase;
+pub use outcome::{Mismatch, Outcome};
+
+use bus_mapping::error::ExecError;
+use eth_types::{geth_types::GethData, l2_predeployed::l1_gas_price_oracle};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use zkevm_circuits::test_util::CircuitTestBuilder;
+
+/// Result of checking a single case.
+#[derive(Clone, Debug)]
+pub struct CaseResult {
+    /// Fields where bus-mapping and revm disagree.
+    pub mismatches: Vec<Mismatch>,
+    /// Errors of the execution steps, used to track covered error paths.
+    pub exec_errors: Vec<ExecError>,
+}
+
+/// Run a case through bus-mapping and revm and compare both outcomes.
+///
+/// Returns `Ok(None)` if the case cannot be traced (for example because the
+/// tx gas is below the intrinsic gas), in which case it should be discarded.
+pub fn check_case(case: &FuzzCase) -> Result<Option<CaseResult>, String> {
+    let ctx = match case.test_ctx() {
+        Ok(ctx) => ctx,
+        Err(err) => {
+            log::debug!("discarding case, tracer failed: {err:?}");
+            return Ok(None);
+        }
+    };
+    let geth_data: GethData = ctx.into();
+    if !reference::trace_mode_matches(&geth_data) {
+        log::warn!("mock and fuzz crate disagree on the scroll feature, skipping case");
+        return Ok(None);
+    }
+    let mut expected = reference::execute(&geth_data)?;
+    let (mut found, exec_errors) = witness::execute(geth_data)?;
+    // The l1 fee oracle is read by the l2 begin tx, which is not part of the
+    // fuzzed programs.
+    for outcome in [&mut expected, &mut found] {
+        outcome
+            .storage
+            .retain(|(address, _), _| *address != *l1_gas_price_oracle::ADDRESS);
+    }
+    Ok(Some(CaseResult {
+        mismatches: expected.diff(&found),
+        exec_errors,
+    }))
+}
+
+/// Run the EVM circuit MockProver on a case, typically a minimized failure.
+/// Returns whether all constraints were satisfied.
+pub fn mock_prove_case(case: &FuzzCase) -> bool {
+    let Ok(ctx) = case.test_ctx() else {
+        return false;
+    };
+    catch_unwind(AssertUnwindSafe(|| {
+        CircuitTestBuilder::new_from_test_ctx(ctx).run();
+    }))
+    .is_ok()
+}
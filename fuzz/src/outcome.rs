@@ -0,0 +1,126 @@
+//! Observable result of executing the root call of a fuzz case.
+
+use eth_types::{Address, Word};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+/// Final state of the root call, as seen by one of the executors.
+///
+/// `stack` and `memory` are taken right before the last opcode of the root
+/// call is executed, `stack` is ordered from bottom to top.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Outcome {
+    /// Whether the tx succeeded.
+    pub success: bool,
+    /// Gas used by the tx, after refunds.
+    pub gas_used: u64,
+    /// Return data of the root call.
+    pub return_data: Vec<u8>,
+    /// Stack of the root call.
+    pub stack: Vec<Word>,
+    /// Memory of the root call.
+    pub memory: Vec<u8>,
+    /// Non-zero storage slots after the tx.
+    pub storage: BTreeMap<(Address, Word), Word>,
+}
+
+/// A field where two outcomes disagree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    /// Name of the field.
+    pub field: String,
+    /// Value of the reference executor (revm).
+    pub expected: String,
+    /// Value of the witness generator (bus-mapping).
+    pub found: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: expected {}, found {}",
+            self.field, self.expected, self.found
+        )
+    }
+}
+
+impl Outcome {
+    /// Compare `self` (the reference) against `other` field by field.
+    pub fn diff(&self, other: &Outcome) -> Vec<Mismatch> {
+        let mut mismatches = Vec::new();
+        let mut check = |field: &str, expected: String, found: String| {
+            if expected != found {
+                mismatches.push(Mismatch {
+                    field: field.to_string(),
+                    expected,
+                    found,
+                });
+            }
+        };
+        check(
+            "success",
+            self.success.to_string(),
+            other.success.to_string(),
+        );
+        check(
+            "gas_used",
+            self.gas_used.to_string(),
+            other.gas_used.to_string(),
+        );
+        check(
+            "return_data",
+            hex::encode(&self.return_data),
+            hex::encode(&other.return_data),
+        );
+        check(
+            "stack",
+            format!("{:?}", self.stack),
+            format!("{:?}", other.stack),
+        );
+        check(
+            "memory",
+            hex::encode(&self.memory),
+            hex::encode(&other.memory),
+        );
+        let keys: BTreeSet<_> = self.storage.keys().chain(other.storage.keys()).collect();
+        for key in keys {
+            let expected = self.storage.get(key).copied().unwrap_or_default();
+            let found = other.storage.get(key).copied().unwrap_or_default();
+            if expected != found {
+                check(
+                    &format!("storage[{:?}][{:#x}]", key.0, key.1),
+                    format!("{expected:#x}"),
+                    format!("{found:#x}"),
+                );
+            }
+        }
+        mismatches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_reports_storage_once() {
+        let key = (Address::repeat_byte(1), Word::one());
+        let expected = Outcome {
+            success: true,
+            storage: BTreeMap::from([(key, Word::from(2))]),
+            ..Default::default()
+        };
+        let found = Outcome {
+            success: true,
+            ..Default::default()
+        };
+        let mismatches = expected.diff(&found);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].expected, "0x2");
+        assert_eq!(mismatches[0].found, "0x0");
+        assert!(found.diff(&found).is_empty());
+    }
+}
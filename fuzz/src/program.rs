@@ -0,0 +1,504 @@
+//! Random program generation.
+//!
+//! Programs are built from [`Snippet`]s which push their own operands, so that
+//! most of them execute without stack errors. Error paths are reached through
+//! dedicated snippets (stack overflow, invalid jump, invalid opcode), random
+//! `POP`s, out of bounds `RETURNDATACOPY`, failing precompile inputs and low
+//! gas limits.
+
+use crate::{
+    case::{contract_address, NUM_CONTRACTS},
+    FuzzCase,
+};
+use eth_types::{evm_types::OpcodeId, Bytecode, ToWord, Word};
+use proptest::{collection::vec, prelude::*, sample::select};
+
+const BINARY_OPS: &[OpcodeId] = &[
+    OpcodeId::ADD,
+    OpcodeId::MUL,
+    OpcodeId::SUB,
+    OpcodeId::DIV,
+    OpcodeId::SDIV,
+    OpcodeId::MOD,
+    OpcodeId::SMOD,
+    OpcodeId::EXP,
+    OpcodeId::SIGNEXTEND,
+    OpcodeId::LT,
+    OpcodeId::GT,
+    OpcodeId::SLT,
+    OpcodeId::SGT,
+    OpcodeId::EQ,
+    OpcodeId::AND,
+    OpcodeId::OR,
+    OpcodeId::XOR,
+    OpcodeId::BYTE,
+    OpcodeId::SHL,
+    OpcodeId::SHR,
+    OpcodeId::SAR,
+];
+
+const TERNARY_OPS: &[OpcodeId] = &[OpcodeId::ADDMOD, OpcodeId::MULMOD];
+
+const UNARY_OPS: &[OpcodeId] = &[OpcodeId::ISZERO, OpcodeId::NOT];
+
+// Block hash, difficulty and blob related opcodes are left out, their values
+// are not part of the mock block context shared with revm.
+const ENV_OPS: &[OpcodeId] = &[
+    OpcodeId::ADDRESS,
+    OpcodeId::ORIGIN,
+    OpcodeId::CALLER,
+    OpcodeId::CALLVALUE,
+    OpcodeId::CALLDATASIZE,
+    OpcodeId::CODESIZE,
+    OpcodeId::GASPRICE,
+    OpcodeId::RETURNDATASIZE,
+    OpcodeId::COINBASE,
+    OpcodeId::TIMESTAMP,
+    OpcodeId::NUMBER,
+    OpcodeId::GASLIMIT,
+    OpcodeId::CHAINID,
+    OpcodeId::SELFBALANCE,
+    OpcodeId::BASEFEE,
+    OpcodeId::PC,
+    OpcodeId::MSIZE,
+    OpcodeId::GAS,
+];
+
+const CALL_OPS: &[OpcodeId] = &[
+    OpcodeId::CALL,
+    OpcodeId::CALLCODE,
+    OpcodeId::DELEGATECALL,
+    OpcodeId::STATICCALL,
+];
+
+/// Addresses of the called precompiles, from ecrecover to blake2f.
+const PRECOMPILES: std::ops::RangeInclusive<u64> = 1..=9;
+
+/// Upper bound of the memory offsets used by the snippets.
+const MAX_OFFSET: u16 = 1024;
+
+/// Callee of a generated call.
+#[derive(Clone, Debug)]
+pub enum CallTarget {
+    /// One of the contracts of the case.
+    Contract(usize),
+    /// A precompile address.
+    Precompile(u64),
+}
+
+/// A group of opcodes that pushes its own operands.
+#[derive(Clone, Debug)]
+pub enum Snippet {
+    /// Binary arithmetic, comparison or bitwise opcode.
+    Binary(OpcodeId, Word, Word),
+    /// `ADDMOD` or `MULMOD`.
+    Ternary(OpcodeId, Word, Word, Word),
+    /// `ISZERO` or `NOT`.
+    Unary(OpcodeId, Word),
+    /// Opcode without operands reading the call or block context.
+    Env(OpcodeId),
+    /// `POP`, underflows if the stack is empty.
+    Pop,
+    /// `MSTORE` at the given offset.
+    MStore(u16, Word),
+    /// `MSTORE8` at the given offset.
+    MStore8(u16, Word),
+    /// `MLOAD` at the given offset.
+    MLoad(u16),
+    /// `SSTORE` to the given slot.
+    SStore(u8, Word),
+    /// `SLOAD` of the given slot.
+    SLoad(u8),
+    /// `TSTORE` to the given slot.
+    TStore(u8, Word),
+    /// `TLOAD` of the given slot.
+    TLoad(u8),
+    /// `SHA3` of a memory range.
+    Sha3(u16, u8),
+    /// `CALLDATALOAD` at the given offset.
+    CallDataLoad(u8),
+    /// `CALLDATACOPY(dest, offset, size)`.
+    CallDataCopy(u16, u8, u8),
+    /// `RETURNDATACOPY(dest, offset, size)`, may be out of bounds.
+    ReturnDataCopy(u16, u8, u8),
+    /// `LOG0`..`LOG4` of a memory range.
+    Log(u8, u16, u8),
+    /// Any of the call opcodes.
+    Call {
+        /// Call opcode.
+        op: OpcodeId,
+        /// Callee.
+        target: CallTarget,
+        /// Gas forwarded to the callee.
+        gas: u32,
+        /// Value, ignored for `DELEGATECALL` and `STATICCALL`.
+        value: u8,
+        /// Offset and size of the call data in memory.
+        args: (u16, u8),
+        /// Offset and size of the return data in memory.
+        ret: (u16, u8),
+    },
+    /// `CREATE` or, if a salt is given, `CREATE2`.
+    Create {
+        /// Salt of `CREATE2`.
+        salt: Option<Word>,
+        /// Endowment of the new contract.
+        value: u8,
+        /// Init code, copied to memory at offset 0.
+        init_code: Vec<u8>,
+    },
+    /// A loop pushing to the stack until it overflows (or runs out of gas).
+    StackOverflow,
+    /// `JUMP` to the given destination, which is almost never a `JUMPDEST`.
+    InvalidJump(u16),
+}
+
+/// How a program ends.
+#[derive(Clone, Debug)]
+pub enum Terminator {
+    /// `STOP`.
+    Stop,
+    /// `RETURN` of a memory range.
+    Return(u16, u8),
+    /// `REVERT` of a memory range.
+    Revert(u16, u8),
+    /// The designated invalid opcode `0xfe`.
+    Invalid,
+    /// Run past the end of the code.
+    FallThrough,
+}
+
+/// A generated program.
+#[derive(Clone, Debug)]
+pub struct Program {
+    /// Body of the program.
+    pub snippets: Vec<Snippet>,
+    /// Last opcode of the program.
+    pub terminator: Terminator,
+}
+
+/// Push operands so that the first one ends up on top of the stack.
+fn push_operands(code: &mut Bytecode, operands: &[Word]) {
+    for operand in operands.iter().rev() {
+        code.push(32, *operand);
+    }
+}
+
+impl Snippet {
+    fn assemble(&self, code: &mut Bytecode) {
+        match self {
+            Self::Binary(op, a, b) => {
+                push_operands(code, &[*a, *b]);
+                code.write_op(*op);
+            }
+            Self::Ternary(op, a, b, n) => {
+                push_operands(code, &[*a, *b, *n]);
+                code.write_op(*op);
+            }
+            Self::Unary(op, a) => {
+                push_operands(code, &[*a]);
+                code.write_op(*op);
+            }
+            Self::Env(op) => {
+                code.write_op(*op);
+            }
+            Self::Pop => {
+                code.write_op(OpcodeId::POP);
+            }
+            Self::MStore(offset, value) => {
+                push_operands(code, &[(*offset).into(), *value]);
+                code.write_op(OpcodeId::MSTORE);
+            }
+            Self::MStore8(offset, value) => {
+                push_operands(code, &[(*offset).into(), *value]);
+                code.write_op(OpcodeId::MSTORE8);
+            }
+            Self::MLoad(offset) => {
+                push_operands(code, &[(*offset).into()]);
+                code.write_op(OpcodeId::MLOAD);
+            }
+            Self::SStore(slot, value) => {
+                push_operands(code, &[(*slot).into(), *value]);
+                code.write_op(OpcodeId::SSTORE);
+            }
+            Self::SLoad(slot) => {
+                push_operands(code, &[(*slot).into()]);
+                code.write_op(OpcodeId::SLOAD);
+            }
+            Self::TStore(slot, value) => {
+                push_operands(code, &[(*slot).into(), *value]);
+                code.write_op(OpcodeId::TSTORE);
+            }
+            Self::TLoad(slot) => {
+                push_operands(code, &[(*slot).into()]);
+                code.write_op(OpcodeId::TLOAD);
+            }
+            Self::Sha3(offset, size) => {
+                push_operands(code, &[(*offset).into(), (*size).into()]);
+                code.write_op(OpcodeId::SHA3);
+            }
+            Self::CallDataLoad(offset) => {
+                push_operands(code, &[(*offset).into()]);
+                code.write_op(OpcodeId::CALLDATALOAD);
+            }
+            Self::CallDataCopy(dest, offset, size) => {
+                push_operands(code, &[(*dest).into(), (*offset).into(), (*size).into()]);
+                code.write_op(OpcodeId::CALLDATACOPY);
+            }
+            Self::ReturnDataCopy(dest, offset, size) => {
+                push_operands(code, &[(*dest).into(), (*offset).into(), (*size).into()]);
+                code.write_op(OpcodeId::RETURNDATACOPY);
+            }
+            Self::Log(topics, offset, size) => {
+                let topics = *topics % 5;
+                let mut operands = vec![(*offset).into(), (*size).into()];
+                operands.extend((0..topics).map(Word::from));
+                push_operands(code, &operands);
+                code.write_op(OpcodeId::from(OpcodeId::LOG0.as_u8() + topics));
+            }
+            Self::Call {
+                op,
+                target,
+                gas,
+                value,
+                args,
+                ret,
+            } => {
+                let address = match target {
+                    CallTarget::Contract(idx) => contract_address(*idx).to_word(),
+                    CallTarget::Precompile(address) => Word::from(*address),
+                };
+                let mut operands = vec![Word::from(*gas), address];
+                if matches!(op, OpcodeId::CALL | OpcodeId::CALLCODE) {
+                    operands.push((*value).into());
+                }
+                operands.extend([args.0.into(), args.1.into(), ret.0.into(), ret.1.into()]);
+                push_operands(code, &operands);
+                code.write_op(*op);
+            }
+            Self::Create {
+                salt,
+                value,
+                init_code,
+            } => {
+                for (idx, chunk) in init_code.chunks(32).enumerate() {
+                    let mut word = [0u8; 32];
+                    word[..chunk.len()].copy_from_slice(chunk);
+                    push_operands(code, &[(idx * 32).into(), Word::from_big_endian(&word)]);
+                    code.write_op(OpcodeId::MSTORE);
+                }
+                let mut operands = vec![(*value).into(), Word::zero(), init_code.len().into()];
+                if let Some(salt) = salt {
+                    operands.push(*salt);
+                }
+                push_operands(code, &operands);
+                code.write_op(if salt.is_some() {
+                    OpcodeId::CREATE2
+                } else {
+                    OpcodeId::CREATE
+                });
+            }
+            Self::StackOverflow => {
+                let dest = code.op_jumpdest() - 1;
+                code.push(1, Word::zero());
+                code.push(2, dest);
+                code.write_op(OpcodeId::JUMP);
+            }
+            Self::InvalidJump(dest) => {
+                push_operands(code, &[(*dest).into()]);
+                code.write_op(OpcodeId::JUMP);
+            }
+        }
+    }
+}
+
+impl Terminator {
+    fn assemble(&self, code: &mut Bytecode) {
+        match self {
+            Self::Stop => {
+                code.write_op(OpcodeId::STOP);
+            }
+            Self::Return(offset, size) => {
+                push_operands(code, &[(*offset).into(), (*size).into()]);
+                code.write_op(OpcodeId::RETURN);
+            }
+            Self::Revert(offset, size) => {
+                push_operands(code, &[(*offset).into(), (*size).into()]);
+                code.write_op(OpcodeId::REVERT);
+            }
+            Self::Invalid => {
+                code.write_op(OpcodeId::INVALID(0xfe));
+            }
+            Self::FallThrough => {}
+        }
+    }
+}
+
+impl Program {
+    /// Assemble the program.
+    pub fn to_bytecode(&self) -> Bytecode {
+        let mut code = Bytecode::default();
+        for snippet in &self.snippets {
+            snippet.assemble(&mut code);
+        }
+        self.terminator.assemble(&mut code);
+        code
+    }
+}
+
+fn word() -> impl Strategy<Value = Word> {
+    prop_oneof![
+        (0u64..64).prop_map(Word::from),
+        Just(Word::MAX),
+        Just(Word::one() << 255),
+        any::<[u8; 32]>().prop_map(|bytes| Word::from_big_endian(&bytes)),
+    ]
+}
+
+fn offset() -> impl Strategy<Value = u16> {
+    0..MAX_OFFSET
+}
+
+fn call_target() -> impl Strategy<Value = CallTarget> {
+    prop_oneof![
+        (0..NUM_CONTRACTS).prop_map(CallTarget::Contract),
+        PRECOMPILES.prop_map(CallTarget::Precompile),
+    ]
+}
+
+/// Snippets that do not create contracts.
+fn leaf_snippet() -> impl Strategy<Value = Snippet> {
+    prop_oneof![
+        (select(BINARY_OPS), word(), word()).prop_map(|(op, a, b)| Snippet::Binary(op, a, b)),
+        (select(TERNARY_OPS), word(), word(), word())
+            .prop_map(|(op, a, b, n)| Snippet::Ternary(op, a, b, n)),
+        (select(UNARY_OPS), word()).prop_map(|(op, a)| Snippet::Unary(op, a)),
+        select(ENV_OPS).prop_map(Snippet::Env),
+        Just(Snippet::Pop),
+        (offset(), word()).prop_map(|(offset, value)| Snippet::MStore(offset, value)),
+        (offset(), word()).prop_map(|(offset, value)| Snippet::MStore8(offset, value)),
+        offset().prop_map(Snippet::MLoad),
+        (0u8..8, word()).prop_map(|(slot, value)| Snippet::SStore(slot, value)),
+        (0u8..8).prop_map(Snippet::SLoad),
+        (0u8..8, word()).prop_map(|(slot, value)| Snippet::TStore(slot, value)),
+        (0u8..8).prop_map(Snippet::TLoad),
+        (offset(), any::<u8>()).prop_map(|(offset, size)| Snippet::Sha3(offset, size)),
+        any::<u8>().prop_map(Snippet::CallDataLoad),
+        (offset(), any::<u8>(), any::<u8>())
+            .prop_map(|(dest, offset, size)| Snippet::CallDataCopy(dest, offset, size)),
+        (offset(), 0u8..64, 0u8..64)
+            .prop_map(|(dest, offset, size)| Snippet::ReturnDataCopy(dest, offset, size)),
+        (0u8..5, offset(), any::<u8>())
+            .prop_map(|(topics, offset, size)| Snippet::Log(topics, offset, size)),
+        (
+            select(CALL_OPS),
+            call_target(),
+            any::<u32>(),
+            0u8..4,
+            (offset(), any::<u8>()),
+            (offset(), any::<u8>()),
+        )
+            .prop_map(|(op, target, gas, value, args, ret)| Snippet::Call {
+                op,
+                target,
+                gas,
+                value,
+                args,
+                ret,
+            }),
+        Just(Snippet::StackOverflow),
+        offset().prop_map(Snippet::InvalidJump),
+    ]
+}
+
+fn terminator() -> impl Strategy<Value = Terminator> {
+    prop_oneof![
+        Just(Terminator::Stop),
+        (offset(), any::<u8>()).prop_map(|(offset, size)| Terminator::Return(offset, size)),
+        (offset(), any::<u8>()).prop_map(|(offset, size)| Terminator::Revert(offset, size)),
+        Just(Terminator::Invalid),
+        Just(Terminator::FallThrough),
+    ]
+}
+
+/// Init code of a created contract: a short program returning a memory range
+/// as the deployed code.
+fn init_code() -> impl Strategy<Value = Vec<u8>> {
+    (vec(leaf_snippet(), 0..8), offset(), any::<u8>()).prop_map(|(snippets, offset, size)| {
+        Program {
+            snippets,
+            terminator: Terminator::Return(offset, size),
+        }
+        .to_bytecode()
+        .code()
+    })
+}
+
+fn snippet() -> impl Strategy<Value = Snippet> {
+    prop_oneof![
+        19 => leaf_snippet(),
+        1 => (proptest::option::of(word()), 0u8..4, init_code()).prop_map(
+            |(salt, value, init_code)| Snippet::Create {
+                salt,
+                value,
+                init_code,
+            }
+        ),
+    ]
+}
+
+/// Strategy for a program of up to `max_len` snippets.
+pub fn program(max_len: usize) -> impl Strategy<Value = Program> {
+    (vec(snippet(), 0..max_len), terminator()).prop_map(|(snippets, terminator)| Program {
+        snippets,
+        terminator,
+    })
+}
+
+/// Strategy for a whole fuzz case.
+pub fn fuzz_case() -> impl Strategy<Value = FuzzCase> {
+    (
+        vec(program(24), NUM_CONTRACTS),
+        vec(any::<u8>(), 0..96),
+        0u8..4,
+        50_000u64..2_000_000,
+    )
+        .prop_map(|(programs, calldata, value, gas)| FuzzCase {
+            contracts: programs
+                .iter()
+                .map(|program| program.to_bytecode().code().into())
+                .collect(),
+            calldata: calldata.into(),
+            value: value.into(),
+            gas,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snippets_push_their_operands() {
+        let program = Program {
+            snippets: vec![
+                Snippet::Binary(OpcodeId::SUB, 5.into(), 3.into()),
+                Snippet::Create {
+                    salt: None,
+                    value: 0,
+                    init_code: vec![0x00],
+                },
+            ],
+            terminator: Terminator::Stop,
+        };
+        let code = program.to_bytecode().code();
+        // PUSH32 3, PUSH32 5, SUB
+        assert_eq!(code[0], OpcodeId::PUSH32.as_u8());
+        assert_eq!(code[32], 3);
+        assert_eq!(code[33], OpcodeId::PUSH32.as_u8());
+        assert_eq!(code[65], 5);
+        assert_eq!(code[66], OpcodeId::SUB.as_u8());
+        assert_eq!(*code.last().unwrap(), OpcodeId::STOP.as_u8());
+        assert_eq!(code[code.len() - 2], OpcodeId::CREATE.as_u8());
+    }
+}
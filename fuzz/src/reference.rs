@@ -0,0 +1,178 @@
+//! Outcome of a fuzz case executed by revm, the reference implementation.
+
+use crate::Outcome;
+use eth_types::{geth_types::GethData, l2_predeployed::l1_gas_price_oracle, Address, Word};
+use revm::{
+    db::{CacheDB, EmptyDB},
+    inspector_handle_register,
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter},
+    primitives::{
+        AccountInfo, Bytecode, ExecutionResult, Output, ResultAndState, SpecId, TransactTo, U256,
+    },
+    Database, Evm, EvmContext, Inspector,
+};
+
+#[cfg(feature = "scroll")]
+const SPEC_ID: SpecId = SpecId::CURIE;
+#[cfg(not(feature = "scroll"))]
+const SPEC_ID: SpecId = SpecId::CANCUN;
+
+/// Whether the traces were generated with the same EVM flavour (l1 geth or
+/// l2geth) as the one revm is configured with. The scroll feature of `mock`
+/// may be enabled by feature unification without the one of this crate.
+pub fn trace_mode_matches(geth_data: &GethData) -> bool {
+    let has_l2_system_contract = geth_data
+        .accounts
+        .iter()
+        .any(|account| account.address == *l1_gas_price_oracle::ADDRESS);
+    has_l2_system_contract == cfg!(feature = "scroll")
+}
+
+fn to_revm_address(address: &Address) -> revm::primitives::Address {
+    revm::primitives::Address::from_slice(address.as_bytes())
+}
+
+fn to_revm_word(word: &Word) -> U256 {
+    U256::from_limbs(word.0)
+}
+
+fn from_revm_word(word: &U256) -> Word {
+    Word(word.into_limbs())
+}
+
+/// Captures the stack and memory of the root call before each of its steps.
+#[derive(Debug, Default)]
+struct RootCallInspector {
+    depth: usize,
+    stack: Vec<U256>,
+    memory: Vec<u8>,
+}
+
+impl<DB: Database> Inspector<DB> for RootCallInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        if self.depth == 1 {
+            self.stack = interp.stack.data().clone();
+            self.memory = interp.shared_memory.context_memory().to_vec();
+        }
+    }
+
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.depth += 1;
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.depth -= 1;
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.depth += 1;
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.depth -= 1;
+        outcome
+    }
+}
+
+/// Execute the first tx of the traced case with revm, starting from the same
+/// pre-state and block context.
+pub fn execute(geth_data: &GethData) -> Result<Outcome, String> {
+    let mut db = CacheDB::new(EmptyDB::default());
+    for account in &geth_data.accounts {
+        let address = to_revm_address(&account.address);
+        let mut info = AccountInfo::from_bytecode(Bytecode::new_raw(account.code.to_vec().into()));
+        info.balance = to_revm_word(&account.balance);
+        info.nonce = account.nonce.as_u64();
+        db.insert_account_info(address, info);
+        for (key, value) in &account.storage {
+            db.insert_account_storage(address, to_revm_word(key), to_revm_word(value))
+                .map_err(|e| format!("{e:?}"))?;
+        }
+    }
+
+    let block = &geth_data.eth_block;
+    let tx = &block.transactions[0];
+    let mut evm = Evm::builder()
+        .with_db(db)
+        .with_external_context(RootCallInspector::default())
+        .with_spec_id(SPEC_ID)
+        .append_handler_register(inspector_handle_register)
+        .modify_cfg_env(|cfg| cfg.chain_id = geth_data.chain_id)
+        .modify_block_env(|env| {
+            env.number = U256::from(block.number.unwrap_or_default().as_u64());
+            env.coinbase = to_revm_address(&block.author.unwrap_or_default());
+            env.timestamp = to_revm_word(&block.timestamp);
+            env.gas_limit = to_revm_word(&block.gas_limit);
+            env.basefee = to_revm_word(&block.base_fee_per_gas.unwrap_or_default());
+            env.difficulty = to_revm_word(&block.difficulty);
+        })
+        .modify_tx_env(|env| {
+            env.caller = to_revm_address(&tx.from);
+            env.transact_to = match tx.to {
+                Some(to) => TransactTo::Call(to_revm_address(&to)),
+                None => TransactTo::Create,
+            };
+            env.value = to_revm_word(&tx.value);
+            env.data = tx.input.to_vec().into();
+            env.gas_limit = tx.gas.as_u64();
+            env.gas_price = to_revm_word(&tx.gas_price.unwrap_or_default());
+            env.nonce = Some(tx.nonce.as_u64());
+            env.chain_id = Some(geth_data.chain_id);
+        })
+        .build();
+
+    let ResultAndState { result, state } = evm.transact().map_err(|e| format!("{e:?}"))?;
+    let inspector = &evm.context.external;
+
+    let (success, return_data) = match &result {
+        ExecutionResult::Success { output, .. } => match output {
+            Output::Call(data) => (true, data.to_vec()),
+            Output::Create(data, _) => (true, data.to_vec()),
+        },
+        ExecutionResult::Revert { output, .. } => (false, output.to_vec()),
+        ExecutionResult::Halt { .. } => (false, Vec::new()),
+    };
+    let storage = state
+        .iter()
+        .flat_map(|(address, account)| {
+            let address = Address::from_slice(address.as_slice());
+            account.storage.iter().map(move |(key, slot)| {
+                (
+                    (address, from_revm_word(key)),
+                    from_revm_word(&slot.present_value),
+                )
+            })
+        })
+        .filter(|(_, value)| !value.is_zero())
+        .collect();
+
+    Ok(Outcome {
+        success,
+        gas_used: result.gas_used(),
+        return_data,
+        stack: inspector.stack.iter().map(from_revm_word).collect(),
+        memory: inspector.memory.clone(),
+        storage,
+    })
+}
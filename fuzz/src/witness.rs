@@ -0,0 +1,125 @@
+//! Outcome of a fuzz case rebuilt from the bus-mapping operations.
+
+use crate::Outcome;
+use bus_mapping::{
+    circuit_input_builder::{CircuitInputBuilder, ExecState},
+    error::ExecError,
+    mock::BlockData,
+    operation::{RWCounter, TxReceiptField},
+};
+use eth_types::{evm_types::OpcodeId, geth_types::GethData, Word};
+use std::collections::BTreeMap;
+
+/// Stack size limit, the stack pointer of an empty stack.
+const STACK_LIMIT: usize = 1024;
+
+/// Run the witness generator over the traced case and rebuild the outcome of
+/// its first tx, together with the errors of all execution steps.
+pub fn execute(geth_data: GethData) -> Result<(Outcome, Vec<ExecError>), String> {
+    let block_data = BlockData::new_from_geth_data(geth_data);
+    let mut builder = block_data.new_circuit_input_builder();
+    builder
+        .handle_block(&block_data.eth_block, &block_data.geth_traces)
+        .map_err(|e| format!("bus-mapping failed: {e:?}"))?;
+    Ok((outcome(&builder), exec_errors(&builder)))
+}
+
+fn exec_errors(builder: &CircuitInputBuilder) -> Vec<ExecError> {
+    builder
+        .block
+        .txs
+        .iter()
+        .flat_map(|tx| tx.steps())
+        .filter_map(|step| step.error.clone())
+        .collect()
+}
+
+fn outcome(builder: &CircuitInputBuilder) -> Outcome {
+    let container = &builder.block.container;
+    let tx = &builder.block.txs[0];
+    let root = &tx.calls()[0];
+
+    let gas_used = container
+        .tx_receipt
+        .iter()
+        .find(|op| op.op().tx_id == 1 && op.op().field == TxReceiptField::CumulativeGasUsed)
+        .map(|op| op.op().value)
+        .unwrap_or_default();
+
+    let storage = container
+        .storage
+        .iter()
+        .map(|op| ((op.op().address, op.op().key), op.op().value))
+        .collect::<BTreeMap<_, _>>()
+        .into_iter()
+        .filter(|(_, value)| !value.is_zero())
+        .collect();
+
+    let mut outcome = Outcome {
+        success: root.is_success,
+        gas_used,
+        storage,
+        ..Default::default()
+    };
+
+    let last_step = tx
+        .steps()
+        .iter()
+        .rev()
+        .find(|step| step.call_index == 0 && matches!(step.exec_state, ExecState::Op(_)));
+    let Some(last_step) = last_step else {
+        return outcome;
+    };
+    let before_last_step = |rwc: RWCounter| rwc.0 < last_step.rwc.0;
+
+    // Replay the stack and memory operations of the root call up to the last
+    // step. Reads are replayed as well, they must agree with the latest write.
+    let mut stack = BTreeMap::new();
+    for op in container
+        .stack
+        .iter()
+        .filter(|op| before_last_step(op.rwc()))
+    {
+        if op.op().call_id == root.call_id {
+            stack.insert(op.op().address.0, op.op().value);
+        }
+    }
+    let mut memory = vec![0u8; last_step.memory_size];
+    for op in container
+        .memory
+        .iter()
+        .filter(|op| before_last_step(op.rwc()))
+    {
+        let address = op.op().address.0;
+        if op.op().call_id == root.call_id && address + 32 <= memory.len() {
+            memory[address..address + 32].copy_from_slice(&op.op().value.to_be_bytes());
+        }
+    }
+    // The stack grows downwards from `STACK_LIMIT`.
+    outcome.stack = (STACK_LIMIT - last_step.stack_size..STACK_LIMIT)
+        .rev()
+        .map(|address| stack.get(&address).copied().unwrap_or_default())
+        .collect();
+
+    let is_return = matches!(
+        last_step.exec_state,
+        ExecState::Op(OpcodeId::RETURN | OpcodeId::REVERT)
+    );
+    if is_return && last_step.error.is_none() && outcome.stack.len() >= 2 {
+        let top = outcome.stack.len() - 1;
+        outcome.return_data = memory_slice(&memory, outcome.stack[top], outcome.stack[top - 1]);
+    }
+    outcome.memory = memory;
+    outcome
+}
+
+/// Read `size` bytes at `offset`, zero padded past the end of the memory.
+fn memory_slice(memory: &[u8], offset: Word, size: Word) -> Vec<u8> {
+    if size.is_zero() {
+        return Vec::new();
+    }
+    let (offset, size) = (offset.as_usize(), size.as_usize());
+    (offset..offset + size)
+        .map(|idx| memory.get(idx).copied().unwrap_or_default())
+        .collect()
+}
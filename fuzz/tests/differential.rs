@@ -0,0 +1,91 @@
+use fuzz::{check_case, mock_prove_case, program::fuzz_case, CaseResult, FuzzCase};
+use itertools::Itertools;
+use proptest::test_runner::{Config, TestCaseError, TestError, TestRunner};
+use std::{cell::RefCell, collections::BTreeMap, path::PathBuf};
+
+#[ctor::ctor]
+fn init_env_logger() {
+    // Enable RUST_LOG during tests
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("error")).init();
+}
+
+fn corpus_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("corpus")
+}
+
+fn read_env_var<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+fn report(result: &CaseResult) -> String {
+    result.mismatches.iter().map(ToString::to_string).join("\n")
+}
+
+#[test]
+fn replay_corpus() {
+    let paths = std::fs::read_dir(corpus_dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "json"))
+        .sorted();
+    for path in paths {
+        let case = FuzzCase::from_file(&path).unwrap();
+        match check_case(&case) {
+            Ok(Some(result)) => assert!(
+                result.mismatches.is_empty(),
+                "{path:?}:\n{}",
+                report(&result)
+            ),
+            Ok(None) => log::warn!("{path:?} skipped"),
+            Err(err) => panic!("{path:?}: {err}"),
+        }
+    }
+}
+
+/// Run with `FUZZ_CASES=<n>` to control the number of cases. The minimized
+/// failing case is written to `failure.json` under the cargo target tmp dir,
+/// out of the replayed corpus, and with
+/// `FUZZ_MOCK_PROVER` set it is also checked with the EVM circuit MockProver.
+#[ignore = "long running"]
+#[test]
+fn differential_fuzz() {
+    let mut runner = TestRunner::new(Config {
+        cases: read_env_var("FUZZ_CASES", 256),
+        ..Config::default()
+    });
+    let exec_errors = RefCell::new(BTreeMap::<String, usize>::new());
+    let result = runner.run(&fuzz_case(), |case| match check_case(&case) {
+        Ok(Some(result)) => {
+            for err in &result.exec_errors {
+                *exec_errors
+                    .borrow_mut()
+                    .entry(format!("{err:?}"))
+                    .or_default() += 1;
+            }
+            if result.mismatches.is_empty() {
+                Ok(())
+            } else {
+                Err(TestCaseError::fail(report(&result)))
+            }
+        }
+        Ok(None) => Err(TestCaseError::reject("untraceable case")),
+        Err(err) => Err(TestCaseError::fail(err)),
+    });
+    log::info!("covered exec errors: {:?}", exec_errors.borrow());
+
+    match result {
+        Ok(()) => {}
+        Err(TestError::Fail(reason, case)) => {
+            let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("failure.json");
+            case.to_file(&path).unwrap();
+            if std::env::var("FUZZ_MOCK_PROVER").is_ok() {
+                log::error!("mock prover satisfied: {}", mock_prove_case(&case));
+            }
+            panic!("minimized failure written to {path:?}:\n{reason}");
+        }
+        Err(err) => panic!("{err}"),
+    }
+}
@@ -100,8 +100,15 @@ type Block struct {
 	Difficulty *hexutil.Big   `json:"difficulty"`
 	GasLimit   *hexutil.Big   `json:"gas_limit"`
 	BaseFee    *hexutil.Big   `json:"base_fee"`
+
+	ParentBeaconBlockRoot *common.Hash `json:"parent_beacon_block_root"`
 }
 
+var (
+	beaconRootsAddress             = common.HexToAddress("0x000F3df6D732807Ef1319fB7B8bB8522d0Beac02")
+	beaconRootsHistoryBufferLength = big.NewInt(8191)
+)
+
 type Account struct {
 	Nonce   hexutil.Uint64              `json:"nonce"`
 	Balance *hexutil.Big                `json:"balance"`
@@ -251,6 +258,15 @@ func Trace(config TraceConfig) ([]*ExecutionResult, error) {
 			stateDB.SetState(address, key, value)
 		}
 	}
+	// EIP-4788: the system call before the txs stores the parent beacon block root, keyed by
+	// the timestamp. Like the circuit input builder, apply its storage writes directly.
+	if root := config.Block.ParentBeaconBlockRoot; root != nil {
+		timestamp := toBigInt(config.Block.Timestamp)
+		timestampSlot := new(big.Int).Mod(timestamp, beaconRootsHistoryBufferLength)
+		rootSlot := new(big.Int).Add(timestampSlot, beaconRootsHistoryBufferLength)
+		stateDB.SetState(beaconRootsAddress, common.BigToHash(timestampSlot), common.BigToHash(timestamp))
+		stateDB.SetState(beaconRootsAddress, common.BigToHash(rootSlot), *root)
+	}
 	stateDB.Finalise(true)
 
 	var (
@@ -0,0 +1,90 @@
+//! Run the MockProver of a single sub-circuit over a witness block dump, as
+//! written by `Block::write_dump_file`.
+//!
+//! Usage: `mock_prove_witness <dump> <evm|state|tx|bytecode|copy|keccak|exp> [degree]`
+//!
+//! The degree defaults to the minimum one required by the circuit for the
+//! witness block.
+
+use halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr, plonk::Circuit};
+use integration_tests::log_init;
+use log::{error, info};
+use std::process::ExitCode;
+use zkevm_circuits::{
+    bytecode_circuit::TestBytecodeCircuit,
+    copy_circuit::TestCopyCircuit,
+    evm_circuit::TestEvmCircuit,
+    exp_circuit::TestExpCircuit,
+    keccak_circuit::TestKeccakCircuit,
+    state_circuit::TestStateCircuit,
+    triage::triage_failures,
+    tx_circuit::TestTxCircuit,
+    util::{log2_ceil, SubCircuit},
+    witness::Block,
+};
+
+const USAGE: &str =
+    "usage: mock_prove_witness <dump> <evm|state|tx|bytecode|copy|keccak|exp> [degree]";
+
+fn mock_prove<C: SubCircuit<Fr> + Circuit<Fr>>(block: &Block, degree: Option<u32>) -> bool {
+    let degree = degree.unwrap_or_else(|| {
+        let (_, rows_needed) = C::min_num_rows_block(block);
+        log2_ceil(C::unusable_rows() + rows_needed)
+    });
+    info!("mock proving with degree {degree}");
+
+    let circuit = C::new_from_block(block);
+    let instance = circuit.instance();
+    let prover = MockProver::<Fr>::run(degree, &circuit, instance).unwrap();
+    match prover.verify_par() {
+        Ok(()) => true,
+        Err(errs) => {
+            error!("{}", triage_failures(block, &errs));
+            false
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    log_init();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (path, circuit) = match args.as_slice() {
+        [path, circuit] | [path, circuit, _] => (path, circuit.as_str()),
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let degree = args
+        .get(2)
+        .map(|degree| degree.parse().expect("invalid degree"));
+
+    let block = Block::read_dump_file(path).expect("cannot read witness block dump");
+    info!(
+        "loaded witness block with {} txs, circuits params {:?}",
+        block.txs.len(),
+        block.circuits_params
+    );
+
+    let satisfied = match circuit {
+        "evm" => mock_prove::<TestEvmCircuit<Fr>>(&block, degree),
+        "state" => mock_prove::<TestStateCircuit<Fr>>(&block, degree),
+        "tx" => mock_prove::<TestTxCircuit<Fr>>(&block, degree),
+        "bytecode" => mock_prove::<TestBytecodeCircuit<Fr>>(&block, degree),
+        "copy" => mock_prove::<TestCopyCircuit<Fr>>(&block, degree),
+        "keccak" => mock_prove::<TestKeccakCircuit<Fr>>(&block, degree),
+        "exp" => mock_prove::<TestExpCircuit<Fr>>(&block, degree),
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if satisfied {
+        info!("{circuit} circuit satisfied");
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{get_client, GenDataOutput};
 use bus_mapping::{
     circuit_input_builder::{
-        BuilderClient, CircuitInputBuilder, CircuitsParams, PrecompileEcParams,
+        BuilderClient, CircuitInputBuilder, CircuitsParams, PrecompileEcParams, N_PAIRING_PER_OP,
     },
     mock::BlockData,
 };
@@ -97,6 +97,7 @@ const CIRCUITS_PARAMS: CircuitsParams = CircuitsParams {
         ec_add: MAX_EC_ADD,
         ec_mul: MAX_EC_MUL,
         ec_pairing: MAX_EC_PAIRING,
+        ec_pairing_pairs: N_PAIRING_PER_OP,
     },
 };
 
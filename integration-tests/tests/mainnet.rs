@@ -1,6 +1,6 @@
 #![allow(unused_mut)]
 use bus_mapping::{
-    circuit_input_builder::{BuilderClient, CircuitsParams, PrecompileEcParams},
+    circuit_input_builder::{BuilderClient, CircuitsParams, PrecompileEcParams, N_PAIRING_PER_OP},
     util::read_env_var,
     Error::JSONRpcError,
 };
@@ -44,6 +44,7 @@ const CIRCUITS_PARAMS: CircuitsParams = CircuitsParams {
         ec_add: 10,
         ec_mul: 10,
         ec_pairing: 4,
+        ec_pairing_pairs: N_PAIRING_PER_OP,
     },
 };
 
@@ -5,7 +5,10 @@ use crate::MOCK_DIFFICULTY;
 #[cfg(feature = "scroll")]
 use crate::MOCK_DIFFICULTY_L2GETH as MOCK_DIFFICULTY;
 use crate::{MockTransaction, MOCK_BASEFEE, MOCK_CHAIN_ID, MOCK_GASLIMIT};
-use eth_types::{Address, Block, Bytes, Hash, Transaction, Word, H64, U64};
+use eth_types::{
+    block_header::set_parent_beacon_block_root, Address, Block, Bytes, Hash, Transaction, Word,
+    H64, U64,
+};
 use ethers_core::types::{Bloom, OtherFields};
 
 #[derive(Clone, Debug)]
@@ -35,6 +38,7 @@ pub struct MockBlock {
     size: Word,
     mix_hash: Hash,
     nonce: H64,
+    parent_beacon_block_root: Option<Hash>,
     // This field is handled here as we assume that all block txs have the same ChainId.
     // Also, the field is stored in the block_table since we don't have a chain_config
     // structure/table.
@@ -66,6 +70,7 @@ impl Default for MockBlock {
             size: Word::zero(),
             mix_hash: Hash::zero(),
             nonce: H64::zero(),
+            parent_beacon_block_root: None,
             chain_id: MOCK_CHAIN_ID,
         }
     }
@@ -73,7 +78,8 @@ impl Default for MockBlock {
 
 impl From<MockBlock> for Block<Transaction> {
     fn from(mut mock: MockBlock) -> Self {
-        Block {
+        let parent_beacon_block_root = mock.parent_beacon_block_root;
+        let mut block = Block {
             hash: mock.hash.or_else(|| Some(Hash::default())),
             parent_hash: mock.parent_hash,
             uncles_hash: mock.uncles_hash,
@@ -124,13 +130,18 @@ impl From<MockBlock> for Block<Transaction> {
             other: OtherFields::default(),
             withdrawals: None,
             withdrawals_root: None,
+        };
+        if let Some(root) = parent_beacon_block_root {
+            set_parent_beacon_block_root(&mut block, root);
         }
+        block
     }
 }
 
 impl From<MockBlock> for Block<()> {
     fn from(mock: MockBlock) -> Self {
-        Block {
+        let parent_beacon_block_root = mock.parent_beacon_block_root;
+        let mut block = Block {
             hash: mock.hash.or_else(|| Some(Hash::default())),
             parent_hash: mock.parent_hash,
             uncles_hash: mock.uncles_hash,
@@ -156,7 +167,11 @@ impl From<MockBlock> for Block<()> {
             other: OtherFields::default(),
             withdrawals: None,
             withdrawals_root: None,
+        };
+        if let Some(root) = parent_beacon_block_root {
+            set_parent_beacon_block_root(&mut block, root);
         }
+        block
     }
 }
 
@@ -297,6 +312,12 @@ impl MockBlock {
         self
     }
 
+    /// Set parent_beacon_block_root field for the MockBlock.
+    pub fn parent_beacon_block_root(&mut self, parent_beacon_block_root: Hash) -> &mut Self {
+        self.parent_beacon_block_root = Some(parent_beacon_block_root);
+        self
+    }
+
     /// Set chain_id field for the MockBlock.
     pub fn chain_id(&mut self, chain_id: u64) -> &mut Self {
         self.chain_id = chain_id;
@@ -2,7 +2,10 @@
 
 use crate::{eth, MockAccount, MockBlock, MockTransaction, MOCK_WALLETS};
 #[cfg(feature = "scroll")]
-use eth_types::l2_types::BlockTrace;
+use eth_types::{
+    block_header::{parent_beacon_block_root, set_parent_beacon_block_root},
+    l2_types::BlockTrace,
+};
 use eth_types::{
     geth_types::{Account, BlockConstants, GethData},
     l2_predeployed::l1_gas_price_oracle,
@@ -211,7 +214,14 @@ impl<const NACC: usize, const NTX: usize> TestContext<NACC, NTX> {
         )?;
 
         #[cfg(feature = "scroll")]
-        let block_trace = l2trace(&trace_config)?;
+        let block_trace = {
+            let mut block_trace = l2trace(&trace_config)?;
+            // the l2geth header has no parent beacon block root
+            if let Some(root) = parent_beacon_block_root(&block) {
+                set_parent_beacon_block_root(&mut block_trace.header, root);
+            }
+            block_trace
+        };
 
         #[cfg(feature = "scroll")]
         let geth_traces = block_trace
@@ -1,7 +1,7 @@
 use crate::{
     common,
-    config::{LayerId, AGG_DEGREES},
-    consts::{AGG_KECCAK_ROW, AGG_VK_FILENAME, CHUNK_PROTOCOL_FILENAME},
+    config::{LayerId, ProverConfig},
+    consts::{AGG_VK_FILENAME, CHUNK_PROTOCOL_FILENAME},
     io::{force_to_read, try_to_read},
     BatchProof, BatchProvingTask, ChunkProof,
 };
@@ -9,7 +9,7 @@ use aggregator::{ChunkInfo, MAX_AGG_SNARKS};
 use anyhow::Result;
 use sha2::{Digest, Sha256};
 use snark_verifier_sdk::Snark;
-use std::{env, iter::repeat};
+use std::iter::repeat;
 
 #[derive(Debug)]
 pub struct Prover {
@@ -21,10 +21,12 @@ pub struct Prover {
 
 impl Prover {
     pub fn from_dirs(params_dir: &str, assets_dir: &str) -> Self {
-        log::debug!("set env KECCAK_ROWS={}", AGG_KECCAK_ROW.to_string());
-        env::set_var("KECCAK_ROWS", AGG_KECCAK_ROW.to_string());
+        Self::from_dirs_with_config(params_dir, assets_dir, ProverConfig::from_env())
+    }
 
-        let prover_impl = common::Prover::from_params_dir(params_dir, &AGG_DEGREES);
+    pub fn from_dirs_with_config(params_dir: &str, assets_dir: &str, config: ProverConfig) -> Self {
+        let prover_impl =
+            common::Prover::from_params_dir(params_dir, &config.agg_degrees()).with_config(config);
         let chunk_protocol = force_to_read(assets_dir, &CHUNK_PROTOCOL_FILENAME);
 
         let raw_vk = try_to_read(assets_dir, &AGG_VK_FILENAME);
@@ -82,7 +84,7 @@ impl Prover {
             &name,
             LayerId::Layer4.id(),
             true,
-            LayerId::Layer4.degree(),
+            self.prover_impl.config().degree(LayerId::Layer4),
             layer3_snark,
             output_dir,
         )?;
@@ -137,7 +139,7 @@ impl Prover {
         let layer3_snark = self.prover_impl.load_or_gen_agg_snark(
             name,
             LayerId::Layer3.id(),
-            LayerId::Layer3.degree(),
+            self.prover_impl.config().degree(LayerId::Layer3),
             &chunk_hashes,
             &layer2_snarks,
             output_dir,
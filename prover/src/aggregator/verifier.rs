@@ -1,6 +1,6 @@
 use crate::{
     common,
-    config::{LAYER4_CONFIG_PATH, LAYER4_DEGREE},
+    config::{LayerId, ProverConfig},
     consts::{agg_vk_filename, DEPLOYMENT_CODE_FILENAME},
     io::force_to_read,
     BatchProof,
@@ -12,7 +12,6 @@ use halo2_proofs::{
     poly::kzg::commitment::ParamsKZG,
 };
 use snark_verifier_sdk::verify_evm_calldata;
-use std::env;
 
 #[derive(Debug)]
 pub struct Verifier {
@@ -36,11 +35,21 @@ impl Verifier {
     }
 
     pub fn from_dirs(params_dir: &str, assets_dir: &str) -> Self {
+        Self::from_dirs_with_config(params_dir, assets_dir, &ProverConfig::from_env())
+    }
+
+    pub fn from_dirs_with_config(
+        params_dir: &str,
+        assets_dir: &str,
+        config: &ProverConfig,
+    ) -> Self {
         let raw_vk = force_to_read(assets_dir, &agg_vk_filename());
         let deployment_code = force_to_read(assets_dir, &DEPLOYMENT_CODE_FILENAME);
 
-        env::set_var("COMPRESSION_CONFIG", &*LAYER4_CONFIG_PATH);
-        let inner = common::Verifier::from_params_dir(params_dir, *LAYER4_DEGREE, &raw_vk);
+        let degree = config.degree(LayerId::Layer4);
+        let inner = config.scoped(LayerId::Layer4, || {
+            common::Verifier::from_params_dir(params_dir, degree, &raw_vk)
+        });
 
         Self {
             inner,
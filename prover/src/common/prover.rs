@@ -1,4 +1,7 @@
-use crate::utils::{load_params, param_path_for_degree};
+use crate::{
+    config::ProverConfig,
+    utils::{load_params, param_path_for_degree},
+};
 use halo2_proofs::{
     halo2curves::bn256::{Bn256, G1Affine},
     plonk::ProvingKey,
@@ -20,6 +23,7 @@ pub struct Prover {
     params_map: BTreeMap<u32, ParamsKZG<Bn256>>,
     // Cached id -> pk
     pk_map: HashMap<String, ProvingKey<G1Affine>>,
+    config: ProverConfig,
 }
 
 impl Prover {
@@ -27,9 +31,19 @@ impl Prover {
         Self {
             params_map,
             pk_map: HashMap::new(),
+            config: ProverConfig::from_env(),
         }
     }
 
+    pub fn with_config(mut self, config: ProverConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn config(&self) -> &ProverConfig {
+        &self.config
+    }
+
     pub fn from_params_dir(params_dir: &str, degrees: &[u32]) -> Self {
         let degrees = BTreeSet::from_iter(degrees);
         let max_degree = **degrees.last().unwrap();
@@ -62,9 +76,6 @@ impl Prover {
             params_map.insert(*d, params);
         }
 
-        Self {
-            params_map,
-            pk_map: HashMap::new(),
-        }
+        Self::from_params(params_map)
     }
 }
@@ -1,6 +1,6 @@
 use super::Prover;
 use crate::{
-    config::layer_config_path,
+    config::LayerId,
     io::{load_snark, write_snark},
     utils::gen_rng,
 };
@@ -8,7 +8,6 @@ use aggregator::{AggregationCircuit, BatchHash, ChunkInfo, MAX_AGG_SNARKS};
 use anyhow::{anyhow, Result};
 use rand::Rng;
 use snark_verifier_sdk::Snark;
-use std::env;
 
 impl Prover {
     pub fn gen_agg_snark(
@@ -19,15 +18,18 @@ impl Prover {
         chunk_hashes: &[ChunkInfo],
         previous_snarks: &[Snark],
     ) -> Result<Snark> {
-        env::set_var("AGGREGATION_CONFIG", layer_config_path(id));
+        let layer =
+            LayerId::from_id(id).unwrap_or_else(|| panic!("Wrong id-{id} to get layer config"));
+        let config = self.config().clone();
+        config.scoped(layer, || {
+            let batch_hash = BatchHash::construct(chunk_hashes);
 
-        let batch_hash = BatchHash::construct(chunk_hashes);
+            let circuit: AggregationCircuit<MAX_AGG_SNARKS> =
+                AggregationCircuit::new(self.params(degree), previous_snarks, &mut rng, batch_hash)
+                    .map_err(|err| anyhow!("Failed to construct aggregation circuit: {err:?}"))?;
 
-        let circuit: AggregationCircuit<MAX_AGG_SNARKS> =
-            AggregationCircuit::new(self.params(degree), previous_snarks, &mut rng, batch_hash)
-                .map_err(|err| anyhow!("Failed to construct aggregation circuit: {err:?}"))?;
-
-        self.gen_snark(id, degree, &mut rng, circuit, "gen_agg_snark")
+            self.gen_snark(id, degree, &mut rng, circuit, "gen_agg_snark")
+        })
     }
 
     pub fn load_or_gen_agg_snark(
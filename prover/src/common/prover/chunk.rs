@@ -21,7 +21,7 @@ impl Prover {
             name,
             LayerId::Layer2.id(),
             true,
-            LayerId::Layer2.degree(),
+            self.config().degree(LayerId::Layer2),
             layer1_snark,
             output_dir,
         )?;
@@ -50,7 +50,7 @@ impl Prover {
 
         // Check pairing for super circuit.
         extract_proof_and_instances_with_pairing_check(
-            self.params(LayerId::Layer1.degree()),
+            self.params(self.config().degree(LayerId::Layer1)),
             &[inner_snark.clone()],
             gen_rng(),
         )
@@ -61,7 +61,7 @@ impl Prover {
             name,
             LayerId::Layer1.id(),
             false,
-            LayerId::Layer1.degree(),
+            self.config().degree(LayerId::Layer1),
             inner_snark,
             output_dir,
         )?;
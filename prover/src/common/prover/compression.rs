@@ -1,6 +1,6 @@
 use super::Prover;
 use crate::{
-    config::layer_config_path,
+    config::LayerId,
     io::{load_snark, write_snark},
     utils::gen_rng,
 };
@@ -8,7 +8,6 @@ use aggregator::CompressionCircuit;
 use anyhow::{anyhow, Result};
 use rand::Rng;
 use snark_verifier_sdk::Snark;
-use std::env;
 
 impl Prover {
     pub fn gen_comp_snark(
@@ -19,12 +18,15 @@ impl Prover {
         mut rng: impl Rng + Send,
         prev_snark: Snark,
     ) -> Result<Snark> {
-        env::set_var("COMPRESSION_CONFIG", layer_config_path(id));
-
-        let circuit =
-            CompressionCircuit::new(self.params(degree), prev_snark, has_accumulator, &mut rng)
-                .map_err(|err| anyhow!("Failed to construct compression circuit: {err:?}"))?;
-        self.gen_snark(id, degree, &mut rng, circuit, "gen_comp_snark")
+        let layer =
+            LayerId::from_id(id).unwrap_or_else(|| panic!("Wrong id-{id} to get layer config"));
+        let config = self.config().clone();
+        config.scoped(layer, || {
+            let circuit =
+                CompressionCircuit::new(self.params(degree), prev_snark, has_accumulator, &mut rng)
+                    .map_err(|err| anyhow!("Failed to construct compression circuit: {err:?}"))?;
+            self.gen_snark(id, degree, &mut rng, circuit, "gen_comp_snark")
+        })
     }
 
     pub fn load_or_gen_comp_snark(
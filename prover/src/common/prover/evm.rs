@@ -1,6 +1,6 @@
 use super::Prover;
 use crate::{
-    config::layer_config_path,
+    config::LayerId,
     utils::{gen_rng, read_env_var},
     EvmProof,
 };
@@ -9,7 +9,6 @@ use anyhow::{anyhow, Result};
 use halo2_proofs::halo2curves::bn256::Fr;
 use rand::Rng;
 use snark_verifier_sdk::{gen_evm_proof_shplonk, CircuitExt, Snark};
-use std::env;
 
 impl Prover {
     pub fn load_or_gen_comp_evm_proof(
@@ -25,18 +24,21 @@ impl Prover {
         match output_dir.and_then(|output_dir| EvmProof::from_json_file(output_dir, &name).ok()) {
             Some(proof) => Ok(proof),
             None => {
-                env::set_var("COMPRESSION_CONFIG", layer_config_path(id));
+                let layer = LayerId::from_id(id)
+                    .unwrap_or_else(|| panic!("Wrong id-{id} to get layer config"));
+                let config = self.config().clone();
+                let result = config.scoped(layer, || {
+                    let mut rng = gen_rng();
+                    let circuit = CompressionCircuit::new(
+                        self.params(degree),
+                        prev_snark,
+                        has_accumulator,
+                        &mut rng,
+                    )
+                    .map_err(|err| anyhow!("Failed to construct compression circuit: {err:?}"))?;
 
-                let mut rng = gen_rng();
-                let circuit = CompressionCircuit::new(
-                    self.params(degree),
-                    prev_snark,
-                    has_accumulator,
-                    &mut rng,
-                )
-                .map_err(|err| anyhow!("Failed to construct compression circuit: {err:?}"))?;
-
-                let result = self.gen_evm_proof(id, degree, &mut rng, circuit, output_dir);
+                    self.gen_evm_proof(id, degree, &mut rng, circuit, output_dir)
+                });
 
                 if let (Some(output_dir), Ok(proof)) = (output_dir, &result) {
                     proof.dump(output_dir, &name)?;
@@ -1,6 +1,6 @@
 use super::Prover;
 use crate::{
-    config::INNER_DEGREE,
+    config::LayerId,
     io::{load_snark, write_snark},
     utils::{gen_rng, metric_of_witness_block},
     zkevm::circuit::{SuperCircuit, TargetCircuit},
@@ -22,20 +22,23 @@ impl Prover {
             metric_of_witness_block(witness_block)
         );
 
-        let degree = *INNER_DEGREE;
+        let config = self.config().clone();
+        let degree = config.inner_degree;
 
-        let circuit = C::from_witness_block(witness_block)?;
+        config.scoped(LayerId::Inner, || {
+            let circuit = C::from_witness_block(witness_block)?;
 
-        Self::assert_if_mock_prover(id, degree, &circuit);
+            Self::assert_if_mock_prover(id, degree, &circuit);
 
-        let (params, pk) = self.params_and_pk(id, degree, &C::dummy_inner_circuit()?)?;
-        log::info!(
-            "gen_inner_snark vk transcript_repr {:?}",
-            pk.get_vk().transcript_repr()
-        );
-        let snark = gen_snark_shplonk(params, pk, circuit, &mut rng, None::<String>)?;
+            let (params, pk) = self.params_and_pk(id, degree, &C::dummy_inner_circuit()?)?;
+            log::info!(
+                "gen_inner_snark vk transcript_repr {:?}",
+                pk.get_vk().transcript_repr()
+            );
+            let snark = gen_snark_shplonk(params, pk, circuit, &mut rng, None::<String>)?;
 
-        Ok(snark)
+            Ok(snark)
+        })
     }
 
     pub fn load_or_gen_inner_snark(
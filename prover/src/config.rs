@@ -1,6 +1,15 @@
-use crate::utils::read_env_var;
+use crate::{consts::AGG_KECCAK_ROW, utils::read_env_var};
 use aggregator::ConfigParams;
-use std::{collections::HashSet, fmt, fs::File, path::Path, sync::LazyLock};
+use eth_types::{constants::BlockConstants, scoped::Scope};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+    fs::File,
+    path::Path,
+    sync::LazyLock,
+};
+use zkevm_circuits::keccak_circuit::keccak_packed_multi::install_num_rows_per_round;
 
 pub static INNER_DEGREE: LazyLock<u32> =
     LazyLock::new(|| read_env_var("SCROLL_PROVER_INNER_DEGREE", 20));
@@ -33,7 +42,7 @@ pub static ZKEVM_DEGREES: LazyLock<Vec<u32>> = LazyLock::new(|| {
 pub static AGG_DEGREES: LazyLock<Vec<u32>> =
     LazyLock::new(|| Vec::from_iter(HashSet::from([*LAYER3_DEGREE, *LAYER4_DEGREE])));
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum LayerId {
     /// Super (inner) circuit layer
     Inner,
@@ -64,6 +73,17 @@ impl LayerId {
         }
     }
 
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "inner" => Some(Self::Inner),
+            "layer1" => Some(Self::Layer1),
+            "layer2" => Some(Self::Layer2),
+            "layer3" => Some(Self::Layer3),
+            "layer4" => Some(Self::Layer4),
+            _ => None,
+        }
+    }
+
     pub fn degree(&self) -> u32 {
         match self {
             Self::Inner => *INNER_DEGREE,
@@ -110,3 +130,142 @@ fn layer_degree(config_file: &str) -> u32 {
 
     params.degree
 }
+
+/// Configuration of a prover, passed explicitly instead of through process-global env vars.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProverConfig {
+    /// Directory of the `layerN.config` files.
+    pub assets_dir: String,
+    /// Degree of the super (inner) circuit.
+    pub inner_degree: u32,
+    /// Keccak rows per round of the super circuit, `None` for the circuit default.
+    pub keccak_rows: Option<usize>,
+    /// Keccak rows per round of the aggregation circuit.
+    pub agg_keccak_rows: usize,
+    /// Chain id, coinbase and difficulty the chunks are proven for.
+    pub block_constants: BlockConstants,
+    /// Layer configs by layer id. Layers not set here are read from `assets_dir`.
+    #[serde(default)]
+    pub layers: BTreeMap<String, ConfigParams>,
+}
+
+impl Default for ProverConfig {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+impl ProverConfig {
+    /// Load the config from the env vars, for compatibility with deployments configured that way.
+    pub fn from_env() -> Self {
+        Self {
+            assets_dir: read_env_var("SCROLL_PROVER_ASSETS_DIR", "configs".to_string()),
+            inner_degree: read_env_var("SCROLL_PROVER_INNER_DEGREE", 20),
+            keccak_rows: std::env::var("KECCAK_ROWS")
+                .ok()
+                .and_then(|rows| rows.parse().ok()),
+            agg_keccak_rows: *AGG_KECCAK_ROW,
+            block_constants: BlockConstants::from_env(),
+            layers: BTreeMap::new(),
+        }
+    }
+
+    /// Set the config of a compression or aggregation layer.
+    pub fn with_layer(mut self, layer: LayerId, params: ConfigParams) -> Self {
+        assert!(
+            layer != LayerId::Inner,
+            "No config for super (inner) circuit"
+        );
+        self.layers.insert(layer.id().to_string(), params);
+        self
+    }
+
+    pub fn layer_config_path(&self, layer: LayerId) -> String {
+        Path::new(&self.assets_dir)
+            .join(format!("{}.config", layer.id()))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    pub fn layer_params(&self, layer: LayerId) -> ConfigParams {
+        if let Some(params) = self.layers.get(layer.id()) {
+            return params.clone();
+        }
+        let path = self.layer_config_path(layer);
+        ConfigParams::from_file(&path).unwrap_or_else(|err| panic!("Failed to load {path}: {err}"))
+    }
+
+    pub fn degree(&self, layer: LayerId) -> u32 {
+        match layer {
+            LayerId::Inner => self.inner_degree,
+            _ => self.layer_params(layer).degree,
+        }
+    }
+
+    pub fn zkevm_degrees(&self) -> Vec<u32> {
+        Vec::from_iter(HashSet::from([
+            self.inner_degree,
+            self.degree(LayerId::Layer1),
+            self.degree(LayerId::Layer2),
+        ]))
+    }
+
+    pub fn agg_degrees(&self) -> Vec<u32> {
+        Vec::from_iter(HashSet::from([
+            self.degree(LayerId::Layer3),
+            self.degree(LayerId::Layer4),
+        ]))
+    }
+
+    /// Run `f` with the circuits of `layer` configured by `self`.
+    ///
+    /// The circuit `configure` functions take no input, so the values are installed for the
+    /// threads running `f`, see [`Scope::run`]. Provers with different configs can run
+    /// concurrently.
+    pub fn scoped<R: Send>(&self, layer: LayerId, f: impl FnOnce() -> R + Send) -> R {
+        let scope = self.block_constants.install(Scope::new());
+        let scope = match layer {
+            LayerId::Inner => match self.keccak_rows {
+                Some(rows) => install_num_rows_per_round(scope, rows),
+                None => scope,
+            },
+            LayerId::Layer3 => self
+                .layer_params(layer)
+                .install_aggregation(install_num_rows_per_round(scope, self.agg_keccak_rows)),
+            _ => self.layer_params(layer).install_compression(scope),
+        };
+        scope.run(f)
+    }
+
+    /// Run `f` with the block constants of `self` installed.
+    pub fn scoped_block_constants<R: Send>(&self, f: impl FnOnce() -> R + Send) -> R {
+        self.block_constants.scoped(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eth_types::constants::get_chain_id_constant;
+    use zkevm_circuits::keccak_circuit::keccak_packed_multi::get_num_rows_per_round;
+
+    #[test]
+    fn test_prover_config_scoped() {
+        let mut config = ProverConfig::from_env();
+        config.keccak_rows = Some(20);
+        config.block_constants.chain_id = 1337;
+        let config: ProverConfig =
+            serde_json::from_str(&serde_json::to_string(&config).unwrap()).unwrap();
+
+        let outside = (get_num_rows_per_round(), get_chain_id_constant());
+        config.scoped(LayerId::Inner, || {
+            assert_eq!(get_num_rows_per_round(), 20);
+            // nested scopes see the values of the outer ones
+            config.scoped_block_constants(|| {
+                assert_eq!(get_num_rows_per_round(), 20);
+                assert_eq!(get_chain_id_constant(), 1337);
+            });
+        });
+        assert_eq!((get_num_rows_per_round(), get_chain_id_constant()), outside);
+    }
+}
@@ -28,9 +28,17 @@ impl<C: TargetCircuit> Prover<C> {
         let circuit = C::from_witness_block(witness_block)?;
         let prover = MockProver::<Fr>::run(*INNER_DEGREE, &circuit, circuit.instances())?;
         if let Err(errs) = prover.verify_par() {
-            log::error!("err num: {}", errs.len());
-            for err in &errs {
-                log::error!("{}", err);
+            #[cfg(feature = "triage")]
+            log::error!(
+                "{}",
+                zkevm_circuits::triage::triage_failures(witness_block, &errs)
+            );
+            #[cfg(not(feature = "triage"))]
+            {
+                log::error!("err num: {}", errs.len());
+                for err in &errs {
+                    log::error!("{}", err);
+                }
             }
             bail!("{:#?}", errs);
         }
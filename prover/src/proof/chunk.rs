@@ -1,6 +1,6 @@
 use super::{dump_as_json, dump_data, dump_vk, from_json_file, Proof};
 use crate::{types::base64, zkevm::SubCircuitRowUsage};
-use aggregator::ChunkInfo;
+use aggregator::{pi::ChunkPi, ChunkInfo};
 use anyhow::{bail, Result};
 use halo2_proofs::{halo2curves::bn256::G1Affine, plonk::ProvingKey};
 use serde_derive::{Deserialize, Serialize};
@@ -84,6 +84,12 @@ impl ChunkProof {
         dump_as_json(dir, &filename, &self)
     }
 
+    /// Check the public input hash of the proof commits to its chunk info.
+    pub fn check_public_inputs(&self) -> Result<()> {
+        ChunkPi::from(&self.chunk_info).check_instances(&self.proof.instances())?;
+        Ok(())
+    }
+
     pub fn to_snark(self) -> Snark {
         let instances = self.proof.instances();
         let protocol = serde_json::from_slice::<Protocol<G1Affine>>(&self.protocol).unwrap();
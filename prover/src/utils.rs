@@ -154,7 +154,7 @@ pub fn metric_of_witness_block(block: &Block) -> ChunkMetric {
     ChunkMetric {
         num_block: block.context.ctxs.len(),
         num_tx: block.txs.len(),
-        num_step: block.txs.iter().map(|tx| tx.steps.len()).sum::<usize>(),
+        num_step: block.steps().count(),
     }
 }
 
@@ -1,4 +1,4 @@
-use super::circuit::{calculate_row_usage_of_witness_block, finalize_builder};
+use super::circuit::{calculate_row_usage_with_trie_model, finalize_builder};
 use bus_mapping::circuit_input_builder::{self, CircuitInputBuilder};
 use eth_types::{
     l2_types::BlockTrace,
@@ -8,8 +8,9 @@ use eth_types::{
 use itertools::Itertools;
 use mpt_zktrie::state::ZktrieState;
 use serde_derive::{Deserialize, Serialize};
-use zkevm_circuits::super_circuit::params::{
-    get_sub_circuit_limit_and_confidence, get_super_circuit_params,
+use zkevm_circuits::{
+    super_circuit::params::{get_sub_circuit_limit_and_confidence, get_super_circuit_params},
+    witness::TriePathModel,
 };
 
 pub use super::SubCircuitRowUsage;
@@ -99,6 +100,12 @@ pub struct CircuitCapacityChecker {
     pub acc_row_usage: RowUsage,
     pub row_usages: Vec<RowUsage>,
     pub builder_ctx: Option<(CodeDB, StateDB, Option<ZktrieState>)>,
+    /// When set, the poseidon rows of mpt updates are estimated in light mode from the trie path
+    /// depths seen in the storage proofs of the traces so far, instead of a fixed ratio.
+    pub trie_model: Option<TriePathModel>,
+    /// State root after the last estimated trace, which the next trace must start from when
+    /// `trie_model` is set.
+    pub last_state_root: Option<H256>,
 }
 
 impl Default for CircuitCapacityChecker {
@@ -115,16 +122,25 @@ impl CircuitCapacityChecker {
             row_usages: Vec::new(),
             light_mode: true,
             builder_ctx: None,
+            trie_model: None,
+            last_state_root: None,
         }
     }
     pub fn reset(&mut self) {
         self.builder_ctx = None;
+        self.last_state_root = None;
+        if let Some(trie_model) = self.trie_model.as_mut() {
+            trie_model.clear();
+        }
         self.acc_row_usage = RowUsage::new();
         self.row_usages = Vec::new();
     }
     pub fn set_light_mode(&mut self, light_mode: bool) {
         self.light_mode = light_mode;
     }
+    pub fn set_trie_path_estimation(&mut self, enabled: bool) {
+        self.trie_model = enabled.then(TriePathModel::default);
+    }
     pub fn get_tx_num(&self) -> usize {
         self.row_usages.len()
     }
@@ -139,13 +155,30 @@ impl CircuitCapacityChecker {
         &mut self,
         trace: BlockTrace,
     ) -> Result<RowUsage, anyhow::Error> {
+        let storage_trace = &trace.storage_trace;
+        // the trie path model needs the proofs of each trace to extend the previous state
+        if let Some(last_state_root) = self.last_state_root.filter(|_| self.trie_model.is_some()) {
+            if last_state_root != storage_trace.root_before {
+                anyhow::bail!(
+                    "state root discontinuity: trace starts from {:?}, but the previous one ended at {:?}",
+                    storage_trace.root_before,
+                    last_state_root
+                );
+            }
+        }
+        let root_before = storage_trace.root_before;
+        let root_after = storage_trace.root_after;
+        if let Some(trie_model) = self.trie_model.as_mut() {
+            trie_model.update_from_trace(storage_trace);
+        }
+
         let (mut estimate_builder, codedb_prev) =
             if let Some((code_db, sdb, mpt_state)) = self.builder_ctx.take() {
                 // here we create a new builder for another (sealed) witness block
                 // this builder inherit the current execution state (sdb) of
                 // the previous one and do not use zktrie state,
-                // notice the prev_root in current builder may be not invalid (since the state has
-                // changed but we may not update it in light mode)
+                // in light mode the prev_root is taken from the storage trace, whose continuity
+                // with the previous trace is checked above when the trie model is set
                 let mut builder_block =
                     circuit_input_builder::Blocks::init(trace.chain_id, get_super_circuit_params());
                 builder_block.start_l1_queue_index = trace.start_l1_queue_index;
@@ -153,7 +186,7 @@ impl CircuitCapacityChecker {
                     .as_ref()
                     .map(|state| state.root())
                     .map(|root| H256(*root))
-                    .unwrap_or(trace.header.state_root)
+                    .unwrap_or(root_before)
                     .to_word();
                 // notice the trace has included all code required for builidng witness block,
                 // so we do not need to pick them from previous one, but we still keep the
@@ -181,7 +214,8 @@ impl CircuitCapacityChecker {
                 )
             };
         let witness_block = finalize_builder(&mut estimate_builder)?;
-        let mut rows = calculate_row_usage_of_witness_block(&witness_block)?;
+        let mut rows =
+            calculate_row_usage_with_trie_model(&witness_block, self.trie_model.as_ref())?;
 
         let mut code_db = codedb_prev.unwrap_or_else(CodeDB::new);
         // merge current codes with previous , and dedup bytecode row usage
@@ -205,6 +239,7 @@ impl CircuitCapacityChecker {
             estimate_builder.sdb,
             estimate_builder.mpt_init_state,
         ));
+        self.last_state_root = Some(root_after);
         Ok(self.acc_row_usage.normalize())
     }
 }
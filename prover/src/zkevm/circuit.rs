@@ -4,6 +4,7 @@ use snark_verifier_sdk::CircuitExt;
 use zkevm_circuits::{super_circuit::params::ScrollSuperCircuit, util::SubCircuit, witness};
 
 mod builder;
+pub(crate) use self::builder::calculate_row_usage_with_trie_model;
 pub use self::builder::{
     block_traces_to_witness_block, calculate_row_usage_of_witness_block, finalize_builder,
     print_chunk_stats, validite_block_traces,
@@ -1,28 +1,42 @@
-use crate::{utils::read_env_var, zkevm::SubCircuitRowUsage};
+use crate::zkevm::SubCircuitRowUsage;
 use anyhow::{bail, Result};
 use bus_mapping::circuit_input_builder::{self, CircuitInputBuilder};
 use eth_types::{
+    constants::get_chain_id_constant,
     l2_types::BlockTrace,
     state_db::{CodeDB, StateDB},
     ToWord,
 };
 use itertools::Itertools;
 use mpt_zktrie::state::ZkTrieHash;
-use std::sync::LazyLock;
 use zkevm_circuits::{
     evm_circuit::witness::Block,
     super_circuit::params::{get_super_circuit_params, ScrollSuperCircuit, MAX_TXS},
-    witness::block_convert,
+    witness::{block_convert, TriePathModel},
 };
 
-static CHAIN_ID: LazyLock<u64> = LazyLock::new(|| read_env_var("CHAIN_ID", 534352));
-
 pub fn calculate_row_usage_of_witness_block(
     witness_block: &Block,
+) -> Result<Vec<SubCircuitRowUsage>> {
+    calculate_row_usage_with_trie_model(witness_block, None)
+}
+
+/// Same as [`calculate_row_usage_of_witness_block`], but in light mode the poseidon rows of the
+/// mpt updates are estimated from the trie path depths in `trie_model` when it is given, instead
+/// of a fixed ratio of the mpt rows.
+pub(crate) fn calculate_row_usage_with_trie_model(
+    witness_block: &Block,
+    trie_model: Option<&TriePathModel>,
 ) -> Result<Vec<SubCircuitRowUsage>> {
     let mut rows = ScrollSuperCircuit::min_num_rows_block_subcircuits(witness_block);
     // Check whether we need to "estimate" poseidon sub circuit row usage
-    if witness_block.mpt_updates.smt_traces.is_empty() {
+    if let (true, Some(trie_model)) = (witness_block.mpt_updates.smt_traces.is_empty(), trie_model)
+    {
+        assert_eq!(rows[11].name, "poseidon");
+        let mpt_poseidon_rows = trie_model.estimate_poseidon_rows(&witness_block.mpt_updates);
+        rows[11].row_num_real += mpt_poseidon_rows;
+        log::debug!("calculate_row_usage_of_witness_block light mode, adding {mpt_poseidon_rows} poseidon rows estimated from trie paths");
+    } else if witness_block.mpt_updates.smt_traces.is_empty() {
         assert_eq!(rows[11].name, "poseidon");
         assert_eq!(rows[14].name, "mpt");
         // We collected real metrics from Scroll mainnet, and here is the graph
@@ -87,23 +101,22 @@ pub fn print_chunk_stats(block_traces: &[BlockTrace]) {
 
 /// check if block traces match preset parameters
 pub fn validite_block_traces(block_traces: &[BlockTrace]) -> Result<()> {
+    let expected = get_chain_id_constant();
     let chain_id = block_traces
         .iter()
         .map(|block_trace| block_trace.chain_id)
         .next()
-        .unwrap_or(*CHAIN_ID);
-    if *CHAIN_ID != chain_id {
-        bail!(
-            "CHAIN_ID env var is wrong. chain id in trace {chain_id}, CHAIN_ID {}",
-            *CHAIN_ID
-        );
+        .unwrap_or(expected);
+    if expected != chain_id {
+        bail!("CHAIN_ID is wrong. chain id in trace {chain_id}, CHAIN_ID {expected}");
     }
     Ok(())
 }
 
 pub fn dummy_witness_block() -> Result<Block> {
     log::debug!("generate dummy witness block");
-    let builder_block = circuit_input_builder::Blocks::init(*CHAIN_ID, get_super_circuit_params());
+    let builder_block =
+        circuit_input_builder::Blocks::init(get_chain_id_constant(), get_super_circuit_params());
     let mut builder: CircuitInputBuilder =
         CircuitInputBuilder::new(StateDB::new(), CodeDB::new(), &builder_block);
     builder.finalize_building()?;
@@ -1,6 +1,6 @@
 use crate::{
     common,
-    config::{LayerId, ZKEVM_DEGREES},
+    config::{LayerId, ProverConfig},
     consts::CHUNK_VK_FILENAME,
     io::try_to_read,
     proof::compare_chunk_info,
@@ -22,7 +22,12 @@ pub struct Prover {
 
 impl Prover {
     pub fn from_dirs(params_dir: &str, assets_dir: &str) -> Self {
-        let prover_impl = common::Prover::from_params_dir(params_dir, &ZKEVM_DEGREES);
+        Self::from_dirs_with_config(params_dir, assets_dir, ProverConfig::from_env())
+    }
+
+    pub fn from_dirs_with_config(params_dir: &str, assets_dir: &str, config: ProverConfig) -> Self {
+        let prover_impl = common::Prover::from_params_dir(params_dir, &config.zkevm_degrees())
+            .with_config(config.clone());
 
         let raw_vk = try_to_read(assets_dir, &CHUNK_VK_FILENAME);
         let verifier = if raw_vk.is_none() {
@@ -33,7 +38,9 @@ impl Prover {
             );
             None
         } else {
-            Some(super::verifier::Verifier::from_dirs(params_dir, assets_dir))
+            Some(super::verifier::Verifier::from_dirs_with_config(
+                params_dir, assets_dir, &config,
+            ))
         };
 
         Self {
@@ -79,7 +86,9 @@ impl Prover {
         {
             Some(proof) => Ok(proof),
             None => {
-                let witness_block = chunk_trace_to_witness_block(chunk.block_traces)?;
+                let config = self.prover_impl.config().clone();
+                let witness_block = config
+                    .scoped_block_constants(|| chunk_trace_to_witness_block(chunk.block_traces))?;
                 let row_usage = calculate_row_usage_of_witness_block(&witness_block)?;
                 log::info!("Got witness block");
 
@@ -1,6 +1,6 @@
 use crate::{
     common,
-    config::{LAYER2_CONFIG_PATH, LAYER2_DEGREE},
+    config::{LayerId, ProverConfig},
     consts::chunk_vk_filename,
     io::force_to_read,
     ChunkProof,
@@ -11,7 +11,6 @@ use halo2_proofs::{
     plonk::VerifyingKey,
     poly::kzg::commitment::ParamsKZG,
 };
-use std::env;
 
 #[derive(Debug)]
 pub struct Verifier {
@@ -31,10 +30,22 @@ impl Verifier {
     }
 
     pub fn from_dirs(params_dir: &str, assets_dir: &str) -> Self {
+        Self::from_dirs_with_config(params_dir, assets_dir, &ProverConfig::from_env())
+    }
+
+    pub fn from_dirs_with_config(
+        params_dir: &str,
+        assets_dir: &str,
+        config: &ProverConfig,
+    ) -> Self {
         let raw_vk = force_to_read(assets_dir, &chunk_vk_filename());
 
-        env::set_var("COMPRESSION_CONFIG", &*LAYER2_CONFIG_PATH);
-        common::Verifier::from_params_dir(params_dir, *LAYER2_DEGREE, &raw_vk).into()
+        let degree = config.degree(LayerId::Layer2);
+        config
+            .scoped(LayerId::Layer2, || {
+                common::Verifier::from_params_dir(params_dir, degree, &raw_vk)
+            })
+            .into()
     }
 
     pub fn verify_chunk_proof(&self, proof: ChunkProof) -> bool {
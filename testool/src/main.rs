@@ -25,6 +25,7 @@ use std::{
     time::SystemTime,
 };
 use strum_macros::EnumString;
+use zkevm_circuits::util::ChallengePhases;
 
 const REPORT_FOLDER: &str = "report";
 const CODEHASH_FILE: &str = "./codehash.txt";
@@ -88,6 +89,10 @@ struct Args {
     #[clap(long)]
     exclude_test_ids: Option<String>,
 
+    /// Configure the super circuit with a single phase and constant challenges (debug only)
+    #[clap(long)]
+    one_phase: bool,
+
     /// Verbose
     #[clap(short, long)]
     v: bool,
@@ -139,7 +144,7 @@ fn run_single_test(
     log::info!("run single test {}", &test);
     let circuits_config = CircuitsConfig {
         verbose: true,
-        super_circuit: circuits_config.super_circuit,
+        ..circuits_config
     };
     //let trace = geth_trace(test.clone())?;
     //crate::utils::print_trace(trace)?;
@@ -159,6 +164,9 @@ fn go() -> Result<()> {
     if args.circuits == Some(Circuits::sc) {
         circuits_config.super_circuit = true;
     }
+    if args.one_phase {
+        circuits_config.challenge_phases = ChallengePhases::One;
+    }
 
     if let Some(oneliner) = &args.oneliner {
         let test = StateTest::parse_oneline_spec(oneliner)?;
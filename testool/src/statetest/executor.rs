@@ -1,9 +1,16 @@
-use super::{AccountMatch, StateTest, StateTestResult};
+use super::{
+    post_state::{
+        diff_post_state, witness_post_state, PostStateDiff, PostStateFieldDiff, WitnessAccount,
+    },
+    AccountMatch, StateTest, StateTestResult,
+};
 use crate::{config::TestSuite, utils::ETH_CHAIN_ID};
-use bus_mapping::circuit_input_builder::{CircuitInputBuilder, CircuitsParams, PrecompileEcParams};
+use bus_mapping::circuit_input_builder::{
+    CircuitInputBuilder, CircuitsParams, PrecompileEcParams, N_PAIRING_PER_OP,
+};
 use eth_types::{
-    geth_types, state_db::CodeDB, Address, Bytes, GethExecTrace, ToBigEndian, ToWord, H256, U256,
-    U64,
+    block_header::set_parent_beacon_block_root, constants::BlockConstants, geth_types,
+    state_db::CodeDB, Address, Bytes, GethExecTrace, ToBigEndian, ToWord, H256, U256, U64,
 };
 use ethers_core::utils::keccak256;
 use ethers_signers::LocalWallet;
@@ -22,7 +29,7 @@ use zkevm_circuits::{
         MAX_VERTICAL_ROWS,
     },
     test_util::CircuitTestBuilder,
-    util::SubCircuit,
+    util::{ChallengePhases, OnePhase, SubCircuit},
     witness::Block,
 };
 
@@ -52,6 +59,8 @@ pub enum StateTestError {
         expected: U256,
         found: U256,
     },
+    #[error("WitnessPostStateMismatch({0})")]
+    WitnessPostStateMismatch(PostStateDiff),
     #[error("SkipTestMaxGasLimit({0})")]
     SkipTestMaxGasLimit(u64),
     #[error("SkipTestMaxSteps({0})")]
@@ -85,12 +94,22 @@ impl StateTestError {
                 | StateTestError::SkipTestDifficulty
         )
     }
+
+    /// Field level diff of the witness post-state, if this is a witness
+    /// post-state mismatch.
+    pub fn post_state_diff(&self) -> Vec<PostStateFieldDiff> {
+        match self {
+            StateTestError::WitnessPostStateMismatch(diff) => diff.0.clone(),
+            _ => Vec::new(),
+        }
+    }
 }
 
 #[derive(Default, Debug, Clone)]
 pub struct CircuitsConfig {
     pub super_circuit: bool,
     pub verbose: bool,
+    pub challenge_phases: ChallengePhases,
 }
 
 fn check_post(
@@ -159,6 +178,25 @@ fn check_post(
     Ok(())
 }
 
+/// Check the post-state committed by the witness RW table, which can disagree
+/// with the trace even if all circuits are satisfied.
+fn check_witness_post(
+    found: &BTreeMap<Address, WitnessAccount>,
+    post: &BTreeMap<Address, AccountMatch>,
+    st: &StateTest,
+) -> Result<(), StateTestError> {
+    log::trace!("check witness post");
+    let diff = diff_post_state(post, found, st.env.current_coinbase);
+    if !diff.is_empty() {
+        for field_diff in &diff.0 {
+            log::error!("witness post-state mismatch: {field_diff}");
+        }
+        return Err(StateTestError::WitnessPostStateMismatch(diff));
+    }
+    log::trace!("check witness post done");
+    Ok(())
+}
+
 fn into_traceconfig(st: StateTest) -> (String, TraceConfig, StateTestResult) {
     let tx_type = st.tx_type();
     let tx = st.build_tx();
@@ -184,6 +222,7 @@ fn into_traceconfig(st: StateTest) -> (String, TraceConfig, StateTestResult) {
                 difficulty: st.env.current_difficulty,
                 gas_limit: U256::from(st.env.current_gas_limit),
                 base_fee: st.env.current_base_fee,
+                parent_beacon_block_root: st.env.current_beacon_root,
             },
 
             transactions: vec![geth_types::Transaction {
@@ -314,7 +353,6 @@ fn trace_config_to_witness_block_l2(
         Ok(_) => 0,
     };
 
-    eth_types::constants::set_scroll_block_constants_with_trace(&block_trace);
     let mut builder =
         CircuitInputBuilder::new_from_l2_trace(circuits_params, block_trace.clone(), false)
             .expect("could not handle block tx");
@@ -393,7 +431,7 @@ fn trace_config_to_witness_block_l1(
             ..eth_types::Transaction::default()
         })
         .collect();
-    let eth_block = eth_types::Block {
+    let mut eth_block = eth_types::Block {
         author: Some(trace_config.block_constants.coinbase),
         timestamp: trace_config.block_constants.timestamp,
         number: Some(U64::from(trace_config.block_constants.number.as_u64())),
@@ -404,6 +442,9 @@ fn trace_config_to_witness_block_l1(
         parent_hash: st.env.previous_hash,
         ..eth_types::Block::default()
     };
+    if let Some(root) = trace_config.block_constants.parent_beacon_block_root {
+        set_parent_beacon_block_root(&mut eth_block, root);
+    }
 
     let wallet: LocalWallet = ethers_core::k256::ecdsa::SigningKey::from_slice(&st.secret_key)
         .unwrap()
@@ -458,6 +499,7 @@ fn get_params_for_super_circuit_test() -> CircuitsParams {
             ec_add: 50,
             ec_mul: 50,
             ec_pairing: 2,
+            ec_pairing_pairs: N_PAIRING_PER_OP,
         },
     }
 }
@@ -482,6 +524,7 @@ fn get_params_for_sub_circuit_test() -> CircuitsParams {
             ec_add: 50,
             ec_mul: 50,
             ec_pairing: 2,
+            ec_pairing_pairs: N_PAIRING_PER_OP,
         },
     }
 }
@@ -503,6 +546,20 @@ pub fn run_test(
     st: StateTest,
     suite: TestSuite,
     circuits_config: CircuitsConfig,
+) -> Result<(), StateTestError> {
+    // tests run in parallel, so the constants of the test are installed for its threads only
+    let block_constants = BlockConstants {
+        chain_id: ETH_CHAIN_ID,
+        coinbase: st.env.current_coinbase,
+        difficulty: U256::zero(),
+    };
+    block_constants.scoped(|| run_test_with_block_constants(st, suite, circuits_config))
+}
+
+fn run_test_with_block_constants(
+    st: StateTest,
+    suite: TestSuite,
+    circuits_config: CircuitsConfig,
 ) -> Result<(), StateTestError> {
     let test_id = st.id.clone();
     log::info!("{test_id}: run-test BEGIN - {circuits_config:?}");
@@ -565,6 +622,8 @@ pub fn run_test(
     };
 
     log::debug!("witness_block created");
+    // the witness block is consumed by the circuit tests below
+    let witness_post = witness_post_state(&trace_config.accounts, &witness_block);
     //builder.sdb.list_accounts();
 
     let row_usage = ScrollSuperCircuit::min_num_rows_block_subcircuits(&witness_block);
@@ -616,14 +675,13 @@ pub fn run_test(
                 "bytecode" => test_with::<BytecodeCircuit<Fr>>(&witness_block),
                 "ecc" => test_with::<EccCircuit<Fr, 9>>(&witness_block),
                 "sig" => {
-                    if !witness_block
-                        .precompile_events
-                        .get_ecrecover_events()
-                        .is_empty()
+                    let events = &witness_block.precompile_events;
+                    if !events.get_ecrecover_events().is_empty()
+                        || !events.get_p256_verify_events().is_empty()
                     {
                         test_with::<SigCircuit<Fr>>(&witness_block);
                     } else {
-                        log::warn!("no ec recover event {}, skip", st.id);
+                        log::warn!("no ec recover or p256 verify event {}, skip", st.id);
                     }
                 }
                 _ => unimplemented!(),
@@ -636,18 +694,12 @@ pub fn run_test(
         // The correct way is to dump trace files,
         // and use seperate tools to test trace files.
         #[cfg(feature = "inner-prove")]
-        {
-            eth_types::constants::set_env_coinbase(&st.env.current_coinbase);
-            prover::test::inner_prove(&test_id, &witness_block);
-        }
+        prover::test::inner_prove(&test_id, &witness_block);
         #[cfg(feature = "chunk-prove")]
-        {
-            eth_types::constants::set_env_coinbase(&st.env.current_coinbase);
-            prover::test::chunk_prove(&test_id, prover::ChunkProvingTask::from(vec![scroll_trace]));
-        }
+        prover::test::chunk_prove(&test_id, prover::ChunkProvingTask::from(vec![scroll_trace]));
 
         #[cfg(not(any(feature = "inner-prove", feature = "chunk-prove")))]
-        mock_prove(&test_id, &witness_block);
+        mock_prove(&test_id, &witness_block, circuits_config.challenge_phases);
     };
     log::debug!("balance_overflow = {balance_overflow}");
     log::debug!(
@@ -692,6 +744,9 @@ pub fn run_test(
                 }
             }
         }
+        // the witness diff covers every field, so it goes first to not be hidden by the first
+        // mismatch `check_post` returns
+        check_witness_post(&witness_post, &post, &st)?;
         check_post(&builder, &post, &st)?;
     }
     log::info!("{test_id}: run-test END");
@@ -699,14 +754,18 @@ pub fn run_test(
 }
 
 #[cfg(not(any(feature = "inner-prove", feature = "chunk-prove")))]
-fn mock_prove(test_id: &str, witness_block: &Block) {
+fn mock_prove(test_id: &str, witness_block: &Block, challenge_phases: ChallengePhases) {
     log::info!("{test_id}: mock-prove BEGIN");
     // TODO: do we need to automatically adjust this k?
     let k = 20;
     // TODO: remove this MOCK_RANDOMNESS?
     let circuit = ScrollSuperCircuit::new_from_block(witness_block);
     let instance = circuit.instance();
-    let prover = MockProver::run(k, &circuit, instance).unwrap();
+    let prover = match challenge_phases {
+        ChallengePhases::Multi => MockProver::run(k, &circuit, instance),
+        ChallengePhases::One => MockProver::run(k, &OnePhase(circuit), instance),
+    }
+    .unwrap();
     prover.assert_satisfied_par();
 
     log::info!("{test_id}: mock-prove END");
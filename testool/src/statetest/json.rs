@@ -26,6 +26,8 @@ fn default_block_base_fee() -> String {
 struct TestEnv {
     #[serde(default = "default_block_base_fee")]
     current_base_fee: String,
+    #[serde(default)]
+    current_beacon_root: Option<String>,
     current_coinbase: String,
     #[serde(default)]
     current_difficulty: String,
@@ -252,6 +254,11 @@ impl<'a> JsonStateTestBuilder<'a> {
         Ok(Env {
             current_base_fee: parse::parse_u256(&env.current_base_fee)
                 .unwrap_or_else(|_| U256::from(DEFAULT_BASE_FEE)),
+            current_beacon_root: env
+                .current_beacon_root
+                .as_deref()
+                .map(parse::parse_hash)
+                .transpose()?,
             current_coinbase: parse::parse_address(&env.current_coinbase)?,
             current_difficulty: parse::parse_u256(&env.current_difficulty).unwrap_or_default(),
             current_gas_limit: parse::parse_u64(&env.current_gas_limit)?,
@@ -443,6 +450,7 @@ mod test {
             id: "add11_d0_g0_v0".to_string(),
             env: Env {
                 current_base_fee: U256::from(DEFAULT_BASE_FEE),
+                current_beacon_root: None,
                 current_coinbase: Address::from_str("0x2adc25665018aa1fe0e6bc666dac8fc2697ff9ba")?,
                 current_difficulty: U256::from(131072u64),
                 current_gas_limit: 0xFF112233445566,
@@ -1,6 +1,7 @@
 mod executor;
 mod json;
 mod parse;
+mod post_state;
 mod results;
 pub mod spec;
 mod suite;
@@ -0,0 +1,313 @@
+use super::AccountMatch;
+use eth_types::{geth_types::Account, state_db::CodeDB, Address, ToWord, Word, H256};
+use ethers_core::utils::keccak256;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
+use zkevm_circuits::{
+    table::{AccountFieldTag, RwTableTag},
+    witness::{Block, Rw},
+};
+
+/// Account state as committed by the RW table of a witness block.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WitnessAccount {
+    pub nonce: Word,
+    pub balance: Word,
+    pub code_hash: Word,
+    pub keccak_code_hash: Word,
+    pub code_size: Word,
+    pub storage: HashMap<Word, Word>,
+}
+
+impl From<&Account> for WitnessAccount {
+    fn from(account: &Account) -> Self {
+        Self {
+            nonce: account.nonce,
+            balance: account.balance,
+            code_hash: CodeDB::hash(&account.code).to_word(),
+            keccak_code_hash: H256(keccak256(&account.code)).to_word(),
+            code_size: account.code.len().to_word(),
+            storage: account.storage.clone(),
+        }
+    }
+}
+
+/// Rebuild the final account and storage state from the pre-state and the
+/// `Account`/`AccountStorage` rows of the witness `RwMap`, replayed in
+/// `rw_counter` order.
+pub fn witness_post_state(
+    pre: &BTreeMap<Address, Account>,
+    block: &Block,
+) -> BTreeMap<Address, WitnessAccount> {
+    let mut state: BTreeMap<Address, WitnessAccount> = pre
+        .iter()
+        .map(|(address, account)| (*address, WitnessAccount::from(account)))
+        .collect();
+
+    let mut rws: Vec<&Rw> = [RwTableTag::Account, RwTableTag::AccountStorage]
+        .iter()
+        .filter_map(|tag| block.rws.0.get(tag))
+        .flatten()
+        .collect();
+    rws.sort_by_key(|rw| rw.rw_counter());
+
+    for rw in rws {
+        match *rw {
+            Rw::Account {
+                account_address,
+                field_tag,
+                value,
+                ..
+            } => {
+                let account = state.entry(account_address).or_default();
+                match field_tag {
+                    AccountFieldTag::Nonce => account.nonce = value,
+                    AccountFieldTag::Balance => account.balance = value,
+                    AccountFieldTag::CodeHash => account.code_hash = value,
+                    AccountFieldTag::KeccakCodeHash => account.keccak_code_hash = value,
+                    AccountFieldTag::CodeSize => account.code_size = value,
+                    AccountFieldTag::NonExisting => {}
+                }
+            }
+            Rw::AccountStorage {
+                account_address,
+                storage_key,
+                value,
+                ..
+            } => {
+                state
+                    .entry(account_address)
+                    .or_default()
+                    .storage
+                    .insert(storage_key, value);
+            }
+            _ => unreachable!("only account and storage rws are replayed"),
+        }
+    }
+    state
+}
+
+/// Account field compared in a post-state diff.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PostStateField {
+    Nonce,
+    Balance,
+    CodeHash,
+    KeccakCodeHash,
+    CodeSize,
+    Storage(Word),
+}
+
+impl fmt::Display for PostStateField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Nonce => write!(f, "nonce"),
+            Self::Balance => write!(f, "balance"),
+            Self::CodeHash => write!(f, "code_hash"),
+            Self::KeccakCodeHash => write!(f, "keccak_code_hash"),
+            Self::CodeSize => write!(f, "code_size"),
+            Self::Storage(slot) => write!(f, "storage[{slot:#x}]"),
+        }
+    }
+}
+
+/// A single field where the witness post-state differs from the expected one.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PostStateFieldDiff {
+    pub address: Address,
+    pub field: PostStateField,
+    pub expected: Word,
+    pub found: Word,
+}
+
+impl fmt::Display for PostStateFieldDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:?}.{} expected:{:#x} found:{:#x}",
+            self.address, self.field, self.expected, self.found
+        )
+    }
+}
+
+/// All field mismatches between the witness post-state and the expected one.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PostStateDiff(pub Vec<PostStateFieldDiff>);
+
+impl PostStateDiff {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Display for PostStateDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (idx, diff) in self.0.iter().enumerate() {
+            if idx > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{diff}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Diff the witness post-state against the expected post-state, field by
+/// field. Only fields set in the expectation are compared. The balance of
+/// `skip_balance_of` is ignored, matching the coinbase special case of the
+/// trace based check.
+pub fn diff_post_state(
+    expected: &BTreeMap<Address, AccountMatch>,
+    found: &BTreeMap<Address, WitnessAccount>,
+    skip_balance_of: Address,
+) -> PostStateDiff {
+    let empty = WitnessAccount::default();
+    let mut diffs = Vec::new();
+    for (address, expected) in expected {
+        let actual = found.get(address).unwrap_or(&empty);
+        let mut push = |field, expected: Word, found: Word| {
+            if expected != found {
+                diffs.push(PostStateFieldDiff {
+                    address: *address,
+                    field,
+                    expected,
+                    found,
+                });
+            }
+        };
+
+        if let Some(nonce) = expected.nonce {
+            push(PostStateField::Nonce, nonce, actual.nonce);
+        }
+        if let Some(balance) = expected.balance {
+            if *address != skip_balance_of {
+                push(PostStateField::Balance, balance, actual.balance);
+            }
+        }
+        if let Some(code) = &expected.code {
+            push(
+                PostStateField::CodeSize,
+                code.len().to_word(),
+                actual.code_size,
+            );
+            // A non-existing account has zero hashes in the RW table, which is
+            // equivalent to an empty code.
+            let is_empty_match = |hash: Word| code.is_empty() && hash.is_zero();
+            if !is_empty_match(actual.code_hash) {
+                push(
+                    PostStateField::CodeHash,
+                    CodeDB::hash(code).to_word(),
+                    actual.code_hash,
+                );
+            }
+            if !is_empty_match(actual.keccak_code_hash) {
+                push(
+                    PostStateField::KeccakCodeHash,
+                    H256(keccak256(code)).to_word(),
+                    actual.keccak_code_hash,
+                );
+            }
+        }
+        for (slot, expected_value) in expected.storage.iter().sorted() {
+            let actual_value = actual.storage.get(slot).cloned().unwrap_or_default();
+            push(
+                PostStateField::Storage(*slot),
+                *expected_value,
+                actual_value,
+            );
+        }
+    }
+    PostStateDiff(diffs)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use eth_types::{Bytes, U256};
+
+    fn account(balance: u64, code: &[u8], storage: &[(u64, u64)]) -> Account {
+        Account {
+            address: Address::zero(),
+            nonce: U256::one(),
+            balance: balance.into(),
+            code: Bytes::from(code.to_vec()),
+            storage: storage
+                .iter()
+                .map(|(k, v)| (U256::from(*k), U256::from(*v)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn diff_post_state_reports_each_field() {
+        let address = Address::repeat_byte(0xaa);
+        let coinbase = Address::repeat_byte(0xcc);
+        let found = BTreeMap::from([
+            (
+                address,
+                WitnessAccount::from(&account(10, &[0x00], &[(1, 2)])),
+            ),
+            (coinbase, WitnessAccount::from(&account(5, &[], &[]))),
+        ]);
+        let expected = BTreeMap::from([
+            (
+                address,
+                AccountMatch {
+                    address,
+                    balance: Some(11.into()),
+                    code: Some(Bytes::from(vec![0x00])),
+                    nonce: Some(U256::one()),
+                    storage: HashMap::from([(1.into(), 3.into()), (2.into(), 0.into())]),
+                },
+            ),
+            (
+                coinbase,
+                AccountMatch {
+                    address: coinbase,
+                    balance: Some(6.into()),
+                    ..Default::default()
+                },
+            ),
+        ]);
+
+        let diff = diff_post_state(&expected, &found, coinbase);
+        assert_eq!(
+            diff.0,
+            vec![
+                PostStateFieldDiff {
+                    address,
+                    field: PostStateField::Balance,
+                    expected: 11.into(),
+                    found: 10.into(),
+                },
+                PostStateFieldDiff {
+                    address,
+                    field: PostStateField::Storage(1.into()),
+                    expected: 3.into(),
+                    found: 2.into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_post_state_accepts_non_existing_account() {
+        let address = Address::repeat_byte(0xaa);
+        let expected = BTreeMap::from([(
+            address,
+            AccountMatch {
+                address,
+                balance: Some(0.into()),
+                code: Some(Bytes::default()),
+                nonce: Some(0.into()),
+                storage: HashMap::new(),
+            },
+        )]);
+
+        assert!(diff_post_state(&expected, &BTreeMap::new(), Address::zero()).is_empty());
+    }
+}
@@ -1,5 +1,7 @@
+use super::post_state::PostStateFieldDiff;
 use anyhow::Result;
 use handlebars::Handlebars;
+use itertools::Itertools;
 use prettytable::{Row, Table};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -34,6 +36,21 @@ pub struct ResultInfo {
     pub level: ResultLevel,
     pub details: String,
     pub path: String,
+    /// Witness post-state diff, only kept for the current run (not cached).
+    #[serde(default)]
+    pub post_state_diff: Vec<PostStateFieldDiff>,
+}
+
+impl ResultInfo {
+    /// Whether two runs of a test have the same result. `post_state_diff` is not
+    /// persisted in the cache file, so it is left out when comparing against results
+    /// of a previous run.
+    pub fn same_result(&self, other: &Self) -> bool {
+        self.test_id == other.test_id
+            && self.level == other.level
+            && self.details == other.details
+            && self.path == other.path
+    }
 }
 
 impl ResultLevel {
@@ -149,22 +166,52 @@ impl Report {
                 println!("- {:?} {}", info.level, test_id);
             }
         }
+        let post_state_diffs = self.post_state_diffs();
+        if post_state_diffs.len() > 1 {
+            post_state_diffs.print_tty(false)?;
+        }
         log::info!(
             "success rate: {:.1}%",
             100f32 * num_succ / (num_succ + num_fail)
         );
         Ok(())
     }
+    /// Table of field level witness post-state mismatches, one row per field.
+    fn post_state_diffs(&self) -> Table {
+        let mut table = Table::new();
+        table.add_row(row![
+            "Witness post-state",
+            "Address",
+            "Field",
+            "Expected",
+            "Found"
+        ]);
+        for (test_id, info) in self.tests.iter().sorted_by_key(|(id, _)| *id) {
+            for diff in &info.post_state_diff {
+                table.add_row(row![
+                    test_id,
+                    format!("{:?}", diff.address),
+                    diff.field,
+                    format!("{:#x}", diff.expected),
+                    format!("{:#x}", diff.found)
+                ]);
+            }
+        }
+        table
+    }
+
     pub fn gen_html(&self, githash: String) -> Result<String> {
         let template = include_str!("report.handlebars");
         let reg = Handlebars::new();
         let mut by_folder = Vec::new();
         let mut by_result = Vec::new();
         let mut diffs = Vec::new();
+        let mut post_state_diffs = Vec::new();
 
         self.by_folder.print_html(&mut by_folder)?;
         self.by_result.print_html(&mut by_result)?;
         self.diffs.gen_info().1.print_html(&mut diffs)?;
+        self.post_state_diffs().print_html(&mut post_state_diffs)?;
 
         // strip_prefix `tests/` for rendering purpose. It helps to generate hyperlink
         let leading_tests_path = "tests/";
@@ -205,6 +252,7 @@ impl Report {
                 "by_folder": String::from_utf8(by_folder)?,
                 "by_result" : String::from_utf8(by_result)? ,
                 "diffs" : String::from_utf8(diffs)?,
+                "post_state_diffs" : String::from_utf8(post_state_diffs)?,
                 "all_results" : tests_for_render,
                 "githash": githash,
         });
@@ -249,6 +297,7 @@ impl Results {
                     level,
                     details,
                     path,
+                    post_state_diff: Vec::new(),
                 },
             );
         }
@@ -304,7 +353,7 @@ impl Results {
 
             if let Some(prev_results) = &prev_results {
                 if let Some(prev_info) = prev_results.tests.get(id) {
-                    if info != prev_info {
+                    if !info.same_result(prev_info) {
                         diffs.tests.push(DiffEntry {
                             id: id.to_string(),
                             prev: Some(prev_info.clone()),
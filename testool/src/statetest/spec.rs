@@ -22,6 +22,7 @@ pub const DEFAULT_BASE_FEE: u32 = 10;
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub struct Env {
     pub current_base_fee: U256,
+    pub current_beacon_root: Option<H256>,
     pub current_coinbase: Address,
     pub current_difficulty: U256,
     pub current_gas_limit: u64,
@@ -122,6 +123,9 @@ impl std::fmt::Display for StateTest {
         table.add_row(row!["number", format!("{}", self.env.current_number)]);
         table.add_row(row!["timestamp", format!("{}", self.env.current_timestamp)]);
         table.add_row(row!["prev_hash", format!("{:?}", self.env.previous_hash)]);
+        if let Some(root) = self.env.current_beacon_root {
+            table.add_row(row!["beacon_root", format!("{root:?}")]);
+        }
         table.add_row(row!["sk", hex::encode(&self.secret_key)]);
         table.add_row(row!["from", format!("{:?}", self.from)]);
         table.add_row(row!["to", format!("{:?}", self.to)]);
@@ -289,6 +293,7 @@ impl StateTest {
             id: String::default(),
             env: Env {
                 current_base_fee: U256::from(DEFAULT_BASE_FEE),
+                current_beacon_root: None,
                 current_coinbase: Address::default(),
                 current_difficulty: U256::default(),
                 current_gas_limit: 16000000,
@@ -107,6 +107,7 @@ pub fn run_statetests_suite(
                     level: ResultLevel::Ignored,
                     details: "Ignored in config file".to_string(),
                     path,
+                    post_state_diff: Vec::new(),
                 })
                 .unwrap();
             return;
@@ -153,6 +154,7 @@ pub fn run_statetests_suite(
                         level,
                         details: panic_err,
                         path,
+                        post_state_diff: Vec::new(),
                     })
                     .unwrap();
                 return;
@@ -173,6 +175,7 @@ pub fn run_statetests_suite(
                     },
                     details: err.to_string(),
                     path,
+                    post_state_diff: err.post_state_diff(),
                 })
                 .unwrap();
             return;
@@ -186,6 +189,7 @@ pub fn run_statetests_suite(
                 level: ResultLevel::Success,
                 details: String::default(),
                 path,
+                post_state_diff: Vec::new(),
             })
             .unwrap();
     };
@@ -248,6 +248,10 @@ impl<'a> YamlStateTestBuilder<'a> {
         Ok(Env {
             current_base_fee: Self::parse_u256(&yaml["currentBaseFee"])
                 .unwrap_or_else(|_| U256::from(DEFAULT_BASE_FEE)),
+            current_beacon_root: yaml["currentBeaconRoot"]
+                .as_str()
+                .map(parse::parse_hash)
+                .transpose()?,
             current_coinbase: Self::parse_address(&yaml["currentCoinbase"], None)?,
             current_difficulty: Self::parse_u256(&yaml["currentDifficulty"]).unwrap_or_default(),
             current_gas_limit: Self::parse_u64(&yaml["currentGasLimit"])?,
@@ -758,6 +762,7 @@ arith:
             id: "arith_d1(data1)_g1_v1".into(),
             env: Env {
                 current_base_fee: U256::from(DEFAULT_BASE_FEE),
+                current_beacon_root: None,
                 current_coinbase: address!("0x2adc25665018aa1fe0e6bc666dac8fc2697ff9ba"),
                 current_difficulty: U256::from(0x20000u64),
                 current_number: 1,
@@ -822,6 +827,22 @@ arith:
         Ok(())
     }
 
+    #[test]
+    fn beacon_root() -> Result<()> {
+        let root = "beac02beac02beac02beac02beac02beac02beac02beac02beac02beac02beac";
+        let yaml = Template::default().to_string().replace(
+            "    previousHash:",
+            &format!("    currentBeaconRoot: {root}\n    previousHash:"),
+        );
+        let tc = YamlStateTestBuilder::new(&Compiler::default()).load_yaml("", &yaml)?;
+
+        assert_eq!(
+            tc[0].env.current_beacon_root,
+            Some(H256::from_slice(&hex::decode(root)?))
+        );
+        Ok(())
+    }
+
     #[test]
     fn result_pass() -> Result<()> {
         let mut tc = YamlStateTestBuilder::new(&Compiler::default())
@@ -5,10 +5,16 @@ use crate::{
         not, or, rlc, select,
     },
     table::{BytecodeFieldTag, BytecodeTable, KeccakTable, LookupTable},
-    util::{get_push_size, Challenges, Expr, Field, SubCircuit, SubCircuitConfig},
+    util::{
+        advice_column_in_phase2, get_push_size, Challenges, Expr, Field, SubCircuit,
+        SubCircuitConfig,
+    },
     witness,
 };
-use eth_types::{state_db::EMPTY_CODE_HASH_LE, ToLittleEndian, ToWord, POSEIDON_CODE_HASH_EMPTY};
+use eth_types::{
+    state_db::EMPTY_CODE_HASH_LE, utils::hash_code_keccak, ToLittleEndian, ToWord,
+    KECCAK_CODE_HASH_EMPTY, POSEIDON_CODE_HASH_EMPTY,
+};
 use gadgets::is_zero::{IsZeroChip, IsZeroConfig, IsZeroInstruction};
 use gadgets::ToScalar;
 use halo2_proofs::{
@@ -27,11 +33,6 @@ use super::{
 #[cfg(feature = "scroll")]
 pub mod to_poseidon_hash;
 
-#[cfg(feature = "onephase")]
-use halo2_proofs::plonk::FirstPhase as SecondPhase;
-#[cfg(not(feature = "onephase"))]
-use halo2_proofs::plonk::SecondPhase;
-
 #[cfg(feature = "poseidon-codehash")]
 use super::circuit::to_poseidon_hash::{ToHashBlockCircuitConfig, HASHBLOCK_BYTES_IN_FIELD};
 
@@ -90,8 +91,8 @@ impl<F: Field> SubCircuitConfig<F> for BytecodeCircuitConfig<F> {
         let q_last = meta.fixed_column();
         let value = bytecode_table.value;
         let push_data_left = meta.advice_column();
-        let push_acc = meta.advice_column_in(SecondPhase);
-        let value_rlc = meta.advice_column_in(SecondPhase);
+        let push_acc = advice_column_in_phase2(meta, challenges.phases());
+        let value_rlc = advice_column_in_phase2(meta, challenges.phases());
         let length = meta.advice_column();
         let push_data_size = meta.advice_column();
         let push_data_left_inv = meta.advice_column();
@@ -266,6 +267,7 @@ impl<F: Field> SubCircuitConfig<F> for BytecodeCircuitConfig<F> {
         // When is_header_to_header or q_last ->
         // assert cur.length == 0
         // assert cur.hash == EMPTY_HASH
+        // assert cur.keccak_code_hash == EMPTY_KECCAK_HASH
         meta.create_gate("Header to header row", |meta| {
             let mut cb = BaseConstraintBuilder::default();
 
@@ -289,6 +291,18 @@ impl<F: Field> SubCircuitConfig<F> for BytecodeCircuitConfig<F> {
                 empty_hash,
             );
 
+            cb.require_equal(
+                "assert cur.keccak_code_hash == EMPTY_KECCAK_HASH",
+                meta.query_advice(bytecode_table.keccak_code_hash, Rotation::cur()),
+                rlc::expr(
+                    &KECCAK_CODE_HASH_EMPTY
+                        .to_word()
+                        .to_le_bytes()
+                        .map(|v| Expression::Constant(F::from(v as u64))),
+                    challenges.evm_word(),
+                ),
+            );
+
             cb.gate(and::expr(vec![
                 meta.query_fixed(q_enable, Rotation::cur()),
                 or::expr(vec![
@@ -303,6 +317,7 @@ impl<F: Field> SubCircuitConfig<F> for BytecodeCircuitConfig<F> {
         // assert next.index == 0
         // assert next.is_code == 1
         // assert next.hash == cur.hash
+        // assert next.keccak_code_hash == cur.keccak_code_hash
         // assert next.value_rlc == next.value
         meta.create_gate("Header to byte row", |meta| {
             let mut cb = BaseConstraintBuilder::default();
@@ -330,6 +345,12 @@ impl<F: Field> SubCircuitConfig<F> for BytecodeCircuitConfig<F> {
                 meta.query_advice(bytecode_table.code_hash, Rotation::cur()),
             );
 
+            cb.require_equal(
+                "next.keccak_code_hash == cur.keccak_code_hash",
+                meta.query_advice(bytecode_table.keccak_code_hash, Rotation::next()),
+                meta.query_advice(bytecode_table.keccak_code_hash, Rotation::cur()),
+            );
+
             cb.require_equal(
                 "next.value_rlc == next.value",
                 meta.query_advice(value_rlc, Rotation::next()),
@@ -347,6 +368,7 @@ impl<F: Field> SubCircuitConfig<F> for BytecodeCircuitConfig<F> {
         // assert next.length == cur.length
         // assert next.index == cur.index + 1
         // assert next.hash == cur.hash
+        // assert next.keccak_code_hash == cur.keccak_code_hash
         // assert next.value_rlc == cur.value_rlc * randomness + next.value
         // if cur.is_code:
         //     assert next.push_data_left == cur.push_data_size
@@ -373,6 +395,12 @@ impl<F: Field> SubCircuitConfig<F> for BytecodeCircuitConfig<F> {
                 meta.query_advice(bytecode_table.code_hash, Rotation::cur()),
             );
 
+            cb.require_equal(
+                "next.keccak_code_hash == cur.keccak_code_hash",
+                meta.query_advice(bytecode_table.keccak_code_hash, Rotation::next()),
+                meta.query_advice(bytecode_table.keccak_code_hash, Rotation::cur()),
+            );
+
             cb.require_equal(
                 "next.value_rlc == cur.value_rlc * randomness + next.value",
                 meta.query_advice(value_rlc, Rotation::next()),
@@ -442,7 +470,7 @@ impl<F: Field> SubCircuitConfig<F> for BytecodeCircuitConfig<F> {
 
         // When is_byte_to_header ->
         // assert cur.index + 1 == cur.length
-        // assert keccak256_table_lookup(cur.hash, cur.length, cur.value_rlc)
+        // assert keccak256_table_lookup(cur.keccak_code_hash, cur.length, cur.value_rlc)
         meta.create_gate("Byte to Header row", |meta| {
             let mut cb = BaseConstraintBuilder::default();
 
@@ -462,9 +490,10 @@ impl<F: Field> SubCircuitConfig<F> for BytecodeCircuitConfig<F> {
                 is_byte_to_header(meta),
             ]))
         });
-        #[cfg(not(feature = "poseidon-codehash"))]
+        // The keccak code hash is proven for every bytecode, also when the code hash is the
+        // poseidon hash, which is proven by the poseidon extension of this circuit.
         meta.lookup_any(
-            "keccak256_table_lookup(cur.value_rlc, cur.length, cur.hash)",
+            "keccak256_table_lookup(cur.value_rlc, cur.length, cur.keccak_code_hash)",
             |meta| {
                 let enable = and::expr(vec![
                     meta.query_fixed(q_enable, Rotation::cur()),
@@ -479,7 +508,7 @@ impl<F: Field> SubCircuitConfig<F> for BytecodeCircuitConfig<F> {
                 let mut constraints = vec![(enable.clone(), keccak_enable)];
 
                 for (circuit_column, table_column) in
-                    keccak_table.match_columns(value_rlc, length, bytecode_table.code_hash)
+                    keccak_table.match_columns(value_rlc, length, bytecode_table.keccak_code_hash)
                 {
                     constraints.push((
                         enable.clone() * meta.query_advice(circuit_column, Rotation::cur()),
@@ -491,6 +520,21 @@ impl<F: Field> SubCircuitConfig<F> for BytecodeCircuitConfig<F> {
             },
         );
 
+        // When the code hash is the keccak hash ->
+        // assert cur.hash == cur.keccak_code_hash
+        #[cfg(not(feature = "poseidon-codehash"))]
+        meta.create_gate("code hash is the keccak code hash", |meta| {
+            let mut cb = BaseConstraintBuilder::default();
+
+            cb.require_equal(
+                "cur.hash == cur.keccak_code_hash",
+                meta.query_advice(bytecode_table.code_hash, Rotation::cur()),
+                meta.query_advice(bytecode_table.keccak_code_hash, Rotation::cur()),
+            );
+
+            cb.gate(meta.query_fixed(q_enable, Rotation::cur()))
+        });
+
         BytecodeCircuitConfig {
             minimum_rows: meta.minimum_rows(),
             q_enable,
@@ -556,6 +600,7 @@ impl<F: Field> BytecodeCircuitConfig<F> {
                 rlc::value(EMPTY_CODE_HASH_LE.as_ref(), challenge)
             }
         });
+        let empty_keccak_hash = Self::keccak_code_hash_rlc(&[], challenges);
 
         let mut is_first_time = true;
         layouter.assign_region(
@@ -568,6 +613,7 @@ impl<F: Field> BytecodeCircuitConfig<F> {
                         &push_data_left_is_zero_chip,
                         &index_length_diff_is_zero_chip,
                         empty_hash,
+                        empty_keccak_hash,
                         last_row_offset,
                         last_row_offset,
                     )?;
@@ -585,6 +631,7 @@ impl<F: Field> BytecodeCircuitConfig<F> {
                         &push_data_left_is_zero_chip,
                         &index_length_diff_is_zero_chip,
                         empty_hash,
+                        empty_keccak_hash,
                         &mut offset,
                         last_row_offset,
                         fail_fast,
@@ -598,6 +645,7 @@ impl<F: Field> BytecodeCircuitConfig<F> {
                         &push_data_left_is_zero_chip,
                         &index_length_diff_is_zero_chip,
                         empty_hash,
+                        empty_keccak_hash,
                         idx,
                         last_row_offset,
                     )?;
@@ -619,6 +667,7 @@ impl<F: Field> BytecodeCircuitConfig<F> {
         // parameter.  This is used to explicitly set intermediate witness values for
         // negative tests.
         let mut value_rlc = challenges.keccak_input().map(|_| F::zero());
+        let keccak_code_hash = Self::keccak_code_hash_rlc(&overwrite.bytes, challenges);
         for (offset, row) in overwrite.rows.iter().enumerate() {
             for (name, column, value) in [
                 ("tag", self.bytecode_table.tag, row.tag),
@@ -649,6 +698,11 @@ impl<F: Field> BytecodeCircuitConfig<F> {
                 .map(|challenge| rlc::value(&row.code_hash.to_le_bytes(), challenge));
             for (name, column, value) in [
                 ("code_hash", self.bytecode_table.code_hash, code_hash),
+                (
+                    "keccak_code_hash",
+                    self.bytecode_table.keccak_code_hash,
+                    keccak_code_hash,
+                ),
                 ("value_rlc", self.value_rlc, value_rlc),
             ] {
                 region.assign_advice(
@@ -671,6 +725,7 @@ impl<F: Field> BytecodeCircuitConfig<F> {
         push_data_left_is_zero_chip: &IsZeroChip<F>,
         index_length_diff_is_zero_chip: &IsZeroChip<F>,
         empty_hash: Value<F>,
+        empty_keccak_hash: Value<F>,
         offset: &mut usize,
         last_row_offset: usize,
         fail_fast: bool,
@@ -693,6 +748,7 @@ impl<F: Field> BytecodeCircuitConfig<F> {
                 rlc::value(&bytecode.rows[0].code_hash.to_le_bytes(), challenge)
             }
         });
+        let keccak_code_hash = Self::keccak_code_hash_rlc(&bytecode.bytes, challenges);
 
         for (idx, row) in bytecode.rows.iter().enumerate() {
             if fail_fast && *offset > last_row_offset {
@@ -745,6 +801,7 @@ impl<F: Field> BytecodeCircuitConfig<F> {
                     true,
                     *offset == last_row_offset,
                     code_hash,
+                    keccak_code_hash,
                     row.tag,
                     row.index,
                     row.is_code,
@@ -782,6 +839,7 @@ impl<F: Field> BytecodeCircuitConfig<F> {
                     push_data_left_is_zero_chip,
                     index_length_diff_is_zero_chip,
                     empty_hash,
+                    empty_keccak_hash,
                     *offset,
                     last_row_offset,
                 )?;
@@ -791,6 +849,17 @@ impl<F: Field> BytecodeCircuitConfig<F> {
         Ok(())
     }
 
+    /// Return the RLC of the keccak hash of a bytecode.
+    pub(crate) fn keccak_code_hash_rlc(
+        bytes: &[u8],
+        challenges: &Challenges<Value<F>>,
+    ) -> Value<F> {
+        let keccak_code_hash = hash_code_keccak(bytes).to_word();
+        challenges
+            .evm_word()
+            .map(|challenge| rlc::value(&keccak_code_hash.to_le_bytes(), challenge))
+    }
+
     /// Return the RLC (LE order) of a bytecode slice, and the intermediate accumulator values.
     fn make_push_rlc(rand: Value<F>, rows: &[BytecodeRow<F>]) -> (Value<F>, Vec<Value<F>>) {
         let mut acc = Value::known(F::zero());
@@ -810,6 +879,7 @@ impl<F: Field> BytecodeCircuitConfig<F> {
         push_data_left_is_zero_chip: &IsZeroChip<F>,
         index_length_diff_is_zero_chip: &IsZeroChip<F>,
         empty_hash: Value<F>,
+        empty_keccak_hash: Value<F>,
         offset: usize,
         last_row_offset: usize,
     ) -> Result<(), Error> {
@@ -821,6 +891,7 @@ impl<F: Field> BytecodeCircuitConfig<F> {
             offset <= last_row_offset,
             offset == last_row_offset,
             empty_hash,
+            empty_keccak_hash,
             F::from(BytecodeFieldTag::Header as u64),
             F::zero(),
             F::zero(),
@@ -844,6 +915,7 @@ impl<F: Field> BytecodeCircuitConfig<F> {
         enable: bool,
         last: bool,
         code_hash: Value<F>,
+        keccak_code_hash: Value<F>,
         tag: F,
         index: F,
         is_code: F,
@@ -903,6 +975,11 @@ impl<F: Field> BytecodeCircuitConfig<F> {
         }
         for (name, column, value) in [
             ("code_hash", self.bytecode_table.code_hash, code_hash),
+            (
+                "keccak_code_hash",
+                self.bytecode_table.keccak_code_hash,
+                keccak_code_hash,
+            ),
             ("push_acc", self.push_acc, push_acc),
             ("push_rlc", self.bytecode_table.push_rlc, push_rlc),
             ("value_rlc", self.value_rlc, value_rlc),
@@ -946,6 +1023,10 @@ impl<F: Field> BytecodeCircuitConfig<F> {
         region.name_column(|| "BYTECODE_push_data_size", self.push_data_size);
         region.name_column(|| "BYTECODE_push_acc", self.push_acc);
         region.name_column(|| "BYTECODE_value_rlc", self.value_rlc);
+        region.name_column(
+            || "BYTECODE_keccak_code_hash",
+            self.bytecode_table.keccak_code_hash,
+        );
         region.name_column(|| "BYTECODE_push_data_left_inv", self.push_data_left_inv);
         region.name_column(
             || "BYTECODE_index_length_diff_inv",
@@ -436,6 +436,7 @@ impl<F: Field, const BYTES_IN_FIELD: usize> ToHashBlockCircuitConfig<F, BYTES_IN
         );
 
         let empty_hash = Value::known(POSEIDON_CODE_HASH_EMPTY.to_word().to_scalar().unwrap());
+        let empty_keccak_hash = BytecodeCircuitConfig::<F>::keccak_code_hash_rlc(&[], challenges);
 
         layouter.assign_region(
             || "assign bytecode with poseidon hash extension",
@@ -451,6 +452,7 @@ impl<F: Field, const BYTES_IN_FIELD: usize> ToHashBlockCircuitConfig<F, BYTES_IN
                         &push_data_left_is_zero_chip,
                         &index_length_diff_is_zero_chip,
                         empty_hash,
+                        empty_keccak_hash,
                         &mut offset,
                         last_row_offset,
                         fail_fast,
@@ -480,6 +482,7 @@ impl<F: Field, const BYTES_IN_FIELD: usize> ToHashBlockCircuitConfig<F, BYTES_IN
                         &push_data_left_is_zero_chip,
                         &index_length_diff_is_zero_chip,
                         empty_hash,
+                        empty_keccak_hash,
                         idx,
                         last_row_offset,
                     )?;
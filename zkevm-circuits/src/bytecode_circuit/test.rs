@@ -203,6 +203,21 @@ fn bytecode_invalid_hash_data() {
     // witness.
 }
 
+/// Test a keccak code hash that doesn't match the bytecode
+#[test]
+fn bytecode_invalid_keccak_code_hash() {
+    let k = 9;
+    let bytecode = vec![8u8, 2, 3, 8, 9, 7, 128];
+    let unrolled = unroll(bytecode);
+    test_bytecode_circuit_unrolled::<Fr>(k, vec![unrolled.clone()], true);
+    // The keccak code hash is computed from the raw bytes, which no longer match the rows
+    {
+        let mut invalid = unrolled;
+        invalid.bytes[0] = 9;
+        test_bytecode_circuit_unrolled::<Fr>(k, vec![invalid], false);
+    }
+}
+
 /// Test invalid index
 #[test]
 fn bytecode_invalid_index() {
@@ -11,7 +11,7 @@ mod test;
 #[cfg(any(feature = "test", test, feature = "test-circuits"))]
 pub use dev::CopyCircuit as TestCopyCircuit;
 
-use crate::util::Field;
+use crate::util::{advice_column_in_phase2, Field};
 use array_init::array_init;
 use bus_mapping::circuit_input_builder::{CopyDataType, CopyEvent};
 use eth_types::Word;
@@ -30,11 +30,6 @@ use halo2_proofs::{
 use itertools::Itertools;
 use std::{collections::BTreeMap, marker::PhantomData};
 
-#[cfg(feature = "onephase")]
-use halo2_proofs::plonk::FirstPhase as SecondPhase;
-#[cfg(not(feature = "onephase"))]
-use halo2_proofs::plonk::SecondPhase;
-
 use crate::{
     evm_circuit::util::constraint_builder::BaseConstraintBuilder,
     table::{
@@ -169,9 +164,9 @@ impl<F: Field> SubCircuitConfig<F> for CopyCircuitConfig<F> {
         let value_prev = meta.advice_column();
 
         // RLC accumulators in the second phase.
-        let value_word_rlc = meta.advice_column_in(SecondPhase);
-        let value_word_rlc_prev = meta.advice_column_in(SecondPhase);
-        let value_acc = meta.advice_column_in(SecondPhase);
+        let value_word_rlc = advice_column_in_phase2(meta, challenges.phases());
+        let value_word_rlc_prev = advice_column_in_phase2(meta, challenges.phases());
+        let value_acc = advice_column_in_phase2(meta, challenges.phases());
 
         let [is_pad, is_tx_calldata, is_bytecode, is_memory, is_memory_copy, is_tx_log, is_access_list_address, is_access_list_storage_key] =
             array_init(|_| meta.advice_column());
@@ -200,7 +195,7 @@ impl<F: Field> SubCircuitConfig<F> for CopyCircuitConfig<F> {
             |meta| meta.query_selector(q_step) * not::expr(meta.query_advice(is_last, CURRENT)),
             |meta| meta.query_advice(id, CURRENT),
             |meta| meta.query_advice(id, NEXT_ROW),
-            |meta| meta.advice_column_in(SecondPhase),
+            |meta| advice_column_in_phase2(meta, challenges.phases()),
         );
 
         let is_src_end = IsEqualChip::configure(
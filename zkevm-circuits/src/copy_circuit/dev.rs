@@ -3,15 +3,15 @@ pub use super::CopyCircuit;
 use crate::{
     copy_circuit::{CopyCircuitConfig, CopyCircuitConfigArgs},
     table::{BytecodeTable, CopyTable, RwTable, TxTable},
-    util::{Challenges, Field, SubCircuit, SubCircuitConfig},
+    util::{ChallengePhases, Challenges, Field, PhasedCircuit, SubCircuit, SubCircuitConfig},
 };
 use halo2_proofs::{
     circuit::{Layouter, SimpleFloorPlanner},
-    plonk::{Challenge, Circuit, ConstraintSystem, Error},
+    plonk::{Circuit, ConstraintSystem, Error},
 };
 
 impl<F: Field> Circuit<F> for CopyCircuit<F> {
-    type Config = (CopyCircuitConfig<F>, Challenges<Challenge>);
+    type Config = (CopyCircuitConfig<F>, Challenges);
     type FloorPlanner = SimpleFloorPlanner;
     #[cfg(feature = "circuit-params")]
     type Params = ();
@@ -21,28 +21,7 @@ impl<F: Field> Circuit<F> for CopyCircuit<F> {
     }
 
     fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
-        let tx_table = TxTable::construct(meta);
-        let rw_table = RwTable::construct(meta);
-        let bytecode_table = BytecodeTable::construct(meta);
-        let q_enable = meta.fixed_column();
-        let copy_table = CopyTable::construct(meta, q_enable);
-        let challenges = Challenges::construct(meta);
-        let challenge_exprs = challenges.exprs(meta);
-
-        (
-            CopyCircuitConfig::new(
-                meta,
-                CopyCircuitConfigArgs {
-                    tx_table,
-                    rw_table,
-                    bytecode_table,
-                    copy_table,
-                    q_enable,
-                    challenges: challenge_exprs,
-                },
-            ),
-            challenges,
-        )
+        Self::configure_with_phases(meta, ChallengePhases::Multi)
     }
 
     fn synthesize(
@@ -76,3 +55,33 @@ impl<F: Field> Circuit<F> for CopyCircuit<F> {
         self.synthesize_sub(&config.0, &challenge_values, &mut layouter)
     }
 }
+
+impl<F: Field> PhasedCircuit<F> for CopyCircuit<F> {
+    fn configure_with_phases(
+        meta: &mut ConstraintSystem<F>,
+        phases: ChallengePhases,
+    ) -> Self::Config {
+        let tx_table = TxTable::construct(meta, phases);
+        let rw_table = RwTable::construct(meta, phases);
+        let bytecode_table = BytecodeTable::construct(meta, phases);
+        let q_enable = meta.fixed_column();
+        let copy_table = CopyTable::construct(meta, q_enable, phases);
+        let challenges = Challenges::construct_with_phases(meta, phases);
+        let challenge_exprs = challenges.exprs(meta);
+
+        (
+            CopyCircuitConfig::new(
+                meta,
+                CopyCircuitConfigArgs {
+                    tx_table,
+                    rw_table,
+                    bytecode_table,
+                    copy_table,
+                    q_enable,
+                    challenges: challenge_exprs,
+                },
+            ),
+            challenges,
+        )
+    }
+}
@@ -3,7 +3,7 @@
 use crate::{
     copy_circuit::*,
     evm_circuit::{test::rand_bytes, witness::block_convert},
-    util::unusable_rows,
+    util::{unusable_rows, OnePhase},
     witness::Block,
 };
 use bus_mapping::{
@@ -376,6 +376,15 @@ fn copy_circuit_valid_calldatacopy() {
     assert_eq!(test_copy_circuit_from_block(block), Ok(()));
 }
 
+#[test]
+fn copy_circuit_valid_calldatacopy_one_phase() {
+    let builder = gen_calldatacopy_data();
+    let block = block_convert(&builder.block, &builder.code_db).unwrap();
+    let circuit = OnePhase(CopyCircuit::<Fr>::new_from_block(&block));
+    let prover = MockProver::<Fr>::run(K, &circuit, vec![]).unwrap();
+    assert_eq!(prover.verify_par(), Ok(()));
+}
+
 #[test]
 fn copy_circuit_valid_codecopy() {
     let builder = gen_codecopy_data();
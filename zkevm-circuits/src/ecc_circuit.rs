@@ -5,7 +5,7 @@ use std::{iter, marker::PhantomData};
 
 use crate::util::Field;
 use bus_mapping::{
-    circuit_input_builder::{EcAddOp, EcMulOp, EcPairingOp, N_BYTES_PER_PAIR, N_PAIRING_PER_OP},
+    circuit_input_builder::{EcAddOp, EcMulOp, EcPairingOp, EcPairingPair, N_BYTES_PER_PAIR},
     precompile::PrecompileCalls,
 };
 use eth_types::{ToLittleEndian, U256};
@@ -42,7 +42,7 @@ use crate::{
     evm_circuit::{param::N_BYTES_WORD, EvmCircuit},
     keccak_circuit::KeccakCircuit,
     table::{EccTable, LookupTable},
-    util::{Challenges, SubCircuit, SubCircuitConfig},
+    util::{ChallengePhases, Challenges, SubCircuit, SubCircuitConfig},
     witness::Block,
 };
 
@@ -83,6 +83,8 @@ pub struct EccCircuitConfig<F: Field> {
     num_limbs: usize,
     /// Number of bits per limb.
     limb_bits: usize,
+    /// Whether the field config has a second phase.
+    challenge_phases: ChallengePhases,
 
     _marker: PhantomData<F>,
 }
@@ -94,15 +96,16 @@ impl<F: Field> SubCircuitConfig<F> for EccCircuitConfig<F> {
         meta: &mut ConstraintSystem<F>,
         Self::ConfigArgs {
             ecc_table,
-            challenges: _,
+            challenges,
         }: Self::ConfigArgs,
     ) -> Self {
         let num_limbs = 3;
         let limb_bits = 88;
-        #[cfg(feature = "onephase")]
-        let num_advice = [35];
-        #[cfg(not(feature = "onephase"))]
-        let num_advice = [35, 1];
+        let challenge_phases = challenges.phases();
+        let num_advice = match challenge_phases {
+            ChallengePhases::One => vec![35],
+            ChallengePhases::Multi => vec![35, 1],
+        };
 
         let fp_config = FpConfig::configure(
             meta,
@@ -127,6 +130,7 @@ impl<F: Field> SubCircuitConfig<F> for EccCircuitConfig<F> {
             ecc_table,
             num_limbs,
             limb_bits,
+            challenge_phases,
             _marker: PhantomData,
         }
     }
@@ -141,6 +145,11 @@ impl<F: Field> SubCircuitConfig<F> for EccCircuitConfig<F> {
 /// We follow a strategy to pre-allocate maximum number of cells for each of the above ECC
 /// operations, which means a witness that exceeds the pre-allocated number of cells for any of the
 /// operations will be invalid.
+///
+/// Every pairing operation is padded to `max_pairing_pairs` pairs and does its own final
+/// exponentiation. Sharing one final exponentiation between several operations would require
+/// raising each of their Miller loop outputs to a random power, which we can't do as the
+/// pairings are computed before the challenges are known.
 #[derive(Clone, Debug, Default)]
 pub struct EccCircuit<F: Field, const XI_0: i64> {
     /// Maximum number of EcAdd operations supported in one instance of the ECC Circuit.
@@ -150,6 +159,8 @@ pub struct EccCircuit<F: Field, const XI_0: i64> {
     pub max_mul_ops: usize,
     /// Maximum number of pairing operations supported in one instance of the ECC Circuit.
     pub max_pairing_ops: usize,
+    /// Maximum number of (G1, G2) pairs in one pairing operation.
+    pub max_pairing_pairs: usize,
 
     /// EcAdd operations provided as witness data to the ECC circuit.
     pub add_ops: Vec<EcAddOp>,
@@ -198,12 +209,24 @@ impl<F: Field, const XI_0: i64> EccCircuit<F, XI_0> {
             );
             return Err(Error::Synthesis);
         }
+        if let Some(op) = self
+            .pairing_ops
+            .iter()
+            .find(|op| op.pairs.len() > self.max_pairing_pairs)
+        {
+            error!(
+                "pairing op with {} pairs > max pairing pairs = {}",
+                op.pairs.len(),
+                self.max_pairing_pairs,
+            );
+            return Err(Error::Synthesis);
+        }
 
         // keccak powers of randomness.
         let keccak_powers = std::iter::successors(Some(Value::known(F::one())), |coeff| {
             Some(challenges.keccak_input() * coeff)
         })
-        .take(N_PAIRING_PER_OP * N_BYTES_PER_PAIR)
+        .take(self.max_pairing_pairs * N_BYTES_PER_PAIR)
         .map(|x| QuantumCell::Witness(x))
         .collect_vec();
 
@@ -283,8 +306,7 @@ impl<F: Field, const XI_0: i64> EccCircuit<F, XI_0> {
                     decompose_ec_pairing_op
                 );
 
-                #[cfg(not(feature = "onephase"))]
-                {
+                if config.challenge_phases == ChallengePhases::Multi {
                     // finalize after first phase.
                     config.fp_config.finalize(&mut ctx);
                     ctx.next_phase();
@@ -695,7 +717,7 @@ impl<F: Field, const XI_0: i64> EccCircuit<F, XI_0> {
         let ecc2_chip = EccChip::construct(fp2_chip.clone());
 
         let decomposed_pairs = op
-            .pairs
+            .padded_pairs(self.max_pairing_pairs)
             .iter()
             .map(|pair| {
                 // process x and y co-ordinates of G1.
@@ -823,8 +845,9 @@ impl<F: Field, const XI_0: i64> EccCircuit<F, XI_0> {
         log::trace!("[ECC] EcPairing g1s and g2s Assigned:");
         log_context_cursor!(ctx);
 
-        // EVM input for EcPairing in Big-Endian representation, padded by 0 bytes so that the
-        // total number of bytes are N_PAIRING_PER_OP * N_BYTES_PER_PAIR.
+        // EVM input for EcPairing in Big-Endian representation, left-padded by 0 bytes so that the
+        // total number of bytes are max_pairing_pairs * N_BYTES_PER_PAIR. The padding does not
+        // change the RLC of the input.
         let input_cells = decomposed_pairs
             .iter()
             .flat_map(|(_, _, g1, g2)| {
@@ -870,7 +893,7 @@ impl<F: Field, const XI_0: i64> EccCircuit<F, XI_0> {
                 ecc2_chip.field_chip().load_constant(ctx, g2_gen.y),
             )
         };
-        // A pairing op satisfying the pairing check.
+        // A pairing op satisfying the pairing check, padded with (G1::identity, G2::generator).
         type TupleG1sG2s<F> = (
             Vec<EcPoint<F, CRTInteger<F>>>,
             Vec<EcPoint<F, FieldExtPoint<CRTInteger<F>>>>,
@@ -878,7 +901,12 @@ impl<F: Field, const XI_0: i64> EccCircuit<F, XI_0> {
         let (dummy_pair_check_ok_g1s, dummy_pair_check_ok_g2s): TupleG1sG2s<F> =
             EcPairingOp::dummy_pairing_check_ok()
                 .pairs
-                .iter()
+                .into_iter()
+                .chain(iter::repeat(EcPairingPair::new(
+                    G1Affine::identity(),
+                    G2Affine::generator(),
+                )))
+                .take(self.max_pairing_pairs)
                 .map(|pair| {
                     let (g1_point, g2_point) =
                         pair.as_g1_g2().expect("dummy pairing check OK pair");
@@ -1322,6 +1350,7 @@ impl<F: Field, const XI_0: i64> SubCircuit<F> for EccCircuit<F, XI_0> {
             max_add_ops: block.circuits_params.max_ec_ops.ec_add,
             max_mul_ops: block.circuits_params.max_ec_ops.ec_mul,
             max_pairing_ops: block.circuits_params.max_ec_ops.ec_pairing,
+            max_pairing_pairs: block.circuits_params.max_ec_ops.ec_pairing_pairs,
             add_ops: block.get_ec_add_ops(),
             mul_ops: block.get_ec_mul_ops(),
             pairing_ops: block.get_ec_pairing_ops(),
@@ -1362,8 +1391,13 @@ impl<F: Field, const XI_0: i64> SubCircuit<F> for EccCircuit<F, XI_0> {
 
         let ec_adds = block.get_ec_add_ops().len();
         let ec_muls = block.get_ec_mul_ops().len();
-        let ec_pairings = block.get_ec_pairing_ops().len();
+        let ec_pairing_ops = block.get_ec_pairing_ops();
+        let ec_pairings = ec_pairing_ops.len();
         let max_ec_ops = &block.circuits_params.max_ec_ops;
+        // a pairing op with more pairs than supported does not fit in the circuit at all.
+        let ec_pairing_too_large = ec_pairing_ops
+            .iter()
+            .any(|op| op.pairs.len() > max_ec_ops.ec_pairing_pairs);
         log::debug!("ecc circuit row usage: ecadd {ec_adds}/{}, ecmul {ec_muls}/{}, ecpairing {ec_pairings}/{}",
         max_ec_ops.ec_add, max_ec_ops.ec_mul, max_ec_ops.ec_pairing);
 
@@ -1373,7 +1407,11 @@ impl<F: Field, const XI_0: i64> SubCircuit<F> for EccCircuit<F, XI_0> {
         let min_row_num = [
             (row_num / max_ec_ops.ec_add) * ec_adds,
             (row_num / max_ec_ops.ec_mul) * ec_muls,
-            (row_num / max_ec_ops.ec_pairing) * ec_pairings,
+            if ec_pairing_too_large {
+                row_num + 1
+            } else {
+                (row_num / max_ec_ops.ec_pairing) * ec_pairings
+            },
         ]
         .into_iter()
         .max()
@@ -1,7 +1,7 @@
 use crate::util::Field;
 use halo2_proofs::{
     circuit::{Layouter, SimpleFloorPlanner},
-    plonk::{Challenge, Circuit, ConstraintSystem, Error},
+    plonk::{Circuit, ConstraintSystem, Error},
 };
 
 use crate::{
@@ -12,7 +12,7 @@ use crate::{
 use super::{EccCircuit, EccCircuitConfig, EccCircuitConfigArgs};
 
 impl<F: Field, const XI_0: i64> Circuit<F> for EccCircuit<F, XI_0> {
-    type Config = (EccCircuitConfig<F>, Challenges<Challenge>);
+    type Config = (EccCircuitConfig<F>, Challenges);
     type FloorPlanner = SimpleFloorPlanner;
 
     fn without_witnesses(&self) -> Self {
@@ -20,8 +20,8 @@ impl<F: Field, const XI_0: i64> Circuit<F> for EccCircuit<F, XI_0> {
     }
 
     fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
-        let ecc_table = EccTable::construct(meta);
         let challenges = Challenges::construct(meta);
+        let ecc_table = EccTable::construct(meta, challenges.phases());
         let challenge_exprs = challenges.exprs(meta);
         (
             EccCircuitConfig::new(
@@ -5,7 +5,7 @@ use std::{
 
 use crate::util::Field;
 use bus_mapping::circuit_input_builder::{
-    EcAddOp, EcMulOp, EcPairingOp, EcPairingPair, PrecompileEcParams,
+    EcAddOp, EcMulOp, EcPairingOp, EcPairingPair, PrecompileEcParams, N_PAIRING_PER_OP,
 };
 use eth_types::U256;
 use halo2_proofs::{
@@ -28,6 +28,7 @@ fn run<F: Field, const MUST_FAIL: bool>(
         max_add_ops: max_ec_ops.ec_add,
         max_mul_ops: max_ec_ops.ec_mul,
         max_pairing_ops: max_ec_ops.ec_pairing,
+        max_pairing_pairs: max_ec_ops.ec_pairing_pairs,
         add_ops,
         mul_ops,
         pairing_ops,
@@ -116,7 +117,7 @@ impl GenRand for EcPairingOp {
         let point_c = G1Affine::from(G1Affine::generator() * alpha * beta);
         let point_d = G2Affine::generator();
 
-        let mut pairs = [
+        let mut pairs = vec![
             EcPairingPair::new(point_p_negated, point_q),
             EcPairingPair::new(point_s, point_t),
             EcPairingPair::new(point_a_negated, point_b),
@@ -251,7 +252,7 @@ mod valid_invalid_cases {
                 let point_q = G2Affine::from(G2Affine::generator() * beta);
                 let point_s = G1Affine::from(G1Affine::generator() * alpha * beta);
                 let point_t = G2Affine::generator();
-                let pairs = [
+                let pairs = vec![
                     EcPairingPair::new(point_p_negated, point_q),
                     EcPairingPair::new(point_s, point_t),
                     EcPairingPair::padding_pair(),
@@ -271,7 +272,7 @@ mod valid_invalid_cases {
                 let point_p_negated = point_p.neg();
                 let point_q = G2Affine::from(G2Affine::generator() * beta);
                 let point_t = G2Affine::from(G2Affine::generator() * alpha * beta);
-                let pairs = [
+                let pairs = vec![
                     EcPairingPair::new(point_p_negated, point_q),
                     EcPairingPair {
                         g1_point: (
@@ -308,7 +309,7 @@ mod valid_invalid_cases {
                 let point_q = G2Affine::from(G2Affine::generator() * beta);
                 let point_s = G1Affine::from(G1Affine::generator() * gamma);
                 let point_t = G2Affine::generator();
-                let pairs = [
+                let pairs = vec![
                     EcPairingPair::new(point_p_negated, point_q),
                     EcPairingPair::new(point_s, point_t),
                     EcPairingPair::padding_pair(),
@@ -322,7 +323,7 @@ mod valid_invalid_cases {
             },
             // 4. invalid: not on curve G1.
             EcPairingOp {
-                pairs: [
+                pairs: vec![
                     EcPairingPair {
                         g1_point: (U256::from(3), U256::from(4)),
                         g2_point: (U256::zero(), U256::zero(), U256::zero(), U256::zero()),
@@ -340,7 +341,7 @@ mod valid_invalid_cases {
         vec![
             // 5. invalid: not on curve G2.
             EcPairingOp {
-                pairs: [
+                pairs: vec![
                     EcPairingPair {
                         g1_point: (U256::zero(), U256::zero()),
                         g2_point: (U256::from(3), U256::from(4), U256::from(5), U256::from(6)),
@@ -354,7 +355,7 @@ mod valid_invalid_cases {
             },
             // 6. valid: all zero.
             EcPairingOp {
-                pairs: [
+                pairs: vec![
                     EcPairingPair::padding_pair(),
                     EcPairingPair::padding_pair(),
                     EcPairingPair::padding_pair(),
@@ -369,7 +370,7 @@ mod valid_invalid_cases {
         vec![
             // 7. valid: [(G1::gen, G2::gen), (-G1::gen, G2::gen)]
             EcPairingOp {
-                pairs: [
+                pairs: vec![
                     EcPairingPair::new(G1Affine::generator(), G2Affine::generator()),
                     EcPairingPair::new(G1Affine::generator().neg(), G2Affine::generator()),
                     EcPairingPair::padding_pair(),
@@ -380,7 +381,7 @@ mod valid_invalid_cases {
             },
             // 8. valid: [(G1::gen, G2::gen), (-G1::gen, G2::gen); 2]
             EcPairingOp {
-                pairs: [
+                pairs: vec![
                     EcPairingPair::new(G1Affine::generator(), G2Affine::generator()),
                     EcPairingPair::new(G1Affine::generator().neg(), G2Affine::generator()),
                     EcPairingPair::new(G1Affine::generator(), G2Affine::generator()),
@@ -415,6 +416,7 @@ fn test_ecc_circuit_valid_invalid() {
             ec_add: 0,
             ec_mul: 0,
             ec_pairing: 2,
+            ec_pairing_pairs: N_PAIRING_PER_OP,
         },
         vec![],
         vec![],
@@ -427,6 +429,7 @@ fn test_ecc_circuit_valid_invalid() {
             ec_add: 0,
             ec_mul: 0,
             ec_pairing: 2,
+            ec_pairing_pairs: N_PAIRING_PER_OP,
         },
         vec![],
         vec![],
@@ -439,6 +442,7 @@ fn test_ecc_circuit_valid_invalid() {
             ec_add: 0,
             ec_mul: 0,
             ec_pairing: 2,
+            ec_pairing_pairs: N_PAIRING_PER_OP,
         },
         vec![],
         vec![],
@@ -494,6 +498,7 @@ fn test_invalid_ec_add() {
             ec_add: ec_adds.len(),
             ec_mul: 0,
             ec_pairing: 0,
+            ec_pairing_pairs: N_PAIRING_PER_OP,
         },
         ec_adds,
         vec![],
@@ -537,6 +542,7 @@ fn test_invalid_ec_add() {
             ec_add: ec_adds.len(),
             ec_mul: 0,
             ec_pairing: 0,
+            ec_pairing_pairs: N_PAIRING_PER_OP,
         },
         ec_adds,
         vec![],
@@ -560,6 +566,37 @@ fn test_ecc_circuit_positive() {
     );
 }
 
+#[test]
+fn test_ecc_circuit_pairing_many_pairs() {
+    use crate::ecc_circuit::util::LOG_TOTAL_NUM_ROWS;
+    use halo2_proofs::halo2curves::bn256::Fr;
+
+    let g1 = G1Affine::generator();
+    let g2 = G2Affine::generator();
+    let pairs = [EcPairingPair::new(g1, g2), EcPairingPair::new(g1.neg(), g2)]
+        .into_iter()
+        .cycle()
+        .take(6)
+        .collect::<Vec<_>>();
+
+    run::<Fr, false>(
+        LOG_TOTAL_NUM_ROWS,
+        PrecompileEcParams {
+            ec_add: 0,
+            ec_mul: 0,
+            ec_pairing: 1,
+            ec_pairing_pairs: 6,
+        },
+        vec![],
+        vec![],
+        vec![EcPairingOp {
+            pairs,
+            output: 1.into(),
+            ..Default::default()
+        }],
+    );
+}
+
 #[test]
 fn test_ecc_circuit_negative() {
     use crate::ecc_circuit::util::LOG_TOTAL_NUM_ROWS;
@@ -576,6 +613,40 @@ fn test_ecc_circuit_negative() {
     );
 }
 
+#[test]
+fn test_ecc_circuit_pairing_too_many_pairs_overflows() {
+    use crate::{
+        super_circuit::params::{get_super_circuit_params, MAX_VERTICAL_ROWS},
+        util::SubCircuit,
+        witness::Block,
+    };
+    use bus_mapping::circuit_input_builder::{PrecompileEvent, PrecompileEvents};
+
+    let circuits_params = get_super_circuit_params();
+    let g1 = G1Affine::generator();
+    let g2 = G2Affine::generator();
+    let pairs = [EcPairingPair::new(g1, g2), EcPairingPair::new(g1.neg(), g2)]
+        .into_iter()
+        .cycle()
+        .take(circuits_params.max_ec_ops.ec_pairing_pairs + 1)
+        .collect::<Vec<_>>();
+    let block = Block {
+        circuits_params,
+        precompile_events: PrecompileEvents {
+            events: vec![PrecompileEvent::EcPairing(Box::new(EcPairingOp {
+                pairs,
+                output: 1.into(),
+                ..Default::default()
+            }))],
+        },
+        ..Default::default()
+    };
+
+    // the capacity checker allows the ECC circuit `MAX_VERTICAL_ROWS` rows with confidence 1.0.
+    let (row_usage, _) = EccCircuit::<Fr, 9>::min_num_rows_block(&block);
+    assert!(row_usage > MAX_VERTICAL_ROWS);
+}
+
 #[test]
 fn variadic_size_check() {
     use crate::ecc_circuit::util::LOG_TOTAL_NUM_ROWS;
@@ -590,6 +661,7 @@ fn variadic_size_check() {
         max_add_ops: default_params.ec_add,
         max_mul_ops: default_params.ec_mul,
         max_pairing_ops: default_params.ec_pairing,
+        max_pairing_pairs: default_params.ec_pairing_pairs,
         add_ops: gen(&mut rng, 25, false),
         mul_ops: gen(&mut rng, 20, false),
         pairing_ops: EC_PAIRING_OPS1.clone(),
@@ -601,6 +673,7 @@ fn variadic_size_check() {
         max_add_ops: default_params.ec_add,
         max_mul_ops: default_params.ec_mul,
         max_pairing_ops: default_params.ec_pairing,
+        max_pairing_pairs: default_params.ec_pairing_pairs,
         add_ops: {
             let mut ops = gen(&mut rng, 30, false);
             ops.extend_from_slice(&EC_ADD_OPS);
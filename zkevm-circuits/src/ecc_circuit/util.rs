@@ -9,7 +9,11 @@ pub const LOG_TOTAL_NUM_ROWS: u32 = 20;
 // Roud up to nearest 100
 pub(super) const EC_ADD_CELLS: usize = 6_900; // actual: 6_851
 pub(super) const EC_MUL_CELLS: usize = 405_500; // actual: 405_476
-pub(super) const EC_PAIRING_CELLS: usize = 6_627_500; // actual: 6_627_442
+pub(super) const EC_PAIRING_CELLS: usize = 6_627_500; // actual: 6_627_442, with 4 pairs
+
+// An op of 8 pairs shares the squarings of the Miller loop and the final exponentiation, hence
+// takes less than 2 * EC_PAIRING_CELLS.
+
 pub(super) const COLUMN_NUM_LIMIT: usize = 150; // Max number of columns allowed
 
 /// Decomposed state of a G1 curve point.
@@ -6,6 +6,8 @@ use halo2_proofs::{
     plonk::*,
 };
 
+#[cfg_attr(not(feature = "coverage"), allow(dead_code))]
+pub mod coverage;
 mod execution;
 pub mod param;
 pub(crate) mod step;
@@ -29,6 +31,8 @@ use crate::{
 };
 use bus_mapping::evm::OpcodeId;
 use execution::ExecutionConfig;
+pub use execution::LocatedStep;
+pub(crate) use execution::{StepLayout, STEP_REGION_NAMES};
 use itertools::Itertools;
 use strum::IntoEnumIterator;
 use table::FixedTableTag;
@@ -128,6 +132,7 @@ impl<F: Field> SubCircuitConfig<F> for EvmCircuitConfig<F> {
             &tx_table,
             &rw_table,
             &bytecode_table,
+            &bytecode_table.columns_code_hashes(),
             &block_table,
             &copy_table,
             &keccak_table,
@@ -267,13 +272,11 @@ impl<F: Field> EvmCircuit<F> {
     }
 
     pub fn get_num_rows_required_no_padding(block: &Block) -> usize {
-        let mut num_rows = 0;
-        for transaction in &block.txs {
-            for step in &transaction.steps {
-                // EndInnerBlock included
-                num_rows += step.execution_state.get_step_height();
-            }
-        }
+        // EndInnerBlock and SystemCall included
+        let mut num_rows = block
+            .steps()
+            .map(|step| step.execution_state.get_step_height())
+            .sum::<usize>();
         // It must have one row for EndBlock and at least one unused one
         num_rows += 1;
         num_rows += ExecutionState::EndBlock.get_step_height();
@@ -428,10 +431,7 @@ pub(crate) mod cached {
 
 // Always exported because of `EXECUTION_STATE_HEIGHT_MAP`
 
-#[cfg(not(feature = "onephase"))]
 use crate::util::Challenges;
-#[cfg(feature = "onephase")]
-use crate::util::MockChallenges as Challenges;
 
 impl<F: Field> Circuit<F> for EvmCircuit<F> {
     type Config = (EvmCircuitConfig<F>, Challenges);
@@ -446,18 +446,18 @@ impl<F: Field> Circuit<F> for EvmCircuit<F> {
     fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
         let challenges = Challenges::construct(meta);
         let challenges_expr = challenges.exprs(meta);
-        let rw_table = RwTable::construct(meta);
-        let tx_table = TxTable::construct(meta);
-        let bytecode_table = BytecodeTable::construct(meta);
-        let block_table = BlockTable::construct(meta);
+        let rw_table = RwTable::construct(meta, challenges.phases());
+        let tx_table = TxTable::construct(meta, challenges.phases());
+        let bytecode_table = BytecodeTable::construct(meta, challenges.phases());
+        let block_table = BlockTable::construct(meta, challenges.phases());
         let q_copy_table = meta.fixed_column();
-        let copy_table = CopyTable::construct(meta, q_copy_table);
-        let keccak_table = KeccakTable::construct(meta);
-        let sha256_table = SHA256Table::construct(meta);
+        let copy_table = CopyTable::construct(meta, q_copy_table, challenges.phases());
+        let keccak_table = KeccakTable::construct(meta, challenges.phases());
+        let sha256_table = SHA256Table::construct(meta, challenges.phases());
         let exp_table = ExpTable::construct(meta);
-        let sig_table = SigTable::construct(meta);
-        let modexp_table = ModExpTable::construct(meta);
-        let ecc_table = EccTable::construct(meta);
+        let sig_table = SigTable::construct(meta, challenges.phases());
+        let modexp_table = ModExpTable::construct(meta, challenges.phases());
+        let ecc_table = EccTable::construct(meta, challenges.phases());
         let pow_of_rand_table = PowOfRandTable::construct(meta, &challenges_expr);
         (
             EvmCircuitConfig::new(
@@ -533,7 +533,7 @@ impl<F: Field> Circuit<F> for EvmCircuit<F> {
             .dev_load(&mut layouter, block, &challenges)?;
         config
             .modexp_table
-            .dev_load(&mut layouter, &block.get_big_modexp())?;
+            .dev_load(&mut layouter, &block.get_big_modexp(), &challenges)?;
         config.ecc_table.dev_load(
             &mut layouter,
             block.circuits_params.max_ec_ops,
@@ -684,6 +684,48 @@ mod evm_circuit_stats {
         );
     }
 
+    /// Merges the coverage dumps of a test suite run with the `coverage`
+    /// feature, writes `coverage.json` and `coverage.html` next to them and
+    /// prints the execution states without any assigned step.
+    ///
+    /// Run with:
+    /// `EVM_COVERAGE_DIR=<dir> cargo test -p zkevm-circuits --release
+    /// --features coverage`, then
+    /// `EVM_COVERAGE_DIR=<dir> cargo test -p zkevm-circuits --release
+    /// --features coverage export_evm_coverage_report -- --nocapture --ignored`
+    #[cfg(feature = "coverage")]
+    #[ignore]
+    #[test]
+    fn export_evm_coverage_report() {
+        use crate::{
+            evm_circuit::coverage::{CoverageReport, COVERAGE_DIR_ENV},
+            stats::DisplayTable,
+        };
+
+        let dir = std::env::var(COVERAGE_DIR_ENV).expect("EVM_COVERAGE_DIR is not set");
+        let report = CoverageReport::load_dir(&dir).unwrap();
+        report.write(&dir).unwrap();
+
+        let mut table = DisplayTable::new([
+            "state".to_string(),
+            "steps".to_string(),
+            "constraints".to_string(),
+            "lookups".to_string(),
+        ]);
+        for (state, coverage) in &report.states {
+            let (constraints_covered, constraints) = coverage.constraint_stats();
+            let (lookups_covered, lookups) = coverage.lookup_stats();
+            table.push_row([
+                state.clone(),
+                coverage.steps.to_string(),
+                format!("{constraints_covered}/{constraints}"),
+                format!("{lookups_covered}/{lookups}"),
+            ]);
+        }
+        table.print();
+        log::info!("uncovered states: {:?}", report.uncovered_states());
+    }
+
     /// This function prints to stdout a table with the top X ExecutionState
     /// cell consumers of each EVM Cell type.
     ///
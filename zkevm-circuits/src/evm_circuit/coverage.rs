@@ -0,0 +1,446 @@
+//! Constraint coverage of the EVM circuit execution gadgets.
+//!
+//! With the `coverage` feature, every named constraint and lookup added by an
+//! execution gadget through the `EVMConstraintBuilder` is kept at configure
+//! time, and evaluated against the witness of each step assigned by
+//! `ExecutionConfig::assign_exec_step`. A constraint is counted as enabled at
+//! a step when its condition is non-zero there, and as non-trivial when it is
+//! enabled and at least one of the cells it queries holds a non-zero value.
+//! Gadgets often reuse a constraint name, so the points of an execution state
+//! are identified by their index in the order they're configured.
+//!
+//! Counts are accumulated per process. `CircuitTestBuilder` dumps them to
+//! `$EVM_COVERAGE_DIR/evm-coverage-<pid>.json` after every EVM circuit
+//! MockProver run, and [`CoverageReport::load_dir`] merges the dumps of a
+//! whole test suite, which can then be exported with
+//! [`CoverageReport::write`].
+
+use super::{
+    step::ExecutionState,
+    util::{constraint_builder::ConstraintLocation, CachedRegion},
+};
+use crate::util::Field;
+use halo2_proofs::plonk::Expression;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Mutex, PoisonError},
+};
+
+/// Environment variable with the directory the coverage dumps are written to.
+pub const COVERAGE_DIR_ENV: &str = "EVM_COVERAGE_DIR";
+
+/// File name prefix of the per process dumps.
+const DUMP_PREFIX: &str = "evm-coverage-";
+
+static COVERAGE: Mutex<CoverageReport> = Mutex::new(CoverageReport {
+    states: BTreeMap::new(),
+});
+
+/// Whether a coverage point is a custom gate constraint or a lookup.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CoveragePointKind {
+    Constraint,
+    Lookup,
+}
+
+/// A named constraint or lookup of an execution gadget, kept apart from the
+/// condition it's enabled under.
+#[derive(Clone, Debug)]
+pub(crate) struct CoveragePoint<F> {
+    pub(crate) name: String,
+    pub(crate) kind: CoveragePointKind,
+    pub(crate) location: ConstraintLocation,
+    pub(crate) condition: Option<Expression<F>>,
+    /// The constraint, or the compressed lookup input, without the condition.
+    pub(crate) expr: Expression<F>,
+}
+
+impl<F: Field> CoveragePoint<F> {
+    /// Returns whether the point is enabled at the step assigned at `offset`,
+    /// and whether it's enabled with a non-trivial witness.
+    fn evaluate(
+        &self,
+        region: &CachedRegion<'_, '_, F>,
+        offset: usize,
+        is_first: bool,
+        is_last: bool,
+    ) -> (bool, bool) {
+        let located = match self.location {
+            ConstraintLocation::Step => true,
+            ConstraintLocation::StepFirst => is_first,
+            ConstraintLocation::StepLast => is_last,
+            ConstraintLocation::NotStepLast => !is_last,
+        };
+        if !located {
+            return (false, false);
+        }
+        let enabled = match &self.condition {
+            Some(condition) => {
+                let mut enabled = false;
+                region
+                    .evaluate(condition, offset)
+                    .map(|value| enabled = !value.is_zero_vartime());
+                enabled
+            }
+            None => true,
+        };
+        (
+            enabled,
+            enabled && has_non_zero_cell(region, &self.expr, offset),
+        )
+    }
+}
+
+/// Whether any advice cell queried by `expr` is non-zero.
+fn has_non_zero_cell<F: Field>(
+    region: &CachedRegion<'_, '_, F>,
+    expr: &Expression<F>,
+    offset: usize,
+) -> bool {
+    expr.evaluate(
+        &|_| false,
+        &|_| false,
+        &|_| false,
+        &|advice_query| {
+            !region
+                .get_advice(offset, advice_query.column_index(), advice_query.rotation())
+                .is_zero_vartime()
+        },
+        &|_| false,
+        &|_| false,
+        &|a| a,
+        &|a, b| a || b,
+        &|a, b| a || b,
+        &|a, _| a,
+    )
+}
+
+/// Number of steps a constraint or lookup was enabled at.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PointCoverage {
+    /// Name of the constraint or lookup, not unique within a gadget.
+    pub name: String,
+    /// Steps where the condition of the point was non-zero.
+    pub enabled: usize,
+    /// Steps where the point was enabled and queried a non-zero cell.
+    pub non_trivial: usize,
+}
+
+impl PointCoverage {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn merge(&mut self, other: &PointCoverage) {
+        assert_eq!(
+            self.name, other.name,
+            "coverage dumps of different circuit versions"
+        );
+        self.enabled += other.enabled;
+        self.non_trivial += other.non_trivial;
+    }
+}
+
+/// Coverage of the gadget of one `ExecutionState`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateCoverage {
+    /// Number of assigned steps.
+    pub steps: usize,
+    /// Coverage of the named constraints, in configure order.
+    pub constraints: Vec<PointCoverage>,
+    /// Coverage of the lookups, in configure order.
+    pub lookups: Vec<PointCoverage>,
+}
+
+impl StateCoverage {
+    fn points_mut(&mut self, kind: CoveragePointKind) -> &mut Vec<PointCoverage> {
+        match kind {
+            CoveragePointKind::Constraint => &mut self.constraints,
+            CoveragePointKind::Lookup => &mut self.lookups,
+        }
+    }
+
+    /// Returns (non-trivially covered, total) constraints.
+    pub fn constraint_stats(&self) -> (usize, usize) {
+        Self::stats(&self.constraints)
+    }
+
+    /// Returns (non-trivially covered, total) lookups.
+    pub fn lookup_stats(&self) -> (usize, usize) {
+        Self::stats(&self.lookups)
+    }
+
+    fn stats(points: &[PointCoverage]) -> (usize, usize) {
+        let covered = points.iter().filter(|p| p.non_trivial > 0).count();
+        (covered, points.len())
+    }
+
+    /// Constraints and lookups never enabled with a non-trivial witness, as
+    /// `<name> #<index>`.
+    pub fn uncovered_points(&self) -> impl Iterator<Item = String> + '_ {
+        let constraints = self.constraints.iter().enumerate();
+        let lookups = self.lookups.iter().enumerate();
+        constraints
+            .chain(lookups)
+            .filter(|(_, p)| p.non_trivial == 0)
+            .map(|(index, p)| format!("{} #{index}", p.name))
+    }
+}
+
+/// Coverage of all execution states, keyed by the `ExecutionState` name.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoverageReport {
+    /// Coverage per execution state.
+    pub states: BTreeMap<String, StateCoverage>,
+}
+
+impl CoverageReport {
+    /// Add the counts of `other` to `self`.
+    pub fn merge(&mut self, other: &CoverageReport) {
+        for (state, other) in &other.states {
+            let coverage = self.states.entry(state.clone()).or_default();
+            coverage.steps += other.steps;
+            for (kind, points) in [
+                (CoveragePointKind::Constraint, &other.constraints),
+                (CoveragePointKind::Lookup, &other.lookups),
+            ] {
+                let merged = coverage.points_mut(kind);
+                for (index, point) in points.iter().enumerate() {
+                    match merged.get_mut(index) {
+                        Some(merged) => merged.merge(point),
+                        None => merged.push(point.clone()),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Execution states without any assigned step.
+    pub fn uncovered_states(&self) -> Vec<&str> {
+        self.states
+            .iter()
+            .filter(|(_, coverage)| coverage.steps == 0)
+            .map(|(state, _)| state.as_str())
+            .collect()
+    }
+
+    /// Merge all the per process dumps found in `dir`.
+    pub fn load_dir(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let mut report = Self::default();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let is_dump = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map_or(false, |name| {
+                    name.starts_with(DUMP_PREFIX) && name.ends_with(".json")
+                });
+            if is_dump {
+                let dump: Self = serde_json::from_slice(&fs::read(&path)?)?;
+                report.merge(&dump);
+            }
+        }
+        Ok(report)
+    }
+
+    /// Serialize the report as JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("coverage report is serializable")
+    }
+
+    /// Render the report as a standalone HTML page, with the least covered
+    /// states first.
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        html.push_str(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>EVM circuit constraint coverage</title>\n<style>\n\
+             body { font-family: sans-serif; }\n\
+             table { border-collapse: collapse; }\n\
+             td, th { border: 1px solid #ccc; padding: 2px 8px; text-align: left; }\n\
+             tr.uncovered { background: #fdd; }\n\
+             tr.partial { background: #ffd; }\n\
+             </style>\n</head>\n<body>\n<h1>EVM circuit constraint coverage</h1>\n",
+        );
+        let uncovered = self.uncovered_states();
+        writeln!(
+            html,
+            "<p>{} of {} execution states have no assigned step.</p>",
+            uncovered.len(),
+            self.states.len()
+        )
+        .unwrap();
+        html.push_str(
+            "<table>\n<tr><th>Execution state</th><th>Steps</th><th>Constraints</th>\
+             <th>Lookups</th><th>Uncovered constraints and lookups</th></tr>\n",
+        );
+        let states = self.states.iter().sorted_by_key(|(state, coverage)| {
+            let (covered, total) = coverage.constraint_stats();
+            (coverage.steps > 0, covered * 100 / total.max(1), *state)
+        });
+        for (state, coverage) in states {
+            let (constraints_covered, constraints) = coverage.constraint_stats();
+            let (lookups_covered, lookups) = coverage.lookup_stats();
+            let class = if coverage.steps == 0 {
+                "uncovered"
+            } else if constraints_covered < constraints || lookups_covered < lookups {
+                "partial"
+            } else {
+                "covered"
+            };
+            let uncovered_points = coverage
+                .uncovered_points()
+                .map(|point| escape_html(&point))
+                .join("<br>");
+            writeln!(
+                html,
+                "<tr class=\"{class}\"><td>{}</td><td>{}</td><td>{constraints_covered}/{constraints}</td>\
+                 <td>{lookups_covered}/{lookups}</td><td><details><summary>{}</summary>{uncovered_points}</details></td></tr>",
+                escape_html(state),
+                coverage.steps,
+                coverage.uncovered_points().count(),
+            )
+            .unwrap();
+        }
+        html.push_str("</table>\n</body>\n</html>\n");
+        html
+    }
+
+    /// Write the report to `coverage.json` and `coverage.html` in `dir`.
+    pub fn write(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join("coverage.json"), self.to_json())?;
+        fs::write(dir.join("coverage.html"), self.to_html())
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn with_coverage<R>(f: impl FnOnce(&mut CoverageReport) -> R) -> R {
+    f(&mut COVERAGE.lock().unwrap_or_else(PoisonError::into_inner))
+}
+
+/// Add the points of a configured gadget to the report, so that they show up
+/// even if they're never assigned.
+pub(crate) fn register<F>(execution_state: ExecutionState, points: &[CoveragePoint<F>]) {
+    with_coverage(|report| {
+        let coverage = report
+            .states
+            .entry(format!("{execution_state:?}"))
+            .or_default();
+        for kind in [CoveragePointKind::Constraint, CoveragePointKind::Lookup] {
+            let registered = coverage.points_mut(kind);
+            if registered.is_empty() {
+                registered.extend(
+                    points
+                        .iter()
+                        .filter(|point| point.kind == kind)
+                        .map(|point| PointCoverage::new(&point.name)),
+                );
+            }
+        }
+    })
+}
+
+/// Record the coverage of the step of `execution_state` assigned at `offset`.
+pub(crate) fn record<F: Field>(
+    execution_state: ExecutionState,
+    points: &[CoveragePoint<F>],
+    region: &CachedRegion<'_, '_, F>,
+    offset: usize,
+    is_first: bool,
+    is_last: bool,
+) {
+    let evaluated = points
+        .iter()
+        .map(|point| (point, point.evaluate(region, offset, is_first, is_last)))
+        .collect_vec();
+    with_coverage(|report| {
+        let coverage = report
+            .states
+            .entry(format!("{execution_state:?}"))
+            .or_default();
+        coverage.steps += 1;
+        for kind in [CoveragePointKind::Constraint, CoveragePointKind::Lookup] {
+            let counts = coverage.points_mut(kind);
+            let evaluated = evaluated.iter().filter(|(point, _)| point.kind == kind);
+            for (index, (point, (enabled, non_trivial))) in evaluated.enumerate() {
+                if index == counts.len() {
+                    counts.push(PointCoverage::new(&point.name));
+                }
+                counts[index].enabled += usize::from(*enabled);
+                counts[index].non_trivial += usize::from(*non_trivial);
+            }
+        }
+    })
+}
+
+/// Returns the coverage accumulated so far by this process.
+pub fn snapshot() -> CoverageReport {
+    with_coverage(|report| report.clone())
+}
+
+/// Write the coverage accumulated so far by this process to
+/// `$EVM_COVERAGE_DIR/evm-coverage-<pid>.json`. Returns the path of the dump,
+/// or `None` if the environment variable is not set.
+pub fn dump() -> io::Result<Option<PathBuf>> {
+    let Ok(dir) = std::env::var(COVERAGE_DIR_ENV) else {
+        return Ok(None);
+    };
+    fs::create_dir_all(&dir)?;
+    let path = Path::new(&dir).join(format!("{DUMP_PREFIX}{}.json", std::process::id()));
+    fs::write(&path, snapshot().to_json())?;
+    Ok(Some(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_adds_counts() {
+        let point = |enabled, non_trivial| PointCoverage {
+            name: "a".to_string(),
+            enabled,
+            non_trivial,
+        };
+        let mut report = CoverageReport::default();
+        report.states.insert(
+            "ADD".to_string(),
+            StateCoverage {
+                steps: 2,
+                // the same name used twice by a gadget is counted apart
+                constraints: vec![point(2, 1), point(1, 0)],
+                ..Default::default()
+            },
+        );
+        report
+            .states
+            .insert("ErrorOutOfGasSHA3".to_string(), StateCoverage::default());
+        let mut merged = report.clone();
+        merged.merge(&report);
+
+        let add = &merged.states["ADD"];
+        assert_eq!(add.steps, 4);
+        assert_eq!(add.constraints[0].enabled, 4);
+        assert_eq!(add.constraints[0].non_trivial, 2);
+        assert_eq!(add.constraints[1].enabled, 2);
+        assert_eq!(add.constraint_stats(), (1, 2));
+        assert_eq!(add.uncovered_points().collect_vec(), vec!["a #1"]);
+        assert_eq!(merged.uncovered_states(), vec!["ErrorOutOfGasSHA3"]);
+        assert!(merged.to_html().contains("ErrorOutOfGasSHA3"));
+    }
+}
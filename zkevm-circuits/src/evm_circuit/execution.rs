@@ -1,4 +1,5 @@
 use super::{
+    coverage::CoveragePoint,
     param::{
         BLOCK_TABLE_LOOKUPS, BYTECODE_TABLE_LOOKUPS, COPY_TABLE_LOOKUPS, ECC_TABLE_LOOKUPS,
         EXP_TABLE_LOOKUPS, FIXED_TABLE_LOOKUPS, KECCAK_TABLE_LOOKUPS, MODEXP_TABLE_LOOKUPS,
@@ -22,7 +23,9 @@ use crate::{
         witness::{Block, Call, ExecStep, Transaction},
     },
     table::{LookupTable, RwTableTag, TxReceiptFieldTag},
-    util::{query_expression, Challenges, Expr, Field},
+    util::{
+        advice_column_in_phase2, advice_column_in_phase3, query_expression, Challenges, Expr, Field,
+    },
 };
 use bus_mapping::util::read_env_var;
 use eth_types::ToLittleEndian;
@@ -42,19 +45,212 @@ use std::{
     sync::LazyLock,
 };
 
-#[cfg(feature = "onephase")]
-use halo2_proofs::plonk::FirstPhase as SecondPhase;
-#[cfg(feature = "onephase")]
-use halo2_proofs::plonk::FirstPhase as ThirdPhase;
-#[cfg(not(feature = "onephase"))]
-use halo2_proofs::plonk::SecondPhase;
-#[cfg(not(feature = "onephase"))]
-use halo2_proofs::plonk::ThirdPhase;
-
 use strum::{EnumCount, IntoEnumIterator};
 pub(crate) static CHECK_RW_LOOKUP: LazyLock<bool> =
     LazyLock::new(|| read_env_var("CHECK_RW_LOOKUP", false));
 
+/// Minimum number of steps in each of the parallel assigned regions of real steps
+pub(crate) const REGION1_MIN_CHUNK_SIZE: usize = 50;
+/// Minimum number of rows in each of the parallel assigned regions of padding steps
+pub(crate) const REGION2_MIN_CHUNK_SIZE: usize = 300;
+
+/// Calculate chunk_size and chunk_num of the parallel assigned regions.
+/// Here a min_chunk_size is provided to reduce threading overhead
+pub(crate) fn chunk_layout(name: &str, task_len: usize, min_chunk_size: usize) -> (usize, usize) {
+    if task_len == 0 {
+        return (0, 0);
+    }
+    let num_threads = std::thread::available_parallelism()
+        .map(|e| e.get())
+        .unwrap_or(1);
+    //let num_threads = 1;
+    let chunk_size = ((task_len + num_threads - 1) / num_threads).max(min_chunk_size);
+    let chunk_num = (task_len + chunk_size - 1) / chunk_size;
+    log::debug!(
+        "{} chunking: len = {}, num_threads = {}, chunk_size = {}, chunk_num = {}",
+        name,
+        task_len,
+        num_threads,
+        chunk_size,
+        chunk_num
+    );
+    (chunk_size, chunk_num)
+}
+
+/// Names of the regions of real steps, padding steps and the EndBlock step
+pub(crate) const STEP_REGION_NAMES: [&str; 3] = [
+    "Execution step region1",
+    "Execution step region2",
+    "Execution step region3",
+];
+
+/// Layout of the execution steps of a block. [`ExecutionConfig::assign_block`]
+/// assigns the steps following it, and it's used to map rows back to steps.
+pub(crate) struct StepLayout<'a> {
+    block: &'a Block,
+    /// Transaction the leading steps are assigned with, they belong to none
+    leading_tx: Transaction,
+    /// Assignment of each real step, in region1
+    steps: Vec<StepAssignment>,
+    region1_height: usize,
+    region1_chunk_size: usize,
+    region1_chunk_num: usize,
+    /// Height of the padding steps, 1 when the evm circuit has a dynamic height
+    region2_height: usize,
+    region2_chunk_size: usize,
+    region2_chunk_num: usize,
+    /// Height of the EndBlock step, plus a dummy "next" row used for Rotation
+    region3_height: usize,
+}
+
+/// Assignment of a real step in region1
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct StepAssignment {
+    /// None for the leading steps
+    tx_idx: Option<usize>,
+    step_idx_in_tx: usize,
+    height: usize,
+    /// Offset from the beginning of region1
+    offset: usize,
+}
+
+/// Execution step located at a row of the evm circuit
+#[derive(Debug)]
+pub struct LocatedStep<'a> {
+    /// Tx and step index in the tx, none for the leading, padding and EndBlock steps
+    pub tx_step: Option<(usize, usize)>,
+    /// The step
+    pub step: &'a ExecStep,
+    /// Row offset inside the step
+    pub row_in_step: usize,
+}
+
+impl<'a> StepLayout<'a> {
+    pub(crate) fn new(block: &'a Block) -> Self {
+        let mut steps = Vec::new();
+        // the "global offset"
+        let mut offset = 0;
+        let tx_steps = block
+            .txs
+            .iter()
+            .enumerate()
+            .map(|(tx_idx, tx)| (Some(tx_idx), &tx.steps));
+        for (tx_idx, steps_in_tx) in iter::once((None, &block.leading_steps)).chain(tx_steps) {
+            for (step_idx_in_tx, step) in steps_in_tx.iter().enumerate() {
+                let height = step.execution_state.get_step_height();
+                steps.push(StepAssignment {
+                    tx_idx,
+                    step_idx_in_tx,
+                    height,
+                    offset,
+                });
+                offset += height;
+            }
+        }
+        let region1_height = offset;
+        let region3_height = ExecutionState::EndBlock.get_step_height() + 1;
+        let evm_rows = block.circuits_params.max_evm_rows;
+        // 0 means "dynamic height". For the ease of implementation, even then we still pad 1
+        // step.
+        let region2_height = if evm_rows == 0 {
+            1
+        } else {
+            evm_rows.saturating_sub(region3_height + region1_height)
+        };
+        let (region1_chunk_size, region1_chunk_num) =
+            chunk_layout("region1", steps.len(), REGION1_MIN_CHUNK_SIZE);
+        let (region2_chunk_size, region2_chunk_num) =
+            chunk_layout("region2", region2_height, REGION2_MIN_CHUNK_SIZE);
+        Self {
+            block,
+            leading_tx: Transaction {
+                calls: vec![Call::default()],
+                ..Default::default()
+            },
+            steps,
+            region1_height,
+            region1_chunk_size,
+            region1_chunk_num,
+            region2_height,
+            region2_chunk_size,
+            region2_chunk_num,
+            region3_height,
+        }
+    }
+
+    /// Real steps assigned in the `chunk`-th sub-region of region1, with the index of the first
+    /// one
+    fn region1_chunk(&self, chunk: usize) -> (usize, &[StepAssignment]) {
+        let begin = chunk * self.region1_chunk_size;
+        let end = ((chunk + 1) * self.region1_chunk_size).min(self.steps.len());
+        (begin, &self.steps[begin..end])
+    }
+
+    /// Rows range of the `chunk`-th sub-region of region2, from the beginning of region2
+    fn region2_chunk(&self, chunk: usize) -> std::ops::Range<usize> {
+        let begin = chunk * self.region2_chunk_size;
+        let end = ((chunk + 1) * self.region2_chunk_size).min(self.region2_height);
+        begin..end
+    }
+
+    fn step(&self, assignment: &StepAssignment) -> (&Transaction, &'a ExecStep) {
+        match assignment.tx_idx {
+            Some(tx_idx) => {
+                let transaction = &self.block.txs[tx_idx];
+                (transaction, &transaction.steps[assignment.step_idx_in_tx])
+            }
+            None => (
+                &self.leading_tx,
+                &self.block.leading_steps[assignment.step_idx_in_tx],
+            ),
+        }
+    }
+
+    /// Row of the evm circuit at `offset` in the `chunk`-th sub-region of the
+    /// step region `region_idx`, indexing [`STEP_REGION_NAMES`].
+    #[cfg_attr(not(any(feature = "triage", test)), allow(dead_code))]
+    pub(crate) fn row(&self, region_idx: usize, chunk: usize, offset: usize) -> Option<usize> {
+        match region_idx {
+            0 => self
+                .steps
+                .get(chunk * self.region1_chunk_size)
+                .map(|step| step.offset + offset),
+            1 => Some(self.region1_height + chunk * self.region2_chunk_size + offset),
+            2 => Some(self.region1_height + self.region2_height + offset),
+            _ => None,
+        }
+    }
+
+    /// Step assigned at `row` of the evm circuit
+    #[cfg_attr(not(any(feature = "triage", test)), allow(dead_code))]
+    pub(crate) fn step_at(&self, row: usize) -> Option<LocatedStep<'a>> {
+        if row < self.region1_height {
+            let idx = self.steps.partition_point(|step| step.offset <= row) - 1;
+            let assignment = &self.steps[idx];
+            Some(LocatedStep {
+                tx_step: assignment
+                    .tx_idx
+                    .map(|tx_idx| (tx_idx, assignment.step_idx_in_tx)),
+                step: self.step(assignment).1,
+                row_in_step: row - assignment.offset,
+            })
+        } else if row < self.region1_height + self.region2_height {
+            Some(LocatedStep {
+                tx_step: None,
+                step: &self.block.padding_step,
+                row_in_step: 0,
+            })
+        } else {
+            let row_in_step = row - self.region1_height - self.region2_height;
+            (row_in_step < ExecutionState::EndBlock.get_step_height()).then_some(LocatedStep {
+                tx_step: None,
+                step: &self.block.end_block_step,
+                row_in_step,
+            })
+        }
+    }
+}
+
 mod add_sub;
 mod addmod;
 mod address;
@@ -139,6 +335,7 @@ mod sload;
 mod sstore;
 mod stop;
 mod swap;
+mod system_call;
 mod tload;
 mod tstore;
 
@@ -213,7 +410,7 @@ use pc::PcGadget;
 use pop::PopGadget;
 use precompiles::{
     BasePrecompileGadget, EcAddGadget, EcMulGadget, EcPairingGadget, EcrecoverGadget,
-    IdentityGadget, ModExpGadget, SHA256Gadget,
+    IdentityGadget, ModExpGadget, P256VerifyGadget, SHA256Gadget,
 };
 use push::PushGadget;
 use return_revert::ReturnRevertGadget;
@@ -230,6 +427,7 @@ use sload::SloadGadget;
 use sstore::SstoreGadget;
 use stop::StopGadget;
 use swap::SwapGadget;
+use system_call::SystemCallGadget;
 use tload::TloadGadget;
 use tstore::TstoreGadget;
 
@@ -272,6 +470,7 @@ pub(crate) struct ExecutionConfig<F> {
     step: Step<F>,
     pub(crate) height_map: HashMap<ExecutionState, usize>,
     stored_expressions_map: HashMap<ExecutionState, Vec<StoredExpression<F>>>,
+    coverage_points_map: HashMap<ExecutionState, Vec<CoveragePoint<F>>>,
     instrument: Instrument,
     // internal state gadgets
     begin_tx_gadget: Box<BeginTxGadget<F>>,
@@ -279,6 +478,7 @@ pub(crate) struct ExecutionConfig<F> {
     padding_gadget: Box<PaddingGadget<F>>,
     end_inner_block_gadget: Box<EndInnerBlockGadget<F>>,
     end_tx_gadget: Box<EndTxGadget<F>>,
+    system_call_gadget: Box<SystemCallGadget<F>>,
     // opcode gadgets
     add_sub_gadget: Box<AddSubGadget<F>>,
     addmod_gadget: Box<AddModGadget<F>>,
@@ -378,6 +578,7 @@ pub(crate) struct ExecutionConfig<F> {
     precompile_bn128mul_gadget: Box<EcMulGadget<F>>,
     precompile_bn128pairing_gadget: Box<EcPairingGadget<F>>,
     precompile_blake2f_gadget: Box<BasePrecompileGadget<F, { ExecutionState::PrecompileBlake2f }>>,
+    precompile_p256_verify_gadget: Box<P256VerifyGadget<F>>,
 }
 
 impl<F: Field> ExecutionConfig<F> {
@@ -391,6 +592,7 @@ impl<F: Field> ExecutionConfig<F> {
         tx_table: &dyn LookupTable<F>,
         rw_table: &dyn LookupTable<F>,
         bytecode_table: &dyn LookupTable<F>,
+        bytecode_code_hashes_table: &dyn LookupTable<F>,
         block_table: &dyn LookupTable<F>,
         copy_table: &dyn LookupTable<F>,
         keccak_table: &dyn LookupTable<F>,
@@ -416,9 +618,9 @@ impl<F: Field> ExecutionConfig<F> {
             .enumerate()
             .map(|(n, _)| {
                 if n < EVM_LOOKUP_COLS {
-                    meta.advice_column_in(ThirdPhase)
+                    advice_column_in_phase3(meta, challenges.phases())
                 } else if n < EVM_LOOKUP_COLS + N_PHASE2_COLUMNS {
-                    meta.advice_column_in(SecondPhase)
+                    advice_column_in_phase2(meta, challenges.phases())
                 } else {
                     meta.advice_column_in(FirstPhase)
                 }
@@ -440,11 +642,14 @@ impl<F: Field> ExecutionConfig<F> {
 
             // NEW: Enabled, this will break hand crafted tests, maybe we can remove them?
             let first_step_check = {
-                let begin_tx_or_padding_selector = step_curr
-                    .execution_state_selector([ExecutionState::BeginTx, ExecutionState::Padding]);
+                let first_step_selector = step_curr.execution_state_selector([
+                    ExecutionState::BeginTx,
+                    ExecutionState::SystemCall,
+                    ExecutionState::Padding,
+                ]);
                 iter::once((
-                    "First step should be BeginTx or Padding",
-                    q_step_first * (1.expr() - begin_tx_or_padding_selector),
+                    "First step should be BeginTx, SystemCall or Padding",
+                    q_step_first * (1.expr() - first_step_selector),
                 ))
             };
 
@@ -531,6 +736,7 @@ impl<F: Field> ExecutionConfig<F> {
         });
 
         let mut stored_expressions_map = HashMap::new();
+        let mut coverage_points_map = HashMap::new();
 
         macro_rules! configure_gadget {
             () => {
@@ -552,6 +758,7 @@ impl<F: Field> ExecutionConfig<F> {
                         &step_curr,
                         &mut height_map,
                         &mut stored_expressions_map,
+                        &mut coverage_points_map,
                         &mut instrument,
                     ))
                 })()
@@ -575,6 +782,7 @@ impl<F: Field> ExecutionConfig<F> {
             end_inner_block_gadget: configure_gadget!(),
             end_tx_gadget: configure_gadget!(),
             padding_gadget: configure_gadget!(),
+            system_call_gadget: configure_gadget!(),
             // opcode gadgets
             add_sub_gadget: configure_gadget!(),
             addmod_gadget: configure_gadget!(),
@@ -673,10 +881,12 @@ impl<F: Field> ExecutionConfig<F> {
             precompile_bn128mul_gadget: configure_gadget!(),
             precompile_bn128pairing_gadget: configure_gadget!(),
             precompile_blake2f_gadget: configure_gadget!(),
+            precompile_p256_verify_gadget: configure_gadget!(),
             // step and presets
             step: step_curr,
             height_map,
             stored_expressions_map,
+            coverage_points_map,
             instrument,
         };
 
@@ -687,6 +897,7 @@ impl<F: Field> ExecutionConfig<F> {
             tx_table,
             rw_table,
             bytecode_table,
+            bytecode_code_hashes_table,
             block_table,
             copy_table,
             keccak_table,
@@ -719,6 +930,7 @@ impl<F: Field> ExecutionConfig<F> {
         step_curr: &Step<F>,
         height_map: &mut HashMap<ExecutionState, usize>,
         stored_expressions_map: &mut HashMap<ExecutionState, Vec<StoredExpression<F>>>,
+        coverage_points_map: &mut HashMap<ExecutionState, Vec<CoveragePoint<F>>>,
         instrument: &mut Instrument,
     ) -> G {
         // Configure the gadget with the max height first so we can find out the actual
@@ -758,6 +970,7 @@ impl<F: Field> ExecutionConfig<F> {
             step_next,
             height_map,
             stored_expressions_map,
+            coverage_points_map,
             instrument,
             G::NAME,
             G::EXECUTION_STATE,
@@ -780,12 +993,24 @@ impl<F: Field> ExecutionConfig<F> {
         step_next: &Step<F>,
         height_map: &mut HashMap<ExecutionState, usize>,
         stored_expressions_map: &mut HashMap<ExecutionState, Vec<StoredExpression<F>>>,
+        coverage_points_map: &mut HashMap<ExecutionState, Vec<CoveragePoint<F>>>,
         instrument: &mut Instrument,
         name: &'static str,
         execution_state: ExecutionState,
         height: usize,
         mut cb: EVMConstraintBuilder<F>,
     ) {
+        // The step height constraint below queries a column outside of the step,
+        // which can't be evaluated for coverage.
+        let coverage_points = cb.take_coverage_points();
+        #[cfg(feature = "coverage")]
+        super::coverage::register(execution_state, &coverage_points);
+        debug_assert!(
+            !coverage_points_map.contains_key(&execution_state),
+            "execution state already configured"
+        );
+        coverage_points_map.insert(execution_state, coverage_points);
+
         // Enforce the step height for this opcode
         let num_rows_until_next_step_next = query_expression(meta, |meta| {
             meta.query_advice(num_rows_until_next_step, Rotation::next())
@@ -862,9 +1087,19 @@ impl<F: Field> ExecutionConfig<F> {
                             vec![ExecutionState::BeginTx, ExecutionState::EndInnerBlock],
                         ),
                         (
-                            "EndInnerBlock can only transition to BeginTx, EndInnerBlock or Padding",
+                            "EndInnerBlock can only transition to BeginTx, EndInnerBlock, SystemCall or Padding",
                             ExecutionState::EndInnerBlock,
-                            vec![ExecutionState::BeginTx, ExecutionState::EndInnerBlock, ExecutionState::Padding],
+                            vec![
+                                ExecutionState::BeginTx,
+                                ExecutionState::EndInnerBlock,
+                                ExecutionState::SystemCall,
+                                ExecutionState::Padding,
+                            ],
+                        ),
+                        (
+                            "SystemCall can only transit to BeginTx or EndInnerBlock",
+                            ExecutionState::SystemCall,
+                            vec![ExecutionState::BeginTx, ExecutionState::EndInnerBlock],
                         ),
                         (
                             "Padding can only transit to Padding or EndBlock",
@@ -878,9 +1113,13 @@ impl<F: Field> ExecutionConfig<F> {
                 .chain(
                     IntoIterator::into_iter([
                         (
-                            "Only EndTx or EndInnerBlock can transit to BeginTx",
+                            "Only EndTx, EndInnerBlock or SystemCall can transit to BeginTx",
                             ExecutionState::BeginTx,
-                            vec![ExecutionState::EndTx, ExecutionState::EndInnerBlock],
+                            vec![
+                                ExecutionState::EndTx,
+                                ExecutionState::EndInnerBlock,
+                                ExecutionState::SystemCall,
+                            ],
                         ),
                         (
                             "Only ExecutionState which halts / precompile or BeginTx can transit to EndTx",
@@ -897,10 +1136,20 @@ impl<F: Field> ExecutionConfig<F> {
                             vec![ExecutionState::Padding],
                         ),
                         (
-                            // Empty block can result multiple EndInnerBlock states.
-                            "Only EndTx or EndInnerBlock can transit to EndInnerBlock",
+                            // Empty block can result multiple EndInnerBlock states, after the
+                            // SystemCall of the block if it has one.
+                            "Only EndTx, EndInnerBlock or SystemCall can transit to EndInnerBlock",
                             ExecutionState::EndInnerBlock,
-                            vec![ExecutionState::EndTx, ExecutionState::EndInnerBlock],
+                            vec![
+                                ExecutionState::EndTx,
+                                ExecutionState::EndInnerBlock,
+                                ExecutionState::SystemCall,
+                            ],
+                        ),
+                        (
+                            "Only EndInnerBlock can transit to SystemCall",
+                            ExecutionState::SystemCall,
+                            vec![ExecutionState::EndInnerBlock],
                         ),
                     ])
                     .filter(move |(_, _, from)| !from.contains(&execution_state))
@@ -909,9 +1158,13 @@ impl<F: Field> ExecutionConfig<F> {
                 .chain(
                     IntoIterator::into_iter([
                         (
-                            "EndInnerBlock -> BeginTx/EndInnerBlock: block number increases by one",
+                            "EndInnerBlock -> BeginTx/EndInnerBlock/SystemCall: block number increases by one",
                             ExecutionState::EndInnerBlock,
-                            vec![ExecutionState::BeginTx, ExecutionState::EndInnerBlock],
+                            vec![
+                                ExecutionState::BeginTx,
+                                ExecutionState::EndInnerBlock,
+                                ExecutionState::SystemCall,
+                            ],
                             step_next.state.block_number.expr() - step_curr.state.block_number.expr() - 1.expr(),
                         ),
                         (
@@ -956,6 +1209,7 @@ impl<F: Field> ExecutionConfig<F> {
         tx_table: &dyn LookupTable<F>,
         rw_table: &dyn LookupTable<F>,
         bytecode_table: &dyn LookupTable<F>,
+        bytecode_code_hashes_table: &dyn LookupTable<F>,
         block_table: &dyn LookupTable<F>,
         copy_table: &dyn LookupTable<F>,
         keccak_table: &dyn LookupTable<F>,
@@ -977,6 +1231,7 @@ impl<F: Field> ExecutionConfig<F> {
                         Table::Tx => tx_table,
                         Table::Rw => rw_table,
                         Table::Bytecode => bytecode_table,
+                        Table::BytecodeCodeHashes => bytecode_code_hashes_table,
                         Table::Block => block_table,
                         Table::Copy => copy_table,
                         Table::Keccak => keccak_table,
@@ -1006,23 +1261,18 @@ impl<F: Field> ExecutionConfig<F> {
     }
 
     pub fn get_num_rows_required_no_padding(&self, block: &Block) -> usize {
-        let mut num_rows = 0;
-        for transaction in &block.txs {
-            for step in &transaction.steps {
-                num_rows += step.execution_state.get_step_height();
-            }
-        }
-        num_rows
+        block
+            .steps()
+            .map(|step| step.execution_state.get_step_height())
+            .sum()
     }
     pub fn get_num_rows_required(&self, block: &Block) -> usize {
         // Start at 1 so we can be sure there is an unused `next` row available
         let mut num_rows = 1;
         let evm_rows = block.circuits_params.max_evm_rows;
         if evm_rows == 0 {
-            for transaction in &block.txs {
-                for step in &transaction.steps {
-                    num_rows += step.execution_state.get_step_height();
-                }
+            for step in block.steps() {
+                num_rows += step.execution_state.get_step_height();
             }
             num_rows += 1; // At least 1 Padding needed
         } else {
@@ -1084,31 +1334,25 @@ impl<F: Field> ExecutionConfig<F> {
         debug_assert_eq!(ExecutionState::Padding.get_step_height(), 1);
 
         let inverter = Inverter::new(MAX_STEP_HEIGHT as u64);
-        let evm_rows = block.circuits_params.max_evm_rows;
-        // 0 means "dynamic height". If fixed height is used in unittests, CI will be quite slow.
-        let no_padding = evm_rows == 0;
 
         // There should be 3 group of regions
         // 1. real steps
         // 2. padding. For the ease of implementation, even for `no_padding` case, we will still pad
         //    1 step.
         // 3. EndBlock
-        let region1_height = self.get_num_rows_required_no_padding(block);
-        let region3_height = ExecutionState::EndBlock.get_step_height() + 1; // plus a dummy "next" row used for Rotation
-        let region2_height = if no_padding {
-            1
-        } else {
-            if region1_height + region3_height >= evm_rows {
-                log::error!(
-                    "evm circuit row not enough, region1_height:{}, region3_height:{}, max_evm_rows:{}",
-                    region1_height,
-                    region3_height,
-                    evm_rows
-                );
-                return Err(Error::Synthesis);
-            }
-            evm_rows - region3_height - region1_height
-        };
+        let layout = StepLayout::new(block);
+        let region1_height = layout.region1_height;
+        let region2_height = layout.region2_height;
+        let region3_height = layout.region3_height;
+        if region2_height == 0 {
+            log::error!(
+                "evm circuit row not enough, region1_height:{}, region3_height:{}, max_evm_rows:{}",
+                region1_height,
+                region3_height,
+                block.circuits_params.max_evm_rows
+            );
+            return Err(Error::Synthesis);
+        }
 
         // A quick path for "reporting" height for the halo2 first pass layouter.
         let assign_shape_fn = |region: &mut Region<'_, F>, height| {
@@ -1130,32 +1374,7 @@ impl<F: Field> ExecutionConfig<F> {
         let padding_step = &block.padding_step;
         let end_block_step = &block.end_block_step;
 
-        // A helper struct used for parallel assignment
-        struct StepAssignment {
-            tx_idx: usize,
-            step_idx_in_tx: usize,
-            height: usize,
-            offset: usize,
-        }
-        let total_step_num = block.txs.iter().map(|t| t.steps.len()).sum::<usize>();
-        let mut step_assignments: Vec<StepAssignment> = Vec::with_capacity(total_step_num);
-
-        // the "global offset"
-        let mut offset = 0;
-        for (tx_idx, tx) in block.txs.iter().enumerate() {
-            for (step_idx, step) in tx.steps.iter().enumerate() {
-                let height = step.execution_state.get_step_height();
-                step_assignments.push(StepAssignment {
-                    tx_idx,
-                    step_idx_in_tx: step_idx,
-                    offset,
-                    height,
-                });
-                offset += height;
-            }
-        }
-        assert_eq!(offset, region1_height);
-        offset = 0;
+        let total_step_num = layout.steps.len();
 
         // Print some logs after each tx, for debugging
         let log_step_fn = |transaction: &Transaction, step: &ExecStep, offset| {
@@ -1194,51 +1413,28 @@ impl<F: Field> ExecutionConfig<F> {
             }
         };
 
-        // Calculate chunk_size and chunk_num
-        // Here a min_chunk_size is provided to reduce threading overhead
-        let chunking_fn = |name: &str, task_len: usize, min_chunk_size: usize| -> (usize, usize) {
-            if task_len == 0 {
-                return (0, 0);
-            }
-            let num_threads = std::thread::available_parallelism()
-                .map(|e| e.get())
-                .unwrap_or(1);
-            //let num_threads = 1;
-            let chunk_size = ((task_len + num_threads - 1) / num_threads).max(min_chunk_size);
-            let chunk_num = (task_len + chunk_size - 1) / chunk_size;
-            log::debug!(
-                "{} chunking: len = {}, num_threads = {}, chunk_size = {}, chunk_num = {}",
-                name,
-                task_len,
-                num_threads,
-                chunk_size,
-                chunk_num
-            );
-            (chunk_size, chunk_num)
-        };
-
         // Step1: assign real steps
-        let (region1_chunk_size, region1_chunk_num) =
-            chunking_fn("region1", step_assignments.len(), 50);
-        let mut region1_is_first_time: Vec<(usize, bool)> = (0..region1_chunk_num)
+        let mut region1_is_first_time: Vec<(usize, bool)> = (0..layout.region1_chunk_num)
             .map(|chunk_idx| (chunk_idx, true))
             .collect();
         let region1_height_sum = layouter
             .assign_regions(
-                || "Execution step region1",
+                || STEP_REGION_NAMES[0],
                 region1_is_first_time
                     .iter_mut()
                     .map(|(chunk_idx, is_first_time)| {
                         |mut region: Region<'_, F>| {
                             let chunk_idx = *chunk_idx;
-                            let begin = chunk_idx * region1_chunk_size;
-                            let end =
-                                ((chunk_idx + 1) * region1_chunk_size).min(step_assignments.len());
-                            let step_idxs: Vec<usize> = (begin..end).collect();
-                            log::trace!("region1 range {} {} {}", chunk_idx, begin, end);
-                            let total_height = step_idxs
+                            let (begin, step_assignments) = layout.region1_chunk(chunk_idx);
+                            log::trace!(
+                                "region1 range {} {} {}",
+                                chunk_idx,
+                                begin,
+                                begin + step_assignments.len()
+                            );
+                            let total_height = step_assignments
                                 .iter()
-                                .map(|idx| step_assignments[*idx].height)
+                                .map(|step_assignment| step_assignment.height)
                                 .sum::<usize>();
                             if *is_first_time {
                                 *is_first_time = false;
@@ -1252,22 +1448,17 @@ impl<F: Field> ExecutionConfig<F> {
                             if chunk_idx == 0 {
                                 self.q_step_first.enable(&mut region, offset)?;
                             }
-                            for step_idx in step_idxs {
-                                let step_assignment = &step_assignments[step_idx];
-                                let transaction = &block.txs[step_assignment.tx_idx];
-                                let step = &transaction.steps[step_assignment.step_idx_in_tx];
+                            for (step_idx, step_assignment) in (begin..).zip(step_assignments) {
+                                let (transaction, step) = layout.step(step_assignment);
                                 let call = &transaction.calls[step.call_index];
-
-                                let height = step.execution_state.get_step_height();
+                                let height = step_assignment.height;
 
                                 log_step_fn(transaction, step, offset);
 
-                                let next = match step_assignments.get(step_idx + 1) {
+                                let next = match layout.steps.get(step_idx + 1) {
                                     None => (&dummy_tx, &last_call, padding_step),
                                     Some(step_assignment) => {
-                                        let transaction = &block.txs[step_assignment.tx_idx];
-                                        let step =
-                                            &transaction.steps[step_assignment.step_idx_in_tx];
+                                        let (transaction, step) = layout.step(step_assignment);
                                         let call = &transaction.calls[step.call_index];
                                         (transaction, call, step)
                                     }
@@ -1280,6 +1471,7 @@ impl<F: Field> ExecutionConfig<F> {
                                     transaction,
                                     call,
                                     step,
+                                    step_idx,
                                     height,
                                     Some(next),
                                     challenges,
@@ -1301,9 +1493,7 @@ impl<F: Field> ExecutionConfig<F> {
         debug_assert_eq!(region1_height, region1_height_sum);
 
         // part2: assign paddings steps when padding needed
-        assert!(region2_height >= 1);
-        let (region2_chunk_size, region2_chunk_num) = chunking_fn("region2", region2_height, 300);
-        let mut region2_is_first_time: Vec<(usize, bool)> = (0..region2_chunk_num)
+        let mut region2_is_first_time: Vec<(usize, bool)> = (0..layout.region2_chunk_num)
             .map(|chunk_idx| (chunk_idx, true))
             .collect();
         log::trace!(
@@ -1312,15 +1502,13 @@ impl<F: Field> ExecutionConfig<F> {
             region1_height + region2_height
         );
         layouter.assign_regions(
-            || "Execution step region2",
+            || STEP_REGION_NAMES[1],
             region2_is_first_time
                 .iter_mut()
                 .map(|(chunk_idx, is_first_time)| {
                     |mut region: Region<'_, F>| {
                         let chunk_idx = *chunk_idx;
-                        let begin = chunk_idx * region2_chunk_size;
-                        let end = ((chunk_idx + 1) * region2_chunk_size).min(region2_height);
-                        let region_height = end - begin;
+                        let region_height = layout.region2_chunk(chunk_idx).len();
                         if *is_first_time {
                             *is_first_time = false;
                             return assign_shape_fn(&mut region, region_height);
@@ -1357,12 +1545,13 @@ impl<F: Field> ExecutionConfig<F> {
 
         let mut region3_is_first_time = true;
         layouter.assign_region(
-            || "Execution step region3",
+            || STEP_REGION_NAMES[2],
             |mut region| {
                 if region3_is_first_time {
                     region3_is_first_time = false;
                     return assign_shape_fn(&mut region, region3_height);
                 }
+                let offset = 0;
                 let height = end_block_step.execution_state.get_step_height();
                 self.assign_exec_step(
                     &mut region,
@@ -1371,6 +1560,7 @@ impl<F: Field> ExecutionConfig<F> {
                     &dummy_tx,
                     &last_call,
                     end_block_step,
+                    total_step_num,
                     height,
                     None,
                     challenges,
@@ -1502,7 +1692,10 @@ impl<F: Field> ExecutionConfig<F> {
         Ok(())
     }
 
+    /// `step_idx` is the index of `step` among all the steps of the block,
+    /// with the EndBlock step last.
     #[allow(clippy::too_many_arguments)]
+    #[cfg_attr(not(feature = "coverage"), allow(unused_variables))]
     fn assign_exec_step(
         &self,
         region: &mut Region<'_, F>,
@@ -1511,6 +1704,7 @@ impl<F: Field> ExecutionConfig<F> {
         transaction: &Transaction,
         call: &Call,
         step: &ExecStep,
+        step_idx: usize,
         height: usize,
         next: Option<(&Transaction, &Call, &ExecStep)>,
         challenges: &Challenges<Value<F>>,
@@ -1543,7 +1737,24 @@ impl<F: Field> ExecutionConfig<F> {
             )?;
         }
 
-        self.assign_exec_step_int(region, offset, block, transaction, call, step, true)
+        self.assign_exec_step_int(region, offset, block, transaction, call, step, true)?;
+
+        #[cfg(feature = "coverage")]
+        {
+            let is_first = step_idx == 0;
+            // Only the EndBlock step is assigned without a next step.
+            let is_last = next.is_none();
+            super::coverage::record(
+                step.execution_state,
+                &self.coverage_points_map[&step.execution_state],
+                region,
+                offset,
+                is_first,
+                is_last,
+            );
+        }
+
+        Ok(())
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -1586,6 +1797,7 @@ impl<F: Field> ExecutionConfig<F> {
             ExecutionState::EndInnerBlock => assign_exec_step!(self.end_inner_block_gadget),
             ExecutionState::EndBlock => assign_exec_step!(self.end_block_gadget),
             ExecutionState::Padding => assign_exec_step!(self.padding_gadget),
+            ExecutionState::SystemCall => assign_exec_step!(self.system_call_gadget),
             // opcode
             ExecutionState::ADD_SUB => assign_exec_step!(self.add_sub_gadget),
             ExecutionState::ADDMOD => assign_exec_step!(self.addmod_gadget),
@@ -1747,6 +1959,9 @@ impl<F: Field> ExecutionConfig<F> {
             ExecutionState::PrecompileBlake2f => {
                 assign_exec_step!(self.precompile_blake2f_gadget)
             }
+            ExecutionState::PrecompileP256Verify => {
+                assign_exec_step!(self.precompile_p256_verify_gadget)
+            }
         }
 
         // Fill in the witness values for stored expressions
@@ -6,8 +6,9 @@ use crate::{
         util::{
             and,
             common_gadget::{
-                CurieGadget, TransferGadgetInfo, TransferWithGasFeeGadget, TxAccessListGadget,
-                TxEip1559Gadget, TxL1FeeGadget, TxL1MsgGadget,
+                HardforkGadget, SystemCallForksGadget, TransferGadgetInfo,
+                TransferWithGasFeeGadget, TxAccessListGadget, TxEip1559Gadget, TxL1FeeGadget,
+                TxL1MsgGadget,
             },
             constraint_builder::{
                 ConstrainBuilderCommon, EVMConstraintBuilder, ReversionInfo, StepStateTransition,
@@ -31,12 +32,19 @@ use crate::{
 };
 use array_init::array_init;
 use bus_mapping::{circuit_input_builder::CopyDataType, precompile::PrecompileCalls};
-use eth_types::{utils::is_precompiled, Address, ToLittleEndian, U256};
+use eth_types::{
+    forks::{ChainSpec, HardforkId},
+    utils::is_precompiled,
+    Address, ToLittleEndian, U256,
+};
 use ethers_core::utils::{get_contract_address, keccak256, rlp::RlpStream};
 use gadgets::util::{expr_from_bytes, not, select, Expr};
 use gadgets::ToScalar;
 use halo2_proofs::{circuit::Value, plonk::Error};
+use itertools::Itertools;
+use strum::IntoEnumIterator;
 
+// 0x01 to 0x09, the RIP-7212 P256VERIFY at 0x100 is added after the P256Verify fork
 const PRECOMPILE_COUNT: usize = 9;
 
 #[derive(Clone, Debug)]
@@ -77,6 +85,8 @@ pub(crate) struct BeginTxGadget<F> {
     call_code_hash_is_empty: IsEqualGadget<F>,
     call_code_hash_is_zero: IsZeroGadget<F>,
     is_precompile_lt: LtGadget<F, N_BYTES_ACCOUNT_ADDRESS>,
+    #[cfg(feature = "scroll")]
+    is_p256_verify: IsEqualGadget<F>,
     precompile_gadget: PrecompileGadget<F>,
     precompile_input_len: Cell<F>, // the number of input bytes taken for the precompile call.
     precompile_input_bytes_rlc: Cell<F>, // input bytes to precompile call.
@@ -93,13 +103,20 @@ pub(crate) struct BeginTxGadget<F> {
     is_callee_warm: Cell<F>,
     is_coinbase_warm: Cell<F>,
     are_precompile_warm: [Cell<F>; PRECOMPILE_COUNT],
+    #[cfg(feature = "scroll")]
+    is_p256_verify_warm: Cell<F>,
     // EIP-3651 (Warm COINBASE) for Shanghai
     coinbase: Cell<F>,
     tx_l1_fee: TxL1FeeGadget<F>,
     tx_l1_msg: TxL1MsgGadget<F>,
     tx_access_list: TxAccessListGadget<F>,
     tx_eip1559: TxEip1559Gadget<F>,
-    curie: CurieGadget<F>,
+    curie: HardforkGadget<F>,
+    #[cfg(feature = "scroll")]
+    p256_verify: HardforkGadget<F>,
+    /// Whether this is the first step of the chunk, i.e. its block has no SystemCall step
+    is_first_step: IsZeroGadget<F>,
+    system_call: SystemCallForksGadget<F>,
 }
 
 impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
@@ -139,7 +156,25 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
         let tx_access_list = TxAccessListGadget::construct(cb, tx_id.expr(), tx_type.expr());
         let is_call_data_empty = IsZeroGadget::construct(cb, tx_call_data_length.expr());
 
-        let curie = CurieGadget::construct(cb, cb.curr.state.block_number.expr());
+        let curie =
+            HardforkGadget::construct(cb, HardforkId::Curie, cb.curr.state.block_number.expr());
+        #[cfg(feature = "scroll")]
+        let p256_verify = HardforkGadget::construct(
+            cb,
+            HardforkId::P256Verify,
+            cb.curr.state.block_number.expr(),
+        );
+
+        // The chunk starts with this tx rather than with a SystemCall step only if no fork of
+        // the system contracts is active at its block, see EndInnerBlock for the other blocks.
+        let is_first_step = IsZeroGadget::construct(cb, cb.curr.state.rw_counter.expr() - 1.expr());
+        let system_call = SystemCallForksGadget::construct(cb, cb.curr.state.block_number.expr());
+        cb.condition(is_first_step.expr(), |cb| {
+            cb.require_zero(
+                "the first block of the chunk has no SystemCall step",
+                system_call.expr(),
+            );
+        });
 
         let tx_l1_msg = TxL1MsgGadget::construct(cb, tx_type.expr(), tx_caller_address.expr());
         let tx_l1_fee = cb.condition(not::expr(tx_l1_msg.is_l1_msg()), |cb| {
@@ -150,7 +185,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
             );
             TxL1FeeGadget::construct(
                 cb,
-                not::expr(curie.is_before_curie.expr()),
+                not::expr(curie.is_before_fork.expr()),
                 tx_id.expr(),
                 tx_data_gas_cost.expr(),
                 tx_signed_length.expr(),
@@ -169,7 +204,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
         let l1_rw_delta = select::expr(
             tx_l1_msg.is_l1_msg(),
             tx_l1_msg.rw_delta(),
-            tx_l1_fee.rw_delta(not::expr(curie.is_before_curie.expr())),
+            tx_l1_fee.rw_delta(),
         ) + 1.expr();
 
         // the cost caused by l1
@@ -254,6 +289,16 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
             not::expr(tx_callee_address_is_zero.expr()),
             is_precompile_lt.expr(),
         ]);
+        // or the P256VERIFY precompile at 0x100, after the P256Verify fork
+        #[cfg(feature = "scroll")]
+        let is_p256_verify = IsEqualGadget::construct(
+            cb,
+            tx_callee_address.expr(),
+            PrecompileCalls::P256Verify.address().expr(),
+        );
+        #[cfg(feature = "scroll")]
+        let is_precompile =
+            is_precompile + is_p256_verify.expr() * not::expr(p256_verify.is_before_fork.expr());
         let precompile_input_len = cb.query_cell();
 
         let tx_call_data_word_length =
@@ -293,9 +338,35 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
         let is_coinbase_warm = cb.query_bool();
         let are_precompile_warm = array_init(|_| cb.query_bool());
 
-        for (addr, is_warm) in (1..=PRECOMPILE_COUNT).zip(are_precompile_warm.iter()) {
-            cb.account_access_list_write(tx_id.expr(), addr.expr(), 1.expr(), is_warm.expr(), None);
+        for (precompile, is_warm) in PrecompileCalls::iter()
+            .filter(|&precompile| is_precompiled(&precompile.into()))
+            .zip_eq(are_precompile_warm.iter())
+        {
+            cb.account_access_list_write(
+                tx_id.expr(),
+                precompile.address().expr(),
+                1.expr(),
+                is_warm.expr(),
+                None,
+            );
         } // rwc_delta += PRECOMPILE_COUNT
+        #[cfg(feature = "scroll")]
+        let is_p256_verify_warm = cb.query_bool();
+        #[cfg(feature = "scroll")]
+        cb.condition(not::expr(p256_verify.is_before_fork.expr()), |cb| {
+            cb.account_access_list_write(
+                tx_id.expr(),
+                PrecompileCalls::P256Verify.address().expr(),
+                1.expr(),
+                is_p256_verify_warm.expr(),
+                None,
+            );
+        }); // rwc_delta += 1 after the P256Verify fork
+        #[cfg(feature = "scroll")]
+        let precompile_count =
+            PRECOMPILE_COUNT.expr() + not::expr(p256_verify.is_before_fork.expr());
+        #[cfg(not(feature = "scroll"))]
+        let precompile_count = PRECOMPILE_COUNT.expr();
 
         // Prepare access list of caller and callee
         cb.account_access_list_write(
@@ -486,7 +557,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                 //   - Write CallContext IsPersistent
                 //   - Write CallContext IsSuccess
                 //   - Write Account (Caller) Nonce
-                //   - Write TxAccessListAccount (Precompile) x PRECOMPILE_COUNT
+                //   - Write TxAccessListAccount (Precompile) x precompile_count
                 //   - Write TxAccessListAccount (Caller)
                 //   - Write TxAccessListAccount (Callee)
                 //   - Write TxAccessListAccount (Coinbase) only for Shanghai
@@ -511,7 +582,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                         + l1_rw_delta.expr()
                         + transfer_with_gas_fee.rw_delta()
                         + tx_access_list.rw_delta_expr()
-                        + PRECOMPILE_COUNT.expr(),
+                        + precompile_count.clone(),
                 ),
                 call_id: To(call_id.expr()),
                 is_root: To(true.expr()),
@@ -618,7 +689,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                     //   - Write CallContext IsPersistent
                     //   - Write CallContext IsSuccess
                     //   - Write Account (Caller) Nonce
-                    //   - Write TxAccessListAccount (Precompile) x PRECOMPILE_COUNT
+                    //   - Write TxAccessListAccount (Precompile) x precompile_count
                     //   - Write TxAccessListAccount (Caller)
                     //   - Write TxAccessListAccount (Callee)
                     //   - Write TxAccessListAccount (Coinbase) only for Shanghai
@@ -646,7 +717,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                             + l1_rw_delta.expr()
                             + transfer_with_gas_fee.rw_delta()
                             + tx_access_list.rw_delta_expr()
-                            + PRECOMPILE_COUNT.expr(),
+                            + precompile_count.clone(),
                     ),
                     call_id: To(call_id.expr()),
                     is_root: To(true.expr()),
@@ -695,7 +766,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                     //   - Write CallContext IsPersistent
                     //   - Write CallContext IsSuccess
                     //   - Write Account Nonce
-                    //   - Write TxAccessListAccount (Precompile) x PRECOMPILE_COUNT
+                    //   - Write TxAccessListAccount (Precompile) x precompile_count
                     //   - Write TxAccessListAccount (Caller)
                     //   - Write TxAccessListAccount (Callee)
                     //   - Write TxAccessListAccount (Coinbase) only for Shanghai
@@ -707,7 +778,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                             + l1_rw_delta.expr()
                             + transfer_with_gas_fee.rw_delta()
                             + tx_access_list.rw_delta_expr()
-                            + PRECOMPILE_COUNT.expr(),
+                            + precompile_count.clone(),
                     ),
                     call_id: To(call_id.expr()),
                     end_tx: To(1.expr()),
@@ -756,7 +827,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                     //   - Write CallContext IsPersistent
                     //   - Write CallContext IsSuccess
                     //   - Write Account Nonce
-                    //   - Write TxAccessListAccount (Precompile) x PRECOMPILE_COUNT
+                    //   - Write TxAccessListAccount (Precompile) x precompile_count
                     //   - Write TxAccessListAccount (Caller)
                     //   - Write TxAccessListAccount (Callee)
                     //   - Write TxAccessListAccount (Coinbase) only for Shanghai
@@ -780,7 +851,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                             + l1_rw_delta.expr()
                             + transfer_with_gas_fee.rw_delta()
                             + tx_access_list.rw_delta_expr()
-                            + PRECOMPILE_COUNT.expr(),
+                            + precompile_count.clone(),
                     ),
                     call_id: To(call_id.expr()),
                     is_root: To(true.expr()),
@@ -829,6 +900,8 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
             call_code_hash_is_empty,
             call_code_hash_is_zero,
             is_precompile_lt,
+            #[cfg(feature = "scroll")]
+            is_p256_verify,
             precompile_gadget,
             precompile_input_len,
             precompile_input_bytes_rlc,
@@ -840,12 +913,18 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
             is_callee_warm,
             is_coinbase_warm,
             are_precompile_warm,
+            #[cfg(feature = "scroll")]
+            is_p256_verify_warm,
             coinbase,
             tx_l1_fee,
             tx_l1_msg,
             tx_access_list,
             tx_eip1559,
             curie,
+            #[cfg(feature = "scroll")]
+            p256_verify,
+            is_first_step,
+            system_call,
         }
     }
 
@@ -897,10 +976,6 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
         self.tx_l1_msg
             .assign(region, offset, tx_type, caller_code_hash)?;
 
-        let is_curie = bus_mapping::circuit_input_builder::curie::is_curie_enabled(
-            block.chain_id,
-            tx.block_number,
-        );
         // Add access-list RW offset.
         rws.offset_add(TxAccessListGadget::<F>::rw_delta_value(tx) as usize);
 
@@ -920,15 +995,18 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                 0
             }
         } else {
-            if is_curie {
-                6
-            } else {
-                3
-            }
+            tx.l1_fee.model().oracle_slots().len()
         });
 
         self.curie
             .assign(region, offset, block.chain_id, tx.block_number)?;
+        #[cfg(feature = "scroll")]
+        self.p256_verify
+            .assign(region, offset, block.chain_id, tx.block_number)?;
+        self.is_first_step
+            .assign(region, offset, F::from(step.rw_counter as u64 - 1))?;
+        self.system_call
+            .assign(region, offset, block.chain_id, tx.block_number)?;
 
         let rw = rws.next();
         debug_assert_eq!(rw.tag(), RwTableTag::CallContext);
@@ -945,6 +1023,10 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
 
         let are_precompile_warm: [_; PRECOMPILE_COUNT] =
             array_init(|_| rws.next().tx_access_list_value_pair().1);
+        #[cfg(feature = "scroll")]
+        let is_p256_verify_warm = ChainSpec::for_chain_id(block.chain_id)
+            .is_active_at_block(HardforkId::P256Verify, tx.block_number)
+            && rws.next().tx_access_list_value_pair().1;
 
         let is_caller_warm = rws.next().tx_access_list_value_pair().1;
         let is_callee_warm = rws.next().tx_access_list_value_pair().1;
@@ -1033,14 +1115,24 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
             .assign(region, offset, callee_address)?;
         self.is_precompile_lt
             .assign(region, offset, callee_address, F::from(0xA))?;
+        #[cfg(feature = "scroll")]
+        self.is_p256_verify.assign(
+            region,
+            offset,
+            callee_address,
+            F::from(PrecompileCalls::P256Verify.address()),
+        )?;
         // precompile related assignment.
         let (precompile_input_len, precompile_input_bytes_rlc) = if tx
             .callee_address
             .as_ref()
-            .map(is_precompiled)
+            .map(|address| {
+                ChainSpec::for_chain_id(block.chain_id)
+                    .is_precompiled_at_block(address, tx.block_number)
+            })
             .unwrap_or_default()
         {
-            let precompile_call: PrecompileCalls = tx.callee_address.unwrap().0[19].into();
+            let precompile_call: PrecompileCalls = tx.callee_address.unwrap().into();
             let input_len = if let Some(input_len) = precompile_call.input_len() {
                 std::cmp::min(input_len, tx.call_data_length)
             } else {
@@ -1206,6 +1298,12 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
         for (cell, val) in self.are_precompile_warm.iter().zip(are_precompile_warm) {
             cell.assign(region, offset, Value::known(F::from(val)))?;
         }
+        #[cfg(feature = "scroll")]
+        self.is_p256_verify_warm.assign(
+            region,
+            offset,
+            Value::known(F::from(is_p256_verify_warm)),
+        )?;
 
         self.coinbase.assign(
             region,
@@ -1245,7 +1343,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
             tx.l1_fee,
             tx.l1_fee_committed,
             tx.tx_data_gas_cost,
-            tx.rlp_signed.len().try_into().unwrap(),
+            tx.rlp_signed.len() as u64,
         )?;
 
         self.tx_access_list.assign(region, offset, tx)?;
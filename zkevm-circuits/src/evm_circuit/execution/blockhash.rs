@@ -5,22 +5,33 @@ use crate::{
         step::ExecutionState,
         util::{
             and,
-            common_gadget::{SameContextGadget, WordByteCapGadget},
+            common_gadget::{
+                history_storage_address, HardforkGadget, HistoryStorageSlotGadget,
+                SameContextGadget, WordByteCapGadget,
+            },
             constraint_builder::{
                 ConstrainBuilderCommon, EVMConstraintBuilder, StepStateTransition,
                 Transition::Delta,
             },
             from_bytes,
             math_gadget::LtGadget,
-            CachedRegion, Cell, U64Word,
+            select, CachedRegion, Cell, U64Word,
         },
         witness::{Block, Call, ExecStep, Transaction},
     },
-    table::BlockContextFieldTag,
+    table::{BlockContextFieldTag, CallContextFieldTag},
     util::{Expr, Field},
 };
-use bus_mapping::evm::OpcodeId;
-use eth_types::evm_types::block_utils::{is_valid_block_number, NUM_PREV_BLOCK_ALLOWED};
+use bus_mapping::{
+    circuit_input_builder::history_storage::is_history_storage_enabled, evm::OpcodeId,
+};
+use eth_types::{
+    evm_types::block_utils::{
+        is_valid_block_number, is_valid_block_number_in_window, HISTORY_SERVE_WINDOW,
+        NUM_PREV_BLOCK_ALLOWED,
+    },
+    forks::HardforkId,
+};
 use gadgets::util::not;
 use gadgets::ToScalar;
 use halo2_proofs::{circuit::Value, plonk::Error};
@@ -33,6 +44,11 @@ pub(crate) struct BlockHashGadget<F> {
     block_hash: Cell<F>,
     chain_id: U64Word<F>,
     diff_lt: LtGadget<F, N_BYTES_U64>,
+    /// After the fork, the hashes are read from the history storage contract
+    history_storage: HardforkGadget<F>,
+    history_slot: HistoryStorageSlotGadget<F>,
+    tx_id: Cell<F>,
+    committed_value: Cell<F>,
 }
 
 impl<F: Field> ExecutionGadget<F> for BlockHashGadget<F> {
@@ -58,53 +74,83 @@ impl<F: Field> ExecutionGadget<F> for BlockHashGadget<F> {
             from_bytes::expr(&chain_id.cells),
         );
 
+        let history_storage = HardforkGadget::construct(
+            cb,
+            HardforkId::HistoryStorage,
+            cb.curr.state.block_number.expr(),
+        );
+        let is_history = not::expr(history_storage.is_before_fork.expr());
+
         let diff_lt = cb.condition(block_number.not_overflow(), |cb| {
             LtGadget::construct(
                 cb,
                 current_block_number.expr(),
                 // even though NUM_PREV_BLOCK_ALLOWED +  1 + block_number.valid_value() may exceed
                 // u64::MAX theoretically, but very very rare in practical。 omit this case.
-                (NUM_PREV_BLOCK_ALLOWED + 1).expr() + block_number.valid_value(),
+                select::expr(
+                    is_history.expr(),
+                    (HISTORY_SERVE_WINDOW + 1).expr(),
+                    (NUM_PREV_BLOCK_ALLOWED + 1).expr(),
+                ) + block_number.valid_value(),
             )
         });
 
         let is_valid = and::expr([block_number.lt_cap(), diff_lt.expr()]);
         let block_hash = cb.query_cell_phase2();
-        cb.condition(is_valid.expr(), |cb| {
-            // For non-scroll, lookup for the block hash.
-            #[cfg(not(feature = "scroll"))]
-            cb.block_lookup(
-                BlockContextFieldTag::BlockHash.expr(),
-                block_number.valid_value(),
-                block_hash.expr(),
-            );
+        cb.condition(
+            and::expr([is_valid.expr(), not::expr(is_history.expr())]),
+            |cb| {
+                // For non-scroll, lookup for the block hash.
+                #[cfg(not(feature = "scroll"))]
+                cb.block_lookup(
+                    BlockContextFieldTag::BlockHash.expr(),
+                    block_number.valid_value(),
+                    block_hash.expr(),
+                );
+
+                // For scroll, the block hash is calculated by Keccak256. The input
+                // is a 16-bytes array, the first 8-bytes are set to the big-endian
+                // of chain ID and the last 8-bytes are set to block number.
+                #[cfg(feature = "scroll")]
+                cb.keccak_table_lookup(
+                    cb.keccak_rlc::<{ 2 * N_BYTES_U64 }>(
+                        chain_id
+                            .cells
+                            .iter()
+                            .rev()
+                            .chain(
+                                block_number
+                                    .original_ref()
+                                    .cells
+                                    .iter()
+                                    .take(N_BYTES_U64)
+                                    .rev(),
+                            )
+                            .rev()
+                            .map(Expr::expr)
+                            .collect::<Vec<_>>()
+                            .try_into()
+                            .unwrap(),
+                    ),
+                    (2 * N_BYTES_U64).expr(),
+                    block_hash.expr(),
+                );
+            },
+        );
 
-            // For scroll, the block hash is calculated by Keccak256. The input
-            // is a 16-bytes array, the first 8-bytes are set to the big-endian
-            // of chain ID and the last 8-bytes are set to block number.
-            #[cfg(feature = "scroll")]
-            cb.keccak_table_lookup(
-                cb.keccak_rlc::<{ 2 * N_BYTES_U64 }>(
-                    chain_id
-                        .cells
-                        .iter()
-                        .rev()
-                        .chain(
-                            block_number
-                                .original_ref()
-                                .cells
-                                .iter()
-                                .take(N_BYTES_U64)
-                                .rev(),
-                        )
-                        .rev()
-                        .map(Expr::expr)
-                        .collect::<Vec<_>>()
-                        .try_into()
-                        .unwrap(),
-                ),
-                (2 * N_BYTES_U64).expr(),
+        // After the fork, the block hash is read from its slot in the history storage contract.
+        let history_slot = HistoryStorageSlotGadget::construct(cb, block_number.valid_value());
+        let tx_id = cb.query_cell();
+        let committed_value = cb.query_cell_phase2();
+        let is_history_read = and::expr([is_valid.expr(), is_history.expr()]);
+        cb.condition(is_history_read.expr(), |cb| {
+            cb.call_context_lookup(false.expr(), None, CallContextFieldTag::TxId, tx_id.expr());
+            cb.account_storage_read(
+                history_storage_address(),
+                history_slot.slot_rlc(),
                 block_hash.expr(),
+                tx_id.expr(),
+                committed_value.expr(),
             );
         });
 
@@ -118,7 +164,7 @@ impl<F: Field> ExecutionGadget<F> for BlockHashGadget<F> {
         cb.stack_push(block_hash.expr());
 
         let step_state_transition = StepStateTransition {
-            rw_counter: Delta(2.expr()),
+            rw_counter: Delta(2.expr() + 2.expr() * is_history_read),
             program_counter: Delta(1.expr()),
             gas_left: Delta(-OpcodeId::BLOCKHASH.constant_gas_cost().expr()),
             ..Default::default()
@@ -133,6 +179,10 @@ impl<F: Field> ExecutionGadget<F> for BlockHashGadget<F> {
             block_hash,
             chain_id,
             diff_lt,
+            history_storage,
+            history_slot,
+            tx_id,
+            committed_value,
         }
     }
 
@@ -149,14 +199,32 @@ impl<F: Field> ExecutionGadget<F> for BlockHashGadget<F> {
 
         let chain_id = block.chain_id;
         let current_block_number = block.context.ctxs[&tx.block_number].number;
+        let is_history = is_history_storage_enabled(chain_id, tx.block_number);
+        let window = if is_history {
+            HISTORY_SERVE_WINDOW
+        } else {
+            NUM_PREV_BLOCK_ALLOWED
+        };
         let block_number = block.rws[step.rw_indices[0]].stack_value();
-        let block_hash = block.rws[step.rw_indices[1]].stack_value();
-        if is_valid_block_number(block_number, current_block_number) {
-            #[cfg(feature = "scroll")]
-            assert_eq!(
-                block_hash,
-                eth_types::evm_types::block_utils::calculate_block_hash(chain_id, block_number).1
-            );
+        let block_hash = block.rws[step.rw_indices[step.rw_indices.len() - 1]].stack_value();
+        if is_valid_block_number_in_window(block_number, current_block_number, window) {
+            if is_history {
+                let (value, _, tx_id, committed_value) =
+                    block.rws[step.rw_indices[2]].storage_value_aux();
+                assert_eq!(block_hash, value);
+                self.tx_id
+                    .assign(region, offset, Value::known(F::from(tx_id as u64)))?;
+                self.committed_value
+                    .assign(region, offset, region.word_rlc(committed_value))?;
+            } else {
+                debug_assert!(is_valid_block_number(block_number, current_block_number));
+                #[cfg(feature = "scroll")]
+                assert_eq!(
+                    block_hash,
+                    eth_types::evm_types::block_utils::calculate_block_hash(chain_id, block_number)
+                        .1
+                );
+            }
         } else {
             assert_eq!(block_hash, 0.into());
         }
@@ -173,6 +241,10 @@ impl<F: Field> ExecutionGadget<F> for BlockHashGadget<F> {
             .assign(region, offset, region.word_rlc(block_hash))?;
         self.chain_id
             .assign(region, offset, Some(chain_id.to_le_bytes()))?;
+        self.history_storage
+            .assign(region, offset, chain_id, tx.block_number)?;
+        self.history_slot
+            .assign(region, offset, block_number_valid)?;
 
         // Block number overflow should be constrained by WordByteCapGadget.
         let block_number: F = block_number
@@ -184,7 +256,7 @@ impl<F: Field> ExecutionGadget<F> for BlockHashGadget<F> {
                 region,
                 offset,
                 current_block_number,
-                F::from(block_number_valid) + F::from(NUM_PREV_BLOCK_ALLOWED + 1),
+                F::from(block_number_valid) + F::from(window + 1),
             )?;
         }
         Ok(())
@@ -1,3 +1,5 @@
+#[cfg(feature = "scroll")]
+use crate::evm_circuit::util::{common_gadget::HardforkGadget, math_gadget::IsEqualGadget};
 use crate::{
     evm_circuit::{
         execution::ExecutionGadget,
@@ -26,9 +28,11 @@ use crate::{
 use bus_mapping::{
     circuit_input_builder::CopyDataType, evm::OpcodeId, precompile::PrecompileCalls,
 };
+#[cfg(feature = "scroll")]
+use eth_types::forks::HardforkId;
 use eth_types::{
     evm_types::{memory::MemoryWordRange, GAS_STIPEND_CALL_WITH_VALUE},
-    utils::is_precompiled,
+    forks::ChainSpec,
     ToAddress, ToBigEndian, ToLittleEndian, U256,
 };
 use gadgets::ToScalar;
@@ -72,6 +76,10 @@ pub(crate) struct CallOpGadget<F> {
     // to handle precompile calls
     is_code_address_zero: IsZeroGadget<F>,
     is_precompile_lt: LtGadget<F, N_BYTES_ACCOUNT_ADDRESS>,
+    #[cfg(feature = "scroll")]
+    is_p256_verify: IsEqualGadget<F>,
+    #[cfg(feature = "scroll")]
+    p256_verify: HardforkGadget<F>,
     precompile_gadget: PrecompileGadget<F>,
     precompile_return_length: Cell<F>,
     precompile_return_length_zero: IsZeroGadget<F>,
@@ -214,7 +222,8 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
         });
 
         // whether the call is to a precompiled contract.
-        // precompile contracts are stored from address 0x01 to 0x09.
+        // precompile contracts are stored from address 0x01 to 0x09, and at 0x100 for the
+        // P256VERIFY precompile after the P256Verify fork.
         let is_code_address_zero = IsZeroGadget::construct(cb, call_gadget.callee_address_expr());
         let is_precompile_lt =
             LtGadget::construct(cb, call_gadget.callee_address_expr(), 0x0A.expr());
@@ -222,6 +231,21 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
             not::expr(is_code_address_zero.expr()),
             is_precompile_lt.expr(),
         ]);
+        #[cfg(feature = "scroll")]
+        let is_p256_verify = IsEqualGadget::construct(
+            cb,
+            call_gadget.callee_address_expr(),
+            PrecompileCalls::P256Verify.address().expr(),
+        );
+        #[cfg(feature = "scroll")]
+        let p256_verify = HardforkGadget::construct(
+            cb,
+            HardforkId::P256Verify,
+            cb.curr.state.block_number.expr(),
+        );
+        #[cfg(feature = "scroll")]
+        let is_precompile =
+            is_precompile + is_p256_verify.expr() * not::expr(p256_verify.is_before_fork.expr());
         let precompile_return_length = cb.query_cell();
         let precompile_return_length_zero =
             IsZeroGadget::construct(cb, precompile_return_length.expr());
@@ -736,6 +760,10 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
             // precompile related fields.
             is_code_address_zero,
             is_precompile_lt,
+            #[cfg(feature = "scroll")]
+            is_p256_verify,
+            #[cfg(feature = "scroll")]
+            p256_verify,
             precompile_gadget,
             precompile_return_length,
             precompile_return_length_zero,
@@ -755,7 +783,7 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
         region: &mut CachedRegion<'_, '_, F>,
         offset: usize,
         block: &Block,
-        _tx: &Transaction,
+        tx: &Transaction,
         call: &Call,
         step: &ExecStep,
     ) -> Result<(), Error> {
@@ -942,7 +970,8 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
         // precompile related assignment.
         let (is_precompile_call, precompile_addr) = {
             let precompile_addr = callee_address.to_address();
-            let is_precompiled_call = is_precompiled(&precompile_addr);
+            let is_precompiled_call = ChainSpec::for_chain_id(block.chain_id)
+                .is_precompiled_at_block(&precompile_addr, tx.block_number);
             (is_precompiled_call, precompile_addr)
         };
         let code_address: F = callee_address.to_address().to_scalar().unwrap();
@@ -950,6 +979,16 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
             .assign(region, offset, code_address)?;
         self.is_precompile_lt
             .assign(region, offset, code_address, 0x0Au64.into())?;
+        #[cfg(feature = "scroll")]
+        self.is_p256_verify.assign(
+            region,
+            offset,
+            code_address,
+            F::from(PrecompileCalls::P256Verify.address()),
+        )?;
+        #[cfg(feature = "scroll")]
+        self.p256_verify
+            .assign(region, offset, block.chain_id, tx.block_number)?;
         log::trace!("callop is precompile call {}", is_precompile_call);
         let precompile_return_length = if is_precompile_call && is_precheck_ok {
             rws.offset_add(15); // skip
@@ -988,8 +1027,8 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
             input_rws,
             output_rws,
             return_rws,
-        ) = if is_precheck_ok && is_precompiled(&callee_address.to_address()) {
-            let precompile_call: PrecompileCalls = precompile_addr.0[19].into();
+        ) = if is_precheck_ok && is_precompile_call {
+            let precompile_call: PrecompileCalls = precompile_addr.into();
             let input_len = if let Some(input_len) = precompile_call.input_len() {
                 min(input_len, cd_length.as_usize())
             } else {
@@ -1140,7 +1179,7 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
 
         if is_precompile_call {
             self.precompile_gadget
-                .assign(region, offset, precompile_addr.0[19].into())?;
+                .assign(region, offset, precompile_addr.into())?;
         }
 
         Ok(())
@@ -4,7 +4,6 @@ use crate::{
     evm_circuit::{
         execution::ExecutionGadget,
         step::ExecutionState,
-        table::{FixedTableTag, Lookup},
         util::{
             constraint_builder::{
                 ConstrainBuilderCommon, EVMConstraintBuilder, StepStateTransition, Transition::Same,
@@ -14,17 +13,13 @@ use crate::{
         },
         witness::{Block, Call, ExecStep, Transaction},
     },
-    table::{AccountFieldTag, BlockContextFieldTag, CallContextFieldTag, TxContextFieldTag},
+    table::{BlockContextFieldTag, CallContextFieldTag, TxContextFieldTag},
     util::{Expr, Field},
 };
 use bus_mapping::l2_predeployed::{
     l2_sequencer_set::{ADDRESS as SEQUENCER_SET, SEQUENCER_SET_ROOT_SLOT},
     message_queue::{ADDRESS as MESSAGE_QUEUE, WITHDRAW_TRIE_ROOT_SLOT},
 };
-use eth_types::{
-    forks::HardforkId,
-    utils::{hash_code, hash_code_keccak},
-};
 use gadgets::ToScalar;
 use halo2_proofs::{
     circuit::{Cell as AssignedCell, Value},
@@ -36,6 +31,7 @@ pub(crate) struct EndBlockGadget<F> {
     chain_id: Cell<F>,
     total_txs: Cell<F>,
     total_txs_is_max_txs: IsEqualGadget<F>,
+    total_txs_is_zero: IsZeroGadget<F>,
     is_empty_block: IsZeroGadget<F>,
     max_rws: Cell<F>,
     max_txs: Cell<F>,
@@ -43,7 +39,6 @@ pub(crate) struct EndBlockGadget<F> {
     phase2_withdraw_root_prev: Cell<F>,
     phase2_sequencer_root: Cell<F>,
     phase2_sequencer_root_prev: Cell<F>,
-    is_curie_fork_block: Cell<F>,
     pub withdraw_root_assigned: Mutex<Option<AssignedCell>>,
     pub sequencer_root_assigned: Mutex<Option<AssignedCell>>,
 }
@@ -60,6 +55,7 @@ impl<F: Clone> Clone for EndBlockGadget<F> {
             chain_id: self.chain_id.clone(),
             total_txs: self.total_txs.clone(),
             total_txs_is_max_txs: self.total_txs_is_max_txs.clone(),
+            total_txs_is_zero: self.total_txs_is_zero.clone(),
             is_empty_block: self.is_empty_block.clone(),
             max_rws: self.max_rws.clone(),
             max_txs: self.max_txs.clone(),
@@ -67,7 +63,6 @@ impl<F: Clone> Clone for EndBlockGadget<F> {
             phase2_withdraw_root_prev: self.phase2_withdraw_root_prev.clone(),
             phase2_sequencer_root: self.phase2_sequencer_root.clone(),
             phase2_sequencer_root_prev: self.phase2_sequencer_root_prev.clone(),
-            is_curie_fork_block: self.is_curie_fork_block.clone(),
         }
     }
 }
@@ -96,6 +91,7 @@ impl<F: Field> ExecutionGadget<F> for EndBlockGadget<F> {
         let max_rws = cb.query_copy_cell();
         let total_txs = cb.query_cell();
         let total_txs_is_max_txs = IsEqualGadget::construct(cb, total_txs.expr(), max_txs.expr());
+        let total_txs_is_zero = IsZeroGadget::construct(cb, total_txs.expr());
         let phase2_withdraw_root = cb.query_copy_cell_phase2();
         let phase2_withdraw_root_prev = cb.query_cell_phase2();
         let phase2_sequencer_root = cb.query_copy_cell_phase2();
@@ -108,89 +104,15 @@ impl<F: Field> ExecutionGadget<F> for EndBlockGadget<F> {
             chain_id.expr(),
         );
 
-        let is_curie_fork_block = cb.query_cell();
-        // Sequencer is allowed to do a predefined state transition at the hardfork block.
-        cb.condition(is_curie_fork_block.expr(), |cb| {
-            cb.add_lookup(
-                "Hardfork lookup",
-                Lookup::Fixed {
-                    tag: FixedTableTag::ChainFork.expr(),
-                    values: [
-                        (HardforkId::Curie as u64).expr(),
-                        chain_id.expr(),
-                        cb.curr.state.block_number.expr(),
-                    ]},
-            );
-            // Ref: bus-mapping/src/circuit_input_builder/curie.rs
-            // Bytecode changes
-            use bus_mapping::l2_predeployed::l1_gas_price_oracle;
-            let v1_codesize = l1_gas_price_oracle::V1_BYTECODE.len();
-            let v1_codehash = hash_code(&l1_gas_price_oracle::V1_BYTECODE);
-            let v1_keccak_codehash = hash_code_keccak(&l1_gas_price_oracle::V1_BYTECODE);
-            log::debug!("l1_oracle poseidon codehash {:?}", v1_codehash);
-            log::debug!("l1_oracle keccak codehash {:?}", v1_keccak_codehash);
-            let v2_codesize = l1_gas_price_oracle::V2_BYTECODE.len();
-            let v2_codehash = hash_code(&l1_gas_price_oracle::V2_BYTECODE);
-            let v2_keccak_codehash = hash_code_keccak(&l1_gas_price_oracle::V2_BYTECODE);
-            let l1_fee_address = Expression::Constant(l1_gas_price_oracle::ADDRESS.to_scalar().expect(
-                "Unexpected address of l2 gasprice oracle contract -> Scalar conversion failure",
-            ));
-            cb.account_write(
-                l1_fee_address.expr(),
-                AccountFieldTag::CodeHash,
-                cb.code_hash(v2_codehash),
-                cb.code_hash(v1_codehash),
-                None,
-            );
-            cb.account_write(
-                l1_fee_address.expr(),
-                AccountFieldTag::KeccakCodeHash,
-                cb.keccak_code_hash(v2_keccak_codehash),
-                cb.keccak_code_hash(v1_keccak_codehash),
-                None,
-            );
-            cb.account_write(
-                l1_fee_address.expr(),
-                AccountFieldTag::CodeSize,
-                Expression::Constant(F::from(v2_codesize as u64)),
-                Expression::Constant(F::from(v1_codesize as u64)),
-                None,
-            );
-            // State changes
-            for (slot, value) in [
-                (*l1_gas_price_oracle::IS_CURIE_SLOT, eth_types::Word::from(1u64)),
-                (*l1_gas_price_oracle::L1_BLOB_BASEFEE_SLOT, eth_types::Word::from(1u64)),
-                (
-                    *l1_gas_price_oracle::COMMIT_SCALAR_SLOT,
-                    *l1_gas_price_oracle::INITIAL_COMMIT_SCALAR,
-                ),
-                (
-                    *l1_gas_price_oracle::BLOB_SCALAR_SLOT,
-                    *l1_gas_price_oracle::INITIAL_BLOB_SCALAR,
-                ),
-            ] {
-                cb.account_storage_write(
-                    l1_fee_address.expr(),
-                    cb.word_rlc_constant(slot),
-                    cb.word_rlc_constant(value),
-                    0.expr(),
-                    0.expr(),
-                    0.expr(),
-                    None,
-                );
-            }
-        });
-
         // Note that rw_counter starts at 1
         let is_empty_block =
             IsZeroGadget::construct(cb, cb.curr.state.rw_counter.clone().expr() - 1.expr());
-        // If the block is empty, we do 0 rw_table lookups
-        // If the block is not empty, we will do 1 call_context lookup
-        // and add 1 withdraw_root lookup add 1 sequencer_root lookup
-        let total_rws = not::expr(is_empty_block.expr())
-            * (cb.curr.state.rw_counter.clone().expr() - 1.expr() + 1.expr())
-            + 2.expr()
-            + is_curie_fork_block.expr() * 7.expr();
+        // If the block has txs, we will do 1 call_context lookup, none if it only has SystemCall
+        // steps or no step at all.
+        // Then we add 1 withdraw_root lookup add 1 sequencer_root lookup
+        let total_rws = cb.curr.state.rw_counter.clone().expr() - 1.expr()
+            + not::expr(total_txs_is_zero.expr())
+            + 2.expr();
 
         // 1. Constraint total_rws and total_txs witness values depending on the empty
         // block case.
@@ -198,7 +120,7 @@ impl<F: Field> ExecutionGadget<F> for EndBlockGadget<F> {
             // 1a.
             cb.require_equal("total_txs is 0 in empty block", total_txs.expr(), 0.expr());
         });
-        cb.condition(not::expr(is_empty_block.expr()), |cb| {
+        cb.condition(not::expr(total_txs_is_zero.expr()), |cb| {
             // 1b. total_txs matches the tx_id that corresponds to the final step.
             cb.call_context_lookup(0.expr(), None, CallContextFieldTag::TxId, total_txs.expr());
         });
@@ -281,8 +203,8 @@ impl<F: Field> ExecutionGadget<F> for EndBlockGadget<F> {
             phase2_sequencer_root_prev,
             total_txs,
             total_txs_is_max_txs,
+            total_txs_is_zero,
             is_empty_block,
-            is_curie_fork_block,
             withdraw_root_assigned: Default::default(),
             sequencer_root_assigned: Default::default(),
         }
@@ -310,21 +232,9 @@ impl<F: Field> ExecutionGadget<F> for EndBlockGadget<F> {
             .assign(region, offset, Value::known(total_txs))?;
         self.total_txs_is_max_txs
             .assign(region, offset, total_txs, max_txs)?;
+        self.total_txs_is_zero.assign(region, offset, total_txs)?;
         let max_txs_assigned = self.max_txs.assign(region, offset, Value::known(max_txs))?;
 
-        let last_block_number = block
-            .context
-            .ctxs
-            .last_key_value()
-            .map(|(_, b)| b.number)
-            .unwrap_or_default();
-        let is_curie = bus_mapping::circuit_input_builder::curie::is_curie_fork_block(
-            block.chain_id,
-            last_block_number.as_u64(),
-        );
-        self.is_curie_fork_block
-            .assign(region, offset, Value::known(F::from(is_curie as u64)))?;
-
         let withdraw_root = self.phase2_withdraw_root.assign(
             region,
             offset,
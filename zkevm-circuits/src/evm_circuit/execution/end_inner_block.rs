@@ -3,6 +3,7 @@ use crate::{
         execution::ExecutionGadget,
         step::ExecutionState,
         util::{
+            common_gadget::SystemCallForksGadget,
             constraint_builder::{
                 ConstrainBuilderCommon, EVMConstraintBuilder, StepStateTransition, Transition,
             },
@@ -32,6 +33,8 @@ pub(crate) struct EndInnerBlockGadget<F> {
     cum_num_txs: Cell<F>,
     /// Gadget used to check if the inner block was empty.
     is_empty_block: IsZeroGadget<F>,
+    /// Whether the next inner block starts with a SystemCall step.
+    next_system_call: SystemCallForksGadget<F>,
     _marker: PhantomData<F>,
 }
 
@@ -86,12 +89,22 @@ impl<F: Field> ExecutionGadget<F> for EndInnerBlockGadget<F> {
                 cb.curr.state.block_number.expr(),
             );
         });
-        cb.condition(not::expr(next_step_padding), |cb| {
+        let next_system_call = cb.condition(not::expr(next_step_padding), |cb| {
             cb.require_equal(
                 "block number increments if there are more inner blocks",
                 cb.next.state.block_number.expr(),
                 cb.curr.state.block_number.expr() + 1.expr(),
             );
+
+            let next_system_call =
+                SystemCallForksGadget::construct(cb, cb.curr.state.block_number.expr() + 1.expr());
+            cb.require_equal(
+                "next block starts with SystemCall iff a system contract fork is active",
+                cb.next
+                    .execution_state_selector([ExecutionState::SystemCall]),
+                next_system_call.expr(),
+            );
+            next_system_call
         });
 
         cb.require_step_state_transition(StepStateTransition {
@@ -107,6 +120,7 @@ impl<F: Field> ExecutionGadget<F> for EndInnerBlockGadget<F> {
             num_txs,
             cum_num_txs,
             is_empty_block,
+            next_system_call,
             _marker: PhantomData,
         }
     }
@@ -116,7 +130,7 @@ impl<F: Field> ExecutionGadget<F> for EndInnerBlockGadget<F> {
         region: &mut CachedRegion<'_, '_, F>,
         offset: usize,
         block: &Block,
-        tx: &Transaction,
+        _: &Transaction,
         _: &Call,
         step: &ExecStep,
     ) -> Result<(), Error> {
@@ -131,14 +145,17 @@ impl<F: Field> ExecutionGadget<F> for EndInnerBlockGadget<F> {
             .filter(|t| t.block_number <= step.block_num)
             .count();
 
+        // the steps of the empty blocks leading the chunk come before any tx
         self.last_tx_id
-            .assign(region, offset, Value::known(F::from(tx.id as u64)))?;
+            .assign(region, offset, Value::known(F::from(cum_num_txs as u64)))?;
         self.num_txs
             .assign(region, offset, Value::known(F::from(num_txs as u64)))?;
         self.cum_num_txs
             .assign(region, offset, Value::known(F::from(cum_num_txs as u64)))?;
         self.is_empty_block
             .assign(region, offset, F::from(num_txs as u64))?;
+        self.next_system_call
+            .assign(region, offset, block.chain_id, step.block_num + 1)?;
 
         Ok(())
     }
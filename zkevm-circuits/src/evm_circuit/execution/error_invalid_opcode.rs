@@ -4,21 +4,25 @@ use crate::{
         step::ExecutionState,
         table::{FixedTableTag, Lookup},
         util::{
-            common_gadget::CommonErrorGadget, constraint_builder::EVMConstraintBuilder,
+            common_gadget::{CommonErrorGadget, HardforkGadget},
+            constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
             CachedRegion, Cell,
         },
         witness::{Block, Call, ExecStep, Transaction},
     },
     util::Field,
 };
-use gadgets::util::Expr;
+use eth_types::{evm_types::OpcodeId, forks::HardforkId};
+use gadgets::util::{not, Expr};
 use halo2_proofs::{circuit::Value, plonk::Error};
 
 /// Gadget for invalid opcodes. It verifies by a fixed lookup for
-/// ResponsibleOpcode.
+/// ResponsibleOpcode, or for the opcodes of curie, that curie is not active yet.
 #[derive(Clone, Debug)]
 pub(crate) struct ErrorInvalidOpcodeGadget<F> {
     opcode: Cell<F>,
+    is_curie_opcode: Cell<F>,
+    curie: HardforkGadget<F>,
     common_error_gadget: CommonErrorGadget<F>,
 }
 
@@ -29,22 +33,41 @@ impl<F: Field> ExecutionGadget<F> for ErrorInvalidOpcodeGadget<F> {
 
     fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
         let opcode = cb.query_cell();
-        cb.add_lookup(
-            "Responsible opcode lookup",
-            Lookup::Fixed {
-                tag: FixedTableTag::ResponsibleOpcode.expr(),
-                values: [
-                    Self::EXECUTION_STATE.as_u64().expr(),
-                    opcode.expr(),
-                    0.expr(),
-                ],
-            },
-        );
+        let is_curie_opcode = cb.query_bool();
+        cb.condition(not::expr(is_curie_opcode.expr()), |cb| {
+            cb.add_lookup(
+                "Responsible opcode lookup",
+                Lookup::Fixed {
+                    tag: FixedTableTag::ResponsibleOpcode.expr(),
+                    values: [
+                        Self::EXECUTION_STATE.as_u64().expr(),
+                        opcode.expr(),
+                        0.expr(),
+                    ],
+                },
+            );
+        });
+
+        // the opcodes introduced by curie are invalid before it, see `HardforkId::opcodes`
+        let curie =
+            HardforkGadget::construct(cb, HardforkId::Curie, cb.curr.state.block_number.expr());
+        cb.condition(is_curie_opcode.expr(), |cb| {
+            cb.require_zero(
+                "opcode introduced by curie",
+                HardforkId::Curie
+                    .opcodes()
+                    .iter()
+                    .fold(1.expr(), |acc, op| acc * (opcode.expr() - op.expr())),
+            );
+            cb.require_equal("curie is not active", curie.is_before_fork.expr(), 1.expr());
+        });
 
         let common_error_gadget = CommonErrorGadget::construct(cb, opcode.expr(), 2.expr());
 
         Self {
             opcode,
+            is_curie_opcode,
+            curie,
             common_error_gadget,
         }
     }
@@ -54,15 +77,22 @@ impl<F: Field> ExecutionGadget<F> for ErrorInvalidOpcodeGadget<F> {
         region: &mut CachedRegion<'_, '_, F>,
         offset: usize,
         block: &Block,
-        _: &Transaction,
+        tx: &Transaction,
         call: &Call,
         step: &ExecStep,
     ) -> Result<(), Error> {
-        let opcode = step.opcode.unwrap().as_u64();
+        let opcode = step.opcode.unwrap();
         self.opcode
-            .assign(region, offset, Value::known(F::from(opcode)))?;
-
-        log::debug!("ErrorInvalidOpcode - opcode = {}", opcode);
+            .assign(region, offset, Value::known(F::from(opcode.as_u64())))?;
+        self.is_curie_opcode.assign(
+            region,
+            offset,
+            Value::known(F::from(!matches!(opcode, OpcodeId::INVALID(_)))),
+        )?;
+        self.curie
+            .assign(region, offset, block.chain_id, tx.block_number)?;
+
+        log::debug!("ErrorInvalidOpcode - opcode = {}", opcode.as_u64());
 
         self.common_error_gadget
             .assign(region, offset, block, call, step, 2)?;
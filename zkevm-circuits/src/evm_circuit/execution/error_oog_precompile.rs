@@ -23,7 +23,7 @@ use halo2_proofs::{circuit::Value, plonk::Error};
 #[derive(Clone, Debug)]
 pub(crate) struct ErrorOOGPrecompileGadget<F> {
     precompile_addr: Cell<F>,
-    addr_bits: BinaryNumberGadget<F, 4>,
+    addr_bits: BinaryNumberGadget<F, 9>,
     call_data_length: Cell<F>,
     is_root: Cell<F>,
     n_pairs: ConstantDivisionGadget<F, N_BYTES_MEMORY_WORD_SIZE>,
@@ -101,6 +101,10 @@ impl<F: Field> ExecutionGadget<F> for ErrorOOGPrecompileGadget<F> {
                 GasCost::PRECOMPILE_BN256PAIRING.expr()
                     + n_pairs.quotient() * GasCost::PRECOMPILE_BN256PAIRING_PER_PAIR.expr(),
             ),
+            (
+                addr_bits.value_equals(PrecompileCalls::P256Verify),
+                GasCost::PRECOMPILE_P256VERIFY.expr(),
+            ),
         ];
 
         cb.require_equal(
@@ -166,8 +170,8 @@ impl<F: Field> ExecutionGadget<F> for ErrorOOGPrecompileGadget<F> {
             offset,
             Value::known(precompile_addr.to_scalar().unwrap()),
         )?;
-        self.addr_bits
-            .assign(region, offset, precompile_addr.to_fixed_bytes()[19])?;
+        let precompile_call: PrecompileCalls = precompile_addr.into();
+        self.addr_bits.assign(region, offset, precompile_call)?;
 
         // call_data_length
         self.call_data_length.assign(
@@ -193,7 +197,6 @@ impl<F: Field> ExecutionGadget<F> for ErrorOOGPrecompileGadget<F> {
         )?;
 
         // required_gas
-        let precompile_call: PrecompileCalls = precompile_addr.to_fixed_bytes()[19].into();
         let required_gas = match precompile_call {
             PrecompileCalls::Bn128Pairing => {
                 precompile_call.base_gas_cost().as_u64()
@@ -209,9 +212,10 @@ impl<F: Field> ExecutionGadget<F> for ErrorOOGPrecompileGadget<F> {
                 precompile_call.base_gas_cost().as_u64()
                     + n_words * GasCost::PRECOMPILE_SHA256_PER_WORD.as_u64()
             }
-            PrecompileCalls::Bn128Add | PrecompileCalls::Bn128Mul | PrecompileCalls::Ecrecover => {
-                precompile_call.base_gas_cost().as_u64()
-            }
+            PrecompileCalls::Bn128Add
+            | PrecompileCalls::Bn128Mul
+            | PrecompileCalls::Ecrecover
+            | PrecompileCalls::P256Verify => precompile_call.base_gas_cost().as_u64(),
             _ => unreachable!(),
         };
 
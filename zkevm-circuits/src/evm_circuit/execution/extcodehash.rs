@@ -1,3 +1,5 @@
+#[cfg(feature = "scroll")]
+use crate::evm_circuit::util::{math_gadget::IsZeroGadget, not};
 use crate::{
     evm_circuit::{
         execution::ExecutionGadget,
@@ -26,6 +28,10 @@ pub(crate) struct ExtcodehashGadget<F> {
     reversion_info: ReversionInfo<F>,
     is_warm: Cell<F>,
     code_hash: Cell<F>,
+    #[cfg(feature = "scroll")]
+    poseidon_code_hash: Cell<F>,
+    #[cfg(feature = "scroll")]
+    not_exists: IsZeroGadget<F>,
 }
 
 impl<F: Field> ExecutionGadget<F> for ExtcodehashGadget<F> {
@@ -53,7 +59,7 @@ impl<F: Field> ExecutionGadget<F> for ExtcodehashGadget<F> {
         let code_hash = cb.query_cell_phase2();
         // For non-existing accounts the code_hash must be 0 in the rw_table.
         cb.account_read(
-            address,
+            address.expr(),
             if cfg!(feature = "scroll") {
                 AccountFieldTag::KeccakCodeHash
             } else {
@@ -61,6 +67,22 @@ impl<F: Field> ExecutionGadget<F> for ExtcodehashGadget<F> {
             },
             code_hash.expr(),
         );
+        // The keccak code hash of an existing account must be the one of its code in the
+        // bytecode table.
+        #[cfg(feature = "scroll")]
+        let (poseidon_code_hash, not_exists) = {
+            let poseidon_code_hash = cb.query_cell_phase2();
+            cb.account_read(
+                address.expr(),
+                AccountFieldTag::CodeHash,
+                poseidon_code_hash.expr(),
+            );
+            let not_exists = IsZeroGadget::construct(cb, code_hash.expr());
+            cb.condition(not::expr(not_exists.expr()), |cb| {
+                cb.bytecode_code_hashes(poseidon_code_hash.expr(), code_hash.expr());
+            });
+            (poseidon_code_hash, not_exists)
+        };
         cb.stack_push(code_hash.expr());
 
         let gas_cost = select::expr(
@@ -87,6 +109,10 @@ impl<F: Field> ExecutionGadget<F> for ExtcodehashGadget<F> {
             reversion_info,
             is_warm,
             code_hash,
+            #[cfg(feature = "scroll")]
+            poseidon_code_hash,
+            #[cfg(feature = "scroll")]
+            not_exists,
         }
     }
 
@@ -121,6 +147,14 @@ impl<F: Field> ExecutionGadget<F> for ExtcodehashGadget<F> {
         let code_hash = block.rws[step.rw_indices[5]].account_value_pair().0;
         self.code_hash
             .assign(region, offset, region.word_rlc(code_hash))?;
+        #[cfg(feature = "scroll")]
+        {
+            let poseidon_code_hash = block.rws[step.rw_indices[6]].account_value_pair().0;
+            self.poseidon_code_hash
+                .assign(region, offset, region.code_hash(poseidon_code_hash))?;
+            self.not_exists
+                .assign_value(region, offset, region.word_rlc(code_hash))?;
+        }
 
         Ok(())
     }
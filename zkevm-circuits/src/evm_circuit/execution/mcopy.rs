@@ -3,7 +3,7 @@ use crate::{
         param::N_BYTES_MEMORY_WORD_SIZE,
         step::ExecutionState,
         util::{
-            common_gadget::SameContextGadget,
+            common_gadget::{HardforkGadget, SameContextGadget},
             constraint_builder::{
                 ConstrainBuilderCommon, EVMConstraintBuilder, StepStateTransition, Transition,
             },
@@ -18,7 +18,7 @@ use crate::{
     util::{Expr, Field},
 };
 use bus_mapping::{circuit_input_builder::CopyDataType, evm::OpcodeId};
-use eth_types::evm_types::GasCost;
+use eth_types::{evm_types::GasCost, forks::HardforkId};
 use gadgets::ToScalar;
 // use gadgets::util::Expr;
 use halo2_proofs::{circuit::Value, plonk::Error};
@@ -29,6 +29,7 @@ use super::ExecutionGadget;
 #[derive(Clone, Debug)]
 pub(crate) struct MCopyGadget<F> {
     same_context: SameContextGadget<F>,
+    curie: HardforkGadget<F>,
     memory_src_address: MemoryAddressGadget<F>,
     memory_dest_address: MemoryAddressGadget<F>,
     copy_rwc_inc: Cell<F>,
@@ -46,6 +47,11 @@ impl<F: Field> ExecutionGadget<F> for MCopyGadget<F> {
     fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
         let opcode = cb.query_cell();
 
+        // an invalid opcode before curie, see `ErrorInvalidOpcodeGadget`
+        let curie =
+            HardforkGadget::construct(cb, HardforkId::Curie, cb.curr.state.block_number.expr());
+        cb.require_zero("MCOPY is defined since curie", curie.is_before_fork.expr());
+
         let src_offset = cb.query_cell_phase2();
         let dest_offset = cb.query_cell_phase2();
         let length = cb.query_word_rlc();
@@ -129,6 +135,7 @@ impl<F: Field> ExecutionGadget<F> for MCopyGadget<F> {
 
         Self {
             same_context,
+            curie,
             memory_src_address,
             memory_dest_address,
             copy_rwc_inc,
@@ -142,11 +149,13 @@ impl<F: Field> ExecutionGadget<F> for MCopyGadget<F> {
         region: &mut CachedRegion<'_, '_, F>,
         offset: usize,
         block: &Block,
-        _transaction: &Transaction,
+        transaction: &Transaction,
         _call: &Call,
         step: &ExecStep,
     ) -> Result<(), Error> {
         self.same_context.assign_exec_step(region, offset, step)?;
+        self.curie
+            .assign(region, offset, block.chain_id, transaction.block_number)?;
 
         let [dest_offset, src_offset, length] =
             [0, 1, 2].map(|idx| block.rws[step.rw_indices[idx]].stack_value());
@@ -3,7 +3,7 @@ use bus_mapping::{
     circuit_input_builder::{N_BYTES_PER_PAIR, N_PAIRING_PER_OP},
     precompile::{EcPairingError, PrecompileAuxData, PrecompileCalls},
 };
-use eth_types::evm_types::GasCost;
+use eth_types::{evm_types::GasCost, forks::HardforkId};
 use gadgets::util::{and, not, or, select, Expr};
 use gadgets::ToScalar;
 use halo2_proofs::{circuit::Value, plonk::Error};
@@ -13,9 +13,9 @@ use crate::{
         execution::ExecutionGadget,
         step::ExecutionState,
         util::{
-            common_gadget::RestoreContextGadget,
+            common_gadget::{HardforkGadget, RestoreContextGadget},
             constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
-            math_gadget::{BinaryNumberGadget, IsZeroGadget, LtGadget},
+            math_gadget::{ConstantDivisionGadget, IsZeroGadget, LtGadget},
             rlc, CachedRegion, Cell,
         },
     },
@@ -23,10 +23,12 @@ use crate::{
     witness::{Block, Call, ExecStep, Transaction},
 };
 
-/// Note: input_len ∈ { 0, 192, 384, 576, 768 } if valid.
+/// Note: input_len is a multiple of 192 if valid, and at most 768 (4 pairs) before the large
+/// ecPairing fork.
 ///
-/// Note: input bytes are padded to 768 bytes within our zkEVM implementation to standardise a
-/// pairing operation, such that each pairing op has 4 pairs: [(G1, G2); 4].
+/// Note: the ECC circuit left-pads the input with zero pairs up to the number of pairs it supports
+/// per pairing op, see `PrecompileEcParams::ec_pairing_pairs`, which keeps the RLC of the input
+/// bytes unchanged. A call with more pairs than that can not be proven.
 #[derive(Clone, Debug)]
 pub struct EcPairingGadget<F> {
     // Random linear combination of input bytes to the precompile ecPairing call.
@@ -40,23 +42,13 @@ pub struct EcPairingGadget<F> {
     // successful.
     output: Cell<F>,
 
-    // Verify invalidity of input bytes. We basically check `or(1, 2)` where:
-    // 1. input_len > 4 * 192
-    // 2. input_len % 192 != 0
-    input_is_zero: IsZeroGadget<F>,
-
-    // call_data_len must less than 2^32.
-    input_lt_769: LtGadget<F, 4>,
-    input_mod_192: Cell<F>,
-    input_div_192: Cell<F>,
-    input_mod_192_lt: LtGadget<F, 1>,
+    // Verify invalidity of input bytes, i.e. input_len % 192 != 0. The quotient is the number of
+    // pairs provided through EVM input. call_data_len must less than 2^32.
+    input_div_192: ConstantDivisionGadget<F, 4>,
     input_mod_192_is_zero: IsZeroGadget<F>,
-
-    /// Number of pairs provided through EVM input. Since a maximum of 4 pairs can be supplied from
-    /// EVM, we need 3 binary bits for a max value of [1, 0, 0].
-    n_pairs: Cell<F>,
-    n_pairs_cmp: BinaryNumberGadget<F, 3>,
-    rand_pow_64: Cell<F>,
+    // Before the large ecPairing fork, more than 4 pairs is invalid as in bernoulli.
+    large_ec_pairing: HardforkGadget<F>,
+    input_lt_769: LtGadget<F, 4>,
 
     is_success: Cell<F>,
     callee_address: Cell<F>,
@@ -81,9 +73,6 @@ impl<F: Field> ExecutionGadget<F> for EcPairingGadget<F> {
             cb.query_bool(),
         );
 
-        let n_pairs = cb.query_cell();
-        let n_pairs_cmp = BinaryNumberGadget::construct(cb, n_pairs.expr());
-
         let [is_success, callee_address, is_root, call_data_offset, call_data_length, return_data_offset, return_data_length] =
             [
                 CallContextFieldTag::IsSuccess,
@@ -102,11 +91,34 @@ impl<F: Field> ExecutionGadget<F> for EcPairingGadget<F> {
             cb.execution_state().precompile_base_gas_cost().expr(),
         );
 
+        // q == len(input) // 192, r == len(input) % 192
+        let input_div_192 =
+            ConstantDivisionGadget::construct(cb, call_data_length.expr(), N_BYTES_PER_PAIR as u64);
+        let input_mod_192_is_zero = IsZeroGadget::construct(cb, input_div_192.remainder());
+        let n_pairs = input_div_192.quotient();
+        let large_ec_pairing = HardforkGadget::construct(
+            cb,
+            HardforkId::LargeEcPairing,
+            cb.curr.state.block_number.expr(),
+        );
+        let input_lt_769 = LtGadget::construct(
+            cb,
+            call_data_length.expr(),
+            (N_PAIRING_PER_OP * N_BYTES_PER_PAIR + 1).expr(),
+        );
+        let is_valid_len = and::expr([
+            input_mod_192_is_zero.expr(),
+            or::expr([
+                not::expr(large_ec_pairing.is_before_fork.expr()),
+                input_lt_769.expr(),
+            ]),
+        ]);
+
         // all gas sent to this call will be consumed if `is_success == false`.
         let gas_cost = select::expr(
             is_success.expr(),
             GasCost::PRECOMPILE_BN256PAIRING.expr()
-                + n_pairs.expr() * GasCost::PRECOMPILE_BN256PAIRING_PER_PAIR.expr(),
+                + n_pairs * GasCost::PRECOMPILE_BN256PAIRING_PER_PAIR.expr(),
             cb.curr.state.gas_left.expr(),
         );
 
@@ -118,151 +130,42 @@ impl<F: Field> ExecutionGadget<F> for EcPairingGadget<F> {
         );
 
         //////////////////////////////// INVALID BEGIN ////////////////////////////////
-        let input_is_zero = IsZeroGadget::construct(cb, call_data_length.expr());
-        let input_lt_769 = LtGadget::construct(cb, call_data_length.expr(), 769.expr());
-        let (input_mod_192, input_div_192, input_mod_192_lt, input_mod_192_is_zero) = cb.condition(
-            and::expr([not::expr(input_is_zero.expr()), input_lt_769.expr()]),
-            |cb| {
-                // r == len(input) % 192
-                let input_mod_192 = cb.query_byte();
-                // r < 192
-                let input_mod_192_lt = LtGadget::construct(cb, input_mod_192.expr(), 192.expr());
-                cb.require_equal("len(input) % 192 < 192", input_mod_192_lt.expr(), 1.expr());
-                // q == len(input) // 192
-                let input_div_192 = cb.query_cell();
-                cb.require_in_set(
-                    "len(input) // 192 ∈ { 0, 1, 2, 3, 4 }",
-                    input_div_192.expr(),
-                    vec![0.expr(), 1.expr(), 2.expr(), 3.expr(), 4.expr()],
-                );
-                // q * 192 + r == call_data_length
-                cb.require_equal(
-                    "q * 192 + r == len(input)",
-                    input_div_192.expr() * 192.expr() + input_mod_192.expr(),
-                    call_data_length.expr(),
-                );
-                let input_mod_192_is_zero = IsZeroGadget::construct(cb, input_mod_192.expr());
-                (
-                    input_mod_192,
-                    input_div_192,
-                    input_mod_192_lt,
-                    input_mod_192_is_zero,
-                )
-            },
-        );
-
-        // when input is zero then input mod_192 is zero
-        cb.condition(input_is_zero.expr(), |cb| {
-            cb.require_true(
-                "when input is zero, then input mod192 is zero",
-                input_mod_192_is_zero.expr(),
+        cb.condition(not::expr(is_valid_len.clone()), |cb| {
+            cb.require_equal(
+                "len(input) is invalid => is_success == false",
+                is_success.expr(),
+                false.expr(),
             );
+            cb.require_zero("pairing check == 0", output.expr());
         });
-
-        cb.condition(
-            // (len(input) > 768) || (len(input) % 192 != 0)
-            or::expr([
-                not::expr(input_lt_769.expr()),
-                not::expr(input_mod_192_is_zero.expr()),
-            ]),
-            |cb| {
-                cb.require_equal(
-                    "len(input) is invalid => is_success == false",
-                    is_success.expr(),
-                    false.expr(),
-                );
-                cb.require_zero("pairing check == 0", output.expr());
-            },
-        );
         //////////////////////////////// INVALID END //////////////////////////////////
 
         ///////////////////////////////// VALID BEGIN /////////////////////////////////
-        let rand_pow_64 = cb.condition(
-            // (len(input) == 0) || ((len(input) <= 768) && (len(input) % 192 == 0))
-            or::expr([
-                input_is_zero.expr(),
-                and::expr([input_lt_769.expr(), input_mod_192_is_zero.expr()]),
-            ]),
-            |cb| {
-                let rand_pow_64 = cb.query_cell_phase2();
-                let (rand_pow_192, rand_pow_384, rand_pow_576) = {
-                    let rand_pow_128 = rand_pow_64.expr() * rand_pow_64.expr();
-                    let rand_pow_192 = rand_pow_128.expr() * rand_pow_64.expr();
-                    let rand_pow_384 = rand_pow_192.expr() * rand_pow_192.expr();
-                    let rand_pow_576 = rand_pow_384.expr() * rand_pow_192.expr();
-                    (rand_pow_192, rand_pow_384, rand_pow_576)
-                };
-                cb.pow_of_rand_lookup(64.expr(), rand_pow_64.expr());
-
-                // RLC(inputs) that was processed in the ECC Circuit.
-                let ecc_circuit_input_rlc = select::expr(
-                    n_pairs_cmp.value_equals(0usize),
-                    0.expr(),
-                    select::expr(
-                        n_pairs_cmp.value_equals(1usize),
-                        input_bytes_rlc.expr() * rand_pow_576.expr(), /* 576 bytes padded */
-                        select::expr(
-                            n_pairs_cmp.value_equals(2usize),
-                            input_bytes_rlc.expr() * rand_pow_384.expr(), /* 384 bytes padded */
-                            select::expr(
-                                n_pairs_cmp.value_equals(3usize),
-                                input_bytes_rlc.expr() * rand_pow_192.expr(), /* 192 bytes padded */
-                                input_bytes_rlc.expr(),                       /* 0 bytes padded */
-                            ),
-                        ),
-                    ),
-                );
-                cb.condition(n_pairs_cmp.value_equals(0usize), |cb| {
-                    cb.require_zero(
-                        "ecPairing: n_pairs == 0 => evm input == 0",
-                        input_bytes_rlc.expr(),
-                    );
-                });
-
-                // Covers the following cases:
-                // 1. pairing == 1 (where input_rlc == 0, i.e. len(input) == 0).
-                // 2. pairing == 1 (where input_rlc != 0, i.e. len(input) != 0).
-                // 3. pairing == 0 (both valid and invalid inputs)
-                //     - G1 point not on curve
-                //     - G2 point not on curve
-                //     - G1 co-ord is not in canonical form
-                //     - G2 co-ord is not in canonical form
-                //     - G1, G2 both valid
-                cb.ecc_table_lookup(
-                    u64::from(PrecompileCalls::Bn128Pairing).expr(),
-                    is_success.expr(),
-                    0.expr(),
-                    0.expr(),
-                    0.expr(),
-                    0.expr(),
-                    ecc_circuit_input_rlc.expr(),
-                    output.expr(),
-                    0.expr(),
-                );
-
-                // since len(input) was valid, we know that we are left with 3 scenarios:
-                // 1. pairing check == true
-                // 2. pairing check == false
-                // 3. invalid inputs:
-                //      - invalid field element
-                //      - point not on G1
-                //      - point not on G2
-                //
-                // In all the above, we know that len(input) % 192 == 0 and len(input) <= 768
-                cb.require_equal(
-                    "ecPairing: n_pairs * N_BYTES_PER_PAIR == call_data_length",
-                    n_pairs.expr() * N_BYTES_PER_PAIR.expr(),
-                    call_data_length.expr(),
-                );
-                cb.require_in_set(
-                    "ecPairing: input_len ∈ { 0, 192, 384, 576, 768 }",
-                    call_data_length.expr(),
-                    vec![0.expr(), 192.expr(), 384.expr(), 576.expr(), 768.expr()],
-                );
-
-                rand_pow_64
-            },
-        );
+        cb.condition(is_valid_len, |cb| {
+            // Covers the following cases:
+            // 1. pairing == 1 (where input_rlc == 0, i.e. len(input) == 0).
+            // 2. pairing == 1 (where input_rlc != 0, i.e. len(input) != 0).
+            // 3. pairing == 0 (both valid and invalid inputs)
+            //     - G1 point not on curve
+            //     - G2 point not on curve
+            //     - G1 co-ord is not in canonical form
+            //     - G2 co-ord is not in canonical form
+            //     - G1, G2 both valid
+            //
+            // The ECC circuit pads the input on the left, so the RLC of the EVM input is also
+            // the RLC of the input that was processed in the ECC circuit.
+            cb.ecc_table_lookup(
+                u64::from(PrecompileCalls::Bn128Pairing).expr(),
+                is_success.expr(),
+                0.expr(),
+                0.expr(),
+                0.expr(),
+                0.expr(),
+                input_bytes_rlc.expr(),
+                output.expr(),
+                0.expr(),
+            );
+        });
         ///////////////////////////////// VALID END ///////////////////////////////////
 
         let restore_context = super::gen_restore_context(
@@ -280,16 +183,10 @@ impl<F: Field> ExecutionGadget<F> for EcPairingGadget<F> {
 
             output,
 
-            input_is_zero,
-            input_lt_769,
-            input_mod_192,
             input_div_192,
-            input_mod_192_lt,
             input_mod_192_is_zero,
-
-            n_pairs,
-            n_pairs_cmp,
-            rand_pow_64,
+            large_ec_pairing,
+            input_lt_769,
 
             is_success,
             callee_address,
@@ -307,45 +204,34 @@ impl<F: Field> ExecutionGadget<F> for EcPairingGadget<F> {
         region: &mut CachedRegion<'_, '_, F>,
         offset: usize,
         block: &Block,
-        _transaction: &Transaction,
+        transaction: &Transaction,
         call: &Call,
         step: &ExecStep,
     ) -> Result<(), Error> {
+        self.large_ec_pairing
+            .assign(region, offset, block.chain_id, transaction.block_number)?;
+        self.input_lt_769.assign(
+            region,
+            offset,
+            F::from(call.call_data_length),
+            F::from((N_PAIRING_PER_OP * N_BYTES_PER_PAIR + 1) as u64),
+        )?;
         if let Some(PrecompileAuxData::EcPairing(res_aux_data)) = step.aux_data.clone() {
             let keccak_rand = region.challenges().keccak_input();
 
             // len(input) related assignment.
-            self.input_is_zero
-                .assign(region, offset, F::from(call.call_data_length))?;
             log::trace!(
                 "assign ec pairing exec step: calldata_len = {}",
                 call.call_data_length
             );
-            self.input_lt_769.assign(
-                region,
-                offset,
-                F::from(call.call_data_length),
-                F::from(769),
-            )?;
-            let (input_div_192, input_mod_192) = (
-                call.call_data_length / (N_BYTES_PER_PAIR as u64),
-                call.call_data_length % (N_BYTES_PER_PAIR as u64),
-            );
-            self.input_div_192
-                .assign(region, offset, Value::known(F::from(input_div_192)))?;
-            self.input_mod_192
-                .assign(region, offset, Value::known(F::from(input_mod_192)))?;
-            self.input_mod_192_lt
-                .assign(region, offset, F::from(input_mod_192), F::from(192))?;
+            let (_, input_mod_192) =
+                self.input_div_192
+                    .assign(region, offset, call.call_data_length as u128)?;
             self.input_mod_192_is_zero
-                .assign(region, offset, F::from(input_mod_192))?;
+                .assign(region, offset, F::from(input_mod_192 as u64))?;
 
             match *res_aux_data {
                 Ok(aux_data) => {
-                    debug_assert!(
-                        call.call_data_length <= (N_PAIRING_PER_OP * N_BYTES_PER_PAIR) as u64,
-                        "len(input) > 768"
-                    );
                     debug_assert!(
                         call.call_data_length % (N_BYTES_PER_PAIR as u64) == 0,
                         "len(input) % 192 != 0"
@@ -388,16 +274,6 @@ impl<F: Field> ExecutionGadget<F> for EcPairingGadget<F> {
                                 .expect("ecPairing: output in {0, 1}"),
                         ),
                     )?;
-                    // Number of pairs provided in the EVM call.
-                    let n_pairs = (call.call_data_length as usize) / N_BYTES_PER_PAIR;
-                    self.n_pairs
-                        .assign(region, offset, Value::known(F::from(n_pairs as u64)))?;
-                    self.n_pairs_cmp.assign(region, offset, n_pairs)?;
-                    self.rand_pow_64.assign(
-                        region,
-                        offset,
-                        keccak_rand.map(|r| r.pow([64, 0, 0, 0])),
-                    )?;
                 }
                 Err(EcPairingError::InvalidInputLen(input_bytes)) => {
                     debug_assert_eq!(
@@ -406,8 +282,8 @@ impl<F: Field> ExecutionGadget<F> for EcPairingGadget<F> {
                         "len(input) != call_data_length"
                     );
                     debug_assert!(
-                        (call.call_data_length > (N_PAIRING_PER_OP * N_BYTES_PER_PAIR) as u64)
-                            || (call.call_data_length % (N_BYTES_PER_PAIR as u64) != 0),
+                        call.call_data_length % (N_BYTES_PER_PAIR as u64) != 0
+                            || call.call_data_length > (N_PAIRING_PER_OP * N_BYTES_PER_PAIR) as u64,
                         "len(input) is expected to be invalid",
                     );
                     // Consider only call_data_length bytes for EVM input.
@@ -468,14 +344,22 @@ impl<F: Field> ExecutionGadget<F> for EcPairingGadget<F> {
 #[cfg(test)]
 mod test {
     use bus_mapping::{
-        circuit_input_builder::CircuitsParams,
+        circuit_input_builder::{CircuitsParams, PrecompileEcParams},
         evm::{OpcodeId, PrecompileCallArgs},
         precompile::PrecompileCalls,
     };
-    use eth_types::{bytecode, evm_types::GasCost, word, ToWord, Word};
+    use eth_types::{
+        bytecode,
+        evm_types::GasCost,
+        forks::{ChainSpec, ForkCondition, HardforkId, MORPH_DEVNET_CHAIN_ID},
+        word, Bytecode, ToWord, Word,
+    };
     use halo2_proofs::halo2curves::bn256::{G1Affine, G2Affine};
     use itertools::Itertools;
-    use mock::{test_ctx::helpers::account_0_code_wallet_0_no_code, TestContext, MOCK_WALLETS};
+    use mock::{
+        test_ctx::helpers::account_0_code_wallet_0_no_code, TestContext, MOCK_CHAIN_ID,
+        MOCK_WALLETS,
+    };
     use rayon::iter::{ParallelBridge, ParallelIterator};
     use std::sync::LazyLock;
 
@@ -504,18 +388,6 @@ mod test {
                 address: PrecompileCalls::Bn128Pairing.address().to_word(),
                 ..Default::default()
             },
-            #[cfg(feature = "scroll")]
-            PrecompileCallArgs {
-                name: "ecPairing (invalid): all zero bytes, len(input) == 5 * 192",
-                setup_code: bytecode! {},
-                call_data_offset: 0x00.into(),
-                call_data_length: 0x3C0.into(),
-                ret_offset: 0x3C0.into(),
-                ret_size: 0x20.into(),
-                value: 1.into(),
-                address: PrecompileCalls::Bn128Pairing.address().to_word(),
-                ..Default::default()
-            },
             PrecompileCallArgs {
                 name: "ecPairing (pairing true): 2 pairs",
                 setup_code: bytecode! {
@@ -739,7 +611,7 @@ mod test {
             },
             #[cfg(feature = "scroll")]
             PrecompileCallArgs {
-                name: "ecPairing (invalid): len(input) > 768",
+                name: "ecPairing (invalid): len(input) == 769",
                 setup_code: bytecode! {},
                 call_data_offset: 0x00.into(),
                 call_data_length: 769.into(),
@@ -1089,11 +961,57 @@ mod test {
         }]
     });
 
+    static MANY_PAIRS_TEST_VECTOR: LazyLock<Vec<PrecompileCallArgs>> = LazyLock::new(|| {
+        vec![
+            PrecompileCallArgs {
+                name: "ecPairing (valid): all zero bytes, len(input) == 5 * 192",
+                setup_code: bytecode! {},
+                call_data_offset: 0x00.into(),
+                call_data_length: 0x3C0.into(),
+                ret_offset: 0x3C0.into(),
+                ret_size: 0x20.into(),
+                address: PrecompileCalls::Bn128Pairing.address().to_word(),
+                ..Default::default()
+            },
+            PrecompileCallArgs {
+                name: "ecPairing (pairing true): 6 pairs",
+                setup_code: {
+                    let mut setup_code = Bytecode::default();
+                    let mut memory_addr = 0x00;
+                    for _ in 0..3 {
+                        for (g1, g2) in [
+                            (G1Affine::generator(), G2Affine::generator()),
+                            (-G1Affine::generator(), G2Affine::generator()),
+                        ] {
+                            for fq in [g1.x, g1.y]
+                                .into_iter()
+                                .chain([g2.x.c1, g2.x.c0, g2.y.c1, g2.y.c0])
+                            {
+                                setup_code.push(32, Word::from_little_endian(&fq.to_bytes()));
+                                setup_code.push(2, memory_addr);
+                                memory_addr += 0x20;
+                                setup_code.write_op(OpcodeId::MSTORE);
+                            }
+                        }
+                    }
+                    setup_code
+                },
+                call_data_offset: 0x00.into(),
+                call_data_length: 0x480.into(),
+                ret_offset: 0x480.into(),
+                ret_size: 0x20.into(),
+                address: PrecompileCalls::Bn128Pairing.address().to_word(),
+                gas: 1_000_000.into(),
+                ..Default::default()
+            },
+        ]
+    });
+
     static INVALID_LEN_TEST: LazyLock<Vec<PrecompileCallArgs>> = LazyLock::new(|| {
         vec![
             #[cfg(feature = "scroll")]
             PrecompileCallArgs {
-                name: "ecPairing (invalid): len(input) > 768",
+                name: "ecPairing (invalid): len(input) % 192 != 0, large input",
                 setup_code: bytecode! {},
                 call_data_offset: 0x00.into(),
                 call_data_length: 0x10340.into(),
@@ -1153,6 +1071,59 @@ mod test {
             })
     }
 
+    fn with_large_ec_pairing<R: Send>(f: impl FnOnce() -> R + Send) -> R {
+        let specs = ChainSpec::morph_from_env()
+            .unwrap()
+            .into_iter()
+            .map(|spec| match spec.chain_id {
+                MORPH_DEVNET_CHAIN_ID => {
+                    spec.with_fork(HardforkId::LargeEcPairing, ForkCondition::Block(0))
+                }
+                _ => spec,
+            })
+            .collect();
+        ChainSpec::scoped(specs, f)
+    }
+
+    #[test]
+    fn precompile_ec_pairing_many_pairs_test() {
+        let call_kinds = vec![OpcodeId::CALL, OpcodeId::STATICCALL];
+
+        // more than 4 pairs fail before the fork and are computed after it
+        with_large_ec_pairing(|| {
+            MANY_PAIRS_TEST_VECTOR
+                .iter()
+                .cartesian_product(&call_kinds)
+                .cartesian_product([MOCK_CHAIN_ID, MORPH_DEVNET_CHAIN_ID])
+                .par_bridge()
+                .for_each(|((test_vector, &call_kind), chain_id)| {
+                    let bytecode = test_vector.with_call_op(call_kind);
+                    let test_ctx: TestContext<2, 1> = TestContext::new(
+                        None,
+                        account_0_code_wallet_0_no_code(bytecode),
+                        |mut txs, accs| {
+                            txs[0]
+                                .from(MOCK_WALLETS[0].clone())
+                                .to(accs[0].address)
+                                .chain_id(chain_id);
+                        },
+                        |block, _txs| block.chain_id(chain_id).number(0xcafeu64),
+                    )
+                    .unwrap();
+
+                    CircuitTestBuilder::new_from_test_ctx(test_ctx)
+                        .params(CircuitsParams {
+                            max_ec_ops: PrecompileEcParams {
+                                ec_pairing_pairs: 6,
+                                ..PrecompileEcParams::default()
+                            },
+                            ..CircuitsParams::default()
+                        })
+                        .run();
+                })
+        })
+    }
+
     #[test]
     fn precompile_ec_pairing_invalid_len_test() {
         let call_kinds = vec![
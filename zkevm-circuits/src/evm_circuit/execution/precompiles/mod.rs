@@ -37,6 +37,9 @@ pub use ec_pairing::EcPairingGadget;
 mod identity;
 pub use identity::IdentityGadget;
 
+mod p256_verify;
+pub use p256_verify::P256VerifyGadget;
+
 mod sha256;
 pub use sha256::SHA256Gadget;
 
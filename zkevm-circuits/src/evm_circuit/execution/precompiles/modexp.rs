@@ -1,8 +1,9 @@
 use crate::util::Field;
 use bus_mapping::precompile::{
-    PrecompileAuxData, PrecompileCalls, MODEXP_INPUT_LIMIT, MODEXP_SIZE_LIMIT,
+    modexp_block_rows, modexp_size_limit, PrecompileAuxData, PrecompileCalls,
+    MODEXP_BERNOULLI_SIZE_LIMIT, MODEXP_BLOCK_ROWS_LIMIT, MODEXP_INPUT_LIMIT, MODEXP_SIZE_LIMIT,
 };
-use eth_types::{evm_types::GasCost, ToBigEndian, U256};
+use eth_types::{evm_types::GasCost, forks::HardforkId, U256};
 use gadgets::util::{self, not, select, Expr};
 use gadgets::ToScalar;
 use halo2_proofs::{
@@ -16,7 +17,7 @@ use crate::{
         param::{N_BITS_U8, N_BYTES_MEMORY_ADDRESS, N_BYTES_U64, N_BYTES_WORD},
         step::ExecutionState,
         util::{
-            common_gadget::RestoreContextGadget,
+            common_gadget::{HardforkGadget, RestoreContextGadget},
             constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
             math_gadget::{
                 BinaryNumberGadget, BitLengthGadget, ByteOrWord, ByteSizeGadget,
@@ -30,100 +31,11 @@ use crate::{
     witness::{Block, Call, ExecStep, Transaction},
 };
 
-#[derive(Clone, Debug)]
-struct RandPowRepresent<F, const BIT_LIMIT: usize> {
-    bits: BinaryNumberGadget<F, BIT_LIMIT>,
-    pow_lookup: Cell<F>,
-    cache_for_degree: Option<Cell<F>>,
-    pow: Expression<F>,
-}
-
-impl<F: Field, const BIT_LIMIT: usize> RandPowRepresent<F, BIT_LIMIT> {
-    const BIT_EXP_MAX_DEGREE: usize = 4;
-
-    /// build randomness r, r**2, ... r**(2**BIT_LIMIT)
-    pub fn base_pows_expr(randomness: Expression<F>) -> [Expression<F>; BIT_LIMIT] {
-        std::iter::successors(Some(randomness), |r| Some(r.clone() * r.clone()))
-            .take(BIT_LIMIT)
-            .collect::<Vec<_>>()
-            .try_into()
-            .expect("same length")
-    }
-
-    /// refere to a binary represent of exponent (like BinaryNumberGadget), can
-    /// link another expression so the expr is linked_val * r ** exponent
-    pub fn configure(
-        cb: &mut EVMConstraintBuilder<F>,
-        _randomness: Expression<F>,
-        exponent: Expression<F>,
-        linked_val: Option<Expression<F>>,
-    ) -> Self {
-        let bits = BinaryNumberGadget::construct(cb, exponent);
-        let pow_lookup = cb.query_cell_phase2();
-        cb.pow_of_rand_lookup(bits.value(), pow_lookup.expr());
-
-        let pow = linked_val.unwrap_or_else(|| 1.expr()) * pow_lookup.expr();
-        // we would cache the pow expression in case degree is too larget
-        let cache_for_degree = if pow.degree() > Self::BIT_EXP_MAX_DEGREE {
-            let cached_cell = cb.query_cell_phase2();
-            cb.require_equal(
-                "pow_assemble cached current expression",
-                cached_cell.expr(),
-                pow.clone(),
-            );
-            Some(cached_cell)
-        } else {
-            None
-        };
-
-        Self {
-            bits,
-            pow_lookup,
-            cache_for_degree,
-            pow,
-        }
-    }
-
-    pub fn expr(&self) -> Expression<F> {
-        self.pow.clone()
-    }
-
-    pub fn assign(
-        &self,
-        region: &mut CachedRegion<'_, '_, F>,
-        offset: usize,
-        exponent: usize,
-        linked_value: Option<Value<F>>,
-    ) -> Result<Value<F>, Error> {
-        assert!(
-            2usize.pow(BIT_LIMIT as u32) > exponent,
-            "exponent ({exponent}) can not exceed bit limit (2**{BIT_LIMIT}-1)"
-        );
-        self.bits.assign(region, offset, exponent)?;
-
-        let pow_of_rand = region
-            .challenges()
-            .keccak_input()
-            .map(|v| v.pow([exponent as u64, 0, 0, 0]));
-        let value_should_assigned =
-            linked_value.unwrap_or_else(|| Value::known(F::one())) * pow_of_rand;
-
-        self.pow_lookup.assign(region, offset, pow_of_rand)?;
-
-        if let Some(cell) = &self.cache_for_degree {
-            cell.assign(region, offset, value_should_assigned)?;
-        }
-
-        Ok(value_should_assigned)
-    }
-}
-
 const SIZE_LIMIT: usize = MODEXP_SIZE_LIMIT;
-const SIZE_REPRESENT_BITS: usize = 6;
 const SIZE_REPRESENT_BYTES: usize = SIZE_LIMIT / 256 + 1;
-const INPUT_LIMIT: usize = 32 * 6;
-const INPUT_REPRESENT_BYTES: usize = MODEXP_INPUT_LIMIT / 256 + 1;
-const INPUT_REPRESENT_BITS: usize = 8;
+const INPUT_LIMIT: usize = MODEXP_INPUT_LIMIT;
+// the block rows of the effect sizes, up to 2^16 * (2 + 16 * 2^16), fit in 5 bytes
+const BLOCK_ROWS_REPRESENT_BYTES: usize = 5;
 
 type Word<F> = [Cell<F>; 32];
 
@@ -164,6 +76,11 @@ fn expr_from_bytes<F: Field, E: Expr<F>>(bytes: &[E]) -> Expression<F> {
         .fold(0.expr(), |acc, byte| acc * F::from(256) + byte.expr())
 }
 
+// the value of the lowest SIZE_REPRESENT_BYTES bytes of a size, see `SizeRepresent::value`
+fn effect_size(size: &U256) -> u64 {
+    size.low_u64() & ((1 << (8 * SIZE_REPRESENT_BYTES)) - 1)
+}
+
 #[derive(Clone, Debug)]
 struct SizeRepresent<F> {
     len_bytes: Word<F>,
@@ -173,7 +90,8 @@ struct SizeRepresent<F> {
 }
 
 impl<F: Field> SizeRepresent<F> {
-    pub fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
+    /// `size_limit` is the (fork dependent) size limit of the operands, up to `SIZE_LIMIT`.
+    pub fn configure(cb: &mut EVMConstraintBuilder<F>, size_limit: Expression<F>) -> Self {
         let len_bytes = cb.query_bytes();
         let expression = rlc_word_rev(&len_bytes, cb.challenges().keccak_input());
         // we calculate at most 31 bytes so it can be fit into a field
@@ -189,7 +107,7 @@ impl<F: Field> SizeRepresent<F> {
         let is_not_exceed_limit = LtGadget::construct(
             cb,
             expr_from_bytes(&len_effect_bytes),
-            (SIZE_LIMIT + 1).expr(),
+            size_limit + 1.expr(),
         );
         Self {
             len_bytes,
@@ -225,6 +143,7 @@ impl<F: Field> SizeRepresent<F> {
         region: &mut CachedRegion<'_, '_, F>,
         offset: usize,
         size: &U256,
+        size_limit: usize,
     ) -> Result<(), Error> {
         let mut bytes = [0u8; 32];
         size.to_big_endian(&mut bytes);
@@ -240,131 +159,72 @@ impl<F: Field> SizeRepresent<F> {
             region,
             offset,
             effect_field.to_scalar().unwrap(),
-            F::from((SIZE_LIMIT + 1) as u64),
+            F::from((size_limit + 1) as u64),
         )?;
         Ok(())
     }
 }
 
-type RandPow<F> = RandPowRepresent<F, SIZE_REPRESENT_BITS>;
-
-// parse as (valid, len, value: [base, exp, modulus])
-type InputParsedResult = (bool, [U256; 3], [[u8; SIZE_LIMIT]; 3]);
-type OutputParsedResult = (usize, [u8; SIZE_LIMIT]);
-
 #[derive(Clone, Debug)]
 struct ModExpInputs<F> {
     base_len: SizeRepresent<F>,
     modulus_len: SizeRepresent<F>,
     exp_len: SizeRepresent<F>,
-    base_pow: RandPow<F>,
-    base: Word<F>,
-    modulus_pow: RandPow<F>,
-    modulus: Word<F>,
-    exp_pow: RandPow<F>,
-    exp: Word<F>,
+    is_within_rows_limit: LtGadget<F, BLOCK_ROWS_REPRESENT_BYTES>,
+    // the first (up to) 32 bytes of the exponent, right-aligned
+    exp_head: Word<F>,
     input_valid: Cell<F>,
-    input_bytes_rlc: Expression<F>,
-    input_len_expected: Expression<F>,
-    pub base_limbs: Limbs<F>,
-    pub exp_limbs: Limbs<F>,
-    pub modulus_limbs: Limbs<F>,
+    header_rlc: Expression<F>,
 }
 
 impl<F: Field> ModExpInputs<F> {
-    pub fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
-        let base_len = SizeRepresent::configure(cb);
-        let modulus_len = SizeRepresent::configure(cb);
-        let exp_len = SizeRepresent::configure(cb);
-
-        let r_pow_32 =
-            RandPowRepresent::<_, 6>::base_pows_expr(cb.challenges().keccak_input())[5].clone(); //r**32
-        let r_pow_64 = r_pow_32.clone().square();
+    pub fn configure(cb: &mut EVMConstraintBuilder<F>, size_limit: Expression<F>) -> Self {
+        let base_len = SizeRepresent::configure(cb, size_limit.clone());
+        let modulus_len = SizeRepresent::configure(cb, size_limit.clone());
+        let exp_len = SizeRepresent::configure(cb, size_limit);
+
+        // the rows the call takes in the modexp circuit are bounded as well, so that a large
+        // modulus is only accepted with a short exponent, see `MODEXP_BLOCK_ROWS_LIMIT`.
+        let is_within_rows_limit = LtGadget::construct(
+            cb,
+            modulus_len.value() * (2.expr() + 16.expr() * exp_len.value()),
+            (MODEXP_BLOCK_ROWS_LIMIT + 1).expr(),
+        );
 
-        let base = cb.query_bytes();
-        let modulus = cb.query_bytes();
-        let exp = cb.query_bytes();
+        let r_pow_32 = std::iter::successors(Some(cb.challenges().keccak_input()), |r| {
+            Some(r.clone() * r.clone())
+        })
+        .nth(5)
+        .expect("r**32"); // r**(2**5)
+        let r_pow_64 = r_pow_32.clone() * r_pow_32.clone();
 
-        let base_limbs = Limbs::configure(cb, &base);
-        let exp_limbs = Limbs::configure(cb, &exp);
-        let modulus_limbs = Limbs::configure(cb, &modulus);
+        let exp_head = cb.query_bytes();
 
         let input_valid = cb.query_bool();
         cb.require_equal(
-            "mark input valid by checking 3 lens is valid",
+            "mark input valid by checking 3 lens and the rows they take are valid",
             input_valid.expr(),
             util::and::expr([
                 base_len.is_valid(),
                 exp_len.is_valid(),
                 modulus_len.is_valid(),
+                is_within_rows_limit.expr(),
             ]),
         );
 
-        let base_len_expected = util::select::expr(input_valid.expr(), base_len.value(), 0.expr());
-
-        let exp_len_expected = util::select::expr(input_valid.expr(), exp_len.value(), 0.expr());
-
-        let modulus_len_expected =
-            util::select::expr(input_valid.expr(), modulus_len.value(), 0.expr());
-
-        let input_len_expected = 96.expr()
-            + base_len_expected.clone()
-            + exp_len_expected.clone()
-            + modulus_len_expected.clone();
-
-        // we put correct size in each input word if input is valid
-        // else we just put as most as possible bytes (32) into it
-        // so we finally handle the memory in limited sized (32*3)
-        let modulus_pow = RandPow::configure(
-            cb,
-            cb.challenges().keccak_input(),
-            modulus_len_expected,
-            None,
-        );
-
-        // exp_pow = r**(modulus_len + exp_len)
-        let exp_pow = RandPow::configure(
-            cb,
-            cb.challenges().keccak_input(),
-            exp_len_expected,
-            Some(modulus_pow.expr()),
-        );
-
-        // base_pow = r**(modulus_len + exp_len + base_len)
-        let base_pow = RandPow::configure(
-            cb,
-            cb.challenges().keccak_input(),
-            base_len_expected,
-            Some(exp_pow.expr()),
-        );
-
-        let input_bytes_rlc = rlc_word_rev(&modulus, cb.challenges().keccak_input()) //rlc of base
-            + modulus_pow.expr() * rlc_word_rev(&exp, cb.challenges().keccak_input()) //rlc of exp plus r**base_len
-            + exp_pow.expr() * rlc_word_rev(&base, cb.challenges().keccak_input()) //rlc of exp plus r**(base_len + exp_len)
-            + base_pow.expr() * modulus_len.memory_rlc()
-            + base_pow.expr() * r_pow_32 * exp_len.memory_rlc()
-            + base_pow.expr() * r_pow_64 * base_len.memory_rlc();
-
-        // println!("phase 2 cell used {}",
-        //     padding_pow.phase2_cell_cost() + [&modulus_pow, &exp_pow,
-        // &base_pow].iter().map(|pw|pw.phase2_cell_cost()).sum::<usize>() );
+        // the 3 lens are the first 96 bytes of the input
+        let header_rlc = base_len.memory_rlc() * r_pow_64
+            + exp_len.memory_rlc() * r_pow_32
+            + modulus_len.memory_rlc();
 
         Self {
             base_len,
             modulus_len,
             exp_len,
-            base_pow,
-            base,
-            modulus_pow,
-            modulus,
-            exp_pow,
-            exp,
+            is_within_rows_limit,
+            exp_head,
             input_valid,
-            input_bytes_rlc,
-            input_len_expected,
-            base_limbs,
-            exp_limbs,
-            modulus_limbs,
+            header_rlc,
         }
     }
 
@@ -374,18 +234,23 @@ impl<F: Field> ModExpInputs<F> {
     pub fn is_valid(&self) -> Expression<F> {
         self.input_valid.expr()
     }
-    pub fn len_expected(&self) -> Expression<F> {
-        self.input_len_expected.clone()
+    pub fn header_rlc(&self) -> Expression<F> {
+        self.header_rlc.clone()
     }
-    pub fn bytes_rlc(&self) -> Expression<F> {
-        self.input_bytes_rlc.clone()
+    /// the lens of [base, exp, modulus] in the modexp table, which are 0 for invalid input
+    pub fn table_lens(&self) -> [Expression<F>; 3] {
+        [&self.base_len, &self.exp_len, &self.modulus_len]
+            .map(|len| select::expr(self.input_valid.expr(), len.value(), 0.expr()))
     }
 
     pub fn assign(
         &self,
         region: &mut CachedRegion<'_, '_, F>,
         offset: usize,
-        (input_valid, lens, values): InputParsedResult,
+        input_valid: bool,
+        lens: &[U256; 3],
+        exp_head: [u8; 32],
+        size_limit: usize,
     ) -> Result<(), Error> {
         self.input_valid.assign(
             region,
@@ -397,170 +262,31 @@ impl<F: Field> ModExpInputs<F> {
             lens.iter()
                 .zip([&self.base_len, &self.exp_len, &self.modulus_len])
         {
-            len_represent.assign(region, offset, len)?;
-        }
-
-        let mut linked_v = None;
-        for (len, pow) in lens
-            .iter()
-            .zip([&self.base_pow, &self.exp_pow, &self.modulus_pow])
-            .rev()
-        {
-            let assigned = pow.assign(
-                region,
-                offset,
-                if input_valid { len.as_usize() } else { 0 },
-                linked_v,
-            )?;
-
-            linked_v = Some(assigned);
-        }
-
-        for (val_r, input_limbs) in
-            values
-                .iter()
-                .zip([&self.base_limbs, &self.exp_limbs, &self.modulus_limbs])
-        {
-            input_limbs.assign(region, offset, val_r)?;
-        }
-
-        for (&val, input_bytes) in values.iter().zip([&self.base, &self.exp, &self.modulus]) {
-            assign_word(region, offset, input_bytes, val)?;
-        }
-
-        Ok(())
-    }
-}
-
-#[derive(Clone, Debug)]
-struct ModExpOutputs<F> {
-    result: Word<F>,
-    is_result_zero: IsZeroGadget<F>,
-    output_bytes_rlc: Expression<F>,
-    pub result_limbs: Limbs<F>,
-}
-
-impl<F: Field> ModExpOutputs<F> {
-    fn configure(
-        cb: &mut EVMConstraintBuilder<F>,
-        inner_success: Expression<F>,
-        modulus_len: Expression<F>,
-    ) -> Self {
-        let output_len = inner_success * modulus_len;
-        let is_result_zero = cb.annotation("if output len is nil", |cb| {
-            IsZeroGadget::construct(cb, output_len)
-        });
-
-        let result = cb.query_bytes();
-        let result_limbs = Limbs::configure(cb, &result);
-
-        let output_bytes_rlc = util::select::expr(
-            is_result_zero.expr(),
-            0.expr(),
-            rlc_word_rev(&result, cb.challenges().keccak_input()),
-        );
-
-        Self {
-            result,
-            is_result_zero,
-            output_bytes_rlc,
-            result_limbs,
+            len_represent.assign(region, offset, len, size_limit)?;
         }
-    }
-
-    pub fn is_nil(&self) -> Expression<F> {
-        self.is_result_zero.expr()
-    }
-
-    pub fn bytes_rlc(&self) -> Expression<F> {
-        self.output_bytes_rlc.clone()
-    }
-
-    pub fn assign(
-        &self,
-        region: &mut CachedRegion<'_, '_, F>,
-        offset: usize,
-        (output_len, data): OutputParsedResult,
-    ) -> Result<(), Error> {
-        self.is_result_zero
-            .assign(region, offset, F::from(output_len as u64))?;
-        self.result_limbs.assign(region, offset, &data)?;
-        assign_word(region, offset, &self.result, data)?;
-        Ok(())
-    }
-}
-
-#[derive(Clone, Debug)]
-pub(crate) struct Limbs<F> {
-    byte14_split_lo: Cell<F>,
-    byte14_split_hi: Cell<F>,
-    limbs: [Expression<F>; 3],
-}
-
-impl<F: Field> Limbs<F> {
-    pub fn configure(cb: &mut EVMConstraintBuilder<F>, word: &Word<F>) -> Self {
-        let byte14_split_lo = cb.query_byte();
-        let byte14_split_hi = cb.query_byte();
-
-        cb.require_equal(
-            "split 14th byte in word into half",
-            word[MODEXP_SIZE_LIMIT - 14].expr(),
-            byte14_split_lo.expr() + byte14_split_hi.expr(),
-        );
-
-        let inv_16 = Expression::Constant(F::from(16u64).invert().unwrap());
-
-        let limbs = [
-            expr_from_bytes(
-                &std::iter::once(&byte14_split_lo)
-                    .chain(&word[MODEXP_SIZE_LIMIT - 13..])
-                    .collect::<Vec<_>>(),
-            ),
-            expr_from_bytes(
-                &word[MODEXP_SIZE_LIMIT - 27..MODEXP_SIZE_LIMIT - 14]
-                    .iter()
-                    .chain(std::iter::once(&byte14_split_hi))
-                    .collect::<Vec<_>>(),
-            ) * inv_16,
-            expr_from_bytes(&word[..MODEXP_SIZE_LIMIT - 27]),
-        ];
-
-        Self {
-            byte14_split_lo,
-            byte14_split_hi,
-            limbs,
-        }
-    }
+        let [_, exp_len, modulus_len] = lens.map(|len| effect_size(&len) as usize);
+        self.is_within_rows_limit.assign(
+            region,
+            offset,
+            F::from(modexp_block_rows(exp_len, modulus_len) as u64),
+            F::from((MODEXP_BLOCK_ROWS_LIMIT + 1) as u64),
+        )?;
 
-    pub fn limbs(&self) -> [Expression<F>; 3] {
-        self.limbs.clone()
-    }
-
-    pub fn assign(
-        &self,
-        region: &mut CachedRegion<'_, '_, F>,
-        offset: usize,
-        big_int: &[u8; MODEXP_SIZE_LIMIT],
-    ) -> Result<(), Error> {
-        let byte14_lo = big_int[MODEXP_SIZE_LIMIT - 14] & 0xf;
-        let byte14_hi = big_int[MODEXP_SIZE_LIMIT - 14] & 0xf0;
-
-        self.byte14_split_lo
-            .assign(region, offset, Value::known(F::from(byte14_lo as u64)))?;
-        self.byte14_split_hi
-            .assign(region, offset, Value::known(F::from(byte14_hi as u64)))?;
-        Ok(())
+        assign_word(region, offset, &self.exp_head, exp_head)
     }
 }
 
 #[derive(Clone, Debug)]
 pub(crate) struct ModExpGasCost<F> {
-    max_length: MinMaxGadget<F, 1>,
-    words: ConstantDivisionGadget<F, 1>,
+    max_length: MinMaxGadget<F, SIZE_REPRESENT_BYTES>,
+    words: ConstantDivisionGadget<F, SIZE_REPRESENT_BYTES>,
     exp_is_zero: IsZeroGadget<F>,
     exp_byte_size: ByteSizeGadget<F>,
     exp_msb_bit_length: BitLengthGadget<F>,
     exp_msb: BinaryNumberGadget<F, N_BITS_U8>,
+    exp_tail_length: MinMaxGadget<F, SIZE_REPRESENT_BYTES>,
+    adjusted_exp_length: Cell<F>,
+    is_adjusted_exp_length_zero: IsZeroGadget<F>,
     calc_gas: ConstantDivisionGadget<F, N_BYTES_U64>,
     dynamic_gas: MinMaxGadget<F, N_BYTES_U64>,
 }
@@ -569,7 +295,8 @@ impl<F: Field> ModExpGasCost<F> {
     fn construct(
         cb: &mut EVMConstraintBuilder<F>,
         b_size: &SizeRepresent<F>,
-        exp: &[Cell<F>; MODEXP_SIZE_LIMIT],
+        e_size: &SizeRepresent<F>,
+        exp_head: &Word<F>,
         m_size: &SizeRepresent<F>,
     ) -> Self {
         let max_length = MinMaxGadget::construct(cb, b_size.value(), m_size.value());
@@ -577,14 +304,18 @@ impl<F: Field> ModExpGasCost<F> {
         let multiplication_complexity = words.quotient() * words.quotient();
         let exp_is_zero = IsZeroGadget::construct(
             cb,
-            rlc::expr(&exp.clone().map(|c| c.expr()), cb.challenges().evm_word()),
+            rlc::expr(
+                &exp_head.clone().map(|c| c.expr()),
+                cb.challenges().evm_word(),
+            ),
         );
 
         let (exp_byte_size, exp_msb, exp_msb_bit_length) =
             cb.condition(not::expr(exp_is_zero.expr()), |cb| {
                 let exp_byte_size = ByteSizeGadget::construct(
                     cb,
-                    exp.iter()
+                    exp_head
+                        .iter()
                         .rev()
                         .map(Expr::expr)
                         .collect::<Vec<Expression<F>>>()
@@ -606,16 +337,23 @@ impl<F: Field> ModExpGasCost<F> {
                 );
                 (exp_byte_size, exp_msb, exp_msb_bit_length)
             });
-        let exp_bit_length =
+        let exp_head_bit_length =
             (exp_byte_size.size() - 1.expr()) * N_BITS_U8.expr() + exp_msb_bit_length.size();
 
-        // We already restrict Esize <= 32. So we can completely ignore the branch concerning
-        // Esize > 32. We only care about whether or not exponent is zero.
-        let iteration_count = select::expr(
-            exp_is_zero.expr(),
-            0.expr(),
-            exp_bit_length.expr() - 1.expr(),
+        // EIP-2565: the adjusted exponent length is 8 * max(Esize - 32, 0) plus the index of the
+        // highest bit in the first 32 bytes of the exponent (0 if they are zero).
+        let exp_tail_length = MinMaxGadget::construct(cb, e_size.value(), 32.expr());
+        let adjusted_exp_length = cb.query_cell();
+        cb.require_equal(
+            "adjusted exponent length",
+            adjusted_exp_length.expr(),
+            (exp_tail_length.max() - 32.expr()) * N_BITS_U8.expr()
+                + select::expr(exp_is_zero.expr(), 0.expr(), exp_head_bit_length - 1.expr()),
         );
+        let is_adjusted_exp_length_zero = IsZeroGadget::construct(cb, adjusted_exp_length.expr());
+        // iteration_count = max(adjusted_exp_length, 1)
+        let iteration_count = adjusted_exp_length.expr() + is_adjusted_exp_length_zero.expr();
+
         let calc_gas =
             ConstantDivisionGadget::construct(cb, multiplication_complexity * iteration_count, 3);
         let dynamic_gas = MinMaxGadget::construct(
@@ -631,6 +369,9 @@ impl<F: Field> ModExpGasCost<F> {
             exp_byte_size,
             exp_msb_bit_length,
             exp_msb,
+            exp_tail_length,
+            adjusted_exp_length,
+            is_adjusted_exp_length_zero,
             calc_gas,
             dynamic_gas,
         }
@@ -640,65 +381,64 @@ impl<F: Field> ModExpGasCost<F> {
         &self,
         region: &mut CachedRegion<'_, '_, F>,
         offset: usize,
-        b_size: &U256,
-        m_size: &U256,
-        exponent: &[u8; MODEXP_SIZE_LIMIT],
+        [b_size, e_size, m_size]: &[U256; 3],
+        exp_head: &[u8; 32],
     ) -> Result<u64, Error> {
-        let mut base_len = [0u8; 32];
-        base_len[(32 - SIZE_REPRESENT_BYTES)..]
-            .copy_from_slice(&b_size.to_be_bytes()[(32 - SIZE_REPRESENT_BYTES)..]);
-        let mut mod_len = [0u8; 32];
-        mod_len[(32 - SIZE_REPRESENT_BYTES)..]
-            .copy_from_slice(&m_size.to_be_bytes()[(32 - SIZE_REPRESENT_BYTES)..]);
-        let b_size = U256::from_big_endian(&base_len);
-        let m_size = U256::from_big_endian(&mod_len);
-
-        self.max_length.assign(
-            region,
-            offset,
-            b_size.to_scalar().expect("Bsize is within scalar field"),
-            m_size.to_scalar().expect("Msize is within scalar field"),
-        )?;
+        let [b_size, e_size, m_size] = [b_size, e_size, m_size].map(effect_size);
+
+        self.max_length
+            .assign(region, offset, F::from(b_size), F::from(m_size))?;
         self.words
-            .assign(region, offset, b_size.max(m_size).as_u128() + 7u128)?;
-        let exp_word = U256::from_big_endian(exponent);
+            .assign(region, offset, b_size.max(m_size) as u128 + 7u128)?;
+        let exp_word = U256::from_big_endian(exp_head);
         self.exp_is_zero.assign_value(
             region,
             offset,
             region
                 .challenges()
                 .evm_word()
-                .map(|r| rlc::value(exponent, r)),
+                .map(|r| rlc::value(exp_head, r)),
         )?;
         self.exp_byte_size
             .assign(region, offset, ByteOrWord::Word(exp_word))?;
         let exp_byte_size = (exp_word.bits() + 7) / 8;
         let exp_msb = if exp_byte_size > 0 {
-            exponent[N_BYTES_WORD - exp_byte_size]
+            exp_head[N_BYTES_WORD - exp_byte_size]
         } else {
             0
         };
         self.exp_msb.assign(region, offset, exp_msb)?;
         self.exp_msb_bit_length
             .assign(region, offset, ByteOrWord::Byte(exp_msb))?;
-        let exp_bit_length = exp_word.bits();
-        let max_length = b_size.max(m_size);
-        let words = (max_length + 7) / 8;
-        let multiplication_complexity = words * words;
-        let iteration_count = if exp_word.is_zero() {
+
+        self.exp_tail_length
+            .assign(region, offset, F::from(e_size), F::from(32))?;
+        let exp_head_msb_index = if exp_word.is_zero() {
             0
         } else {
-            exp_bit_length - 1
+            exp_word.bits() as u64 - 1
         };
+        let adjusted_exp_length = 8 * (e_size.max(32) - 32) + exp_head_msb_index;
+        self.adjusted_exp_length.assign(
+            region,
+            offset,
+            Value::known(F::from(adjusted_exp_length)),
+        )?;
+        self.is_adjusted_exp_length_zero
+            .assign(region, offset, F::from(adjusted_exp_length))?;
+
+        let words = (b_size.max(m_size) + 7) / 8;
+        let multiplication_complexity = words * words;
+        let iteration_count = adjusted_exp_length.max(1);
         let numerator = multiplication_complexity * iteration_count;
-        self.calc_gas.assign(region, offset, numerator.as_u128())?;
+        self.calc_gas.assign(region, offset, numerator as u128)?;
         self.dynamic_gas.assign(
             region,
             offset,
             F::from(GasCost::PRECOMPILE_MODEXP_MIN.0),
-            F::from((numerator / 3).as_u64()),
+            F::from(numerator / 3),
         )?;
-        let gas_cost = std::cmp::max(GasCost::PRECOMPILE_MODEXP_MIN.0, (numerator / 3).as_u64());
+        let gas_cost = std::cmp::max(GasCost::PRECOMPILE_MODEXP_MIN.0, numerator / 3);
 
         Ok(gas_cost)
     }
@@ -722,14 +462,13 @@ pub struct ModExpGadget<F> {
     return_data_length: Cell<F>,
     restore_context_gadget: RestoreContextGadget<F>,
 
+    large_modexp: HardforkGadget<F>,
     input: ModExpInputs<F>,
-    padding_zero: RandPowRepresent<F, INPUT_REPRESENT_BITS>,
-    output: ModExpOutputs<F>,
+    result_rlc: Cell<F>,
 
     input_bytes_acc: Cell<F>,
     is_gas_insufficient: LtGadget<F, N_BYTES_U64>,
     gas_cost_gadget: ModExpGasCost<F>,
-    garbage_bytes_holder: [Cell<F>; INPUT_LIMIT - 96],
 }
 
 impl<F: Field> ExecutionGadget<F> for ModExpGadget<F> {
@@ -764,16 +503,28 @@ impl<F: Field> ExecutionGadget<F> for ModExpGadget<F> {
             cb.execution_state().precompile_base_gas_cost().expr(),
         );
 
-        let input = ModExpInputs::configure(cb);
-        let padding_zero = RandPowRepresent::configure(
+        // the operands are limited to 32 bytes before the large modexp fork, as in bernoulli
+        let large_modexp = HardforkGadget::construct(
             cb,
-            cb.challenges().keccak_input(),
-            MODEXP_INPUT_LIMIT.expr() - input.len_expected(),
-            None,
+            HardforkId::LargeModexp,
+            cb.curr.state.block_number.expr(),
+        );
+        let input = ModExpInputs::configure(
+            cb,
+            select::expr(
+                large_modexp.is_before_fork.expr(),
+                MODEXP_BERNOULLI_SIZE_LIMIT.expr(),
+                SIZE_LIMIT.expr(),
+            ),
         );
 
-        let gas_cost_gadget =
-            ModExpGasCost::construct(cb, &input.base_len, &input.exp, &input.modulus_len);
+        let gas_cost_gadget = ModExpGasCost::construct(
+            cb,
+            &input.base_len,
+            &input.exp_len,
+            &input.exp_head,
+            &input.modulus_len,
+        );
         let is_gas_insufficient = LtGadget::construct(
             cb,
             cb.curr.state.gas_left.expr(),
@@ -786,39 +537,27 @@ impl<F: Field> ExecutionGadget<F> for ModExpGadget<F> {
         cb.require_equal(
             "call success if valid input and enough gas",
             is_success.expr(),
-            call_success.clone(),
-        );
-
-        let output = ModExpOutputs::configure(
-            cb,
-            //FIXME: there may be still some edge cases lead to nil output (even modulus_len is
-            // not 0)
             call_success,
-            input.modulus_len(),
         );
 
-        cb.condition(util::not::expr(output.is_nil()), |cb| {
-            cb.modexp_table_lookup(
-                input.base_limbs.limbs(),
-                input.exp_limbs.limbs(),
-                input.modulus_limbs.limbs(),
-                output.result_limbs.limbs(),
-            );
-        });
-
-        let garbage_bytes_holder = cb.query_bytes();
-
-        cb.require_equal(
-            "input acc bytes with padding must equal",
+        // the modexp circuit proves the header and the exponent head against the (padded) input
+        // bytes, and computes the result, which is 0 for a zero modulus or an invalid input.
+        let result_rlc = cb.query_cell_phase2();
+        let [base_len, exp_len, modulus_len] = input.table_lens();
+        cb.modexp_table_lookup(
             input_bytes_acc.expr(),
-            padding_zero.expr() * input.bytes_rlc()
-                + rlc_rev(&garbage_bytes_holder, cb.challenges().keccak_input()),
+            input.header_rlc(),
+            base_len,
+            exp_len,
+            modulus_len,
+            rlc_word_rev(&input.exp_head, cb.challenges().keccak_input()),
+            result_rlc.expr(),
         );
 
         cb.require_equal(
             "output acc bytes must equal",
             output_bytes_rlc.expr(),
-            output.bytes_rlc(),
+            select::expr(is_success.expr(), result_rlc.expr(), 0.expr()),
         );
 
         let gas_cost = select::expr(
@@ -827,7 +566,7 @@ impl<F: Field> ExecutionGadget<F> for ModExpGadget<F> {
             cb.curr.state.gas_left.expr(),
         );
 
-        let required_input_len = MODEXP_INPUT_LIMIT.expr();
+        let required_input_len = INPUT_LIMIT.expr();
         let pad_right = LtGadget::construct(cb, call_data_length.expr(), required_input_len.expr());
         let padding = cb.condition(pad_right.expr(), |cb| {
             PaddingGadget::construct(
@@ -874,13 +613,12 @@ impl<F: Field> ExecutionGadget<F> for ModExpGadget<F> {
             return_data_offset,
             return_data_length,
             restore_context_gadget,
+            large_modexp,
             input,
-            padding_zero,
-            output,
+            result_rlc,
             input_bytes_acc,
             is_gas_insufficient,
             gas_cost_gadget,
-            garbage_bytes_holder,
         }
     }
 
@@ -889,39 +627,34 @@ impl<F: Field> ExecutionGadget<F> for ModExpGadget<F> {
         region: &mut CachedRegion<'_, '_, F>,
         offset: usize,
         block: &Block,
-        _tx: &Transaction,
+        tx: &Transaction,
         call: &Call,
         step: &ExecStep,
     ) -> Result<(), Error> {
+        self.large_modexp
+            .assign(region, offset, block.chain_id, tx.block_number)?;
         if let Some(PrecompileAuxData::Modexp(data)) = &step.aux_data {
-            self.input
-                .assign(region, offset, (data.valid, data.input_lens, data.inputs))?;
-
-            let input_expected_len = 96
-                + if data.valid {
-                    data.input_lens.iter().map(U256::as_usize).sum::<usize>()
-                } else {
-                    0
-                };
-
-            let garbage_bytes = if call.call_data_length as usize > input_expected_len {
-                let mut bts = vec![0; input_expected_len - 96]; //front prefix zero
-                bts.append(&mut Vec::from(&data.input_bytes[input_expected_len..]));
-                bts.resize(96, 0); //padding zero
-                bts
-            } else {
-                Vec::from([0u8; 96])
-            };
-
-            self.padding_zero
-                .assign(region, offset, INPUT_LIMIT - input_expected_len, None)?;
-
-            for (cell, bt) in self.garbage_bytes_holder.iter().zip(garbage_bytes) {
-                cell.assign(region, offset, Value::known(F::from(bt as u64)))?;
-            }
+            let exponent = &data.inputs[1];
+            let exp_head_len = exponent.len().min(32);
+            let mut exp_head = [0u8; 32];
+            exp_head[32 - exp_head_len..].copy_from_slice(&exponent[..exp_head_len]);
+            self.input.assign(
+                region,
+                offset,
+                data.valid,
+                &data.input_lens,
+                exp_head,
+                modexp_size_limit(block.chain_id, tx.block_number),
+            )?;
 
-            self.output
-                .assign(region, offset, (data.output_len, data.output))?;
+            self.result_rlc.assign(
+                region,
+                offset,
+                region
+                    .challenges()
+                    .keccak_input()
+                    .map(|r| rlc::value(data.output.iter().rev(), r)),
+            )?;
 
             let input_rlc = region
                 .challenges()
@@ -938,8 +671,8 @@ impl<F: Field> ExecutionGadget<F> for ModExpGadget<F> {
                     .map(|r| rlc::value(data.return_bytes.iter().rev(), r)),
             )?;
 
-            // if the input to modexp has more than 192 bytes, then we only keep the first 192 bytes
-            // and discard the remaining bytes
+            // if the input to modexp has more than INPUT_LIMIT bytes, then we only keep the first
+            // INPUT_LIMIT bytes and discard the remaining bytes
             let input_len_limit = INPUT_LIMIT as u64;
             let n_padded_zeros = if call.call_data_length > input_len_limit {
                 0
@@ -960,29 +693,26 @@ impl<F: Field> ExecutionGadget<F> for ModExpGadget<F> {
                 .assign(region, offset, n_padded_zeroes_pow * input_rlc)?;
             self.output_bytes_rlc.assign(region, offset, output_rlc)?;
 
-            let required_gas_cost = self.gas_cost_gadget.assign(
-                region,
-                offset,
-                &data.input_lens[0],
-                &data.input_lens[2],
-                &data.inputs[1],
-            )?;
+            let required_gas_cost =
+                self.gas_cost_gadget
+                    .assign(region, offset, &data.input_lens, &exp_head)?;
             self.is_gas_insufficient.assign(
                 region,
                 offset,
                 F::from(step.gas_left),
                 F::from(required_gas_cost),
             )?;
-            self.pad_right
-                .assign(region, offset, call.call_data_length.into(), 192.into())?;
+            self.pad_right.assign(
+                region,
+                offset,
+                call.call_data_length.into(),
+                input_len_limit.into(),
+            )?;
             self.padding.assign(
                 region,
                 offset,
                 PrecompileCalls::Modexp,
-                region
-                    .challenges()
-                    .keccak_input()
-                    .map(|r| rlc::value(data.input_bytes.iter().rev(), r)),
+                input_rlc,
                 call.call_data_length,
                 region.challenges().keccak_input(),
             )?;
@@ -1035,72 +765,34 @@ mod test {
     use super::*;
     use bus_mapping::{
         evm::{OpcodeId, PrecompileCallArgs},
-        precompile::PrecompileCalls,
+        precompile::{ModExpAuxData, PrecompileCalls},
+    };
+    use eth_types::{
+        bytecode,
+        forks::{ChainSpec, ForkCondition, MORPH_DEVNET_CHAIN_ID},
+        word, ToWord,
     };
-    use eth_types::{bytecode, word, ToWord};
-    use ethers_core::k256::elliptic_curve::PrimeField;
     use itertools::Itertools;
-    use mock::TestContext;
+    use mock::{
+        test_ctx::helpers::account_0_code_wallet_0_no_code, TestContext, MOCK_CHAIN_ID,
+        MOCK_WALLETS,
+    };
     use std::sync::LazyLock;
 
     use crate::test_util::CircuitTestBuilder;
 
-    #[test]
-    fn test_limbs() {
-        use crate::table::ModExpTable;
-        use halo2_proofs::halo2curves::bn256::Fr;
-        use misc_precompiled_circuit::circuits::modexp::Number;
-        use num_bigint::BigUint;
-
-        // simply take an hash for test
-        let bi = BigUint::parse_bytes(
-            b"fcb51a0695d8f838b1ee009b3fbf66bda078cd64590202a864a8f3e8c4315c47",
-            16,
-        )
-        .unwrap();
-        let n = Number::<Fr>::from_bn(&bi);
-        let w = word!("0xfcb51a0695d8f838b1ee009b3fbf66bda078cd64590202a864a8f3e8c4315c47");
-        let mut bytes = [0u8; 32];
-        w.to_big_endian(&mut bytes);
-        assert_eq!(BigUint::from_bytes_be(&bytes), bi);
-
-        let byte14_lo = bytes[MODEXP_SIZE_LIMIT - 14] & 0xf;
-        let byte14_hi = bytes[MODEXP_SIZE_LIMIT - 14] & 0xf0;
-
-        let limb0: Fr = U256::from_big_endian(
-            &(std::iter::once(byte14_lo))
-                .chain(bytes[MODEXP_SIZE_LIMIT - 13..].iter().copied())
-                .collect::<Vec<_>>(),
-        )
-        .to_scalar()
-        .unwrap();
-
-        let limb1: Fr = U256::from_big_endian(
-            &bytes[MODEXP_SIZE_LIMIT - 27..MODEXP_SIZE_LIMIT - 14]
-                .iter()
-                .copied()
-                .chain(std::iter::once(byte14_hi))
-                .collect::<Vec<_>>(),
-        )
-        .to_scalar()
-        .unwrap();
-
-        let limb2: Fr = U256::from_big_endian(&bytes[..MODEXP_SIZE_LIMIT - 27])
-            .to_scalar()
-            .unwrap();
-
-        assert_eq!(limb0, n.limbs[0].value);
-        assert_eq!(limb1, n.limbs[1].value * Fr::from(16_u64));
-        assert_eq!(limb2, n.limbs[2].value);
-
-        let nt: Fr = ModExpTable::native_u256(&w);
-
-        let table_split = ModExpTable::split_u256_108bit_limbs(&w);
-        assert_eq!(Fr::from_u128(table_split[0]), n.limbs[0].value);
-        assert_eq!(Fr::from_u128(table_split[1]), n.limbs[1].value);
-        assert_eq!(Fr::from_u128(table_split[2]), n.limbs[2].value);
-        assert_eq!(nt, n.limbs[3].value);
-        //Limb::new(None, value)
+    fn with_large_modexp<R: Send>(f: impl FnOnce() -> R + Send) -> R {
+        let specs = ChainSpec::morph_from_env()
+            .unwrap()
+            .into_iter()
+            .map(|spec| match spec.chain_id {
+                MORPH_DEVNET_CHAIN_ID => {
+                    spec.with_fork(HardforkId::LargeModexp, ForkCondition::Block(0))
+                }
+                _ => spec,
+            })
+            .collect();
+        ChainSpec::scoped(specs, f)
     }
 
     static TEST_VECTOR: LazyLock<Vec<PrecompileCallArgs>> = LazyLock::new(|| {
@@ -1467,20 +1159,15 @@ mod test {
                 address: PrecompileCalls::Modexp.address().to_word(),
                 ..Default::default()
             },
-        ]
-    });
-
-    static TEST_INVALID_VECTOR: LazyLock<Vec<PrecompileCallArgs>> = LazyLock::new(|| {
-        vec![
             PrecompileCallArgs {
-                name: "modexp Msize length too large invalid",
+                name: "modexp 33 bytes base, exponent and modulus",
                 setup_code: bytecode! {
                     // Base size
-                    PUSH1(0x1)
+                    PUSH1(0x21)
                     PUSH1(0x00)
                     MSTORE
                     // Esize
-                    PUSH1(0x1)
+                    PUSH1(0x21)
                     PUSH1(0x20)
                     MSTORE
                     // Msize
@@ -1488,53 +1175,58 @@ mod test {
                     PUSH1(0x40)
                     MSTORE
                     // B, E and M
-                    PUSH32(word!("0x08090A0000000000000000000000000000000000000000000000000000000000"))
+                    PUSH32(word!("0x1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed"))
                     PUSH1(0x60)
                     MSTORE
+                    PUSH32(word!("0x198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2"))
+                    PUSH1(0x80)
+                    MSTORE
+                    PUSH32(word!("0x12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"))
+                    PUSH1(0xa0)
+                    MSTORE
+                    PUSH32(word!("0x08090A0000000000000000000000000000000000000000000000000000000000"))
+                    PUSH1(0xc0)
+                    MSTORE
                 },
                 call_data_offset: 0x0.into(),
-                call_data_length: 0x63.into(),
-                ret_offset: 0x9f.into(),
-                ret_size: 0x01.into(),
+                call_data_length: 0xc3.into(),
+                ret_offset: 0xe0.into(),
+                ret_size: 0x21.into(),
                 address: PrecompileCalls::Modexp.address().to_word(),
                 gas: 100000.into(),
                 ..Default::default()
             },
+        ]
+    });
+
+    static TEST_INVALID_VECTOR: LazyLock<Vec<PrecompileCallArgs>> = LazyLock::new(|| {
+        vec![
             PrecompileCallArgs {
-                name: "modexp Base&Esize&Msize length too large invalid",
+                name: "modexp Msize length too large invalid",
                 setup_code: bytecode! {
                     // Base size
-                    PUSH1(0x21)
+                    PUSH1(0x1)
                     PUSH1(0x00)
                     MSTORE
                     // Esize
-                    PUSH1(0x21)
+                    PUSH1(0x1)
                     PUSH1(0x20)
                     MSTORE
                     // Msize
-                    PUSH1(0x21)
+                    PUSH2(0x0101)
                     PUSH1(0x40)
                     MSTORE
                     // B, E and M
-                    PUSH32(word!("0x1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed"))
-                    PUSH1(0x60)
-                    MSTORE
-                    PUSH32(word!("0x198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2"))
-                    PUSH1(0x80)
-                    MSTORE
-                    PUSH32(word!("0x12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"))
-                    PUSH1(0xa0)
-                    MSTORE
                     PUSH32(word!("0x08090A0000000000000000000000000000000000000000000000000000000000"))
-                    PUSH1(0xc0)
+                    PUSH1(0x60)
                     MSTORE
                 },
                 call_data_offset: 0x0.into(),
-                call_data_length: 0xc3.into(),
-                ret_offset: 0xe0.into(),
-                ret_size: 0x21.into(),
+                call_data_length: 0x63.into(),
+                ret_offset: 0x9f.into(),
+                ret_size: 0x01.into(),
                 address: PrecompileCalls::Modexp.address().to_word(),
-                gas: 1000.into(),
+                gas: 100000.into(),
                 ..Default::default()
             },
             PrecompileCallArgs {
@@ -1609,6 +1301,83 @@ mod test {
         }
     }
 
+    #[test]
+    fn precompile_modexp_test_large_operands() {
+        // 33 bytes operands are rejected before the fork and computed after it
+        let bytecode = TEST_U256_VECTOR[2].with_call_op(OpcodeId::STATICCALL);
+        for chain_id in [MOCK_CHAIN_ID, MORPH_DEVNET_CHAIN_ID] {
+            let ctx = TestContext::<2, 1>::new(
+                None,
+                account_0_code_wallet_0_no_code(bytecode.clone()),
+                |mut txs, accs| {
+                    txs[0]
+                        .from(MOCK_WALLETS[0].clone())
+                        .to(accs[0].address)
+                        .chain_id(chain_id);
+                },
+                |block, _txs| block.chain_id(chain_id).number(0xcafeu64),
+            )
+            .unwrap();
+            with_large_modexp(|| CircuitTestBuilder::new_from_test_ctx(ctx).run());
+        }
+    }
+
+    #[test]
+    fn precompile_modexp_test_rows_limit() {
+        // a 256 bytes modulus is accepted with a 64 bytes exponent, not with a 65 bytes one, see
+        // `MODEXP_BLOCK_ROWS_LIMIT`. Both calls only take the header rows in the modexp circuit,
+        // as their modulus is zero.
+        for (exp_len, is_valid) in [(0x40u64, true), (0x41, false)] {
+            let bytecode = PrecompileCallArgs {
+                name: "modexp 256 bytes modulus",
+                setup_code: bytecode! {
+                    // Base size
+                    PUSH1(0x1)
+                    PUSH1(0x00)
+                    MSTORE
+                    // Esize
+                    PUSH1(exp_len)
+                    PUSH1(0x20)
+                    MSTORE
+                    // Msize
+                    PUSH2(0x0100)
+                    PUSH1(0x40)
+                    MSTORE
+                },
+                call_data_offset: 0x0.into(),
+                call_data_length: 0x60.into(),
+                ret_offset: 0x60.into(),
+                ret_size: 0x20.into(),
+                address: PrecompileCalls::Modexp.address().to_word(),
+                gas: 1_000_000.into(),
+                ..Default::default()
+            }
+            .with_call_op(OpcodeId::STATICCALL);
+            let mut header = [0u8; 96];
+            U256::from(1).to_big_endian(&mut header[..32]);
+            U256::from(exp_len).to_big_endian(&mut header[32..64]);
+            U256::from(0x100).to_big_endian(&mut header[64..]);
+            assert_eq!(
+                ModExpAuxData::check_input(&header, MODEXP_SIZE_LIMIT).0,
+                is_valid
+            );
+
+            let ctx = TestContext::<2, 1>::new(
+                None,
+                account_0_code_wallet_0_no_code(bytecode),
+                |mut txs, accs| {
+                    txs[0]
+                        .from(MOCK_WALLETS[0].clone())
+                        .to(accs[0].address)
+                        .chain_id(MORPH_DEVNET_CHAIN_ID);
+                },
+                |block, _txs| block.chain_id(MORPH_DEVNET_CHAIN_ID).number(0xcafeu64),
+            )
+            .unwrap();
+            with_large_modexp(|| CircuitTestBuilder::new_from_test_ctx(ctx).run());
+        }
+    }
+
     // notice, "invalid" test would not actuall work until bus-mapping put calling fail case being
     // handle in normal CallOp, i.e. return None in
     // bus_mapping::circuit_input_builder::input_state_ref::CircuitInputStateRef::get_step_err
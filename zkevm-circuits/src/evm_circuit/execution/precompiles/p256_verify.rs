@@ -0,0 +1,648 @@
+use crate::util::Field;
+use bus_mapping::precompile::{PrecompileAuxData, P256VERIFY_INPUT_LEN};
+use eth_types::{evm_types::GasCost, word, ToLittleEndian, U256};
+use gadgets::util::{and, not, select, Expr};
+use gadgets::ToScalar;
+use halo2_proofs::{
+    circuit::Value,
+    plonk::{Error, Expression},
+};
+use std::sync::LazyLock;
+
+use crate::{
+    evm_circuit::{
+        execution::ExecutionGadget,
+        param::N_BYTES_WORD,
+        step::ExecutionState,
+        util::{
+            common_gadget::RestoreContextGadget,
+            constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
+            math_gadget::{IsEqualGadget, LtWordGadget, ModGadget},
+            rlc, CachedRegion, Cell, Word,
+        },
+    },
+    table::CallContextFieldTag,
+    witness::{Block, Call, ExecStep, Transaction},
+};
+
+static FP_MODULUS: LazyLock<U256> =
+    LazyLock::new(|| word!("0xffffffff00000001000000000000000000000000ffffffffffffffffffffffff"));
+static FQ_MODULUS: LazyLock<U256> =
+    LazyLock::new(|| word!("0xffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551"));
+
+#[derive(Clone, Debug)]
+pub struct P256VerifyGadget<F> {
+    input_bytes_rlc: Cell<F>,
+    output_bytes_rlc: Cell<F>,
+    return_bytes_rlc: Cell<F>,
+
+    input_len_ok: IsEqualGadget<F>,
+
+    is_valid: Cell<F>,
+    msg_hash_keccak_rlc: Cell<F>,
+    sig_r_keccak_rlc: Cell<F>,
+    sig_s_keccak_rlc: Cell<F>,
+    pk_x_keccak_rlc: Cell<F>,
+    pk_y_keccak_rlc: Cell<F>,
+
+    msg_hash_raw: Word<F>,
+    msg_hash: Word<F>,
+    fq_modulus: Word<F>,
+    msg_hash_mod: ModGadget<F, true>,
+
+    sig_r: Word<F>,
+    sig_r_canonical: LtWordGadget<F>,
+    sig_s: Word<F>,
+    sig_s_canonical: LtWordGadget<F>,
+
+    fp_modulus: Word<F>,
+    pk_x: Word<F>,
+    pk_x_canonical: LtWordGadget<F>,
+    pk_y: Word<F>,
+    pk_y_canonical: LtWordGadget<F>,
+
+    is_success: Cell<F>,
+    callee_address: Cell<F>,
+    is_root: Cell<F>,
+    call_data_offset: Cell<F>,
+    call_data_length: Cell<F>,
+    return_data_offset: Cell<F>,
+    return_data_length: Cell<F>,
+    restore_context: RestoreContextGadget<F>,
+}
+
+impl<F: Field> ExecutionGadget<F> for P256VerifyGadget<F> {
+    const EXECUTION_STATE: ExecutionState = ExecutionState::PrecompileP256Verify;
+
+    const NAME: &'static str = "P256VERIFY";
+
+    fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
+        let (input_bytes_rlc, output_bytes_rlc, return_bytes_rlc) = (
+            cb.query_cell_phase2(),
+            cb.query_cell_phase2(),
+            cb.query_cell_phase2(),
+        );
+        let (
+            is_valid,
+            msg_hash_keccak_rlc,
+            sig_r_keccak_rlc,
+            sig_s_keccak_rlc,
+            pk_x_keccak_rlc,
+            pk_y_keccak_rlc,
+        ) = (
+            cb.query_bool(),
+            cb.query_cell_phase2(),
+            cb.query_cell_phase2(),
+            cb.query_cell_phase2(),
+            cb.query_cell_phase2(),
+            cb.query_cell_phase2(),
+        );
+
+        let msg_hash_raw = cb.query_word_rlc();
+        let msg_hash = cb.query_word_rlc();
+        let fq_modulus = cb.query_word_rlc();
+        let msg_hash_mod = ModGadget::construct(cb, [&msg_hash_raw, &fq_modulus, &msg_hash]);
+
+        let sig_r = cb.query_word_rlc();
+        let sig_r_canonical = LtWordGadget::construct(cb, &sig_r, &fq_modulus);
+        let sig_s = cb.query_word_rlc();
+        let sig_s_canonical = LtWordGadget::construct(cb, &sig_s, &fq_modulus);
+
+        let fp_modulus = cb.query_word_rlc();
+        let pk_x = cb.query_word_rlc();
+        let pk_x_canonical = LtWordGadget::construct(cb, &pk_x, &fp_modulus);
+        let pk_y = cb.query_word_rlc();
+        let pk_y_canonical = LtWordGadget::construct(cb, &pk_y, &fp_modulus);
+
+        let inputs_canonical = and::expr([
+            sig_r_canonical.expr(),
+            sig_s_canonical.expr(),
+            pk_x_canonical.expr(),
+            pk_y_canonical.expr(),
+        ]);
+
+        for (name, keccak_rlc, word) in [
+            (
+                "msg hash cells assigned incorrectly",
+                &msg_hash_keccak_rlc,
+                &msg_hash_raw,
+            ),
+            (
+                "sig_r cells assigned incorrectly",
+                &sig_r_keccak_rlc,
+                &sig_r,
+            ),
+            (
+                "sig_s cells assigned incorrectly",
+                &sig_s_keccak_rlc,
+                &sig_s,
+            ),
+            ("pk_x cells assigned incorrectly", &pk_x_keccak_rlc, &pk_x),
+            ("pk_y cells assigned incorrectly", &pk_y_keccak_rlc, &pk_y),
+        ] {
+            cb.require_equal(
+                name,
+                keccak_rlc.expr(),
+                cb.keccak_rlc::<N_BYTES_WORD>(
+                    word.cells
+                        .iter()
+                        .map(Expr::expr)
+                        .collect::<Vec<Expression<F>>>()
+                        .try_into()
+                        .expect("word is 32 bytes"),
+                ),
+            );
+        }
+        cb.require_equal(
+            "Secp256r1::Fq modulus assigned correctly",
+            fq_modulus.expr(),
+            cb.word_rlc::<N_BYTES_WORD>(FQ_MODULUS.to_le_bytes().map(|b| b.expr())),
+        );
+        cb.require_equal(
+            "Secp256r1::Fp modulus assigned correctly",
+            fp_modulus.expr(),
+            cb.word_rlc::<N_BYTES_WORD>(FP_MODULUS.to_le_bytes().map(|b| b.expr())),
+        );
+
+        let [is_success, callee_address, is_root, call_data_offset, call_data_length, return_data_offset, return_data_length] =
+            [
+                CallContextFieldTag::IsSuccess,
+                CallContextFieldTag::CalleeAddress,
+                CallContextFieldTag::IsRoot,
+                CallContextFieldTag::CallDataOffset,
+                CallContextFieldTag::CallDataLength,
+                CallContextFieldTag::ReturnDataOffset,
+                CallContextFieldTag::ReturnDataLength,
+            ]
+            .map(|tag| cb.call_context(None, tag));
+
+        let gas_cost = select::expr(
+            is_success.expr(),
+            GasCost::PRECOMPILE_P256VERIFY.expr(),
+            cb.curr.state.gas_left.expr(),
+        );
+
+        // RIP-7212 only accepts an input of exactly 160 bytes, any other length fails the
+        // verification without reverting.
+        let input_len_ok =
+            IsEqualGadget::construct(cb, call_data_length.expr(), P256VERIFY_INPUT_LEN.expr());
+
+        // lookup to the sign_verify table:
+        //
+        // || msg_hash | r | s | pk_x | pk_y | is_valid ||
+        cb.condition(
+            and::expr([input_len_ok.expr(), inputs_canonical.expr()]),
+            |cb| {
+                cb.p256_sig_table_lookup(
+                    msg_hash.expr(),
+                    sig_r.expr(),
+                    sig_s.expr(),
+                    pk_x.expr(),
+                    pk_y.expr(),
+                    is_valid.expr(),
+                );
+            },
+        );
+        cb.condition(not::expr(inputs_canonical.expr()), |cb| {
+            cb.require_zero(
+                "is_valid == false if r, s, pk_x or pk_y not canonical",
+                is_valid.expr(),
+            );
+        });
+        cb.condition(not::expr(input_len_ok.expr()), |cb| {
+            cb.require_zero("is_valid == false if input length != 160", is_valid.expr());
+        });
+
+        cb.precompile_info_lookup(
+            cb.execution_state().as_u64().expr(),
+            callee_address.expr(),
+            cb.execution_state().precompile_base_gas_cost().expr(),
+        );
+
+        let (r_pow_32, r_pow_64, r_pow_96, r_pow_128) = {
+            let challenges = cb.challenges().keccak_powers_of_randomness::<16>();
+            let r_pow_16 = challenges[15].clone();
+            let r_pow_32 = r_pow_16.square();
+            let r_pow_64 = r_pow_32.expr().square();
+            let r_pow_96 = r_pow_64.expr() * r_pow_32.expr();
+            let r_pow_128 = r_pow_64.expr().square();
+            (r_pow_32, r_pow_64, r_pow_96, r_pow_128)
+        };
+        cb.condition(input_len_ok.expr(), |cb| {
+            cb.require_equal(
+                "input bytes (RLC) = [msg_hash | sig_r | sig_s | pk_x | pk_y]",
+                input_bytes_rlc.expr(),
+                (msg_hash_keccak_rlc.expr() * r_pow_128)
+                    + (sig_r_keccak_rlc.expr() * r_pow_96)
+                    + (sig_s_keccak_rlc.expr() * r_pow_64)
+                    + (pk_x_keccak_rlc.expr() * r_pow_32)
+                    + pk_y_keccak_rlc.expr(),
+            );
+        });
+        // The output is a 32 bytes big-endian 1 if the signature is valid, and empty otherwise,
+        // so its RLC equals is_valid in both cases.
+        cb.require_equal(
+            "output bytes (RLC) = is_valid",
+            output_bytes_rlc.expr(),
+            is_valid.expr(),
+        );
+
+        let restore_context = super::gen_restore_context(
+            cb,
+            is_root.expr(),
+            is_success.expr(),
+            gas_cost.expr(),
+            select::expr(is_valid.expr(), 0x20.expr(), 0x00.expr()), // ReturnDataLength
+        );
+
+        Self {
+            input_bytes_rlc,
+            output_bytes_rlc,
+            return_bytes_rlc,
+
+            input_len_ok,
+
+            is_valid,
+            msg_hash_keccak_rlc,
+            sig_r_keccak_rlc,
+            sig_s_keccak_rlc,
+            pk_x_keccak_rlc,
+            pk_y_keccak_rlc,
+
+            msg_hash_raw,
+            msg_hash,
+            fq_modulus,
+            msg_hash_mod,
+
+            sig_r,
+            sig_r_canonical,
+            sig_s,
+            sig_s_canonical,
+
+            fp_modulus,
+            pk_x,
+            pk_x_canonical,
+            pk_y,
+            pk_y_canonical,
+
+            is_success,
+            callee_address,
+            is_root,
+            call_data_offset,
+            call_data_length,
+            return_data_offset,
+            return_data_length,
+            restore_context,
+        }
+    }
+
+    fn assign_exec_step(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block: &Block,
+        _tx: &Transaction,
+        call: &Call,
+        step: &ExecStep,
+    ) -> Result<(), Error> {
+        if let Some(PrecompileAuxData::P256Verify(aux_data)) = &step.aux_data {
+            for (cell, bytes) in [
+                (&self.input_bytes_rlc, &aux_data.input_bytes),
+                (&self.output_bytes_rlc, &aux_data.output_bytes),
+                (&self.return_bytes_rlc, &aux_data.return_bytes),
+            ] {
+                cell.assign(
+                    region,
+                    offset,
+                    region
+                        .challenges()
+                        .keccak_input()
+                        .map(|r| rlc::value(bytes.iter().rev(), r)),
+                )?;
+            }
+            self.input_len_ok.assign(
+                region,
+                offset,
+                F::from(call.call_data_length),
+                F::from(P256VERIFY_INPUT_LEN as u64),
+            )?;
+            self.is_valid.assign(
+                region,
+                offset,
+                Value::known(F::from(aux_data.is_valid as u64)),
+            )?;
+            for (cell, value) in [
+                (&self.msg_hash_keccak_rlc, aux_data.msg_hash),
+                (&self.sig_r_keccak_rlc, aux_data.sig_r),
+                (&self.sig_s_keccak_rlc, aux_data.sig_s),
+                (&self.pk_x_keccak_rlc, aux_data.pk_x),
+                (&self.pk_y_keccak_rlc, aux_data.pk_y),
+            ] {
+                cell.assign(
+                    region,
+                    offset,
+                    region
+                        .challenges()
+                        .keccak_input()
+                        .map(|r| rlc::value(&value.to_le_bytes(), r)),
+                )?;
+            }
+            for (word_rlc, value) in [
+                (&self.msg_hash_raw, aux_data.msg_hash),
+                (&self.sig_r, aux_data.sig_r),
+                (&self.sig_s, aux_data.sig_s),
+                (&self.pk_x, aux_data.pk_x),
+                (&self.pk_y, aux_data.pk_y),
+            ] {
+                word_rlc.assign(region, offset, Some(value.to_le_bytes()))?;
+            }
+            let (quotient, remainder) = aux_data.msg_hash.div_mod(*FQ_MODULUS);
+            self.msg_hash
+                .assign(region, offset, Some(remainder.to_le_bytes()))?;
+            self.fq_modulus
+                .assign(region, offset, Some(FQ_MODULUS.to_le_bytes()))?;
+            self.msg_hash_mod.assign(
+                region,
+                offset,
+                aux_data.msg_hash,
+                *FQ_MODULUS,
+                remainder,
+                quotient,
+            )?;
+            self.sig_r_canonical
+                .assign(region, offset, aux_data.sig_r, *FQ_MODULUS)?;
+            self.sig_s_canonical
+                .assign(region, offset, aux_data.sig_s, *FQ_MODULUS)?;
+            self.fp_modulus
+                .assign(region, offset, Some(FP_MODULUS.to_le_bytes()))?;
+            self.pk_x_canonical
+                .assign(region, offset, aux_data.pk_x, *FP_MODULUS)?;
+            self.pk_y_canonical
+                .assign(region, offset, aux_data.pk_y, *FP_MODULUS)?;
+        } else {
+            log::error!("unexpected aux_data {:?} for p256verify", step.aux_data);
+            return Err(Error::Synthesis);
+        }
+
+        self.is_success.assign(
+            region,
+            offset,
+            Value::known(F::from(u64::from(call.is_success))),
+        )?;
+        self.callee_address.assign(
+            region,
+            offset,
+            Value::known(call.code_address.unwrap().to_scalar().unwrap()),
+        )?;
+        self.is_root
+            .assign(region, offset, Value::known(F::from(call.is_root as u64)))?;
+        self.call_data_offset.assign(
+            region,
+            offset,
+            Value::known(F::from(call.call_data_offset)),
+        )?;
+        self.call_data_length.assign(
+            region,
+            offset,
+            Value::known(F::from(call.call_data_length)),
+        )?;
+        self.return_data_offset.assign(
+            region,
+            offset,
+            Value::known(F::from(call.return_data_offset)),
+        )?;
+        self.return_data_length.assign(
+            region,
+            offset,
+            Value::known(F::from(call.return_data_length)),
+        )?;
+        self.restore_context
+            .assign(region, offset, block, call, step, 7)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bus_mapping::{
+        evm::{OpcodeId, PrecompileCallArgs},
+        precompile::PrecompileCalls,
+    };
+    use eth_types::{
+        bytecode,
+        forks::{ChainSpec, ForkCondition, HardforkId, MORPH_DEVNET_CHAIN_ID},
+        word, Bytecode, ToWord, Word,
+    };
+    use itertools::Itertools;
+    use mock::{
+        test_ctx::helpers::account_0_code_wallet_0_no_code, TestContext, MOCK_CHAIN_ID,
+        MOCK_WALLETS,
+    };
+    use rayon::iter::{ParallelBridge, ParallelIterator};
+    use std::sync::LazyLock;
+
+    use crate::test_util::CircuitTestBuilder;
+
+    // Valid [msg_hash | r | s | pk_x | pk_y] from the RIP-7212 reference implementation.
+    static INPUT: LazyLock<[Word; 5]> = LazyLock::new(|| {
+        [
+            word!("0x4cee90eb86eaa050036147a12d49004b6b9c72bd725d39d4785011fe190f0b4d"),
+            word!("0xa73bd4903f0ce3b639bbbf6e8e80d16931ff4bcf5993d58468e8fb19086e8cac"),
+            word!("0x36dbcd03009df8c59286b162af3bd7fcc0450c9aa81be5d10d312af6c66b1d60"),
+            word!("0x4aebd3099c618202fcfe16ae7770b0c49ab5eadf74b754204a3bb6060e44eff3"),
+            word!("0x7618b065f9832de4ca6ca971a7a1adc826d0f7c00181a5fb2ddf79ae00b4e10e"),
+        ]
+    });
+
+    /// Place the valid input in memory from 0x00, with the word at `index` replaced by `value`.
+    fn setup_code(replaced: Option<(usize, Word)>) -> Bytecode {
+        let mut input = *INPUT;
+        if let Some((index, value)) = replaced {
+            input[index] = value;
+        }
+        let mut code = Bytecode::default();
+        for (i, value) in input.into_iter().enumerate() {
+            code.append(&bytecode! {
+                PUSH32(value)
+                PUSH1(i * 0x20)
+                MSTORE
+            });
+        }
+        code
+    }
+
+    static TEST_VECTOR: LazyLock<Vec<PrecompileCallArgs>> = LazyLock::new(|| {
+        vec![
+            PrecompileCallArgs {
+                name: "p256verify (valid sig)",
+                setup_code: setup_code(None),
+                call_data_offset: 0x00.into(),
+                call_data_length: 0xa0.into(),
+                ret_offset: 0xa0.into(),
+                ret_size: 0x20.into(),
+                address: PrecompileCalls::P256Verify.address().to_word(),
+                ..Default::default()
+            },
+            PrecompileCallArgs {
+                name: "p256verify (invalid sig, tampered msg hash)",
+                setup_code: setup_code(Some((0, INPUT[0] + 1))),
+                call_data_offset: 0x00.into(),
+                call_data_length: 0xa0.into(),
+                ret_offset: 0xa0.into(),
+                ret_size: 0x20.into(),
+                address: PrecompileCalls::P256Verify.address().to_word(),
+                ..Default::default()
+            },
+            PrecompileCallArgs {
+                name: "p256verify (pk not on curve)",
+                setup_code: setup_code(Some((4, INPUT[4] + 1))),
+                call_data_offset: 0x00.into(),
+                call_data_length: 0xa0.into(),
+                ret_offset: 0xa0.into(),
+                ret_size: 0x20.into(),
+                address: PrecompileCalls::P256Verify.address().to_word(),
+                ..Default::default()
+            },
+            PrecompileCallArgs {
+                name: "p256verify (zero sig_s)",
+                setup_code: setup_code(Some((2, Word::zero()))),
+                call_data_offset: 0x00.into(),
+                call_data_length: 0xa0.into(),
+                ret_offset: 0xa0.into(),
+                ret_size: 0x20.into(),
+                address: PrecompileCalls::P256Verify.address().to_word(),
+                ..Default::default()
+            },
+            PrecompileCallArgs {
+                name: "p256verify (overflowing sig_r)",
+                setup_code: setup_code(Some((
+                    1,
+                    word!("0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffee"),
+                ))),
+                call_data_offset: 0x00.into(),
+                call_data_length: 0xa0.into(),
+                ret_offset: 0xa0.into(),
+                ret_size: 0x20.into(),
+                address: PrecompileCalls::P256Verify.address().to_word(),
+                ..Default::default()
+            },
+            PrecompileCallArgs {
+                name: "p256verify (overflowing pk_x)",
+                setup_code: setup_code(Some((
+                    3,
+                    word!("0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffee"),
+                ))),
+                call_data_offset: 0x00.into(),
+                call_data_length: 0xa0.into(),
+                ret_offset: 0xa0.into(),
+                ret_size: 0x20.into(),
+                address: PrecompileCalls::P256Verify.address().to_word(),
+                ..Default::default()
+            },
+            PrecompileCallArgs {
+                name: "p256verify (short input)",
+                setup_code: setup_code(None),
+                call_data_offset: 0x00.into(),
+                call_data_length: 0x9f.into(),
+                ret_offset: 0xa0.into(),
+                ret_size: 0x20.into(),
+                address: PrecompileCalls::P256Verify.address().to_word(),
+                ..Default::default()
+            },
+            PrecompileCallArgs {
+                name: "p256verify (extra input bytes)",
+                setup_code: setup_code(None),
+                call_data_offset: 0x00.into(),
+                call_data_length: 0xa1.into(),
+                ret_offset: 0xa0.into(),
+                ret_size: 0x20.into(),
+                address: PrecompileCalls::P256Verify.address().to_word(),
+                ..Default::default()
+            },
+            PrecompileCallArgs {
+                name: "p256verify (empty input)",
+                setup_code: bytecode! {},
+                call_data_offset: 0x00.into(),
+                call_data_length: 0x00.into(),
+                ret_offset: 0x00.into(),
+                ret_size: 0x20.into(),
+                address: PrecompileCalls::P256Verify.address().to_word(),
+                ..Default::default()
+            },
+        ]
+    });
+
+    static OOG_TEST_VECTOR: LazyLock<Vec<PrecompileCallArgs>> = LazyLock::new(|| {
+        vec![PrecompileCallArgs {
+            name: "p256verify (oog)",
+            setup_code: setup_code(None),
+            call_data_offset: 0x00.into(),
+            call_data_length: 0xa0.into(),
+            ret_offset: 0xa0.into(),
+            ret_size: 0x20.into(),
+            gas: 0.into(),
+            value: 2.into(),
+            address: PrecompileCalls::P256Verify.address().to_word(),
+            ..Default::default()
+        }]
+    });
+
+    fn with_p256_verify<R: Send>(f: impl FnOnce() -> R + Send) -> R {
+        let specs = ChainSpec::morph_from_env()
+            .unwrap()
+            .into_iter()
+            .map(|spec| match spec.chain_id {
+                MORPH_DEVNET_CHAIN_ID => {
+                    spec.with_fork(HardforkId::P256Verify, ForkCondition::Block(0))
+                }
+                _ => spec,
+            })
+            .collect();
+        ChainSpec::scoped(specs, f)
+    }
+
+    /// Run the test vectors with every call kind, before the P256Verify fork, where 0x100 is an
+    /// empty account, and after it.
+    fn run_test_vectors(test_vectors: &[PrecompileCallArgs]) {
+        let call_kinds = vec![
+            OpcodeId::CALL,
+            OpcodeId::STATICCALL,
+            OpcodeId::DELEGATECALL,
+            OpcodeId::CALLCODE,
+        ];
+
+        with_p256_verify(|| {
+            test_vectors
+                .iter()
+                .cartesian_product(&call_kinds)
+                .cartesian_product([MOCK_CHAIN_ID, MORPH_DEVNET_CHAIN_ID])
+                .par_bridge()
+                .for_each(|((test_vector, &call_kind), chain_id)| {
+                    let bytecode = test_vector.with_call_op(call_kind);
+                    let test_ctx: TestContext<2, 1> = TestContext::new(
+                        None,
+                        account_0_code_wallet_0_no_code(bytecode),
+                        |mut txs, accs| {
+                            txs[0]
+                                .from(MOCK_WALLETS[0].clone())
+                                .to(accs[0].address)
+                                .chain_id(chain_id);
+                        },
+                        |block, _txs| block.chain_id(chain_id).number(0xcafeu64),
+                    )
+                    .unwrap();
+
+                    CircuitTestBuilder::new_from_test_ctx(test_ctx).run();
+                })
+        })
+    }
+
+    #[test]
+    fn precompile_p256_verify_test() {
+        run_test_vectors(&TEST_VECTOR);
+    }
+
+    #[test]
+    fn precompile_p256_verify_oog_test() {
+        run_test_vectors(&OOG_TEST_VECTOR);
+    }
+}
@@ -179,6 +179,10 @@ impl<F: Field> ExecutionGadget<F> for ReturnRevertGadget<F> {
             let prev_keccak_code_hash = cb.query_cell_phase2();
             #[cfg(feature = "scroll")]
             {
+                // the bytecode circuit proves the keccak hash of the deployed bytecode, which is
+                // in the bytecode table because of the copy table lookup above.
+                cb.bytecode_code_hashes(code_hash.expr(), keccak_code_hash.expr());
+
                 cb.account_read(
                     address.expr(),
                     AccountFieldTag::KeccakCodeHash,
@@ -0,0 +1,373 @@
+use crate::{
+    evm_circuit::{
+        execution::ExecutionGadget,
+        step::ExecutionState,
+        util::{
+            common_gadget::{BeaconRootWriteGadget, CurieUpgradeGadget, HistoryStorageWriteGadget},
+            constraint_builder::{
+                ConstrainBuilderCommon, EVMConstraintBuilder, StepStateTransition,
+                Transition::Delta,
+            },
+            not, CachedRegion, Cell,
+        },
+        witness::{Block, Call, ExecStep, Transaction},
+    },
+    table::{BlockContextFieldTag, RwTableTag},
+    util::{Expr, Field},
+};
+use bus_mapping::circuit_input_builder::{
+    beacon_roots::is_beacon_roots_enabled, curie::is_curie_fork_block,
+    history_storage::is_history_storage_enabled,
+};
+use halo2_proofs::{circuit::Value, plonk::Error};
+
+/// Pre-block protocol updates, applied before the first tx of each block once a fork of the
+/// system contracts is active, and of the curie fork block. The system does them on its own behalf: there is no tx, no gas
+/// and no signature.
+#[derive(Clone, Debug)]
+pub(crate) struct SystemCallGadget<F> {
+    /// The number of transactions in this block.
+    num_txs: Cell<F>,
+    /// The number of transactions up until this block, including the txs in this block.
+    cum_num_txs: Cell<F>,
+    beacon_root: BeaconRootWriteGadget<F>,
+    history_storage: HistoryStorageWriteGadget<F>,
+    curie: CurieUpgradeGadget<F>,
+}
+
+impl<F: Field> ExecutionGadget<F> for SystemCallGadget<F> {
+    const NAME: &'static str = "SystemCall";
+
+    const EXECUTION_STATE: ExecutionState = ExecutionState::SystemCall;
+
+    fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
+        // `cb.curr.state.block_number` is constrained inside execution.rs
+        let [num_txs, cum_num_txs] = [
+            BlockContextFieldTag::NumTxs,
+            BlockContextFieldTag::CumNumTxs,
+        ]
+        .map(|field_tag| {
+            let cell = cb.query_cell();
+            cb.block_lookup(
+                field_tag.expr(),
+                cb.curr.state.block_number.expr(),
+                cell.expr(),
+            );
+            cell
+        });
+        // The storage writes belong to the first tx of the block, or to the id right after the
+        // last tx if the block is empty.
+        let tx_id = cum_num_txs.expr() - num_txs.expr() + 1.expr();
+
+        // curie: upgrade of the L1 gas price oracle at the fork block
+        let curie = CurieUpgradeGadget::construct(cb, tx_id.expr());
+        // EIP-4788: parent beacon block root into the beacon roots contract
+        let beacon_root = BeaconRootWriteGadget::construct(cb, tx_id.expr());
+        // EIP-2935: parent hash into the history storage contract
+        let history_storage = HistoryStorageWriteGadget::construct(cb, tx_id);
+        // EndInnerBlock and the first BeginTx of the chunk check that the blocks with an active
+        // fork have a SystemCall step, and this that the others have none.
+        cb.require_zero(
+            "SystemCall writes into a system contract",
+            beacon_root.is_before_fork()
+                * history_storage.is_before_fork()
+                * not::expr(curie.is_fork_block()),
+        );
+
+        cb.require_step_state_transition(StepStateTransition {
+            rw_counter: Delta(
+                curie.rw_delta() + beacon_root.rw_delta() + history_storage.rw_delta(),
+            ),
+            ..StepStateTransition::any()
+        });
+
+        Self {
+            num_txs,
+            cum_num_txs,
+            beacon_root,
+            history_storage,
+            curie,
+        }
+    }
+
+    fn assign_exec_step(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block: &Block,
+        _: &Transaction,
+        _: &Call,
+        step: &ExecStep,
+    ) -> Result<(), Error> {
+        let num_txs = block
+            .txs
+            .iter()
+            .filter(|t| t.block_number == step.block_num)
+            .count();
+        let cum_num_txs = block
+            .txs
+            .iter()
+            .filter(|t| t.block_number <= step.block_num)
+            .count();
+        self.num_txs
+            .assign(region, offset, Value::known(F::from(num_txs as u64)))?;
+        self.cum_num_txs
+            .assign(region, offset, Value::known(F::from(cum_num_txs as u64)))?;
+
+        // the code hash reads are checked against constants
+        let mut rws = step
+            .rw_indices
+            .iter()
+            .filter(|idx| idx.0 == RwTableTag::AccountStorage)
+            .map(|idx| {
+                let (value, value_prev, _, committed_value) = block.rws[*idx].storage_value_aux();
+                (value, value_prev, committed_value)
+            });
+
+        let curie_writes = is_curie_fork_block(block.chain_id, step.block_num)
+            .then(|| [(); 4].map(|_| rws.next().unwrap()));
+        self.curie
+            .assign(region, offset, block.chain_id, step.block_num, curie_writes)?;
+
+        let beacon_root_writes = is_beacon_roots_enabled(block.chain_id, step.block_num)
+            .then(|| [(); 2].map(|_| rws.next().unwrap()));
+        self.beacon_root.assign(
+            region,
+            offset,
+            block.chain_id,
+            step.block_num,
+            block.context.ctxs[&step.block_num].timestamp.as_u64(),
+            beacon_root_writes,
+        )?;
+
+        // the genesis block has no parent hash to write
+        let history_storage_write = (is_history_storage_enabled(block.chain_id, step.block_num)
+            && step.block_num > 0)
+            .then(|| rws.next().unwrap());
+        self.history_storage.assign(
+            region,
+            offset,
+            block.chain_id,
+            step.block_num,
+            history_storage_write,
+        )?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{evm_circuit::step::ExecutionState, test_util::CircuitTestBuilder};
+    use eth_types::{
+        bytecode,
+        forks::{ChainSpec, ForkCondition, HardforkId, MORPH_DEVNET_CHAIN_ID},
+        l2_predeployed::{history_storage, l1_gas_price_oracle},
+        Word,
+    };
+    use mock::{eth, TestContext, MOCK_ACCOUNTS, MOCK_CHAIN_ID};
+
+    fn with_fork<R: Send>(fork: HardforkId, f: impl FnOnce() -> R + Send) -> R {
+        with_fork_at(fork, 0, f)
+    }
+
+    fn with_fork_at<R: Send>(fork: HardforkId, block: u64, f: impl FnOnce() -> R + Send) -> R {
+        let specs = ChainSpec::morph_from_env()
+            .unwrap()
+            .into_iter()
+            .map(|spec| match spec.chain_id {
+                MORPH_DEVNET_CHAIN_ID => spec.with_fork(fork, ForkCondition::Block(block)),
+                _ => spec,
+            })
+            .collect();
+        ChainSpec::scoped(specs, f)
+    }
+
+    /// Run the circuits, checking that the block starts with a SystemCall step
+    /// iff a fork of the system contracts is active.
+    fn run(ctx: TestContext<3, 1>, system_call: bool) {
+        CircuitTestBuilder::new_from_test_ctx(ctx)
+            .block_modifier(Box::new(move |block| {
+                assert_eq!(
+                    block
+                        .leading_steps
+                        .iter()
+                        .map(|step| step.execution_state)
+                        .collect::<Vec<_>>(),
+                    if system_call {
+                        vec![ExecutionState::SystemCall]
+                    } else {
+                        vec![]
+                    }
+                );
+            }))
+            .run();
+    }
+
+    /// Read the hash of the parent block, which the SystemCall writes into the history
+    /// storage contract.
+    fn test_history_storage(chain_id: u64) {
+        let block_number = 0xcafe_u64;
+        let code = bytecode! {
+            PUSH32(Word::from(block_number - 1))
+            BLOCKHASH
+            STOP
+        };
+        let history_hashes = (block_number - 256..block_number)
+            .map(|n| Word::from(0xbeef_0000u64 + n))
+            .collect();
+        let ctx = TestContext::<3, 1>::new(
+            Some(history_hashes),
+            |accs| {
+                accs[0].address(MOCK_ACCOUNTS[0]).code(code);
+                accs[1].address(MOCK_ACCOUNTS[1]).balance(eth(10));
+                accs[2]
+                    .address(*history_storage::ADDRESS)
+                    .code(history_storage::BYTECODE.clone());
+            },
+            |mut txs, accs| {
+                txs[0].from(accs[1].address).to(accs[0].address);
+            },
+            |block, _tx| block.chain_id(chain_id).number(block_number),
+        )
+        .unwrap();
+
+        run(ctx, chain_id == MORPH_DEVNET_CHAIN_ID);
+    }
+
+    #[test]
+    fn system_call_history_storage() {
+        with_fork(HardforkId::HistoryStorage, || {
+            test_history_storage(MORPH_DEVNET_CHAIN_ID)
+        });
+    }
+
+    #[test]
+    fn system_call_history_storage_before_fork() {
+        with_fork(HardforkId::HistoryStorage, || {
+            test_history_storage(MOCK_CHAIN_ID)
+        });
+    }
+
+    /// A transfer in the genesis block of a chain with the fork at genesis: the SystemCall reads
+    /// the code hash of the contract, but there is no parent hash to write.
+    #[test]
+    fn system_call_history_storage_at_genesis() {
+        with_fork(HardforkId::HistoryStorage, || {
+            let ctx = TestContext::<3, 1>::new(
+                None,
+                |accs| {
+                    accs[0].address(MOCK_ACCOUNTS[0]).balance(eth(1));
+                    accs[1].address(MOCK_ACCOUNTS[1]).balance(eth(10));
+                    accs[2]
+                        .address(*history_storage::ADDRESS)
+                        .code(history_storage::BYTECODE.clone());
+                },
+                |mut txs, accs| {
+                    txs[0].from(accs[1].address).to(accs[0].address);
+                },
+                |block, _tx| block.chain_id(MORPH_DEVNET_CHAIN_ID).number(0),
+            )
+            .unwrap();
+
+            run(ctx, true);
+        });
+    }
+
+    /// A transfer in the curie fork block, whose SystemCall upgrades the L1 gas price oracle.
+    #[test]
+    fn system_call_curie() {
+        let block_number = 0xcafe_u64;
+        with_fork_at(HardforkId::Curie, block_number, || {
+            let ctx = TestContext::<3, 1>::new(
+                None,
+                |accs| {
+                    accs[0].address(MOCK_ACCOUNTS[0]).balance(eth(1));
+                    accs[1].address(MOCK_ACCOUNTS[1]).balance(eth(10));
+                    accs[2]
+                        .address(*l1_gas_price_oracle::ADDRESS)
+                        .code(l1_gas_price_oracle::V1_BYTECODE.clone());
+                },
+                |mut txs, accs| {
+                    txs[0].from(accs[1].address).to(accs[0].address);
+                },
+                |block, _tx| block.chain_id(MORPH_DEVNET_CHAIN_ID).number(block_number),
+            )
+            .unwrap();
+
+            run(ctx, true);
+        });
+    }
+
+    // the beacon roots fork can not be scheduled with the scroll circuits
+    #[cfg(not(feature = "scroll"))]
+    mod beacon_roots {
+        use super::*;
+        use eth_types::{l2_predeployed::beacon_roots, ToWord, H256};
+
+        const TIMESTAMP: u64 = 1_700_000_000;
+
+        fn with_beacon_roots<R: Send>(f: impl FnOnce() -> R + Send) -> R {
+            with_fork(HardforkId::BeaconRoots, f)
+        }
+
+        /// Read the root of the current block from the beacon roots contract and return it.
+        fn test_ok(chain_id: u64, root: H256, expected: H256) {
+            let code = bytecode! {
+                PUSH32(Word::from(TIMESTAMP))
+                PUSH1(0x00)
+                MSTORE
+                PUSH1(0x20) // retLength
+                PUSH1(0x20) // retOffset
+                PUSH1(0x20) // argsLength
+                PUSH1(0x00) // argsOffset
+                PUSH20(beacon_roots::ADDRESS.to_word())
+                GAS
+                STATICCALL
+                POP
+                PUSH1(0x20)
+                PUSH1(0x20)
+                RETURN
+            };
+            let ctx = TestContext::<3, 1>::new(
+                None,
+                |accs| {
+                    accs[0].address(MOCK_ACCOUNTS[0]).code(code);
+                    accs[1].address(MOCK_ACCOUNTS[1]).balance(eth(10));
+                    accs[2]
+                        .address(*beacon_roots::ADDRESS)
+                        .code(beacon_roots::BYTECODE.clone());
+                },
+                |mut txs, accs| {
+                    txs[0].from(accs[1].address).to(accs[0].address);
+                },
+                |block, _tx| {
+                    block
+                        .chain_id(chain_id)
+                        .timestamp(TIMESTAMP.into())
+                        .parent_beacon_block_root(root)
+                },
+            )
+            .unwrap();
+            assert_eq!(
+                ctx.geth_traces[0].return_value.trim_start_matches("0x"),
+                format!("{expected:x}")
+            );
+
+            run(ctx, chain_id == MORPH_DEVNET_CHAIN_ID);
+        }
+
+        #[test]
+        fn system_call_beacon_root() {
+            let root = H256::repeat_byte(0xbe);
+            with_beacon_roots(|| test_ok(MORPH_DEVNET_CHAIN_ID, root, root));
+        }
+
+        #[test]
+        fn system_call_beacon_root_before_fork() {
+            // the root is not written, so the read reverts and returns nothing
+            let root = H256::repeat_byte(0xbe);
+            with_beacon_roots(|| test_ok(MOCK_CHAIN_ID, root, H256::zero()));
+        }
+    }
+}
@@ -3,8 +3,11 @@ use crate::{
         execution::ExecutionGadget,
         step::ExecutionState,
         util::{
-            common_gadget::SameContextGadget,
-            constraint_builder::{EVMConstraintBuilder, StepStateTransition, Transition::Delta},
+            common_gadget::{HardforkGadget, SameContextGadget},
+            constraint_builder::{
+                ConstrainBuilderCommon, EVMConstraintBuilder, StepStateTransition,
+                Transition::Delta,
+            },
             CachedRegion, Cell, StepRws, Word,
         },
         witness::{Block, Call, ExecStep, Transaction},
@@ -13,13 +16,14 @@ use crate::{
     util::{Expr, Field},
 };
 use bus_mapping::evm::OpcodeId;
-use eth_types::ToLittleEndian;
+use eth_types::{forks::HardforkId, ToLittleEndian};
 use gadgets::ToScalar;
 use halo2_proofs::{circuit::Value, plonk::Error};
 
 #[derive(Clone, Debug)]
 pub(crate) struct TloadGadget<F> {
     same_context: SameContextGadget<F>,
+    curie: HardforkGadget<F>,
     tx_id: Cell<F>,
     callee_address: Cell<F>,
     key: Word<F>,
@@ -34,6 +38,11 @@ impl<F: Field> ExecutionGadget<F> for TloadGadget<F> {
     fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
         let opcode = cb.query_cell();
 
+        // an invalid opcode before curie, see `ErrorInvalidOpcodeGadget`
+        let curie =
+            HardforkGadget::construct(cb, HardforkId::Curie, cb.curr.state.block_number.expr());
+        cb.require_zero("TLOAD is defined since curie", curie.is_before_fork.expr());
+
         let tx_id = cb.call_context(None, CallContextFieldTag::TxId);
         let callee_address = cb.call_context(None, CallContextFieldTag::CalleeAddress);
 
@@ -62,6 +71,7 @@ impl<F: Field> ExecutionGadget<F> for TloadGadget<F> {
 
         Self {
             same_context,
+            curie,
             tx_id,
             callee_address,
             key,
@@ -79,6 +89,8 @@ impl<F: Field> ExecutionGadget<F> for TloadGadget<F> {
         step: &ExecStep,
     ) -> Result<(), Error> {
         self.same_context.assign_exec_step(region, offset, step)?;
+        self.curie
+            .assign(region, offset, block.chain_id, tx.block_number)?;
 
         self.tx_id.assign(
             region,
@@ -3,7 +3,7 @@ use crate::{
         execution::ExecutionGadget,
         step::ExecutionState,
         util::{
-            common_gadget::SameContextGadget,
+            common_gadget::{HardforkGadget, SameContextGadget},
             constraint_builder::{
                 ConstrainBuilderCommon, EVMConstraintBuilder, ReversionInfo, StepStateTransition,
                 Transition::Delta,
@@ -16,13 +16,14 @@ use crate::{
     util::{Expr, Field},
 };
 use bus_mapping::evm::OpcodeId;
-use eth_types::ToLittleEndian;
+use eth_types::{forks::HardforkId, ToLittleEndian};
 use gadgets::ToScalar;
 use halo2_proofs::{circuit::Value, plonk::Error};
 
 #[derive(Clone, Debug)]
 pub(crate) struct TstoreGadget<F> {
     same_context: SameContextGadget<F>,
+    curie: HardforkGadget<F>,
     tx_id: Cell<F>,
     is_static: Cell<F>,
     reversion_info: ReversionInfo<F>,
@@ -40,6 +41,11 @@ impl<F: Field> ExecutionGadget<F> for TstoreGadget<F> {
     fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
         let opcode = cb.query_cell();
 
+        // an invalid opcode before curie, see `ErrorInvalidOpcodeGadget`
+        let curie =
+            HardforkGadget::construct(cb, HardforkId::Curie, cb.curr.state.block_number.expr());
+        cb.require_zero("TSTORE is defined since curie", curie.is_before_fork.expr());
+
         let tx_id = cb.call_context(None, CallContextFieldTag::TxId);
 
         // constraint not in static call
@@ -79,6 +85,7 @@ impl<F: Field> ExecutionGadget<F> for TstoreGadget<F> {
 
         Self {
             same_context,
+            curie,
             tx_id,
             is_static,
             reversion_info,
@@ -99,6 +106,8 @@ impl<F: Field> ExecutionGadget<F> for TstoreGadget<F> {
         step: &ExecStep,
     ) -> Result<(), Error> {
         self.same_context.assign_exec_step(region, offset, step)?;
+        self.curie
+            .assign(region, offset, block.chain_id, tx.block_number)?;
 
         self.tx_id.assign(
             region,
@@ -34,6 +34,7 @@ pub(crate) const EVM_LOOKUP_COLS: usize = FIXED_TABLE_LOOKUPS
     + TX_TABLE_LOOKUPS
     + RW_TABLE_LOOKUPS
     + BYTECODE_TABLE_LOOKUPS
+    + BYTECODE_CODE_HASHES_TABLE_LOOKUPS
     + BLOCK_TABLE_LOOKUPS
     + COPY_TABLE_LOOKUPS
     + KECCAK_TABLE_LOOKUPS
@@ -50,6 +51,10 @@ pub(crate) const LOOKUP_CONFIG: &[(Table, usize)] = &[
     (Table::Tx, TX_TABLE_LOOKUPS),
     (Table::Rw, RW_TABLE_LOOKUPS),
     (Table::Bytecode, BYTECODE_TABLE_LOOKUPS),
+    (
+        Table::BytecodeCodeHashes,
+        BYTECODE_CODE_HASHES_TABLE_LOOKUPS,
+    ),
     (Table::Block, BLOCK_TABLE_LOOKUPS),
     (Table::Copy, COPY_TABLE_LOOKUPS),
     (Table::Keccak, KECCAK_TABLE_LOOKUPS),
@@ -73,6 +78,9 @@ pub const RW_TABLE_LOOKUPS: usize = 8;
 /// Bytecode Table lookups done in EVMCircuit
 pub const BYTECODE_TABLE_LOOKUPS: usize = 1;
 
+/// Bytecode code hashes lookups done in EVMCircuit
+pub const BYTECODE_CODE_HASHES_TABLE_LOOKUPS: usize = 1;
+
 /// Block Table lookups done in EVMCircuit
 pub const BLOCK_TABLE_LOOKUPS: usize = 1;
 
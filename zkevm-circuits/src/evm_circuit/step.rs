@@ -14,6 +14,7 @@ use halo2_proofs::{
     circuit::Value,
     plonk::{Advice, Column, ConstraintSystem, Error, Expression},
 };
+use serde::{Deserialize, Serialize};
 use std::{fmt::Display, iter, marker::ConstParamTy};
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
@@ -30,12 +31,15 @@ impl From<PrecompileCalls> for ExecutionState {
             PrecompileCalls::Bn128Mul => ExecutionState::PrecompileBn256ScalarMul,
             PrecompileCalls::Bn128Pairing => ExecutionState::PrecompileBn256Pairing,
             PrecompileCalls::Blake2F => ExecutionState::PrecompileBlake2f,
+            PrecompileCalls::P256Verify => ExecutionState::PrecompileP256Verify,
         }
     }
 }
 
 #[allow(non_camel_case_types)]
-#[derive(ConstParamTy, Clone, Copy, Debug, PartialEq, Eq, Hash, EnumIter)]
+#[derive(
+    ConstParamTy, Clone, Copy, Debug, PartialEq, Eq, Hash, EnumIter, Serialize, Deserialize,
+)]
 pub enum ExecutionState {
     // Internal state
     BeginTx,
@@ -140,6 +144,9 @@ pub enum ExecutionState {
     PrecompileBn256ScalarMul,
     PrecompileBn256Pairing,
     PrecompileBlake2f,
+    PrecompileP256Verify,
+    // Internal state, appended to keep the indices of the dumps
+    SystemCall,
 }
 
 impl Default for ExecutionState {
@@ -175,6 +182,7 @@ impl ExecutionState {
                 | Self::PrecompileBn256ScalarMul
                 | Self::PrecompileBn256Pairing
                 | Self::PrecompileBlake2f
+                | Self::PrecompileP256Verify
                 | Self::ErrorOutOfGasPrecompile
                 | Self::ErrorPrecompileFailed
         )
@@ -191,6 +199,7 @@ impl ExecutionState {
             Self::PrecompileBn256ScalarMul => PrecompileCalls::Bn128Mul,
             Self::PrecompileBn256Pairing => PrecompileCalls::Bn128Pairing,
             Self::PrecompileBlake2f => PrecompileCalls::Blake2F,
+            Self::PrecompileP256Verify => PrecompileCalls::P256Verify,
             _ => return GasCost(0),
         })
         .base_gas_cost()
@@ -162,6 +162,7 @@ pub(crate) enum Table {
     Tx,
     Rw,
     Bytecode,
+    BytecodeCodeHashes,
     Block,
     Copy,
     Keccak,
@@ -265,6 +266,14 @@ pub(crate) enum Lookup<F> {
         /// Warning: If the bytecode is truncated, this is the actual data, without zero-padding.
         push_rlc: Expression<F>,
     },
+    /// Lookup to the code hashes of the bytecode table, which binds the code hash of each
+    /// bytecode to its keccak code hash.
+    BytecodeCodeHashes {
+        /// Hash of the code, which is the poseidon hash with `poseidon-codehash`.
+        code_hash: Expression<F>,
+        /// RLC of the keccak hash of the code.
+        keccak_code_hash: Expression<F>,
+    },
     /// Lookup to block table, which contains constants of this block.
     Block {
         /// Tag to specify which field to read.
@@ -332,18 +341,24 @@ pub(crate) enum Lookup<F> {
         exponentiation_lo_hi: [Expression<F>; 2],
     },
     SigTable {
+        curve: Expression<F>,
         msg_hash_rlc: Expression<F>,
         sig_v: Expression<F>,
         sig_r_rlc: Expression<F>,
         sig_s_rlc: Expression<F>,
         recovered_addr: Expression<F>,
         is_valid: Expression<F>,
+        pk_x_rlc: Expression<F>,
+        pk_y_rlc: Expression<F>,
     },
     ModExpTable {
-        base_limbs: [Expression<F>; 3],
-        exp_limbs: [Expression<F>; 3],
-        modulus_limbs: [Expression<F>; 3],
-        result_limbs: [Expression<F>; 3],
+        input_rlc: Expression<F>,
+        header_rlc: Expression<F>,
+        base_len: Expression<F>,
+        exp_len: Expression<F>,
+        modulus_len: Expression<F>,
+        exp_head_rlc: Expression<F>,
+        result_rlc: Expression<F>,
     },
     EccTable {
         op_type: Expression<F>,
@@ -375,6 +390,7 @@ impl<F: Field> Lookup<F> {
             Self::Tx { .. } => Table::Tx,
             Self::Rw { .. } => Table::Rw,
             Self::Bytecode { .. } => Table::Bytecode,
+            Self::BytecodeCodeHashes { .. } => Table::BytecodeCodeHashes,
             Self::Block { .. } => Table::Block,
             Self::CopyTable { .. } => Table::Copy,
             Self::KeccakTable { .. } => Table::Keccak,
@@ -442,6 +458,14 @@ impl<F: Field> Lookup<F> {
                     push_rlc.clone(),
                 ]
             }
+            Self::BytecodeCodeHashes {
+                code_hash,
+                keccak_code_hash,
+            } => vec![
+                1.expr(), // q_enable
+                code_hash.clone(),
+                keccak_code_hash.clone(),
+            ],
             Self::Block {
                 field_tag,
                 number,
@@ -516,40 +540,44 @@ impl<F: Field> Lookup<F> {
                 exponentiation_lo_hi[1].clone(),
             ],
             Self::SigTable {
+                curve,
                 msg_hash_rlc,
                 sig_v,
                 sig_r_rlc,
                 sig_s_rlc,
                 recovered_addr,
                 is_valid,
+                pk_x_rlc,
+                pk_y_rlc,
             } => vec![
                 1.expr(), // q_enable
+                curve.clone(),
                 msg_hash_rlc.clone(),
                 sig_v.clone(),
                 sig_r_rlc.clone(),
                 sig_s_rlc.clone(),
                 recovered_addr.clone(),
                 is_valid.clone(),
+                pk_x_rlc.clone(),
+                pk_y_rlc.clone(),
             ],
             Self::ModExpTable {
-                base_limbs,
-                exp_limbs,
-                modulus_limbs,
-                result_limbs,
+                input_rlc,
+                header_rlc,
+                base_len,
+                exp_len,
+                modulus_len,
+                exp_head_rlc,
+                result_rlc,
             } => vec![
                 1.expr(), // q_head
-                base_limbs[0].clone(),
-                exp_limbs[0].clone(),
-                modulus_limbs[0].clone(),
-                result_limbs[0].clone(),
-                base_limbs[1].clone(),
-                exp_limbs[1].clone(),
-                modulus_limbs[1].clone(),
-                result_limbs[1].clone(),
-                base_limbs[2].clone(),
-                exp_limbs[2].clone(),
-                modulus_limbs[2].clone(),
-                result_limbs[2].clone(),
+                input_rlc.clone(),
+                header_rlc.clone(),
+                base_len.clone(),
+                exp_len.clone(),
+                modulus_len.clone(),
+                exp_head_rlc.clone(),
+                result_rlc.clone(),
             ],
             Self::EccTable {
                 op_type,
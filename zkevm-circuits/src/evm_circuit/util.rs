@@ -206,6 +206,34 @@ impl<'r, 'b, F: Field> CachedRegion<'r, 'b, F> {
             [(((row_index - self.height_start) as i32) + rotation.0) as usize]
     }
 
+    /// Evaluate `expr` with the cached values of the step at `offset`.
+    pub fn evaluate(&self, expr: &Expression<F>, offset: usize) -> Value<F> {
+        expr.evaluate(
+            &|scalar| Value::known(scalar),
+            &|_| unimplemented!("selector column"),
+            &|fixed_query| {
+                Value::known(self.get_fixed(
+                    offset,
+                    fixed_query.column_index(),
+                    fixed_query.rotation(),
+                ))
+            },
+            &|advice_query| {
+                Value::known(self.get_advice(
+                    offset,
+                    advice_query.column_index(),
+                    advice_query.rotation(),
+                ))
+            },
+            &|_| unimplemented!("instance column"),
+            &|challenge| *self.challenges().indexed()[challenge.index()],
+            &|a| -a,
+            &|a, b| a + b,
+            &|a, b| a * b,
+            &|a, scalar| a * Value::known(scalar),
+        )
+    }
+
     pub fn challenges(&self) -> &Challenges<Value<F>> {
         self.challenges
     }
@@ -275,30 +303,7 @@ impl<F: Field> StoredExpression<F> {
         region: &mut CachedRegion<'_, '_, F>,
         offset: usize,
     ) -> Result<Value<F>, Error> {
-        let value = self.expr.evaluate(
-            &|scalar| Value::known(scalar),
-            &|_| unimplemented!("selector column"),
-            &|fixed_query| {
-                Value::known(region.get_fixed(
-                    offset,
-                    fixed_query.column_index(),
-                    fixed_query.rotation(),
-                ))
-            },
-            &|advice_query| {
-                Value::known(region.get_advice(
-                    offset,
-                    advice_query.column_index(),
-                    advice_query.rotation(),
-                ))
-            },
-            &|_| unimplemented!("instance column"),
-            &|challenge| *region.challenges().indexed()[challenge.index()],
-            &|a| -a,
-            &|a, b| a + b,
-            &|a, b| a * b,
-            &|a, scalar| a * Value::known(scalar),
-        );
+        let value = region.evaluate(&self.expr, offset);
         self.cell.assign(region, offset, value)?;
         Ok(value)
     }
@@ -720,7 +725,7 @@ pub(crate) fn transpose_val_ret<F, E>(value: Value<Result<F, E>>) -> Result<Valu
 }
 
 pub(crate) fn is_precompiled(address: &Address) -> bool {
-    address.0[0..19] == [0u8; 19] && (1..=9).contains(&address.0[19])
+    eth_types::utils::is_precompiled(address)
 }
 
 /// Helper struct to read rw operations from a step sequentially.
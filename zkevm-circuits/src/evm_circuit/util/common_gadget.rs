@@ -24,7 +24,7 @@ use crate::{
     witness::{Block, Call, ExecStep},
 };
 use either::Either;
-use eth_types::{evm_types::GasCost, ToLittleEndian, U256};
+use eth_types::{evm_types::GasCost, utils::hash_code_keccak, ToLittleEndian, ToWord, U256};
 use gadgets::util::{select, sum};
 use gadgets::ToScalar;
 use halo2_proofs::{
@@ -32,18 +32,51 @@ use halo2_proofs::{
     plonk::{Error, Expression},
 };
 
+mod beacon_roots;
 mod curie;
+mod hardfork;
+mod history_storage;
 mod tx_access_list;
 mod tx_eip1559;
 mod tx_l1_fee;
 mod tx_l1_msg;
 
-pub(crate) use curie::CurieGadget;
+pub(crate) use beacon_roots::BeaconRootWriteGadget;
+pub(crate) use curie::CurieUpgradeGadget;
+pub(crate) use hardfork::{ForkBlockGadget, HardforkGadget, SystemCallForksGadget};
+pub(crate) use history_storage::{
+    history_storage_address, HistoryStorageSlotGadget, HistoryStorageWriteGadget,
+};
 pub(crate) use tx_access_list::TxAccessListGadget;
 pub(crate) use tx_eip1559::TxEip1559Gadget;
 pub(crate) use tx_l1_fee::TxL1FeeGadget;
 pub(crate) use tx_l1_msg::TxL1MsgGadget;
 
+/// Read of the keccak code hash of the system contract at `address`, which must be the hash of
+/// `code`, the code of the EIP. The SystemCall applies the storage effects of the contract
+/// directly, this binds them to its code.
+pub(crate) fn system_contract_code_read<F: Field>(
+    cb: &mut EVMConstraintBuilder<F>,
+    address: Expression<F>,
+    code: &[u8],
+) {
+    let code_hash = cb.word_rlc(
+        hash_code_keccak(code)
+            .to_word()
+            .to_le_bytes()
+            .map(|byte| byte.expr()),
+    );
+    cb.account_read(
+        address,
+        if cfg!(feature = "scroll") {
+            AccountFieldTag::KeccakCodeHash
+        } else {
+            AccountFieldTag::CodeHash
+        },
+        code_hash,
+    );
+}
+
 /// Construction of execution state that stays in the same call context, which
 /// lookups the opcode and verifies the execution state is responsible for it,
 /// then calculates the gas_cost and constrain the state transition.
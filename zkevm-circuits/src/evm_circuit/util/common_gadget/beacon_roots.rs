@@ -0,0 +1,157 @@
+use super::{
+    system_contract_code_read, CachedRegion, Cell, HardforkGadget, HistoryStorageSlotGadget,
+};
+use crate::{
+    evm_circuit::util::{
+        constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
+        from_bytes, U64Word,
+    },
+    table::BlockContextFieldTag,
+    util::{Expr, Field},
+};
+use bus_mapping::l2_predeployed::beacon_roots::{self, HISTORY_BUFFER_LENGTH};
+use eth_types::{evm_types::block_utils::HISTORY_SERVE_WINDOW, forks::HardforkId, U256};
+use gadgets::{util::not, ToScalar};
+use halo2_proofs::plonk::{Error, Expression};
+
+// The timestamp slot is the timestamp modulo the length of the ring buffer, which is computed
+// by the slot gadget of the history storage contract.
+const _: () = assert!(HISTORY_BUFFER_LENGTH == HISTORY_SERVE_WINDOW);
+
+/// The address of the beacon roots contract
+fn beacon_roots_address<F: Field>() -> Expression<F> {
+    Expression::Constant(
+        beacon_roots::ADDRESS
+            .to_scalar()
+            .expect("Unexpected address of beacon roots contract -> Scalar conversion failure"),
+    )
+}
+
+/// Writes of the timestamp and the parent beacon block root into the beacon roots contract,
+/// done by the system call of each block once the fork is active.
+#[derive(Clone, Debug)]
+pub(crate) struct BeaconRootWriteGadget<F> {
+    fork: HardforkGadget<F>,
+    timestamp: U64Word<F>,
+    /// `timestamp % HISTORY_BUFFER_LENGTH`, holding the timestamp
+    timestamp_slot: HistoryStorageSlotGadget<F>,
+    /// `timestamp_slot + HISTORY_BUFFER_LENGTH`, holding the root
+    root_slot: U64Word<F>,
+    root: Cell<F>,
+    value_prev: [Cell<F>; 2],
+    committed_value: [Cell<F>; 2],
+}
+
+impl<F: Field> BeaconRootWriteGadget<F> {
+    pub(crate) fn construct(cb: &mut EVMConstraintBuilder<F>, tx_id: Expression<F>) -> Self {
+        let fork = HardforkGadget::construct(
+            cb,
+            HardforkId::BeaconRoots,
+            cb.curr.state.block_number.expr(),
+        );
+
+        let timestamp = cb.query_word_rlc();
+        cb.block_lookup(
+            BlockContextFieldTag::Timestamp.expr(),
+            cb.curr.state.block_number.expr(),
+            from_bytes::expr(&timestamp.cells),
+        );
+
+        let root_slot = cb.query_word_rlc();
+        let root = cb.query_cell_phase2();
+        let value_prev = [(); 2].map(|_| cb.query_cell_phase2());
+        let committed_value = [(); 2].map(|_| cb.query_cell_phase2());
+        let timestamp_slot = cb.condition(not::expr(fork.is_before_fork.expr()), |cb| {
+            system_contract_code_read(cb, beacon_roots_address(), &beacon_roots::BYTECODE);
+            let timestamp_slot =
+                HistoryStorageSlotGadget::construct(cb, from_bytes::expr(&timestamp.cells));
+            cb.require_equal(
+                "root_slot == timestamp_slot + HISTORY_BUFFER_LENGTH",
+                from_bytes::expr(&root_slot.cells),
+                timestamp_slot.slot_value() + HISTORY_BUFFER_LENGTH.expr(),
+            );
+
+            // The block table of the scroll circuits holds no beacon root, the fork can not be
+            // scheduled there, see `HardforkId::is_schedulable`.
+            #[cfg(not(feature = "scroll"))]
+            cb.block_lookup(
+                BlockContextFieldTag::ParentBeaconBlockRoot.expr(),
+                cb.curr.state.block_number.expr(),
+                root.expr(),
+            );
+            cb.account_storage_write(
+                beacon_roots_address(),
+                timestamp_slot.slot_rlc(),
+                timestamp.expr(),
+                value_prev[0].expr(),
+                tx_id.expr(),
+                committed_value[0].expr(),
+                None,
+            );
+            cb.account_storage_write(
+                beacon_roots_address(),
+                root_slot.expr(),
+                root.expr(),
+                value_prev[1].expr(),
+                tx_id,
+                committed_value[1].expr(),
+                None,
+            );
+            timestamp_slot
+        });
+
+        Self {
+            fork,
+            timestamp,
+            timestamp_slot,
+            root_slot,
+            root,
+            value_prev,
+            committed_value,
+        }
+    }
+
+    /// `writes` are the (value, value_prev, committed_value) of the timestamp and the root
+    /// writes, if the fork is active.
+    pub(crate) fn assign(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        chain_id: u64,
+        block_number: u64,
+        timestamp: u64,
+        writes: Option<[(U256, U256, U256); 2]>,
+    ) -> Result<(), Error> {
+        self.fork.assign(region, offset, chain_id, block_number)?;
+        self.timestamp
+            .assign(region, offset, Some(timestamp.to_le_bytes()))?;
+        self.timestamp_slot.assign(region, offset, timestamp)?;
+        self.root_slot.assign(
+            region,
+            offset,
+            Some((timestamp % HISTORY_BUFFER_LENGTH + HISTORY_BUFFER_LENGTH).to_le_bytes()),
+        )?;
+
+        let [(_, timestamp_prev, timestamp_committed), (root, root_prev, root_committed)] =
+            writes.unwrap_or_default();
+        for (cell, value) in [
+            (&self.root, root),
+            (&self.value_prev[0], timestamp_prev),
+            (&self.committed_value[0], timestamp_committed),
+            (&self.value_prev[1], root_prev),
+            (&self.committed_value[1], root_committed),
+        ] {
+            cell.assign(region, offset, region.word_rlc(value))?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn is_before_fork(&self) -> Expression<F> {
+        self.fork.is_before_fork.expr()
+    }
+
+    pub(crate) fn rw_delta(&self) -> Expression<F> {
+        // the code hash read and the 2 writes
+        3.expr() * not::expr(self.fork.is_before_fork.expr())
+    }
+}
@@ -1,93 +1,66 @@
-use super::{CachedRegion, Cell};
+use super::{CachedRegion, Cell, ForkBlockGadget};
 use crate::{
-    evm_circuit::{
-        table::{FixedTableTag, Lookup},
-        util::{
-            constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
-            math_gadget::{BatchedIsZeroGadget, LtGadget},
-        },
-    },
-    table::BlockContextFieldTag,
+    evm_circuit::util::constraint_builder::EVMConstraintBuilder,
     util::{Expr, Field},
 };
+use bus_mapping::l2_predeployed::l1_gas_price_oracle;
+use eth_types::{forks::HardforkId, Word, U256};
+use gadgets::ToScalar;
+use halo2_proofs::plonk::{Error, Expression};
 
-use eth_types::forks::{
-    HardforkId, MORPH_DEVNET_CHAIN_ID, MORPH_MAINNET_CHAIN_ID, MORPH_TESTNET_CHAIN_ID,
-};
-use gadgets::util::not;
-use halo2_proofs::{
-    circuit::Value,
-    plonk::{Error, Expression},
-};
-
+/// Writes of the storage of the L1 gas price oracle upgraded by curie, done by the system call
+/// of the fork block.
+/// Ref: bus-mapping/src/circuit_input_builder/curie.rs
 #[derive(Clone, Debug)]
-pub(crate) struct CurieGadget<F> {
-    chain_id: Cell<F>,
-    /// Morph chains have non-zero curie hard fork block number
-    is_morph_chain: BatchedIsZeroGadget<F, 3>,
-    /// The block height at which curie hard fork happens
-    curie_fork_block_num: Cell<F>,
-    pub(crate) is_before_curie: LtGadget<F, 8>, // block num is u64
+pub(crate) struct CurieUpgradeGadget<F> {
+    fork_block: ForkBlockGadget<F>,
+    value_prev: [Cell<F>; 4],
+    committed_value: [Cell<F>; 4],
 }
 
-impl<F: Field> CurieGadget<F> {
-    pub(crate) fn construct(cb: &mut EVMConstraintBuilder<F>, block_number: Expression<F>) -> Self {
-        let chain_id = cb.query_cell();
-        // Lookup block table with chain_id
-        cb.block_lookup(
-            BlockContextFieldTag::ChainId.expr(),
-            block_number.expr(),
-            chain_id.expr(),
-        );
+impl<F: Field> CurieUpgradeGadget<F> {
+    pub(crate) fn construct(cb: &mut EVMConstraintBuilder<F>, tx_id: Expression<F>) -> Self {
+        let fork_block =
+            ForkBlockGadget::construct(cb, HardforkId::Curie, cb.curr.state.block_number.expr());
 
-        // TODO: refactor
-        // is_morph_chain means (chain_id - 2818) * (chain_id - 2810) * (chain_id - 53077) == 0
-        // let is_morph_chain = IsZeroGadget::construct(
-        //     cb,
-        //     (chain_id.expr() - MORPH_MAINNET_CHAIN_ID.expr())
-        //         * (chain_id.expr() - MORPH_TESTNET_CHAIN_ID.expr())
-        //         * (chain_id.expr() - MORPH_DEVNET_CHAIN_ID.expr()),
-        // );
-        let is_morph_chain = BatchedIsZeroGadget::construct(
-            cb,
-            [
-                chain_id.expr() - MORPH_MAINNET_CHAIN_ID.expr(),
-                chain_id.expr() - MORPH_TESTNET_CHAIN_ID.expr(),
-                chain_id.expr() - MORPH_DEVNET_CHAIN_ID.expr(),
-            ],
-        );
-
-        // For Morph Networks (mainnet, testnet, devnet),
-        // curie_fork_block_num should be pre-defined.
-        // For other chain ids, it should be 0.
-        let curie_fork_block_num = cb.query_cell();
-        cb.condition(is_morph_chain.expr(), |cb| {
-            cb.add_lookup(
-                "Hardfork lookup",
-                Lookup::Fixed {
-                    tag: FixedTableTag::ChainFork.expr(),
-                    values: [
-                        (HardforkId::Curie as u64).expr(),
-                        chain_id.expr(),
-                        curie_fork_block_num.expr(),
-                    ],
-                },
-            );
-        });
-        cb.condition(not::expr(is_morph_chain.expr()), |cb| {
-            cb.require_zero("enable curie since genesis", curie_fork_block_num.expr());
+        let l1_fee_address = Expression::Constant(l1_gas_price_oracle::ADDRESS.to_scalar().expect(
+            "Unexpected address of l2 gasprice oracle contract -> Scalar conversion failure",
+        ));
+        let value_prev = [(); 4].map(|_| cb.query_cell_phase2());
+        let committed_value = [(); 4].map(|_| cb.query_cell_phase2());
+        cb.condition(fork_block.expr(), |cb| {
+            for (((slot, value), value_prev), committed_value) in [
+                (*l1_gas_price_oracle::IS_CURIE_SLOT, Word::from(1)),
+                (*l1_gas_price_oracle::L1_BLOB_BASEFEE_SLOT, Word::from(1)),
+                (
+                    *l1_gas_price_oracle::COMMIT_SCALAR_SLOT,
+                    *l1_gas_price_oracle::INITIAL_COMMIT_SCALAR,
+                ),
+                (
+                    *l1_gas_price_oracle::BLOB_SCALAR_SLOT,
+                    *l1_gas_price_oracle::INITIAL_BLOB_SCALAR,
+                ),
+            ]
+            .into_iter()
+            .zip(value_prev.iter())
+            .zip(committed_value.iter())
+            {
+                cb.account_storage_write(
+                    l1_fee_address.expr(),
+                    cb.word_rlc_constant(slot),
+                    cb.word_rlc_constant(value),
+                    value_prev.expr(),
+                    tx_id.expr(),
+                    committed_value.expr(),
+                    None,
+                );
+            }
         });
 
-        let is_before_curie = LtGadget::construct(
-            cb,
-            cb.curr.state.block_number.expr(),
-            curie_fork_block_num.expr(),
-        );
         Self {
-            chain_id,
-            is_morph_chain,
-            curie_fork_block_num,
-            is_before_curie,
+            fork_block,
+            value_prev,
+            committed_value,
         }
     }
 
@@ -97,34 +70,27 @@ impl<F: Field> CurieGadget<F> {
         offset: usize,
         chain_id: u64,
         block_number: u64,
+        writes: Option<[(U256, U256, U256); 4]>,
     ) -> Result<(), Error> {
-        self.chain_id
-            .assign(region, offset, Value::known(F::from(chain_id)))?;
-        self.is_morph_chain.assign(
-            region,
-            offset,
-            [
-                F::from(chain_id) - F::from(MORPH_MAINNET_CHAIN_ID),
-                F::from(chain_id) - F::from(MORPH_TESTNET_CHAIN_ID),
-                F::from(chain_id) - F::from(MORPH_DEVNET_CHAIN_ID),
-            ],
-        )?;
-        let curie_fork_block_num = if chain_id == MORPH_TESTNET_CHAIN_ID {
-            0
-        } else {
-            bus_mapping::circuit_input_builder::curie::get_curie_fork_block(chain_id)
-        };
-        self.curie_fork_block_num.assign(
-            region,
-            offset,
-            Value::known(F::from(curie_fork_block_num)),
-        )?;
-        self.is_before_curie.assign(
-            region,
-            offset,
-            F::from(block_number),
-            F::from(curie_fork_block_num),
-        )?;
+        self.fork_block
+            .assign(region, offset, chain_id, block_number)?;
+        for ((value_prev, committed_value), (_, prev, committed)) in self
+            .value_prev
+            .iter()
+            .zip(self.committed_value.iter())
+            .zip(writes.unwrap_or_default())
+        {
+            value_prev.assign(region, offset, region.word_rlc(prev))?;
+            committed_value.assign(region, offset, region.word_rlc(committed))?;
+        }
         Ok(())
     }
+
+    pub(crate) fn is_fork_block(&self) -> Expression<F> {
+        self.fork_block.expr()
+    }
+
+    pub(crate) fn rw_delta(&self) -> Expression<F> {
+        4.expr() * self.fork_block.expr()
+    }
 }
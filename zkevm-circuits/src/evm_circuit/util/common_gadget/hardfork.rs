@@ -0,0 +1,226 @@
+use super::{CachedRegion, Cell};
+use crate::{
+    evm_circuit::{
+        table::{FixedTableTag, Lookup},
+        util::{
+            constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
+            math_gadget::{BatchedIsZeroGadget, IsZeroGadget, LtGadget},
+        },
+    },
+    table::BlockContextFieldTag,
+    util::{Expr, Field},
+};
+
+use eth_types::forks::{
+    ChainSpec, HardforkId, MORPH_DEVNET_CHAIN_ID, MORPH_MAINNET_CHAIN_ID, MORPH_TESTNET_CHAIN_ID,
+};
+use gadgets::util::not;
+use halo2_proofs::{
+    circuit::Value,
+    plonk::{Error, Expression},
+};
+
+/// Whether a hardfork is active at `block_number`. The Morph chains look the fork height up
+/// in the ChainFork fixed table, other chains use the fork's default activation, which matches
+/// the witness as only Morph chains can be given a spec, see `ChainSpec::check`.
+#[derive(Clone, Debug)]
+pub(crate) struct HardforkGadget<F> {
+    fork: HardforkId,
+    chain_id: Cell<F>,
+    /// Morph chains have pre-defined hard fork block numbers
+    is_morph_chain: BatchedIsZeroGadget<F, 3>,
+    /// The block height at which the hard fork happens
+    fork_block_num: Cell<F>,
+    pub(crate) is_before_fork: LtGadget<F, 8>, // block num is u64
+}
+
+impl<F: Field> HardforkGadget<F> {
+    pub(crate) fn construct(
+        cb: &mut EVMConstraintBuilder<F>,
+        fork: HardforkId,
+        block_number: Expression<F>,
+    ) -> Self {
+        let chain_id = cb.query_cell();
+        // Lookup block table with chain_id
+        cb.block_lookup(
+            BlockContextFieldTag::ChainId.expr(),
+            block_number.expr(),
+            chain_id.expr(),
+        );
+
+        // TODO: refactor
+        // is_morph_chain means (chain_id - 2818) * (chain_id - 2810) * (chain_id - 53077) == 0
+        // let is_morph_chain = IsZeroGadget::construct(
+        //     cb,
+        //     (chain_id.expr() - MORPH_MAINNET_CHAIN_ID.expr())
+        //         * (chain_id.expr() - MORPH_TESTNET_CHAIN_ID.expr())
+        //         * (chain_id.expr() - MORPH_DEVNET_CHAIN_ID.expr()),
+        // );
+        let is_morph_chain = BatchedIsZeroGadget::construct(
+            cb,
+            [
+                chain_id.expr() - MORPH_MAINNET_CHAIN_ID.expr(),
+                chain_id.expr() - MORPH_TESTNET_CHAIN_ID.expr(),
+                chain_id.expr() - MORPH_DEVNET_CHAIN_ID.expr(),
+            ],
+        );
+
+        // For Morph Networks (mainnet, testnet, devnet),
+        // fork_block_num should be pre-defined.
+        // For other chain ids, it is the default of the fork: 0 for curie, never for the opt-in
+        // forks.
+        let fork_block_num = cb.query_cell();
+        cb.condition(is_morph_chain.expr(), |cb| {
+            cb.add_lookup(
+                "Hardfork lookup",
+                Lookup::Fixed {
+                    tag: FixedTableTag::ChainFork.expr(),
+                    values: [(fork as u64).expr(), chain_id.expr(), fork_block_num.expr()],
+                },
+            );
+        });
+        cb.condition(not::expr(is_morph_chain.expr()), |cb| {
+            cb.require_equal(
+                "fork at its default height",
+                fork_block_num.expr(),
+                Self::default_fork_block(fork).expr(),
+            );
+        });
+
+        let is_before_fork = LtGadget::construct(cb, block_number, fork_block_num.expr());
+        Self {
+            fork,
+            chain_id,
+            is_morph_chain,
+            fork_block_num,
+            is_before_fork,
+        }
+    }
+
+    pub(crate) fn assign(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        chain_id: u64,
+        block_number: u64,
+    ) -> Result<(), Error> {
+        self.chain_id
+            .assign(region, offset, Value::known(F::from(chain_id)))?;
+        self.is_morph_chain.assign(
+            region,
+            offset,
+            [
+                F::from(chain_id) - F::from(MORPH_MAINNET_CHAIN_ID),
+                F::from(chain_id) - F::from(MORPH_TESTNET_CHAIN_ID),
+                F::from(chain_id) - F::from(MORPH_DEVNET_CHAIN_ID),
+            ],
+        )?;
+        let fork_block_num = self.fork_block_num(chain_id);
+        self.fork_block_num
+            .assign(region, offset, Value::known(F::from(fork_block_num)))?;
+        self.is_before_fork.assign(
+            region,
+            offset,
+            F::from(block_number),
+            F::from(fork_block_num),
+        )?;
+        Ok(())
+    }
+
+    /// The height of the fork on `chain_id`: the same heights as the ChainFork fixed table,
+    /// which only holds the Morph chains since `ChainSpec::check` rejects the specs of other
+    /// chains.
+    fn fork_block_num(&self, chain_id: u64) -> u64 {
+        ChainSpec::for_chain_id(chain_id).fork_block(self.fork)
+    }
+
+    fn default_fork_block(fork: HardforkId) -> u64 {
+        ChainSpec::new(0).fork_block(fork)
+    }
+}
+
+/// Whether `block_number` is the block at which a hardfork activates.
+#[derive(Clone, Debug)]
+pub(crate) struct ForkBlockGadget<F> {
+    fork: HardforkGadget<F>,
+    is_fork_block: IsZeroGadget<F>,
+}
+
+impl<F: Field> ForkBlockGadget<F> {
+    pub(crate) fn construct(
+        cb: &mut EVMConstraintBuilder<F>,
+        fork: HardforkId,
+        block_number: Expression<F>,
+    ) -> Self {
+        let fork = HardforkGadget::construct(cb, fork, block_number.expr());
+        let is_fork_block = IsZeroGadget::construct(cb, block_number - fork.fork_block_num.expr());
+        Self {
+            fork,
+            is_fork_block,
+        }
+    }
+
+    pub(crate) fn expr(&self) -> Expression<F> {
+        self.is_fork_block.expr()
+    }
+
+    pub(crate) fn assign(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        chain_id: u64,
+        block_number: u64,
+    ) -> Result<(), Error> {
+        self.fork.assign(region, offset, chain_id, block_number)?;
+        self.is_fork_block.assign(
+            region,
+            offset,
+            F::from(block_number) - F::from(self.fork.fork_block_num(chain_id)),
+        )?;
+        Ok(())
+    }
+}
+
+/// Whether a block starts with a SystemCall step: one of the forks of the system contracts is
+/// active at `block_number`, or it is the curie fork block.
+#[derive(Clone, Debug)]
+pub(crate) struct SystemCallForksGadget<F> {
+    beacon_roots: HardforkGadget<F>,
+    history_storage: HardforkGadget<F>,
+    curie: ForkBlockGadget<F>,
+}
+
+impl<F: Field> SystemCallForksGadget<F> {
+    pub(crate) fn construct(cb: &mut EVMConstraintBuilder<F>, block_number: Expression<F>) -> Self {
+        let [beacon_roots, history_storage] = [HardforkId::BeaconRoots, HardforkId::HistoryStorage]
+            .map(|fork| HardforkGadget::construct(cb, fork, block_number.expr()));
+        let curie = ForkBlockGadget::construct(cb, HardforkId::Curie, block_number);
+        Self {
+            beacon_roots,
+            history_storage,
+            curie,
+        }
+    }
+
+    pub(crate) fn expr(&self) -> Expression<F> {
+        not::expr(
+            self.beacon_roots.is_before_fork.expr()
+                * self.history_storage.is_before_fork.expr()
+                * not::expr(self.curie.expr()),
+        )
+    }
+
+    pub(crate) fn assign(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        chain_id: u64,
+        block_number: u64,
+    ) -> Result<(), Error> {
+        self.beacon_roots
+            .assign(region, offset, chain_id, block_number)?;
+        self.history_storage
+            .assign(region, offset, chain_id, block_number)?;
+        self.curie.assign(region, offset, chain_id, block_number)
+    }
+}
@@ -0,0 +1,312 @@
+use super::{system_contract_code_read, CachedRegion, Cell, HardforkGadget};
+use crate::{
+    evm_circuit::{
+        param::N_BYTES_U64,
+        util::{
+            constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
+            from_bytes,
+            math_gadget::{IsZeroGadget, LtGadget, RangeCheckGadget},
+            U64Word,
+        },
+    },
+    table::BlockContextFieldTag,
+    util::{Expr, Field},
+};
+use bus_mapping::l2_predeployed::history_storage;
+use eth_types::{evm_types::block_utils::HISTORY_SERVE_WINDOW, forks::HardforkId, U256};
+use gadgets::{util::not, ToScalar};
+use halo2_proofs::{
+    circuit::Value,
+    plonk::{Error, Expression},
+};
+
+/// The address of the history storage contract
+pub(crate) fn history_storage_address<F: Field>() -> Expression<F> {
+    Expression::Constant(
+        history_storage::ADDRESS
+            .to_scalar()
+            .expect("Unexpected address of history storage contract -> Scalar conversion failure"),
+    )
+}
+
+/// Slot of the history storage contract holding the hash of a block: the block number modulo
+/// `HISTORY_SERVE_WINDOW`.
+#[derive(Clone, Debug)]
+pub(crate) struct HistoryStorageSlotGadget<F> {
+    quotient: Cell<F>,
+    quotient_range: RangeCheckGadget<F, N_BYTES_U64>,
+    slot: U64Word<F>,
+    slot_lt_window: LtGadget<F, 2>,
+}
+
+impl<F: Field> HistoryStorageSlotGadget<F> {
+    pub(crate) fn construct(cb: &mut EVMConstraintBuilder<F>, block_number: Expression<F>) -> Self {
+        let quotient = cb.query_cell();
+        let quotient_range = RangeCheckGadget::construct(cb, quotient.expr());
+        let slot = cb.query_word_rlc();
+        let slot_lt_window = LtGadget::construct(
+            cb,
+            from_bytes::expr(&slot.cells),
+            HISTORY_SERVE_WINDOW.expr(),
+        );
+        cb.require_equal(
+            "slot < HISTORY_SERVE_WINDOW",
+            slot_lt_window.expr(),
+            1.expr(),
+        );
+        cb.require_equal(
+            "block_number == quotient * HISTORY_SERVE_WINDOW + slot",
+            block_number,
+            quotient.expr() * HISTORY_SERVE_WINDOW.expr() + from_bytes::expr(&slot.cells),
+        );
+
+        Self {
+            quotient,
+            quotient_range,
+            slot,
+            slot_lt_window,
+        }
+    }
+
+    /// The slot as a storage key
+    pub(crate) fn slot_rlc(&self) -> Expression<F> {
+        self.slot.expr()
+    }
+
+    /// The slot as a number
+    pub(crate) fn slot_value(&self) -> Expression<F> {
+        from_bytes::expr(&self.slot.cells)
+    }
+
+    pub(crate) fn assign(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block_number: u64,
+    ) -> Result<(), Error> {
+        let quotient = block_number / HISTORY_SERVE_WINDOW;
+        let slot = block_number % HISTORY_SERVE_WINDOW;
+        self.quotient
+            .assign(region, offset, Value::known(F::from(quotient)))?;
+        self.quotient_range
+            .assign(region, offset, F::from(quotient))?;
+        self.slot.assign(region, offset, Some(slot.to_le_bytes()))?;
+        self.slot_lt_window
+            .assign(region, offset, F::from(slot), F::from(HISTORY_SERVE_WINDOW))?;
+        Ok(())
+    }
+}
+
+/// Write of the parent hash into the history storage contract, done by the system call of each
+/// block once the fork is active. The genesis block has no parent, so it writes nothing.
+///
+/// The scroll circuits hold no block hashes: as for BLOCKHASH before the fork, the hash of the
+/// parent is `keccak256(chain_id || number - 1)`.
+#[derive(Clone, Debug)]
+pub(crate) struct HistoryStorageWriteGadget<F> {
+    fork: HardforkGadget<F>,
+    number: Cell<F>,
+    is_genesis: IsZeroGadget<F>,
+    chain_id: U64Word<F>,
+    parent_number: U64Word<F>,
+    slot: HistoryStorageSlotGadget<F>,
+    parent_hash: Cell<F>,
+    value_prev: Cell<F>,
+    committed_value: Cell<F>,
+}
+
+impl<F: Field> HistoryStorageWriteGadget<F> {
+    pub(crate) fn construct(cb: &mut EVMConstraintBuilder<F>, tx_id: Expression<F>) -> Self {
+        let fork = HardforkGadget::construct(
+            cb,
+            HardforkId::HistoryStorage,
+            cb.curr.state.block_number.expr(),
+        );
+
+        let number = cb.query_cell();
+        cb.block_lookup(
+            BlockContextFieldTag::Number.expr(),
+            cb.curr.state.block_number.expr(),
+            number.expr(),
+        );
+        let is_genesis = IsZeroGadget::construct(cb, number.expr());
+
+        let chain_id = cb.query_word_rlc();
+        cb.block_lookup(
+            BlockContextFieldTag::ChainId.expr(),
+            cb.curr.state.block_number.expr(),
+            from_bytes::expr(&chain_id.cells),
+        );
+
+        let parent_number = cb.query_word_rlc();
+        let parent_hash = cb.query_cell_phase2();
+        let value_prev = cb.query_cell_phase2();
+        let committed_value = cb.query_cell_phase2();
+        let slot = cb.condition(not::expr(fork.is_before_fork.expr()), |cb| {
+            system_contract_code_read(cb, history_storage_address(), &history_storage::BYTECODE);
+            cb.condition(not::expr(is_genesis.expr()), |cb| {
+                cb.require_equal(
+                    "parent_number == number - 1",
+                    from_bytes::expr(&parent_number.cells),
+                    number.expr() - 1.expr(),
+                );
+                let slot =
+                    HistoryStorageSlotGadget::construct(cb, from_bytes::expr(&parent_number.cells));
+
+                #[cfg(not(feature = "scroll"))]
+                cb.block_lookup(
+                    BlockContextFieldTag::BlockHash.expr(),
+                    from_bytes::expr(&parent_number.cells),
+                    parent_hash.expr(),
+                );
+                #[cfg(feature = "scroll")]
+                cb.keccak_table_lookup(
+                    cb.keccak_rlc::<{ 2 * N_BYTES_U64 }>(
+                        chain_id
+                            .cells
+                            .iter()
+                            .rev()
+                            .chain(parent_number.cells.iter().rev())
+                            .rev()
+                            .map(Expr::expr)
+                            .collect::<Vec<_>>()
+                            .try_into()
+                            .unwrap(),
+                    ),
+                    (2 * N_BYTES_U64).expr(),
+                    parent_hash.expr(),
+                );
+                cb.account_storage_write(
+                    history_storage_address(),
+                    slot.slot_rlc(),
+                    parent_hash.expr(),
+                    value_prev.expr(),
+                    tx_id,
+                    committed_value.expr(),
+                    None,
+                );
+                slot
+            })
+        });
+
+        Self {
+            fork,
+            number,
+            is_genesis,
+            chain_id,
+            parent_number,
+            slot,
+            parent_hash,
+            value_prev,
+            committed_value,
+        }
+    }
+
+    pub(crate) fn assign(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        chain_id: u64,
+        block_number: u64,
+        write: Option<(U256, U256, U256)>,
+    ) -> Result<(), Error> {
+        self.fork.assign(region, offset, chain_id, block_number)?;
+        self.number
+            .assign(region, offset, Value::known(F::from(block_number)))?;
+        self.is_genesis
+            .assign(region, offset, F::from(block_number))?;
+        self.chain_id
+            .assign(region, offset, Some(chain_id.to_le_bytes()))?;
+        let parent_number = block_number.saturating_sub(1);
+        self.parent_number
+            .assign(region, offset, Some(parent_number.to_le_bytes()))?;
+        self.slot.assign(region, offset, parent_number)?;
+
+        let (parent_hash, value_prev, committed_value) = write.unwrap_or_default();
+        for (cell, value) in [
+            (&self.parent_hash, parent_hash),
+            (&self.value_prev, value_prev),
+            (&self.committed_value, committed_value),
+        ] {
+            cell.assign(region, offset, region.word_rlc(value))?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn is_before_fork(&self) -> Expression<F> {
+        self.fork.is_before_fork.expr()
+    }
+
+    pub(crate) fn rw_delta(&self) -> Expression<F> {
+        // the code hash read and the write, if not at genesis
+        not::expr(self.fork.is_before_fork.expr()) * (2.expr() - self.is_genesis.expr())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evm_circuit::util::math_gadget::test_util::{
+        test_math_gadget_container, try_test, MathGadgetContainer,
+    };
+    use halo2_proofs::halo2curves::bn256::Fr;
+
+    #[derive(Clone)]
+    struct HistoryStorageSlotTestContainer<F> {
+        gadget: HistoryStorageSlotGadget<F>,
+        block_number: Cell<F>,
+        expected_slot: Cell<F>,
+    }
+
+    impl<F: Field> MathGadgetContainer<F> for HistoryStorageSlotTestContainer<F> {
+        fn configure_gadget_container(cb: &mut EVMConstraintBuilder<F>) -> Self {
+            let block_number = cb.query_cell();
+            let expected_slot = cb.query_cell();
+            let gadget = HistoryStorageSlotGadget::construct(cb, block_number.expr());
+            cb.require_equal(
+                "slot must be correct",
+                from_bytes::expr(&gadget.slot.cells),
+                expected_slot.expr(),
+            );
+
+            HistoryStorageSlotTestContainer {
+                gadget,
+                block_number,
+                expected_slot,
+            }
+        }
+
+        fn assign_gadget_container(
+            &self,
+            witnesses: &[U256],
+            region: &mut CachedRegion<'_, '_, F>,
+        ) -> Result<(), Error> {
+            let [block_number, expected_slot] = [0, 1].map(|i| witnesses[i].as_u64());
+            self.block_number
+                .assign(region, 0, Value::known(F::from(block_number)))?;
+            self.expected_slot
+                .assign(region, 0, Value::known(F::from(expected_slot)))?;
+            self.gadget.assign(region, 0, block_number)?;
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_history_storage_slot() {
+        for (block_number, slot, expect_success) in [
+            (0, 0, true),
+            (8190, 8190, true),
+            (8191, 0, true),
+            (20000, 3618, true),
+            (u64::MAX, u64::MAX % HISTORY_SERVE_WINDOW, true),
+            (8191, 8191, false),
+        ] {
+            try_test!(
+                HistoryStorageSlotTestContainer<Fr>,
+                [U256::from(block_number), U256::from(slot)],
+                expect_success,
+            );
+        }
+    }
+}
@@ -12,15 +12,27 @@ use crate::{
     util::{Expr, Field},
 };
 use bus_mapping::{
-    circuit_input_builder::{TxL1Fee, TX_L1_COMMIT_EXTRA_COST, TX_L1_FEE_PRECISION},
+    circuit_input_builder::{L1FeeModel, TxL1Fee},
     l2_predeployed::l1_gas_price_oracle,
 };
 use eth_types::{ToLittleEndian, U256};
-use gadgets::util::not;
+use gadgets::util::{not, sum};
 use gadgets::ToScalar;
-use halo2_proofs::plonk::{Error, Expression};
+use halo2_proofs::{
+    circuit::Value,
+    plonk::{Error, Expression},
+};
+
+mod curie;
+mod pre_curie;
+
+use curie::CurieL1FeeGadget;
+use pre_curie::PreCurieL1FeeGadget;
 
 /// Transaction L1 fee gadget for L1GasPriceOracle contract
+///
+/// The oracle slots of the fee model in use are read into shared cells, then the gadget of the
+/// model constrains the fee from them.
 #[derive(Clone, Debug)]
 pub(crate) struct TxL1FeeGadget<F> {
     /// Transaction L1 fee
@@ -29,32 +41,14 @@ pub(crate) struct TxL1FeeGadget<F> {
     tx_l1_fee_word: Word<F>,
     /// Remainder when calculating L1 fee
     remainder_word: U64Word<F>,
-    /// Remainder must in [0, TX_L1_FEE_PRECISION)
+    /// Remainder must in [0, precision of the fee model)
     remainder_range: LtGadget<F, 8>,
-    /// Current value of L1 base fee
-    base_fee_word: U64Word<F>,
-    /// Current value of L1 fee overhead
-    fee_overhead_word: U64Word<F>,
-    /// Current value of L1 fee scalar
-    fee_scalar_word: U64Word<F>,
-    /// Current value of L1 blob base fee
-    l1_blob_basefee_word: U64Word<F>,
-    /// Current value of L1 scalar fee
-    commit_scalar_word: U64Word<F>,
-    /// Current value of L1 blob scalar fee
-    blob_scalar_word: U64Word<F>,
-    /// Current value of L1 base fee
-    base_fee_committed: Cell<F>,
-    /// Committed value of L1 fee overhead
-    fee_overhead_committed: Cell<F>,
-    /// Committed value of L1 fee scalar
-    fee_scalar_committed: Cell<F>,
-    /// Committed value of L1 blob base fee
-    l1_blob_basefee_committed: Cell<F>,
-    /// Committed value of L1 scalar fee
-    commit_scalar_committed: Cell<F>,
-    /// Committed value of L1 blob scalar fee
-    blob_scalar_committed: Cell<F>,
+    /// Whether each fee model is in use, in the order of `L1FeeModel::ALL`
+    is_model: [Cell<F>; 2],
+    /// Current values of the oracle slots read by the fee model
+    oracle_words: [U64Word<F>; L1FeeModel::MAX_ORACLE_SLOTS],
+    /// Committed values of the oracle slots read by the fee model
+    oracle_committed: [Cell<F>; L1FeeModel::MAX_ORACLE_SLOTS],
 }
 
 impl<F: Field> TxL1FeeGadget<F> {
@@ -65,81 +59,39 @@ impl<F: Field> TxL1FeeGadget<F> {
         tx_data_gas_cost: Expression<F>,
         tx_signed_length: Expression<F>,
     ) -> Self {
-        let this = Self::raw_construct(cb, is_curie.expr(), tx_data_gas_cost, tx_signed_length);
+        let this = Self::raw_construct(cb, is_curie, tx_data_gas_cost, tx_signed_length);
 
         let l1_fee_address = Expression::Constant(l1_gas_price_oracle::ADDRESS.to_scalar().expect(
             "Unexpected address of l2 gasprice oracle contract -> Scalar conversion failure",
         ));
 
-        let [base_fee_slot, overhead_slot, scalar_slot] = [
-            &l1_gas_price_oracle::BASE_FEE_SLOT,
-            &l1_gas_price_oracle::OVERHEAD_SLOT,
-            &l1_gas_price_oracle::SCALAR_SLOT,
-        ]
-        .map(|slot| cb.word_rlc(slot.to_le_bytes().map(|b| b.expr())));
-
-        let [l1_blob_basefee, commit_scalar, blob_scalar] = [
-            &l1_gas_price_oracle::L1_BLOB_BASEFEE_SLOT,
-            &l1_gas_price_oracle::COMMIT_SCALAR_SLOT,
-            &l1_gas_price_oracle::BLOB_SCALAR_SLOT,
-        ]
-        .map(|slot| cb.word_rlc(slot.to_le_bytes().map(|b| b.expr())));
-
-        // Read L1 base fee
-        cb.account_storage_read(
-            l1_fee_address.expr(),
-            base_fee_slot,
-            this.base_fee_word.expr(),
-            tx_id.expr(),
-            this.base_fee_committed.expr(),
-        );
-
-        // Read L1 fee overhead
-        cb.account_storage_read(
-            l1_fee_address.expr(),
-            overhead_slot,
-            this.fee_overhead_word.expr(),
-            tx_id.expr(),
-            this.fee_overhead_committed.expr(),
-        );
-
-        // Read L1 fee scalar
-        cb.account_storage_read(
-            l1_fee_address.clone(),
-            scalar_slot,
-            this.fee_scalar_word.expr(),
-            tx_id.clone(),
-            this.fee_scalar_committed.expr(),
-        );
-
-        cb.condition(is_curie.expr(), |cb| {
-            // Read l1blob_basefee_committed
-            cb.account_storage_read(
-                l1_fee_address.expr(),
-                l1_blob_basefee,
-                this.l1_blob_basefee_word.expr(),
-                tx_id.clone(),
-                this.l1_blob_basefee_committed.expr(),
-            );
-
-            // Read L1 commit_scalar_committed
-            cb.account_storage_read(
-                l1_fee_address.expr(),
-                commit_scalar,
-                this.commit_scalar_word.expr(),
-                tx_id.expr(),
-                this.commit_scalar_committed.expr(),
-            );
-
-            // Read L1 blob_scalar_committed scalar
-            cb.account_storage_read(
-                l1_fee_address,
-                blob_scalar,
-                this.blob_scalar_word.expr(),
-                tx_id,
-                this.blob_scalar_committed.expr(),
+        // Read the i-th oracle slot of the model in use, if it reads that many
+        for (i, (word, committed)) in this
+            .oracle_words
+            .iter()
+            .zip(this.oracle_committed.iter())
+            .enumerate()
+        {
+            let (is_read, slot) = L1FeeModel::ALL.iter().zip(this.is_model.iter()).fold(
+                (0.expr(), 0.expr()),
+                |(is_read, slot), (model, is_model)| match model.oracle_slots().get(i) {
+                    Some(model_slot) => (
+                        is_read + is_model.expr(),
+                        slot + is_model.expr() * cb.word_rlc_constant(*model_slot),
+                    ),
+                    None => (is_read, slot),
+                },
             );
-        });
+            cb.condition(is_read, |cb| {
+                cb.account_storage_read(
+                    l1_fee_address.expr(),
+                    slot,
+                    word.expr(),
+                    tx_id.expr(),
+                    committed.expr(),
+                );
+            });
+        }
         this
     }
 
@@ -158,82 +110,50 @@ impl<F: Field> TxL1FeeGadget<F> {
             l1_fee_committed,
             tx_signed_length
         );
+        let model = l1_fee.model();
         let (tx_l1_fee, remainder) = l1_fee.tx_l1_fee(tx_data_gas_cost, tx_signed_length);
 
         self.tx_l1_fee_word
             .assign(region, offset, Some(U256::from(tx_l1_fee).to_le_bytes()))?;
         self.remainder_word
             .assign(region, offset, Some(remainder.to_le_bytes()))?;
-        self.base_fee_word
-            .assign(region, offset, Some(l1_fee.base_fee.to_le_bytes()))?;
-        self.fee_overhead_word
-            .assign(region, offset, Some(l1_fee.fee_overhead.to_le_bytes()))?;
-        self.fee_scalar_word
-            .assign(region, offset, Some(l1_fee.fee_scalar.to_le_bytes()))?;
         self.remainder_range.assign(
             region,
             offset,
             F::from(remainder),
-            F::from(TX_L1_FEE_PRECISION),
-        )?;
-
-        // curie fields
-        self.l1_blob_basefee_word.assign(
-            region,
-            offset,
-            Some(l1_fee.l1_blob_basefee.to_le_bytes()),
+            F::from(model.precision()),
         )?;
-        self.commit_scalar_word
-            .assign(region, offset, Some(l1_fee.commit_scalar.to_le_bytes()))?;
-        self.blob_scalar_word
-            .assign(region, offset, Some(l1_fee.blob_scalar.to_le_bytes()))?;
 
-        self.base_fee_committed.assign(
-            region,
-            offset,
-            region.word_rlc(l1_fee_committed.base_fee.into()),
-        )?;
-        self.fee_overhead_committed.assign(
-            region,
-            offset,
-            region.word_rlc(l1_fee_committed.fee_overhead.into()),
-        )?;
-        self.fee_scalar_committed.assign(
-            region,
-            offset,
-            region.word_rlc(l1_fee_committed.fee_scalar.into()),
-        )?;
+        for (is_model, m) in self.is_model.iter().zip(L1FeeModel::ALL) {
+            is_model.assign(region, offset, Value::known(F::from((m == model) as u64)))?;
+        }
 
-        // curie fields
-        self.l1_blob_basefee_committed.assign(
-            region,
-            offset,
-            region.word_rlc(l1_fee_committed.l1_blob_basefee.into()),
-        )?;
-        self.commit_scalar_committed.assign(
-            region,
-            offset,
-            region.word_rlc(l1_fee_committed.commit_scalar.into()),
-        )?;
-        self.blob_scalar_committed.assign(
-            region,
-            offset,
-            region.word_rlc(l1_fee_committed.blob_scalar.into()),
-        )?;
+        let values = l1_fee.oracle_values();
+        let committed_values = l1_fee_committed.oracle_values();
+        for (i, (word, committed)) in self
+            .oracle_words
+            .iter()
+            .zip(self.oracle_committed.iter())
+            .enumerate()
+        {
+            let value = values.get(i).copied().unwrap_or_default();
+            let committed_value = committed_values.get(i).copied().unwrap_or_default();
+            word.assign(region, offset, Some(value.to_le_bytes()))?;
+            committed.assign(region, offset, region.word_rlc(committed_value.into()))?;
+        }
 
         Ok(())
     }
 
-    pub(crate) fn rw_delta(&self, is_curie: Expression<F>) -> Expression<F> {
-        // L1 base fee Read
-        // L1 fee overhead Read
-        // L1 fee scalar Read
-        // + curie fields
-        // l1 blob baseFee
-        // commit scalar
-        // blob scalar
-        // TODO: we could optimze the "overhead" and "scalar" for curie
-        3.expr() + is_curie.expr() * 3.expr()
+    pub(crate) fn rw_delta(&self) -> Expression<F> {
+        // one storage read per oracle slot of the model in use:
+        // 3 before curie, 6 after curie
+        sum::expr(
+            L1FeeModel::ALL
+                .iter()
+                .zip(self.is_model.iter())
+                .map(|(model, is_model)| is_model.expr() * model.oracle_slots().len().expr()),
+        )
     }
 
     pub(crate) fn tx_l1_fee(&self) -> Expression<F> {
@@ -253,80 +173,58 @@ impl<F: Field> TxL1FeeGadget<F> {
         let tx_l1_fee_word = cb.query_word_rlc();
         let remainder_word = cb.query_word_rlc();
 
-        let base_fee_word = cb.query_word_rlc();
-        let fee_overhead_word = cb.query_word_rlc();
-        let fee_scalar_word = cb.query_word_rlc();
-        // curie fields
-        let l1_blob_basefee_word = cb.query_word_rlc();
-        let commit_scalar_word = cb.query_word_rlc();
-        let blob_scalar_word = cb.query_word_rlc();
+        let is_model = [(); 2].map(|_| cb.query_bool());
+        let [is_pre_curie_model, is_curie_model] = [0, 1].map(|i| is_model[i].expr());
+        cb.require_equal(
+            "exactly one l1 fee model is in use",
+            sum::expr(&is_model),
+            1.expr(),
+        );
+        cb.require_equal(
+            "pre-curie fee model iff not curie",
+            is_pre_curie_model.expr(),
+            not::expr(is_curie),
+        );
+
+        let oracle_words = [(); L1FeeModel::MAX_ORACLE_SLOTS].map(|_| cb.query_word_rlc());
+        let oracle = oracle_words
+            .iter()
+            .map(|word: &U64Word<F>| from_bytes::expr(&word.cells))
+            .collect::<Vec<_>>();
 
         let tx_l1_fee = from_bytes::expr(&tx_l1_fee_word.cells[..N_BYTES_U64]);
-        let [remainder, base_fee, fee_overhead, fee_scalar] = [
-            &remainder_word,
-            &base_fee_word,
-            &fee_overhead_word,
-            &fee_scalar_word,
-        ]
-        .map(|word| from_bytes::expr(&word.cells[..N_BYTES_U64]));
-
-        let remainder_range = LtGadget::construct(cb, remainder.expr(), TX_L1_FEE_PRECISION.expr());
+        let remainder = from_bytes::expr(&remainder_word.cells[..N_BYTES_U64]);
+
+        let precision = sum::expr(
+            L1FeeModel::ALL
+                .iter()
+                .zip(is_model.iter())
+                .map(|(model, is_model)| is_model.expr() * model.precision().expr()),
+        );
+        let remainder_range = LtGadget::construct(cb, remainder.expr(), precision.expr());
         cb.require_equal(
             "remainder must less than l1 fee precision",
             1.expr(),
             remainder_range.expr(),
         );
 
-        let [l1_blob_basefee, commit_scalar, blob_scalar] = [
-            &l1_blob_basefee_word,
-            &commit_scalar_word,
-            &blob_scalar_word,
-        ]
-        .map(|word| from_bytes::expr(&word.cells[..N_BYTES_U64]));
-
-        // For curie and non-curie, see l2geth side implementation:
-        // <https://github.com/scroll-tech/go-ethereum/blob/36d7325ea1cb6749f353f84df7e9903f93aa903b/rollup/fees/rollup_fee.go#L76>
-        cb.condition(is_curie.expr(), |cb| {
-            cb.require_equal(
-                "commitScalar * l1BaseFee + blobScalar * _data.length * l1BlobBaseFee == tx_l1_fee * 10e9 + remainder",
-                commit_scalar * base_fee.clone() + blob_scalar * tx_signed_length * l1_blob_basefee,
-                tx_l1_fee.clone() * TX_L1_FEE_PRECISION.expr() + remainder.clone(),
-            );
-        });
-
-        cb.condition(not::expr(is_curie.expr()), |cb| {
-            let tx_l1_gas = tx_data_gas_cost + TX_L1_COMMIT_EXTRA_COST.expr() + fee_overhead;
-            cb.require_equal(
-                "fee_scalar * base_fee * tx_l1_gas == tx_l1_fee * 10e9 + remainder",
-                fee_scalar * base_fee * tx_l1_gas,
-                tx_l1_fee * TX_L1_FEE_PRECISION.expr() + remainder,
-            );
-        });
+        let pre_curie = PreCurieL1FeeGadget::construct(&oracle, tx_data_gas_cost);
+        let curie = CurieL1FeeGadget::construct(&oracle, tx_signed_length);
+        cb.require_equal(
+            "l1 fee of the model in use == tx_l1_fee * precision + remainder",
+            is_pre_curie_model * pre_curie.fee_scaled() + is_curie_model * curie.fee_scaled(),
+            tx_l1_fee * precision + remainder,
+        );
 
-        let base_fee_committed = cb.query_cell_phase2();
-        let fee_overhead_committed = cb.query_cell_phase2();
-        let fee_scalar_committed = cb.query_cell_phase2();
-        // curie fields
-        let l1_blob_basefee_committed = cb.query_cell_phase2();
-        let commit_scalar_committed = cb.query_cell_phase2();
-        let blob_scalar_committed = cb.query_cell_phase2();
+        let oracle_committed = [(); L1FeeModel::MAX_ORACLE_SLOTS].map(|_| cb.query_cell_phase2());
 
         Self {
             tx_l1_fee_word,
             remainder_word,
             remainder_range,
-            base_fee_word,
-            fee_overhead_word,
-            fee_scalar_word,
-            l1_blob_basefee_word,
-            commit_scalar_word,
-            blob_scalar_word,
-            base_fee_committed,
-            fee_overhead_committed,
-            fee_scalar_committed,
-            l1_blob_basefee_committed,
-            commit_scalar_committed,
-            blob_scalar_committed,
+            is_model,
+            oracle_words,
+            oracle_committed,
         }
     }
 }
@@ -338,7 +236,10 @@ mod tests {
         constraint_builder::ConstrainBuilderCommon,
         math_gadget::test_util::{test_math_gadget_container, try_test, MathGadgetContainer},
     };
-    use eth_types::U256;
+    use eth_types::{
+        forks::{ChainSpec, ForkCondition, HardforkId, MORPH_DEVNET_CHAIN_ID},
+        U256,
+    };
     use halo2_proofs::{circuit::Value, halo2curves::bn256::Fr};
 
     //refer to test in <https://github.com/scroll-tech/go-ethereum/blob/develop/rollup/fees/rollup_fee_test.go#L10>
@@ -361,6 +262,40 @@ mod tests {
     const TEST_TX_RLP_SIGNED_LENGTH: u128 = 4;
     const TEST_TX_L1_FEE_AFTER_CURIE: u128 = 21;
 
+    // curie block of the test devnet
+    const TEST_CURIE_BLOCK: u64 = 5;
+
+    fn with_test_devnet<R: Send>(f: impl FnOnce() -> R + Send) -> R {
+        let specs = ChainSpec::morph_from_env()
+            .unwrap()
+            .into_iter()
+            .map(|spec| match spec.chain_id {
+                MORPH_DEVNET_CHAIN_ID => {
+                    spec.with_fork(HardforkId::Curie, ForkCondition::Block(TEST_CURIE_BLOCK))
+                }
+                _ => spec,
+            })
+            .collect();
+        ChainSpec::scoped(specs, f)
+    }
+
+    #[test]
+    fn test_tx_l1_fee_model_for_block() {
+        with_test_devnet(|| {
+            for (block_number, model) in [
+                (0, L1FeeModel::PreCurie),
+                (TEST_CURIE_BLOCK - 1, L1FeeModel::PreCurie),
+                (TEST_CURIE_BLOCK, L1FeeModel::Curie),
+                (TEST_CURIE_BLOCK + 1, L1FeeModel::Curie),
+            ] {
+                assert_eq!(
+                    L1FeeModel::for_block(MORPH_DEVNET_CHAIN_ID, block_number),
+                    model
+                );
+            }
+        });
+    }
+
     #[test]
     fn test_tx_l1_fee_with_right_values() {
         // test both before & after curie upgrade
@@ -382,7 +317,7 @@ mod tests {
             ]
             .map(U256::from);
 
-            try_test!(TxL1FeeGadgetTestContainer<Fr>, witnesses, true);
+            with_test_devnet(|| try_test!(TxL1FeeGadgetTestContainer<Fr>, witnesses, true));
         }
     }
 
@@ -409,7 +344,7 @@ mod tests {
             ]
             .map(U256::from);
 
-            try_test!(TxL1FeeGadgetTestContainer<Fr>, witnesses, false);
+            with_test_devnet(|| try_test!(TxL1FeeGadgetTestContainer<Fr>, witnesses, false));
         }
     }
 
@@ -466,9 +401,12 @@ mod tests {
                 [6, 7, 8, 9, 10, 11].map(|i| witnesses[i].as_u64());
 
             let l1_fee = TxL1Fee {
-                chain_id: eth_types::forks::MORPH_DEVNET_CHAIN_ID,
-                // block_number 5 is starting number for curie in test devnet.
-                block_number: if is_curie == 1 { 5 + 1 } else { 1 },
+                chain_id: MORPH_DEVNET_CHAIN_ID,
+                block_number: if is_curie == 1 {
+                    TEST_CURIE_BLOCK + 1
+                } else {
+                    1
+                },
                 base_fee: if is_curie == 1 {
                     base_fee_after_curie
                 } else {
@@ -0,0 +1,27 @@
+use crate::util::Field;
+use halo2_proofs::plonk::Expression;
+
+/// L1 fee after curie, from the oracle base fee, blob base fee and the commit and blob scalars.
+#[derive(Clone, Debug)]
+pub(crate) struct CurieL1FeeGadget<F> {
+    /// commitScalar * l1BaseFee + blobScalar * _data.length * l1BlobBaseFee
+    fee_scaled: Expression<F>,
+}
+
+impl<F: Field> CurieL1FeeGadget<F> {
+    /// `oracle` are the values of `L1FeeModel::Curie.oracle_slots()`.
+    pub(crate) fn construct(oracle: &[Expression<F>], tx_signed_length: Expression<F>) -> Self {
+        let [base_fee, l1_blob_basefee, commit_scalar, blob_scalar] =
+            [0, 3, 4, 5].map(|i| oracle[i].clone());
+
+        // <https://github.com/scroll-tech/go-ethereum/blob/36d7325ea1cb6749f353f84df7e9903f93aa903b/rollup/fees/rollup_fee.go#L76>
+        Self {
+            fee_scaled: commit_scalar * base_fee + blob_scalar * tx_signed_length * l1_blob_basefee,
+        }
+    }
+
+    /// The L1 fee times 1e9.
+    pub(crate) fn fee_scaled(&self) -> Expression<F> {
+        self.fee_scaled.clone()
+    }
+}
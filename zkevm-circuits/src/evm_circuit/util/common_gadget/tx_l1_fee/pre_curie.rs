@@ -0,0 +1,28 @@
+use crate::util::{Expr, Field};
+use bus_mapping::circuit_input_builder::TX_L1_COMMIT_EXTRA_COST;
+use halo2_proofs::plonk::Expression;
+
+/// L1 fee before curie, from the oracle base fee, overhead and scalar.
+#[derive(Clone, Debug)]
+pub(crate) struct PreCurieL1FeeGadget<F> {
+    /// fee_scalar * base_fee * tx_l1_gas
+    fee_scaled: Expression<F>,
+}
+
+impl<F: Field> PreCurieL1FeeGadget<F> {
+    /// `oracle` are the values of `L1FeeModel::PreCurie.oracle_slots()`.
+    pub(crate) fn construct(oracle: &[Expression<F>], tx_data_gas_cost: Expression<F>) -> Self {
+        let [base_fee, fee_overhead, fee_scalar] = [0, 1, 2].map(|i| oracle[i].clone());
+
+        // <https://github.com/scroll-tech/go-ethereum/blob/36d7325ea1cb6749f353f84df7e9903f93aa903b/rollup/fees/rollup_fee.go#L76>
+        let tx_l1_gas = tx_data_gas_cost + TX_L1_COMMIT_EXTRA_COST.expr() + fee_overhead;
+        Self {
+            fee_scaled: fee_scalar * base_fee * tx_l1_gas,
+        }
+    }
+
+    /// The L1 fee times 1e9.
+    pub(crate) fn fee_scaled(&self) -> Expression<F> {
+        self.fee_scaled.clone()
+    }
+}
@@ -1,15 +1,16 @@
 use crate::{
     evm_circuit::{
+        coverage::CoveragePoint,
         param::STACK_CAPACITY,
         step::{ExecutionState, Step},
         table::{FixedTableTag, Lookup, RwValues},
         util::{Cell, RandomLinearCombination, Word},
     },
     table::{
-        AccountFieldTag, BytecodeFieldTag, CallContextFieldTag, RwTableTag, TxContextFieldTag,
-        TxLogFieldTag, TxReceiptFieldTag,
+        AccountFieldTag, BytecodeFieldTag, CallContextFieldTag, RwTableTag, SigCurve,
+        TxContextFieldTag, TxLogFieldTag, TxReceiptFieldTag,
     },
-    util::{build_tx_log_expression, Challenges, Expr, Field},
+    util::{build_tx_log_expression, ChallengePhases, Challenges, Expr, Field},
 };
 use bus_mapping::util::{KECCAK_CODE_HASH_EMPTY, POSEIDON_CODE_HASH_EMPTY};
 use eth_types::{state_db::EMPTY_CODE_HASH_LE, ToLittleEndian, ToWord, H256};
@@ -24,6 +25,9 @@ use halo2_proofs::{
 };
 use itertools::Itertools;
 
+#[cfg(feature = "coverage")]
+use crate::evm_circuit::coverage::CoveragePointKind;
+
 use super::{rlc, CachedRegion, CellType, StoredExpression};
 
 // Max degree allowed in all expressions passing through the ConstraintBuilder.
@@ -275,8 +279,8 @@ impl<F: Field> BaseConstraintBuilder<F> {
 }
 
 /// Internal type to select the location where the constraints are enabled
-#[derive(Debug, PartialEq)]
-enum ConstraintLocation {
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum ConstraintLocation {
     Step,
     StepFirst,
     StepLast,
@@ -310,6 +314,7 @@ pub(crate) struct EVMConstraintBuilder<'a, F> {
     conditions: Vec<Expression<F>>,
     constraints_location: ConstraintLocation,
     stored_expressions: Vec<StoredExpression<F>>,
+    coverage_points: Vec<CoveragePoint<F>>,
     pub(crate) max_inner_degree: (&'static str, usize),
     #[cfg(feature = "debug-annotations")]
     annotations: Vec<String>,
@@ -320,6 +325,8 @@ impl<'a, F: Field> ConstrainBuilderCommon<F> for EVMConstraintBuilder<'a, F> {
         #[cfg(feature = "debug-annotations")]
         let name =
             Box::leak(format!("{}: {}", self.annotations.iter().join(">"), name).into_boxed_str());
+        #[cfg(feature = "coverage")]
+        self.push_coverage_point(name, CoveragePointKind::Constraint, constraint.clone());
         let constraint = self.split_expression(
             name,
             constraint * self.condition_expr(),
@@ -358,6 +365,7 @@ impl<'a, F: Field> EVMConstraintBuilder<'a, F> {
             conditions: Vec::new(),
             constraints_location: ConstraintLocation::Step,
             stored_expressions: Vec::new(),
+            coverage_points: Vec::new(),
             max_inner_degree: ("", 0),
             annotations: Vec::new(),
         }
@@ -383,6 +391,12 @@ impl<'a, F: Field> EVMConstraintBuilder<'a, F> {
         )
     }
 
+    /// Returns the named constraints and lookups recorded so far for the
+    /// coverage report. Always empty without the `coverage` feature.
+    pub(crate) fn take_coverage_points(&mut self) -> Vec<CoveragePoint<F>> {
+        std::mem::take(&mut self.coverage_points)
+    }
+
     fn condition_expr_opt(&self) -> Option<Expression<F>> {
         let mut iter = self.conditions.iter();
         let first = match iter.next() {
@@ -454,14 +468,14 @@ impl<'a, F: Field> EVMConstraintBuilder<'a, F> {
         self.query_cell_with_type(CellType::StoragePhase1)
     }
 
-    #[allow(clippy::let_and_return)]
     pub(crate) fn query_cell_phase2(&mut self) -> Cell<F> {
         let cell = self.query_cell_with_type(CellType::StoragePhase2);
-        #[cfg(not(feature = "onephase"))]
-        assert_eq!(
-            cell.column.column_type(),
-            &halo2_proofs::plonk::Advice::new(halo2_proofs::plonk::SecondPhase)
-        );
+        if self.challenges.phases() == ChallengePhases::Multi {
+            assert_eq!(
+                cell.column.column_type(),
+                &halo2_proofs::plonk::Advice::new(halo2_proofs::plonk::SecondPhase)
+            );
+        }
         cell
     }
 
@@ -733,6 +747,20 @@ impl<'a, F: Field> EVMConstraintBuilder<'a, F> {
         );
     }
 
+    pub(crate) fn bytecode_code_hashes(
+        &mut self,
+        code_hash: Expression<F>,
+        keccak_code_hash: Expression<F>,
+    ) {
+        self.add_lookup(
+            "Bytecode (code hashes)",
+            Lookup::BytecodeCodeHashes {
+                code_hash,
+                keccak_code_hash,
+            },
+        );
+    }
+
     // Tx context
 
     pub(crate) fn tx_context(
@@ -1502,12 +1530,40 @@ impl<'a, F: Field> EVMConstraintBuilder<'a, F> {
         self.add_lookup(
             "sig table",
             Lookup::SigTable {
+                curve: SigCurve::Secp256k1.expr(),
                 msg_hash_rlc: msg_hash_rlc.expr(),
                 sig_v: sig_v.expr(),
                 sig_r_rlc: sig_r_rlc.expr(),
                 sig_s_rlc: sig_s_rlc.expr(),
                 recovered_addr: recovered_addr.expr(),
                 is_valid: is_valid.expr(),
+                pk_x_rlc: 0.expr(),
+                pk_y_rlc: 0.expr(),
+            },
+        );
+    }
+
+    pub(crate) fn p256_sig_table_lookup(
+        &mut self,
+        msg_hash_rlc: Expression<F>,
+        sig_r_rlc: Expression<F>,
+        sig_s_rlc: Expression<F>,
+        pk_x_rlc: Expression<F>,
+        pk_y_rlc: Expression<F>,
+        is_valid: Expression<F>,
+    ) {
+        self.add_lookup(
+            "sig table (p256)",
+            Lookup::SigTable {
+                curve: SigCurve::Secp256r1.expr(),
+                msg_hash_rlc: msg_hash_rlc.expr(),
+                sig_v: 0.expr(),
+                sig_r_rlc: sig_r_rlc.expr(),
+                sig_s_rlc: sig_s_rlc.expr(),
+                recovered_addr: 0.expr(),
+                is_valid: is_valid.expr(),
+                pk_x_rlc: pk_x_rlc.expr(),
+                pk_y_rlc: pk_y_rlc.expr(),
             },
         );
     }
@@ -1596,20 +1652,27 @@ impl<'a, F: Field> EVMConstraintBuilder<'a, F> {
     }
 
     // ModExp table
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn modexp_table_lookup(
         &mut self,
-        base_limbs: [Expression<F>; 3],
-        exp_limbs: [Expression<F>; 3],
-        modulus_limbs: [Expression<F>; 3],
-        result_limbs: [Expression<F>; 3],
+        input_rlc: Expression<F>,
+        header_rlc: Expression<F>,
+        base_len: Expression<F>,
+        exp_len: Expression<F>,
+        modulus_len: Expression<F>,
+        exp_head_rlc: Expression<F>,
+        result_rlc: Expression<F>,
     ) {
         self.add_lookup(
-            "u256 exponentiation modulus lookup",
+            "big int exponentiation modulus lookup",
             Lookup::ModExpTable {
-                base_limbs,
-                exp_limbs,
-                modulus_limbs,
-                result_limbs,
+                input_rlc,
+                header_rlc,
+                base_len,
+                exp_len,
+                modulus_len,
+                exp_head_rlc,
+                result_rlc,
             },
         );
     }
@@ -1732,6 +1795,12 @@ impl<'a, F: Field> EVMConstraintBuilder<'a, F> {
     }
 
     pub(crate) fn add_lookup(&mut self, name: &str, lookup: Lookup<F>) {
+        #[cfg(feature = "coverage")]
+        self.push_coverage_point(
+            name,
+            CoveragePointKind::Lookup,
+            rlc::expr(&lookup.input_exprs(), self.challenges.lookup_input()),
+        );
         let lookup = match self.condition_expr_opt() {
             Some(condition) => lookup.conditional(condition),
             None => lookup,
@@ -1744,6 +1813,17 @@ impl<'a, F: Field> EVMConstraintBuilder<'a, F> {
         self.store_expression(name, compressed_expr, CellType::Lookup(lookup.table()));
     }
 
+    #[cfg(feature = "coverage")]
+    fn push_coverage_point(&mut self, name: &str, kind: CoveragePointKind, expr: Expression<F>) {
+        self.coverage_points.push(CoveragePoint {
+            name: name.to_string(),
+            kind,
+            location: self.constraints_location,
+            condition: self.condition_expr_opt(),
+            expr,
+        });
+    }
+
     pub(crate) fn store_expression(
         &mut self,
         name: &str,
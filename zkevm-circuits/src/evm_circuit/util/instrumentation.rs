@@ -93,6 +93,9 @@ impl Instrument {
                     CellType::Lookup(Table::Bytecode) => {
                         report.bytecode_table = data_entry;
                     }
+                    CellType::Lookup(Table::BytecodeCodeHashes) => {
+                        report.bytecode_code_hashes_table = data_entry;
+                    }
                     CellType::Lookup(Table::Block) => {
                         report.block_table = data_entry;
                     }
@@ -142,6 +145,7 @@ pub(crate) struct ExecStateReport {
     pub(crate) tx_table: StateReportRow,
     pub(crate) rw_table: StateReportRow,
     pub(crate) bytecode_table: StateReportRow,
+    pub(crate) bytecode_code_hashes_table: StateReportRow,
     pub(crate) block_table: StateReportRow,
     pub(crate) copy_table: StateReportRow,
     pub(crate) keccak_table: StateReportRow,
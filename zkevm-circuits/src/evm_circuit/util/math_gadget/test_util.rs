@@ -16,24 +16,10 @@ use crate::{
     table::LookupTable,
 };
 
-#[cfg(not(feature = "onephase"))]
-use crate::util::Challenges;
-#[cfg(feature = "onephase")]
-use crate::util::MockChallenges as Challenges;
-
-use halo2_proofs::plonk::FirstPhase;
-#[cfg(feature = "onephase")]
-use halo2_proofs::plonk::FirstPhase as SecondPhase;
-#[cfg(feature = "onephase")]
-use halo2_proofs::plonk::FirstPhase as ThirdPhase;
-#[cfg(not(feature = "onephase"))]
-use halo2_proofs::plonk::SecondPhase;
-#[cfg(not(feature = "onephase"))]
-use halo2_proofs::plonk::ThirdPhase;
-
-use crate::util::Field;
+use crate::util::{advice_column_in_phase2, advice_column_in_phase3, Challenges, Field};
 use eth_types::{Word, U256};
 pub(crate) use halo2_proofs::circuit::{Layouter, Value};
+use halo2_proofs::plonk::FirstPhase;
 use halo2_proofs::{
     circuit::SimpleFloorPlanner,
     dev::MockProver,
@@ -127,9 +113,9 @@ impl<F: Field, G: MathGadgetContainer<F>> Circuit<F> for UnitTestMathGadgetBaseC
             .enumerate()
             .map(|(n, _)| {
                 if n < lookup_column_count {
-                    meta.advice_column_in(ThirdPhase)
+                    advice_column_in_phase3(meta, challenges.phases())
                 } else if n < lookup_column_count + N_PHASE2_COLUMNS {
-                    meta.advice_column_in(SecondPhase)
+                    advice_column_in_phase2(meta, challenges.phases())
                 } else {
                     meta.advice_column_in(FirstPhase)
                 }
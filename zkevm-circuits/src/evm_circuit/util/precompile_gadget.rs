@@ -13,7 +13,8 @@ use super::{
 
 #[derive(Clone, Debug)]
 pub struct PrecompileGadget<F> {
-    address: BinaryNumberGadget<F, 4>,
+    // 9 bits to cover the P256VERIFY precompile at 0x100.
+    address: BinaryNumberGadget<F, 9>,
 }
 
 impl<F: Field> PrecompileGadget<F> {
@@ -49,6 +50,7 @@ impl<F: Field> PrecompileGadget<F> {
         constrain_next_state!(cb, Bn128Mul, PrecompileBn256ScalarMul);
         constrain_next_state!(cb, Bn128Pairing, PrecompileBn256Pairing);
         constrain_next_state!(cb, Blake2F, PrecompileBlake2f);
+        constrain_next_state!(cb, P256Verify, PrecompileP256Verify);
 
         // Without constraining the next step's state, only constrain the first two Phase2 cells,
         // i.e. RLC(input_bytes) and RLC(return_bytes)
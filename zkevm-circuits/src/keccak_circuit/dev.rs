@@ -21,8 +21,8 @@ impl<F: Field> Circuit<F> for KeccakCircuit<F> {
     }
 
     fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
-        let keccak_table = KeccakTable::construct(meta);
         let challenges = Challenges::construct(meta);
+        let keccak_table = KeccakTable::construct(meta, challenges.phases());
 
         let config = {
             let challenges = challenges.exprs(meta);
@@ -3,6 +3,7 @@ use crate::{
     evm_circuit::util::rlc,
     util::{Challenges, Field},
 };
+use eth_types::scoped::{Scope, ScopedValue};
 use halo2_proofs::{
     circuit::Value,
     plonk::{Error, Expression},
@@ -13,19 +14,35 @@ use std::{env::var, vec};
 
 const MAX_DEGREE: usize = 9;
 
+static NUM_ROWS_PER_ROUND: ScopedValue<usize> = ScopedValue::new();
+
 /// Obtain the rows required for 1 iteration of f-box's inner round
-/// function (consisting of 5 phases) within Keccak circuit
+/// function (consisting of 5 phases) within Keccak circuit.
+/// The value installed by [`with_num_rows_per_round`] takes precedence over the
+/// `KECCAK_ROWS` env var.
 pub fn get_num_rows_per_round() -> usize {
-    let r = var("KECCAK_ROWS")
-        .unwrap_or_else(|_| format!("{DEFAULT_KECCAK_ROWS}"))
-        .parse()
-        .expect("Cannot parse KECCAK_ROWS env var as usize");
+    let r = NUM_ROWS_PER_ROUND.get_or_else(|| {
+        var("KECCAK_ROWS")
+            .unwrap_or_else(|_| format!("{DEFAULT_KECCAK_ROWS}"))
+            .parse()
+            .expect("Cannot parse KECCAK_ROWS env var as usize")
+    });
     assert!(
         r > NUM_BYTES_PER_WORD,
-        "env variable KECCAK_ROWS must be greater than (NUM_BYTES_PER_WORD + 1)."
+        "KECCAK_ROWS must be greater than (NUM_BYTES_PER_WORD + 1)."
     );
     r
 }
+
+/// Run `f` with the keccak circuit configured for `rows` rows per round, see [`Scope::run`].
+pub fn with_num_rows_per_round<R: Send>(rows: usize, f: impl FnOnce() -> R + Send) -> R {
+    install_num_rows_per_round(Scope::new(), rows).run(f)
+}
+
+/// Add `rows` rows per round of the keccak circuit to the values installed by `scope`.
+pub fn install_num_rows_per_round(scope: Scope, rows: usize) -> Scope {
+    scope.set(&NUM_ROWS_PER_ROUND, rows)
+}
 /// Obtain the rows required for 1 iteration of the f-box
 /// function (consisting of nr = 12 + 2*l inner rounds)
 /// within Keccak circuit
@@ -48,6 +48,8 @@ pub mod table;
 
 #[cfg(any(feature = "test", test))]
 pub mod test_util;
+#[cfg(any(feature = "triage", test))]
+pub mod triage;
 
 #[cfg(any(feature = "test", test))]
 mod stats;
@@ -1,173 +1,1175 @@
-//! The Modexp circuit is responsible for modexp operations on big integer from precompiled contract
-//! calls ModExp, current the size of supported integer is up to 32 bytes (U256)
+//! The ModExp circuit is responsible for the big integer modular exponentiations of the
+//! precompiled contract ModExp, with base, exponent and modulus of up to `MODEXP_SIZE_LIMIT`
+//! bytes, and block rows of up to `MODEXP_BLOCK_ROWS_LIMIT`.
+//!
+//! Each call is laid out as `MODEXP_INPUT_LIMIT` input rows, one per (padded) input byte, which
+//! accumulate the RLCs of the input and of its sections, followed (for a non-zero modulus) by a
+//! chain of blocks of byte limbs. A block proves `a * b = q * modulus + r` over the integers, as
+//! the polynomial identity `a(X) * b(X) - q(X) * m(X) - r(X) = (X - 256) * c(X)` evaluated at the
+//! keccak input challenge. The chain reduces the base, initialises the accumulator with one and
+//! then squares (and multiplies with the reduced base or with one) for each exponent bit, from
+//! the most significant one. The last block proves that the result is less than the modulus.
 
 #[cfg(any(feature = "test", test, feature = "test-circuits"))]
 mod dev;
-#[cfg(any(feature = "test", test, feature = "test-circuits"))]
+#[cfg(any(feature = "test", test))]
 mod test;
 
-use halo2_proofs::{
-    circuit::{Layouter, Region, Value},
-    plonk::{Advice, Column, ConstraintSystem, Error},
-};
-
 use crate::{
-    table::ModExpTable,
-    util::{Challenges, Field, SubCircuit, SubCircuitConfig},
+    evm_circuit::util::constraint_builder::{BaseConstraintBuilder, ConstrainBuilderCommon},
+    table::{LookupTable, ModExpTable, U8Table},
+    util::{
+        advice_column_in_phase2, rlc_be_bytes, Challenges, Expr, Field, SubCircuit,
+        SubCircuitConfig,
+    },
     witness,
 };
-use bus_mapping::circuit_input_builder::BigModExp;
-use eth_types::Word;
-
-//use misc_precompiled_circuit::value_for_assign;
-use misc_precompiled_circuit::circuits::{
-    modexp::{ModExpChip, Number},
-    range::{RangeCheckChip, RangeCheckConfig},
-    CommonGateConfig as ModExpConfig, Limb,
+use bus_mapping::{circuit_input_builder::BigModExp, precompile::MODEXP_INPUT_LIMIT};
+use gadgets::{
+    is_zero::{IsZeroChip, IsZeroConfig, IsZeroInstruction},
+    less_than::{LtChip, LtConfig, LtInstruction},
+    util::{and, not, select, sum},
 };
+use halo2_proofs::{
+    circuit::{Layouter, Region, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Expression, Fixed, VirtualCells},
+    poly::Rotation,
+};
+use itertools::Itertools;
+use num_bigint::BigUint;
+use std::marker::PhantomData;
+
+/// The carries of a block are offset by this value, so that they fit in 3 unsigned bytes.
+const CARRY_OFFSET: u64 = 1 << 23;
+
+/// The kinds of blocks of byte limbs, in the order of the `kind` columns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum BlockKind {
+    /// r = base mod m, with a = base and b = 1.
+    Reduce,
+    /// r = 1 mod m, with a = b = 1.
+    One,
+    /// r = a * a mod m.
+    Square,
+    /// r = a * b mod m, with b being either the reduced base or 1 by the exponent bit.
+    Mult,
+    /// r = m - a - 1, which proves that the result a is less than m.
+    Cmp,
+}
+
+const BLOCK_KINDS: [BlockKind; 5] = [
+    BlockKind::Reduce,
+    BlockKind::One,
+    BlockKind::Square,
+    BlockKind::Mult,
+    BlockKind::Cmp,
+];
 
 /// ModExp circuit config
 #[derive(Clone, Debug)]
-pub struct ModExpCircuitConfig {
-    modexp_config: ModExpConfig,
-    rangecheck_config: RangeCheckConfig,
+pub struct ModExpCircuitConfig<F> {
+    /// Whether the row is enabled.
+    q_enable: Column<Fixed>,
+    /// The first enabled row.
+    q_first: Column<Fixed>,
+    /// The last enabled row, which must be a padding row.
+    q_last: Column<Fixed>,
+    /// Whether the row is an input row.
+    is_input: Column<Advice>,
+    /// Whether the row is a block row.
+    is_block: Column<Advice>,
+    /// Index of the input byte on input rows.
+    index: Column<Advice>,
+    /// The range checked bytes of the row. On input rows, `bytes[0]` is the input byte. On block
+    /// rows, `bytes[0]` and `bytes[1]` are the limbs of q and r, `bytes[2..5]` and `bytes[5..8]`
+    /// are the little-endian bytes of the offset carry limbs `c[k]` and `c[n + k]`.
+    bytes: [Column<Advice>; 8],
+    /// RLC of the input bytes so far.
+    input_acc: Column<Advice>,
+    /// RLC of the header bytes so far.
+    header_acc: Column<Advice>,
+    /// RLC of the base bytes so far.
+    base_acc: Column<Advice>,
+    /// RLC of the exponent bytes so far.
+    exp_acc: Column<Advice>,
+    /// RLC of the first 32 exponent bytes so far.
+    exp_head_acc: Column<Advice>,
+    /// RLC of the modulus bytes so far, kept on the block rows.
+    modulus_acc: Column<Advice>,
+    /// Sum of the modulus bytes so far.
+    modulus_sum: Column<Advice>,
+    /// index < 96, 96 + base_len, 96 + base_len + exp_len, 96 + base_len + exp_len +
+    /// modulus_len and 96 + base_len + 32.
+    section_lt: [LtConfig<F, 2>; 5],
+    /// Whether the row is the last input row.
+    is_input_end: IsZeroConfig<F>,
+    /// Whether the modulus is zero.
+    is_modulus_zero: IsZeroConfig<F>,
+    /// The block kind flags.
+    kind: [Column<Advice>; 5],
+    /// Whether the row is the first one of its block.
+    is_block_start: Column<Advice>,
+    /// Index of the limbs of the row, decreasing to 0 at the last row of the block.
+    limb_idx: Column<Advice>,
+    /// Whether the row is the last one of its block.
+    is_block_end: IsZeroConfig<F>,
+    /// Horner accumulators of the q, r and carries limbs.
+    limb_accs: [Column<Advice>; 4],
+    /// challenge ^ number of limbs so far.
+    pow: Column<Advice>,
+    /// RLC of the first operand of the block.
+    a: Column<Advice>,
+    /// RLC of the second operand of the block.
+    b: Column<Advice>,
+    /// RLC of the reduced base.
+    base_red: Column<Advice>,
+    /// The exponent bit of the block.
+    bit: Column<Advice>,
+    /// Index of the exponent bit in its byte.
+    bit_idx: Column<Advice>,
+    /// Whether the bit is the least significant one of its byte.
+    is_last_bit: IsZeroConfig<F>,
+    /// Number of exponent bytes not processed.
+    exp_bytes_left: Column<Advice>,
+    /// Whether all exponent bytes have been processed.
+    is_exp_done: IsZeroConfig<F>,
+    /// The processed bits of the current exponent byte.
+    byte_acc: Column<Advice>,
+    /// RLC of the processed exponent bytes.
+    exp_rlc: Column<Advice>,
+    /// The ModExp table.
     modexp_table: ModExpTable,
+    /// u8 lookup table.
+    u8_table: U8Table,
+}
+
+/// Arguments to configure ModExp circuit
+pub struct ModExpCircuitConfigArgs<F: Field> {
+    /// The ModExp table.
+    pub modexp_table: ModExpTable,
+    /// u8 lookup table.
+    pub u8_table: U8Table,
+    /// Challenges
+    pub challenges: Challenges<Expression<F>>,
 }
 
-impl<F: Field> SubCircuitConfig<F> for ModExpCircuitConfig {
-    type ConfigArgs = ModExpTable;
+impl<F: Field> SubCircuitConfig<F> for ModExpCircuitConfig<F> {
+    type ConfigArgs = ModExpCircuitConfigArgs<F>;
 
     /// Return a new ModExpCircuitConfig
-    fn new(meta: &mut ConstraintSystem<F>, modexp_table: Self::ConfigArgs) -> Self {
-        let rangecheck_config = RangeCheckChip::configure(meta);
-        let modexp_config = ModExpChip::configure(meta, &rangecheck_config);
+    fn new(
+        meta: &mut ConstraintSystem<F>,
+        Self::ConfigArgs {
+            modexp_table,
+            u8_table,
+            challenges,
+        }: Self::ConfigArgs,
+    ) -> Self {
+        let q_enable = meta.fixed_column();
+        let q_first = meta.fixed_column();
+        let q_last = meta.fixed_column();
+        let is_input = meta.advice_column();
+        let is_block = meta.advice_column();
+        let index = meta.advice_column();
+        let bytes = [(); 8].map(|_| meta.advice_column());
+        let input_acc = advice_column_in_phase2(meta, challenges.phases());
+        let header_acc = advice_column_in_phase2(meta, challenges.phases());
+        let base_acc = advice_column_in_phase2(meta, challenges.phases());
+        let exp_acc = advice_column_in_phase2(meta, challenges.phases());
+        let exp_head_acc = advice_column_in_phase2(meta, challenges.phases());
+        let modulus_acc = advice_column_in_phase2(meta, challenges.phases());
+        let modulus_sum = meta.advice_column();
+        let kind = [(); 5].map(|_| meta.advice_column());
+        let is_block_start = meta.advice_column();
+        let limb_idx = meta.advice_column();
+        let limb_accs = [(); 4].map(|_| advice_column_in_phase2(meta, challenges.phases()));
+        let pow = advice_column_in_phase2(meta, challenges.phases());
+        let a = advice_column_in_phase2(meta, challenges.phases());
+        let b = advice_column_in_phase2(meta, challenges.phases());
+        let base_red = advice_column_in_phase2(meta, challenges.phases());
+        let bit = meta.advice_column();
+        let bit_idx = meta.advice_column();
+        let exp_bytes_left = meta.advice_column();
+        let byte_acc = meta.advice_column();
+        let exp_rlc = advice_column_in_phase2(meta, challenges.phases());
+
+        let r = challenges.keccak_input();
+        let input_limit = MODEXP_INPUT_LIMIT.expr();
+
+        let q_input = |meta: &mut VirtualCells<'_, F>| {
+            and::expr([
+                meta.query_fixed(q_enable, Rotation::cur()),
+                meta.query_advice(is_input, Rotation::cur()),
+            ])
+        };
+        let q_block = |meta: &mut VirtualCells<'_, F>| {
+            and::expr([
+                meta.query_fixed(q_enable, Rotation::cur()),
+                meta.query_advice(is_block, Rotation::cur()),
+            ])
+        };
+
+        let section_lt = [0, 1, 2, 3, 4].map(|i| {
+            LtChip::configure(
+                meta,
+                q_input,
+                |meta| meta.query_advice(index, Rotation::cur()),
+                |meta| {
+                    let [base_len, exp_len, modulus_len] = [
+                        modexp_table.base_len,
+                        modexp_table.exp_len,
+                        modexp_table.modulus_len,
+                    ]
+                    .map(|col| meta.query_advice(col, Rotation::cur()));
+                    let bounds = [
+                        96.expr(),
+                        96.expr() + base_len.clone(),
+                        96.expr() + base_len.clone() + exp_len.clone(),
+                        96.expr() + base_len.clone() + exp_len + modulus_len,
+                        128.expr() + base_len,
+                    ];
+                    bounds[i].clone()
+                },
+                u8_table.into(),
+            )
+        });
+        let [input_end_inv, modulus_inv, block_end_inv, last_bit_inv, exp_done_inv] =
+            [(); 5].map(|_| meta.advice_column());
+        let is_input_end = IsZeroChip::configure(
+            meta,
+            q_input,
+            |meta| meta.query_advice(index, Rotation::cur()) - input_limit.clone() + 1.expr(),
+            input_end_inv,
+        );
+        let is_modulus_zero = IsZeroChip::configure(
+            meta,
+            q_input,
+            |meta| meta.query_advice(modulus_sum, Rotation::cur()),
+            modulus_inv,
+        );
+        let is_block_end = IsZeroChip::configure(
+            meta,
+            q_block,
+            |meta| meta.query_advice(limb_idx, Rotation::cur()),
+            block_end_inv,
+        );
+        let is_last_bit = IsZeroChip::configure(
+            meta,
+            q_block,
+            |meta| meta.query_advice(bit_idx, Rotation::cur()),
+            last_bit_inv,
+        );
+        let is_exp_done = IsZeroChip::configure(
+            meta,
+            q_block,
+            |meta| meta.query_advice(exp_bytes_left, Rotation::cur()),
+            exp_done_inv,
+        );
+
+        for byte in bytes {
+            meta.lookup("modexp circuit: byte range", |meta| {
+                vec![(
+                    meta.query_fixed(q_enable, Rotation::cur())
+                        * meta.query_advice(byte, Rotation::cur()),
+                    u8_table.into(),
+                )]
+            });
+        }
+
+        meta.create_gate("modexp circuit: row types", |meta| {
+            let mut cb = BaseConstraintBuilder::default();
+
+            let is_input = meta.query_advice(is_input, Rotation::cur());
+            let is_block = meta.query_advice(is_block, Rotation::cur());
+            let q_head = meta.query_advice(modexp_table.q_head, Rotation::cur());
+            cb.require_boolean("is_input is boolean", is_input.clone());
+            cb.require_boolean("is_block is boolean", is_block.clone());
+            cb.require_boolean("q_head is boolean", q_head.clone());
+            cb.require_boolean(
+                "a row is either input, block or padding",
+                is_input.clone() + is_block.clone(),
+            );
+
+            cb.condition(q_head, |cb| {
+                cb.require_equal("q_head is an input row", is_input, 1.expr());
+                cb.require_zero(
+                    "q_head is the first input row",
+                    meta.query_advice(index, Rotation::cur()),
+                );
+            });
+
+            let kind = kind.map(|col| meta.query_advice(col, Rotation::cur()));
+            let is_exp_done = is_exp_done.expr();
+            cb.condition(is_block.clone(), |cb| {
+                for flag in kind.iter() {
+                    cb.require_boolean("block kind flag is boolean", flag.clone());
+                }
+                cb.require_equal("block has exactly one kind", sum::expr(&kind), 1.expr());
+                cb.require_boolean(
+                    "is_block_start is boolean",
+                    meta.query_advice(is_block_start, Rotation::cur()),
+                );
+                cb.require_boolean("bit is boolean", meta.query_advice(bit, Rotation::cur()));
+            });
+            cb.condition(
+                is_block.clone() * kind[BlockKind::Cmp as usize].clone(),
+                |cb| {
+                    cb.require_equal(
+                        "comparison after all exponent bytes",
+                        is_exp_done.clone(),
+                        1.expr(),
+                    );
+                },
+            );
+            cb.condition(is_block * kind[BlockKind::Square as usize].clone(), |cb| {
+                cb.require_zero("square while exponent bytes are left", is_exp_done);
+            });
+
+            cb.gate(meta.query_fixed(q_enable, Rotation::cur()))
+        });
+
+        meta.create_gate("modexp circuit: first row", |meta| {
+            let mut cb = BaseConstraintBuilder::default();
+            cb.require_zero(
+                "first row is not a block row",
+                meta.query_advice(is_block, Rotation::cur()),
+            );
+            cb.require_equal(
+                "first row is either a head or padding",
+                meta.query_advice(is_input, Rotation::cur()),
+                meta.query_advice(modexp_table.q_head, Rotation::cur()),
+            );
+            cb.gate(meta.query_fixed(q_first, Rotation::cur()))
+        });
+
+        meta.create_gate("modexp circuit: last row", |meta| {
+            let mut cb = BaseConstraintBuilder::default();
+            cb.require_zero(
+                "last row is padding",
+                meta.query_advice(is_input, Rotation::cur())
+                    + meta.query_advice(is_block, Rotation::cur()),
+            );
+            cb.gate(meta.query_fixed(q_last, Rotation::cur()))
+        });
+
+        meta.create_gate("modexp circuit: input rows", |meta| {
+            let mut cb = BaseConstraintBuilder::default();
+
+            let not_head = not::expr(meta.query_advice(modexp_table.q_head, Rotation::cur()));
+            let byte = meta.query_advice(bytes[0], Rotation::cur());
+            let lt = section_lt
+                .clone()
+                .map(|config| config.is_lt(meta, Rotation::cur()));
+            let in_exp = lt[2].clone() - lt[1].clone();
+            for (acc, flag) in [
+                (input_acc, 1.expr()),
+                (header_acc, lt[0].clone()),
+                (base_acc, lt[1].clone() - lt[0].clone()),
+                (exp_acc, in_exp.clone()),
+                (exp_head_acc, in_exp * lt[4].clone()),
+                (modulus_acc, lt[3].clone() - lt[2].clone()),
+            ] {
+                let prev = not_head.clone() * meta.query_advice(acc, Rotation::prev());
+                cb.require_equal(
+                    "input section acc = flag ? acc::prev * r + byte : acc::prev",
+                    meta.query_advice(acc, Rotation::cur()),
+                    prev.clone() + flag * (prev * r.clone() + byte.clone() - prev.clone()),
+                );
+            }
+            cb.require_equal(
+                "modulus_sum = modulus_sum::prev + in_modulus * byte",
+                meta.query_advice(modulus_sum, Rotation::cur()),
+                not_head * meta.query_advice(modulus_sum, Rotation::prev())
+                    + (lt[3].clone() - lt[2].clone()) * byte,
+            );
+
+            cb.gate(q_input(meta))
+        });
+
+        meta.create_gate("modexp circuit: block rows", |meta| {
+            let mut cb = BaseConstraintBuilder::default();
+
+            let not_start = not::expr(meta.query_advice(is_block_start, Rotation::cur()));
+            let bytes = bytes.map(|col| meta.query_advice(col, Rotation::cur()));
+            let carry = |bytes: &[Expression<F>]| {
+                bytes[0].clone() + bytes[1].clone() * 256.expr() + bytes[2].clone() * 65536.expr()
+                    - CARRY_OFFSET.expr()
+            };
+            let limbs = [
+                bytes[0].clone(),
+                bytes[1].clone(),
+                carry(&bytes[2..5]),
+                carry(&bytes[5..8]),
+            ];
+            for (acc, limb) in limb_accs.into_iter().zip(limbs) {
+                cb.require_equal(
+                    "limb acc = limb acc::prev * r + limb",
+                    meta.query_advice(acc, Rotation::cur()),
+                    not_start.clone() * meta.query_advice(acc, Rotation::prev()) * r.clone() + limb,
+                );
+            }
+            cb.require_equal(
+                "pow = pow::prev * r",
+                meta.query_advice(pow, Rotation::cur()),
+                (not_start.clone() * meta.query_advice(pow, Rotation::prev())
+                    + not::expr(not_start))
+                    * r.clone(),
+            );
+
+            let [q_acc, r_acc, carry_lo_acc, carry_hi_acc] =
+                limb_accs.map(|col| meta.query_advice(col, Rotation::cur()));
+            let modulus = meta.query_advice(modulus_acc, Rotation::cur());
+            let a = meta.query_advice(a, Rotation::cur());
+            let is_cmp = meta.query_advice(kind[BlockKind::Cmp as usize], Rotation::cur());
+            let lhs = select::expr(
+                is_cmp,
+                modulus.clone() - a.clone() - 1.expr(),
+                a * meta.query_advice(b, Rotation::cur()) - q_acc * modulus,
+            );
+            cb.condition(is_block_end.expr(), |cb| {
+                cb.require_equal(
+                    "lhs - r = (X - 256) * c at X = challenge",
+                    lhs - r_acc,
+                    (r.clone() - 256.expr())
+                        * (carry_lo_acc + meta.query_advice(pow, Rotation::cur()) * carry_hi_acc),
+                );
+            });
+
+            cb.gate(q_block(meta))
+        });
+
+        meta.create_gate("modexp circuit: transitions", |meta| {
+            let mut cb = BaseConstraintBuilder::default();
+
+            let cur = |meta: &mut VirtualCells<'_, F>, col: Column<Advice>| {
+                meta.query_advice(col, Rotation::cur())
+            };
+            let next = |meta: &mut VirtualCells<'_, F>, col: Column<Advice>| {
+                meta.query_advice(col, Rotation::next())
+            };
+
+            let [is_input_next, is_block_next, q_head_next] =
+                [is_input, is_block, modexp_table.q_head].map(|col| next(meta, col));
+            let [is_input, is_block] = [is_input, is_block].map(|col| cur(meta, col));
+            let kind_next = kind.map(|col| next(meta, col));
+            let r_acc = cur(meta, limb_accs[1]);
+            let block_end = is_block.clone() * is_block_end.expr();
+
+            // a new event starts with its head row, or the circuit continues with padding
+            let end_event = |cb: &mut BaseConstraintBuilder<F>| {
+                cb.require_zero("event ends", is_block_next.clone());
+                cb.require_equal(
+                    "next row is either a head or padding",
+                    is_input_next.clone(),
+                    q_head_next.clone(),
+                );
+            };
+            let start_block = |cb: &mut BaseConstraintBuilder<F>,
+                               meta: &mut VirtualCells<'_, F>,
+                               kinds: &[BlockKind]| {
+                cb.require_equal("next row is a block row", is_block_next.clone(), 1.expr());
+                cb.require_equal("block starts", next(meta, is_block_start), 1.expr());
+                cb.require_equal(
+                    "next block kind",
+                    sum::expr(kinds.iter().map(|&kind| kind_next[kind as usize].clone())),
+                    1.expr(),
+                );
+            };
+            let state_unchanged = |cb: &mut BaseConstraintBuilder<F>,
+                                   meta: &mut VirtualCells<'_, F>,
+                                   cols: &[Column<Advice>]| {
+                for &col in cols {
+                    cb.require_equal("state unchanged", next(meta, col), cur(meta, col));
+                }
+            };
+            let exp_state = [base_red, bit_idx, exp_bytes_left, byte_acc, exp_rlc];
+
+            cb.condition(1.expr() - is_input.clone() - is_block.clone(), |cb| {
+                cb.require_zero(
+                    "padding is followed by padding",
+                    is_input_next.clone() + is_block_next.clone(),
+                );
+            });
+
+            cb.condition(
+                (is_input_next.clone() + is_block_next.clone()) * not::expr(q_head_next.clone()),
+                |cb| {
+                    for col in <ModExpTable as LookupTable<F>>::advice_columns(&modexp_table)
+                        .into_iter()
+                        .skip(1)
+                    {
+                        cb.require_equal(
+                            "table columns are the same in an event",
+                            next(meta, col),
+                            cur(meta, col),
+                        );
+                    }
+                },
+            );
+            cb.condition(is_block_next.clone(), |cb| {
+                state_unchanged(cb, meta, &[modulus_acc, exp_acc]);
+            });
+
+            cb.condition(is_input.clone() * not::expr(is_input_end.expr()), |cb| {
+                cb.require_equal("next row is an input row", is_input_next.clone(), 1.expr());
+                cb.require_zero("next row is not a head", q_head_next.clone());
+                cb.require_equal(
+                    "index increases",
+                    next(meta, index),
+                    cur(meta, index) + 1.expr(),
+                );
+            });
+
+            cb.condition(is_input.clone() * is_input_end.expr(), |cb| {
+                for (acc, col) in [
+                    (input_acc, modexp_table.input_rlc),
+                    (header_acc, modexp_table.header_rlc),
+                    (exp_head_acc, modexp_table.exp_head_rlc),
+                ] {
+                    cb.require_equal("input section rlc", cur(meta, acc), cur(meta, col));
+                }
+            });
+            cb.condition(
+                is_input.clone() * is_input_end.expr() * is_modulus_zero.expr(),
+                |cb| {
+                    cb.require_zero(
+                        "result is zero for a zero modulus",
+                        cur(meta, modexp_table.result_rlc),
+                    );
+                    end_event(cb);
+                },
+            );
+            cb.condition(
+                is_input * is_input_end.expr() * not::expr(is_modulus_zero.expr()),
+                |cb| {
+                    start_block(cb, meta, &[BlockKind::Reduce]);
+                    cb.require_equal("reduce a = base", next(meta, a), cur(meta, base_acc));
+                    cb.require_equal("reduce b = 1", next(meta, b), 1.expr());
+                },
+            );
+
+            cb.condition(is_block.clone() * not::expr(is_block_end.expr()), |cb| {
+                cb.require_equal("next row is a block row", is_block_next.clone(), 1.expr());
+                cb.require_zero("block continues", next(meta, is_block_start));
+                cb.require_equal(
+                    "limb_idx decreases",
+                    next(meta, limb_idx),
+                    cur(meta, limb_idx) - 1.expr(),
+                );
+                state_unchanged(cb, meta, &kind);
+                state_unchanged(cb, meta, &[a, b, bit]);
+                state_unchanged(cb, meta, &exp_state);
+            });
+
+            let kind_cur = kind.map(|col| cur(meta, col));
+            cb.condition(
+                block_end.clone() * kind_cur[BlockKind::Reduce as usize].clone(),
+                |cb| {
+                    start_block(cb, meta, &[BlockKind::One]);
+                    cb.require_equal("one a = 1", next(meta, a), 1.expr());
+                    cb.require_equal("one b = 1", next(meta, b), 1.expr());
+                    cb.require_equal("reduced base", next(meta, base_red), r_acc.clone());
+                    cb.require_equal(
+                        "all exponent bytes left",
+                        next(meta, exp_bytes_left),
+                        cur(meta, modexp_table.exp_len),
+                    );
+                    cb.require_equal("first bit index", next(meta, bit_idx), 7.expr());
+                    cb.require_zero("byte_acc starts with 0", next(meta, byte_acc));
+                    cb.require_zero("exp_rlc starts with 0", next(meta, exp_rlc));
+                },
+            );
+            cb.condition(
+                block_end.clone() * kind_cur[BlockKind::One as usize].clone(),
+                |cb| {
+                    start_block(cb, meta, &[BlockKind::Square, BlockKind::Cmp]);
+                    cb.require_equal("a = acc", next(meta, a), r_acc.clone());
+                    cb.require_equal("b = acc", next(meta, b), r_acc.clone());
+                    state_unchanged(cb, meta, &exp_state);
+                },
+            );
+            cb.condition(
+                block_end.clone() * kind_cur[BlockKind::Square as usize].clone(),
+                |cb| {
+                    start_block(cb, meta, &[BlockKind::Mult]);
+                    let bit_next = next(meta, bit);
+                    cb.require_equal("a = acc ^ 2", next(meta, a), r_acc.clone());
+                    cb.require_equal(
+                        "b = bit ? base : 1",
+                        next(meta, b),
+                        select::expr(bit_next, cur(meta, base_red), 1.expr()),
+                    );
+                    state_unchanged(cb, meta, &exp_state);
+                },
+            );
+            cb.condition(
+                block_end.clone() * kind_cur[BlockKind::Mult as usize].clone(),
+                |cb| {
+                    start_block(cb, meta, &[BlockKind::Square, BlockKind::Cmp]);
+                    cb.require_equal("a = acc", next(meta, a), r_acc.clone());
+                    cb.require_equal("b = acc", next(meta, b), r_acc.clone());
+                    state_unchanged(cb, meta, &[base_red]);
+                },
+            );
+            let byte = cur(meta, byte_acc) * 2.expr() + cur(meta, bit);
+            cb.condition(
+                block_end.clone() * kind_cur[BlockKind::Mult as usize].clone() * is_last_bit.expr(),
+                |cb| {
+                    cb.require_equal(
+                        "exp_rlc = exp_rlc * r + byte",
+                        next(meta, exp_rlc),
+                        cur(meta, exp_rlc) * r.clone() + byte.clone(),
+                    );
+                    cb.require_zero("byte_acc restarts", next(meta, byte_acc));
+                    cb.require_equal("bit index restarts", next(meta, bit_idx), 7.expr());
+                    cb.require_equal(
+                        "exponent byte done",
+                        next(meta, exp_bytes_left),
+                        cur(meta, exp_bytes_left) - 1.expr(),
+                    );
+                },
+            );
+            cb.condition(
+                block_end.clone()
+                    * kind_cur[BlockKind::Mult as usize].clone()
+                    * not::expr(is_last_bit.expr()),
+                |cb| {
+                    cb.require_equal("byte_acc = byte", next(meta, byte_acc), byte);
+                    cb.require_equal(
+                        "bit index decreases",
+                        next(meta, bit_idx),
+                        cur(meta, bit_idx) - 1.expr(),
+                    );
+                    state_unchanged(cb, meta, &[exp_bytes_left, exp_rlc]);
+                },
+            );
+            cb.condition(
+                block_end * kind_cur[BlockKind::Cmp as usize].clone(),
+                |cb| {
+                    cb.require_equal(
+                        "result = acc",
+                        cur(meta, modexp_table.result_rlc),
+                        cur(meta, a),
+                    );
+                    cb.require_equal(
+                        "processed exponent bytes are the input ones",
+                        cur(meta, exp_rlc),
+                        cur(meta, exp_acc),
+                    );
+                    end_event(cb);
+                },
+            );
+
+            cb.gate(
+                meta.query_fixed(q_enable, Rotation::cur())
+                    - meta.query_fixed(q_last, Rotation::cur()),
+            )
+        });
+
         Self {
-            modexp_config,
-            rangecheck_config,
+            q_enable,
+            q_first,
+            q_last,
+            is_input,
+            is_block,
+            index,
+            bytes,
+            input_acc,
+            header_acc,
+            base_acc,
+            exp_acc,
+            exp_head_acc,
+            modulus_acc,
+            modulus_sum,
+            section_lt,
+            is_input_end,
+            is_modulus_zero,
+            kind,
+            is_block_start,
+            limb_idx,
+            is_block_end,
+            limb_accs,
+            pow,
+            a,
+            b,
+            base_red,
+            bit,
+            bit_idx,
+            is_last_bit,
+            exp_bytes_left,
+            is_exp_done,
+            byte_acc,
+            exp_rlc,
             modexp_table,
+            u8_table,
         }
     }
 }
 
-impl ModExpCircuitConfig {
-    pub(crate) fn assign_group<F: Field>(
+/// The assignments of a row of the ModExp circuit.
+#[derive(Clone, Debug)]
+struct ModExpRow<F> {
+    is_input: bool,
+    is_block: bool,
+    q_head: bool,
+    table: [Value<F>; 7],
+    section_bounds: [u64; 5],
+    index: u64,
+    bytes: [u8; 8],
+    /// input, header, base, exp, exp_head and modulus accumulators
+    input_accs: [Value<F>; 6],
+    modulus_sum: u64,
+    kind: Option<BlockKind>,
+    is_block_start: bool,
+    limb_idx: u64,
+    limb_accs: [Value<F>; 4],
+    pow: Value<F>,
+    a: Value<F>,
+    b: Value<F>,
+    base_red: Value<F>,
+    bit: bool,
+    bit_idx: u64,
+    exp_bytes_left: u64,
+    byte_acc: u64,
+    exp_rlc: Value<F>,
+}
+
+impl<F: Field> ModExpRow<F> {
+    fn padding() -> Self {
+        let zero = Value::known(F::zero());
+        Self {
+            is_input: false,
+            is_block: false,
+            q_head: false,
+            table: [zero; 7],
+            section_bounds: [0; 5],
+            index: 0,
+            bytes: [0; 8],
+            input_accs: [zero; 6],
+            modulus_sum: 0,
+            kind: None,
+            is_block_start: false,
+            limb_idx: 0,
+            limb_accs: [zero; 4],
+            pow: zero,
+            a: zero,
+            b: zero,
+            base_red: zero,
+            bit: false,
+            bit_idx: 0,
+            exp_bytes_left: 0,
+            byte_acc: 0,
+            exp_rlc: zero,
+        }
+    }
+}
+
+/// Number of rows taken by a ModExp call.
+fn event_rows(event: &BigModExp) -> usize {
+    let modulus_len = event.modulus.len();
+    let block_rows = if event.modulus.iter().all(|&byte| byte == 0) {
+        0
+    } else {
+        event.base.len().max(modulus_len) + modulus_len * (2 + 16 * event.exponent.len())
+    };
+    MODEXP_INPUT_LIMIT + block_rows
+}
+
+/// Push the rows of a block proving `a * b = q * modulus + r` (or `r = modulus - a - 1` for
+/// `BlockKind::Cmp`) with `n` limbs, and return r.
+fn push_block<F: Field>(
+    rows: &mut Vec<ModExpRow<F>>,
+    template: &ModExpRow<F>,
+    kind: BlockKind,
+    [a, b, modulus]: [&BigUint; 3],
+    n: usize,
+    r: Value<F>,
+) -> BigUint {
+    let (quotient, remainder) = if kind == BlockKind::Cmp {
+        (BigUint::default(), modulus - a - 1u32)
+    } else {
+        let product = a * b;
+        (&product / modulus, &product % modulus)
+    };
+    let limbs = |value: &BigUint| {
+        let mut limbs = value.to_bytes_le();
+        assert!(limbs.len() <= n, "block operand exceeds {n} limbs");
+        limbs.resize(n, 0);
+        limbs.into_iter().map(i64::from).collect::<Vec<_>>()
+    };
+    let [a_limbs, b_limbs, q_limbs, r_limbs, m_limbs] =
+        [a, b, &quotient, &remainder, modulus].map(limbs);
+
+    // coefficients of lhs(X) - r(X)
+    let mut coeffs = vec![0i64; 2 * n];
+    if kind == BlockKind::Cmp {
+        for k in 0..n {
+            coeffs[k] = m_limbs[k] - a_limbs[k];
+        }
+        coeffs[0] -= 1;
+    } else {
+        for i in 0..n {
+            for j in 0..n {
+                coeffs[i + j] += a_limbs[i] * b_limbs[j] - q_limbs[i] * m_limbs[j];
+            }
+        }
+    }
+    for k in 0..n {
+        coeffs[k] -= r_limbs[k];
+    }
+    // lhs(X) - r(X) = (X - 256) * c(X), with the carries offset by CARRY_OFFSET
+    let mut carries = Vec::with_capacity(2 * n);
+    let mut carry = 0;
+    for coeff in coeffs {
+        assert_eq!((carry - coeff) % 256, 0, "inexact carry");
+        carry = (carry - coeff) / 256;
+        let offset_carry = carry + CARRY_OFFSET as i64;
+        assert!((0..1 << 24).contains(&offset_carry), "carry out of range");
+        carries.push(offset_carry as u64);
+    }
+    assert_eq!(carry, 0, "lhs and r are not equal");
+
+    let [a_rlc, b_rlc] = [a, b].map(|value| rlc_be_bytes(&value.to_bytes_be(), r));
+    let mut limb_accs = [Value::known(F::zero()); 4];
+    let mut pow = Value::known(F::one());
+    for k in (0..n).rev() {
+        let [lo_0, lo_1, lo_2, _] = (carries[k] as u32).to_le_bytes();
+        let [hi_0, hi_1, hi_2, _] = (carries[n + k] as u32).to_le_bytes();
+        let limbs = [
+            F::from(q_limbs[k] as u64),
+            F::from(r_limbs[k] as u64),
+            F::from(carries[k]) - F::from(CARRY_OFFSET),
+            F::from(carries[n + k]) - F::from(CARRY_OFFSET),
+        ];
+        for (acc, limb) in limb_accs.iter_mut().zip(limbs) {
+            *acc = *acc * r + Value::known(limb);
+        }
+        pow = pow * r;
+        rows.push(ModExpRow {
+            is_input: false,
+            is_block: true,
+            q_head: false,
+            bytes: [
+                q_limbs[k] as u8,
+                r_limbs[k] as u8,
+                lo_0,
+                lo_1,
+                lo_2,
+                hi_0,
+                hi_1,
+                hi_2,
+            ],
+            kind: Some(kind),
+            is_block_start: k == n - 1,
+            limb_idx: k as u64,
+            limb_accs,
+            pow,
+            a: a_rlc,
+            b: b_rlc,
+            ..template.clone()
+        });
+    }
+    remainder
+}
+
+/// Get the rows of a ModExp call.
+fn event_assignments<F: Field>(
+    event: &BigModExp,
+    challenges: &Challenges<Value<F>>,
+) -> Vec<ModExpRow<F>> {
+    let r = challenges.keccak_input();
+    let [base_len, exp_len, modulus_len] =
+        [&event.base, &event.exponent, &event.modulus].map(|bytes| bytes.len() as u64);
+    let section_bounds = [
+        96,
+        96 + base_len,
+        96 + base_len + exp_len,
+        96 + base_len + exp_len + modulus_len,
+        128 + base_len,
+    ];
+
+    let mut rows = Vec::with_capacity(event_rows(event));
+    let mut row = ModExpRow {
+        table: ModExpTable::assignments(event, challenges),
+        section_bounds,
+        ..ModExpRow::padding()
+    };
+    for (index, byte) in event.padded_input().into_iter().enumerate() {
+        let index = index as u64;
+        let lt = section_bounds.map(|bound| index < bound);
+        let in_exp = lt[2] && !lt[1];
+        let in_modulus = lt[3] && !lt[2];
+        let flags = [
+            true,
+            lt[0],
+            lt[1] && !lt[0],
+            in_exp,
+            in_exp && lt[4],
+            in_modulus,
+        ];
+        for (acc, flag) in row.input_accs.iter_mut().zip(flags) {
+            if flag {
+                *acc = *acc * r + Value::known(F::from(byte as u64));
+            }
+        }
+        if in_modulus {
+            row.modulus_sum += byte as u64;
+        }
+        rows.push(ModExpRow {
+            is_input: true,
+            q_head: index == 0,
+            index,
+            bytes: [byte, 0, 0, 0, 0, 0, 0, 0],
+            ..row.clone()
+        });
+    }
+    if row.modulus_sum == 0 {
+        return rows;
+    }
+
+    let [base, modulus] = [&event.base, &event.modulus].map(|bytes| BigUint::from_bytes_be(bytes));
+    let one = BigUint::from(1u32);
+    let n = event.modulus.len();
+    let base_red = push_block(
+        &mut rows,
+        &row,
+        BlockKind::Reduce,
+        [&base, &one, &modulus],
+        n.max(event.base.len()),
+        r,
+    );
+    row.base_red = rlc_be_bytes(&base_red.to_bytes_be(), r);
+    row.exp_bytes_left = exp_len;
+    row.bit_idx = 7;
+    let mut acc = push_block(
+        &mut rows,
+        &row,
+        BlockKind::One,
+        [&one, &one, &modulus],
+        n,
+        r,
+    );
+    for &exp_byte in event.exponent.iter() {
+        for bit_idx in (0..8).rev() {
+            let bit = (exp_byte >> bit_idx) & 1 == 1;
+            row.bit = bit;
+            row.bit_idx = bit_idx;
+            let squared = push_block(
+                &mut rows,
+                &row,
+                BlockKind::Square,
+                [&acc, &acc, &modulus],
+                n,
+                r,
+            );
+            let factor = if bit { &base_red } else { &one };
+            acc = push_block(
+                &mut rows,
+                &row,
+                BlockKind::Mult,
+                [&squared, factor, &modulus],
+                n,
+                r,
+            );
+            row.byte_acc = (row.byte_acc << 1) + bit as u64;
+        }
+        row.exp_rlc = row.exp_rlc * r + Value::known(F::from(exp_byte as u64));
+        row.exp_bytes_left -= 1;
+        row.byte_acc = 0;
+    }
+    row.bit = false;
+    row.bit_idx = 7;
+    push_block(
+        &mut rows,
+        &row,
+        BlockKind::Cmp,
+        [&acc, &acc, &modulus],
+        n,
+        r,
+    );
+    debug_assert_eq!(rows.len(), event_rows(event));
+
+    rows
+}
+
+impl<F: Field> ModExpCircuitConfig<F> {
+    fn assign_row(
         &self,
         region: &mut Region<F>,
-        table_offset: usize,
-        mut calc_offset: usize,
-        event: &BigModExp,
-        modexp_chip: &ModExpChip<F>,
-        range_check_chip: &mut RangeCheckChip<F>,
-    ) -> Result<usize, Error> {
-        let base = self.assign_value(region, table_offset, self.modexp_table.base, &event.base)?;
-        let exp =
-            self.assign_value(region, table_offset, self.modexp_table.exp, &event.exponent)?;
-        let modulus = self.assign_value(
-            region,
-            table_offset,
-            self.modexp_table.modulus,
-            &event.modulus,
-        )?;
-        let ret = modexp_chip.mod_exp(
-            region,
-            range_check_chip,
-            &mut calc_offset,
-            &base,
-            &exp,
-            &modulus,
-        )?;
-        for i in 0..4 {
-            region.assign_fixed(
-                || format!("modexp table head {}", table_offset + i),
-                self.modexp_table.q_head,
-                table_offset + i,
-                || Value::known(if i == 0 { F::one() } else { F::zero() }),
+        offset: usize,
+        row: &ModExpRow<F>,
+    ) -> Result<(), Error> {
+        let bool_value = |flag: bool| Value::known(if flag { F::one() } else { F::zero() });
+        let u64_value = |value: u64| Value::known(F::from(value));
+
+        let mut advices = vec![
+            (self.is_input, bool_value(row.is_input)),
+            (self.is_block, bool_value(row.is_block)),
+            (self.modexp_table.q_head, bool_value(row.q_head)),
+            (self.index, u64_value(row.index)),
+            (self.modulus_sum, u64_value(row.modulus_sum)),
+            (self.is_block_start, bool_value(row.is_block_start)),
+            (self.limb_idx, u64_value(row.limb_idx)),
+            (self.pow, row.pow),
+            (self.a, row.a),
+            (self.b, row.b),
+            (self.base_red, row.base_red),
+            (self.bit, bool_value(row.bit)),
+            (self.bit_idx, u64_value(row.bit_idx)),
+            (self.exp_bytes_left, u64_value(row.exp_bytes_left)),
+            (self.byte_acc, u64_value(row.byte_acc)),
+            (self.exp_rlc, row.exp_rlc),
+        ];
+        advices.extend(
+            <ModExpTable as LookupTable<F>>::advice_columns(&self.modexp_table)
+                .into_iter()
+                .skip(1)
+                .zip_eq(row.table),
+        );
+        advices.extend(
+            self.bytes
+                .into_iter()
+                .zip(row.bytes.map(|byte| u64_value(byte as u64))),
+        );
+        advices.extend(
+            [
+                self.input_acc,
+                self.header_acc,
+                self.base_acc,
+                self.exp_acc,
+                self.exp_head_acc,
+                self.modulus_acc,
+            ]
+            .into_iter()
+            .zip(row.input_accs),
+        );
+        advices.extend(self.limb_accs.into_iter().zip(row.limb_accs));
+        advices.extend(
+            self.kind
+                .into_iter()
+                .zip(BLOCK_KINDS.map(|kind| bool_value(row.kind == Some(kind)))),
+        );
+        for (column, value) in advices {
+            region.assign_advice(
+                || format!("modexp circuit: {column:?} {offset}"),
+                column,
+                offset,
+                || value,
             )?;
+        }
 
-            ret.limbs[i]
-                .cell
-                .clone()
-                .expect("should has assigned after modexp")
-                .copy_advice(
-                    || "copy to result limbs",
+        if row.is_input {
+            for (config, bound) in self.section_lt.iter().zip(row.section_bounds) {
+                LtChip::construct(config.clone()).assign(
                     region,
-                    self.modexp_table.result,
-                    table_offset + i,
+                    offset,
+                    F::from(row.index),
+                    F::from(bound),
                 )?;
+            }
+        }
+        for (config, value) in [
+            (
+                &self.is_input_end,
+                F::from(row.index) - F::from(MODEXP_INPUT_LIMIT as u64 - 1),
+            ),
+            (&self.is_modulus_zero, F::from(row.modulus_sum)),
+            (&self.is_block_end, F::from(row.limb_idx)),
+            (&self.is_last_bit, F::from(row.bit_idx)),
+            (&self.is_exp_done, F::from(row.exp_bytes_left)),
+        ] {
+            IsZeroChip::construct(config.clone()).assign(region, offset, Value::known(value))?;
         }
-        Ok(calc_offset)
+
+        Ok(())
     }
 
-    fn assign_value<F: Field>(
+    pub(crate) fn assign(
         &self,
-        region: &mut Region<F>,
-        offset: usize,
-        col: Column<Advice>,
-        value: &Word,
-    ) -> Result<Number<F>, Error> {
-        let limbs_v = ModExpTable::split_u256_108bit_limbs(value);
-        let native_v = ModExpTable::native_u256(value);
-        let mut limbs = Vec::new();
-
-        for (i, limb) in limbs_v.into_iter().enumerate() {
-            let fv = F::from_u128(limb);
-            let c = region.assign_advice(
-                || "assign modexp limb",
-                col,
-                offset + i,
-                || Value::known(fv),
-            )?;
-            limbs.push(Limb::new(Some(c), fv));
-        }
-        let c = region.assign_advice(
-            || "assign modexp native",
-            col,
-            offset + 3,
-            || Value::known(native_v),
-        )?;
-        limbs.push(Limb::new(Some(c), native_v));
-        Ok(Number {
-            limbs: limbs.try_into().expect("just 4 pushes"),
-        })
+        layouter: &mut impl Layouter<F>,
+        events: &[BigModExp],
+        num_rows: usize,
+        challenges: &Challenges<Value<F>>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "modexp circuit",
+            |mut region| {
+                let rows = events
+                    .iter()
+                    .flat_map(|event| event_assignments(event, challenges))
+                    .collect::<Vec<_>>();
+                assert!(
+                    rows.len() < num_rows,
+                    "no enough rows for modexp circuit, expected {}, limit {num_rows}",
+                    rows.len() + 1,
+                );
+                let padding = ModExpRow::padding();
+
+                for offset in 0..num_rows {
+                    for (column, flag) in [
+                        (self.q_enable, true),
+                        (self.q_first, offset == 0),
+                        (self.q_last, offset == num_rows - 1),
+                    ] {
+                        region.assign_fixed(
+                            || format!("modexp circuit: {column:?} {offset}"),
+                            column,
+                            offset,
+                            || Value::known(if flag { F::one() } else { F::zero() }),
+                        )?;
+                    }
+                    self.assign_row(&mut region, offset, rows.get(offset).unwrap_or(&padding))?;
+                }
+                Ok(())
+            },
+        )
     }
 }
 
-const MODEXPCONFIG_EACH_CHIP_ROWS: usize = 39962;
-
 /// ModExp circuit for precompile modexp
 #[derive(Clone, Debug, Default)]
-pub struct ModExpCircuit<F: Field>(Vec<BigModExp>, std::marker::PhantomData<F>);
+pub struct ModExpCircuit<F: Field> {
+    /// ModExp events
+    pub events: Vec<BigModExp>,
+    /// Max number of rows in the modexp circuit, 0 for as many as the events need.
+    pub max_rows: usize,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field> ModExpCircuit<F> {
+    /// Return a new ModExpCircuit
+    pub fn new(events: Vec<BigModExp>, max_rows: usize) -> Self {
+        Self {
+            events,
+            max_rows,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Number of rows required by the events, including the last padding row.
+    fn min_num_rows(events: &[BigModExp]) -> usize {
+        events.iter().map(event_rows).sum::<usize>() + 1
+    }
+}
 
 impl<F: Field> SubCircuit<F> for ModExpCircuit<F> {
-    type Config = ModExpCircuitConfig;
+    type Config = ModExpCircuitConfig<F>;
 
     fn unusable_rows() -> usize {
-        // No column queried at more than 4 distinct rotations, so returns 8 as
+        // No column queried at more than 3 distinct rotations, so returns 6 as
         // minimum unusable rows.
-        8
+        6
     }
 
     fn new_from_block(block: &witness::Block) -> Self {
-        let event_limit = block.circuits_params.max_keccak_rows / MODEXPCONFIG_EACH_CHIP_ROWS;
-
-        let mut exp_events = block.get_big_modexp();
-        if event_limit != 0 {
-            assert!(
-                exp_events.len() <= event_limit,
-                "no enough rows for modexp circuit, expected {}, limit {}",
-                exp_events.len(),
-                event_limit,
-            );
-            exp_events.resize(event_limit, Default::default());
-            log::info!("modexp circuit work with maxium {} entries", event_limit);
-        }
-
-        Self(exp_events, Default::default())
+        Self::new(
+            block.get_big_modexp(),
+            block.circuits_params.max_keccak_rows,
+        )
     }
 
     fn min_num_rows_block(block: &witness::Block) -> (usize, usize) {
-        let exp_events = block.get_big_modexp();
-        let real_len = exp_events.len() * MODEXPCONFIG_EACH_CHIP_ROWS;
+        let real_len = Self::min_num_rows(&block.get_big_modexp());
         (
             real_len,
             real_len
@@ -179,34 +1181,14 @@ impl<F: Field> SubCircuit<F> for ModExpCircuit<F> {
     fn synthesize_sub(
         &self,
         config: &Self::Config,
-        _challenges: &Challenges<Value<F>>,
+        challenges: &Challenges<Value<F>>,
         layouter: &mut impl Layouter<F>,
     ) -> Result<(), Error> {
-        let modexp_chip = ModExpChip::new(config.modexp_config.clone());
-        let mut range_chip = RangeCheckChip::new(config.rangecheck_config.clone());
-
-        layouter.assign_region(
-            || "modexp circuit",
-            |mut region| {
-                range_chip.initialize(&mut region)?;
-                let _modexp_count = self.0.len();
-                let mut calc_offset = 0;
-                for (n, event) in self.0.iter().enumerate() {
-                    calc_offset = config.assign_group(
-                        &mut region,
-                        n * 4,
-                        calc_offset,
-                        event,
-                        &modexp_chip,
-                        &mut range_chip,
-                    )?;
-                }
-                //assert_eq!(max(calc_offset, range_chip.offset), MODEXPCONFIG_EACH_CHIP_ROWS *
-                // modexp_count);
-                Ok(())
-            },
-        )?;
-
-        config.modexp_table.fill_blank(layouter)
+        let num_rows = if self.max_rows == 0 {
+            Self::min_num_rows(&self.events)
+        } else {
+            self.max_rows
+        };
+        config.assign(layouter, &self.events, num_rows, challenges)
     }
 }
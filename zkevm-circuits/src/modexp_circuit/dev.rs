@@ -1,13 +1,12 @@
 use super::*;
-use crate::util::MockChallenges;
+use crate::table::U8Table;
 use halo2_proofs::{
     circuit::SimpleFloorPlanner,
-    halo2curves::bn256::Fr,
     plonk::{Circuit, ConstraintSystem},
 };
 
-impl Circuit<Fr> for ModExpCircuit<Fr> {
-    type Config = (ModExpCircuitConfig, MockChallenges);
+impl<F: Field> Circuit<F> for ModExpCircuit<F> {
+    type Config = (ModExpCircuitConfig<F>, Challenges);
     type FloorPlanner = SimpleFloorPlanner;
     #[cfg(feature = "circuit-params")]
     type Params = ();
@@ -16,21 +15,31 @@ impl Circuit<Fr> for ModExpCircuit<Fr> {
         Self::default()
     }
 
-    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
-        let modexp_table = ModExpTable::construct(meta);
-        let challenge = MockChallenges::construct(meta);
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let challenges = Challenges::construct(meta);
+        let modexp_table = ModExpTable::construct(meta, challenges.phases());
+        let u8_table = U8Table::construct(meta);
+        let challenges_expr = challenges.exprs(meta);
         (
-            <ModExpCircuitConfig as SubCircuitConfig<Fr>>::new(meta, modexp_table),
-            challenge,
+            ModExpCircuitConfig::new(
+                meta,
+                ModExpCircuitConfigArgs {
+                    modexp_table,
+                    u8_table,
+                    challenges: challenges_expr,
+                },
+            ),
+            challenges,
         )
     }
 
     fn synthesize(
         &self,
-        (config, challenge): Self::Config,
-        mut layouter: impl Layouter<Fr>,
+        (config, challenges): Self::Config,
+        mut layouter: impl Layouter<F>,
     ) -> Result<(), Error> {
-        let challenges = challenge.values(&layouter);
-        <Self as SubCircuit<Fr>>::synthesize_sub(self, &config, &challenges, &mut layouter)
+        let challenges = challenges.values(&layouter);
+        config.u8_table.load(&mut layouter)?;
+        self.synthesize_sub(&config, &challenges, &mut layouter)
     }
 }
@@ -1,57 +1,126 @@
-#![allow(unused_imports)]
 use super::*;
 
+use bus_mapping::precompile::{ModExpAuxData, MODEXP_SIZE_LIMIT};
 use eth_types::U256;
-use halo2_proofs::dev::MockProver;
+use halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr};
 
 #[test]
 fn test_modexp_circuit_00() {
-    let event1 = construct_modexp(Word::from(1u128), Word::from(3u128), Word::from(7u128));
+    let event1 = construct_modexp(&[1], &[3], &[7]);
 
-    let test_circuit = ModExpCircuit(vec![event1], Default::default());
-    let prover = MockProver::run(16, &test_circuit, vec![]).unwrap();
+    let test_circuit = ModExpCircuit::<Fr>::new(vec![event1], 0);
+    let prover = MockProver::run(12, &test_circuit, vec![]).unwrap();
     assert_eq!(prover.verify(), Ok(()));
 }
 
 #[test]
 fn test_modexp_circuit_01() {
-    let event1 = construct_modexp(Word::from(1u128), Word::from(2u128), Word::from(7u128));
+    let event1 = construct_modexp(&word_bytes(1), &word_bytes(2), &word_bytes(7));
 
-    let test_circuit = ModExpCircuit(vec![event1], Default::default());
-    let prover = MockProver::run(16, &test_circuit, vec![]).unwrap();
+    let test_circuit = ModExpCircuit::<Fr>::new(vec![event1], 0);
+    let prover = MockProver::run(15, &test_circuit, vec![]).unwrap();
     assert_eq!(prover.verify(), Ok(()));
 }
+
 #[test]
 fn test_modexp_circuit_02() {
-    let event1 = construct_modexp(Word::from(2u128), Word::from(2u128), Word::from(7u128));
-    let event2 = construct_modexp(Word::from(3u128), Word::from(21u128), Word::from(78u128));
+    let event1 = construct_modexp(&[2], &[2], &[7]);
+    let event2 = construct_modexp(&[3], &[21], &[78]);
 
-    let test_circuit = ModExpCircuit(vec![event1, event2], Default::default());
-    let prover = MockProver::run(17, &test_circuit, vec![]).unwrap();
+    let test_circuit = ModExpCircuit::<Fr>::new(vec![event1, event2], 0);
+    let prover = MockProver::run(12, &test_circuit, vec![]).unwrap();
     assert_eq!(prover.verify(), Ok(()));
 }
 
 // test all zeros case (exp == mod == base == 0)
 #[test]
 fn test_modexp_circuit_03() {
-    let event1 = construct_modexp(Word::from(0u128), Word::from(0u128), Word::from(0u128));
+    let event1 = construct_modexp(&word_bytes(0), &word_bytes(0), &word_bytes(0));
+
+    let test_circuit = ModExpCircuit::<Fr>::new(vec![event1], 0);
+    let prover = MockProver::run(12, &test_circuit, vec![]).unwrap();
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+// test empty operands, a modulus of 1 and a base larger than the modulus
+#[test]
+fn test_modexp_circuit_04() {
+    let event1 = construct_modexp(&[], &[], &[5]);
+    let event2 = construct_modexp(&[0xff, 0xff, 0xff], &[0x01, 0x01], &[1]);
+    let event3 = construct_modexp(&[0xff, 0xff, 0xff], &[0x00, 0x03], &[0x01, 0x00, 0x01]);
+
+    let test_circuit = ModExpCircuit::<Fr>::new(vec![event1, event2, event3], 0);
+    let prover = MockProver::run(12, &test_circuit, vec![]).unwrap();
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+// test operands of the size limit, as in an RSA verification
+#[test]
+fn test_modexp_circuit_05() {
+    let base = vec![0xab; MODEXP_SIZE_LIMIT];
+    let mut modulus = vec![0xff; MODEXP_SIZE_LIMIT];
+    modulus[MODEXP_SIZE_LIMIT - 1] = 0xfd;
+    let event1 = construct_modexp(&base, &[0x01, 0x00, 0x01], &modulus);
+
+    let test_circuit = ModExpCircuit::<Fr>::new(vec![event1], 0);
+    let prover = MockProver::run(15, &test_circuit, vec![]).unwrap();
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+// test input lens beyond the size limit
+#[test]
+fn test_modexp_circuit_invalid_lens() {
+    let mut input = vec![0u8; 96];
+    U256::from(MODEXP_SIZE_LIMIT + 1).to_big_endian(&mut input[..32]);
+    U256::from(1).to_big_endian(&mut input[32..64]);
+    U256::from(1).to_big_endian(&mut input[64..96]);
+    input.extend([0xff; 16]);
+    let event1 = construct_event(&input);
+    assert!(event1.modulus.is_empty());
 
-    let test_circuit = ModExpCircuit(vec![event1], Default::default());
-    let prover = MockProver::run(16, &test_circuit, vec![]).unwrap();
+    let test_circuit = ModExpCircuit::<Fr>::new(vec![event1], 0);
+    let prover = MockProver::run(12, &test_circuit, vec![]).unwrap();
     assert_eq!(prover.verify(), Ok(()));
 }
 
-fn construct_modexp(base: U256, exp: U256, modulus: U256) -> BigModExp {
-    let result = if modulus == Word::zero() {
-        Word::zero()
-    } else {
-        base.pow(exp).div_mod(modulus).1
-    };
+#[test]
+fn test_modexp_circuit_wrong_result() {
+    let mut event1 = construct_modexp(&[3], &[21], &[78]);
+    event1.result[0] += 1;
+
+    let test_circuit = ModExpCircuit::<Fr>::new(vec![event1], 0);
+    let prover = MockProver::run(12, &test_circuit, vec![]).unwrap();
+    assert!(prover.verify().is_err());
+}
+
+fn word_bytes(value: u64) -> Vec<u8> {
+    let mut bytes = vec![0u8; 32];
+    U256::from(value).to_big_endian(&mut bytes);
+    bytes
+}
+
+fn construct_modexp(base: &[u8], exp: &[u8], modulus: &[u8]) -> BigModExp {
+    let mut input = vec![0u8; 96];
+    for (i, len) in [base.len(), exp.len(), modulus.len()]
+        .into_iter()
+        .enumerate()
+    {
+        U256::from(len).to_big_endian(&mut input[32 * i..32 * (i + 1)]);
+    }
+    input.extend_from_slice(base);
+    input.extend_from_slice(exp);
+    input.extend_from_slice(modulus);
+    construct_event(&input)
+}
 
+fn construct_event(input: &[u8]) -> BigModExp {
+    let aux_data = ModExpAuxData::new(input, &[], &[], MODEXP_SIZE_LIMIT);
+    let [base, exponent, modulus] = aux_data.inputs;
     BigModExp {
+        input: input.to_vec(),
         base,
-        exponent: exp,
+        exponent,
         modulus,
-        result,
+        result: aux_data.output,
     }
 }
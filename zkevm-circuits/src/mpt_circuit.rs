@@ -180,7 +180,7 @@ impl Circuit<Fr> for MptCircuit<Fr> {
     fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
         let challenges = Challenges::construct(meta);
         let poseidon_table = PoseidonTable::construct(meta);
-        let mpt_table = MptTable::construct(meta);
+        let mpt_table = MptTable::construct(meta, challenges.phases());
 
         let config = {
             MptCircuitConfig::new(
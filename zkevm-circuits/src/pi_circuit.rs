@@ -23,12 +23,8 @@ use halo2_proofs::plonk::{Assigned, Expression, Fixed, Instance};
 
 use crate::{
     table::{BlockTable, LookupTable, TxTable},
-    util::{Challenges, SubCircuit, SubCircuitConfig},
+    util::{advice_column_in_phase2, Challenges, SubCircuit, SubCircuitConfig},
 };
-#[cfg(feature = "onephase")]
-use halo2_proofs::plonk::FirstPhase as SecondPhase;
-#[cfg(not(feature = "onephase"))]
-use halo2_proofs::plonk::SecondPhase;
 
 use crate::{
     evm_circuit::{util::constraint_builder::BaseConstraintBuilder, EvmCircuitExports},
@@ -341,6 +337,8 @@ impl BlockContext {
             history_hashes: vec![],
             parent_hash: Default::default(),
             state_root: Default::default(),
+            hash: Default::default(),
+            parent_beacon_block_root: None,
         }
     }
 }
@@ -444,19 +442,19 @@ impl<F: Field> SubCircuitConfig<F> for PiCircuitConfig<F> {
         // |       ...          |      ...        |   ...     |      ....        |      ...       |
 
         // hold the raw public input's value (e.g. gas_limit in block_context)
-        let rpi = meta.advice_column_in(SecondPhase);
+        let rpi = advice_column_in_phase2(meta, challenges.phases());
         // hold the raw public input's bytes
         let rpi_bytes = meta.advice_column();
         // hold the accumulated value of rpi_bytes (e.g. gas_limit in block_context)
-        let rpi_bytes_acc = meta.advice_column_in(SecondPhase);
+        let rpi_bytes_acc = advice_column_in_phase2(meta, challenges.phases());
         // hold the accumulated value of rlc(rpi_bytes, keccak_input)
-        let rpi_rlc_acc = meta.advice_column_in(SecondPhase);
+        let rpi_rlc_acc = advice_column_in_phase2(meta, challenges.phases());
         // hold the accumulated length of rpi_bytes for looking into keccak table
         let rpi_length_acc = meta.advice_column();
 
         // boolean column for indicating if the rpi_bytes is padding
         let is_rpi_padding = meta.advice_column();
-        let real_rpi = meta.advice_column_in(SecondPhase);
+        let real_rpi = advice_column_in_phase2(meta, challenges.phases());
 
         let pi = meta.instance_column();
 
@@ -87,10 +87,10 @@ impl<F: Field, const MAX_TXS: usize, const MAX_CALLDATA: usize, const MAX_INNER_
     }
 
     fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
-        let block_table = BlockTable::construct(meta);
-        let tx_table = TxTable::construct(meta);
-        let keccak_table = KeccakTable::construct(meta);
         let challenges = Challenges::construct(meta);
+        let block_table = BlockTable::construct(meta, challenges.phases());
+        let tx_table = TxTable::construct(meta, challenges.phases());
+        let keccak_table = KeccakTable::construct(meta, challenges.phases());
         let challenge_exprs = challenges.exprs(meta);
         (
             PiCircuitConfig::new(
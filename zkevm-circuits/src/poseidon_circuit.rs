@@ -10,6 +10,7 @@ use halo2_proofs::{
     plonk::{Circuit, ConstraintSystem, Error},
 };
 use hash_circuit::hash::{Hashable, PoseidonHashChip, PoseidonHashConfig, PoseidonHashTable};
+use mpt_circuits::serde::SMTTrace;
 
 /// re-wrapping for mpt circuit
 #[derive(Default, Clone, Debug)]
@@ -92,72 +93,7 @@ impl<F: Field> SubCircuit<F> for PoseidonCircuit<F> {
     }
 
     fn min_num_rows_block(block: &witness::Block) -> (usize, usize) {
-        let mut path_hash_counter: std::collections::HashMap<[u8; 32], usize> = Default::default();
-        let mut account_counter: std::collections::HashMap<[u8; 32], usize> = Default::default();
-        let mut storage_counter: std::collections::HashMap<[u8; 32], usize> = Default::default();
-        let mut key_counter: std::collections::HashMap<[u8; 32], usize> = Default::default();
-        let insert = |map: &mut std::collections::HashMap<[u8; 32], usize>, k| {
-            *map.entry(k).or_insert(0) += 1;
-        };
-        for smt_trace in &block.mpt_updates.smt_traces {
-            // for a smt trace there are mutiple sources for hashes:
-            // + account path, each layer (include the root) cost 1 hashes
-            insert(&mut path_hash_counter, smt_trace.account_path[0].root.0);
-            for node in &smt_trace.account_path[0].path {
-                insert(&mut path_hash_counter, node.value.0);
-            }
-            for node in &smt_trace.account_path[1].path {
-                insert(&mut path_hash_counter, node.value.0);
-            }
-
-            // + the hashes required for leaf is dynamic and depended
-            // on the type of mpt updates, here we suppose to count
-            // all of the 4 hashes once
-            if let Some(node) = smt_trace.account_path[0].leaf {
-                insert(&mut account_counter, node.value.0);
-            }
-            if let Some(node) = smt_trace.account_path[1].leaf {
-                insert(&mut account_counter, node.value.0);
-            }
-
-            // + and the address key
-            insert(&mut key_counter, smt_trace.account_key.0);
-
-            // + state path, like account path
-            if let Some(path) = &smt_trace.state_path[0] {
-                for node in &path.path {
-                    insert(&mut path_hash_counter, node.value.0);
-                }
-            }
-
-            if let Some(path) = &smt_trace.state_path[1] {
-                for node in &path.path {
-                    insert(&mut path_hash_counter, node.value.0);
-                }
-            }
-
-            // + state leaf
-            if let Some(node) = smt_trace.state_path[0].as_ref().and_then(|pt| pt.leaf) {
-                insert(&mut storage_counter, node.value.0);
-            }
-            if let Some(node) = smt_trace.state_path[1].as_ref().and_then(|pt| pt.leaf) {
-                insert(&mut storage_counter, node.value.0);
-            }
-
-            // + the storage key
-            if let Some(hash) = smt_trace.state_key {
-                insert(&mut key_counter, hash.0);
-            }
-        }
-        let sum_count = |h: &std::collections::HashMap<[u8; 32], usize>| h.values().sum::<usize>();
-        let prev_dedup_size = sum_count(&path_hash_counter)
-            + sum_count(&key_counter)
-            + sum_count(&account_counter) * 4
-            + sum_count(&storage_counter);
-        let after_dedup_size = path_hash_counter.len()
-            + key_counter.len()
-            + account_counter.len() * 4
-            + storage_counter.len();
+        let (prev_dedup_size, after_dedup_size) = count_mpt_hashes(&block.mpt_updates.smt_traces);
         log::debug!("poseidon circuit row num: dedup mpt from {prev_dedup_size} to {after_dedup_size}, mpt update len {}, smt trace len {}",
         block.mpt_updates.len(), block.mpt_updates.smt_traces.len());
         let mpt_row_num = after_dedup_size * F::hash_block_size();
@@ -262,3 +198,75 @@ fn get_storage_poseidon_witness<F: Field>(
     .map(|(inp, domain, hash)| (inp.map(F::from), domain.into(), Some(F::from(hash))))
     .collect()
 }
+
+/// Count the poseidon hashes proven for the MPT updates of `smt_traces`, before and after
+/// deduplication.
+pub(crate) fn count_mpt_hashes(smt_traces: &[SMTTrace]) -> (usize, usize) {
+    let mut path_hash_counter: std::collections::HashMap<[u8; 32], usize> = Default::default();
+    let mut account_counter: std::collections::HashMap<[u8; 32], usize> = Default::default();
+    let mut storage_counter: std::collections::HashMap<[u8; 32], usize> = Default::default();
+    let mut key_counter: std::collections::HashMap<[u8; 32], usize> = Default::default();
+    let insert = |map: &mut std::collections::HashMap<[u8; 32], usize>, k| {
+        *map.entry(k).or_insert(0) += 1;
+    };
+    for smt_trace in smt_traces {
+        // for a smt trace there are mutiple sources for hashes:
+        // + account path, each layer (include the root) cost 1 hashes
+        insert(&mut path_hash_counter, smt_trace.account_path[0].root.0);
+        for node in &smt_trace.account_path[0].path {
+            insert(&mut path_hash_counter, node.value.0);
+        }
+        for node in &smt_trace.account_path[1].path {
+            insert(&mut path_hash_counter, node.value.0);
+        }
+
+        // + the hashes required for leaf is dynamic and depended
+        // on the type of mpt updates, here we suppose to count
+        // all of the 4 hashes once
+        if let Some(node) = smt_trace.account_path[0].leaf {
+            insert(&mut account_counter, node.value.0);
+        }
+        if let Some(node) = smt_trace.account_path[1].leaf {
+            insert(&mut account_counter, node.value.0);
+        }
+
+        // + and the address key
+        insert(&mut key_counter, smt_trace.account_key.0);
+
+        // + state path, like account path
+        if let Some(path) = &smt_trace.state_path[0] {
+            for node in &path.path {
+                insert(&mut path_hash_counter, node.value.0);
+            }
+        }
+
+        if let Some(path) = &smt_trace.state_path[1] {
+            for node in &path.path {
+                insert(&mut path_hash_counter, node.value.0);
+            }
+        }
+
+        // + state leaf
+        if let Some(node) = smt_trace.state_path[0].as_ref().and_then(|pt| pt.leaf) {
+            insert(&mut storage_counter, node.value.0);
+        }
+        if let Some(node) = smt_trace.state_path[1].as_ref().and_then(|pt| pt.leaf) {
+            insert(&mut storage_counter, node.value.0);
+        }
+
+        // + the storage key
+        if let Some(hash) = smt_trace.state_key {
+            insert(&mut key_counter, hash.0);
+        }
+    }
+    let sum_count = |h: &std::collections::HashMap<[u8; 32], usize>| h.values().sum::<usize>();
+    let prev_dedup_size = sum_count(&path_hash_counter)
+        + sum_count(&key_counter)
+        + sum_count(&account_counter) * 4
+        + sum_count(&storage_counter);
+    let after_dedup_size = path_hash_counter.len()
+        + key_counter.len()
+        + account_counter.len() * 4
+        + storage_counter.len();
+    (prev_dedup_size, after_dedup_size)
+}
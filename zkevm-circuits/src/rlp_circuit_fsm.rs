@@ -9,8 +9,9 @@ use crate::{
     evm_circuit::util::constraint_builder::{BaseConstraintBuilder, ConstrainBuilderCommon},
     table::{LookupTable, RlpFsmRlpTable, U8Table},
     util::{
+        advice_column_in_phase2,
         is_zero::{IsZeroChip, IsZeroConfig},
-        Challenges, Field, SubCircuit, SubCircuitConfig,
+        ChallengePhases, Challenges, Field, SubCircuit, SubCircuitConfig,
     },
     witness::{
         rlp_fsm::StackOp,
@@ -29,9 +30,7 @@ use gadgets::{
 };
 use halo2_proofs::{
     circuit::{Layouter, Region, Value},
-    plonk::{
-        Advice, Any, Column, ConstraintSystem, Error, Expression, Fixed, SecondPhase, VirtualCells,
-    },
+    plonk::{Advice, Any, Column, ConstraintSystem, Error, Expression, Fixed, VirtualCells},
     poly::Rotation,
 };
 use itertools::Itertools;
@@ -85,14 +84,14 @@ impl<F: Field> LookupTable<F> for RlpFsmDataTable {
 
 impl RlpFsmDataTable {
     /// Construct the data table.
-    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
+    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>, phases: ChallengePhases) -> Self {
         Self {
             tx_id: meta.advice_column(),
             format: meta.advice_column(),
             byte_idx: meta.advice_column(),
             byte_rev_idx: meta.advice_column(),
             byte_value: meta.advice_column(),
-            bytes_rlc: meta.advice_column_in(SecondPhase),
+            bytes_rlc: advice_column_in_phase2(meta, phases),
             gas_cost_acc: meta.advice_column(),
         }
     }
@@ -212,9 +211,9 @@ pub struct RlpDecodingTable {
 
 impl RlpDecodingTable {
     /// Construct the decoding table.
-    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
+    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>, phases: ChallengePhases) -> Self {
         Self {
-            id: meta.advice_column_in(SecondPhase),
+            id: advice_column_in_phase2(meta, phases),
             tx_id: meta.advice_column(),
             format: meta.advice_column(),
             depth: meta.advice_column(),
@@ -453,8 +452,8 @@ impl<F: Field> RlpCircuitConfig<F> {
             meta.advice_column(),
         );
 
-        let tag_value_acc = meta.advice_column_in(SecondPhase);
-        let bytes_rlc = meta.advice_column_in(SecondPhase);
+        let tag_value_acc = advice_column_in_phase2(meta, challenges.phases());
+        let bytes_rlc = advice_column_in_phase2(meta, challenges.phases());
 
         let state_bits = BinaryNumberChip::configure(meta, q_enabled, Some(state.into()));
         let tag_bits = BinaryNumberChip::configure(meta, q_enabled, Some(tag.into()));
@@ -3233,9 +3232,9 @@ impl<F: Field> SubCircuitConfig<F> for RlpCircuitConfig<F> {
     type ConfigArgs = RlpCircuitConfigArgs<F>;
 
     fn new(meta: &mut ConstraintSystem<F>, args: Self::ConfigArgs) -> Self {
-        let data_table = RlpFsmDataTable::construct(meta);
+        let data_table = RlpFsmDataTable::construct(meta, args.challenges.phases());
         let rom_table = RlpFsmRomTable::construct(meta);
-        let decoding_table = RlpDecodingTable::construct(meta);
+        let decoding_table = RlpDecodingTable::construct(meta, args.challenges.phases());
 
         Self::configure(
             meta,
@@ -7,10 +7,10 @@ use rand::rngs::OsRng;
 
 use super::{circuit::*, BLOCK_SIZE};
 
-use crate::util::Challenges;
+use crate::util::{advice_column_in_phase2, Challenges};
 use halo2_proofs::{
     halo2curves::bn256::{Bn256, Fr},
-    plonk::{Advice, Any, Column, Fixed, SecondPhase},
+    plonk::{Advice, Any, Column, Fixed},
     poly::{
         commitment::ParamsProver,
         kzg::{
@@ -58,16 +58,16 @@ impl Circuit<Fr> for MyCircuit {
             }
         }
 
+        let challenges = Challenges::construct(meta);
         let dev_table = DevTable {
             s_enable: meta.fixed_column(),
             input_len: meta.advice_column(),
-            input_rlc: meta.advice_column_in(SecondPhase),
-            hashes_rlc: meta.advice_column_in(SecondPhase),
+            input_rlc: advice_column_in_phase2(meta, challenges.phases()),
+            hashes_rlc: advice_column_in_phase2(meta, challenges.phases()),
             is_effect: meta.advice_column(),
         };
         meta.enable_constant(dev_table.s_enable);
 
-        let challenges = Challenges::construct(meta);
         let chng = challenges.exprs(meta).keccak_input();
         (CircuitConfig::configure(meta, dev_table, chng), challenges)
     }
@@ -1,4 +1,4 @@
-//! Circuit to verify multiple ECDSA secp256k1 signatures.
+//! Circuit to verify multiple ECDSA secp256k1 and secp256r1 signatures.
 //
 // This module uses halo2-ecc's ecdsa chip
 //  - to prove the correctness of secp signatures
@@ -20,13 +20,18 @@ use crate::{
         EvmCircuit,
     },
     keccak_circuit::KeccakCircuit,
-    sig_circuit::ecdsa::ecdsa_verify_no_pubkey_check,
-    table::{KeccakTable, SigTable},
-    util::{Challenges, Expr, Field, SubCircuit, SubCircuitConfig},
+    sig_circuit::ecdsa::{
+        ecdsa_verify_no_pubkey_check, ecdsa_verify_no_pubkey_check_any_a, is_on_curve_any_a,
+    },
+    table::{KeccakTable, SigCurve, SigTable},
+    util::{
+        advice_column_in_phase2, ChallengePhases, Challenges, Expr, Field, SubCircuit,
+        SubCircuitConfig,
+    },
 };
 use eth_types::{
     self,
-    sign_types::{pk_bytes_le, pk_bytes_swap_endianness, SignData},
+    sign_types::{pk_bytes_le, pk_bytes_swap_endianness, P256SignData, SignData},
 };
 use halo2_base::{
     gates::{range::RangeConfig, GateInstructions, RangeInstructions},
@@ -35,7 +40,7 @@ use halo2_base::{
 };
 use halo2_ecc::{
     bigint::CRTInteger,
-    ecc::EccChip,
+    ecc::{EcPoint, EccChip},
     fields::{
         fp::{FpConfig, FpStrategy},
         FieldChip,
@@ -49,7 +54,10 @@ pub(crate) use utils::*;
 
 use halo2_proofs::{
     circuit::{Layouter, Value},
-    halo2curves::secp256k1::{Fp, Fq, Secp256k1Affine},
+    halo2curves::{
+        secp256k1::{Fp, Fq, Secp256k1Affine},
+        secp256r1::{Fp as P256Fp, Fq as P256Fq, Secp256r1Affine},
+    },
     plonk::{Advice, Column, ConstraintSystem, Error, Expression, Selector},
     poly::Rotation,
 };
@@ -57,6 +65,7 @@ use halo2_proofs::{
 use ethers_core::utils::keccak256;
 use itertools::Itertools;
 use log::error;
+use num_bigint::BigUint;
 use std::{iter, marker::PhantomData};
 
 /// Circuit configuration arguments
@@ -82,6 +91,8 @@ pub struct SigCircuitConfig<F: Field> {
     keccak_table: KeccakTable,
     /// The exposed table to be used by tx circuit and ecrecover
     sig_table: SigTable,
+    /// Whether the ECDSA chip has a second phase
+    challenge_phases: ChallengePhases,
 }
 
 impl<F: Field> SubCircuitConfig<F> for SigCircuitConfig<F> {
@@ -93,29 +104,31 @@ impl<F: Field> SubCircuitConfig<F> for SigCircuitConfig<F> {
         Self::ConfigArgs {
             keccak_table,
             sig_table,
-            challenges: _,
+            challenges,
         }: Self::ConfigArgs,
     ) -> Self {
-        #[cfg(feature = "onephase")]
-        let num_advice = [calc_required_advices(MAX_NUM_SIG)];
-        #[cfg(not(feature = "onephase"))]
-        // need an additional phase 2 column/basic gate to hold the witnesses during RLC
-        // computations
-        let num_advice = [calc_required_advices(MAX_NUM_SIG), 1];
+        let challenge_phases = challenges.phases();
+        let num_advice = match challenge_phases {
+            ChallengePhases::One => {
+                log::info!("configuring ECDSA chip with single phase");
+                vec![calc_required_advices(MAX_NUM_SIG)]
+            }
+            ChallengePhases::Multi => {
+                log::info!("configuring ECDSA chip with multiple phases");
+                // need an additional phase 2 column/basic gate to hold the witnesses during RLC
+                // computations
+                vec![calc_required_advices(MAX_NUM_SIG), 1]
+            }
+        };
 
         let num_lookup_advice = [calc_required_lookup_advices(MAX_NUM_SIG)];
 
-        #[cfg(feature = "onephase")]
-        log::info!("configuring ECDSA chip with single phase");
-        #[cfg(not(feature = "onephase"))]
-        log::info!("configuring ECDSA chip with multiple phases");
-
         // halo2-ecc's ECDSA config
         //
         // get the following parameters by running
         // `cargo test --release --package zkevm-circuits --lib sig_circuit::test::sign_verify --
         // --nocapture`
-        // - num_advice: 56
+        // - num_advice: 57
         // - num_lookup_advice: 8
         // - num_fixed: 1
         // - lookup_bits: 19
@@ -138,10 +151,7 @@ impl<F: Field> SubCircuitConfig<F> for SigCircuitConfig<F> {
         );
 
         // we need one phase 2 column to store RLC results
-        #[cfg(feature = "onephase")]
-        let rlc_column = meta.advice_column_in(halo2_proofs::plonk::FirstPhase);
-        #[cfg(not(feature = "onephase"))]
-        let rlc_column = meta.advice_column_in(halo2_proofs::plonk::SecondPhase);
+        let rlc_column = advice_column_in_phase2(meta, challenge_phases);
 
         meta.enable_equality(rlc_column);
 
@@ -151,6 +161,8 @@ impl<F: Field> SubCircuitConfig<F> for SigCircuitConfig<F> {
         meta.enable_equality(sig_table.sig_v);
         meta.enable_equality(sig_table.is_valid);
         meta.enable_equality(sig_table.msg_hash_rlc);
+        meta.enable_equality(sig_table.pk_x_rlc);
+        meta.enable_equality(sig_table.pk_y_rlc);
 
         // Ref. spec SignVerifyChip 1. Verify that keccak(pub_key_bytes) = pub_key_hash
         // by keccak table lookup, where pub_key_bytes is built from the pub_key
@@ -195,6 +207,7 @@ impl<F: Field> SubCircuitConfig<F> for SigCircuitConfig<F> {
             q_keccak,
             keccak_table,
             sig_table,
+            challenge_phases,
         }
     }
 }
@@ -213,6 +226,10 @@ pub struct SigCircuit<F: Field> {
     pub max_verif: usize,
     /// Without padding
     pub signatures: Vec<SignData>,
+    /// Max number of secp256r1 verifications
+    pub max_p256_verif: usize,
+    /// secp256r1 signatures, without padding
+    pub p256_signatures: Vec<P256SignData>,
     /// Marker
     pub _marker: PhantomData<F>,
 }
@@ -221,11 +238,14 @@ impl<F: Field> SubCircuit<F> for SigCircuit<F> {
     type Config = SigCircuitConfig<F>;
 
     fn new_from_block(block: &crate::witness::Block) -> Self {
-        assert!(block.circuits_params.max_txs <= MAX_NUM_SIG);
+        let max_verif = MAX_NUM_SIG - P256_SIG_COST * MAX_NUM_P256_SIG;
+        assert!(block.circuits_params.max_txs <= max_verif);
 
         SigCircuit {
-            max_verif: MAX_NUM_SIG,
+            max_verif,
             signatures: block.get_sign_data(true),
+            max_p256_verif: MAX_NUM_P256_SIG,
+            p256_signatures: block.get_p256_sign_data(),
             _marker: Default::default(),
         }
     }
@@ -250,7 +270,13 @@ impl<F: Field> SubCircuit<F> for SigCircuit<F> {
         layouter: &mut impl Layouter<F>,
     ) -> Result<(), Error> {
         config.ecdsa_config.range.load_lookup_table(layouter)?;
-        self.assign(config, layouter, &self.signatures, challenges)?;
+        self.assign(
+            config,
+            layouter,
+            &self.signatures,
+            &self.p256_signatures,
+            challenges,
+        )?;
         Ok(())
     }
 
@@ -270,15 +296,17 @@ impl<F: Field> SubCircuit<F> for SigCircuit<F> {
             .count()
             + block.precompile_events.get_ecrecover_events().len();
         // Reserve one ecdsa verification for padding tx such that the bad case in which some tx
-        // calls MAX_NUM_SIG - 1 ecrecover precompile won't happen. If that case happens, the sig
+        // calls max_num_verif ecrecover precompile won't happen. If that case happens, the sig
         // circuit won't have more space for the padding tx's ECDSA verification. Then the
         // prover won't be able to produce any valid proof.
-        let max_num_verif = MAX_NUM_SIG - 1;
+        let max_num_verif = MAX_NUM_SIG - P256_SIG_COST * MAX_NUM_P256_SIG - 1;
+        let p256_verif_count = block.precompile_events.get_p256_verify_events().len();
 
         // Instead of showing actual minimum row usage,
         // halo2-lib based circuits use min_row_num to represent a percentage of total-used capacity
         // This functionality allows l2geth to decide if additional ops can be added.
-        let min_row_num = (row_num / max_num_verif) * ecdsa_verif_count;
+        let min_row_num = ((row_num / max_num_verif) * ecdsa_verif_count)
+            .max((row_num / MAX_NUM_P256_SIG) * p256_verif_count);
 
         (min_row_num, row_num)
     }
@@ -290,6 +318,8 @@ impl<F: Field> SigCircuit<F> {
         Self {
             max_verif,
             signatures: Vec::new(),
+            max_p256_verif: 0,
+            p256_signatures: Vec::new(),
             _marker: PhantomData,
         }
     }
@@ -446,6 +476,73 @@ impl<F: Field> SigCircuit<F> {
         })
     }
 
+    /// Verifies a secp256r1 signature against a public key that is not necessarily on the curve,
+    /// as done by the P256VERIFY precompile.
+    ///
+    /// Returns the cells for
+    /// - public key
+    /// - message hash
+    /// - a boolean whether the signature is correct or not
+    fn assign_p256_ecdsa(
+        &self,
+        ctx: &mut Context<F>,
+        ecdsa_chip: &FpChip<F>,
+        sign_data: &P256SignData,
+    ) -> Result<AssignedP256ECDSA<F>, Error> {
+        let P256SignData {
+            signature: (sig_r, sig_s),
+            pk,
+            msg_hash,
+        } = sign_data;
+
+        // build secp256r1 chips from the range chip of the Fp chip
+        let p256_fp_chip =
+            P256FpChip::<F>::construct(ecdsa_chip.range.clone(), 88, 3, modulus::<P256Fp>());
+        let p256_fq_chip =
+            P256FqChip::<F>::construct(ecdsa_chip.range.clone(), 88, 3, modulus::<P256Fq>());
+        let ecc_chip = EccChip::<F, P256FpChip<F>>::construct(p256_fp_chip.clone());
+
+        // a public key that is not on the curve is replaced by (0, 0), which is never valid
+        let pk_assigned = ecc_chip.load_private(ctx, (Value::known(pk.x), Value::known(pk.y)));
+        let pk_is_valid =
+            is_on_curve_any_a::<F, P256Fp, Secp256r1Affine>(&p256_fp_chip, ctx, &pk_assigned);
+        let zero = p256_fp_chip.load_constant(ctx, BigUint::default());
+        let pk_or_zero = ecc_chip.select(
+            ctx,
+            &pk_assigned,
+            &EcPoint::construct(zero.clone(), zero),
+            &pk_is_valid,
+        );
+
+        let integer_r =
+            p256_fq_chip.load_private(ctx, P256FqChip::<F>::fe_to_witness(&Value::known(*sig_r)));
+        let integer_s =
+            p256_fq_chip.load_private(ctx, P256FqChip::<F>::fe_to_witness(&Value::known(*sig_s)));
+        let msg_hash = p256_fq_chip.load_private(
+            ctx,
+            P256FqChip::<F>::fe_to_witness(&Value::known(*msg_hash)),
+        );
+
+        // unlike for ecrecover, the verification result is exposed as is in the sig table
+        let sig_is_valid = ecdsa_verify_no_pubkey_check_any_a::<F, P256Fp, P256Fq, Secp256r1Affine>(
+            &p256_fp_chip,
+            ctx,
+            &pk_or_zero,
+            &integer_r,
+            &integer_s,
+            &msg_hash,
+            4,
+        );
+
+        Ok(AssignedP256ECDSA {
+            pk: pk_assigned,
+            msg_hash,
+            integer_r,
+            integer_s,
+            sig_is_valid,
+        })
+    }
+
     fn enable_keccak_lookup(
         &self,
         config: &SigCircuitConfig<F>,
@@ -644,6 +741,60 @@ impl<F: Field> SigCircuit<F> {
         })
     }
 
+    /// Input the secp256r1 signature data,
+    /// Output the cells for byte decomposition of the key, message and signature
+    fn p256_sign_data_decomposition(
+        &self,
+        ctx: &mut Context<F>,
+        ecdsa_chip: &FpChip<F>,
+        sign_data: &P256SignData,
+        assigned_data: &AssignedP256ECDSA<F>,
+    ) -> Result<P256SignDataDecomposed<F>, Error> {
+        let powers_of_256_cells =
+            iter::successors(Some(F::one()), |coeff| Some(F::from(256) * coeff))
+                .take(32)
+                .map(|x| QuantumCell::Constant(x))
+                .collect_vec();
+
+        let assert_crt = |ctx: &mut Context<F>,
+                          bytes: [u8; 32],
+                          crt_integer: &CRTInteger<F>|
+         -> Result<_, Error> {
+            let byte_cells: Vec<QuantumCell<F>> = bytes
+                .iter()
+                .map(|&x| QuantumCell::Witness(Value::known(F::from(x as u64))))
+                .collect_vec();
+            self.assert_crt_int_byte_repr(
+                ctx,
+                &ecdsa_chip.range,
+                crt_integer,
+                &byte_cells,
+                &powers_of_256_cells,
+            )?;
+            Ok(byte_cells)
+        };
+
+        Ok(P256SignDataDecomposed {
+            msg_hash_cells: assert_crt(
+                ctx,
+                sign_data.msg_hash.to_bytes(),
+                &assigned_data.msg_hash,
+            )?,
+            r_cells: assert_crt(
+                ctx,
+                sign_data.signature.0.to_bytes(),
+                &assigned_data.integer_r,
+            )?,
+            s_cells: assert_crt(
+                ctx,
+                sign_data.signature.1.to_bytes(),
+                &assigned_data.integer_s,
+            )?,
+            pk_x_cells: assert_crt(ctx, sign_data.pk.x.to_bytes(), &assigned_data.pk.x)?,
+            pk_y_cells: assert_crt(ctx, sign_data.pk.y.to_bytes(), &assigned_data.pk.y)?,
+        })
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn assign_sig_verify(
         &self,
@@ -739,12 +890,45 @@ impl<F: Field> SigCircuit<F> {
         Ok((to_be_keccak_checked, assigned_sig_verif))
     }
 
+    /// Compute the RLCs of the decomposed secp256r1 signature data.
+    fn assign_p256_sig_verify(
+        &self,
+        ctx: &mut Context<F>,
+        rlc_chip: &RangeConfig<F>,
+        sign_data_decomposed: &P256SignDataDecomposed<F>,
+        challenges: &Challenges<Value<F>>,
+        assigned_ecdsa: &AssignedP256ECDSA<F>,
+    ) -> AssignedP256SignatureVerify<F> {
+        let evm_challenge_powers = iter::successors(Some(Value::known(F::one())), |coeff| {
+            Some(challenges.evm_word() * coeff)
+        })
+        .take(32)
+        .map(|x| QuantumCell::Witness(x))
+        .collect_vec();
+
+        let mut word_rlc = |cells: &[QuantumCell<F>]| {
+            rlc_chip
+                .gate
+                .inner_product(ctx, cells.to_vec(), evm_challenge_powers.clone())
+        };
+
+        AssignedP256SignatureVerify {
+            msg_hash_rlc: word_rlc(&sign_data_decomposed.msg_hash_cells),
+            r_rlc: word_rlc(&sign_data_decomposed.r_cells),
+            s_rlc: word_rlc(&sign_data_decomposed.s_cells),
+            pk_x_rlc: word_rlc(&sign_data_decomposed.pk_x_cells),
+            pk_y_rlc: word_rlc(&sign_data_decomposed.pk_y_cells),
+            sig_is_valid: assigned_ecdsa.sig_is_valid,
+        }
+    }
+
     /// Assign witness data to the sig circuit.
     pub(crate) fn assign(
         &self,
         config: &SigCircuitConfig<F>,
         layouter: &mut impl Layouter<F>,
         signatures: &[SignData],
+        p256_signatures: &[P256SignData],
         challenges: &Challenges<Value<F>>,
     ) -> Result<Vec<AssignedSignatureVerify<F>>, Error> {
         if signatures.len() > self.max_verif {
@@ -755,15 +939,25 @@ impl<F: Field> SigCircuit<F> {
             );
             return Err(Error::Synthesis);
         }
+        if p256_signatures.len() > self.max_p256_verif {
+            error!(
+                "p256_signatures.len() = {} > max_p256_verif = {}",
+                p256_signatures.len(),
+                self.max_p256_verif
+            );
+            return Err(Error::Synthesis);
+        }
+        // the advice columns are configured for MAX_NUM_SIG secp256k1 verifications
+        assert!(self.max_verif + P256_SIG_COST * self.max_p256_verif <= MAX_NUM_SIG);
         let mut first_pass = SKIP_FIRST_PASS;
         let ecdsa_chip = &config.ecdsa_config;
 
-        let assigned_sig_verifs = layouter.assign_region(
+        let (assigned_sig_verifs, assigned_p256_sig_verifs, zero) = layouter.assign_region(
             || "ecdsa chip verification",
             |region| {
                 if first_pass {
                     first_pass = false;
-                    return Ok(vec![]);
+                    return Ok((vec![], vec![], None));
                 }
 
                 let mut ctx = ecdsa_chip.new_context(region);
@@ -777,6 +971,12 @@ impl<F: Field> SigCircuit<F> {
                     .take(self.max_verif)
                     .map(|sign_data| self.assign_ecdsa(&mut ctx, ecdsa_chip, sign_data))
                     .collect::<Result<Vec<AssignedECDSA<F, FpChip<F>>>, Error>>()?;
+                let assigned_p256_ecdsas = p256_signatures
+                    .iter()
+                    .chain(std::iter::repeat(&P256SignData::default()))
+                    .take(self.max_p256_verif)
+                    .map(|sign_data| self.assign_p256_ecdsa(&mut ctx, ecdsa_chip, sign_data))
+                    .collect::<Result<Vec<AssignedP256ECDSA<F>>, Error>>()?;
 
                 // ================================================
                 // step 2: decompose the keys and messages
@@ -795,12 +995,25 @@ impl<F: Field> SigCircuit<F> {
                         )
                     })
                     .collect::<Result<Vec<SignDataDecomposed<F>>, Error>>()?;
+                let p256_sign_data_decomposed = p256_signatures
+                    .iter()
+                    .chain(std::iter::repeat(&P256SignData::default()))
+                    .take(self.max_p256_verif)
+                    .zip_eq(assigned_p256_ecdsas.iter())
+                    .map(|(sign_data, assigned_ecdsa)| {
+                        self.p256_sign_data_decomposition(
+                            &mut ctx,
+                            ecdsa_chip,
+                            sign_data,
+                            assigned_ecdsa,
+                        )
+                    })
+                    .collect::<Result<Vec<P256SignDataDecomposed<F>>, Error>>()?;
 
                 // IMPORTANT: Move to Phase2 before RLC
                 log::info!("before proceeding to the next phase");
 
-                #[cfg(not(feature = "onephase"))]
-                {
+                if config.challenge_phases == ChallengePhases::Multi {
                     // finalize the current lookup table before moving to next phase
                     ecdsa_chip.finalize(&mut ctx);
                     ctx.print_stats(&["ECDSA context"]);
@@ -835,6 +1048,21 @@ impl<F: Field> SigCircuit<F> {
                     >>()?
                     .into_iter()
                     .unzip();
+                let assigned_p256_sig_values = assigned_p256_ecdsas
+                    .iter()
+                    .zip_eq(p256_sign_data_decomposed.iter())
+                    .map(|(assigned_ecdsa, sign_data_decomp)| {
+                        self.assign_p256_sig_verify(
+                            &mut ctx,
+                            &ecdsa_chip.range,
+                            sign_data_decomp,
+                            challenges,
+                            assigned_ecdsa,
+                        )
+                    })
+                    .collect_vec();
+                // used to fill the sig table columns that don't apply to a curve
+                let zero = ecdsa_chip.range.gate.load_zero(&mut ctx);
 
                 // ================================================
                 // step 4: deferred keccak checks
@@ -861,7 +1089,7 @@ impl<F: Field> SigCircuit<F> {
                 log::info!("total number of lookup cells: {}", lookup_cells);
 
                 ctx.print_stats(&["ECDSA context"]);
-                Ok(assigned_sig_values)
+                Ok((assigned_sig_values, assigned_p256_sig_values, Some(zero)))
             },
         )?;
 
@@ -871,12 +1099,9 @@ impl<F: Field> SigCircuit<F> {
             |mut region| {
                 // step 5: export as a lookup table
                 for (idx, assigned_sig_verif) in assigned_sig_verifs.iter().enumerate() {
-                    region.assign_fixed(
-                        || "assign sig_table selector",
-                        config.sig_table.q_enable,
-                        idx,
-                        || Value::known(F::one()),
-                    )?;
+                    config
+                        .sig_table
+                        .assign_fixed_row(&mut region, idx, SigCurve::Secp256k1)?;
 
                     assigned_sig_verif
                         .v
@@ -911,6 +1136,38 @@ impl<F: Field> SigCircuit<F> {
                         config.sig_table.msg_hash_rlc,
                         idx,
                     );
+
+                    for column in [config.sig_table.pk_x_rlc, config.sig_table.pk_y_rlc] {
+                        zero.unwrap().copy_advice(&mut region, column, idx);
+                    }
+                }
+
+                // the secp256r1 rows follow the secp256k1 ones
+                for (idx, assigned_sig_verif) in assigned_p256_sig_verifs
+                    .iter()
+                    .enumerate()
+                    .map(|(i, verif)| (assigned_sig_verifs.len() + i, verif))
+                {
+                    config
+                        .sig_table
+                        .assign_fixed_row(&mut region, idx, SigCurve::Secp256r1)?;
+
+                    for (assigned, column) in [
+                        (
+                            &assigned_sig_verif.msg_hash_rlc,
+                            config.sig_table.msg_hash_rlc,
+                        ),
+                        (&assigned_sig_verif.r_rlc, config.sig_table.sig_r_rlc),
+                        (&assigned_sig_verif.s_rlc, config.sig_table.sig_s_rlc),
+                        (&assigned_sig_verif.sig_is_valid, config.sig_table.is_valid),
+                        (&assigned_sig_verif.pk_x_rlc, config.sig_table.pk_x_rlc),
+                        (&assigned_sig_verif.pk_y_rlc, config.sig_table.pk_y_rlc),
+                    ] {
+                        assigned.copy_advice(&mut region, column, idx);
+                    }
+                    for column in [config.sig_table.sig_v, config.sig_table.recovered_addr] {
+                        zero.unwrap().copy_advice(&mut region, column, idx);
+                    }
                 }
                 Ok(())
             },
@@ -1,9 +1,6 @@
 use super::*;
 
-//#[cfg(not(feature = "onephase"))]
 use crate::{util::Challenges, witness::keccak::keccak_inputs_sign_verify};
-//#[cfg(feature = "onephase")]
-//use crate::util::MockChallenges as Challenges;
 
 use halo2_proofs::{circuit::SimpleFloorPlanner, plonk::Circuit};
 
@@ -16,9 +13,9 @@ pub struct SigCircuitTesterConfig<F: Field> {
 
 impl<F: Field> SigCircuitTesterConfig<F> {
     pub(crate) fn new(meta: &mut ConstraintSystem<F>) -> Self {
-        let keccak_table = KeccakTable::construct(meta);
-        let sig_table = SigTable::construct(meta);
         let challenges = Challenges::construct(meta);
+        let keccak_table = KeccakTable::construct(meta, challenges.phases());
+        let sig_table = SigTable::construct(meta, challenges.phases());
         let challenges_expr = challenges.exprs(meta);
         let sign_verify = SigCircuitConfig::new(
             meta,
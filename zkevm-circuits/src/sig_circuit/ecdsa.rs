@@ -3,15 +3,50 @@
 
 use halo2_base::{
     gates::{GateInstructions, RangeInstructions},
-    utils::{fe_to_biguint, modulus, CurveAffineExt},
+    utils::{biguint_to_fe, fe_to_biguint, modulus, CurveAffineExt},
     AssignedValue, Context,
-    QuantumCell::{self, Existing},
+    QuantumCell::{self, Constant, Existing},
 };
 use halo2_ecc::{
     bigint::{big_less_than, CRTInteger},
-    ecc::{fixed_base, scalar_multiply, EcPoint, EccChip},
+    ecc::{fixed_base, multi_scalar_multiply, EcPoint, EccChip},
     fields::{fp::FpConfig, FieldChip, PrimeField, Selectable},
 };
+use halo2_proofs::circuit::Value;
+use num::Integer;
+use num_bigint::{BigInt, BigUint};
+use std::sync::LazyLock;
+
+type FpPoint<F> = EcPoint<F, CRTInteger<F>>;
+
+/// Constants of the GLV endomorphism of secp256k1, phi(x, y) = (beta * x, y) = lambda * (x, y),
+/// and the short basis (a1, b1), (a2, b2) of the lattice of the (k1, k2) with
+/// k1 + lambda * k2 == 0 (mod n), from libsecp256k1.
+struct GlvParams {
+    n: BigInt,
+    lambda: BigUint,
+    beta: BigUint,
+    a1: BigInt,
+    minus_b1: BigInt,
+    a2: BigInt,
+    b2: BigInt,
+}
+
+static SECP256K1_GLV: LazyLock<GlvParams> = LazyLock::new(|| {
+    let parse = |hex: &str| BigUint::parse_bytes(hex.as_bytes(), 16).unwrap();
+    GlvParams {
+        n: parse("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141").into(),
+        lambda: parse("5363ad4cc05c30e0a5261c028812645a122e22ea20816678df02967c1b23bd72"),
+        beta: parse("7ae96a2b657c07106e64479eac3434e99cf0497512f58995c1396c28719501ee"),
+        a1: parse("3086d221a7d46bcde86c90e49284eb15").into(),
+        minus_b1: parse("e4437ed6010e88286f547fa90abfe4c3").into(),
+        a2: parse("114ca50f7a8e2f3f657c1108d9d44cfd8").into(),
+        b2: parse("3086d221a7d46bcde86c90e49284eb15").into(),
+    }
+});
+
+/// Number of bits of the absolute values of the halves of a GLV decomposed scalar.
+const GLV_HALF_BITS: usize = 128;
 
 // CF is the coordinate field of GA
 // SF is the scalar field of GA
@@ -32,6 +67,99 @@ pub(crate) fn ecdsa_verify_no_pubkey_check<F: PrimeField, CF: PrimeField, SF: Pr
     var_window_bits: usize,
     fixed_window_bits: usize,
 ) -> (AssignedValue<F>, AssignedValue<F>, CRTInteger<F>)
+where
+    GA: CurveAffineExt<Base = CF, ScalarExt = SF>,
+{
+    ecdsa_verify_with_var_base_mul::<F, CF, SF, GA>(
+        base_chip,
+        ctx,
+        pubkey,
+        r,
+        s,
+        msghash,
+        fixed_window_bits,
+        |ctx, pubkey, is_pubkey_zero, scalar| {
+            let ecc_chip = EccChip::<F, FpConfig<F, CF>>::construct(base_chip.clone());
+            let pubkey_prime = ecc_chip.load_random_point::<GA>(ctx);
+            let pubkey_prime = ecc_chip.select(ctx, &pubkey_prime, pubkey, is_pubkey_zero);
+            glv_scalar_multiply::<F, CF, SF, GA>(
+                base_chip,
+                ctx,
+                &pubkey_prime,
+                scalar,
+                var_window_bits,
+            )
+        },
+    )
+}
+
+// Same as ecdsa_verify_no_pubkey_check, for curves whose `a` coefficient is not 0 (e.g. for
+// Secp256r1), which halo2-ecc's variable base scalar multiplication does not support.
+//
+// A pubkey of (0, 0) is treated as invalid, so that the caller can replace a pubkey that is not
+// on the curve by (0, 0).
+// returns
+// - if the signature is valid
+pub(crate) fn ecdsa_verify_no_pubkey_check_any_a<
+    F: PrimeField,
+    CF: PrimeField,
+    SF: PrimeField,
+    GA,
+>(
+    base_chip: &FpConfig<F, CF>,
+    ctx: &mut Context<F>,
+    pubkey: &FpPoint<F>,
+    r: &CRTInteger<F>,
+    s: &CRTInteger<F>,
+    msghash: &CRTInteger<F>,
+    fixed_window_bits: usize,
+) -> AssignedValue<F>
+where
+    GA: CurveAffineExt<Base = CF, ScalarExt = SF>,
+{
+    let (res, _, _) = ecdsa_verify_with_var_base_mul::<F, CF, SF, GA>(
+        base_chip,
+        ctx,
+        pubkey,
+        r,
+        s,
+        msghash,
+        fixed_window_bits,
+        |ctx, pubkey, is_pubkey_zero, scalar| {
+            let ecc_chip = EccChip::<F, FpConfig<F, CF>>::construct(base_chip.clone());
+            let generator = ecc_chip.assign_constant_point(ctx, GA::generator());
+            let pubkey_prime = ecc_chip.select(ctx, &generator, pubkey, is_pubkey_zero);
+            scalar_multiply_any_a::<F, CF, GA>(
+                base_chip,
+                ctx,
+                &pubkey_prime,
+                &scalar.truncation.limbs,
+                base_chip.limb_bits,
+            )
+        },
+    );
+
+    res
+}
+
+// The ECDSA verification, parameterized by the variable base scalar multiplication
+// `var_base_mul(ctx, pubkey, is_pubkey_zero, scalar)`, which must not fail for a (0, 0) pubkey.
+#[allow(clippy::too_many_arguments)]
+fn ecdsa_verify_with_var_base_mul<F: PrimeField, CF: PrimeField, SF: PrimeField, GA>(
+    base_chip: &FpConfig<F, CF>,
+    ctx: &mut Context<F>,
+    pubkey: &FpPoint<F>,
+    r: &CRTInteger<F>,
+    s: &CRTInteger<F>,
+    msghash: &CRTInteger<F>,
+    fixed_window_bits: usize,
+    var_base_mul: impl FnOnce(
+        &mut Context<F>,
+        &FpPoint<F>,
+        &AssignedValue<F>,
+        &CRTInteger<F>,
+    ) -> FpPoint<F>,
+) -> (AssignedValue<F>, AssignedValue<F>, CRTInteger<F>)
 where
     GA: CurveAffineExt<Base = CF, ScalarExt = SF>,
 {
@@ -120,16 +248,7 @@ where
 
     // compute u2 * pubkey
     let u2_prime = scalar_chip.select(ctx, &one, &u2, &s_is_zero);
-    let pubkey_prime = ecc_chip.load_random_point::<GA>(ctx);
-    let pubkey_prime = ecc_chip.select(ctx, &pubkey_prime, pubkey, &is_pubkey_zero);
-    let u2_mul = scalar_multiply::<F, _>(
-        base_chip,
-        ctx,
-        &pubkey_prime,
-        &u2_prime.truncation.limbs,
-        base_chip.limb_bits,
-        var_window_bits,
-    );
+    let u2_mul = var_base_mul(ctx, pubkey, &is_pubkey_zero, &u2_prime);
     let u2_is_zero =
         base_chip
             .range()
@@ -248,3 +367,273 @@ where
 
     (res, is_pubkey_zero, y_3)
 }
+
+// Splits k (mod n) into k1 + lambda * k2 with |k1|, |k2| < 2^128, returned as
+// (|k1|, k1 < 0, |k2|, k2 < 0).
+pub(super) fn glv_decompose(k: &BigInt) -> (BigUint, bool, BigUint, bool) {
+    let GlvParams {
+        n,
+        a1,
+        minus_b1,
+        a2,
+        b2,
+        ..
+    } = &*SECP256K1_GLV;
+    let k = k.mod_floor(n);
+    let half_n = n >> 1;
+
+    // c1 = round(b2 * k / n), c2 = round(-b1 * k / n)
+    let c1 = (b2 * &k + &half_n) / n;
+    let c2 = (minus_b1 * &k + &half_n) / n;
+    let k1 = &k - &c1 * a1 - &c2 * a2;
+    let k2 = &c1 * minus_b1 - &c2 * b2;
+
+    (
+        k1.magnitude().clone(),
+        k1.sign() == num_bigint::Sign::Minus,
+        k2.magnitude().clone(),
+        k2.sign() == num_bigint::Sign::Minus,
+    )
+}
+
+// Computes scalar * p on secp256k1 with the GLV endomorphism: the scalar is split into two halves
+// of 128 bits such that scalar == k1 + lambda * k2 (mod n), and k1 * p + k2 * phi(p) is computed
+// with a single multi scalar multiplication, which shares the 128 doublings between both halves
+// instead of doing 256 doublings.
+//
+// Same assumptions as halo2-ecc's `scalar_multiply` on p.
+fn glv_scalar_multiply<F: PrimeField, CF: PrimeField, SF: PrimeField, GA>(
+    chip: &FpConfig<F, CF>,
+    ctx: &mut Context<F>,
+    p: &FpPoint<F>,
+    scalar: &CRTInteger<F>,
+    window_bits: usize,
+) -> FpPoint<F>
+where
+    GA: CurveAffineExt<Base = CF, ScalarExt = SF>,
+{
+    let glv = &*SECP256K1_GLV;
+    assert_eq!(
+        BigInt::from(modulus::<SF>()),
+        glv.n,
+        "GLV is only set up for secp256k1"
+    );
+    assert!(
+        chip.num_limbs >= 2 && 2 * chip.limb_bits >= GLV_HALF_BITS,
+        "the halves of the scalar must fit in the two lowest limbs"
+    );
+
+    let gate = chip.range().gate();
+    let scalar_chip = FpConfig::<F, SF>::construct(
+        chip.range.clone(),
+        chip.limb_bits,
+        chip.num_limbs,
+        modulus::<SF>(),
+    );
+    let decomposed = scalar.value.as_ref().map(glv_decompose);
+
+    // load |k_i| both as a CRT integer for the relation mod n, and as two limbs of
+    // GLV_HALF_BITS / 2 bits for the scalar multiplication, which bounds |k_i| < 2^128
+    let half_limb_bits = GLV_HALF_BITS / 2;
+    let load_half = |ctx: &mut Context<F>, abs: Value<BigUint>, is_neg: Value<bool>| {
+        let integer = scalar_chip.load_private(ctx, abs.as_ref().map(|v| BigInt::from(v.clone())));
+        for limb in integer.truncation.limbs.iter().skip(2) {
+            gate.assert_is_const(ctx, limb, F::ZERO);
+        }
+        let (lo, hi) = abs
+            .map(|v| {
+                let lo = &v & ((BigUint::from(1u64) << half_limb_bits) - 1u64);
+                let hi = v >> half_limb_bits;
+                (biguint_to_fe::<F>(&lo), biguint_to_fe::<F>(&hi))
+            })
+            .unzip();
+        let lo = gate.load_witness(ctx, lo);
+        let hi = gate.load_witness(ctx, hi);
+        chip.range().range_check(ctx, &lo, half_limb_bits);
+        chip.range().range_check(ctx, &hi, half_limb_bits);
+        // lo + hi * 2^64 == limb_0 + limb_1 * 2^limb_bits, where both sides are far below the
+        // native modulus
+        let from_halves = gate.inner_product(
+            ctx,
+            vec![Existing(lo), Existing(hi)],
+            vec![
+                Constant(F::ONE),
+                Constant(biguint_to_fe(&(BigUint::from(1u64) << half_limb_bits))),
+            ],
+        );
+        let from_limbs = gate.inner_product(
+            ctx,
+            vec![
+                Existing(integer.truncation.limbs[0]),
+                Existing(integer.truncation.limbs[1]),
+            ],
+            vec![Constant(F::ONE), Constant(chip.limb_bases[1])],
+        );
+        gate.assert_equal(ctx, Existing(from_halves), Existing(from_limbs));
+
+        let is_neg = gate.load_witness(ctx, is_neg.map(|b| F::from(b as u64)));
+        gate.assert_bit(ctx, is_neg);
+        let neg_integer = scalar_chip.negate(ctx, &integer);
+        let signed = scalar_chip.select(ctx, &neg_integer, &integer, &is_neg);
+
+        (signed, vec![lo, hi], is_neg)
+    };
+    let (k1, k1_limbs, k1_is_neg) = load_half(
+        ctx,
+        decomposed.as_ref().map(|d| d.0.clone()),
+        decomposed.as_ref().map(|d| d.1),
+    );
+    let (k2, k2_limbs, k2_is_neg) = load_half(
+        ctx,
+        decomposed.as_ref().map(|d| d.2.clone()),
+        decomposed.as_ref().map(|d| d.3),
+    );
+
+    // scalar == k1 + lambda * k2 (mod n)
+    let lambda = scalar_chip.load_constant(ctx, glv.lambda.clone());
+    let lambda_k2 = scalar_chip.mul_no_carry(ctx, &k2, &lambda);
+    let k1_plus_lambda_k2 = scalar_chip.add_no_carry(ctx, &k1, &lambda_k2);
+    let diff = scalar_chip.sub_no_carry(ctx, &k1_plus_lambda_k2, scalar);
+    scalar_chip.check_carry_mod_to_zero(ctx, &diff);
+
+    // k1 * p + k2 * phi(p) == |k1| * (+-p) + |k2| * (+-phi(p)), with phi(x, y) = (beta * x, y)
+    let beta = chip.load_constant(ctx, glv.beta.clone());
+    let neg_y = chip.negate(ctx, &p.y);
+    let p1 = EcPoint::construct(p.x.clone(), chip.select(ctx, &neg_y, &p.y, &k1_is_neg));
+    let p2 = EcPoint::construct(
+        chip.mul(ctx, &p.x, &beta),
+        chip.select(ctx, &neg_y, &p.y, &k2_is_neg),
+    );
+
+    multi_scalar_multiply::<F, _, GA>(
+        chip,
+        ctx,
+        &[p1, p2],
+        &[k1_limbs, k2_limbs],
+        half_limb_bits,
+        window_bits,
+    )
+}
+
+// Doubles a point on a curve with any `a` coefficient, unlike halo2-ecc's `ec_double` which
+// assumes `a == 0`. Assumes that `p.y != 0`, which always holds on curves of odd prime order.
+fn ec_double_any_a<F: PrimeField, CF: PrimeField, GA>(
+    chip: &FpConfig<F, CF>,
+    ctx: &mut Context<F>,
+    p: &FpPoint<F>,
+) -> FpPoint<F>
+where
+    GA: CurveAffineExt<Base = CF>,
+{
+    // lambda = (3 x^2 + a) / (2 y)
+    let two_y = chip.scalar_mul_no_carry(ctx, &p.y, 2);
+    let three_x = chip.scalar_mul_no_carry(ctx, &p.x, 3);
+    let three_x_sq = chip.mul_no_carry(ctx, &three_x, &p.x);
+    let numerator = chip.add_constant_no_carry(ctx, &three_x_sq, fe_to_biguint(&GA::a()));
+    let lambda = chip.divide(ctx, &numerator, &two_y);
+
+    // x_0 = lambda^2 - 2 x
+    let lambda_sq = chip.mul_no_carry(ctx, &lambda, &lambda);
+    let two_x = chip.scalar_mul_no_carry(ctx, &p.x, 2);
+    let x_0 = chip.sub_no_carry(ctx, &lambda_sq, &two_x);
+    let x_0 = chip.carry_mod(ctx, &x_0);
+
+    // y_0 = lambda * (x - x_0) - y
+    let dx = chip.sub_no_carry(ctx, &p.x, &x_0);
+    let lambda_dx = chip.mul_no_carry(ctx, &lambda, &dx);
+    let y_0 = chip.sub_no_carry(ctx, &lambda_dx, &p.y);
+    let y_0 = chip.carry_mod(ctx, &y_0);
+
+    EcPoint::construct(x_0, y_0)
+}
+
+// Returns p + q if `cond` is set, and an unconstrained point otherwise. Unlike halo2-ecc's
+// `ec_add_unequal`, the witness generation does not fail when `p.x == q.x` and `cond` is unset.
+fn ec_add_unequal_if<F: PrimeField, CF: PrimeField>(
+    chip: &FpConfig<F, CF>,
+    ctx: &mut Context<F>,
+    p: &FpPoint<F>,
+    q: &FpPoint<F>,
+    cond: &AssignedValue<F>,
+) -> FpPoint<F> {
+    let one = chip.load_constant(ctx, fe_to_biguint(&CF::ONE));
+
+    // lambda = (q.y - p.y) / (q.x - p.x), where q.x - p.x is replaced by 1 if not `cond`
+    let dx = chip.sub_no_carry(ctx, &q.x, &p.x);
+    let dx = chip.select(ctx, &dx, &one, cond);
+    let dy = chip.sub_no_carry(ctx, &q.y, &p.y);
+    let lambda = chip.divide(ctx, &dy, &dx);
+
+    // x_3 = lambda^2 - p.x - q.x
+    let lambda_sq = chip.mul_no_carry(ctx, &lambda, &lambda);
+    let lambda_sq_minus_px = chip.sub_no_carry(ctx, &lambda_sq, &p.x);
+    let x_3 = chip.sub_no_carry(ctx, &lambda_sq_minus_px, &q.x);
+    let x_3 = chip.carry_mod(ctx, &x_3);
+
+    // y_3 = lambda * (p.x - x_3) - p.y
+    let dx_13 = chip.sub_no_carry(ctx, &p.x, &x_3);
+    let lambda_dx_13 = chip.mul_no_carry(ctx, &lambda, &dx_13);
+    let y_3 = chip.sub_no_carry(ctx, &lambda_dx_13, &p.y);
+    let y_3 = chip.carry_mod(ctx, &y_3);
+
+    EcPoint::construct(x_3, y_3)
+}
+
+// Computes scalar * p with a most significant bit first double-and-add, on a curve with any `a`
+// coefficient. The scalar is given as little-endian limbs of `max_bits` bits each, like in
+// halo2-ecc's `scalar_multiply`.
+//
+// Assumes that p is not the identity, and that the scalar is non-zero and smaller than the order
+// of p, so that none of the intermediate additions is a doubling or hits the identity.
+fn scalar_multiply_any_a<F: PrimeField, CF: PrimeField, GA>(
+    chip: &FpConfig<F, CF>,
+    ctx: &mut Context<F>,
+    p: &FpPoint<F>,
+    scalar: &[AssignedValue<F>],
+    max_bits: usize,
+) -> FpPoint<F>
+where
+    GA: CurveAffineExt<Base = CF>,
+{
+    let ecc_chip = EccChip::<F, FpConfig<F, CF>>::construct(chip.clone());
+    let gate = chip.range().gate();
+    let bits = scalar
+        .iter()
+        .flat_map(|limb| gate.num_to_bits(ctx, limb, max_bits))
+        .collect::<Vec<_>>();
+
+    // Until the first set bit, acc is a dummy p, which is replaced by the actual p at the first
+    // set bit.
+    let mut acc = p.clone();
+    let mut is_started = gate.load_zero(ctx);
+    for bit in bits.iter().rev() {
+        let doubled = ec_double_any_a::<F, CF, GA>(chip, ctx, &acc);
+        let added = ec_add_unequal_if(chip, ctx, &doubled, p, bit);
+        let next = ecc_chip.select(ctx, &added, &doubled, bit);
+        acc = ecc_chip.select(ctx, &next, p, &is_started);
+        is_started = gate.or(ctx, Existing(is_started), Existing(*bit));
+    }
+
+    acc
+}
+
+// Returns whether p is on the curve y^2 = x^3 + a * x + b. Unlike halo2-ecc's
+// `is_on_curve_or_infinity`, this does not assume `a == 0`, and (0, 0) is never on the curve as
+// `b != 0` for the supported curves.
+pub(crate) fn is_on_curve_any_a<F: PrimeField, CF: PrimeField, GA>(
+    chip: &FpConfig<F, CF>,
+    ctx: &mut Context<F>,
+    p: &FpPoint<F>,
+) -> AssignedValue<F>
+where
+    GA: CurveAffineExt<Base = CF>,
+{
+    let lhs = chip.mul(ctx, &p.y, &p.y);
+    let x_sq = chip.mul(ctx, &p.x, &p.x);
+    let x_sq_plus_a = chip.add_constant_no_carry(ctx, &x_sq, fe_to_biguint(&GA::a()));
+    let rhs = chip.mul_no_carry(ctx, &x_sq_plus_a, &p.x);
+    let rhs = chip.add_constant_no_carry(ctx, &rhs, fe_to_biguint(&GA::b()));
+    let rhs = chip.carry_mod(ctx, &rhs);
+
+    chip.is_equal(ctx, &lhs, &rhs)
+}
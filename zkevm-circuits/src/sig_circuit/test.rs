@@ -1,11 +1,12 @@
 use crate::{sig_circuit::SigCircuit, util::Field};
-use eth_types::sign_types::{sign, SignData};
+use eth_types::sign_types::{sign, sign_p256, P256SignData, SignData};
 use halo2_proofs::{
     arithmetic::Field as HaloField,
     dev::MockProver,
     halo2curves::{
         group::Curve,
         secp256k1::{self, Secp256k1Affine},
+        secp256r1::{self, Secp256r1Affine},
     },
 };
 use rand::{Rng, RngCore};
@@ -147,6 +148,67 @@ fn test_edge_cases() {
     run::<Fr>(LOG_TOTAL_NUM_ROWS as u32, 10, signatures);
 }
 
+#[test]
+fn p256_sign_verify() {
+    use super::utils::{LOG_TOTAL_NUM_ROWS, MAX_NUM_P256_SIG};
+    use halo2_proofs::halo2curves::bn256::Fr;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use snark_verifier::util::arithmetic::PrimeCurveAffine;
+    let mut rng = XorShiftRng::seed_from_u64(1);
+
+    let sign_data = |rng: &mut XorShiftRng,
+                     sk: secp256r1::Fq,
+                     pk: Secp256r1Affine,
+                     msg_hash: secp256r1::Fq| P256SignData {
+        signature: sign_p256(secp256r1::Fq::random(rng), sk, msg_hash),
+        pk,
+        msg_hash,
+    };
+
+    let sk = secp256r1::Fq::random(&mut rng);
+    let pk = (Secp256r1Affine::generator() * sk).to_affine();
+    let other_pk = (Secp256r1Affine::generator() * sk.double()).to_affine();
+    let mut off_curve_pk = pk;
+    off_curve_pk.y += secp256r1::Fp::one();
+    let msg_hash = secp256r1::Fq::random(&mut rng);
+
+    let signatures = vec![
+        // 1. valid signature
+        sign_data(&mut rng, sk, pk, msg_hash),
+        // 2. valid signature of msg_hash == 0
+        sign_data(&mut rng, sk, pk, secp256r1::Fq::zero()),
+        // 3. signature checked against another key
+        sign_data(&mut rng, sk, other_pk, msg_hash),
+        // 4. public key not on the curve
+        sign_data(&mut rng, sk, off_curve_pk, msg_hash),
+        // 5. public key at infinity
+        sign_data(&mut rng, sk, Secp256r1Affine::identity(), msg_hash),
+        // 6. r == 0 and s == 0
+        P256SignData {
+            signature: (secp256r1::Fq::zero(), secp256r1::Fq::zero()),
+            pk,
+            msg_hash,
+        },
+    ];
+    assert_eq!(
+        signatures
+            .iter()
+            .map(|sig| sig.verify())
+            .collect::<Vec<_>>(),
+        vec![true, true, false, false, false, false]
+    );
+    assert!(signatures.len() <= MAX_NUM_P256_SIG);
+
+    run_p256::<Fr>(
+        LOG_TOTAL_NUM_ROWS as u32,
+        1,
+        vec![SignData::default()],
+        MAX_NUM_P256_SIG,
+        signatures,
+    );
+}
+
 #[test]
 fn sign_verify() {
     use super::utils::LOG_TOTAL_NUM_ROWS;
@@ -229,6 +291,55 @@ fn sign_verify() {
     }
 }
 
+#[test]
+fn glv_decompose_bounds() {
+    use super::ecdsa::glv_decompose;
+    use eth_types::sign_types::SECP256K1_Q;
+    use halo2_proofs::halo2curves::group::ff::PrimeField;
+    use num::{BigInt, BigUint, Integer, Zero};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    let mut rng = XorShiftRng::seed_from_u64(1);
+
+    let n = BigInt::from(SECP256K1_Q.clone());
+    let lambda = BigInt::parse_bytes(
+        b"5363ad4cc05c30e0a5261c028812645a122e22ea20816678df02967c1b23bd72",
+        16,
+    )
+    .unwrap();
+    let half_bound = BigUint::from(1u64) << 128;
+    let signed = |abs: BigUint, is_neg: bool| {
+        if is_neg {
+            -BigInt::from(abs)
+        } else {
+            BigInt::from(abs)
+        }
+    };
+
+    let edge_scalars = [
+        BigInt::zero(),
+        BigInt::from(1u64),
+        &n - 1u64,
+        lambda.clone(),
+        &n - &lambda,
+        BigInt::from(half_bound.clone()),
+    ];
+    let random_scalars = (0..1000).map(|_| {
+        let k = secp256k1::Fq::random(&mut rng);
+        BigInt::from(BigUint::from_bytes_le(&k.to_repr()))
+    });
+    for k in edge_scalars.into_iter().chain(random_scalars) {
+        let (k1, k1_is_neg, k2, k2_is_neg) = glv_decompose(&k);
+        assert!(k1 < half_bound, "|k1| >= 2^128 for k = {k}");
+        assert!(k2 < half_bound, "|k2| >= 2^128 for k = {k}");
+        let recomposed = signed(k1, k1_is_neg) + &lambda * signed(k2, k2_is_neg);
+        assert!(
+            (recomposed - &k).mod_floor(&n).is_zero(),
+            "k != k1 + lambda * k2 (mod n) for k = {k}"
+        );
+    }
+}
+
 // Generate a test key pair
 fn gen_key_pair(rng: impl RngCore) -> (secp256k1::Fq, Secp256k1Affine) {
     // generate a valid signature
@@ -264,10 +375,22 @@ fn sign_with_rng(
 }
 
 fn run<F: Field>(k: u32, max_verif: usize, signatures: Vec<SignData>) {
+    run_p256::<F>(k, max_verif, signatures, 0, vec![]);
+}
+
+fn run_p256<F: Field>(
+    k: u32,
+    max_verif: usize,
+    signatures: Vec<SignData>,
+    max_p256_verif: usize,
+    p256_signatures: Vec<P256SignData>,
+) {
     // SignVerifyChip -> ECDSAChip -> MainGate instance column
     let circuit = SigCircuit::<F> {
         max_verif,
         signatures,
+        max_p256_verif,
+        p256_signatures,
         _marker: PhantomData,
     };
 
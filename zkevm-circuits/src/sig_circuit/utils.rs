@@ -7,13 +7,26 @@ use halo2_ecc::{
 };
 use halo2_proofs::{
     circuit::Value,
-    halo2curves::secp256k1::{Fp, Fq},
+    halo2curves::{
+        secp256k1::{Fp, Fq},
+        secp256r1::{Fp as P256Fp, Fq as P256Fq},
+    },
 };
 
 // Hard coded parameters.
 // FIXME: allow for a configurable param.
 pub(super) const MAX_NUM_SIG: usize = 128;
-// Each ecdsa signature requires 461174 cells
+// Max number of secp256r1 signatures, for the P256VERIFY precompile. Each of them is budgeted as
+// P256_SIG_COST secp256k1 signatures out of MAX_NUM_SIG.
+pub(super) const MAX_NUM_P256_SIG: usize = 8;
+// The variable base scalar multiplication of secp256r1 can't use halo2-ecc's windowed method,
+// which requires a == 0, so it costs about twice as many cells as secp256k1.
+pub(super) const P256_SIG_COST: usize = 2;
+// Each ecdsa signature requires 461174 cells.
+// This was measured with the 256 bits variable base scalar multiplication, and is an upper bound
+// since it uses the GLV endomorphism. Re-measure with
+// `cargo test --release --package zkevm-circuits --lib sig_circuit::test::sign_verify --
+// --nocapture` before raising MAX_NUM_SIG.
 pub(super) const CELLS_PER_SIG: usize = 461174;
 // Each ecdsa signature requires 63276 lookup cells
 pub(super) const LOOKUP_CELLS_PER_SIG: usize = 63276;
@@ -64,6 +77,10 @@ pub(super) fn calc_required_lookup_advices(num_verif: usize) -> usize {
 pub(super) type FqChip<F> = FpConfig<F, Fq>;
 /// Chip to handle ECDSA::Fp, the base field
 pub(super) type FpChip<F> = FpConfig<F, Fp>;
+/// Chip to handle overflow integers of secp256r1's scalar field
+pub(super) type P256FqChip<F> = FpConfig<F, P256Fq>;
+/// Chip to handle secp256r1's base field
+pub(super) type P256FpChip<F> = FpConfig<F, P256Fp>;
 
 pub(crate) struct AssignedECDSA<F: Field, FC: FieldChip<F>> {
     pub(super) pk: EcPoint<F, FC::FieldPoint>,
@@ -75,6 +92,14 @@ pub(crate) struct AssignedECDSA<F: Field, FC: FieldChip<F>> {
     pub(super) sig_is_valid: AssignedValue<F>,
 }
 
+pub(crate) struct AssignedP256ECDSA<F: Field> {
+    pub(super) pk: EcPoint<F, CRTInteger<F>>,
+    pub(super) msg_hash: CRTInteger<F>,
+    pub(super) integer_r: CRTInteger<F>,
+    pub(super) integer_s: CRTInteger<F>,
+    pub(super) sig_is_valid: AssignedValue<F>,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct AssignedSignatureVerify<F: Field> {
     pub(crate) address: AssignedValue<F>,
@@ -87,6 +112,16 @@ pub(crate) struct AssignedSignatureVerify<F: Field> {
     pub(crate) sig_is_valid: AssignedValue<F>,
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct AssignedP256SignatureVerify<F: Field> {
+    pub(crate) msg_hash_rlc: AssignedValue<F>,
+    pub(crate) r_rlc: AssignedValue<F>,
+    pub(crate) s_rlc: AssignedValue<F>,
+    pub(crate) pk_x_rlc: AssignedValue<F>,
+    pub(crate) pk_y_rlc: AssignedValue<F>,
+    pub(crate) sig_is_valid: AssignedValue<F>,
+}
+
 pub(super) struct SignDataDecomposed<F: Field> {
     pub(super) pk_hash_cells: Vec<QuantumCell<F>>,
     pub(super) msg_hash_cells: Vec<QuantumCell<F>>,
@@ -97,3 +132,11 @@ pub(super) struct SignDataDecomposed<F: Field> {
     pub(super) s_cells: Vec<QuantumCell<F>>,
     //v:  AssignedValue<'v, F>, // bool
 }
+
+pub(super) struct P256SignDataDecomposed<F: Field> {
+    pub(super) msg_hash_cells: Vec<QuantumCell<F>>,
+    pub(super) r_cells: Vec<QuantumCell<F>>,
+    pub(super) s_cells: Vec<QuantumCell<F>>,
+    pub(super) pk_x_cells: Vec<QuantumCell<F>>,
+    pub(super) pk_y_cells: Vec<QuantumCell<F>>,
+}
@@ -21,7 +21,10 @@ use self::{
 use crate::{
     evm_circuit::{param::N_BYTES_WORD, util::rlc},
     table::{AccountFieldTag, LookupTable, MptTable, RwTable, RwTableTag},
-    util::{Challenges, Expr, Field, SubCircuit, SubCircuitConfig},
+    util::{
+        advice_column_in_phase2, ChallengePhases, Challenges, Expr, Field, SubCircuit,
+        SubCircuitConfig,
+    },
     witness::{self, MptUpdates, Rw, RwMap},
 };
 use constraint_builder::{ConstraintBuilder, Queries};
@@ -32,7 +35,10 @@ use gadgets::{
 };
 use halo2_proofs::{
     circuit::{AssignedCell, Cell, Layouter, Region, Value},
-    plonk::{Advice, Assigned, Column, ConstraintSystem, Error, Expression, Fixed, VirtualCells},
+    plonk::{
+        Advice, Assigned, Column, ConstraintSystem, Error, Expression, FirstPhase, Fixed,
+        SecondPhase, VirtualCells,
+    },
     poly::Rotation,
 };
 use itertools::Itertools;
@@ -43,11 +49,6 @@ use param::*;
 use random_linear_combination::{Chip as RlcChip, Config as RlcConfig, Queries as RlcQueries};
 use std::marker::PhantomData;
 
-#[cfg(feature = "onephase")]
-use halo2_proofs::plonk::FirstPhase as SecondPhase;
-#[cfg(not(feature = "onephase"))]
-use halo2_proofs::plonk::SecondPhase;
-
 #[cfg(any(feature = "test", test, feature = "test-circuits"))]
 use std::collections::HashMap;
 
@@ -127,28 +128,39 @@ impl<F: Field> SubCircuitConfig<F> for StateCircuitConfig<F> {
             challenges.evm_word(),
         );
 
-        let initial_value = meta.advice_column_in(SecondPhase);
+        let initial_value = advice_column_in_phase2(meta, challenges.phases());
         // If the rw lookup is for an Account with field tag = CodeHash and both values are 0, we
         // actually want to do an mpt lookup for an non-existing account instead of an mpt lookup
         // for the code hash. Similarly, if the rw lookup is for an storage key with both values =
         // 0, we instead want to do an mpt lookup for a non-existing storage slot, instead of for a
         // changed storage value. (This is why the field tag for Rw::Storage is
         // Some(AccountFieldTag::CodeHash as u64) instead of None.)
-        let is_non_exist = BatchedIsZeroChip::configure(
-            meta,
-            (SecondPhase, SecondPhase),
-            |meta| meta.query_fixed(selector, Rotation::cur()),
-            |meta| {
-                [
-                    meta.query_advice(rw_table.field_tag, Rotation::cur())
-                        - AccountFieldTag::CodeHash.expr(),
-                    meta.query_advice(initial_value, Rotation::cur()),
-                    meta.query_advice(rw_table.value, Rotation::cur()),
-                ]
-            },
-        );
-        let mpt_proof_type = meta.advice_column_in(SecondPhase);
-        let state_root = meta.advice_column_in(SecondPhase);
+        let is_non_exist_q_enable =
+            |meta: &mut VirtualCells<'_, F>| meta.query_fixed(selector, Rotation::cur());
+        let is_non_exist_values = |meta: &mut VirtualCells<'_, F>| {
+            [
+                meta.query_advice(rw_table.field_tag, Rotation::cur())
+                    - AccountFieldTag::CodeHash.expr(),
+                meta.query_advice(initial_value, Rotation::cur()),
+                meta.query_advice(rw_table.value, Rotation::cur()),
+            ]
+        };
+        let is_non_exist = match challenges.phases() {
+            ChallengePhases::Multi => BatchedIsZeroChip::configure(
+                meta,
+                (SecondPhase, SecondPhase),
+                is_non_exist_q_enable,
+                is_non_exist_values,
+            ),
+            ChallengePhases::One => BatchedIsZeroChip::configure(
+                meta,
+                (FirstPhase, FirstPhase),
+                is_non_exist_q_enable,
+                is_non_exist_values,
+            ),
+        };
+        let mpt_proof_type = advice_column_in_phase2(meta, challenges.phases());
+        let state_root = advice_column_in_phase2(meta, challenges.phases());
         meta.enable_equality(state_root);
 
         let sort_keys = SortKeysConfig {
@@ -24,9 +24,9 @@ where
     }
 
     fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
-        let rw_table = RwTable::construct(meta);
-        let mpt_table = MptTable::construct(meta);
         let challenges = Challenges::construct(meta);
+        let rw_table = RwTable::construct(meta, challenges.phases());
+        let mpt_table = MptTable::construct(meta, challenges.phases());
 
         let config = {
             let challenges = challenges.exprs(meta);
@@ -22,7 +22,7 @@ impl<const N: usize> DisplayTable<N> {
             rows: Vec::new(),
         }
     }
-    fn push_row(&mut self, row: [String; N]) {
+    pub(crate) fn push_row(&mut self, row: [String; N]) {
         self.rows.push(row)
     }
     fn print_row(row: &[String; N], rows_width: &[usize; N]) {
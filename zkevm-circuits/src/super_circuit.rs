@@ -73,7 +73,7 @@ use crate::{
         keccak_packed_multi::get_num_rows_per_round, KeccakCircuit, KeccakCircuitConfig,
         KeccakCircuitConfigArgs,
     },
-    modexp_circuit::{ModExpCircuit, ModExpCircuitConfig},
+    modexp_circuit::{ModExpCircuit, ModExpCircuitConfig, ModExpCircuitConfigArgs},
     pi_circuit::{PiCircuit, PiCircuitConfig, PiCircuitConfigArgs},
     poseidon_circuit::{PoseidonCircuit, PoseidonCircuitConfig, PoseidonCircuitConfigArgs},
     rlp_circuit_fsm::{RlpCircuit, RlpCircuitConfig, RlpCircuitConfigArgs},
@@ -89,7 +89,10 @@ use crate::{
         SigTable, TxTable, U16Table, U8Table,
     },
     tx_circuit::{TxCircuit, TxCircuitConfig, TxCircuitConfigArgs},
-    util::{circuit_stats, log2_ceil, Challenges, SubCircuit, SubCircuitConfig},
+    util::{
+        circuit_stats, log2_ceil, ChallengePhases, Challenges, PhasedCircuit, SubCircuit,
+        SubCircuitConfig,
+    },
     witness::{block_convert, Block, Transaction},
 };
 
@@ -121,7 +124,7 @@ pub struct SuperCircuitConfig<F: Field> {
     state_circuit: StateCircuitConfig<F>,
     tx_circuit: TxCircuitConfig<F>,
     sig_circuit: SigCircuitConfig<F>,
-    modexp_circuit: ModExpCircuitConfig,
+    modexp_circuit: ModExpCircuitConfig<F>,
     ecc_circuit: EccCircuitConfig<F>,
     sha256_circuit: SHA256CircuitConfig,
     #[cfg(not(feature = "poseidon-codehash"))]
@@ -170,39 +173,40 @@ impl SubCircuitConfig<Fr> for SuperCircuitConfig<Fr> {
         let log_circuit_info = |meta: &ConstraintSystem<Fr>, tag: &str| {
             log::debug!("circuit info after {}: {:#?}", tag, circuit_stats(meta));
         };
+        let phases = challenges.phases();
         let challenges_expr = challenges.exprs(meta);
 
-        let tx_table = TxTable::construct(meta);
+        let tx_table = TxTable::construct(meta, phases);
         log_circuit_info(meta, "tx table");
-        let rw_table = RwTable::construct(meta);
+        let rw_table = RwTable::construct(meta, phases);
         log_circuit_info(meta, "rw table");
 
-        let mpt_table = MptTable::construct(meta);
+        let mpt_table = MptTable::construct(meta, phases);
         log_circuit_info(meta, "mpt table");
         let poseidon_table = PoseidonTable::construct(meta);
         log_circuit_info(meta, "poseidon table");
 
-        let bytecode_table = BytecodeTable::construct(meta);
+        let bytecode_table = BytecodeTable::construct(meta, phases);
         log_circuit_info(meta, "bytecode table");
-        let block_table = BlockTable::construct(meta);
+        let block_table = BlockTable::construct(meta, phases);
         log_circuit_info(meta, "block table");
         let q_copy_table = meta.fixed_column();
         log::debug!("q_copy_table {:?}", q_copy_table);
-        let copy_table = CopyTable::construct(meta, q_copy_table);
+        let copy_table = CopyTable::construct(meta, q_copy_table, phases);
         log_circuit_info(meta, "copy table");
         let exp_table = ExpTable::construct(meta);
         log_circuit_info(meta, "exp table");
         let rlp_table = RlpTable::construct(meta);
         log_circuit_info(meta, "rlp table");
-        let keccak_table = KeccakTable::construct(meta);
+        let keccak_table = KeccakTable::construct(meta, phases);
         log_circuit_info(meta, "keccak table");
-        let sha256_table = SHA256Table::construct(meta);
+        let sha256_table = SHA256Table::construct(meta, phases);
         log_circuit_info(meta, "sha256 table");
-        let sig_table = SigTable::construct(meta);
+        let sig_table = SigTable::construct(meta, phases);
         log_circuit_info(meta, "sig table");
-        let modexp_table = ModExpTable::construct(meta);
+        let modexp_table = ModExpTable::construct(meta, phases);
         log_circuit_info(meta, "modexp table");
-        let ecc_table = EccTable::construct(meta);
+        let ecc_table = EccTable::construct(meta, phases);
         log_circuit_info(meta, "ecc table");
         let pow_of_rand_table = PowOfRandTable::construct(meta, &challenges_expr);
         log_circuit_info(meta, "power of randomness table");
@@ -321,7 +325,14 @@ impl SubCircuitConfig<Fr> for SuperCircuitConfig<Fr> {
         #[cfg(feature = "zktrie")]
         log_circuit_info(meta, "zktrie circuit");
 
-        let modexp_circuit = ModExpCircuitConfig::new(meta, modexp_table);
+        let modexp_circuit = ModExpCircuitConfig::new(
+            meta,
+            ModExpCircuitConfigArgs {
+                modexp_table,
+                u8_table,
+                challenges: challenges_expr.clone(),
+            },
+        );
         log_circuit_info(meta, "modexp circuit");
         let state_circuit = StateCircuitConfig::new(
             meta,
@@ -384,8 +395,7 @@ impl SubCircuitConfig<Fr> for SuperCircuitConfig<Fr> {
         );
         log_circuit_info(meta, "ecc circuit");
 
-        #[cfg(feature = "onephase")]
-        if meta.max_phase() != 0 {
+        if phases == ChallengePhases::One && meta.max_phase() != 0 {
             log::warn!("max_phase: {}", meta.max_phase());
         }
 
@@ -763,20 +773,7 @@ impl<
     }
 
     fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
-        let challenges = Challenges::construct(meta);
-        (
-            SuperCircuitConfig::new(
-                meta,
-                SuperCircuitConfigArgs {
-                    max_txs: MAX_TXS,
-                    max_calldata: MAX_CALLDATA,
-                    max_inner_blocks: MAX_INNER_BLOCKS,
-                    mock_randomness: MOCK_RANDOMNESS,
-                    challenges,
-                },
-            ),
-            challenges,
-        )
+        Self::configure_with_phases(meta, ChallengePhases::Multi)
     }
 
     fn synthesize(
@@ -793,6 +790,35 @@ impl<
     }
 }
 
+impl<
+        const MAX_TXS: usize,
+        const MAX_CALLDATA: usize,
+        const MAX_INNER_BLOCKS: usize,
+        const MOCK_RANDOMNESS: u64,
+    > PhasedCircuit<Fr>
+    for SuperCircuit<Fr, MAX_TXS, MAX_CALLDATA, MAX_INNER_BLOCKS, MOCK_RANDOMNESS>
+{
+    fn configure_with_phases(
+        meta: &mut ConstraintSystem<Fr>,
+        phases: ChallengePhases,
+    ) -> Self::Config {
+        let challenges = Challenges::construct_with_phases(meta, phases);
+        (
+            SuperCircuitConfig::new(
+                meta,
+                SuperCircuitConfigArgs {
+                    max_txs: MAX_TXS,
+                    max_calldata: MAX_CALLDATA,
+                    max_inner_blocks: MAX_INNER_BLOCKS,
+                    mock_randomness: MOCK_RANDOMNESS,
+                    challenges,
+                },
+            ),
+            challenges,
+        )
+    }
+}
+
 impl<
         const MAX_TXS: usize,
         const MAX_CALLDATA: usize,
@@ -873,4 +899,15 @@ impl<
         let instance = circuit.instance();
         Ok((k, circuit, instance))
     }
+
+    /// Build super circuit from a witness block dump written by
+    /// [`Block::write_dump_file`].
+    pub fn build_from_witness_dump(
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<(u32, Self, Vec<Vec<Fr>>), bus_mapping::Error> {
+        let block = Block::read_dump_file(path).map_err(bus_mapping::Error::IoError)?;
+        assert_eq!(block.circuits_params.max_txs, MAX_TXS);
+        assert_eq!(block.circuits_params.max_calldata, MAX_CALLDATA);
+        Self::build_from_witness_block(block)
+    }
 }
@@ -19,8 +19,11 @@ pub const MAX_POSEIDON_ROWS: usize = 1_000_000;
 pub const MAX_VERTICAL_ROWS: usize = 1_000_000;
 pub const MAX_RWS: usize = 1_000_000;
 pub const MAX_PRECOMPILE_EC_ADD: usize = 50;
-pub const MAX_PRECOMPILE_EC_MUL: usize = 50;
+// 50 EcAdd, 20 EcMul and 2 EcPairing of 8 pairs take at most 34_965_000 cells, which fit in the
+// 35 advice columns of the ECC circuit.
+pub const MAX_PRECOMPILE_EC_MUL: usize = 20;
 pub const MAX_PRECOMPILE_EC_PAIRING: usize = 2;
+pub const MAX_PRECOMPILE_EC_PAIRING_PAIRS: usize = 8;
 
 /// default params for super circuit
 pub fn get_super_circuit_params() -> CircuitsParams {
@@ -42,6 +45,7 @@ pub fn get_super_circuit_params() -> CircuitsParams {
             ec_add: MAX_PRECOMPILE_EC_ADD,
             ec_mul: MAX_PRECOMPILE_EC_MUL,
             ec_pairing: MAX_PRECOMPILE_EC_PAIRING,
+            ec_pairing_pairs: MAX_PRECOMPILE_EC_PAIRING_PAIRS,
         },
     }
 }
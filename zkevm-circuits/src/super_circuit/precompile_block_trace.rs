@@ -741,7 +741,7 @@ pub(crate) fn block_invalid_precompile() -> BlockTrace {
                 PUSH1(0x20)
                 MSTORE
                 // Msize
-                PUSH1(0x21)
+                PUSH2(0x0101)
                 PUSH1(0x40)
                 MSTORE
                 // B, E and M
@@ -764,15 +764,15 @@ pub(crate) fn block_invalid_precompile() -> BlockTrace {
             name: "modexp length too large invalid",
             setup_code: bytecode! {
                 // Base size
-                PUSH1(0x21)
+                PUSH2(0x0101)
                 PUSH1(0x00)
                 MSTORE
                 // Esize
-                PUSH1(0x21)
+                PUSH2(0x0101)
                 PUSH1(0x20)
                 MSTORE
                 // Msize
-                PUSH1(0x21)
+                PUSH2(0x0101)
                 PUSH1(0x40)
                 MSTORE
                 // B, E and M
@@ -1,5 +1,6 @@
 #![allow(unused_imports)]
 pub use super::*;
+use crate::{test_util::triage_failures, util::OnePhase};
 use bus_mapping::{
     circuit_input_builder::CircuitInputBuilder,
     evm::{OpcodeId, PrecompileCallArgs},
@@ -56,6 +57,13 @@ fn super_circuit_degree() {
     assert!(cs.degree() <= 9);
 }
 
+#[test]
+fn super_circuit_one_phase() {
+    let mut cs = ConstraintSystem::<Fr>::default();
+    OnePhase::<SuperCircuit<Fr, 1, 32, 64, 0x100>>::configure(&mut cs);
+    assert_eq!(cs.num_challenges(), 0);
+}
+
 #[cfg(feature = "scroll")]
 fn test_super_circuit<
     const MAX_TXS: usize,
@@ -95,13 +103,13 @@ fn test_super_circuit<
         MAX_CALLDATA,
         MAX_INNER_BLOCKS,
         MOCK_RANDOMNESS,
-    >::build_from_witness_block(block)
+    >::build_from_witness_block(block.clone())
     .unwrap();
     let prover = MockProver::run(k, &circuit, instance).unwrap();
 
     let res = prover.verify_at_rows_par(0..active_row_num, 0..active_row_num);
     if let Err(errs) = res {
-        error!("Verification failures: {:#?}", errs);
+        error!("{}", triage_failures(&block, &errs));
         prover.assert_satisfied_par();
         panic!("Failed verification");
     }
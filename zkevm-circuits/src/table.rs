@@ -1,6 +1,7 @@
 //! Table definitions used cross-circuits
 
 use crate::{
+    bytecode_circuit::circuit::BytecodeCircuitConfig,
     copy_circuit::util::number_or_hash_to_field,
     evm_circuit::util::{
         constraint_builder::{BaseConstraintBuilder, ConstrainBuilderCommon},
@@ -8,7 +9,10 @@ use crate::{
     },
     exp_circuit::param::{OFFSET_INCREMENT, ROWS_PER_STEP},
     impl_expr,
-    util::{build_tx_log_address, rlc_be_bytes, Challenges, Field},
+    util::{
+        advice_column_in_phase2, build_tx_log_address, rlc_be_bytes, ChallengePhases, Challenges,
+        Field,
+    },
     witness::{
         Block, BlockContexts, Bytecode, MptUpdateRow, MptUpdates, RlpFsmWitnessGen, Rw, RwMap,
         RwRow, Transaction,
@@ -35,16 +39,12 @@ use halo2_proofs::{
     plonk::{Advice, Any, Column, ConstraintSystem, Error, Expression, Fixed, VirtualCells},
     poly::Rotation,
 };
+use serde::{Deserialize, Serialize};
 use sha3::Digest;
 use snark_verifier::util::arithmetic::PrimeCurveAffine;
 
 use std::iter::repeat;
 
-#[cfg(feature = "onephase")]
-use halo2_proofs::plonk::FirstPhase as SecondPhase;
-#[cfg(not(feature = "onephase"))]
-use halo2_proofs::plonk::SecondPhase;
-
 use halo2_proofs::plonk::TableColumn;
 use itertools::Itertools;
 use std::array;
@@ -223,16 +223,16 @@ pub struct TxTable {
 
 impl TxTable {
     /// Construct a new TxTable
-    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
+    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>, phases: ChallengePhases) -> Self {
         let q_enable = meta.fixed_column();
         Self {
             q_enable,
             tx_id: meta.advice_column(),
             tag: meta.advice_column(),
             index: meta.advice_column(),
-            value: meta.advice_column_in(SecondPhase),
+            value: advice_column_in_phase2(meta, phases),
             access_list_address: meta.advice_column(),
-            chunk_txbytes_hash_rlc: meta.advice_column_in(SecondPhase),
+            chunk_txbytes_hash_rlc: advice_column_in_phase2(meta, phases),
         }
     }
 
@@ -453,7 +453,7 @@ impl<F: Field> LookupTable<F> for TxTable {
 }
 
 /// Tag to identify the operation type in a RwTable row
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, EnumIter)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, EnumIter, Serialize, Deserialize)]
 pub enum RwTableTag {
     /// Start (used for padding)
     Start = 1,
@@ -504,7 +504,9 @@ impl From<RwTableTag> for usize {
 }
 
 /// Tag for an AccountField in RwTable
-#[derive(Clone, Copy, Debug, EnumIter, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(
+    Clone, Copy, Debug, EnumIter, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
 pub enum AccountFieldTag {
     /// Nonce field
     Nonce,
@@ -522,7 +524,7 @@ pub enum AccountFieldTag {
 impl_expr!(AccountFieldTag);
 
 /// Tag for a TxLogField in RwTable
-#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, Serialize, Deserialize)]
 pub enum TxLogFieldTag {
     /// Address field
     Address = 1,
@@ -534,7 +536,7 @@ pub enum TxLogFieldTag {
 impl_expr!(TxLogFieldTag);
 
 /// Tag for a TxReceiptField in RwTable
-#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, EnumCount)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, EnumCount, Serialize, Deserialize)]
 pub enum TxReceiptFieldTag {
     /// Tx result
     PostStateOrStatus = 1,
@@ -546,7 +548,7 @@ pub enum TxReceiptFieldTag {
 impl_expr!(TxReceiptFieldTag);
 
 /// Tag for a CallContextField in RwTable
-#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, Serialize, Deserialize)]
 pub enum CallContextFieldTag {
     /// RwCounterEndOfReversion
     RwCounterEndOfReversion = 1,
@@ -673,7 +675,7 @@ impl<F: Field> LookupTable<F> for RwTable {
 }
 impl RwTable {
     /// Construct a new RwTable
-    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
+    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>, phases: ChallengePhases) -> Self {
         Self {
             q_enable: meta.fixed_column(),
             rw_counter: meta.advice_column(),
@@ -682,13 +684,13 @@ impl RwTable {
             id: meta.advice_column(),
             address: meta.advice_column(),
             field_tag: meta.advice_column(),
-            storage_key: meta.advice_column_in(SecondPhase),
-            value: meta.advice_column_in(SecondPhase),
-            value_prev: meta.advice_column_in(SecondPhase),
+            storage_key: advice_column_in_phase2(meta, phases),
+            value: advice_column_in_phase2(meta, phases),
+            value_prev: advice_column_in_phase2(meta, phases),
             // It seems that aux1 for the moment is not using randomness
             // TODO check in a future review
-            aux1: meta.advice_column_in(SecondPhase),
-            aux2: meta.advice_column_in(SecondPhase),
+            aux1: advice_column_in_phase2(meta, phases),
+            aux2: advice_column_in_phase2(meta, phases),
         }
     }
     fn assign<F: Field>(
@@ -829,16 +831,19 @@ impl<F: Field> LookupTable<F> for MptTable {
 
 impl MptTable {
     /// Construct a new MptTable
-    pub(crate) fn construct<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
+    pub(crate) fn construct<F: Field>(
+        meta: &mut ConstraintSystem<F>,
+        phases: ChallengePhases,
+    ) -> Self {
         Self {
             q_enable: meta.fixed_column(),
             address: meta.advice_column(),
-            storage_key: meta.advice_column_in(SecondPhase),
+            storage_key: advice_column_in_phase2(meta, phases),
             proof_type: meta.advice_column(),
-            new_root: meta.advice_column_in(SecondPhase),
-            old_root: meta.advice_column_in(SecondPhase),
-            new_value: meta.advice_column_in(SecondPhase),
-            old_value: meta.advice_column_in(SecondPhase),
+            new_root: advice_column_in_phase2(meta, phases),
+            old_root: advice_column_in_phase2(meta, phases),
+            new_value: advice_column_in_phase2(meta, phases),
+            old_value: advice_column_in_phase2(meta, phases),
         }
     }
 
@@ -1194,14 +1199,18 @@ pub struct BytecodeTable {
     /// The RLC of the PUSH data (LE order), or 0.
     /// Warning: If the bytecode is truncated, this is the actual data, without zero-padding.
     pub push_rlc: Column<Advice>,
+    /// The RLC of the keccak hash of the bytecode. It equals `code_hash` unless the code hash is
+    /// the poseidon hash. Not part of the lookup columns, see `columns_code_hashes`.
+    pub keccak_code_hash: Column<Advice>,
 }
 
 impl BytecodeTable {
     /// Construct a new BytecodeTable
-    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
+    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>, phases: ChallengePhases) -> Self {
         let [tag, index, is_code, value] = array::from_fn(|_| meta.advice_column());
-        let code_hash = meta.advice_column_in(SecondPhase);
-        let push_rlc = meta.advice_column_in(SecondPhase);
+        let code_hash = advice_column_in_phase2(meta, phases);
+        let push_rlc = advice_column_in_phase2(meta, phases);
+        let keccak_code_hash = advice_column_in_phase2(meta, phases);
         Self {
             q_enable: meta.fixed_column(),
             code_hash,
@@ -1210,6 +1219,7 @@ impl BytecodeTable {
             is_code,
             value,
             push_rlc,
+            keccak_code_hash,
         }
     }
 
@@ -1228,6 +1238,8 @@ impl BytecodeTable {
                 let bytecode_table_columns =
                     <BytecodeTable as LookupTable<F>>::advice_columns(self);
                 for bytecode in bytecodes.clone() {
+                    let keccak_code_hash =
+                        BytecodeCircuitConfig::keccak_code_hash_rlc(&bytecode.bytes, challenges);
                     for row in bytecode.table_assignments(challenges) {
                         region.assign_fixed(
                             || format!("bytecode table row {offset}"),
@@ -1243,6 +1255,12 @@ impl BytecodeTable {
                                 || value,
                             )?;
                         }
+                        region.assign_advice(
+                            || format!("bytecode table row {offset}"),
+                            self.keccak_code_hash,
+                            offset,
+                            || keccak_code_hash,
+                        )?;
                         offset += 1;
                     }
                 }
@@ -1251,6 +1269,15 @@ impl BytecodeTable {
         )
     }
 
+    /// The sub-table binding the code hash of each bytecode to its keccak code hash.
+    pub fn columns_code_hashes(&self) -> [Column<Any>; 3] {
+        [
+            self.q_enable.into(),
+            self.code_hash.into(),
+            self.keccak_code_hash.into(),
+        ]
+    }
+
     /// A sub-table of bytecode without is_code nor push_rlc.
     fn columns_mini(&self) -> Vec<Column<Any>> {
         vec![
@@ -1330,6 +1357,8 @@ pub enum BlockContextFieldTag {
     /// included in this block which also taking skipped l1 msgs into account.
     /// This could possibly be larger than NumTxs.
     NumAllTxs,
+    /// Parent beacon block root field, since EIP-4788
+    ParentBeaconBlockRoot,
 }
 impl_expr!(BlockContextFieldTag);
 
@@ -1352,11 +1381,11 @@ pub struct BlockTable {
 
 impl BlockTable {
     /// Construct a new BlockTable
-    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
+    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>, phases: ChallengePhases) -> Self {
         Self {
             tag: meta.fixed_column(),
             index: meta.advice_column(),
-            value: meta.advice_column_in(SecondPhase),
+            value: advice_column_in_phase2(meta, phases),
         }
     }
 
@@ -1468,13 +1497,13 @@ impl<F: Field> LookupTable<F> for KeccakTable {
 
 impl KeccakTable {
     /// Construct a new KeccakTable
-    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
+    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>, phases: ChallengePhases) -> Self {
         Self {
             q_enable: meta.fixed_column(),
             is_final: meta.advice_column(),
-            input_rlc: meta.advice_column_in(SecondPhase),
+            input_rlc: advice_column_in_phase2(meta, phases),
             input_len: meta.advice_column(),
-            output_rlc: meta.advice_column_in(SecondPhase),
+            output_rlc: advice_column_in_phase2(meta, phases),
         }
     }
 
@@ -1635,13 +1664,13 @@ impl<F: Field> LookupTable<F> for SHA256Table {
 
 impl SHA256Table {
     /// Construct a new KeccakTable
-    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
+    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>, phases: ChallengePhases) -> Self {
         Self {
             q_enable: meta.fixed_column(),
             is_final: meta.advice_column(),
             input_len: meta.advice_column(),
-            input_rlc: meta.advice_column_in(SecondPhase),
-            output_rlc: meta.advice_column_in(SecondPhase),
+            input_rlc: advice_column_in_phase2(meta, phases),
+            output_rlc: advice_column_in_phase2(meta, phases),
         }
     }
 
@@ -1777,17 +1806,21 @@ struct CopyThread<F: Field> {
 
 impl CopyTable {
     /// Construct a new CopyTable
-    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>, q_enable: Column<Fixed>) -> Self {
+    pub fn construct<F: Field>(
+        meta: &mut ConstraintSystem<F>,
+        q_enable: Column<Fixed>,
+        phases: ChallengePhases,
+    ) -> Self {
         Self {
             q_enable,
             is_first: meta.advice_column(),
-            id: meta.advice_column_in(SecondPhase),
+            id: advice_column_in_phase2(meta, phases),
             tag: BinaryNumberChip::configure(meta, q_enable, None),
             addr: meta.advice_column(),
             src_addr_end: meta.advice_column(),
             real_bytes_left: meta.advice_column(),
             value_wrod_rlc: meta.advice_column(), // TODO: rm
-            rlc_acc: meta.advice_column_in(SecondPhase),
+            rlc_acc: advice_column_in_phase2(meta, phases),
             rw_counter: meta.advice_column(),
             rwc_inc_left: meta.advice_column(),
         }
@@ -2404,14 +2437,14 @@ impl<F: Field> LookupTable<F> for RlpFsmRlpTable {
 
 impl RlpFsmRlpTable {
     /// Construct the RLP table.
-    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
+    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>, phases: ChallengePhases) -> Self {
         Self {
             q_enable: meta.fixed_column(),
             tx_id: meta.advice_column(),
             format: meta.advice_column(),
             rlp_tag: meta.advice_column(),
-            tag_value: meta.advice_column_in(SecondPhase),
-            tag_bytes_rlc: meta.advice_column_in(SecondPhase),
+            tag_value: advice_column_in_phase2(meta, phases),
+            tag_bytes_rlc: advice_column_in_phase2(meta, phases),
             tag_length: meta.advice_column(),
             is_output: meta.advice_column(),
             is_none: meta.advice_column(),
@@ -2513,6 +2546,8 @@ impl RlpFsmRlpTable {
 pub struct SigTable {
     /// Indicates whether or not the gates are enabled on the current row.
     pub q_enable: Column<Fixed>,
+    /// The curve of the signature verified on the current row, see [`SigCurve`].
+    pub curve: Column<Fixed>,
     /// Random-linear combination of the Keccak256 hash of the message that's signed.
     pub msg_hash_rlc: Column<Advice>,
     /// should be in range [0, 1]
@@ -2525,19 +2560,39 @@ pub struct SigTable {
     pub recovered_addr: Column<Advice>,
     /// Indicates whether or not the signature is valid or not upon signature verification.
     pub is_valid: Column<Advice>,
+    /// Random-linear combination of the public key's `x` coordinate, only used by secp256r1.
+    pub pk_x_rlc: Column<Advice>,
+    /// Random-linear combination of the public key's `y` coordinate, only used by secp256r1.
+    pub pk_y_rlc: Column<Advice>,
 }
 
+/// The curve a row of the [`SigTable`] verifies a signature on.
+///
+/// secp256k1 rows recover the signer's address (`pk_*_rlc` are 0), while secp256r1 rows verify
+/// the signature against a given public key (`sig_v` and `recovered_addr` are 0).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SigCurve {
+    /// secp256k1, used by transactions and the ecrecover precompile.
+    Secp256k1 = 0,
+    /// secp256r1 (P-256), used by the P256VERIFY precompile.
+    Secp256r1,
+}
+impl_expr!(SigCurve);
+
 impl SigTable {
     /// Construct the SigTable.
-    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
+    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>, phases: ChallengePhases) -> Self {
         Self {
             q_enable: meta.fixed_column(),
-            msg_hash_rlc: meta.advice_column_in(SecondPhase),
+            curve: meta.fixed_column(),
+            msg_hash_rlc: advice_column_in_phase2(meta, phases),
             sig_v: meta.advice_column(),
-            sig_s_rlc: meta.advice_column_in(SecondPhase),
-            sig_r_rlc: meta.advice_column_in(SecondPhase),
+            sig_s_rlc: advice_column_in_phase2(meta, phases),
+            sig_r_rlc: advice_column_in_phase2(meta, phases),
             recovered_addr: meta.advice_column(),
             is_valid: meta.advice_column(),
+            pk_x_rlc: advice_column_in_phase2(meta, phases),
+            pk_y_rlc: advice_column_in_phase2(meta, phases),
         }
     }
 
@@ -2554,33 +2609,16 @@ impl SigTable {
                 let signatures: Vec<SignData> = block.get_sign_data(false);
 
                 let evm_word = challenges.evm_word();
+                let word_rlc = |bytes: [u8; 32]| {
+                    evm_word.map(|challenge| rlc::value(bytes.iter().collect_vec(), challenge))
+                };
                 for (offset, sign_data) in signatures.iter().enumerate() {
-                    let msg_hash_rlc = evm_word.map(|challenge| {
-                        rlc::value(
-                            sign_data.msg_hash.to_bytes().iter().collect_vec(),
-                            challenge,
-                        )
-                    });
-                    let sig_r_rlc = evm_word.map(|challenge| {
-                        rlc::value(
-                            sign_data.signature.0.to_bytes().iter().collect_vec(),
-                            challenge,
-                        )
-                    });
-                    let sig_s_rlc = evm_word.map(|challenge| {
-                        rlc::value(
-                            sign_data.signature.1.to_bytes().iter().collect_vec(),
-                            challenge,
-                        )
-                    });
+                    let msg_hash_rlc = word_rlc(sign_data.msg_hash.to_bytes());
+                    let sig_r_rlc = word_rlc(sign_data.signature.0.to_bytes());
+                    let sig_s_rlc = word_rlc(sign_data.signature.1.to_bytes());
                     let sig_v = Value::known(F::from(sign_data.signature.2 as u64));
                     let recovered_addr = Value::known(sign_data.get_addr().to_scalar().unwrap());
-                    region.assign_fixed(
-                        || format!("sig table q_enable {offset}"),
-                        self.q_enable,
-                        offset,
-                        || Value::known(F::one()),
-                    )?;
+                    self.assign_fixed_row(&mut region, offset, SigCurve::Secp256k1)?;
                     for (column_name, column, value) in [
                         ("msg_hash_rlc", self.msg_hash_rlc, msg_hash_rlc),
                         ("sig_v", self.sig_v, sig_v),
@@ -2592,6 +2630,62 @@ impl SigTable {
                             self.is_valid,
                             Value::known(F::from(!sign_data.get_addr().is_zero())),
                         ),
+                        ("pk_x_rlc", self.pk_x_rlc, Value::known(F::zero())),
+                        ("pk_y_rlc", self.pk_y_rlc, Value::known(F::zero())),
+                    ] {
+                        region.assign_advice(
+                            || format!("sig table {column_name} {offset}"),
+                            column,
+                            offset,
+                            || value,
+                        )?;
+                    }
+                }
+
+                for (offset, sign_data) in block
+                    .get_p256_sign_data()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, sign_data)| (signatures.len() + i, sign_data))
+                {
+                    self.assign_fixed_row(&mut region, offset, SigCurve::Secp256r1)?;
+                    for (column_name, column, value) in [
+                        (
+                            "msg_hash_rlc",
+                            self.msg_hash_rlc,
+                            word_rlc(sign_data.msg_hash.to_bytes()),
+                        ),
+                        ("sig_v", self.sig_v, Value::known(F::zero())),
+                        (
+                            "sig_r_rlc",
+                            self.sig_r_rlc,
+                            word_rlc(sign_data.signature.0.to_bytes()),
+                        ),
+                        (
+                            "sig_s_rlc",
+                            self.sig_s_rlc,
+                            word_rlc(sign_data.signature.1.to_bytes()),
+                        ),
+                        (
+                            "recovered_addr",
+                            self.recovered_addr,
+                            Value::known(F::zero()),
+                        ),
+                        (
+                            "is_valid",
+                            self.is_valid,
+                            Value::known(F::from(sign_data.verify())),
+                        ),
+                        (
+                            "pk_x_rlc",
+                            self.pk_x_rlc,
+                            word_rlc(sign_data.pk.x.to_bytes()),
+                        ),
+                        (
+                            "pk_y_rlc",
+                            self.pk_y_rlc,
+                            word_rlc(sign_data.pk.y.to_bytes()),
+                        ),
                     ] {
                         region.assign_advice(
                             || format!("sig table {column_name} {offset}"),
@@ -2608,30 +2702,59 @@ impl SigTable {
 
         Ok(())
     }
+
+    /// Assign the fixed columns of an enabled row.
+    pub(crate) fn assign_fixed_row<F: Field>(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: usize,
+        curve: SigCurve,
+    ) -> Result<(), Error> {
+        region.assign_fixed(
+            || format!("sig table q_enable {offset}"),
+            self.q_enable,
+            offset,
+            || Value::known(F::one()),
+        )?;
+        region.assign_fixed(
+            || format!("sig table curve {offset}"),
+            self.curve,
+            offset,
+            || Value::known(F::from(curve as u64)),
+        )?;
+
+        Ok(())
+    }
 }
 
 impl<F: Field> LookupTable<F> for SigTable {
     fn columns(&self) -> Vec<Column<Any>> {
         vec![
             self.q_enable.into(),
+            self.curve.into(),
             self.msg_hash_rlc.into(),
             self.sig_v.into(),
             self.sig_r_rlc.into(),
             self.sig_s_rlc.into(),
             self.recovered_addr.into(),
             self.is_valid.into(),
+            self.pk_x_rlc.into(),
+            self.pk_y_rlc.into(),
         ]
     }
 
     fn annotations(&self) -> Vec<String> {
         vec![
             String::from("q_enable"),
+            String::from("curve"),
             String::from("msg_hash_rlc"),
             String::from("sig_v"),
             String::from("sig_r_rlc"),
             String::from("sig_s_rlc"),
             String::from("recovered_addr"),
             String::from("is_valid"),
+            String::from("pk_x_rlc"),
+            String::from("pk_y_rlc"),
         ]
     }
 }
@@ -2722,17 +2845,20 @@ impl<F: Field> LookupTable<F> for EccTable {
 
 impl EccTable {
     /// Construct the ECC table.
-    pub(crate) fn construct<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
+    pub(crate) fn construct<F: Field>(
+        meta: &mut ConstraintSystem<F>,
+        phases: ChallengePhases,
+    ) -> Self {
         Self {
             op_type: meta.fixed_column(),
             is_valid: meta.advice_column(),
-            arg1_rlc: meta.advice_column_in(SecondPhase),
-            arg2_rlc: meta.advice_column_in(SecondPhase),
-            arg3_rlc: meta.advice_column_in(SecondPhase),
-            arg4_rlc: meta.advice_column_in(SecondPhase),
-            input_rlc: meta.advice_column_in(SecondPhase),
-            output1_rlc: meta.advice_column_in(SecondPhase),
-            output2_rlc: meta.advice_column_in(SecondPhase),
+            arg1_rlc: advice_column_in_phase2(meta, phases),
+            arg2_rlc: advice_column_in_phase2(meta, phases),
+            arg3_rlc: advice_column_in_phase2(meta, phases),
+            arg4_rlc: advice_column_in_phase2(meta, phases),
+            input_rlc: advice_column_in_phase2(meta, phases),
+            output1_rlc: advice_column_in_phase2(meta, phases),
+            output2_rlc: advice_column_in_phase2(meta, phases),
         }
     }
 
@@ -2851,82 +2977,59 @@ impl EccTable {
     }
 }
 
-/// Lookup table embedded in the modexp circuit for precompile.
+/// Lookup table embedded in the modexp circuit for precompile. All the columns are
+/// constant over the rows of a modexp call, and the EVM circuit looks up its head row.
 #[derive(Clone, Copy, Debug)]
 pub struct ModExpTable {
-    /// Use for indicate beginning of a limbs group
-    pub q_head: Column<Fixed>,
-    /// base represented by limbs
-    pub base: Column<Advice>,
-    /// exp represented by limbs
-    pub exp: Column<Advice>,
-    /// modulus represented by limbs
-    pub modulus: Column<Advice>,
-    /// result represented by limbs
-    pub result: Column<Advice>,
+    /// Whether the row is the first one of a modexp call
+    pub q_head: Column<Advice>,
+    /// RLC of the call input, right-padded with zeros to `MODEXP_INPUT_LIMIT` bytes
+    pub input_rlc: Column<Advice>,
+    /// RLC of the first 96 input bytes, which specify the lens of the operands
+    pub header_rlc: Column<Advice>,
+    /// Length of the base, 0 if the input is invalid, see `ModExpAuxData::check_input`
+    pub base_len: Column<Advice>,
+    /// Length of the exponent, 0 if the input is invalid, see `ModExpAuxData::check_input`
+    pub exp_len: Column<Advice>,
+    /// Length of the modulus, 0 if the input is invalid, see `ModExpAuxData::check_input`
+    pub modulus_len: Column<Advice>,
+    /// RLC of the first (up to) 32 bytes of the exponent
+    pub exp_head_rlc: Column<Advice>,
+    /// RLC of the result, in big-endian bytes of the modulus length
+    pub result_rlc: Column<Advice>,
 }
 
 impl ModExpTable {
     /// Construct the modexp table.
-    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
-        let ret = Self {
-            q_head: meta.fixed_column(),
-            base: meta.advice_column(),
-            exp: meta.advice_column(),
-            modulus: meta.advice_column(),
-            result: meta.advice_column(),
-        };
-        meta.enable_equality(ret.base);
-        meta.enable_equality(ret.exp);
-        meta.enable_equality(ret.modulus);
-        meta.enable_equality(ret.result);
-        ret
-    }
-
-    /// helper for devide a U256 into 3 108bit limbs
-    pub fn split_u256_108bit_limbs(word: &Word) -> [u128; 3] {
-        let bit108 = 1u128 << 108;
-        let (next, limb0) = word.div_mod(U256::from(bit108));
-        let (limb2, limb1) = next.div_mod(U256::from(bit108));
-        [limb0.as_u128(), limb1.as_u128(), limb2.as_u128()]
-    }
-
-    /// helper for obtain the modulus of a U256 in Fr
-    pub fn native_u256<F: Field>(word: &Word) -> F {
-        let minus1 = -F::one();
-        let div = Word::from_little_endian(minus1.to_repr().as_ref()) + Word::from(1u64);
-        let (_, remainder) = word.div_mod(div);
-
-        let mut bytes = [0u8; 64];
-        remainder.to_little_endian(&mut bytes[..32]);
-        F::from_uniform_bytes(&bytes)
+    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>, phases: ChallengePhases) -> Self {
+        Self {
+            q_head: meta.advice_column(),
+            input_rlc: advice_column_in_phase2(meta, phases),
+            header_rlc: advice_column_in_phase2(meta, phases),
+            base_len: meta.advice_column(),
+            exp_len: meta.advice_column(),
+            modulus_len: meta.advice_column(),
+            exp_head_rlc: advice_column_in_phase2(meta, phases),
+            result_rlc: advice_column_in_phase2(meta, phases),
+        }
     }
 
-    /// fill a blank 4-row region start from offset for empty lookup
-    pub fn fill_blank<F: Field>(&self, layouter: &mut impl Layouter<F>) -> Result<(), Error> {
-        layouter.assign_region(
-            || "modexp table blank region",
-            |mut region| {
-                for i in 0..4 {
-                    // fill last totally 0 row
-                    region.assign_fixed(
-                        || "modexp table blank head row",
-                        self.q_head,
-                        i,
-                        || Value::known(F::zero()),
-                    )?;
-                    for &col in [&self.base, &self.exp, &self.modulus, &self.result] {
-                        region.assign_advice(
-                            || "modexp table blank limb row",
-                            col,
-                            i,
-                            || Value::known(F::zero()),
-                        )?;
-                    }
-                }
-                Ok(())
-            },
-        )
+    /// Get the values of the table columns (except `q_head`) for a modexp call.
+    pub fn assignments<F: Field>(
+        event: &BigModExp,
+        challenges: &Challenges<Value<F>>,
+    ) -> [Value<F>; 7] {
+        let input = event.padded_input();
+        let keccak_input = challenges.keccak_input();
+        [
+            rlc_be_bytes(&input, keccak_input),
+            rlc_be_bytes(&input[..96], keccak_input),
+            Value::known(F::from(event.base.len() as u64)),
+            Value::known(F::from(event.exponent.len() as u64)),
+            Value::known(F::from(event.modulus.len() as u64)),
+            rlc_be_bytes(event.exponent_head(), keccak_input),
+            rlc_be_bytes(&event.result, keccak_input),
+        ]
     }
 
     /// Get assignments to the modexp table. Meant to be used for dev purposes.
@@ -2934,62 +3037,31 @@ impl ModExpTable {
         &self,
         layouter: &mut impl Layouter<F>,
         events: &[BigModExp],
+        challenges: &Challenges<Value<F>>,
     ) -> Result<(), Error> {
         layouter.assign_region(
             || "modexp table",
             |mut region| {
-                let mut offset = 0usize;
-
-                for event in events {
-                    for i in 0..4 {
-                        region.assign_fixed(
-                            || format!("modexp table head {}", offset + i),
-                            self.q_head,
-                            offset + i,
-                            || Value::known(if i == 0 { F::one() } else { F::zero() }),
-                        )?;
-                    }
-
-                    let base_limbs = Self::split_u256_108bit_limbs(&event.base);
-                    let exp_limbs = Self::split_u256_108bit_limbs(&event.exponent);
-                    let modulus_limbs = Self::split_u256_108bit_limbs(&event.modulus);
-                    let result_limbs = Self::split_u256_108bit_limbs(&event.result);
-
-                    for i in 0..3 {
-                        for (limbs, &col) in [base_limbs, exp_limbs, modulus_limbs, result_limbs]
-                            .iter()
-                            .zip([&self.base, &self.exp, &self.modulus, &self.result])
-                        {
-                            region.assign_advice(
-                                || format!("modexp table limb row {}", offset + i),
-                                col,
-                                offset + i,
-                                || Value::known(F::from_u128(limbs[i])),
-                            )?;
-                        }
-                    }
-
-                    // native is not used by lookup (and in fact it can be omitted in dev)
-                    for (word, &col) in
-                        [&event.base, &event.exponent, &event.modulus, &event.result]
-                            .iter()
-                            .zip([&self.base, &self.exp, &self.modulus, &self.result])
-                    {
+                let columns = <Self as LookupTable<F>>::advice_columns(self);
+                // the first row is all-zero, for the disabled lookups
+                let rows = once([Value::known(F::zero()); 8]).chain(events.iter().map(|event| {
+                    let mut row = [Value::known(F::one()); 8];
+                    row[1..].copy_from_slice(&Self::assignments(event, challenges));
+                    row
+                }));
+                for (offset, row) in rows.enumerate() {
+                    for (&column, value) in columns.iter().zip_eq(row) {
                         region.assign_advice(
-                            || format!("modexp table native row {}", offset + 3),
-                            col,
-                            offset + 3,
-                            || Value::<F>::known(Self::native_u256(word)),
+                            || format!("modexp table row {offset}"),
+                            column,
+                            offset,
+                            || value,
                         )?;
                     }
-
-                    offset += 4;
                 }
-
                 Ok(())
             },
-        )?;
-        self.fill_blank(layouter)
+        )
     }
 }
 
@@ -2997,41 +3069,26 @@ impl<F: Field> LookupTable<F> for ModExpTable {
     fn columns(&self) -> Vec<Column<Any>> {
         vec![
             self.q_head.into(),
-            self.base.into(),
-            self.exp.into(),
-            self.modulus.into(),
-            self.result.into(),
+            self.input_rlc.into(),
+            self.header_rlc.into(),
+            self.base_len.into(),
+            self.exp_len.into(),
+            self.modulus_len.into(),
+            self.exp_head_rlc.into(),
+            self.result_rlc.into(),
         ]
     }
 
     fn annotations(&self) -> Vec<String> {
         vec![
-            String::from("is_head"),
-            String::from("base"),
-            String::from("exp"),
-            String::from("modulus"),
-            String::from("result"),
-        ]
-    }
-
-    fn table_exprs(&self, meta: &mut VirtualCells<F>) -> Vec<Expression<F>> {
-        vec![
-            // ignore the is_valid field as the EVM circuit's use-case (Ecrecover precompile) does
-            // not care whether the signature is valid or not. It only cares about the recovered
-            // address.
-            meta.query_fixed(self.q_head, Rotation::cur()),
-            meta.query_advice(self.base, Rotation::cur()),
-            meta.query_advice(self.exp, Rotation::cur()),
-            meta.query_advice(self.modulus, Rotation::cur()),
-            meta.query_advice(self.result, Rotation::cur()),
-            meta.query_advice(self.base, Rotation::next()),
-            meta.query_advice(self.exp, Rotation::next()),
-            meta.query_advice(self.modulus, Rotation::next()),
-            meta.query_advice(self.result, Rotation::next()),
-            meta.query_advice(self.base, Rotation(2)),
-            meta.query_advice(self.exp, Rotation(2)),
-            meta.query_advice(self.modulus, Rotation(2)),
-            meta.query_advice(self.result, Rotation(2)),
+            String::from("q_head"),
+            String::from("input_rlc"),
+            String::from("header_rlc"),
+            String::from("base_len"),
+            String::from("exp_len"),
+            String::from("modulus_len"),
+            String::from("exp_head_rlc"),
+            String::from("result_rlc"),
         ]
     }
 }
@@ -3060,7 +3117,7 @@ impl PowOfRandTable {
             q_enable: meta.fixed_column(),
             is_first: meta.fixed_column(),
             exponent: meta.fixed_column(),
-            pow_of_rand: meta.advice_column_in(SecondPhase),
+            pow_of_rand: advice_column_in_phase2(meta, challenges.phases()),
         };
 
         meta.create_gate("pow_of_rand_table: first row", |meta| {
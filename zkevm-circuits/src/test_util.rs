@@ -1,5 +1,6 @@
 //! Testing utilities
 
+pub use crate::triage::{triage_failures, Triage};
 use crate::{
     copy_circuit::CopyCircuit,
     evm_circuit::{cached::EvmCircuitCached, EvmCircuit},
@@ -12,7 +13,7 @@ use eth_types::geth_types::GethData;
 
 use halo2_proofs::{
     circuit::Value,
-    dev::{unwrap_value, MockProver},
+    dev::{unwrap_value, MockProver, VerifyFailure},
     halo2curves::bn256::Fr,
 };
 use mock::TestContext;
@@ -31,9 +32,10 @@ fn init_env_logger() {
 /// Struct used to easily generate tests for EVM &| State circuits being able to
 /// customize all of the steps involved in the testing itself.
 ///
-/// By default, the tests run through `prover.assert_satisfied_par()` but the
-/// builder pattern provides functions that allow to pass different functions
-/// that the prover should execute when verifying the CTB correctness.
+/// By default, the tests verify the active rows of each circuit and panic with
+/// the [`Triage`] of the failures, but the builder pattern provides functions
+/// that allow to pass different functions that the prover should execute when
+/// verifying the CTB correctness.
 ///
 /// The CTB also includes a mechanism to receive calls that will modify the
 /// block produced from the [`TestContext`] and apply them before starting to
@@ -82,12 +84,62 @@ pub struct CircuitTestBuilder<const NACC: usize, const NTX: usize> {
     test_ctx: Option<TestContext<NACC, NTX>>,
     circuits_params: Option<CircuitsParams>,
     block: Option<Block>,
-    evm_checks: Option<Box<dyn Fn(MockProver<Fr>, &Vec<usize>, &Vec<usize>)>>,
-    state_checks: Option<Box<dyn Fn(MockProver<Fr>, &Vec<usize>, &Vec<usize>)>>,
-    copy_checks: Option<Box<dyn Fn(MockProver<Fr>, &Vec<usize>, &Vec<usize>)>>,
+    evm_checks: Checks,
+    state_checks: Checks,
+    copy_checks: Checks,
     block_modifiers: Vec<Box<dyn Fn(&mut Block)>>,
 }
 
+#[allow(clippy::type_complexity)]
+/// Checks run on the MockProver of a circuit by the [`CircuitTestBuilder`].
+enum Checks {
+    /// Verify the active rows and panic with the triage of the failures
+    Default,
+    /// Custom checks, given the prover and the active gate and lookup rows
+    Custom(Box<dyn Fn(MockProver<Fr>, &Vec<usize>, &Vec<usize>)>),
+    /// Don't run the circuit
+    Skip,
+}
+
+#[allow(clippy::type_complexity)]
+impl From<Option<Box<dyn Fn(MockProver<Fr>, &Vec<usize>, &Vec<usize>)>>> for Checks {
+    fn from(checks: Option<Box<dyn Fn(MockProver<Fr>, &Vec<usize>, &Vec<usize>)>>) -> Self {
+        checks.map_or(Self::Skip, Self::Custom)
+    }
+}
+
+impl Checks {
+    fn is_skipped(&self) -> bool {
+        matches!(self, Self::Skip)
+    }
+
+    #[allow(clippy::ptr_arg)]
+    fn run(
+        &self,
+        block: &Block,
+        prover: MockProver<Fr>,
+        gate_rows: &Vec<usize>,
+        lookup_rows: &Vec<usize>,
+    ) {
+        match self {
+            Self::Default => assert_verified(
+                block,
+                prover.verify_at_rows_par(gate_rows.iter().cloned(), lookup_rows.iter().cloned()),
+            ),
+            Self::Custom(checks) => checks(prover, gate_rows, lookup_rows),
+            Self::Skip => {}
+        }
+    }
+}
+
+/// Assert the MockProver verification `result` of a circuit built from
+/// `block` is ok, panicking with the [`Triage`] of the failures otherwise.
+pub fn assert_verified(block: &Block, result: Result<(), Vec<VerifyFailure>>) {
+    if let Err(failures) = result {
+        panic!("{}", triage_failures(block, &failures));
+    }
+}
+
 impl<const NACC: usize, const NTX: usize> CircuitTestBuilder<NACC, NTX> {
     /// Generates an empty/set to default `CircuitTestBuilder`.
     fn empty() -> Self {
@@ -95,24 +147,9 @@ impl<const NACC: usize, const NTX: usize> CircuitTestBuilder<NACC, NTX> {
             test_ctx: None,
             circuits_params: None,
             block: None,
-            evm_checks: Some(Box::new(|prover, gate_rows, lookup_rows| {
-                assert_eq!(prover.verify_at_rows_par(
-                    gate_rows.iter().cloned(),
-                    lookup_rows.iter().cloned(),
-                ), Ok(()));
-            })),
-            state_checks: Some(Box::new(|prover, gate_rows, lookup_rows| {
-                assert_eq!(prover.verify_at_rows_par(
-                    gate_rows.iter().cloned(),
-                    lookup_rows.iter().cloned(),
-                ), Ok(()));
-            })),
-            copy_checks: Some(Box::new(|prover, gate_rows, lookup_rows| {
-                assert_eq!(prover.verify_at_rows_par(
-                    gate_rows.iter().cloned(),
-                    lookup_rows.iter().cloned(),
-                ), Ok(()));
-            })),
+            evm_checks: Checks::Default,
+            state_checks: Checks::Default,
+            copy_checks: Checks::Default,
             block_modifiers: vec![],
         }
     }
@@ -160,7 +197,7 @@ impl<const NACC: usize, const NTX: usize> CircuitTestBuilder<NACC, NTX> {
         mut self,
         state_checks: Option<Box<dyn Fn(MockProver<Fr>, &Vec<usize>, &Vec<usize>)>>,
     ) -> Self {
-        self.state_checks = state_checks;
+        self.state_checks = state_checks.into();
         self
     }
 
@@ -171,7 +208,7 @@ impl<const NACC: usize, const NTX: usize> CircuitTestBuilder<NACC, NTX> {
         mut self,
         evm_checks: Option<Box<dyn Fn(MockProver<Fr>, &Vec<usize>, &Vec<usize>)>>,
     ) -> Self {
-        self.evm_checks = evm_checks;
+        self.evm_checks = evm_checks.into();
         self
     }
 
@@ -182,7 +219,7 @@ impl<const NACC: usize, const NTX: usize> CircuitTestBuilder<NACC, NTX> {
         mut self,
         copy_checks: Option<Box<dyn Fn(MockProver<Fr>, &Vec<usize>, &Vec<usize>)>>,
     ) -> Self {
-        self.copy_checks = copy_checks;
+        self.copy_checks = copy_checks.into();
         self
     }
 
@@ -257,7 +294,7 @@ impl<const NACC: usize, const NTX: usize> CircuitTestBuilder<NACC, NTX> {
 
         const NUM_BLINDING_ROWS: usize = 64;
         // Run evm circuit test
-        if let Some(evm_checks) = &self.evm_checks {
+        if !self.evm_checks.is_skipped() {
             let k = block.get_evm_test_circuit_degree();
             assert!(k <= 20);
             let (active_gate_rows, active_lookup_rows) = EvmCircuit::<Fr>::get_active_rows(&block);
@@ -265,11 +302,15 @@ impl<const NACC: usize, const NTX: usize> CircuitTestBuilder<NACC, NTX> {
             let circuit = EvmCircuitCached::get_test_cicuit_from_block(block.clone());
             let prover = MockProver::<Fr>::run(k, &circuit, vec![]).unwrap();
 
-            evm_checks(prover, &active_gate_rows, &active_lookup_rows)
+            self.evm_checks
+                .run(&block, prover, &active_gate_rows, &active_lookup_rows);
+
+            #[cfg(feature = "coverage")]
+            crate::evm_circuit::coverage::dump().expect("could not dump evm circuit coverage");
         }
 
         // Run state circuit test
-        if let Some(state_checks) = &self.state_checks {
+        if !self.state_checks.is_skipped() {
             let (_, rows_needed) = StateCircuit::<Fr>::min_num_rows_block(&block);
             let k: u32 = log2_ceil(rows_needed + NUM_BLINDING_ROWS);
             assert!(k <= 20);
@@ -284,11 +325,11 @@ impl<const NACC: usize, const NTX: usize> CircuitTestBuilder<NACC, NTX> {
                 .count();
             let rows = (rows_needed - non_start_rows_len..rows_needed).collect();
 
-            state_checks(prover, &rows, &rows);
+            self.state_checks.run(&block, prover, &rows, &rows);
         }
 
         // Run copy circuit test
-        if let Some(copy_checks) = &self.copy_checks {
+        if !self.copy_checks.is_skipped() {
             let (active_rows, max_rows) = CopyCircuit::<Fr>::min_num_rows_block(&block);
             let k1 = block.get_evm_test_circuit_degree();
             let k2 = log2_ceil(max_rows + NUM_BLINDING_ROWS);
@@ -298,7 +339,7 @@ impl<const NACC: usize, const NTX: usize> CircuitTestBuilder<NACC, NTX> {
             let prover = MockProver::<Fr>::run(k, &copy_circuit, instance).unwrap();
             let rows = (0..active_rows).collect();
 
-            copy_checks(prover, &rows, &rows);
+            self.copy_checks.run(&block, prover, &rows, &rows);
         }
     }
 }
@@ -0,0 +1,349 @@
+//! Triage of MockProver verification failures.
+//!
+//! The [`VerifyFailure`]s reported by the MockProver only refer to regions,
+//! row offsets and gate or lookup indices of the whole (possibly merged)
+//! circuit layout. [`triage_failures`] maps each failure back to the
+//! sub-circuit and region it happened in and, for the EVM circuit, to the
+//! execution step with its RW rows, then groups the failures by root cause.
+
+use crate::{
+    evm_circuit::{ExecutionState, LocatedStep, StepLayout, STEP_REGION_NAMES},
+    witness::Block,
+};
+use halo2_proofs::dev::{FailureLocation, VerifyFailure};
+use std::fmt::{self, Display};
+
+/// Maximum number of failure sites displayed per root cause
+const MAX_SITES_PER_CAUSE: usize = 8;
+
+/// Regions assigned by each sub-circuit, and by the tables they expose, by
+/// their name without the chunk suffix of parallel assigned regions.
+const SUB_CIRCUIT_REGIONS: &[(&str, &[&str])] = &[
+    (
+        "evm",
+        &[
+            STEP_REGION_NAMES[0],
+            STEP_REGION_NAMES[1],
+            STEP_REGION_NAMES[2],
+            "fixed table",
+            "byte table",
+        ],
+    ),
+    (
+        "state",
+        &[
+            "state circuit",
+            "state circuit (StateCircuitConfig)",
+            "state circuit (synthesize_sub) part1",
+            "state circuit (synthesize_sub) part2",
+            "state circuit (synthesize_sub) part3",
+            "assign call_context_field_tags fixed column",
+            "assign u8 fixed column",
+            "assign u10 fixed column",
+            "assign u16 fixed column",
+        ],
+    ),
+    ("tx", &["Tx ROM table", "tx table aux"]),
+    (
+        "bytecode",
+        &[
+            "assign bytecode",
+            "assign bytecode with poseidon hash extension",
+            "push table",
+        ],
+    ),
+    ("copy", &["assign copy table"]),
+    ("keccak", &["assign keccak rows"]),
+    ("exp", &["exponentiation circuit"]),
+    ("modexp", &["modexp circuit"]),
+    ("ecc", &["ecc circuit", "expose ecc table"]),
+    ("sig", &["ecdsa chip verification", "expose sig table"]),
+    ("pi", &["pi region", "pi connecting region"]),
+    (
+        "rlp",
+        &["RLP ROM table", "RLP data table region", "RLP sm region"],
+    ),
+    (
+        "sha256",
+        &[
+            "initialize hasher",
+            "sha256 input",
+            "sha256 digest",
+            "sha256 state initialized bind",
+            "sha256 state initialized by iv bind",
+        ],
+    ),
+    (
+        "tables",
+        &[
+            "tx table",
+            "rw table",
+            "mpt table zkevm",
+            "bytecode table",
+            "block table",
+            "copy table",
+            "exponentiation table",
+            "keccak table",
+            "modexp table",
+            "poseidon table",
+            "poseidon codehash table",
+            "power of randomness table",
+            "Pow2 table",
+            "BitwiseOp table",
+        ],
+    ),
+];
+
+/// Location of a single verification failure.
+#[derive(Debug)]
+pub struct FailureSite<'a> {
+    /// Region name, if the failure happened inside a region
+    pub region: Option<String>,
+    /// Row offset inside the region, or absolute row outside of any region
+    pub offset: Option<usize>,
+    /// Execution step, for failures in the EVM circuit
+    pub step: Option<LocatedStep<'a>>,
+}
+
+/// Verification failures sharing the same root cause.
+#[derive(Debug)]
+pub struct FailureGroup<'a> {
+    /// Sub-circuit the failures belong to
+    pub circuit: &'static str,
+    /// Failing constraint, lookup, permutation or unassigned cell
+    pub cause: String,
+    /// Execution state of the failing steps, for failures in the EVM circuit
+    pub execution_state: Option<ExecutionState>,
+    /// One of the failures, with the cell values reported by the MockProver
+    pub example: String,
+    /// Locations of all the failures
+    pub sites: Vec<FailureSite<'a>>,
+}
+
+/// Verification failures grouped by root cause, displayed with the relevant
+/// RW rows of the witness block.
+#[derive(Debug)]
+pub struct Triage<'a> {
+    block: &'a Block,
+    /// Groups of failures, in the order of their first occurrence
+    pub groups: Vec<FailureGroup<'a>>,
+}
+
+/// Map the `failures` reported by the MockProver of a circuit (or of the super
+/// circuit) built from `block` back to sub-circuits and execution steps.
+pub fn triage_failures<'a>(block: &'a Block, failures: &[VerifyFailure]) -> Triage<'a> {
+    let layout = StepLayout::new(block);
+    let mut groups: Vec<FailureGroup<'a>> = Vec::new();
+
+    for failure in failures {
+        let (cause, location) = match failure {
+            VerifyFailure::ConstraintNotSatisfied {
+                constraint,
+                location,
+                ..
+            } => (format!("constraint {constraint}"), Some(location)),
+            VerifyFailure::ConstraintPoisoned { constraint } => {
+                (format!("poisoned constraint {constraint}"), None)
+            }
+            VerifyFailure::Lookup {
+                name,
+                lookup_index,
+                location,
+            } => (format!("lookup {lookup_index} ('{name}')"), Some(location)),
+            VerifyFailure::Permutation { column, location } => {
+                (format!("permutation of {column}"), Some(location))
+            }
+            VerifyFailure::CellNotAssigned {
+                gate,
+                region,
+                gate_offset,
+                column,
+                offset,
+            } => {
+                let name = region_name(region);
+                let row = gate_offset.checked_add_signed(*offset);
+                let site = FailureSite {
+                    step: row.and_then(|row| locate_step(&layout, &name, row)),
+                    region: Some(name),
+                    offset: row,
+                };
+                push_site(
+                    &mut groups,
+                    format!("unassigned cell in {column:?} queried by {gate}"),
+                    failure,
+                    site,
+                );
+                continue;
+            }
+            _ => (format!("{failure}"), None),
+        };
+        let site = match location {
+            Some(FailureLocation::InRegion { region, offset }) => {
+                let name = region_name(region);
+                FailureSite {
+                    step: locate_step(&layout, &name, *offset),
+                    region: Some(name),
+                    offset: Some(*offset),
+                }
+            }
+            Some(FailureLocation::OutsideRegion { row }) => FailureSite {
+                region: None,
+                offset: Some(*row),
+                step: None,
+            },
+            None => FailureSite {
+                region: None,
+                offset: None,
+                step: None,
+            },
+        };
+        push_site(&mut groups, cause, failure, site);
+    }
+
+    Triage { block, groups }
+}
+
+fn push_site<'a>(
+    groups: &mut Vec<FailureGroup<'a>>,
+    cause: String,
+    failure: &VerifyFailure,
+    site: FailureSite<'a>,
+) {
+    let circuit = site
+        .region
+        .as_deref()
+        .and_then(region_circuit)
+        .unwrap_or("unknown");
+    let execution_state = site.step.as_ref().map(|step| step.step.execution_state);
+    match groups.iter_mut().find(|group| {
+        group.circuit == circuit && group.cause == cause && group.execution_state == execution_state
+    }) {
+        Some(group) => group.sites.push(site),
+        None => groups.push(FailureGroup {
+            circuit,
+            cause,
+            execution_state,
+            example: failure.to_string(),
+            sites: vec![site],
+        }),
+    }
+}
+
+/// Name of a region from its display format `Region {index} ('{name}')`, as
+/// the fields of `halo2_proofs::dev::metadata::Region` aren't public.
+fn region_name(region: impl Display) -> String {
+    let region = region.to_string();
+    match region.split_once("('") {
+        Some((_, name)) => name.strip_suffix("')").unwrap_or(name).to_string(),
+        None => region,
+    }
+}
+
+/// Splits the name of a region assigned in parallel, as `{name}_{chunk}`, into
+/// the name and the chunk index.
+fn split_chunk(name: &str) -> (&str, usize) {
+    name.rsplit_once('_')
+        .and_then(|(name, chunk)| Some((name, chunk.parse().ok()?)))
+        .unwrap_or((name, 0))
+}
+
+fn region_circuit(name: &str) -> Option<&'static str> {
+    let (name, _) = split_chunk(name);
+    SUB_CIRCUIT_REGIONS
+        .iter()
+        .find(|(_, regions)| regions.contains(&name))
+        .map(|(circuit, _)| *circuit)
+}
+
+/// Locate the execution step at `offset` of the region `name`.
+fn locate_step<'a>(layout: &StepLayout<'a>, name: &str, offset: usize) -> Option<LocatedStep<'a>> {
+    let (name, chunk) = split_chunk(name);
+    let region_idx = STEP_REGION_NAMES
+        .iter()
+        .position(|region| *region == name)?;
+    layout
+        .row(region_idx, chunk, offset)
+        .and_then(|row| layout.step_at(row))
+}
+
+impl Display for FailureSite<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.region, self.offset) {
+            (Some(region), Some(offset)) => write!(f, "region '{region}' offset {offset}")?,
+            (Some(region), None) => write!(f, "region '{region}'")?,
+            (None, Some(row)) => write!(f, "row {row} outside any region")?,
+            (None, None) => write!(f, "no location")?,
+        }
+        if let Some(step) = &self.step {
+            match step.tx_step {
+                Some((tx_idx, step_idx)) => write!(f, ", tx {tx_idx} step {step_idx}")?,
+                None => write!(f, ", {:?} step", step.step.execution_state)?,
+            }
+            if let Some(opcode) = step.step.opcode {
+                write!(f, " {opcode:?}")?;
+            }
+            write!(
+                f,
+                " (pc {}, rw_counter {}), row {} of the step",
+                step.step.program_counter, step.step.rw_counter, step.row_in_step
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Triage<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let num_failures: usize = self.groups.iter().map(|group| group.sites.len()).sum();
+        writeln!(
+            f,
+            "{num_failures} verification failures with {} root causes",
+            self.groups.len()
+        )?;
+        for (idx, group) in self.groups.iter().enumerate() {
+            write!(f, "\n#{idx} [{}] {}", group.circuit, group.cause)?;
+            if let Some(execution_state) = group.execution_state {
+                write!(f, " in {execution_state:?}")?;
+            }
+            writeln!(f, ": {} failures", group.sites.len())?;
+            for site in group.sites.iter().take(MAX_SITES_PER_CAUSE) {
+                writeln!(f, "  at {site}")?;
+            }
+            if group.sites.len() > MAX_SITES_PER_CAUSE {
+                writeln!(
+                    f,
+                    "  ... and {} more",
+                    group.sites.len() - MAX_SITES_PER_CAUSE
+                )?;
+            }
+            writeln!(f, "  example: {}", group.example.trim_end())?;
+            if let Some(step) = group.sites.iter().find_map(|site| site.step.as_ref()) {
+                writeln!(f, "  rw rows of the first failing step:")?;
+                for &(tag, idx) in &step.step.rw_indices {
+                    writeln!(f, "    {tag:?}[{idx}]: {:?}", self.block.rws[(tag, idx)])?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn region_names() {
+        let name = region_name("Region 3 ('Execution step region1_2')");
+        assert_eq!(name, "Execution step region1_2");
+        assert_eq!(region_circuit(&name), Some("evm"));
+        assert_eq!(split_chunk(&name), ("Execution step region1", 2));
+        assert_eq!(
+            region_circuit("state circuit (synthesize_sub) part2"),
+            Some("state")
+        );
+        assert_eq!(region_circuit("mpt table zkevm_3"), Some("tables"));
+        // names are matched exactly, not by prefix
+        assert_eq!(region_circuit("state circuit part4"), None);
+    }
+}
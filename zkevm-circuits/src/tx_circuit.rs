@@ -17,8 +17,8 @@ use crate::{
     // sig_circuit::SigCircuit,
     table::{
         BlockContextFieldTag::{CumNumTxs, NumAllTxs, NumTxs},
-        BlockTable, KeccakTable, LookupTable, PowOfRandTable, RlpFsmRlpTable as RlpTable, SigTable,
-        TxFieldTag,
+        BlockTable, KeccakTable, LookupTable, PowOfRandTable, RlpFsmRlpTable as RlpTable, SigCurve,
+        SigTable, TxFieldTag,
         TxFieldTag::{
             AccessListAddressesLen, AccessListRLC, AccessListStorageKeysLen, BlockNumber, CallData,
             CallDataGasCost, CallDataLength, CallDataRLC, CalleeAddress, CallerAddress, ChainID,
@@ -28,6 +28,7 @@ use crate::{
         TxTable, U16Table, U8Table,
     },
     util::{
+        advice_column_in_phase2,
         is_zero::{IsZeroChip, IsZeroConfig},
         keccak, rlc_be_bytes, SubCircuit, SubCircuitConfig,
     },
@@ -77,10 +78,6 @@ use std::{
 };
 
 use crate::{util::Challenges, witness::rlp_fsm::get_rlp_len_tag_length};
-#[cfg(feature = "onephase")]
-use halo2_proofs::plonk::FirstPhase as SecondPhase;
-#[cfg(not(feature = "onephase"))]
-use halo2_proofs::plonk::SecondPhase;
 use halo2_proofs::plonk::{Any, Fixed};
 use itertools::Itertools;
 
@@ -514,7 +511,7 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
         // tag, rlp_tag, tx_type, is_none
         let tx_type = meta.advice_column();
         let rlp_tag = meta.advice_column();
-        let tx_value_rlc = meta.advice_column_in(SecondPhase);
+        let tx_value_rlc = advice_column_in_phase2(meta, challenges.phases());
         let tx_value_length = meta.advice_column();
         let is_none = meta.advice_column();
         let tag_bits = BinaryNumberChip::configure(meta, q_enable, Some(tx_table.tag.into()));
@@ -529,7 +526,7 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
         // columns for accumulating length and gas_cost of call_data
         let is_final = meta.advice_column();
         let calldata_gas_cost_acc = meta.advice_column();
-        let section_rlc = meta.advice_column_in(SecondPhase);
+        let section_rlc = advice_column_in_phase2(meta, challenges.phases());
         let calldata_byte = meta.advice_column();
 
         // booleans to reduce degree
@@ -561,14 +558,14 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
         let is_access_list = meta.advice_column();
         let is_access_list_address = meta.advice_column();
         let is_access_list_storage_key = meta.advice_column();
-        let field_rlc = meta.advice_column_in(SecondPhase);
+        let field_rlc = advice_column_in_phase2(meta, challenges.phases());
 
         // Chunk bytes accumulator
         let is_chunk_bytes = meta.advice_column();
         let chunk_bytes_len = meta.advice_column();
-        let chunk_txbytes_rlc = meta.advice_column_in(SecondPhase);
+        let chunk_txbytes_rlc = advice_column_in_phase2(meta, challenges.phases());
         let chunk_txbytes_len_acc = meta.advice_column();
-        let pow_of_rand = meta.advice_column_in(SecondPhase);
+        let pow_of_rand = advice_column_in_phase2(meta, challenges.phases());
 
         meta.enable_equality(chunk_bytes_len);
         meta.enable_equality(chunk_txbytes_rlc);
@@ -656,7 +653,7 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
                 ])
             },
             tx_table.value,
-            |meta| meta.advice_column_in(SecondPhase), // value is at 2nd phase
+            |meta| advice_column_in_phase2(meta, challenges.phases()), // value is at 2nd phase
         );
 
         // tx_id transition in the fixed part of tx table
@@ -2909,11 +2906,12 @@ impl<F: Field> TxCircuitConfig<F> {
                 + meta.query_advice(is_eip2930, Rotation::cur()) * sig_v.expr();
 
             let input_exprs = vec![
-                1.expr(),     // q_enable = true
-                msg_hash_rlc, // msg_hash_rlc
-                v,            // sig_v
-                sig_r,        // sig_r
-                sig_s,        // sig_s
+                1.expr(),                   // q_enable = true
+                SigCurve::Secp256k1.expr(), // curve
+                msg_hash_rlc,               // msg_hash_rlc
+                v,                          // sig_v
+                sig_r,                      // sig_r
+                sig_s,                      // sig_s
                 sv_address,
                 1.expr(), // is_valid
             ];
@@ -2921,6 +2919,7 @@ impl<F: Field> TxCircuitConfig<F> {
             // LookupTable::table_exprs is not used here since `is_valid` not used by evm circuit.
             let table_exprs = vec![
                 meta.query_fixed(sig_table.q_enable, Rotation::cur()),
+                meta.query_fixed(sig_table.curve, Rotation::cur()),
                 // msg_hash_rlc not needed to be looked up for tx circuit?
                 meta.query_advice(sig_table.msg_hash_rlc, Rotation::cur()),
                 meta.query_advice(sig_table.sig_v, Rotation::cur()),
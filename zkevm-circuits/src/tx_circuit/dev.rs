@@ -121,6 +121,8 @@ impl<F: Field> TxCircuitTester<F> {
             sig_circuit: SigCircuit {
                 max_verif: max_txs,
                 signatures: get_sign_data(&txs, max_txs, chain_id as usize).unwrap(),
+                max_p256_verif: 0,
+                p256_signatures: vec![],
                 _marker: PhantomData,
             },
             tx_circuit: TxCircuit::new(max_txs, max_calldata, chain_id, start_l1_queue_index, txs),
@@ -167,14 +169,14 @@ impl<F: Field> Circuit<F> for TxCircuitTester<F> {
     }
 
     fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
-        let block_table = BlockTable::construct(meta);
-        let tx_table = TxTable::construct(meta);
-        let keccak_table = KeccakTable::construct(meta);
+        let challenges = Challenges::construct(meta);
+        let block_table = BlockTable::construct(meta, challenges.phases());
+        let tx_table = TxTable::construct(meta, challenges.phases());
+        let keccak_table = KeccakTable::construct(meta, challenges.phases());
         let rlp_table = RlpTable::construct(meta);
-        let sig_table = SigTable::construct(meta);
+        let sig_table = SigTable::construct(meta, challenges.phases());
         let u8_table = U8Table::construct(meta);
         let u16_table = U16Table::construct(meta);
-        let challenges = Challenges::construct(meta);
 
         let challenges_expr = challenges.exprs(meta);
         let pow_of_rand_table = PowOfRandTable::construct(meta, &challenges_expr);
@@ -179,6 +179,8 @@ fn run<F: Field>(
         sig_circuit: SigCircuit {
             max_verif: max_txs,
             signatures: get_sign_data(&txs, max_txs, chain_id as usize).unwrap(),
+            max_p256_verif: 0,
+            p256_signatures: vec![],
             _marker: PhantomData,
         },
         tx_circuit: TxCircuit::new(max_txs, max_calldata, chain_id, start_l1_queue_index, txs),
@@ -4,13 +4,11 @@ use std::collections::BTreeSet;
 use bus_mapping::evm::OpcodeId;
 use halo2_proofs::{
     circuit::{Layouter, Value},
-    plonk::{Challenge, Circuit, ConstraintSystem, Error, Expression, FirstPhase, VirtualCells},
+    plonk::{
+        Advice, Challenge, Circuit, Column, ConstraintSystem, Error, Expression, FirstPhase,
+        SecondPhase, ThirdPhase, VirtualCells,
+    },
 };
-
-#[cfg(feature = "onephase")]
-use halo2_proofs::plonk::FirstPhase as SecondPhase;
-#[cfg(not(feature = "onephase"))]
-use halo2_proofs::plonk::SecondPhase;
 use sha3::Digest;
 
 use crate::{evm_circuit::util::rlc, table::TxLogFieldTag, witness};
@@ -48,14 +46,118 @@ pub(crate) fn rlc_be_bytes<F: Field>(bytes: &[u8], rand: Value<F>) -> Value<F> {
     })
 }
 
+/// How the challenges are allocated when a circuit is configured.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChallengePhases {
+    /// The challenges are squeezed from the transcript, and the columns depending on them are
+    /// allocated in the second and third phases.
+    #[default]
+    Multi,
+    /// The challenges are constants and every column is allocated in the first phase. Proving is
+    /// faster but the RLCs are not binding: debug only.
+    One,
+}
+
+/// Allocate an advice column which depends on the `evm_word` or `keccak_input` challenges: in the
+/// second phase, or in the first one with [`ChallengePhases::One`].
+pub fn advice_column_in_phase2<F: Field>(
+    meta: &mut ConstraintSystem<F>,
+    phases: ChallengePhases,
+) -> Column<Advice> {
+    match phases {
+        ChallengePhases::Multi => meta.advice_column_in(SecondPhase),
+        ChallengePhases::One => meta.advice_column_in(FirstPhase),
+    }
+}
+
+/// Allocate an advice column which depends on the `lookup_input` challenge: in the third phase,
+/// or in the first one with [`ChallengePhases::One`].
+pub fn advice_column_in_phase3<F: Field>(
+    meta: &mut ConstraintSystem<F>,
+    phases: ChallengePhases,
+) -> Column<Advice> {
+    match phases {
+        ChallengePhases::Multi => meta.advice_column_in(ThirdPhase),
+        ChallengePhases::One => meta.advice_column_in(FirstPhase),
+    }
+}
+
+/// A circuit which can be configured with either [`ChallengePhases`]. Its
+/// [`Circuit::configure`] uses [`ChallengePhases::Multi`].
+pub trait PhasedCircuit<F: Field>: Circuit<F> {
+    /// Configure the circuit with the challenges allocated according to `phases`.
+    fn configure_with_phases(
+        meta: &mut ConstraintSystem<F>,
+        phases: ChallengePhases,
+    ) -> Self::Config;
+}
+
+/// The circuit `C` configured with [`ChallengePhases::One`]: debug only.
+#[derive(Clone, Debug, Default)]
+pub struct OnePhase<C>(pub C);
+
+impl<F: Field, C: PhasedCircuit<F>> Circuit<F> for OnePhase<C> {
+    type Config = C::Config;
+    type FloorPlanner = C::FloorPlanner;
+    #[cfg(feature = "circuit-params")]
+    type Params = ();
+
+    fn without_witnesses(&self) -> Self {
+        Self(self.0.without_witnesses())
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        C::configure_with_phases(meta, ChallengePhases::One)
+    }
+
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<F>) -> Result<(), Error> {
+        self.0.synthesize(config, layouter)
+    }
+}
+
+/// A challenge as allocated by [`Challenges::construct`].
+#[derive(Clone, Copy, Debug)]
+pub enum PhasedChallenge {
+    /// Squeezed from the transcript, with [`ChallengePhases::Multi`].
+    Squeezed(Challenge),
+    /// A constant, with [`ChallengePhases::One`].
+    Constant(u64),
+}
+
+impl Default for PhasedChallenge {
+    fn default() -> Self {
+        Self::Squeezed(Challenge::default())
+    }
+}
+
+impl PhasedChallenge {
+    fn expr<F: Field>(self, meta: &mut VirtualCells<F>) -> Expression<F> {
+        match self {
+            Self::Squeezed(challenge) => meta.query_challenge(challenge),
+            Self::Constant(value) => Expression::Constant(F::from(value)),
+        }
+    }
+
+    fn value<F: Field>(self, layouter: &impl Layouter<F>) -> Value<F> {
+        match self {
+            Self::Squeezed(challenge) => layouter.get_challenge(challenge),
+            Self::Constant(value) => Value::known(F::from(value)),
+        }
+    }
+}
+
 /// Wrap multiple challenges:
 /// `construct`: the default consturct route to provide all challenges used in `SuperCircuit`.
 /// `construct_p1`: construct challenge up to second phase
+///
+/// The challenges are allocated according to their [`ChallengePhases`], which the configs of the
+/// sub-circuits follow when allocating the columns depending on them.
 #[derive(Default, Clone, Copy, Debug)]
-pub struct Challenges<T = Challenge> {
+pub struct Challenges<T = PhasedChallenge> {
     evm_word: T,
     keccak_input: T,
     lookup_input: Option<T>,
+    phases: ChallengePhases,
 }
 
 /// ..
@@ -88,6 +190,7 @@ impl MockChallenges {
             evm_word: Expression::Constant(F::from(self.evm_word)),
             keccak_input: Expression::Constant(F::from(self.keccak_input)),
             lookup_input: self.lookup_input.map(|c| Expression::Constant(F::from(c))),
+            phases: ChallengePhases::Multi,
         }
     }
     /// ..
@@ -96,6 +199,7 @@ impl MockChallenges {
             evm_word: Value::known(F::from(self.evm_word)),
             keccak_input: Value::known(F::from(self.keccak_input)),
             lookup_input: self.lookup_input.map(|c| Value::known(F::from(c))),
+            phases: ChallengePhases::Multi,
         }
     }
 }
@@ -103,61 +207,104 @@ impl MockChallenges {
 impl Challenges {
     /// Construct `Challenges` by allocating challenges only to secondary phases.
     pub fn construct_p1<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
-        #[cfg(any(not(feature = "onephase"), feature = "test", test))]
-        let _dummy_cols = [
-            meta.advice_column(),
-            meta.advice_column_in(halo2_proofs::plonk::SecondPhase),
-        ];
+        Self::construct_p1_with_phases(meta, ChallengePhases::Multi)
+    }
 
-        Self {
-            evm_word: meta.challenge_usable_after(FirstPhase),
-            keccak_input: meta.challenge_usable_after(FirstPhase),
-            lookup_input: None,
+    /// Construct `Challenges` by allocating challenges only to secondary phases, or none with
+    /// [`ChallengePhases::One`].
+    pub fn construct_p1_with_phases<F: Field>(
+        meta: &mut ConstraintSystem<F>,
+        phases: ChallengePhases,
+    ) -> Self {
+        if phases == ChallengePhases::Multi || cfg!(any(feature = "test", test)) {
+            let _dummy_cols = [meta.advice_column(), meta.advice_column_in(SecondPhase)];
+        }
+
+        match phases {
+            ChallengePhases::Multi => Self {
+                evm_word: PhasedChallenge::Squeezed(meta.challenge_usable_after(FirstPhase)),
+                keccak_input: PhasedChallenge::Squeezed(meta.challenge_usable_after(FirstPhase)),
+                lookup_input: None,
+                phases,
+            },
+            ChallengePhases::One => Self::from_mock(MockChallenges::construct_p1(meta)),
         }
     }
 
     /// Construct `Challenges` by allocating challenges in specific phases.
     pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
-        #[cfg(any(not(feature = "onephase"), feature = "test", test))]
-        let _dummy_cols = [
-            meta.advice_column(),
-            meta.advice_column_in(halo2_proofs::plonk::SecondPhase),
-            meta.advice_column_in(halo2_proofs::plonk::ThirdPhase),
-        ];
+        Self::construct_with_phases(meta, ChallengePhases::Multi)
+    }
+
+    /// Construct `Challenges` by allocating challenges in specific phases, or none with
+    /// [`ChallengePhases::One`].
+    pub fn construct_with_phases<F: Field>(
+        meta: &mut ConstraintSystem<F>,
+        phases: ChallengePhases,
+    ) -> Self {
+        if phases == ChallengePhases::Multi || cfg!(any(feature = "test", test)) {
+            let _dummy_cols = [
+                meta.advice_column(),
+                meta.advice_column_in(SecondPhase),
+                meta.advice_column_in(ThirdPhase),
+            ];
+        }
+
+        match phases {
+            ChallengePhases::Multi => Self {
+                evm_word: PhasedChallenge::Squeezed(meta.challenge_usable_after(FirstPhase)),
+                keccak_input: PhasedChallenge::Squeezed(meta.challenge_usable_after(FirstPhase)),
+                lookup_input: Some(PhasedChallenge::Squeezed(
+                    meta.challenge_usable_after(SecondPhase),
+                )),
+                phases,
+            },
+            ChallengePhases::One => Self::from_mock(MockChallenges::construct(meta)),
+        }
+    }
 
+    fn from_mock(mock: MockChallenges) -> Self {
         Self {
-            evm_word: meta.challenge_usable_after(FirstPhase),
-            keccak_input: meta.challenge_usable_after(FirstPhase),
-            lookup_input: Some(meta.challenge_usable_after(SecondPhase)),
+            evm_word: PhasedChallenge::Constant(mock.evm_word),
+            keccak_input: PhasedChallenge::Constant(mock.keccak_input),
+            lookup_input: mock.lookup_input.map(PhasedChallenge::Constant),
+            phases: ChallengePhases::One,
         }
     }
 
     /// Returns `Expression` of challenges from `ConstraintSystem`.
     pub fn exprs<F: Field>(&self, meta: &mut ConstraintSystem<F>) -> Challenges<Expression<F>> {
         let [evm_word, keccak_input] = query_expression(meta, |meta| {
-            [self.evm_word, self.keccak_input].map(|challenge| meta.query_challenge(challenge))
+            [self.evm_word, self.keccak_input].map(|challenge| challenge.expr(meta))
         });
         let lookup_input = self
             .lookup_input
-            .map(|c| query_expression(meta, |meta| meta.query_challenge(c)));
+            .map(|c| query_expression(meta, |meta| c.expr(meta)));
         Challenges {
             evm_word,
             keccak_input,
             lookup_input,
+            phases: self.phases,
         }
     }
 
     /// Returns `Value` of challenges from `Layouter`.
     pub fn values<F: Field>(&self, layouter: &impl Layouter<F>) -> Challenges<Value<F>> {
         Challenges {
-            evm_word: layouter.get_challenge(self.evm_word),
-            keccak_input: layouter.get_challenge(self.keccak_input),
-            lookup_input: self.lookup_input.map(|c| layouter.get_challenge(c)),
+            evm_word: self.evm_word.value(layouter),
+            keccak_input: self.keccak_input.value(layouter),
+            lookup_input: self.lookup_input.map(|c| c.value(layouter)),
+            phases: self.phases,
         }
     }
 }
 
 impl<T: Clone> Challenges<T> {
+    /// Returns how the challenges are allocated.
+    pub fn phases(&self) -> ChallengePhases {
+        self.phases
+    }
+
     /// Returns challenge of `evm_word`.
     pub fn evm_word(&self) -> T {
         self.evm_word.clone()
@@ -193,6 +340,7 @@ impl<T: Clone> Challenges<T> {
             evm_word,
             keccak_input,
             lookup_input: Some(lookup_input),
+            phases: ChallengePhases::Multi,
         }
     }
 }
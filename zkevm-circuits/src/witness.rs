@@ -11,11 +11,16 @@ pub mod keccak;
 mod bytecode;
 pub use bytecode::Bytecode;
 
+mod dump;
+pub use dump::WITNESS_DUMP_VERSION;
+
 mod call;
 pub use call::Call;
 
 mod mpt;
-pub use mpt::{MptUpdate, MptUpdateRow, MptUpdates, WithdrawProof};
+pub use mpt::{
+    MptUpdate, MptUpdateRow, MptUpdates, TriePathModel, WithdrawProof, INSERTION_EXTRA_DEPTH,
+};
 
 mod receipt;
 pub use receipt::Receipt;
@@ -1,5 +1,6 @@
 use ethers_core::types::Signature;
 use gadgets::ToScalar;
+use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 
 #[cfg(any(feature = "test", test))]
@@ -18,20 +19,27 @@ use bus_mapping::{
     },
     Error,
 };
-use eth_types::{sign_types::SignData, Address, ToBigEndian, ToLittleEndian, Word, H256, U256};
+use eth_types::{
+    sign_types::{P256SignData, SignData},
+    Address, ToBigEndian, ToLittleEndian, Word, H256, U256,
+};
 use halo2_proofs::{circuit::Value, halo2curves::bn256::Fr};
 use itertools::Itertools;
 
 use super::{
-    mpt::ZktrieState as MptState, step::step_convert, tx::tx_convert, Bytecode, ExecStep,
-    MptUpdates, RwMap, Transaction,
+    mpt::ZktrieState as MptState,
+    step::step_convert,
+    tx::{leading_steps_convert, tx_convert},
+    Bytecode, ExecStep, MptUpdates, RwMap, Transaction,
 };
 use crate::util::Challenges;
 
 /// Block is the struct used by all circuits, which contains all the needed
 /// data for witness generation.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Block {
+    /// SystemCall and EndInnerBlock steps before the first transaction
+    pub leading_steps: Vec<ExecStep>,
     /// Transactions in the block
     pub txs: Vec<Transaction>,
     /// Signatures in the block
@@ -76,13 +84,27 @@ pub struct Block {
 }
 
 /// ...
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct BlockContexts {
     /// Hashmap that maps block number to its block context.
     pub ctxs: BTreeMap<u64, BlockContext>,
 }
 
+impl BlockContexts {
+    /// The hashes of the blocks, in block order.
+    pub fn block_hashes(&self) -> Vec<H256> {
+        self.ctxs.values().map(|ctx| ctx.hash).collect()
+    }
+}
+
 impl Block {
+    /// The execution steps before padding: the leading steps, then the steps of each tx.
+    pub fn steps(&self) -> impl Iterator<Item = &ExecStep> {
+        self.leading_steps
+            .iter()
+            .chain(self.txs.iter().flat_map(|tx| tx.steps.iter()))
+    }
+
     /// First block number
     pub fn first_block_number(&self) -> U256 {
         self.context
@@ -151,6 +173,11 @@ impl Block {
         signatures
     }
 
+    /// Get P256Verify signatures from all precompiled contract calls in this block.
+    pub(crate) fn get_p256_sign_data(&self) -> Vec<P256SignData> {
+        self.precompile_events.get_p256_verify_events()
+    }
+
     /// Get EcAdd operations from all precompiled contract calls in this block.
     pub(crate) fn get_ec_add_ops(&self) -> Vec<EcAddOp> {
         self.precompile_events.get_ec_add_events()
@@ -180,13 +207,11 @@ impl Block {
         let mut num_rows = 0;
         let mut counter = HashMap::new();
         let mut step_num = 0;
-        for transaction in &self.txs {
-            step_num += transaction.steps.len();
-            for step in &transaction.steps {
-                let height = step.execution_state.get_step_height();
-                num_rows += height;
-                *counter.entry(step.execution_state).or_insert(0) += height;
-            }
+        for step in self.steps() {
+            step_num += 1;
+            let height = step.execution_state.get_step_height();
+            num_rows += height;
+            *counter.entry(step.execution_state).or_insert(0) += height;
         }
         // TODO: change to log::trace?
         let mut counter_vec: Vec<_> = counter.into_iter().collect();
@@ -341,7 +366,7 @@ impl Block {
 }
 
 /// Block context for execution
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockContext {
     /// The address of the miner for the block
     pub coinbase: Address,
@@ -363,6 +388,10 @@ pub struct BlockContext {
     pub parent_hash: H256,
     /// State root of this block
     pub state_root: H256,
+    /// Hash of this block, as reported by the node
+    pub hash: H256,
+    /// Parent beacon block root, since EIP-4788
+    pub parent_beacon_block_root: Option<H256>,
 }
 
 impl BlockContext {
@@ -431,10 +460,32 @@ impl BlockContext {
                 ],
             ],
             self.block_hash_assignments(randomness),
+            self.beacon_root_assignments(randomness),
         ]
         .concat()
     }
 
+    fn beacon_root_assignments<F: Field>(&self, randomness: Value<F>) -> Vec<[Value<F>; 3]> {
+        use eth_types::ToWord;
+
+        // the scroll block table layout is fixed by the public input, the beacon roots fork can
+        // not be scheduled there
+        if cfg!(feature = "scroll") {
+            return vec![];
+        }
+        self.parent_beacon_block_root
+            .map(|root| {
+                [
+                    Value::known(F::from(BlockContextFieldTag::ParentBeaconBlockRoot as u64)),
+                    Value::known(self.number.to_scalar().unwrap()),
+                    randomness
+                        .map(|randomness| rlc::value(&root.to_word().to_le_bytes(), randomness)),
+                ]
+            })
+            .into_iter()
+            .collect()
+    }
+
     fn block_hash_assignments<F: Field>(&self, randomness: Value<F>) -> Vec<[Value<F>; 3]> {
         use eth_types::ToWord;
 
@@ -487,6 +538,8 @@ impl From<&circuit_input_builder::Blocks> for BlockContexts {
                             chain_id: block.chain_id,
                             parent_hash: block.parent_hash,
                             state_root: block.state_root,
+                            hash: block.hash,
+                            parent_beacon_block_root: block.header.parent_beacon_block_root,
                         },
                     )
                 })
@@ -592,6 +645,11 @@ pub fn block_convert(
     let block = Block {
         context: BlockContexts::from(block),
         rws,
+        leading_steps: leading_steps_convert(
+            block.txs().first().map(|tx| tx.block_num),
+            last_block_num,
+            &block.block_steps.system_call_steps,
+        ),
         txs: block
             .txs()
             .iter()
@@ -602,7 +660,13 @@ pub fn block_convert(
                 } else {
                     last_block_num + 1
                 };
-                tx_convert(tx, idx + 1, chain_id, next_block_num)
+                tx_convert(
+                    tx,
+                    idx + 1,
+                    chain_id,
+                    next_block_num,
+                    &block.block_steps.system_call_steps,
+                )
             })
             .collect(),
         sigs: block.txs().iter().map(|tx| tx.signature).collect(),
@@ -0,0 +1,114 @@
+//! Versioned binary dump of a witness [`Block`], used to reproduce circuit
+//! failures away from the traces and the environment that produced them.
+//!
+//! A dump is the `MAGIC` bytes followed by the little endian format version,
+//! and then the zstd compressed bincode encoding of the [`Block`].
+
+use super::Block;
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+/// Version of the dump format, to bump whenever the serialized layout of
+/// [`Block`] or of any of its fields changes.
+pub const WITNESS_DUMP_VERSION: u32 = 10;
+
+const MAGIC: [u8; 4] = *b"zkwb";
+
+const ZSTD_LEVEL: i32 = 3;
+
+fn invalid_data(err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+impl Block {
+    /// Write the dump of the block to `writer`.
+    pub fn write_dump<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&WITNESS_DUMP_VERSION.to_le_bytes())?;
+        let mut encoder = zstd::Encoder::new(writer, ZSTD_LEVEL)?;
+        bincode::serialize_into(&mut encoder, self).map_err(invalid_data)?;
+        encoder.finish()?.flush()
+    }
+
+    /// Read a block from a dump written by [`Block::write_dump`].
+    pub fn read_dump<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut header = [0u8; 8];
+        reader.read_exact(&mut header)?;
+        if header[..4] != MAGIC {
+            return Err(invalid_data("not a witness block dump"));
+        }
+        let version = u32::from_le_bytes(header[4..].try_into().unwrap());
+        if version != WITNESS_DUMP_VERSION {
+            return Err(invalid_data(format!(
+                "witness block dump version {version}, expected {WITNESS_DUMP_VERSION}"
+            )));
+        }
+        let decoder = zstd::Decoder::new(reader)?;
+        let mut block: Self = bincode::deserialize_from(decoder).map_err(invalid_data)?;
+        block.mpt_updates.restore_proof_types();
+        Ok(block)
+    }
+
+    /// Write the dump of the block to the file at `path`.
+    pub fn write_dump_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.write_dump(BufWriter::new(File::create(path)?))
+    }
+
+    /// Read a block from the dump file at `path`.
+    pub fn read_dump_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::read_dump(BufReader::new(File::open(path)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::witness::block_convert;
+    use bus_mapping::mock::BlockData;
+    use eth_types::{bytecode, geth_types::GethData};
+    use mock::test_ctx::{helpers::*, TestContext};
+
+    #[test]
+    fn witness_block_dump_roundtrip() {
+        let code = bytecode! {
+            PUSH1(0x20)
+            PUSH1(0x00)
+            SSTORE
+            STOP
+        };
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+        let block = block_convert(&builder.block, &builder.code_db).unwrap();
+
+        let mut dump = Vec::new();
+        block.write_dump(&mut dump).unwrap();
+        let loaded = Block::read_dump(dump.as_slice()).unwrap();
+
+        assert_eq!(loaded.txs, block.txs);
+        assert_eq!(
+            loaded.circuits_params.max_rws,
+            block.circuits_params.max_rws
+        );
+        assert_eq!(loaded.rws.0.len(), block.rws.0.len());
+        assert_eq!(
+            loaded.mpt_updates.proof_types,
+            block.mpt_updates.proof_types
+        );
+
+        dump[4] += 1;
+        assert!(Block::read_dump(dump.as_slice()).is_err());
+    }
+}
@@ -38,12 +38,10 @@ pub fn keccak_inputs(block: &Block) -> Result<Vec<Vec<u8>>, Error> {
         &block.context,
         &block.txs,
     ));
-    /*
-    // Bytecode Circuit don't use keccak code hash
-    for bytecode in block.code_db.0.values() {
-        keccak_inputs.push(bytecode.clone());
+    // Bytecode Circuit, which proves the keccak code hash of every bytecode
+    for bytecode in block.bytecodes.values() {
+        keccak_inputs.push(bytecode.bytes.clone());
     }
-    */
     log::debug!(
         "keccak total len after bytecodes: {}",
         keccak_inputs.iter().map(|i| i.len()).sum::<usize>()
@@ -217,3 +215,44 @@ pub fn keccak_inputs_tx_circuit(txs: &[Transaction]) -> Result<Vec<Vec<u8>>, Err
 
     Ok(inputs)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::witness::block_convert;
+    use bus_mapping::mock::BlockData;
+    use eth_types::{bytecode, geth_types::GethData};
+    use mock::test_ctx::{helpers::*, TestContext};
+
+    #[test]
+    fn keccak_inputs_include_bytecodes() {
+        let code = bytecode! {
+            PUSH1(0x20)
+            PUSH1(0x00)
+            SSTORE
+            STOP
+        };
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code.clone()),
+            tx_from_1_to_0,
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+        let block = block_convert(&builder.block, &builder.code_db).unwrap();
+
+        let inputs = keccak_inputs(&block).unwrap();
+        assert!(block
+            .bytecodes
+            .values()
+            .any(|bytecode| bytecode.bytes == code.code()));
+        for bytecode in block.bytecodes.values() {
+            assert!(inputs.contains(&bytecode.bytes));
+        }
+    }
+}
@@ -13,9 +13,11 @@ use serde::{Deserialize, Serialize};
 pub use state::ZktrieState;
 use std::collections::BTreeMap;
 
+mod estimate;
 #[cfg(test)]
 mod test;
 mod witness;
+pub use estimate::{TriePathModel, INSERTION_EXTRA_DEPTH};
 use witness::WitnessGenerator;
 
 /// Used to store withdraw proof
@@ -30,7 +32,7 @@ pub struct WithdrawProof {
 }
 
 /// An MPT update whose validity is proved by the MptCircuit
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MptUpdate {
     key: Key,
     old_value: Word,
@@ -39,6 +41,7 @@ pub struct MptUpdate {
     new_root: Word,
     // for debugging
     #[cfg(debug_assertions)]
+    #[serde(skip)]
     original_rws: Vec<Rw>,
 }
 
@@ -93,7 +96,7 @@ impl Default for MptUpdate {
 }
 
 /// All the MPT updates in the MptCircuit, accessible by their key
-#[derive(Default, Clone, Debug)]
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
 pub struct MptUpdates {
     old_root: Word,
     new_root: Word,
@@ -103,6 +106,8 @@ pub struct MptUpdates {
     pub withdraw_proof: WithdrawProof,
     /// The detailed mpt witness
     pub smt_traces: Vec<SMTTrace>,
+    // Recomputed from `updates` when loaded from a witness dump.
+    #[serde(skip)]
     pub(crate) proof_types: Vec<MPTProofType>,
 }
 
@@ -268,6 +273,15 @@ impl MptUpdates {
         wit_gen
     }
 
+    /// Rebuild `proof_types`, which are not serialized, after loading the
+    /// updates from a witness dump.
+    pub(crate) fn restore_proof_types(&mut self) {
+        if self.smt_traces.is_empty() {
+            return;
+        }
+        self.proof_types = self.updates.values().map(MptUpdate::proof_type).collect();
+    }
+
     pub(crate) fn mock_from(rows: &[Rw]) -> Self {
         Self::from_rws_with_mock_state_roots(rows, 0xcafeu64.into(), 0xdeadbeefu64.into())
     }
@@ -423,7 +437,7 @@ impl MptUpdate {
     }
 }
 
-#[derive(Eq, PartialEq, Hash, Clone, Debug, Copy, PartialOrd, Ord)]
+#[derive(Eq, PartialEq, Hash, Clone, Debug, Copy, PartialOrd, Ord, Serialize, Deserialize)]
 enum Key {
     Account {
         address: Address,
@@ -0,0 +1,115 @@
+//! A poseidon-free model of the hashes the MptCircuit proves, used to bound the poseidon rows
+//! of a chunk without rebuilding the zktrie.
+use super::{Key, MptUpdates};
+use eth_types::{l2_types::StorageTrace, Address, ToWord, Word};
+use halo2_proofs::halo2curves::bn256::Fr;
+use hash_circuit::hash::Hashable;
+use mpt_zktrie::state::builder::{AccountProof, StorageProof};
+use std::collections::HashMap;
+
+/// Extra branch nodes assumed when a leaf is inserted into a trie.
+///
+/// An insertion either fills the empty node ending the existing path, adding no depth, or meets
+/// the leaf of another key and pushes it down to the first bit where the two keys differ: one
+/// branch node plus one per further shared bit. Keys are poseidon hashes, so the number of
+/// further shared bits is geometric with `P(K >= j) = 2^-j`, and an insertion adds `1 + K`
+/// nodes, 2 on average. By a Chernoff bound, `n` insertions add more than `4n` nodes with
+/// probability at most `(16/27)^n`, i.e. below `1e-4` from 18 insertions on. A single insertion
+/// can still exceed it, which the slack of the leaf hashes (4 counted per account leaf, where
+/// the circuit proves 1) covers in practice; `trie_path_model_calibration` checks the estimate
+/// against the hashes counted from real smt traces.
+pub const INSERTION_EXTRA_DEPTH: usize = 4;
+
+/// Depths of the trie paths seen in the storage proofs of a sequence of traces.
+///
+/// For each MPT update the circuit hashes the old and new paths, both account leaves (4 hashes
+/// each) and the account key, plus the storage paths, leaves and key for storage updates.
+/// [`TriePathModel::estimate_hashes`] counts these from the recorded depths without computing
+/// any poseidon hash. The estimate mirrors the count before deduplication, so it is an upper
+/// bound of the rows the poseidon circuit uses as long as the depths are exact and insertions
+/// do not split deeper than [`INSERTION_EXTRA_DEPTH`].
+#[derive(Debug, Default, Clone)]
+pub struct TriePathModel {
+    pub(super) accounts: HashMap<Address, usize>,
+    pub(super) storages: HashMap<(Address, Word), usize>,
+}
+
+impl TriePathModel {
+    /// Record the path depths from the proofs of `storage_trace`. Proofs of later traces
+    /// override the ones already recorded.
+    pub fn update_from_trace(&mut self, storage_trace: &StorageTrace) {
+        for (address, proof) in storage_trace.proofs.iter().flatten() {
+            if let Ok(proof) = AccountProof::try_from(proof.as_slice()) {
+                self.accounts.insert(*address, proof.path.len());
+            }
+        }
+        for (address, proofs) in &storage_trace.storage_proofs {
+            for (key, proof) in proofs {
+                if let Ok(proof) = StorageProof::try_from(proof.as_slice()) {
+                    self.storages
+                        .insert((*address, key.to_word()), proof.path.len());
+                }
+            }
+        }
+    }
+
+    /// Forget all the recorded depths.
+    pub fn clear(&mut self) {
+        self.accounts.clear();
+        self.storages.clear();
+    }
+
+    /// Estimated number of poseidon hashes for `updates`, before deduplication.
+    pub fn estimate_hashes(&self, updates: &MptUpdates) -> usize {
+        // keys not covered by any proof fall back to the deepest path seen so far
+        let default_account_depth = self.accounts.values().copied().max().unwrap_or_default();
+        let default_storage_depth = self.storages.values().copied().max().unwrap_or_default();
+
+        updates
+            .updates
+            .values()
+            .map(|update| {
+                let inserted = update.old_value.is_zero() && !update.new_value.is_zero();
+                let extra = |is_target| {
+                    if inserted && is_target {
+                        INSERTION_EXTRA_DEPTH
+                    } else {
+                        0
+                    }
+                };
+                let (address, storage_key) = match update.key {
+                    Key::Account { address, .. } => (address, None),
+                    Key::AccountStorage {
+                        address,
+                        storage_key,
+                        ..
+                    } => (address, Some(storage_key)),
+                };
+                let account_depth = self
+                    .accounts
+                    .get(&address)
+                    .copied()
+                    .unwrap_or(default_account_depth)
+                    + extra(storage_key.is_none());
+                // root, old and new account paths, both account leaves and the account key
+                let mut hashes = 1 + 2 * account_depth + 2 * 4 + 1;
+                if let Some(storage_key) = storage_key {
+                    let storage_depth = self
+                        .storages
+                        .get(&(address, storage_key))
+                        .copied()
+                        .unwrap_or(default_storage_depth)
+                        + extra(true);
+                    // old and new storage paths, both storage leaves and the storage key
+                    hashes += 2 * storage_depth + 2 + 1;
+                }
+                hashes
+            })
+            .sum()
+    }
+
+    /// Estimated number of poseidon rows for `updates`.
+    pub fn estimate_poseidon_rows(&self, updates: &MptUpdates) -> usize {
+        self.estimate_hashes(updates) * Fr::hash_block_size()
+    }
+}
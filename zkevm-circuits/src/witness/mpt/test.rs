@@ -1,4 +1,7 @@
 use super::*;
+use crate::poseidon_circuit::count_mpt_hashes;
+use bus_mapping::circuit_input_builder::CircuitInputBuilder;
+use eth_types::{l2_types::StorageTrace, Bytes};
 use mpt_zktrie::state::builder::init_hash_scheme;
 
 #[test]
@@ -986,3 +989,120 @@ fn empty_account_storage_write() {
         serde_json::to_string_pretty(&updates.smt_traces.last().unwrap()).unwrap()
     );
 }
+
+#[test]
+fn trie_path_model_estimate() {
+    let address = Address::repeat_byte(1);
+    let mut updates = MptUpdates::default();
+    updates.insert(MptUpdate {
+        key: Key::Account {
+            address,
+            field_tag: AccountFieldTag::Nonce,
+        },
+        old_value: Word::one(),
+        new_value: Word::from(2),
+        ..Default::default()
+    });
+    updates.insert(MptUpdate {
+        key: Key::AccountStorage {
+            tx_id: 1,
+            address,
+            storage_key: Word::from(3),
+            exists: true,
+        },
+        new_value: Word::one(),
+        ..Default::default()
+    });
+
+    let mut model = TriePathModel::default();
+    model.accounts.insert(address, 10);
+    model.storages.insert((address, Word::from(3)), 2);
+
+    // account update: root, 2 paths of 10 nodes, 2 leaves and the account key
+    let account_hashes = 1 + 2 * 10 + 2 * 4 + 1;
+    // storage insertion: the account part, plus 2 storage paths extended by the insertion,
+    // 2 leaves and the storage key
+    let storage_hashes = account_hashes + 2 * (2 + INSERTION_EXTRA_DEPTH) + 2 + 1;
+    assert_eq!(
+        model.estimate_hashes(&updates),
+        account_hashes + storage_hashes
+    );
+}
+
+/// Compare the estimate of `model` for `updates` with the hashes the MptCircuit proves for them.
+/// Returns `(estimated, actual)`, both before deduplication.
+///
+/// Light mode `updates` carry no smt traces, so they are replayed on a partial zktrie built from
+/// the proofs of `storage_trace`, the trace they come from.
+fn calibrate(
+    model: &TriePathModel,
+    updates: &MptUpdates,
+    storage_trace: &StorageTrace,
+) -> (usize, usize) {
+    let (actual, _) = if updates.smt_traces.is_empty() {
+        let state = ZktrieState::from_trace_with_additional(
+            storage_trace.root_before,
+            CircuitInputBuilder::collect_account_proofs(storage_trace),
+            CircuitInputBuilder::collect_storage_proofs(storage_trace),
+            storage_trace.deletion_proofs.iter().map(Bytes::as_ref),
+        )
+        .unwrap();
+        let mut replayed = updates.clone();
+        replayed.fill_state_roots_from_generator(WitnessGenerator::from(&state));
+        count_mpt_hashes(&replayed.smt_traces)
+    } else {
+        count_mpt_hashes(&updates.smt_traces)
+    };
+    (model.estimate_hashes(updates), actual)
+}
+
+#[test]
+fn trie_path_model_calibration() {
+    init_hash_scheme();
+
+    // a trie of 256 accounts
+    let mut seed = MptUpdates::default();
+    for i in 0..256 {
+        seed.insert(nonce_update(Address::from_low_u64_be(i + 1)));
+    }
+    let wit_gen =
+        seed.fill_state_roots_from_generator(WitnessGenerator::from(&ZktrieState::default()));
+
+    // insert 64 accounts, with the proofs a light mode trace would carry for them
+    let addresses = (0..64)
+        .map(|i| Address::from_low_u64_be(0x1000 + i))
+        .collect_vec();
+    let storage_trace = StorageTrace {
+        root_before: wit_gen.root(),
+        proofs: Some(
+            addresses
+                .iter()
+                .map(|&address| {
+                    let proof = wit_gen.account_proof(address);
+                    (address, proof.into_iter().map(Bytes::from).collect())
+                })
+                .collect(),
+        ),
+        ..Default::default()
+    };
+    let mut updates = MptUpdates::default();
+    for &address in &addresses {
+        updates.insert(nonce_update(address));
+    }
+
+    let mut model = TriePathModel::default();
+    model.update_from_trace(&storage_trace);
+    let (estimated, actual) = calibrate(&model, &updates, &storage_trace);
+    assert!(
+        estimated >= actual,
+        "estimated {estimated} mpt hashes, counted {actual}"
+    );
+
+    // the light mode replay counts the same hashes as the smt traces of a full build
+    let mut full = updates.clone();
+    full.fill_state_roots_from_generator(wit_gen);
+    assert_eq!(
+        calibrate(&model, &full, &storage_trace),
+        (estimated, actual)
+    );
+}
@@ -16,9 +16,10 @@ use crate::{
     },
     table::RwTableTag,
 };
+use serde::{Deserialize, Serialize};
 
 /// Step executed in a transaction
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExecStep {
     /// The index in the Transaction calls
     pub call_index: usize,
@@ -235,10 +236,12 @@ impl From<&circuit_input_builder::ExecStep> for ExecutionState {
                 PrecompileCalls::Bn128Mul => ExecutionState::PrecompileBn256ScalarMul,
                 PrecompileCalls::Bn128Pairing => ExecutionState::PrecompileBn256Pairing,
                 PrecompileCalls::Blake2F => ExecutionState::PrecompileBlake2f,
+                PrecompileCalls::P256Verify => ExecutionState::PrecompileP256Verify,
             },
             circuit_input_builder::ExecState::BeginTx => ExecutionState::BeginTx,
             circuit_input_builder::ExecState::EndTx => ExecutionState::EndTx,
             circuit_input_builder::ExecState::EndBlock => ExecutionState::EndBlock,
+            circuit_input_builder::ExecState::SystemCall => ExecutionState::SystemCall,
             circuit_input_builder::ExecState::Padding => ExecutionState::Padding,
         }
     }
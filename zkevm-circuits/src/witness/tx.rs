@@ -36,12 +36,13 @@ use halo2_proofs::{
 use mock::MockTransaction;
 use num::Integer;
 use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
 use std::{cmp::Ordering, collections::BTreeMap};
 
 use super::{step::step_convert, Call, ExecStep};
 
 /// Transaction in a witness block
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Transaction {
     /// The block number in which this tx is included in
     pub block_number: u64,
@@ -1211,6 +1212,7 @@ pub(super) fn tx_convert(
     id: usize,
     chain_id: u64,
     next_block_num: u64,
+    system_call_steps: &BTreeMap<u64, circuit_input_builder::ExecStep>,
 ) -> Transaction {
     if tx.chain_id != 0 {
         debug_assert_eq!(
@@ -1290,29 +1292,79 @@ pub(super) fn tx_convert(
                 is_static: call.is_static,
             })
             .collect(),
-        steps: tx
-            .steps()
-            .iter()
-            .map(|step| step_convert(step, tx.block_num))
-            .chain({
-                // TODO: it is a bit counter-intuitive to treat EndInnerBlock step, even multiple
-                // EndInnerBlock steps to belong to the last prev tx.
-                // We can change design later to make it easier to understand.
-                let last_step = tx.steps().last().unwrap();
-                let rw_counter = last_step.rwc.0 + last_step.bus_mapping_instance.len();
-                debug_assert!(next_block_num >= tx.block_num);
-                (tx.block_num..next_block_num)
-                    .map(|block_num| ExecStep {
-                        rw_counter,
-                        execution_state: ExecutionState::EndInnerBlock,
-                        block_num,
-                        call_index: last_step.call_index,
-                        ..Default::default()
-                    })
-                    .collect::<Vec<ExecStep>>()
-            })
-            .collect(),
+        steps: {
+            let system_call = |block_num: u64| {
+                system_call_steps
+                    .get(&block_num)
+                    .map(|step| step_convert(step, block_num))
+            };
+            // The SystemCall step of a block comes right before its first tx. The ones up to the
+            // first tx of the chunk are its leading steps, see `leading_steps_convert`, the others
+            // follow the EndInnerBlock step of their previous block.
+            let mut steps: Vec<ExecStep> = tx
+                .steps()
+                .iter()
+                .map(|step| step_convert(step, tx.block_num))
+                .collect();
+            // TODO: it is a bit counter-intuitive to treat EndInnerBlock step, even multiple
+            // EndInnerBlock steps to belong to the last prev tx.
+            // We can change design later to make it easier to understand.
+            let last_step = tx.steps().last().unwrap();
+            let mut rw_counter = last_step.rwc.0 + last_step.bus_mapping_instance.len();
+            debug_assert!(next_block_num >= tx.block_num);
+            for block_num in tx.block_num..next_block_num {
+                steps.push(ExecStep {
+                    rw_counter,
+                    execution_state: ExecutionState::EndInnerBlock,
+                    block_num,
+                    call_index: last_step.call_index,
+                    ..Default::default()
+                });
+                if let Some(step) = system_call(block_num + 1) {
+                    rw_counter = step.rw_counter + step.rw_indices.len();
+                    steps.push(step);
+                }
+            }
+            steps
+        },
+    }
+}
+
+/// The steps before the first tx of the chunk: the SystemCall step of each of its leading empty
+/// blocks, followed by the EndInnerBlock step of the block, and the SystemCall step of the block
+/// of the first tx. A chunk without txs has the steps of all its blocks. The blocks before the
+/// first SystemCall step have no steps, as for a chunk starting with a BeginTx step.
+pub(super) fn leading_steps_convert(
+    first_tx_block_num: Option<u64>,
+    last_block_num: u64,
+    system_call_steps: &BTreeMap<u64, circuit_input_builder::ExecStep>,
+) -> Vec<ExecStep> {
+    let Some(&first_block_num) = system_call_steps.keys().next() else {
+        return vec![];
+    };
+    let mut steps = Vec::new();
+    let mut rw_counter = 1;
+    for block_num in first_block_num..first_tx_block_num.unwrap_or(last_block_num + 1) {
+        if let Some(step) = system_call_steps.get(&block_num) {
+            let step = step_convert(step, block_num);
+            rw_counter = step.rw_counter + step.rw_indices.len();
+            steps.push(step);
+        }
+        steps.push(ExecStep {
+            rw_counter,
+            execution_state: ExecutionState::EndInnerBlock,
+            block_num,
+            ..Default::default()
+        });
+    }
+    if let Some(block_num) = first_tx_block_num {
+        steps.extend(
+            system_call_steps
+                .get(&block_num)
+                .map(|step| step_convert(step, block_num)),
+        );
     }
+    steps
 }
 
 #[cfg(test)]